@@ -3,7 +3,10 @@ use tauri::{Manager, State};
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::models::{AppDetail, AppSummary, AvailableUpdateInfo, UpdateSourceInfo};
+use crate::models::{
+    license_covers, AppDetail, AppSummary, AvailableUpdateInfo, DetectionConflict, DiskUsageEntry,
+    RelaunchPolicy, UnusedApp, UpdateSourceInfo,
+};
 use crate::scheduler;
 use crate::utils::AppError;
 
@@ -42,7 +45,7 @@ pub async fn get_app_detail(
                 "SELECT id, bundle_id, display_name, app_path, installed_version, bundle_version,
                         icon_cache_path, architectures, install_source, obtained_from,
                         homebrew_cask_token, is_ignored, first_seen_at, last_seen_at, mas_app_id,
-                        homebrew_formula_name, description
+                        homebrew_formula_name, description, helper_apps, relaunch_policy
                  FROM apps WHERE bundle_id = ?1",
                 [&bundle_id],
                 |row| {
@@ -67,6 +70,12 @@ pub async fn get_app_detail(
                         description: row.get(16)?,
                         update_sources: Vec::new(),
                         available_update: None,
+                        helper_apps: row
+                            .get::<_, Option<String>>(17)?
+                            .and_then(|j| serde_json::from_str(&j).ok())
+                            .unwrap_or_default(),
+                        relaunch_policy: RelaunchPolicy::from_str(&row.get::<_, String>(18)?),
+                        location_issue: None,
                     })
                 },
             )
@@ -94,15 +103,21 @@ pub async fn get_app_detail(
 
         let available_update: Option<AvailableUpdateInfo> = conn
             .query_row(
-                "SELECT available_version, source_type, release_notes_url, download_url,
-                        release_notes, is_paid_upgrade, detected_at, notes
-                 FROM available_updates
-                 WHERE app_id = ?1 AND dismissed_at IS NULL
-                 ORDER BY detected_at DESC LIMIT 1",
+                "SELECT au.available_version, au.source_type, au.release_notes_url, au.download_url,
+                        au.release_notes, au.is_paid_upgrade, au.detected_at, au.notes,
+                        au.download_size, a.licensed_version
+                 FROM available_updates au
+                 JOIN apps a ON a.id = au.app_id
+                 WHERE au.app_id = ?1 AND au.dismissed_at IS NULL
+                 ORDER BY au.detected_at DESC LIMIT 1",
                 [app.id],
                 |row| {
+                    let available_version: String = row.get(0)?;
+                    let licensed_version: Option<String> = row.get(9)?;
                     Ok(AvailableUpdateInfo {
-                        available_version: row.get(0)?,
+                        license_confirmed: licensed_version
+                            .is_some_and(|licensed| license_covers(&licensed, &available_version)),
+                        available_version,
                         source_type: row.get(1)?,
                         release_notes_url: row.get(2)?,
                         download_url: row.get(3)?,
@@ -110,14 +125,19 @@ pub async fn get_app_detail(
                         is_paid_upgrade: row.get::<_, i32>(5)? != 0,
                         detected_at: row.get(6)?,
                         notes: row.get(7)?,
+                        download_size: row.get::<_, Option<i64>>(8)?.map(|n| n.max(0) as u64),
                     })
                 },
             )
             .ok();
 
+        let location_issue =
+            crate::platform::translocation::location_issue(&app.bundle_id, &app.app_path);
+
         Ok(AppDetail {
             update_sources,
             available_update,
+            location_issue,
             ..app
         })
     })
@@ -134,6 +154,34 @@ pub async fn trigger_full_scan(
     scheduler::run_full_scan(&app_handle, &db).await
 }
 
+/// Re-detect one app instead of running a full scan, e.g. to fix a stale version
+/// or install source. Returns the refreshed detail.
+#[tauri::command]
+pub async fn rescan_app(
+    bundle_id: String,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<AppDetail, AppError> {
+    let db = db.inner().clone();
+    scheduler::rescan_app(&app_handle, &db, &bundle_id).await
+}
+
+/// Mark the pending paid upgrade of an app as covered by a license the user owns,
+/// unlocking it for updating. The license covers later releases of the same major version.
+#[tauri::command]
+pub async fn confirm_upgrade_license(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let db = db.lock().await;
+    let detail = db.get_app_detail(&bundle_id)?;
+    let update = detail
+        .available_update
+        .filter(|u| u.is_paid_upgrade)
+        .ok_or_else(|| AppError::Custom(format!("{} has no pending paid upgrade", detail.display_name)))?;
+    db.set_licensed_version(&bundle_id, &update.available_version)
+}
+
 #[tauri::command]
 pub async fn set_app_ignored(
     bundle_id: String,
@@ -143,3 +191,129 @@ pub async fn set_app_ignored(
     let db = db.lock().await;
     db.set_app_ignored(&bundle_id, ignored)
 }
+
+/// Never match an app to a Homebrew cask again after the user reports the match
+/// as wrong, and drop the updates that came from it.
+#[tauri::command]
+pub async fn report_bad_match(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let db = db.lock().await;
+    let mut settings = scheduler::load_settings_from_db(&db);
+    if !settings.cask_blocklist.iter().any(|b| b.eq_ignore_ascii_case(&bundle_id)) {
+        settings.cask_blocklist.push(bundle_id.clone());
+        db.save_settings(&settings)?;
+        crate::utils::apply_cask_blocklist(&settings);
+    }
+    db.clear_cask_match(&bundle_id)?;
+    log::info!("Blocklisted {} from cask matching", bundle_id);
+    Ok(())
+}
+
+/// Apps whose detectors disagreed about path, version or source in the last scan.
+#[tauri::command]
+pub async fn get_detection_conflicts(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<DetectionConflict>, AppError> {
+    let db = db.lock().await;
+    db.get_detection_conflicts()
+}
+
+/// Pick which detector's path, version and source to trust for an app, or None
+/// to go back to merging. Applied on the next scan.
+#[tauri::command]
+pub async fn set_preferred_detector(
+    bundle_id: String,
+    detector: Option<String>,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let db = db.lock().await;
+    if let Some(ref detector) = detector {
+        let known = db
+            .get_detection_conflicts()?
+            .iter()
+            .filter(|c| c.bundle_id == bundle_id)
+            .any(|c| c.candidates.iter().any(|candidate| &candidate.detector == detector));
+        if !known {
+            return Err(AppError::Custom(format!("{} didn't detect this app in the last scan", detector)));
+        }
+    }
+    db.set_preferred_detector(&bundle_id, detector.as_deref())
+}
+
+/// Ignore or un-ignore several apps in one transaction. Returns how many changed.
+#[tauri::command]
+pub async fn set_apps_ignored(
+    bundle_ids: Vec<String>,
+    ignored: bool,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<usize, AppError> {
+    let db = db.lock().await;
+    db.set_apps_ignored(&bundle_ids, ignored)
+}
+
+/// Put an app that runs translocated, off a disk image or from ~/Downloads into
+/// /Applications, reopening it if it was running. Returns the new path.
+#[tauri::command]
+pub async fn move_app_to_applications(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<String, AppError> {
+    let detail = db.lock().await.get_app_detail(&bundle_id)?;
+    let was_running = crate::utils::app_lifecycle::is_app_running(&bundle_id);
+    if was_running {
+        crate::utils::app_lifecycle::quit_app_gracefully(&detail.display_name, &bundle_id);
+    }
+
+    let new_path = if std::path::Path::new(&detail.app_path).parent() == Some(std::path::Path::new("/Applications")) {
+        // Already in place but still quarantined; clearing the flag ends translocation
+        let _ = std::process::Command::new("xattr")
+            .current_dir("/tmp")
+            .args(["-dr", "com.apple.quarantine", &detail.app_path])
+            .output();
+        detail.app_path.clone()
+    } else {
+        let dest = crate::platform::translocation::move_to_applications(&detail.app_path)?;
+        let dest = dest.to_string_lossy().to_string();
+        db.lock().await.update_app_path(detail.id, &dest)?;
+        dest
+    };
+
+    if was_running {
+        crate::utils::app_lifecycle::relaunch_app(&new_path);
+    }
+    Ok(new_path)
+}
+
+/// Choose whether updaters may quit and reopen this app while it's running.
+#[tauri::command]
+pub async fn set_app_relaunch_policy(
+    bundle_id: String,
+    policy: RelaunchPolicy,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let db = db.lock().await;
+    db.set_app_relaunch_policy(&bundle_id, policy)
+}
+
+/// Apps sorted by cached on-disk footprint (bundle + associated data), largest first.
+/// Sizes are refreshed in the background after each full scan.
+#[tauri::command]
+pub async fn get_disk_usage(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<DiskUsageEntry>, AppError> {
+    let db = db.lock().await;
+    db.get_disk_usage()
+}
+
+/// Apps not opened in the last `days` days, least recently used first, with their
+/// cached sizes — candidates for uninstalling. Last-used dates are refreshed on each scan.
+#[tauri::command]
+pub async fn get_unused_apps(
+    days: u32,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<UnusedApp>, AppError> {
+    let db = db.lock().await;
+    db.get_unused_apps(days)
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::{AppArchive, ArchivedFile};
+use crate::utils::AppError;
+
+/// Resolve the archive folder from settings, defaulting to ~/Documents/macPlus Archives.
+pub(crate) fn archive_dir(setting: Option<&str>) -> Option<PathBuf> {
+    match setting.filter(|s| !s.is_empty()) {
+        Some(dir) => match dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|h| h.join(rest)),
+            None => Some(PathBuf::from(dir)),
+        },
+        None => dirs::document_dir().map(|d| d.join("macPlus Archives")),
+    }
+}
+
+fn ditto(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("ditto")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run ditto: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "ditto failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Compress an app bundle and the given support files into a dated zip in `dir`.
+///
+/// Files are staged (ditto clones on APFS, so this is cheap) into a folder laid out as
+/// `<App>.app` plus `Support/<n>/<name>`, then zipped with `ditto -c -k --keepParent`
+/// so extended attributes and code signatures survive. Returns the zip path and the
+/// mapping of support files to their entries.
+pub(crate) fn create_archive(
+    display_name: &str,
+    app_path: &str,
+    support_paths: &[String],
+    dir: &Path,
+) -> Result<(PathBuf, Vec<ArchivedFile>), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+
+    let safe_name = display_name.replace(['/', ':'], "-");
+    let stamp = chrono::Local::now().format("%Y-%m-%d %H.%M.%S");
+    let zip_path = dir.join(format!("{} {}.zip", safe_name, stamp));
+
+    let staging = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let root = staging.path().join(&safe_name);
+    let app_name = Path::new(app_path)
+        .file_name()
+        .ok_or_else(|| format!("Invalid app path: {}", app_path))?;
+    ditto(&[app_path, &root.join(app_name).to_string_lossy()])?;
+
+    let mut archived = Vec::new();
+    for (i, original) in support_paths.iter().enumerate() {
+        let name = match Path::new(original).file_name() {
+            Some(n) => n.to_string_lossy().to_string(),
+            None => continue,
+        };
+        let entry = format!("Support/{}/{}", i, name);
+        match ditto(&[original, &root.join(&entry).to_string_lossy()]) {
+            Ok(()) => archived.push(ArchivedFile {
+                original_path: original.clone(),
+                entry,
+            }),
+            // Support files are best-effort; the app bundle is what matters
+            Err(e) => log::warn!("Skipping {} in archive: {}", original, e),
+        }
+    }
+
+    ditto(&[
+        "-c",
+        "-k",
+        "--sequesterRsrc",
+        "--keepParent",
+        &root.to_string_lossy(),
+        &zip_path.to_string_lossy(),
+    ])?;
+
+    Ok((zip_path, archived))
+}
+
+/// Move `src` to `dest`, falling back to a copy when they're on different volumes.
+fn move_into_place(src: &Path, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if std::fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+    ditto(&[&src.to_string_lossy(), &dest.to_string_lossy()])
+}
+
+/// Extract an archive and put the app and its support files back in their original
+/// locations. Paths that already exist are left untouched. Returns the restored paths.
+fn extract_archive(archive: &AppArchive) -> Result<Vec<String>, String> {
+    let app_dest = Path::new(&archive.app_path);
+    if app_dest.exists() {
+        return Err(format!("{} already exists", archive.app_path));
+    }
+    if !Path::new(&archive.archive_path).exists() {
+        return Err(format!("Archive not found: {}", archive.archive_path));
+    }
+
+    let staging = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    ditto(&["-x", "-k", &archive.archive_path, &staging.path().to_string_lossy()])?;
+
+    // The zip holds a single top-level folder (created with --keepParent)
+    let root = std::fs::read_dir(staging.path())
+        .map_err(|e| format!("Failed to read extracted archive: {}", e))?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.is_dir())
+        .ok_or("Archive is empty")?;
+
+    let app_name = app_dest.file_name().ok_or("Invalid app path")?;
+    let app_src = root.join(app_name);
+    if let Err(e) = move_into_place(&app_src, app_dest) {
+        // /Applications may need admin rights
+        let cmd = format!(
+            "/usr/bin/ditto '{}' '{}'",
+            app_src.to_string_lossy().replace('\'', "'\\''"),
+            archive.app_path.replace('\'', "'\\''")
+        );
+        crate::utils::sudo_session::run_elevated_shell(&cmd)
+            .map_err(|elevated| format!("{} (elevated: {})", e, elevated))?;
+    }
+
+    let mut restored = vec![archive.app_path.clone()];
+    for file in &archive.support_files {
+        let dest = Path::new(&file.original_path);
+        if dest.exists() {
+            continue;
+        }
+        match move_into_place(&root.join(&file.entry), dest) {
+            Ok(()) => restored.push(file.original_path.clone()),
+            Err(e) => log::warn!("Failed to restore {}: {}", file.original_path, e),
+        }
+    }
+
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn list_app_archives(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<AppArchive>, AppError> {
+    let db_guard = db.lock().await;
+    db_guard.get_app_archives()
+}
+
+/// Restore an app (and any archived support files) from an uninstall archive.
+#[tauri::command]
+pub async fn restore_archived_app(
+    app_handle: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    archive_id: i64,
+) -> Result<Vec<String>, AppError> {
+    let archive = {
+        let db_guard = db.lock().await;
+        db_guard.get_app_archive(archive_id)?
+    };
+
+    let to_extract = archive.clone();
+    let restored = tokio::task::spawn_blocking(move || extract_archive(&to_extract))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+        .map_err(AppError::CommandFailed)?;
+
+    {
+        let db_guard = db.lock().await;
+        db_guard.mark_archive_restored(archive_id)?;
+    }
+
+    let _ = app_handle.emit(
+        "app-restored",
+        serde_json::json!({
+            "bundleId": archive.bundle_id,
+            "displayName": archive.display_name,
+            "restoredPaths": restored,
+        }),
+    );
+
+    Ok(restored)
+}
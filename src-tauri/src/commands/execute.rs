@@ -1,17 +1,113 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{Emitter, State};
 use tokio::sync::Mutex;
 
+use super::update_script;
 use crate::db::Database;
 use crate::executor::{
-    delegated_executor::DelegatedExecutor, homebrew_executor::HomebrewExecutor,
+    adobe_rum_executor::AdobeRumExecutor, brew_batch::{self, CaskEvent},
+    delegated_executor::DelegatedExecutor,
+    homebrew_executor::HomebrewExecutor,
     homebrew_formula_executor::HomebrewFormulaExecutor,
+    jetbrains_toolbox_executor::JetBrainsToolboxExecutor,
     mas_executor::MasExecutor, microsoft_autoupdate_executor::MicrosoftAutoUpdateExecutor,
-    sparkle_executor::SparkleExecutor, UpdateExecutor,
+    sparkle_executor::SparkleExecutor, classify_failure, is_transient_failure, set_error_code, UpdateExecutor,
 };
-use crate::models::{AppDetail, AppSource, UpdateExecuteComplete, UpdateExecuteProgress, UpdateResult};
-use crate::utils::{app_lifecycle, sudo_session, AppError};
+use crate::models::{
+    is_waiting_phase, AppDetail, AppSource, BulkFailureGroup, BulkSummaryApp, BulkUpdateSummary, DmgLicensePolicy, PermanentDeletePolicy, QueuedAction, RelaunchPolicy, ScheduledUpdate,
+    TeamIdChange, UpdateElevationPreflight, UpdateErrorCode, UpdateExecuteComplete,
+    UpdateExecuteProgress, UpdateLicensePrompt, UpdatePreview, UpdateResult, UpdateSchedule, UpdateTrustWarning,
+};
+use crate::platform::permissions::{self, PermissionState};
+use crate::scheduler::{offline, scheduled_updates};
+use crate::updaters::jetbrains_toolbox;
+use crate::utils::i18n::{tr, tr_args};
+use crate::utils::{self, app_lifecycle, brew, codesign, sudo_session, AppError, AppResult};
+
+/// Team ID changes the user confirmed, by bundle ID. Each one applies to the next
+/// update of that app only.
+static APPROVED_TEAM_ID_CHANGES: StdMutex<Vec<(String, TeamIdChange)>> = StdMutex::new(Vec::new());
+
+/// Apps whose disk image license agreement the user accepted. Each approval
+/// applies to the next update of that app only.
+static ACCEPTED_LICENSES: StdMutex<Vec<String>> = StdMutex::new(Vec::new());
+
+/// What direct-download updates of one app are checked against: the Team ID the
+/// installed version is signed with, and a change the user already approved.
+struct TeamIdCheck {
+    expected: Option<String>,
+    approved: Option<TeamIdChange>,
+}
+
+/// Build the Team ID check for `detail`. The Team ID is read from the bundle and
+/// recorded the first time it's needed.
+async fn team_id_check(db: &Mutex<Database>, detail: &AppDetail) -> TeamIdCheck {
+    let stored = db.lock().await.get_team_id(detail.id);
+    let expected = match stored {
+        Some(id) => Some(id),
+        None => {
+            let id = codesign::team_id(Path::new(&detail.app_path));
+            if let Some(ref id) = id {
+                let _ = db.lock().await.set_team_id(detail.id, Some(id));
+            }
+            id
+        }
+    };
+    let approved = {
+        let mut approvals = APPROVED_TEAM_ID_CHANGES.lock().unwrap_or_else(|e| e.into_inner());
+        approvals
+            .iter()
+            .position(|(bid, _)| *bid == detail.bundle_id)
+            .map(|i| approvals.remove(i).1)
+    };
+    TeamIdCheck { expected, approved }
+}
+
+/// The license setting for the next update of `bundle_id`: `Accept` when the
+/// user accepted its agreement after the previous attempt, `policy` otherwise.
+fn dmg_license_for(bundle_id: &str, policy: DmgLicensePolicy) -> DmgLicensePolicy {
+    let mut accepted = ACCEPTED_LICENSES.lock().unwrap_or_else(|e| e.into_inner());
+    match accepted.iter().position(|bid| bid == bundle_id) {
+        Some(i) => {
+            accepted.remove(i);
+            DmgLicensePolicy::Accept
+        }
+        None => policy,
+    }
+}
+
+/// Tell the frontend an update was held back for the user to read a license agreement.
+fn emit_license_prompt(app_handle: &tauri::AppHandle, detail: &AppDetail, result: &UpdateResult) {
+    if let Some(license) = result.license.as_ref().filter(|l| !l.accepted) {
+        let _ = app_handle.emit(
+            "update-license-prompt",
+            UpdateLicensePrompt {
+                bundle_id: detail.bundle_id.clone(),
+                display_name: detail.display_name.clone(),
+                text: license.text.clone(),
+            },
+        );
+    }
+}
+
+/// Tell the frontend an update was held back because its signer changed.
+fn emit_trust_warning(app_handle: &tauri::AppHandle, detail: &AppDetail, result: &UpdateResult) {
+    if let Some(ref change) = result.team_id_change {
+        let _ = app_handle.emit(
+            "update-trust-warning",
+            UpdateTrustWarning {
+                bundle_id: detail.bundle_id.clone(),
+                display_name: detail.display_name.clone(),
+                installed_team_id: change.installed.clone(),
+                downloaded_team_id: change.downloaded.clone(),
+            },
+        );
+    }
+}
 
 /// Truncate long hex-only version strings (e.g. commit hashes) for display.
 fn truncate_version(version: &str) -> &str {
@@ -30,21 +126,68 @@ fn record_update_result(db: &Database, history_id: i64, result: &UpdateResult) {
         let _ = db.record_update_complete(history_id);
     } else {
         let _ = db.record_update_failed(history_id, result.message.as_deref().unwrap_or("Unknown error"));
+        let code = result.error_code.unwrap_or(UpdateErrorCode::Unknown);
+        crate::utils::metrics::record_failure(code.as_str());
+    }
+    if let Some(ref verdict) = result.gatekeeper {
+        let _ = db.record_update_gatekeeper(history_id, &verdict.summary());
+    }
+    if result.license.as_ref().is_some_and(|l| l.accepted) {
+        let _ = db.record_update_license_accepted(history_id);
+    }
+    crate::utils::metrics::flush(db);
+}
+
+/// Ask Gatekeeper about the bundle a successful update installed, so an app that
+/// would be blocked on first launch is flagged now rather than when it's opened.
+/// Formulae and delegated updates don't install a bundle themselves.
+fn assess_installed_bundle(detail: &AppDetail, result: &mut UpdateResult) {
+    if !result.success || result.delegated || !detail.app_path.ends_with(".app") {
+        return;
+    }
+    result.gatekeeper = codesign::gatekeeper_assess(Path::new(&detail.app_path));
+    if let Some(ref verdict) = result.gatekeeper {
+        if !verdict.accepted {
+            log::warn!("Gatekeeper would block {}: {}", detail.bundle_id, verdict.summary());
+            result.error_code = Some(UpdateErrorCode::GatekeeperBlocked);
+        }
     }
 }
 
 /// Check whether a URL points to a directly downloadable installer file.
-fn is_downloadable_url(url: &str) -> bool {
+pub(crate) fn is_downloadable_url(url: &str) -> bool {
     let lower = url.to_lowercase();
     lower.ends_with(".dmg") || lower.ends_with(".zip") || lower.ends_with(".pkg")
         || lower.contains(".dmg?") || lower.contains(".zip?") || lower.contains(".pkg?")
 }
 
+/// Failed result for an App Store update of an app without an App Store
+/// receipt, e.g. one that was deleted and downloaded again from its developer.
+/// The App Store can't update it; the next scan classifies it by where it came from.
+fn app_store_mismatch(detail: &AppDetail, bundle_id: &str) -> Option<UpdateResult> {
+    if crate::detection::bundle_reader::has_mas_receipt(Path::new(&detail.app_path)) {
+        return None;
+    }
+    let message = tr_args(
+        "{app} wasn't installed from the App Store. Rescan your apps to update it from its developer.",
+        &[("app", detail.display_name.as_str())],
+    );
+    Some(UpdateResult {
+        from_version: detail.installed_version.clone(),
+        error_code: Some(UpdateErrorCode::SourceMismatch),
+        ..UpdateResult::failed(bundle_id, "mas", message)
+    })
+}
+
 /// Route to the correct executor based on the available update's source_type,
 /// falling back to install_source-based routing when no update info is present.
 async fn route_and_execute(
     detail: &AppDetail,
     bundle_id: &str,
+    db: &Arc<Mutex<Database>>,
+    permanent_delete: PermanentDeletePolicy,
+    dmg_license: DmgLicensePolicy,
+    team_ids: &TeamIdCheck,
     on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
 ) -> Result<UpdateResult, AppError> {
     // Primary routing: by available_update.source_type
@@ -56,6 +199,11 @@ async fn route_and_execute(
                     if is_downloadable_url(url) {
                         return SparkleExecutor::new(url.clone(), detail.display_name.clone())
                             .with_source_type("homebrew_cask")
+                            .with_relaunch_policy(detail.relaunch_policy)
+                            .with_permanent_delete(permanent_delete)
+                            .with_dmg_license(dmg_license)
+                            .with_team_id_check(team_ids.expected.clone(), team_ids.approved.clone())
+                            .with_journal(db.clone())
                             .execute(bundle_id, &detail.app_path, on_progress)
                             .await;
                     }
@@ -69,7 +217,14 @@ async fn route_and_execute(
                 }
             }
             "adobe_cc" => {
-                // Open Adobe Creative Cloud for the user to apply updates
+                // Install with Remote Update Manager where it's set up
+                if let Some(executor) = AdobeRumExecutor::for_app(bundle_id, detail.display_name.clone()) {
+                    return executor
+                        .with_pre_version(detail.installed_version.clone())
+                        .execute(bundle_id, &detail.app_path, on_progress)
+                        .await;
+                }
+                // Otherwise open Adobe Creative Cloud for the user to apply updates
                 let _ = std::process::Command::new("open")
                     .arg("-b")
                     .arg("com.adobe.acc.AdobeCreativeCloud")
@@ -83,9 +238,17 @@ async fn route_and_execute(
                     to_version: detail.available_update.as_ref().map(|u| u.available_version.clone()),
                     handled_relaunch: false,
                     delegated: true,
+                    removal: None,
+                    team_id_change: None,
+                    gatekeeper: None,
+                    license: None,
+                    error_code: None,
                 });
             }
             "mas" => {
+                if let Some(mismatch) = app_store_mismatch(detail, bundle_id) {
+                    return Ok(mismatch);
+                }
                 return MasExecutor::new(detail.mas_app_id.clone())
                     .with_pre_version(detail.installed_version.clone())
                     .execute(bundle_id, &detail.app_path, on_progress)
@@ -95,6 +258,11 @@ async fn route_and_execute(
                 if let Some(ref url) = update.download_url {
                     if is_downloadable_url(url) {
                         return SparkleExecutor::new(url.clone(), detail.display_name.clone())
+                            .with_relaunch_policy(detail.relaunch_policy)
+                            .with_permanent_delete(permanent_delete)
+                            .with_dmg_license(dmg_license)
+                            .with_team_id_check(team_ids.expected.clone(), team_ids.approved.clone())
+                            .with_journal(db.clone())
                             .execute(bundle_id, &detail.app_path, on_progress)
                             .await;
                     }
@@ -109,6 +277,11 @@ async fn route_and_execute(
                         let source = if update.source_type.as_str() == "homebrew_api" { "homebrew_api" } else { "github" };
                         return SparkleExecutor::new(url.clone(), detail.display_name.clone())
                             .with_source_type(source)
+                            .with_relaunch_policy(detail.relaunch_policy)
+                            .with_permanent_delete(permanent_delete)
+                            .with_dmg_license(dmg_license)
+                            .with_team_id_check(team_ids.expected.clone(), team_ids.approved.clone())
+                            .with_journal(db.clone())
                             .execute(bundle_id, &detail.app_path, on_progress)
                             .await;
                     }
@@ -122,10 +295,40 @@ async fn route_and_execute(
                 }
                 // Fallback to delegated (opens release page)
             }
+            "jetbrains_toolbox" => {
+                // Toolbox keeps its own record of the IDEs it installed
+                if jetbrains_toolbox::toolbox_app_path().is_some()
+                    && jetbrains_toolbox::is_toolbox_managed(Path::new(&detail.app_path))
+                {
+                    return JetBrainsToolboxExecutor::new(detail.display_name.clone())
+                        .with_pre_version(detail.installed_version.clone())
+                        .execute(bundle_id, &detail.app_path, on_progress)
+                        .await;
+                }
+                // Otherwise install the official DMG, unless Homebrew owns the app
+                if let Some(ref url) = update.download_url {
+                    if is_downloadable_url(url)
+                        && AppSource::from_str(&detail.install_source) != AppSource::Homebrew
+                    {
+                        return SparkleExecutor::new(url.clone(), detail.display_name.clone())
+                            .with_source_type("jetbrains_toolbox")
+                            .with_relaunch_policy(detail.relaunch_policy)
+                            .with_permanent_delete(permanent_delete)
+                            .with_dmg_license(dmg_license)
+                            .with_team_id_check(team_ids.expected.clone(), team_ids.approved.clone())
+                            .with_journal(db.clone())
+                            .execute(bundle_id, &detail.app_path, on_progress)
+                            .await;
+                    }
+                }
+            }
             "microsoft_autoupdate" => {
                 return MicrosoftAutoUpdateExecutor::new(detail.display_name.clone())
                     .with_cask_token(detail.homebrew_cask_token.clone())
                     .with_pre_version(detail.installed_version.clone())
+                    .with_homebrew_managed(
+                        AppSource::from_str(&detail.install_source) == AppSource::Homebrew,
+                    )
                     .execute(bundle_id, &detail.app_path, on_progress)
                     .await;
             }
@@ -161,6 +364,9 @@ async fn route_and_execute(
             }
         }
         AppSource::MacAppStore => {
+            if let Some(mismatch) = app_store_mismatch(detail, bundle_id) {
+                return Ok(mismatch);
+            }
             MasExecutor::new(detail.mas_app_id.clone())
                 .with_pre_version(detail.installed_version.clone())
                 .execute(bundle_id, &detail.app_path, on_progress)
@@ -174,14 +380,214 @@ async fn route_and_execute(
     }
 }
 
+/// A bulk update that runs through the brew CLI rather than downloading
+/// the app directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BrewJob {
+    Formula(String),
+    Cask(String),
+}
+
+/// The brew CLI job an update will run, following the same routing as a
+/// single update. None for updates that don't touch brew, such as direct
+/// downloads, the App Store or delegated updates; those can run alongside it.
+pub(crate) fn brew_job(detail: &AppDetail) -> Option<BrewJob> {
+    let cask = || detail.homebrew_cask_token.clone().map(BrewJob::Cask);
+    if let Some(ref update) = detail.available_update {
+        let downloadable = update.download_url.as_deref().is_some_and(is_downloadable_url);
+        match update.source_type.as_str() {
+            "homebrew_cask" | "github" | "homebrew_api" if downloadable => return None,
+            "homebrew_cask" | "github" | "homebrew_api" if detail.homebrew_cask_token.is_some() => {
+                return cask()
+            }
+            "sparkle" if downloadable => return None,
+            "adobe_cc" | "mas" | "microsoft_autoupdate" => return None,
+            _ => {}
+        }
+    }
+    match AppSource::from_str(&detail.install_source) {
+        AppSource::HomebrewFormula => detail.homebrew_formula_name.clone().map(BrewJob::Formula),
+        AppSource::Homebrew => cask(),
+        _ => None,
+    }
+}
+
+/// Whether the update replaces the app bundle itself, through a direct download
+/// or `brew upgrade --cask`, which macOS only allows with App Management.
+/// Installers that run elsewhere (App Store, Toolbox, vendor updaters) don't.
+fn replaces_app_bundle(detail: &AppDetail) -> bool {
+    match brew_job(detail) {
+        Some(BrewJob::Cask(_)) => return true,
+        Some(BrewJob::Formula(_)) => return false,
+        None => {}
+    }
+    detail.available_update.as_ref().is_some_and(|update| {
+        update.download_url.as_deref().is_some_and(is_downloadable_url)
+            && match update.source_type.as_str() {
+                "homebrew_cask" | "sparkle" | "github" | "homebrew_api" => true,
+                "jetbrains_toolbox" => {
+                    jetbrains_toolbox::toolbox_app_path().is_none()
+                        || !jetbrains_toolbox::is_toolbox_managed(Path::new(&detail.app_path))
+                }
+                _ => false,
+            }
+    })
+}
+
+/// Result for an update that would stop with "Operation not permitted" partway
+/// through because macOS denies macPlus App Management. Checked before anything
+/// is downloaded; an undetermined permission lets the update run.
+async fn app_management_block(detail: &AppDetail, bundle_id: &str) -> Option<UpdateResult> {
+    if !replaces_app_bundle(detail) {
+        return None;
+    }
+    let state = tokio::task::spawn_blocking(permissions::app_management_state)
+        .await
+        .ok()?;
+    if state != PermissionState::Denied {
+        return None;
+    }
+    log::warn!("App Management is denied, not updating {}", bundle_id);
+    let message = tr_args(
+        "macOS doesn't allow macPlus to replace {name}. Turn on App Management for macPlus in System Settings \u{203a} Privacy & Security, then try again.",
+        &[("name", &detail.display_name)],
+    );
+    let source_type = detail
+        .available_update
+        .as_ref()
+        .map_or(detail.install_source.as_str(), |u| u.source_type.as_str());
+    Some(UpdateResult {
+        from_version: detail.installed_version.clone(),
+        error_code: Some(UpdateErrorCode::AppManagementDenied),
+        ..UpdateResult::failed(bundle_id, source_type, message)
+    })
+}
+
+/// Most times an update is tried while it keeps failing for transient reasons.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry; doubled for each retry after it.
+const RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Run an update, retrying with exponential backoff while it fails for a
+/// transient reason such as a timeout or a server error. Returns the outcome of
+/// the last attempt and how many attempts were made.
+async fn execute_with_retry(
+    detail: &AppDetail,
+    bundle_id: &str,
+    db: &Arc<Mutex<Database>>,
+    permanent_delete: PermanentDeletePolicy,
+    dmg_license: DmgLicensePolicy,
+    team_ids: &TeamIdCheck,
+    on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+) -> (Result<UpdateResult, AppError>, u32) {
+    if let Some(blocked) = app_management_block(detail, bundle_id).await {
+        return (Ok(blocked), 1);
+    }
+    let mut attempt = 1;
+    loop {
+        let mut result =
+            route_and_execute(detail, bundle_id, db, permanent_delete, dmg_license, team_ids, on_progress).await;
+        if let Ok(ref mut r) = result {
+            set_error_code(r);
+        }
+        let failure = match &result {
+            Ok(r) if r.success || r.delegated || r.team_id_change.is_some() => None,
+            Ok(r) => r.message.clone(),
+            Err(e) => Some(e.to_string()),
+        };
+        let retry = attempt < MAX_ATTEMPTS
+            && !offline::is_offline()
+            && failure.as_deref().is_some_and(is_transient_failure);
+        if !retry {
+            return (result, attempt);
+        }
+
+        let delay = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+        log::warn!(
+            "Update of {} failed on attempt {} ({}), retrying in {}s",
+            bundle_id,
+            attempt,
+            failure.unwrap_or_default(),
+            delay.as_secs()
+        );
+        on_progress(0, &format!("Network problem \u{2014} retrying in {}s...", delay.as_secs()), None);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Why an update can't be installed yet: it's a paid upgrade the user hasn't
+/// confirmed owning a license for.
+fn paid_upgrade_block(detail: &AppDetail) -> Option<String> {
+    let update = detail.available_update.as_ref().filter(|u| u.needs_license())?;
+    Some(format!(
+        "{} {} is a paid upgrade; confirm you own a license for it before updating",
+        detail.display_name, update.available_version
+    ))
+}
+
+/// Result for an update queued because macPlus is offline. It isn't recorded
+/// as a failure; the update runs once connectivity returns.
+fn queued_result(app_handle: &tauri::AppHandle, bundle_id: &str) -> UpdateResult {
+    offline::enqueue(app_handle, QueuedAction::Update { bundle_id: bundle_id.to_string() });
+    UpdateResult {
+        error_code: Some(UpdateErrorCode::Offline),
+        ..UpdateResult::failed(
+            bundle_id,
+            "queued",
+            tr("Offline \u{2014} the update will start when you're back online"),
+        )
+    }
+}
+
+/// What updating an app will do before it runs, including the other formulae
+/// a Homebrew formula upgrade pulls in along with it.
+#[tauri::command]
+pub async fn preview_update(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdatePreview, AppError> {
+    let detail = db.lock().await.get_app_detail(&bundle_id)?;
+    let update = detail
+        .available_update
+        .clone()
+        .ok_or_else(|| AppError::NotFound(format!("No update available for {}", bundle_id)))?;
+    let formula_impact = match brew_job(&detail) {
+        Some(BrewJob::Formula(name)) => Some(
+            tokio::task::spawn_blocking(move || brew_batch::formula_upgrade_impact(&name))
+                .await
+                .unwrap_or_default(),
+        ),
+        _ => None,
+    };
+    Ok(UpdatePreview {
+        elevation_reason: elevation_reason(&detail),
+        bundle_id,
+        display_name: detail.display_name,
+        from_version: detail.installed_version,
+        to_version: update.available_version,
+        source_type: update.source_type,
+        download_size: update.download_size,
+        formula_impact,
+    })
+}
+
 #[tauri::command]
 pub async fn execute_update(
     bundle_id: String,
     app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
 ) -> Result<UpdateResult, AppError> {
+    if offline::is_offline() {
+        return Ok(queued_result(&app_handle, &bundle_id));
+    }
     let db_guard = db.lock().await;
     let detail = db_guard.get_app_detail(&bundle_id)?;
+    if let Some(message) = paid_upgrade_block(&detail) {
+        return Err(AppError::Custom(message));
+    }
+    let settings = crate::scheduler::load_settings_from_db(&db_guard);
 
     // Record history start
     let to_version_raw = detail.available_update.as_ref()
@@ -195,6 +601,18 @@ pub async fn execute_update(
         &detail.install_source,
     ).ok();
     drop(db_guard);
+    let team_ids = team_id_check(&db, &detail).await;
+
+    let reason = elevation_reason(&detail);
+    let _ = app_handle.emit(
+        "update-elevation-preflight",
+        UpdateElevationPreflight {
+            bundle_id: bundle_id.clone(),
+            display_name: detail.display_name.clone(),
+            needs_password: reason.is_some(),
+            reason,
+        },
+    );
 
     let handle = app_handle.clone();
     let bid = bundle_id.clone();
@@ -204,15 +622,30 @@ pub async fn execute_update(
             "update-execute-progress",
             UpdateExecuteProgress {
                 bundle_id: bid.clone(),
-                phase: phase.to_string(),
+                phase: tr(phase),
                 percent,
                 downloaded_bytes: bytes.map(|(d, _)| d),
                 total_bytes: bytes.and_then(|(_, t)| t),
+                waiting: is_waiting_phase(phase),
             },
         );
     };
 
-    let result = route_and_execute(&detail, &bundle_id, &on_progress).await?;
+    let (result, attempts) = execute_with_retry(
+        &detail,
+        &bundle_id,
+        db.inner(),
+        settings.permanent_delete,
+        dmg_license_for(&bundle_id, settings.dmg_license),
+        &team_ids,
+        &on_progress,
+    )
+    .await;
+    if let Some(hid) = history_id {
+        let _ = db.lock().await.record_update_attempts(hid, attempts);
+    }
+    let mut result = result?;
+    assess_installed_bundle(&detail, &mut result);
 
     // Record history result
     if let Some(hid) = history_id {
@@ -220,11 +653,7 @@ pub async fn execute_update(
         record_update_result(&db_guard, hid, &result);
     }
 
-    // Check if app needs relaunch (skip if the executor already handled it)
-    let needs_relaunch = result.success
-        && !result.handled_relaunch
-        && (result.source_type == "homebrew_cask" || result.source_type == "homebrew_formula")
-        && app_lifecycle::is_app_running(&bundle_id);
+    let needs_relaunch = needs_relaunch(&detail, &result);
 
     let _ = app_handle.emit(
         "update-execute-complete",
@@ -236,8 +665,13 @@ pub async fn execute_update(
             needs_relaunch,
             app_path: if needs_relaunch { Some(detail.app_path.clone()) } else { None },
             delegated: result.delegated,
+            gatekeeper: result.gatekeeper.clone(),
+            error_code: result.error_code,
+            fix_command: terminal_fix(&detail, &result),
         },
     );
+    emit_trust_warning(&app_handle, &detail, &result);
+    emit_license_prompt(&app_handle, &detail, &result);
 
     // Send native notification for completed updates
     {
@@ -245,21 +679,18 @@ pub async fn execute_update(
         let settings = crate::scheduler::load_settings_from_db(&db_guard);
         drop(db_guard);
 
-        if settings.notification_on_updates {
-            use tauri_plugin_notification::NotificationExt;
-            let body = if result.delegated {
-                format!("Opened {} \u{2014} update within the app", detail.display_name)
+        let notify = settings.notification_on_updates
+            && settings.notification_policy.notifies_update_result(result.success || result.delegated);
+        if notify {
+            let template = if result.delegated {
+                "Opened {app} — update within the app"
             } else if result.success {
-                format!("{} updated successfully", detail.display_name)
+                "{app} updated successfully"
             } else {
-                format!("Failed to update {}", detail.display_name)
+                "Failed to update {app}"
             };
-
-            let mut builder = app_handle.notification().builder().title("macPlus").body(&body);
-            if settings.notification_sound {
-                builder = builder.sound("Glass");
-            }
-            let _ = builder.show();
+            let body = tr_args(template, &[("app", detail.display_name.as_str())]);
+            crate::scheduler::notifications::send_update_notification(&app_handle, &settings, &body, false);
         }
     }
 
@@ -277,6 +708,7 @@ pub async fn execute_update(
         if let Some(ref ver) = new_version {
             let _ = db_guard.update_installed_version(detail.id, ver);
         }
+        let _ = db_guard.set_team_id(detail.id, codesign::team_id(Path::new(&detail.app_path)).as_deref());
         let _ = db_guard.clear_available_updates(detail.id);
         if let Some(ref token) = detail.homebrew_cask_token {
             let _ = db_guard.clear_updates_for_cask_token(token);
@@ -286,12 +718,75 @@ pub async fn execute_update(
     Ok(result)
 }
 
+/// Install an update that was held back because it's signed by a different
+/// developer than the installed app. Only the Team ID change the user saw is
+/// accepted; a download signed by yet another team is held back again.
+#[tauri::command]
+pub async fn confirm_update_despite_warning(
+    bundle_id: String,
+    installed_team_id: String,
+    downloaded_team_id: Option<String>,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdateResult, AppError> {
+    {
+        let mut approvals = APPROVED_TEAM_ID_CHANGES.lock().unwrap_or_else(|e| e.into_inner());
+        approvals.retain(|(bid, _)| *bid != bundle_id);
+        approvals.push((
+            bundle_id.clone(),
+            TeamIdChange { installed: installed_team_id, downloaded: downloaded_team_id },
+        ));
+    }
+    log::warn!("User approved a signing Team ID change for {}", bundle_id);
+    execute_update(bundle_id, app_handle, db).await
+}
+
+/// Install an update that was held back for the user to read the license
+/// agreement of its disk image, accepting the agreement this once.
+#[tauri::command]
+pub async fn accept_license_and_update(
+    bundle_id: String,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdateResult, AppError> {
+    {
+        let mut accepted = ACCEPTED_LICENSES.lock().unwrap_or_else(|e| e.into_inner());
+        if !accepted.contains(&bundle_id) {
+            accepted.push(bundle_id.clone());
+        }
+    }
+    log::info!("User accepted the disk image license agreement of {}", bundle_id);
+    execute_update(bundle_id, app_handle, db).await
+}
+
+/// The apps "Update All" installs: every pending update that isn't ignored,
+/// except paid upgrades, which stay out until the user confirms owning the new
+/// license. Shared by the window, the tray menu and headless mode.
+pub(crate) fn pending_bulk_updates(db: &Database) -> AppResult<Vec<String>> {
+    Ok(db
+        .get_all_apps()?
+        .into_iter()
+        .filter(|app| app.has_update && !app.is_ignored && !app.needs_license)
+        .map(|app| app.bundle_id)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_bulk_update_ids(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<String>, AppError> {
+    pending_bulk_updates(&*db.lock().await)
+}
+
 #[tauri::command]
 pub async fn execute_bulk_update(
     bundle_ids: Vec<String>,
     app_handle: tauri::AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
 ) -> Result<Vec<UpdateResult>, AppError> {
+    if offline::is_offline() {
+        return Ok(bundle_ids.iter().map(|bid| queued_result(&app_handle, bid)).collect());
+    }
     let db = db.inner().clone();
     let semaphore = Arc::new(tokio::sync::Semaphore::new(4));
 
@@ -334,150 +829,69 @@ pub async fn execute_bulk_update(
         None
     };
 
-    let mut handles = Vec::new();
+    // Updates that run through brew go one at a time in their own lane, since
+    // brew holds a lock while it works; everything else runs alongside them.
+    let (brew_jobs, direct) = {
+        let db_guard = db.lock().await;
+        let mut brew_jobs = Vec::new();
+        let mut direct = Vec::new();
+        for (index, bundle_id) in bundle_ids.into_iter().enumerate() {
+            let job = db_guard
+                .get_app_detail(&bundle_id)
+                .ok()
+                .filter(|detail| paid_upgrade_block(detail).is_none())
+                .and_then(|detail| brew_job(&detail));
+            match job {
+                Some(job) => brew_jobs.push((index, bundle_id, job)),
+                None => direct.push((index, bundle_id)),
+            }
+        }
+        (brew_jobs, direct)
+    };
+
+    let brew_bundle_ids: Vec<(usize, String)> = brew_jobs
+        .iter()
+        .map(|(index, bundle_id, _)| (*index, bundle_id.clone()))
+        .collect();
+    let brew_lane = tokio::spawn(run_brew_lane(brew_jobs, db.clone(), app_handle.clone()));
 
-    for bundle_id in bundle_ids {
+    let mut handles = Vec::new();
+    for (index, bundle_id) in direct {
         let db = db.clone();
         let app_handle = app_handle.clone();
         let semaphore = semaphore.clone();
 
         let handle = tokio::spawn(async move {
             let _permit = semaphore.acquire().await.unwrap();
-
-            let db_guard = db.lock().await;
-            let detail = match db_guard.get_app_detail(&bundle_id) {
-                Ok(d) => d,
-                Err(e) => {
-                    return UpdateResult {
-                        bundle_id: bundle_id.clone(),
-                        success: false,
-                        message: Some(format!("Failed to get app detail: {}", e)),
-                        source_type: "unknown".to_string(),
-                        from_version: None,
-                        to_version: None,
-                        handled_relaunch: false,
-                        delegated: false,
-                    };
-                }
-            };
-
-            // Record history start
-            let to_version_raw = detail.available_update.as_ref()
-                .map(|u| u.available_version.clone())
-                .unwrap_or_else(|| "unknown".to_string());
-            let to_version = truncate_version(&to_version_raw).to_string();
-            let history_id = db_guard.record_update_start(
-                detail.id,
-                detail.installed_version.as_deref().unwrap_or("unknown"),
-                &to_version,
-                &detail.install_source,
-            ).ok();
-            drop(db_guard);
-
-            let emit_handle = app_handle.clone();
-            let bid = bundle_id.clone();
-
-            let on_progress = move |percent: u8, phase: &str, bytes: Option<(u64, Option<u64>)>| {
-                let _ = emit_handle.emit(
-                    "update-execute-progress",
-                    UpdateExecuteProgress {
-                        bundle_id: bid.clone(),
-                        phase: phase.to_string(),
-                        percent,
-                        downloaded_bytes: bytes.map(|(d, _)| d),
-                        total_bytes: bytes.and_then(|(_, t)| t),
-                    },
-                );
-            };
-
-            let result = match route_and_execute(&detail, &bundle_id, &on_progress).await {
-                Ok(r) => {
-                    // Record history result
-                    if let Some(hid) = history_id {
-                        let db_guard = db.lock().await;
-                        record_update_result(&db_guard, hid, &r);
-                    }
-
-                    let needs_relaunch = r.success
-                        && !r.handled_relaunch
-                        && (r.source_type == "homebrew_cask" || r.source_type == "homebrew_formula")
-                        && app_lifecycle::is_app_running(&bundle_id);
-
-                    let _ = app_handle.emit(
-                        "update-execute-complete",
-                        UpdateExecuteComplete {
-                            bundle_id: bundle_id.clone(),
-                            display_name: detail.display_name.clone(),
-                            success: r.success,
-                            message: r.message.clone(),
-                            needs_relaunch,
-                            app_path: if needs_relaunch { Some(detail.app_path.clone()) } else { None },
-                            delegated: r.delegated,
-                        },
-                    );
-                    if r.success && !r.delegated {
-                        let new_version = crate::detection::bundle_reader::read_bundle(
-                            std::path::Path::new(&detail.app_path),
-                        )
-                        .and_then(|b| b.installed_version)
-                        .or_else(|| detail.available_update.as_ref().map(|u| u.available_version.clone()));
-
-                        let db_guard = db.lock().await;
-                        if let Some(ref ver) = new_version {
-                            let _ = db_guard.update_installed_version(detail.id, ver);
-                        }
-                        let _ = db_guard.clear_available_updates(detail.id);
-                        if let Some(ref token) = detail.homebrew_cask_token {
-                            let _ = db_guard.clear_updates_for_cask_token(token);
-                        }
-                    }
-                    r
-                }
-                Err(e) => {
-                    // Record history failure
-                    if let Some(hid) = history_id {
-                        let db_guard = db.lock().await;
-                        let _ = db_guard.record_update_failed(hid, &e.to_string());
-                    }
-
-                    let source = AppSource::from_str(&detail.install_source);
-                    let _ = app_handle.emit(
-                        "update-execute-complete",
-                        UpdateExecuteComplete {
-                            bundle_id: bundle_id.clone(),
-                            display_name: detail.display_name.clone(),
-                            success: false,
-                            message: Some(e.to_string()),
-                            needs_relaunch: false,
-                            app_path: None,
-                            delegated: false,
-                        },
-                    );
-                    UpdateResult {
-                        bundle_id: bundle_id.clone(),
-                        success: false,
-                        message: Some(e.to_string()),
-                        source_type: source.as_str().to_string(),
-                        from_version: detail.installed_version.clone(),
-                        to_version: None,
-                        handled_relaunch: false,
-                        delegated: false,
-                    }
-                }
-            };
-
-            result
+            (index, update_one(bundle_id, None, db, app_handle).await)
         });
-
         handles.push(handle);
     }
 
-    let mut results = Vec::new();
+    // A panicked lane still owes a result for every app it was given
+    let mut results = match brew_lane.await {
+        Ok(results) => results,
+        Err(e) => {
+            log::error!("Brew update lane failed: {}", e);
+            brew_bundle_ids
+                .into_iter()
+                .map(|(index, bundle_id)| {
+                    let message = format!("Update task failed: {}", e);
+                    (index, UpdateResult::failed(bundle_id, "unknown", message))
+                })
+                .collect()
+        }
+    };
     for handle in handles {
         if let Ok(result) = handle.await {
             results.push(result);
         }
     }
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<UpdateResult> = results.into_iter().map(|(_, result)| result).collect();
+
+    let summary = bulk_summary(&results, &*db.lock().await);
+    let _ = app_handle.emit("bulk-update-summary", summary);
 
     // Cancel the sudo keepalive task now that all updates are done
     if let Some((handle, stop)) = keepalive_handle {
@@ -489,14 +903,442 @@ pub async fn execute_bulk_update(
     Ok(results)
 }
 
+/// Whether the app is still running its old version after `result`. Brew
+/// upgrades replace the bundle underneath it; the other executors relaunch the
+/// app themselves when they need to.
+fn needs_relaunch(detail: &AppDetail, result: &UpdateResult) -> bool {
+    result.success
+        && !result.handled_relaunch
+        && detail.relaunch_policy == RelaunchPolicy::Automatic
+        && (result.source_type == "homebrew_cask" || result.source_type == "homebrew_formula")
+        && app_lifecycle::is_app_running(&detail.bundle_id)
+}
+
+/// The "Fix in Terminal" command of a failed update.
+fn terminal_fix(detail: &AppDetail, result: &UpdateResult) -> Option<String> {
+    if result.success || result.delegated {
+        return None;
+    }
+    let code = result.error_code?;
+    update_script::fix_command(detail, code, result.message.as_deref())
+}
+
+/// Totals of a finished bulk update, its failures grouped by error code, and
+/// the apps that still need a relaunch or something done by hand.
+pub(crate) fn bulk_summary(results: &[UpdateResult], db: &Database) -> BulkUpdateSummary {
+    let mut summary = BulkUpdateSummary {
+        total: results.len(),
+        ..Default::default()
+    };
+    for r in results {
+        let detail = db.get_app_detail(&r.bundle_id).ok();
+        let app = BulkSummaryApp {
+            bundle_id: r.bundle_id.clone(),
+            display_name: detail
+                .as_ref()
+                .map_or_else(|| r.bundle_id.clone(), |d| d.display_name.clone()),
+            message: r.message.clone(),
+            app_path: None,
+            fix_command: None,
+        };
+        if r.success {
+            summary.succeeded += 1;
+            if r.delegated {
+                summary.delegated += 1;
+                summary.needs_action.push(app);
+            } else if r.error_code == Some(UpdateErrorCode::GatekeeperBlocked) {
+                summary.needs_action.push(app);
+            } else if let Some(detail) = detail.filter(|d| needs_relaunch(d, r)) {
+                summary.needs_relaunch.push(BulkSummaryApp {
+                    app_path: Some(detail.app_path),
+                    ..app
+                });
+            }
+            continue;
+        }
+
+        summary.failed += 1;
+        let code = r.error_code.unwrap_or(UpdateErrorCode::Unknown);
+        let app = BulkSummaryApp {
+            fix_command: detail
+                .as_ref()
+                .and_then(|d| update_script::fix_command(d, code, r.message.as_deref())),
+            ..app
+        };
+        if code.needs_user_action() {
+            summary.needs_action.push(app.clone());
+        }
+        match summary.failures.iter_mut().find(|g| g.error_code == code) {
+            Some(group) => group.apps.push(app),
+            None => summary.failures.push(BulkFailureGroup {
+                error_code: code,
+                apps: vec![app],
+            }),
+        }
+    }
+    summary
+}
+
+/// What a batched `brew upgrade` already did for one app of a bulk update.
+enum Batched {
+    /// The formula is now at this version
+    Formula(String),
+    /// brew reported the cask as upgraded
+    Cask,
+}
+
+/// Update one app of a bulk update and report it like a single update would.
+/// An app a batched `brew upgrade` already took care of is only updated on its
+/// own when the batch didn't change its version.
+async fn update_one(
+    bundle_id: String,
+    batched: Option<Batched>,
+    db: Arc<Mutex<Database>>,
+    app_handle: tauri::AppHandle,
+) -> UpdateResult {
+    let db_guard = db.lock().await;
+    let detail = match db_guard.get_app_detail(&bundle_id) {
+        Ok(d) => d,
+        Err(e) => {
+            return UpdateResult::failed(
+                &bundle_id,
+                "unknown",
+                format!("Failed to get app detail: {}", e),
+            );
+        }
+    };
+    if let Some(message) = paid_upgrade_block(&detail) {
+        return UpdateResult {
+            from_version: detail.installed_version.clone(),
+            to_version: detail
+                .available_update
+                .as_ref()
+                .map(|u| u.available_version.clone()),
+            error_code: Some(UpdateErrorCode::PaidUpgrade),
+            ..UpdateResult::failed(&bundle_id, &detail.install_source, message)
+        };
+    }
+    let settings = crate::scheduler::load_settings_from_db(&db_guard);
+
+    // Record history start
+    let to_version_raw = detail.available_update.as_ref()
+        .map(|u| u.available_version.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+    let to_version = truncate_version(&to_version_raw).to_string();
+    let history_id = db_guard.record_update_start(
+        detail.id,
+        detail.installed_version.as_deref().unwrap_or("unknown"),
+        &to_version,
+        &detail.install_source,
+    ).ok();
+    drop(db_guard);
+    let team_ids = team_id_check(&db, &detail).await;
+
+    let emit_handle = app_handle.clone();
+    let bid = bundle_id.clone();
+
+    let on_progress = move |percent: u8, phase: &str, bytes: Option<(u64, Option<u64>)>| {
+        let _ = emit_handle.emit(
+            "update-execute-progress",
+            UpdateExecuteProgress {
+                bundle_id: bid.clone(),
+                phase: tr(phase),
+                percent,
+                downloaded_bytes: bytes.map(|(d, _)| d),
+                total_bytes: bytes.and_then(|(_, t)| t),
+                waiting: is_waiting_phase(phase),
+            },
+        );
+    };
+
+    let batched = match batched {
+        Some(Batched::Formula(version)) => Some(version)
+            .filter(|v| detail.installed_version.as_deref() != Some(v.as_str()))
+            .map(|v| batched_result(&detail, "homebrew_formula", Some(v))),
+        Some(Batched::Cask) => {
+            let (changed, new_version) = crate::executor::homebrew_executor::bundle_changed(
+                &detail.app_path,
+                detail.installed_version.as_deref(),
+                detail.bundle_version.as_deref(),
+            );
+            changed.then(|| batched_result(&detail, "homebrew_cask", new_version))
+        }
+        None => None,
+    };
+    let (result, attempts) = match batched {
+        Some(result) => {
+            on_progress(100, "Upgraded together with the other selected apps", None);
+            (Ok(result), 1)
+        }
+        None => {
+            let dmg_license = dmg_license_for(&bundle_id, settings.dmg_license);
+            execute_with_retry(
+                &detail,
+                &bundle_id,
+                &db,
+                settings.permanent_delete,
+                dmg_license,
+                &team_ids,
+                &on_progress,
+            )
+            .await
+        }
+    };
+    if let Some(hid) = history_id {
+        let _ = db.lock().await.record_update_attempts(hid, attempts);
+    }
+    let result = match result {
+        Ok(mut r) => {
+            assess_installed_bundle(&detail, &mut r);
+            // Record history result
+            if let Some(hid) = history_id {
+                let db_guard = db.lock().await;
+                record_update_result(&db_guard, hid, &r);
+            }
+
+            let needs_relaunch = needs_relaunch(&detail, &r);
+
+            let _ = app_handle.emit(
+                "update-execute-complete",
+                UpdateExecuteComplete {
+                    bundle_id: bundle_id.clone(),
+                    display_name: detail.display_name.clone(),
+                    success: r.success,
+                    message: r.message.clone(),
+                    needs_relaunch,
+                    app_path: if needs_relaunch { Some(detail.app_path.clone()) } else { None },
+                    delegated: r.delegated,
+                    gatekeeper: r.gatekeeper.clone(),
+                    error_code: r.error_code,
+                    fix_command: terminal_fix(&detail, &r),
+                },
+            );
+            emit_trust_warning(&app_handle, &detail, &r);
+            emit_license_prompt(&app_handle, &detail, &r);
+            if r.success && !r.delegated {
+                let new_version = crate::detection::bundle_reader::read_bundle(
+                    std::path::Path::new(&detail.app_path),
+                )
+                .and_then(|b| b.installed_version)
+                .or_else(|| detail.available_update.as_ref().map(|u| u.available_version.clone()));
+
+                let db_guard = db.lock().await;
+                if let Some(ref ver) = new_version {
+                    let _ = db_guard.update_installed_version(detail.id, ver);
+                }
+                let _ = db_guard.set_team_id(
+                    detail.id,
+                    codesign::team_id(Path::new(&detail.app_path)).as_deref(),
+                );
+                let _ = db_guard.clear_available_updates(detail.id);
+                if let Some(ref token) = detail.homebrew_cask_token {
+                    let _ = db_guard.clear_updates_for_cask_token(token);
+                }
+            }
+            r
+        }
+        Err(e) => {
+            // Record history failure
+            if let Some(hid) = history_id {
+                let db_guard = db.lock().await;
+                let _ = db_guard.record_update_failed(hid, &e.to_string());
+            }
+
+            let source = AppSource::from_str(&detail.install_source);
+            let error_code = Some(classify_failure(&e.to_string()));
+            let fix_command = error_code
+                .and_then(|code| update_script::fix_command(&detail, code, Some(&e.to_string())));
+            let _ = app_handle.emit(
+                "update-execute-complete",
+                UpdateExecuteComplete {
+                    bundle_id: bundle_id.clone(),
+                    display_name: detail.display_name.clone(),
+                    success: false,
+                    message: Some(e.to_string()),
+                    needs_relaunch: false,
+                    app_path: None,
+                    delegated: false,
+                    gatekeeper: None,
+                    error_code,
+                    fix_command,
+                },
+            );
+            UpdateResult {
+                from_version: detail.installed_version.clone(),
+                error_code,
+                ..UpdateResult::failed(&bundle_id, source.as_str(), e.to_string())
+            }
+        }
+    };
+
+    result
+}
+
+/// Result for an app a batched `brew upgrade` moved to `to_version`.
+fn batched_result(detail: &AppDetail, source_type: &str, to_version: Option<String>) -> UpdateResult {
+    let (kind, name) = match source_type {
+        "homebrew_formula" => ("formula", detail.homebrew_formula_name.as_deref()),
+        _ => ("cask", detail.homebrew_cask_token.as_deref()),
+    };
+    UpdateResult {
+        bundle_id: detail.bundle_id.clone(),
+        success: true,
+        message: Some(format!(
+            "Successfully upgraded {} '{}'",
+            kind,
+            name.unwrap_or(&detail.display_name)
+        )),
+        source_type: source_type.to_string(),
+        from_version: detail.installed_version.clone(),
+        to_version,
+        handled_relaunch: false,
+        delegated: false,
+        removal: None,
+        team_id_change: None,
+        gatekeeper: None,
+        license: None,
+        error_code: None,
+    }
+}
+
+/// Run the bulk updates that go through brew, one at a time. Formulae go first,
+/// dependencies before the formulae that use them, and are upgraded together
+/// with a single `brew upgrade`; casks brew already manages are upgraded
+/// together the same way. Any the batches didn't upgrade, e.g. because they
+/// need administrator privileges, are then updated on their own.
+async fn run_brew_lane(
+    jobs: Vec<(usize, String, BrewJob)>,
+    db: Arc<Mutex<Database>>,
+    app_handle: tauri::AppHandle,
+) -> Vec<(usize, UpdateResult)> {
+    let formulae: Vec<String> = jobs
+        .iter()
+        .filter_map(|(_, _, job)| match job {
+            BrewJob::Formula(name) => Some(name.clone()),
+            BrewJob::Cask(_) => None,
+        })
+        .collect();
+    let mut order = formulae.clone();
+    let mut upgraded_formulae = HashMap::new();
+    if formulae.len() >= 2 {
+        let phase = tr_args(
+            "Upgrading {count} formulae together...",
+            &[("count", &formulae.len().to_string())],
+        );
+        for (_, bundle_id, job) in &jobs {
+            if matches!(job, BrewJob::Formula(_)) {
+                emit_batch_progress(&app_handle, bundle_id, 20, phase.clone());
+            }
+        }
+        (order, upgraded_formulae) = tokio::task::spawn_blocking(move || {
+            let order = brew_batch::order_formulae(&formulae);
+            let upgraded = brew_batch::upgrade_formulae(&order);
+            (order, upgraded)
+        })
+        .await
+        .unwrap_or_default();
+    }
+
+    // Casks installed outside brew need `brew install --force`, which the
+    // single-app path takes care of
+    let casks: HashMap<String, String> = jobs
+        .iter()
+        .filter_map(|(_, bundle_id, job)| match job {
+            BrewJob::Cask(token) if brew::is_cask_installed(token) => {
+                Some((token.clone(), bundle_id.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    let mut upgraded_casks = HashSet::new();
+    // Without App Management every cask would fail partway; the single-app path
+    // reports that for each of them instead
+    let app_management_denied = !casks.is_empty()
+        && tokio::task::spawn_blocking(permissions::app_management_state).await.ok()
+            == Some(PermissionState::Denied);
+    if casks.len() >= 2 && !app_management_denied {
+        let phase = tr_args(
+            "Upgrading {count} apps together...",
+            &[("count", &casks.len().to_string())],
+        );
+        for bundle_id in casks.values() {
+            emit_batch_progress(&app_handle, bundle_id, 10, phase.clone());
+        }
+        let handle = app_handle.clone();
+        upgraded_casks = tokio::task::spawn_blocking(move || {
+            let tokens: Vec<String> = casks.keys().cloned().collect();
+            brew_batch::upgrade_casks(&tokens, |event| {
+                let (token, percent, phase) = match event {
+                    CaskEvent::Progress { token, percent, phase } => (token, percent, phase),
+                    CaskEvent::Upgraded(token) => (token, 95, "Verifying installation..."),
+                    CaskEvent::Failed { .. } => return,
+                };
+                emit_batch_progress(&handle, &casks[&token], percent, tr(phase));
+            })
+        })
+        .await
+        .unwrap_or_default();
+    }
+
+    let position = |job: &BrewJob| match job {
+        BrewJob::Formula(name) => order.iter().position(|n| n == name).unwrap_or(0),
+        BrewJob::Cask(_) => order.len(),
+    };
+    let mut jobs = jobs;
+    jobs.sort_by_key(|(_, _, job)| position(job));
+
+    let mut results = Vec::with_capacity(jobs.len());
+    for (index, bundle_id, job) in jobs {
+        let batched = match job {
+            BrewJob::Formula(ref name) => upgraded_formulae.get(name).cloned().map(Batched::Formula),
+            BrewJob::Cask(ref token) => upgraded_casks.contains(token).then_some(Batched::Cask),
+        };
+        results.push((index, update_one(bundle_id, batched, db.clone(), app_handle.clone()).await));
+    }
+    results
+}
+
+fn emit_batch_progress(app_handle: &tauri::AppHandle, bundle_id: &str, percent: u8, phase: String) {
+    let _ = app_handle.emit(
+        "update-execute-progress",
+        UpdateExecuteProgress {
+            bundle_id: bundle_id.to_string(),
+            phase,
+            percent,
+            downloaded_bytes: None,
+            total_bytes: None,
+            waiting: false,
+        },
+    );
+}
+
+/// Why updating `detail` will probably ask for an administrator password: its
+/// update path may need elevation and the bundle or its folder isn't writable
+/// by this user. None when no prompt is expected.
+fn elevation_reason(detail: &AppDetail) -> Option<String> {
+    if !may_need_elevation(detail) {
+        return None;
+    }
+    let app = Path::new(&detail.app_path);
+    let target = [Some(app), app.parent()]
+        .into_iter()
+        .flatten()
+        .find(|p| p.exists() && !utils::is_writable(p))?;
+    Some(tr_args("{path} isn't writable by your user", &[("path", &target.display().to_string())]))
+}
+
 /// Check whether an app's update path is likely to need elevation.
 fn may_need_elevation(detail: &AppDetail) -> bool {
     // Check the update source_type first
     if let Some(ref update) = detail.available_update {
         match update.source_type.as_str() {
-            "homebrew_cask" | "sparkle" | "github" | "homebrew_api" | "microsoft_autoupdate" => return true,
+            "homebrew_cask" | "sparkle" | "github" | "homebrew_api" | "microsoft_autoupdate"
+            | "jetbrains_toolbox" => return true,
             "mas" => return true,
-            "adobe_cc" => return false,
+            // Remote Update Manager runs as root; Creative Cloud asks for nothing
+            "adobe_cc" => {
+                return AdobeRumExecutor::for_app(&detail.bundle_id, String::new()).is_some()
+            }
             _ => {}
         }
     }
@@ -507,6 +1349,48 @@ fn may_need_elevation(detail: &AppDetail) -> bool {
     }
 }
 
+/// Defer updates of `bundle_ids` to the next time the clock shows `time`
+/// ("HH:MM"), e.g. "02:00" to update tonight.
+#[tauri::command]
+pub async fn schedule_updates(
+    bundle_ids: Vec<String>,
+    time: String,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdateSchedule, AppError> {
+    let run_at = scheduled_updates::next_local_run_at(&time)
+        .ok_or_else(|| AppError::Custom(format!("Invalid time \"{}\", expected HH:MM", time)))?;
+    let db = db.lock().await;
+    db.schedule_updates(&bundle_ids, run_at.with_timezone(&chrono::Utc))?;
+    scheduled_updates::emit_changed(&app_handle, &db);
+    log::info!("Scheduled {} updates for {}", bundle_ids.len(), run_at);
+    Ok(UpdateSchedule {
+        run_at: run_at.to_rfc3339(),
+        scheduled: bundle_ids.len(),
+        launch_at_login: crate::scheduler::load_settings_from_db(&db).launch_at_login,
+    })
+}
+
+#[tauri::command]
+pub async fn get_scheduled_updates(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<ScheduledUpdate>, AppError> {
+    db.lock().await.get_scheduled_updates()
+}
+
+/// Drop scheduled updates of `bundle_ids` so they don't run.
+#[tauri::command]
+pub async fn cancel_scheduled_updates(
+    bundle_ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<usize, AppError> {
+    let db = db.lock().await;
+    let removed = db.cancel_scheduled_updates(&bundle_ids)?;
+    scheduled_updates::emit_changed(&app_handle, &db);
+    Ok(removed)
+}
+
 #[tauri::command]
 pub async fn relaunch_app(
     bundle_id: String,
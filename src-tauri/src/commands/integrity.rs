@@ -0,0 +1,201 @@
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::executor::{homebrew_executor::HomebrewExecutor, recovery, sparkle_executor::SparkleExecutor, UpdateExecutor};
+use crate::models::{
+    AppDetail, IntegrityReport, RepairMethod, StartupIssue, StartupIssueKind, UpdateResult,
+};
+use crate::utils::{codesign, AppError};
+
+/// Most problems reported back; a badly damaged bundle can list thousands of files.
+const MAX_PROBLEMS: usize = 50;
+
+/// Split `codesign --verify` stderr into one problem per line, dropping the
+/// `<app path>: ` prefix codesign puts on its summary line.
+pub(crate) fn parse_verify_problems(stderr: &str, app_path: &str) -> Vec<String> {
+    let prefix = format!("{}: ", app_path);
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.strip_prefix(prefix.as_str()).unwrap_or(line).to_string())
+        .take(MAX_PROBLEMS)
+        .collect()
+}
+
+/// How `detail` can be reinstalled without the user's help: Homebrew when it's a
+/// known cask, otherwise the installer download of its pending update.
+fn repair_method(detail: &AppDetail) -> Option<RepairMethod> {
+    if detail.homebrew_cask_token.is_some() {
+        return Some(RepairMethod::Homebrew);
+    }
+    detail
+        .available_update
+        .as_ref()
+        .and_then(|u| u.download_url.as_deref())
+        .filter(|url| super::execute::is_downloadable_url(url))
+        .map(|_| RepairMethod::Download)
+}
+
+/// Check an app's code signature and sealed resources, e.g. after a sync tool or
+/// disk problem may have modified or removed files inside the bundle.
+#[tauri::command]
+pub async fn verify_app_integrity(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<IntegrityReport, AppError> {
+    let detail = db.lock().await.get_app_detail(&bundle_id)?;
+    let app_path = detail.app_path.clone();
+    let verified = tokio::task::spawn_blocking(move || codesign::verify_signature(Path::new(&app_path)))
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let problems = match verified {
+        Ok(()) => Vec::new(),
+        Err(stderr) => {
+            log::warn!("Integrity check failed for {}: {}", bundle_id, stderr);
+            parse_verify_problems(&stderr, &detail.app_path)
+        }
+    };
+    let valid = problems.is_empty();
+    Ok(IntegrityReport {
+        bundle_id,
+        valid,
+        repair: if valid { None } else { repair_method(&detail) },
+        problems,
+    })
+}
+
+/// Reinstall a damaged app through Homebrew or its installer download.
+#[tauri::command]
+pub async fn repair_app(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdateResult, AppError> {
+    let (detail, settings, team_id) = {
+        let db_guard = db.lock().await;
+        let detail = db_guard.get_app_detail(&bundle_id)?;
+        let settings = crate::scheduler::load_settings_from_db(&db_guard);
+        let team_id = db_guard.get_team_id(detail.id);
+        (detail, settings, team_id)
+    };
+    let on_progress = |_: u8, _: &str, _: Option<(u64, Option<u64>)>| {};
+
+    let method = repair_method(&detail);
+    let result = match (method, &detail.homebrew_cask_token, &detail.available_update) {
+        (Some(RepairMethod::Homebrew), Some(token), _) => {
+            HomebrewExecutor::new(token.clone())
+                .with_pre_version(detail.installed_version.clone())
+                .with_reinstall()
+                .execute(&bundle_id, &detail.app_path, &on_progress)
+                .await?
+        }
+        (Some(RepairMethod::Download), _, Some(update)) => {
+            let url = update.download_url.clone().unwrap_or_default();
+            SparkleExecutor::new(url, detail.display_name.clone())
+                .with_source_type(&update.source_type)
+                .with_relaunch_policy(detail.relaunch_policy)
+                .with_permanent_delete(settings.permanent_delete)
+                .with_dmg_license(settings.dmg_license)
+                .with_team_id_check(team_id, None)
+                .with_journal(db.inner().clone())
+                .execute(&bundle_id, &detail.app_path, &on_progress)
+                .await?
+        }
+        _ => {
+            return Err(AppError::Custom(format!(
+                "{} can't be reinstalled automatically; download it again from its developer",
+                detail.display_name
+            )));
+        }
+    };
+
+    if result.success {
+        let new_version = crate::detection::bundle_reader::read_bundle(Path::new(&detail.app_path))
+            .and_then(|b| b.installed_version);
+        let db_guard = db.lock().await;
+        if let Some(ref version) = new_version {
+            let _ = db_guard.update_installed_version(detail.id, version);
+        }
+        // The download is the pending update's installer, so the app is now up to date
+        if method == Some(RepairMethod::Download) {
+            let _ = db_guard.clear_available_updates(detail.id);
+        }
+    }
+    Ok(result)
+}
+
+/// Find what interrupted updates of an earlier run left behind: apps missing
+/// from their path, leftover backups of app bundles and mounted disk images.
+#[tauri::command]
+pub async fn get_startup_issues(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<StartupIssue>, AppError> {
+    let apps = db.lock().await.get_app_paths()?;
+    let mut issues = tokio::task::spawn_blocking(move || recovery::find_startup_issues(&apps))
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let db_guard = db.lock().await;
+    for issue in issues.iter_mut().filter(|i| i.kind == StartupIssueKind::MissingApp) {
+        issue.repairable = issue
+            .bundle_id
+            .as_deref()
+            .and_then(|id| db_guard.get_app_detail(id).ok())
+            .is_some_and(|detail| repair_method(&detail).is_some());
+    }
+    Ok(issues)
+}
+
+/// Fix the startup issue at `path`: reinstall a missing app, put back or
+/// trash a leftover backup, or detach a stale disk image.
+#[tauri::command]
+pub async fn repair_startup_issue(
+    path: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let issue = get_startup_issues(db.clone())
+        .await?
+        .into_iter()
+        .find(|issue| issue.path == path)
+        .ok_or_else(|| AppError::NotFound(path.clone()))?;
+
+    if issue.kind == StartupIssueKind::MissingApp {
+        let bundle_id = issue.bundle_id.clone().unwrap_or_default();
+        let result = repair_app(bundle_id, db).await?;
+        if !result.success {
+            return Err(AppError::CommandFailed(result.message.unwrap_or_default()));
+        }
+        return Ok(());
+    }
+
+    let app_path = match issue.bundle_id.as_deref() {
+        Some(id) => Some(db.lock().await.get_app_detail(id)?.app_path),
+        None => None,
+    };
+    tokio::task::spawn_blocking(move || recovery::repair_startup_issue(&issue, app_path.as_deref()))
+        .await
+        .map_err(|e| AppError::Custom(e.to_string()))?
+        .map_err(AppError::CommandFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_problems_drop_app_prefix() {
+        let stderr = "/Applications/Foo.app: a sealed resource is missing or invalid\n\
+file modified: /Applications/Foo.app/Contents/Resources/en.lproj/Main.nib\n\n";
+        assert_eq!(
+            parse_verify_problems(stderr, "/Applications/Foo.app"),
+            vec![
+                "a sealed resource is missing or invalid".to_string(),
+                "file modified: /Applications/Foo.app/Contents/Resources/en.lproj/Main.nib".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::detection::pkg_receipts::{self, OrphanedReceipt};
+use crate::platform::login_items::{self, LoginItem};
+use crate::platform::system_extensions::{self, SystemExtensionInfo};
+use crate::utils::AppError;
+
+/// List installed system extensions and third-party kexts, each mapped to the
+/// tracked app that owns it. These are reported separately from the app list so
+/// VPN/security tools are not double-counted.
+#[tauri::command]
+pub async fn get_system_extensions(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<SystemExtensionInfo>, AppError> {
+    let apps = {
+        let db = db.lock().await;
+        db.get_app_paths()?
+    };
+
+    let (mut extensions, kexts) = tokio::join!(
+        system_extensions::list_system_extensions(),
+        system_extensions::list_kexts(),
+    );
+    extensions.extend(kexts);
+
+    let extensions = tokio::task::spawn_blocking(move || {
+        system_extensions::assign_parents(&mut extensions, &apps);
+        extensions
+    })
+    .await
+    .map_err(|e| AppError::Custom(format!("task join: {}", e)))?;
+
+    Ok(extensions)
+}
+
+/// List launch agents/daemons and app-bundled login items, associated with tracked apps.
+#[tauri::command]
+pub async fn list_login_items(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<LoginItem>, AppError> {
+    let apps = {
+        let db = db.lock().await;
+        db.get_app_paths()?
+    };
+    Ok(login_items::list_login_items(&apps).await)
+}
+
+#[tauri::command]
+pub async fn enable_login_item(
+    label: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    toggle_login_item(&label, true, &db).await
+}
+
+#[tauri::command]
+pub async fn disable_login_item(
+    label: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    toggle_login_item(&label, false, &db).await
+}
+
+async fn toggle_login_item(
+    label: &str,
+    enabled: bool,
+    db: &State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let apps = {
+        let db = db.lock().await;
+        db.get_app_paths()?
+    };
+    let item = login_items::list_login_items(&apps)
+        .await
+        .into_iter()
+        .find(|i| i.label == label)
+        .ok_or_else(|| AppError::NotFound(format!("Login item not found: {}", label)))?;
+
+    tokio::task::spawn_blocking(move || login_items::set_login_item_enabled(&item, enabled))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+}
+
+/// List package receipts whose app was uninstalled or whose payload no longer exists.
+#[tauri::command]
+pub async fn audit_pkg_receipts() -> Result<Vec<OrphanedReceipt>, AppError> {
+    tokio::task::spawn_blocking(pkg_receipts::find_orphaned_receipts)
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))
+}
+
+/// Run `pkgutil --forget` (elevated) for each package ID. Returns the IDs that were forgotten.
+#[tauri::command]
+pub async fn forget_pkg_receipts(package_ids: Vec<String>) -> Result<Vec<String>, AppError> {
+    use crate::utils::sudo_session::{self, ElevatedError};
+
+    // Apple receipts are never touched
+    let package_ids: Vec<String> = package_ids
+        .into_iter()
+        .filter(|id| !id.starts_with("com.apple."))
+        .collect();
+
+    tokio::task::spawn_blocking(move || {
+        if package_ids.len() > 1 {
+            sudo_session::pre_authenticate();
+        }
+
+        let mut forgotten = Vec::new();
+        for id in &package_ids {
+            match sudo_session::run_elevated("/usr/sbin/pkgutil", &["--forget", id]) {
+                Ok(out) if out.status.success() => forgotten.push(id.clone()),
+                Ok(out) => log::warn!(
+                    "pkgutil --forget {} failed: {}",
+                    id,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ),
+                Err(ElevatedError::UserCancelled) => {
+                    if forgotten.is_empty() {
+                        return Err(AppError::CommandFailed(
+                            "Administrator approval is required to forget package receipts".to_string(),
+                        ));
+                    }
+                    break;
+                }
+                Err(e) => log::warn!("pkgutil --forget {} failed: {}", id, e),
+            }
+        }
+        Ok(forgotten)
+    })
+    .await
+    .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+}
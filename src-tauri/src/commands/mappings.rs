@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::{AppMapping, MappingConflict, MappingImportReport, MappingPack};
+use crate::utils::AppError;
+
+/// Version of the mapping file format written by `export_mappings`.
+const PACK_FORMAT_VERSION: u32 = 1;
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Trim an imported mapping, or None if it's malformed or doesn't map anything.
+fn normalize(mapping: AppMapping) -> Option<AppMapping> {
+    let mapping = AppMapping {
+        bundle_id: mapping.bundle_id.trim().to_string(),
+        github_repo: non_empty(mapping.github_repo),
+        custom_feed_url: non_empty(mapping.custom_feed_url),
+        homebrew_cask_token: non_empty(mapping.homebrew_cask_token),
+        mas_id: mapping.mas_id,
+    };
+    let repo_ok = mapping.github_repo.as_deref().map_or(true, |repo| {
+        repo.split_once('/')
+            .is_some_and(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+    });
+    let feed_ok = mapping
+        .custom_feed_url
+        .as_deref()
+        .map_or(true, |url| url.starts_with("https://") || url.starts_with("http://"));
+    let maps_something = mapping.github_repo.is_some()
+        || mapping.custom_feed_url.is_some()
+        || mapping.homebrew_cask_token.is_some()
+        || mapping.mas_id.is_some();
+    (!mapping.bundle_id.is_empty() && repo_ok && feed_ok && maps_something).then_some(mapping)
+}
+
+/// Work out which imported mappings to write. Ones that disagree with an
+/// existing mapping are reported as conflicts and left alone unless `overwrite`.
+pub(crate) fn plan_import(
+    existing: &HashMap<String, AppMapping>,
+    incoming: Vec<AppMapping>,
+    overwrite: bool,
+) -> (Vec<AppMapping>, MappingImportReport) {
+    let mut report = MappingImportReport::default();
+    let mut to_write = Vec::new();
+    for raw in incoming {
+        let bundle_id = raw.bundle_id.clone();
+        let Some(mapping) = normalize(raw) else {
+            report.invalid.push(bundle_id);
+            continue;
+        };
+        match existing.get(&mapping.bundle_id) {
+            None => {
+                report.added += 1;
+                to_write.push(mapping);
+            }
+            Some(current) if *current == mapping => report.unchanged += 1,
+            Some(_) if overwrite => {
+                report.replaced += 1;
+                to_write.push(mapping);
+            }
+            Some(current) => report.conflicts.push(MappingConflict {
+                current: current.clone(),
+                incoming: mapping,
+            }),
+        }
+    }
+    (to_write, report)
+}
+
+/// Write all user-defined mappings to a JSON file that others can import.
+/// Returns how many mappings were exported.
+#[tauri::command]
+pub async fn export_mappings(
+    path: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<usize, AppError> {
+    let mappings = db.lock().await.get_user_mappings()?;
+    let count = mappings.len();
+    let pack = MappingPack {
+        format_version: PACK_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        mappings,
+    };
+    let json = serde_json::to_string_pretty(&pack).map_err(|e| AppError::Custom(e.to_string()))?;
+    tokio::fs::write(&path, json).await?;
+    Ok(count)
+}
+
+/// Import mappings exported by `export_mappings`. Mappings that disagree with
+/// ones the user already has are only replaced when `overwrite` is set.
+#[tauri::command]
+pub async fn import_mappings(
+    path: String,
+    overwrite: bool,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<MappingImportReport, AppError> {
+    let json = tokio::fs::read_to_string(&path).await?;
+    let pack: MappingPack = serde_json::from_str(&json)
+        .map_err(|e| AppError::Custom(format!("Not a mapping file: {}", e)))?;
+    if pack.format_version > PACK_FORMAT_VERSION {
+        return Err(AppError::Custom(
+            "This mapping file was made by a newer version of macPlus".to_string(),
+        ));
+    }
+
+    let db = db.lock().await;
+    let (to_write, report) = plan_import(&db.get_app_mappings(), pack.mappings, overwrite);
+    db.upsert_user_mappings(&to_write)?;
+    log::info!(
+        "Imported mappings: {} added, {} replaced, {} unchanged, {} conflicts, {} invalid",
+        report.added,
+        report.replaced,
+        report.unchanged,
+        report.conflicts.len(),
+        report.invalid.len()
+    );
+    Ok(report)
+}
+
+/// Match an app to a cask the user picked, e.g. when it was matched to the wrong
+/// cask of a multi-app vendor. The token must exist in the Homebrew cask index.
+#[tauri::command]
+pub async fn set_cask_token(
+    bundle_id: String,
+    token: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+    http_client: State<'_, reqwest::Client>,
+) -> Result<(), AppError> {
+    let token = token.trim().to_lowercase();
+    let index = crate::updaters::homebrew_api::fetch_cask_index(http_client.inner())
+        .await
+        .ok_or_else(|| AppError::Custom("Couldn't load the list of Homebrew casks".to_string()))?;
+    if !index.has_token(&token) {
+        return Err(AppError::Custom(format!("There's no Homebrew cask named \"{}\"", token)));
+    }
+
+    let db = db.lock().await;
+    db.get_app_detail(&bundle_id)?;
+    // Updates found through the old match no longer apply
+    db.clear_cask_match(&bundle_id)?;
+    db.replace_cask_token(&bundle_id, &token)?;
+    db.set_user_cask_token(&bundle_id, Some(&token))?;
+    log::info!("Cask token for {} set to '{}'", bundle_id, token);
+    Ok(())
+}
+
+/// Remove a cask token set with `set_cask_token` and go back to automatic
+/// matching on the next scan.
+#[tauri::command]
+pub async fn clear_cask_token(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let db = db.lock().await;
+    db.set_user_cask_token(&bundle_id, None)?;
+    db.clear_cask_match(&bundle_id)?;
+    log::info!("Cleared cask token for {}", bundle_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(bundle_id: &str, repo: &str) -> AppMapping {
+        AppMapping {
+            bundle_id: bundle_id.to_string(),
+            github_repo: Some(repo.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn import_reports_conflicts_unless_overwriting() {
+        let existing = HashMap::from([
+            ("com.a".to_string(), mapping("com.a", "a/a")),
+            ("com.b".to_string(), mapping("com.b", "b/b")),
+        ]);
+        let incoming = vec![
+            mapping("com.a", "a/a"),
+            mapping("com.b", "b/fork"),
+            mapping(" com.c ", " c/c "),
+            mapping("com.d", "not-a-repo"),
+        ];
+
+        let (to_write, report) = plan_import(&existing, incoming.clone(), false);
+        assert_eq!(to_write, vec![mapping("com.c", "c/c")]);
+        assert_eq!((report.added, report.replaced, report.unchanged), (1, 0, 1));
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].incoming, mapping("com.b", "b/fork"));
+        assert_eq!(report.invalid, vec!["com.d".to_string()]);
+
+        let (to_write, report) = plan_import(&existing, incoming, true);
+        assert_eq!(to_write.len(), 2);
+        assert_eq!(report.replaced, 1);
+        assert!(report.conflicts.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::MetricsSummary;
+use crate::utils::{metrics, AppError};
+
+/// Summed local metrics, whether or not collection is on: turning it off
+/// deletes what was kept, so an empty summary is returned then.
+#[tauri::command]
+pub async fn get_metrics(db: State<'_, Arc<Mutex<Database>>>) -> Result<MetricsSummary, AppError> {
+    let db = db.lock().await;
+    metrics::flush(&db);
+    let mut summary = db.get_metrics_summary()?;
+    summary.enabled = crate::scheduler::load_settings_from_db(&db).collect_metrics;
+    Ok(summary)
+}
+
+/// An exported metrics summary. Only the macPlus version is added to the counts.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricsExport {
+    app_version: &'static str,
+    exported_at: String,
+    metrics: MetricsSummary,
+}
+
+/// Write the metrics summary to a JSON file, e.g. to attach to a bug report.
+#[tauri::command]
+pub async fn export_metrics(
+    path: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), AppError> {
+    let summary = get_metrics(db).await?;
+    let export = MetricsExport {
+        app_version: env!("CARGO_PKG_VERSION"),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        metrics: summary,
+    };
+    let json =
+        serde_json::to_string_pretty(&export).map_err(|e| AppError::Custom(e.to_string()))?;
+    tokio::fs::write(&path, json).await?;
+    Ok(())
+}
+
+/// Delete the metrics kept so far; collection goes on if it's turned on.
+#[tauri::command]
+pub async fn clear_metrics(db: State<'_, Arc<Mutex<Database>>>) -> Result<(), AppError> {
+    let db = db.lock().await;
+    metrics::flush(&db);
+    db.clear_metrics()
+}
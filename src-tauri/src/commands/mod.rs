@@ -1,7 +1,15 @@
 pub mod apps;
+pub mod archive;
 pub mod execute;
+pub mod integrity;
+pub mod inventory;
+pub mod mappings;
+pub mod metrics;
+pub mod privacy;
+pub mod secrets;
 pub mod self_update;
 pub mod settings;
 pub mod system;
 pub mod uninstall;
+pub mod update_script;
 pub mod updates;
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::utils::{keychain, AppError};
+
+/// Wipe everything macPlus has stored: all database tables (settings included),
+/// the cache folder (icons, GitHub responses, staged updates) and Keychain secrets.
+/// Uninstall archives are the user's own files and are left alone. The app should
+/// be relaunched afterwards so in-memory state starts fresh.
+#[tauri::command]
+pub async fn delete_all_data(
+    db: State<'_, Arc<Mutex<Database>>>,
+    app_handle: AppHandle,
+) -> Result<(), AppError> {
+    {
+        let db = db.lock().await;
+        db.delete_all_data()?;
+    }
+
+    crate::updaters::github_releases::clear_etag_cache().await;
+    crate::utils::http_client::clear_response_cache();
+
+    if let Ok(cache_dir) = app_handle.path().app_cache_dir() {
+        if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let removed = if path.is_dir() {
+                    std::fs::remove_dir_all(&path)
+                } else {
+                    std::fs::remove_file(&path)
+                };
+                if let Err(e) = removed {
+                    log::warn!("Failed to remove {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    tokio::task::spawn_blocking(|| {
+        for key in [keychain::GITHUB_TOKEN, keychain::PROXY_PASSWORD] {
+            keychain::delete_secret(key)?;
+        }
+        Ok::<_, AppError>(())
+    })
+    .await
+    .map_err(|e| AppError::Custom(format!("task join: {}", e)))??;
+
+    log::info!("All macPlus data deleted");
+    Ok(())
+}
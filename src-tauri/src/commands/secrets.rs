@@ -0,0 +1,22 @@
+use crate::utils::{keychain, AppError};
+
+#[tauri::command]
+pub async fn set_secret(key: String, value: String) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || keychain::set_secret(&key, &value))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn get_secret(key: String) -> Result<Option<String>, AppError> {
+    tokio::task::spawn_blocking(move || keychain::get_secret(&key))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn delete_secret(key: String) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || keychain::delete_secret(&key))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+}
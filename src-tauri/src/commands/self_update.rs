@@ -1,22 +1,33 @@
 use std::io::{Read as _, Write as _};
 use std::os::unix::process::CommandExt;
 use std::process::Command;
+use std::sync::Arc;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use base64::Engine;
 use futures::StreamExt;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
 
+use crate::db::Database;
 use crate::executor::sparkle_executor;
-use crate::updaters::github_releases::check_github_release;
+use crate::models::UpdateChannel;
+use crate::updaters::github_releases::{check_github_release, check_github_release_with_prereleases};
 use crate::updaters::version_compare;
 use crate::utils::brew::brew_path;
-use crate::utils::AppError;
+use crate::utils::codesign;
+use crate::utils::{http_client, AppError};
 
 const SELF_REPO_OWNER: &str = "smallsimplesuper";
 const SELF_REPO_NAME: &str = "macplus";
 const SELF_BUNDLE_ID: &str = "com.macplus.app";
 
+/// Minisign public key for `tauri_plugin_updater`, injected by the release workflow.
+/// Builds without it (local/dev) only have the DMG self-update path.
+pub const UPDATER_PUBKEY: Option<&str> = option_env!("MACPLUS_UPDATER_PUBKEY");
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SelfUpdateInfo {
@@ -25,22 +36,49 @@ pub struct SelfUpdateInfo {
     pub release_notes_url: Option<String>,
     pub download_url: Option<String>,
     pub can_brew_upgrade: bool,
+    /// Channel the update was found on ("stable" or "beta")
+    pub channel: String,
+    /// True when the available version is a GitHub prerelease
+    pub is_prerelease: bool,
+}
+
+/// The self-update channel from settings.
+pub async fn self_update_channel(app_handle: &AppHandle) -> UpdateChannel {
+    let db = app_handle.state::<Arc<Mutex<Database>>>();
+    let db_guard = db.lock().await;
+    crate::scheduler::load_settings_from_db(&db_guard).update_channel
 }
 
 /// Standalone check that can be called from both the Tauri command and the scheduler.
-pub async fn check_self_update_inner(client: &reqwest::Client) -> Option<SelfUpdateInfo> {
+pub async fn check_self_update_inner(
+    client: &reqwest::Client,
+    channel: UpdateChannel,
+) -> Option<SelfUpdateInfo> {
     let current_version = env!("CARGO_PKG_VERSION");
 
-    let update = check_github_release(
-        SELF_REPO_OWNER,
-        SELF_REPO_NAME,
-        SELF_BUNDLE_ID,
-        Some(current_version),
-        client,
-    )
-    .await
-    .ok()
-    .flatten()?;
+    let (update, is_prerelease) = match channel {
+        UpdateChannel::Stable => check_github_release(
+            SELF_REPO_OWNER,
+            SELF_REPO_NAME,
+            SELF_BUNDLE_ID,
+            Some(current_version),
+            client,
+        )
+        .await
+        .ok()
+        .flatten()
+        .map(|u| (u, false))?,
+        UpdateChannel::Beta => check_github_release_with_prereleases(
+            SELF_REPO_OWNER,
+            SELF_REPO_NAME,
+            SELF_BUNDLE_ID,
+            Some(current_version),
+            client,
+        )
+        .await
+        .ok()
+        .flatten()?,
+    };
 
     // Double-check: the version from GitHub must actually be newer
     if !version_compare::is_newer(current_version, &update.available_version) {
@@ -55,7 +93,10 @@ pub async fn check_self_update_inner(client: &reqwest::Client) -> Option<SelfUpd
         current_version: current_version.to_string(),
         release_notes_url: update.release_notes_url,
         download_url: update.download_url,
-        can_brew_upgrade,
+        // Homebrew only ships stable builds
+        can_brew_upgrade: can_brew_upgrade && !is_prerelease,
+        channel: channel.as_str().to_string(),
+        is_prerelease,
     })
 }
 
@@ -65,8 +106,7 @@ fn check_brew_installed() -> bool {
         return false;
     };
 
-    std::process::Command::new(brew.as_os_str())
-        .current_dir("/tmp")
+    crate::utils::brew::brew_command(brew)
         .args(["list", "--cask", "macplus"])
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
@@ -77,9 +117,11 @@ fn check_brew_installed() -> bool {
 
 #[tauri::command]
 pub async fn check_self_update(
+    app_handle: AppHandle,
     http_client: State<'_, reqwest::Client>,
 ) -> Result<Option<SelfUpdateInfo>, AppError> {
-    Ok(check_self_update_inner(http_client.inner()).await)
+    let channel = self_update_channel(&app_handle).await;
+    Ok(check_self_update_inner(http_client.inner(), channel).await)
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -89,16 +131,19 @@ pub struct SelfUpdateProgress {
     pub percent: u8,
     pub downloaded_bytes: Option<u64>,
     pub total_bytes: Option<u64>,
+    /// Waiting on the user, e.g. for an administrator password
+    pub waiting: bool,
 }
 
 fn emit_progress(app: &AppHandle, phase: &str, percent: u8, dl: Option<u64>, total: Option<u64>) {
     let _ = app.emit(
         "self-update-progress",
         SelfUpdateProgress {
-            phase: phase.to_string(),
+            phase: crate::utils::i18n::tr(phase),
             percent,
             downloaded_bytes: dl,
             total_bytes: total,
+            waiting: crate::models::is_waiting_phase(phase),
         },
     );
 }
@@ -119,40 +164,114 @@ fn is_writable(path: &std::path::Path) -> bool {
     }
 }
 
-#[tauri::command]
-pub async fn execute_self_update(
-    download_url: String,
-    app_handle: AppHandle,
-    http_client: State<'_, reqwest::Client>,
-) -> Result<(), AppError> {
-    // 1. Find current app path
+/// Check that a downloaded update has a valid code signature and the same Team ID as
+/// the running app. Unsigned/ad-hoc dev builds have no Team ID to compare against, so
+/// they only require a valid signature.
+fn verify_update_bundle(running: &std::path::Path, downloaded: &std::path::Path) -> Result<(), String> {
+    codesign::verify_signature(downloaded).map_err(|e| format!("invalid code signature: {}", e))?;
+
+    match (codesign::team_id(running), codesign::team_id(downloaded)) {
+        (Some(expected), Some(actual)) if expected == actual => Ok(()),
+        (Some(expected), actual) => Err(format!(
+            "Team ID mismatch (expected {}, got {})",
+            expected,
+            actual.as_deref().unwrap_or("none")
+        )),
+        (None, _) => {
+            log::warn!("Self-update: running app has no Team ID, skipping Team ID comparison");
+            Ok(())
+        }
+    }
+}
+
+/// Install via the signed Tauri updater (`latest.json` + minisign signature).
+///
+/// Returns `None` when the signed flow isn't available — no embedded pubkey, the
+/// manifest can't be fetched, or it doesn't offer `expected_version` (e.g. a beta build
+/// that isn't in `latest.json`) — so the caller can fall back to the DMG path.
+async fn try_signed_update(
+    app_handle: &AppHandle,
+    expected_version: Option<&str>,
+) -> Option<Result<(), AppError>> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    UPDATER_PUBKEY?;
+    let update = match app_handle.updater() {
+        Ok(updater) => match updater.check().await {
+            Ok(Some(update)) => update,
+            Ok(None) => return None,
+            Err(e) => {
+                log::warn!("Signed updater check failed, falling back to DMG: {}", e);
+                return None;
+            }
+        },
+        Err(e) => {
+            log::warn!("Signed updater unavailable: {}", e);
+            return None;
+        }
+    };
+    if expected_version.map_or(false, |v| v.trim_start_matches('v') != update.version) {
+        log::info!(
+            "Signed updater offers {} but {} was requested, using DMG path",
+            update.version,
+            expected_version.unwrap_or_default()
+        );
+        return None;
+    }
+
+    emit_progress(app_handle, "Downloading signed update...", 5, None, None);
+    let mut downloaded: u64 = 0;
+    let mut last_emit = Instant::now();
+    let result = update
+        .download_and_install(
+            |chunk_len, total| {
+                downloaded += chunk_len as u64;
+                if last_emit.elapsed() >= Duration::from_millis(150) {
+                    last_emit = Instant::now();
+                    let pct = total
+                        .map(|t| ((downloaded as f64 / t as f64) * 100.0) as u8)
+                        .unwrap_or(0);
+                    let mapped = 5 + (pct as u16 * 80 / 100) as u8;
+                    emit_progress(app_handle, "Downloading update...", mapped, Some(downloaded), total);
+                }
+            },
+            || emit_progress(app_handle, "Verifying and installing...", 90, None, None),
+        )
+        .await;
+
+    // A signature failure must not silently fall back to the unsigned path
+    Some(result.map_err(|e| AppError::CommandFailed(format!("Signed update failed: {}", e))))
+}
+
+/// Resolve the running `.app` bundle (exe is `.app/Contents/MacOS/<binary>`).
+fn current_app_bundle() -> Result<PathBuf, AppError> {
     let exe = std::env::current_exe()
         .map_err(|e| AppError::CommandFailed(format!("Failed to find current executable: {}", e)))?;
-    // exe is .app/Contents/MacOS/<binary> — go up 3 levels to get .app bundle
-    let app_bundle = exe
-        .parent() // MacOS/
+    exe.parent() // MacOS/
         .and_then(|p| p.parent()) // Contents/
         .and_then(|p| p.parent()) // .app/
-        .ok_or_else(|| AppError::CommandFailed("Failed to resolve .app bundle path".to_string()))?;
-    let app_path_str = app_bundle.to_string_lossy().to_string();
-
-    emit_progress(&app_handle, "Preparing update...", 2, None, None);
-
-    // 2. Create stable temp dir
-    let pid = std::process::id();
-    let tmp_dir = std::path::PathBuf::from(format!("/tmp/macplus-update-{}", pid));
-    if tmp_dir.exists() {
-        let _ = std::fs::remove_dir_all(&tmp_dir);
-    }
-    std::fs::create_dir_all(&tmp_dir)
-        .map_err(|e| AppError::CommandFailed(format!("Failed to create temp dir: {}", e)))?;
-
-    // 3. Download DMG with streaming progress
-    emit_progress(&app_handle, "Requesting download...", 5, None, None);
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| AppError::CommandFailed("Failed to resolve .app bundle path".to_string()))
+}
 
-    let response = http_client
-        .get(&download_url)
-        .send()
+/// Progress callback: (phase, percent, downloaded bytes, total bytes).
+type ProgressFn<'a> = &'a (dyn Fn(&str, u8, Option<u64>, Option<u64>) + Send + Sync);
+
+/// Download the update (DMG, ZIP or PKG) into `work_dir`, extract the new bundle and verify its
+/// signature against `app_bundle`. Returns the path of the extracted `.app`.
+async fn download_and_extract(
+    download_url: &str,
+    app_bundle: &std::path::Path,
+    work_dir: &std::path::Path,
+    progress: ProgressFn<'_>,
+) -> Result<PathBuf, AppError> {
+    // 1. Download with streaming progress
+    progress("Requesting download...", 5, None, None);
+
+    let client = http_client::download_client_builder()
+        .build()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to create HTTP client: {}", e)))?;
+    let response = http_client::send_download(client.get(download_url))
         .await
         .map_err(|e| AppError::CommandFailed(format!("Download failed: {}", e)))?;
 
@@ -187,7 +306,7 @@ pub async fn execute_self_update(
         });
 
     let total_bytes = response.content_length();
-    let download_path = tmp_dir.join(&filename);
+    let download_path = work_dir.join(&filename);
     let mut file = std::fs::File::create(&download_path)
         .map_err(|e| AppError::CommandFailed(format!("Failed to create download file: {}", e)))?;
     let mut downloaded: u64 = 0;
@@ -208,20 +327,15 @@ pub async fn execute_self_update(
                 .unwrap_or(0);
             // Map download progress to 5-50% range
             let mapped = 5 + (pct as u16 * 45 / 100) as u8;
-            emit_progress(
-                &app_handle,
-                "Downloading update...",
-                mapped,
-                Some(downloaded),
-                total_bytes,
-            );
+            progress("Downloading update...", mapped, Some(downloaded), total_bytes);
         }
     }
     drop(file);
+    crate::utils::metrics::record_download(downloaded);
 
-    emit_progress(&app_handle, "Download complete, extracting...", 50, None, None);
+    progress("Download complete, extracting...", 50, None, None);
 
-    // 4. Detect file type
+    // 2. Detect file type
     let mut magic_buf = [0u8; 16];
     let magic_len = {
         let mut f = std::fs::File::open(&download_path)
@@ -231,39 +345,50 @@ pub async fn execute_self_update(
     };
     let file_type = sparkle_executor::detect_file_type(&content_type, &filename, &magic_buf[..magic_len]);
 
-    if file_type != sparkle_executor::FileType::Dmg {
-        return Err(AppError::CommandFailed(format!(
-            "Expected DMG file but detected {:?} for {}",
-            file_type, filename
-        )));
-    }
-
-    // 5. Extract from DMG
+    // 3. Extract the bundle — packages are expanded rather than installed so the
+    //    new app goes through the same signature check and swap as DMG/ZIP assets
     let progress_cb = |pct: u8, phase: &str, _bytes: Option<(u64, Option<u64>)>| {
-        emit_progress(&app_handle, phase, pct, None, None);
+        progress(phase, pct, None, None);
     };
-    let new_app_path =
-        sparkle_executor::extract_from_dmg(&download_path, &tmp_dir, &progress_cb, "macPlus")?;
-
-    emit_progress(&app_handle, "Preparing to install...", 75, None, None);
-
-    // 6. Check write access and pre-authenticate if needed
-    let needs_sudo = !is_writable(app_bundle);
-    if needs_sudo {
-        emit_progress(&app_handle, "Requesting administrator privileges...", 80, None, None);
-        if !crate::utils::sudo_session::pre_authenticate() {
-            return Err(AppError::CommandFailed(
-                "Update cancelled \u{2014} administrator approval is required".to_string(),
-            ));
+    let new_app_path = match file_type {
+        sparkle_executor::FileType::Dmg => {
+            sparkle_executor::extract_from_dmg(
+                &download_path,
+                work_dir,
+                &progress_cb,
+                "macPlus",
+                true,
+            )?
         }
-    }
+        sparkle_executor::FileType::Zip => sparkle_executor::extract_from_zip(&download_path, work_dir)?,
+        sparkle_executor::FileType::Pkg => sparkle_executor::extract_from_pkg(&download_path, work_dir)?,
+        sparkle_executor::FileType::Unknown => {
+            return Err(AppError::CommandFailed(format!(
+                "Unsupported update format for {}",
+                filename
+            )));
+        }
+    };
 
-    emit_progress(&app_handle, "Installing update...", 85, None, None);
+    // 4. Verify the new bundle is validly signed by the same team as the running app
+    progress("Verifying signature...", 72, None, None);
+    let (running_bundle, downloaded_bundle) = (app_bundle.to_path_buf(), new_app_path.clone());
+    tokio::task::spawn_blocking(move || verify_update_bundle(&running_bundle, &downloaded_bundle))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?
+        .map_err(|e| AppError::CommandFailed(format!("Update rejected: {}", e)))?;
 
-    // 7. Replace app bundle inline (no shell script)
-    let old_app = app_bundle.to_path_buf();
+    Ok(new_app_path)
+}
+
+/// Swap `new_app` into place at `old_app`, keeping a backup until the copy succeeds
+/// and rolling back on failure.
+fn replace_app_bundle(
+    old_app: &std::path::Path,
+    new_app: &std::path::Path,
+    needs_sudo: bool,
+) -> Result<(), AppError> {
     let backup = old_app.with_extension("update-backup");
-    let new_app = new_app_path.clone();
 
     if needs_sudo {
         let old_app_s = old_app.to_string_lossy().to_string();
@@ -274,14 +399,8 @@ pub async fn execute_self_update(
             "mv '{}' '{}' && cp -R '{}' '{}' && xattr -rd com.apple.quarantine '{}' 2>/dev/null; rm -rf '{}'",
             old_app_s, backup_s, new_app_s, old_app_s, old_app_s, backup_s
         );
-        let cmd_clone = cmd.clone();
-        let result = tokio::task::spawn_blocking(move || {
-            crate::utils::sudo_session::run_elevated_shell(&cmd_clone)
-        })
-        .await
-        .map_err(|e| AppError::CommandFailed(format!("spawn_blocking failed: {}", e)))?;
 
-        match result {
+        match crate::utils::sudo_session::run_elevated_shell(&cmd) {
             Ok(output) if output.status.success() => {
                 log::info!("Self-update: replaced app bundle with sudo");
             }
@@ -289,8 +408,8 @@ pub async fn execute_self_update(
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 // Rollback
                 if backup.exists() {
-                    let _ = std::fs::remove_dir_all(&old_app);
-                    let _ = std::fs::rename(&backup, &old_app);
+                    let _ = std::fs::remove_dir_all(old_app);
+                    let _ = std::fs::rename(&backup, old_app);
                 }
                 return Err(AppError::CommandFailed(format!(
                     "Elevated replacement failed: {}",
@@ -300,8 +419,8 @@ pub async fn execute_self_update(
             Err(e) => {
                 // Rollback
                 if backup.exists() {
-                    let _ = std::fs::remove_dir_all(&old_app);
-                    let _ = std::fs::rename(&backup, &old_app);
+                    let _ = std::fs::remove_dir_all(old_app);
+                    let _ = std::fs::rename(&backup, old_app);
                 }
                 return Err(AppError::CommandFailed(format!(
                     "Elevated replacement failed: {}",
@@ -311,7 +430,7 @@ pub async fn execute_self_update(
         }
     } else {
         // Non-sudo path
-        std::fs::rename(&old_app, &backup)
+        std::fs::rename(old_app, &backup)
             .map_err(|e| AppError::CommandFailed(format!("Failed to backup old app: {}", e)))?;
 
         let new_app_str = new_app.to_string_lossy().to_string();
@@ -334,8 +453,8 @@ pub async fn execute_self_update(
             }
             _ => {
                 // Rollback
-                let _ = std::fs::remove_dir_all(&old_app);
-                let _ = std::fs::rename(&backup, &old_app);
+                let _ = std::fs::remove_dir_all(old_app);
+                let _ = std::fs::rename(&backup, old_app);
                 return Err(AppError::CommandFailed(
                     "Failed to copy new app bundle".to_string(),
                 ));
@@ -352,8 +471,90 @@ pub async fn execute_self_update(
         ));
     }
 
-    // Clean up temp dir
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn execute_self_update(
+    download_url: String,
+    expected_version: Option<String>,
+    app_handle: AppHandle,
+) -> Result<(), AppError> {
+    if let Some(result) = try_signed_update(&app_handle, expected_version.as_deref()).await {
+        result?;
+        log::info!("Self-update: installed via signed updater");
+        emit_progress(&app_handle, "Update installed — restart to apply", 100, None, None);
+        let _ = app_handle.emit("self-update-complete", serde_json::json!({ "success": true }));
+        return Ok(());
+    }
+
+    let app_bundle = current_app_bundle()?;
+    let app_path_str = app_bundle.to_string_lossy().to_string();
+
+    emit_progress(&app_handle, "Preparing update...", 2, None, None);
+
+    // A silently staged copy of the same version can be installed without downloading
+    let staged = match expected_version.as_deref() {
+        Some(v) => {
+            let (version, bundle) = (v.trim_start_matches('v').to_string(), app_bundle.clone());
+            tokio::task::spawn_blocking(move || verified_staged_update(&version, &bundle))
+                .await
+                .ok()
+                .flatten()
+        }
+        None => None,
+    };
+
+    // Create stable temp dir in the download folder
+    let pid = std::process::id();
+    let tmp_dir = crate::utils::staging::staging_dir().join(format!("macplus-update-{}", pid));
+    let new_app_path = match staged {
+        Some(path) => path,
+        None => {
+            if tmp_dir.exists() {
+                let _ = std::fs::remove_dir_all(&tmp_dir);
+            }
+            std::fs::create_dir_all(&tmp_dir)
+                .map_err(|e| AppError::CommandFailed(format!("Failed to create temp dir: {}", e)))?;
+
+            let handle = app_handle.clone();
+            let progress = move |phase: &str, pct: u8, dl: Option<u64>, total: Option<u64>| {
+                emit_progress(&handle, phase, pct, dl, total);
+            };
+            match download_and_extract(&download_url, &app_bundle, &tmp_dir, &progress).await {
+                Ok(path) => path,
+                Err(e) => {
+                    let _ = std::fs::remove_dir_all(&tmp_dir);
+                    return Err(e);
+                }
+            }
+        }
+    };
+
+    emit_progress(&app_handle, "Preparing to install...", 75, None, None);
+
+    // Check write access and pre-authenticate if needed
+    let needs_sudo = !is_writable(&app_bundle);
+    if needs_sudo {
+        emit_progress(&app_handle, "Requesting administrator privileges...", 80, None, None);
+        if !crate::utils::sudo_session::pre_authenticate() {
+            return Err(AppError::CommandFailed(
+                "Update cancelled \u{2014} administrator approval is required".to_string(),
+            ));
+        }
+    }
+
+    emit_progress(&app_handle, "Installing update...", 85, None, None);
+
+    // Replace app bundle inline (no shell script)
+    let (old_app, new_app) = (app_bundle.clone(), new_app_path.clone());
+    tokio::task::spawn_blocking(move || replace_app_bundle(&old_app, &new_app, needs_sudo))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("spawn_blocking failed: {}", e)))??;
+
+    // Clean up temp dir and any staged copy
     let _ = std::fs::remove_dir_all(&tmp_dir);
+    clear_staged_update();
 
     emit_progress(
         &app_handle,
@@ -370,8 +571,291 @@ pub async fn execute_self_update(
     Ok(())
 }
 
+// --- Silent background updates ---
+
+/// The staged update's download and updater signature, kept for re-verification.
+const STAGED_DOWNLOAD: &str = "download";
+const STAGED_SIGNATURE: &str = "download.sig";
+
+/// Where a silently downloaded update waits to be applied on quit: in the download
+/// folder, hidden and named so launch cleanup of `macplus-*` leftovers keeps it.
+fn staged_update_dir() -> PathBuf {
+    crate::utils::staging::staging_dir().join(".macplus-staged-update")
+}
+
+/// The staged `.app` if one exists for `version`.
+fn staged_update(version: &str) -> Option<PathBuf> {
+    let dir = staged_update_dir();
+    let staged_version = std::fs::read_to_string(dir.join("version")).ok()?;
+    let app = dir.join("macPlus.app");
+    (staged_version.trim() == version && app.join("Contents/MacOS/macPlus").exists()).then_some(app)
+}
+
+/// The staged `.app` for `version`, checked again right before it's installed: it must
+/// still be validly signed by the running app's team and, in release builds, the
+/// download it came from must still match its updater signature. A staged copy that
+/// fails either check is discarded.
+fn verified_staged_update(version: &str, app_bundle: &std::path::Path) -> Option<PathBuf> {
+    let staged_app = staged_update(version)?;
+    let dir = staged_update_dir();
+    let verified =
+        verify_update_bundle(app_bundle, &staged_app).and_then(|()| match UPDATER_PUBKEY {
+            Some(pubkey) => verify_staged_download(&dir, pubkey),
+            None => Ok(()),
+        });
+    match verified {
+        Ok(()) => Some(staged_app),
+        Err(e) => {
+            log::warn!("Self-update: discarding staged v{}: {}", version, e);
+            clear_staged_update();
+            None
+        }
+    }
+}
+
+/// Check the staged download against the updater signature saved next to it.
+fn verify_staged_download(dir: &std::path::Path, pubkey: &str) -> Result<(), String> {
+    let body = std::fs::read(dir.join(STAGED_DOWNLOAD))
+        .map_err(|e| format!("staged download unreadable: {}", e))?;
+    let signature = std::fs::read_to_string(dir.join(STAGED_SIGNATURE))
+        .map_err(|e| format!("updater signature unreadable: {}", e))?;
+    codesign::verify_minisign(&body, &signature, pubkey)
+}
+
+/// The manifest the signed updater reads (the endpoint in `tauri.conf.json`).
+const UPDATER_MANIFEST_URL: &str =
+    "https://github.com/smallsimplesuper/macplus/releases/latest/download/latest.json";
+
+/// The updater's `.app.tar.gz` for a release, with the minisign signature CI made for it.
+#[derive(Debug, PartialEq)]
+struct SignedAsset {
+    url: String,
+    signature: String,
+}
+
+/// The signed archive for `arch` in an updater manifest, if the manifest is for
+/// `version`. Only the `.app.tar.gz` is signed in CI, so an asset is only taken
+/// when both its URL and the file its signature names are that archive.
+fn signed_asset(manifest: &serde_json::Value, version: &str, arch: &str) -> Option<SignedAsset> {
+    let manifest_version = manifest.get("version")?.as_str()?;
+    if manifest_version.trim_start_matches('v') != version.trim_start_matches('v') {
+        return None;
+    }
+    let platforms = manifest.get("platforms")?;
+    let platform = [format!("darwin-{}", arch), "darwin-universal".to_string()]
+        .iter()
+        .find_map(|key| platforms.get(key.as_str()))?;
+    let url = platform.get("url")?.as_str()?;
+    let signature = platform.get("signature")?.as_str()?;
+
+    let is_archive = |name: &str| name.ends_with(".app.tar.gz");
+    let signed = signed_file_name(signature)?;
+    (is_archive(url) && is_archive(&signed)).then(|| SignedAsset {
+        url: url.to_string(),
+        signature: signature.to_string(),
+    })
+}
+
+/// The file a base64-encoded minisign signature was made for, from its trusted
+/// comment (`timestamp:1700000000\tfile:macPlus.app.tar.gz`).
+fn signed_file_name(signature_b64: &str) -> Option<String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(signature_b64.trim())
+        .ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let comment = text
+        .lines()
+        .find_map(|line| line.strip_prefix("trusted comment:"))?;
+    comment
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("file:"))
+        .map(String::from)
+}
+
+/// The signed archive for `version` from the updater manifest.
+async fn fetch_signed_asset(version: &str) -> Result<SignedAsset, AppError> {
+    let client = http_client::download_client_builder()
+        .build()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to create HTTP client: {}", e)))?;
+    let response = http_client::send(client.get(UPDATER_MANIFEST_URL))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("Updater manifest request failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::NotFound(format!(
+            "No updater manifest (HTTP {})",
+            response.status()
+        )));
+    }
+    let manifest: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("Invalid updater manifest: {}", e)))?;
+    signed_asset(&manifest, version, std::env::consts::ARCH)
+        .ok_or_else(|| AppError::NotFound(format!("No signed update archive for v{}", version)))
+}
+
+/// Download `url` to `path`, without progress reporting.
+async fn download_file(url: &str, path: &std::path::Path) -> Result<(), AppError> {
+    let client = http_client::download_client_builder()
+        .build()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to create HTTP client: {}", e)))?;
+    let response = http_client::send_download(client.get(url))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("Download failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::CommandFailed(format!(
+            "Download returned HTTP {}",
+            response.status()
+        )));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("Download failed: {}", e)))?;
+    std::fs::write(path, &bytes)?;
+    crate::utils::metrics::record_download(bytes.len() as u64);
+    Ok(())
+}
+
+/// Unpack the updater archive into `work_dir` and verify the bundle inside against
+/// `app_bundle`, like any other download.
+fn extract_signed_archive(
+    archive: &std::path::Path,
+    work_dir: &std::path::Path,
+    app_bundle: &std::path::Path,
+) -> Result<PathBuf, AppError> {
+    let output = Command::new("tar")
+        .current_dir("/tmp")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(work_dir)
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to run tar: {}", e)))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "Failed to unpack update: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let app = std::fs::read_dir(work_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .ok_or_else(|| AppError::NotFound("No app in the update archive".to_string()))?;
+    verify_update_bundle(app_bundle, &app)
+        .map_err(|e| AppError::CommandFailed(format!("Update rejected: {}", e)))?;
+    Ok(app)
+}
+
+fn clear_staged_update() {
+    let _ = std::fs::remove_dir_all(staged_update_dir());
+}
+
+/// Download, verify and stage `info` without touching the installed app. The update is
+/// applied by `apply_staged_update` when macPlus quits or relaunches. Homebrew-managed
+/// installs are left to `brew upgrade`.
+pub async fn stage_self_update(info: &SelfUpdateInfo) -> Result<(), AppError> {
+    if info.can_brew_upgrade || staged_update(&info.available_version).is_some() {
+        return Ok(());
+    }
+    let dir = staged_update_dir();
+
+    clear_staged_update();
+    let result = stage_into(&dir, info).await;
+    if result.is_err() {
+        clear_staged_update();
+    }
+    result
+}
+
+async fn stage_into(dir: &std::path::Path, info: &SelfUpdateInfo) -> Result<(), AppError> {
+    let version = &info.available_version;
+    let work_dir = dir.join("work");
+    std::fs::create_dir_all(&work_dir)?;
+
+    let app_bundle = current_app_bundle()?;
+    let extracted = match UPDATER_PUBKEY {
+        // Release builds stage the signed updater archive and keep it with its
+        // signature, so both can be checked again right before the staged copy
+        // replaces the app
+        Some(pubkey) => {
+            let asset = fetch_signed_asset(version).await?;
+            let archive = work_dir.join("macPlus.app.tar.gz");
+            download_file(&asset.url, &archive).await?;
+            codesign::verify_minisign(&std::fs::read(&archive)?, &asset.signature, pubkey)
+                .map_err(|e| AppError::CommandFailed(format!("Update rejected: {}", e)))?;
+            let (unpack_from, unpack_to) = (archive.clone(), work_dir.clone());
+            let extracted = tokio::task::spawn_blocking(move || {
+                extract_signed_archive(&unpack_from, &unpack_to, &app_bundle)
+            })
+            .await
+            .map_err(|e| AppError::Custom(format!("task join: {}", e)))??;
+            std::fs::rename(&archive, dir.join(STAGED_DOWNLOAD))?;
+            std::fs::write(dir.join(STAGED_SIGNATURE), &asset.signature)?;
+            extracted
+        }
+        None => {
+            let download_url = info
+                .download_url
+                .as_deref()
+                .ok_or_else(|| AppError::NotFound("No download for self-update".to_string()))?;
+            let silent = |_: &str, _: u8, _: Option<u64>, _: Option<u64>| {};
+            download_and_extract(download_url, &app_bundle, &work_dir, &silent).await?
+        }
+    };
+
+    // Move the bundle out of the work dir so the rest of the download can be discarded
+    std::fs::rename(&extracted, dir.join("macPlus.app"))?;
+    let _ = std::fs::remove_dir_all(&work_dir);
+    std::fs::write(dir.join("version"), version)?;
+
+    log::info!("Self-update: staged v{} for install on quit", version);
+    Ok(())
+}
+
+/// Install a staged update in place, once its signatures check out again. Runs while
+/// quitting, so it never prompts for elevation: if the app's folder isn't writable the
+/// staged copy is kept for the regular update flow. Returns true if the bundle was replaced.
+pub fn apply_staged_update() -> bool {
+    let dir = staged_update_dir();
+    let version = match std::fs::read_to_string(dir.join("version")) {
+        Ok(v) => v.trim().to_string(),
+        Err(_) => return false,
+    };
+    if !version_compare::is_newer(env!("CARGO_PKG_VERSION"), &version) {
+        clear_staged_update();
+        return false;
+    }
+    let Ok(app_bundle) = current_app_bundle() else {
+        return false;
+    };
+    if !is_writable(&app_bundle) {
+        log::info!("Self-update: staged v{} needs elevation, leaving for manual install", version);
+        return false;
+    }
+    let Some(staged_app) = verified_staged_update(&version, &app_bundle) else {
+        return false;
+    };
+
+    match replace_app_bundle(&app_bundle, &staged_app, false) {
+        Ok(()) => {
+            log::info!("Self-update: applied staged v{}", version);
+            clear_staged_update();
+            true
+        }
+        Err(e) => {
+            log::warn!("Self-update: failed to apply staged v{}: {}", version, e);
+            false
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn relaunch_self(app_handle: AppHandle) -> Result<(), AppError> {
+    // Pick up a silently staged update so the relaunch lands on the new version
+    let _ = tokio::task::spawn_blocking(apply_staged_update).await;
+
     let exe = std::env::current_exe()
         .map_err(|e| AppError::CommandFailed(format!("Failed to find current executable: {}", e)))?;
     let app_bundle = exe
@@ -425,3 +909,50 @@ pub async fn relaunch_self(app_handle: AppHandle) -> Result<(), AppError> {
     tokio::time::sleep(Duration::from_millis(200)).await;
     std::process::exit(0);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Public key and signatures made with a throwaway key in the format
+    /// `tauri signer` writes, over the body `macPlus update archive`.
+    const TEST_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IEVGQ0RBQjg5Njc0NTIzMDEKUldRQkkwVm5pYXZONzVPNXE1NGh4NU92UDJUencxdzU0Wk9ZRVV3Qis1MVowdkVBSmRCek9lT3cK";
+    const ARCHIVE_SIG: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIHRhdXJpIHNlY3JldCBrZXkKUlVRQkkwVm5pYXZONy9YN05aSWJDL1ZQVlJmaTNjTjkraVQvK0pMSmoyN1MzUEVCU1VFTStUai9zYUF3VmFIUklxWmlhNGVCcWgzQkhpeHVDSVltV0xNTUlGNW1RNkNtYUFjPQp0cnVzdGVkIGNvbW1lbnQ6IHRpbWVzdGFtcDoxNzAwMDAwMDAwCWZpbGU6bWFjUGx1cy5hcHAudGFyLmd6CnhwbUlaOFZiVW1jTFB5ZVlPMnBsdWwxU09ReGJVREpOVThnbHNiQnNVOWZiTVF6YlIxZHp3clB4YWl5azFaNlZnTjhlOXdrK2g5NlRZT1RBRUczQURRPT0K";
+    const DMG_SIG: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIHRhdXJpIHNlY3JldCBrZXkKUlVRQkkwVm5pYXZONy9YN05aSWJDL1ZQVlJmaTNjTjkraVQvK0pMSmoyN1MzUEVCU1VFTStUai9zYUF3VmFIUklxWmlhNGVCcWgzQkhpeHVDSVltV0xNTUlGNW1RNkNtYUFjPQp0cnVzdGVkIGNvbW1lbnQ6IHRpbWVzdGFtcDoxNzAwMDAwMDAwCWZpbGU6bWFjUGx1c18wLjMuMF91bml2ZXJzYWwuZG1nCkR5YlRxTy8vZWhMNmVUUm9aQ3lNQmwwUkJFT2ZFWk5SQW5tdnNQMTQ4S1h0dUg3eTI0Zm5uTDJkN0x2LzRhUFVvOG5xbXNlR2hnYkl5MkJ0NDdBU0NBPT0K";
+
+    fn manifest(url: &str, signature: &str) -> serde_json::Value {
+        serde_json::json!({
+            "version": "0.3.0",
+            "platforms": {
+                "darwin-aarch64": { "url": url, "signature": signature },
+                "darwin-x86_64": { "url": url, "signature": signature }
+            }
+        })
+    }
+
+    #[test]
+    fn stages_the_signed_updater_archive() {
+        let url = "https://github.com/smallsimplesuper/macplus/releases/download/v0.3.0/macPlus_universal.app.tar.gz";
+        let asset = signed_asset(&manifest(url, ARCHIVE_SIG), "v0.3.0", "aarch64").unwrap();
+        assert_eq!(asset.url, url);
+        let signed = signed_file_name(&asset.signature);
+        assert_eq!(signed.as_deref(), Some("macPlus.app.tar.gz"));
+
+        let verify = |body: &[u8]| codesign::verify_minisign(body, &asset.signature, TEST_PUBKEY);
+        assert!(verify(b"macPlus update archive").is_ok());
+        assert!(verify(b"another file").is_err());
+    }
+
+    #[test]
+    fn rejects_assets_the_signature_wasnt_made_for() {
+        let archive = "https://example.com/macPlus_universal.app.tar.gz";
+        let dmg = "https://example.com/macPlus_0.3.0_universal.dmg";
+        let asset = |url: &str, sig: &str, version: &str| {
+            signed_asset(&manifest(url, sig), version, "aarch64")
+        };
+        assert_eq!(asset(dmg, ARCHIVE_SIG, "0.3.0"), None);
+        assert_eq!(asset(archive, DMG_SIG, "0.3.0"), None);
+        assert_eq!(asset(archive, ARCHIVE_SIG, "0.2.0"), None);
+        assert!(asset(archive, ARCHIVE_SIG, "0.3.0").is_some());
+    }
+}
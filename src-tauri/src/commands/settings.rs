@@ -4,7 +4,7 @@ use tauri::State;
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::models::AppSettings;
+use crate::models::{AppSettings, WindowMode};
 use crate::utils::AppError;
 
 #[tauri::command]
@@ -34,31 +34,75 @@ pub async fn update_settings(
     db: State<'_, Arc<Mutex<Database>>>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), AppError> {
-    let json = serde_json::to_string(&settings)
-        .map_err(|e| AppError::Custom(format!("Failed to serialize settings: {}", e)))?;
+    let previous = {
+        let db = db.lock().await;
+        crate::scheduler::load_settings_from_db(&db)
+    };
+    let previous_mode = previous.window_mode;
+
+    // Only check the proxy/CA when they change, so a CA file removed later doesn't
+    // block saving unrelated settings
+    let network_changed = settings.proxy_mode != previous.proxy_mode
+        || settings.proxy_url != previous.proxy_url
+        || settings.custom_ca_path != previous.custom_ca_path;
+    if network_changed {
+        crate::utils::http_client::validate_network_settings(&settings)?;
+    }
+    if settings.download_dir != previous.download_dir {
+        if let Some(dir) = settings.download_dir.as_deref() {
+            crate::utils::staging::validate_download_dir(dir)?;
+        }
+    }
+
+    // Keep the standard window's frame for when it's turned back on
+    if previous_mode == WindowMode::Standard && settings.window_mode != WindowMode::Standard {
+        crate::platform::window_geometry::save_window_geometry(&app_handle).await;
+    }
 
     let update_count = {
         let db = db.lock().await;
-        db.conn.execute(
-            "INSERT INTO settings (key, value, updated_at) VALUES ('app_settings', ?1, datetime('now'))
-             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
-            [&json],
-        )?;
+        db.save_settings(&settings)?;
+        if previous.cache_release_notes && !settings.cache_release_notes {
+            db.clear_cached_release_notes()?;
+        }
+        if previous.collect_metrics && !settings.collect_metrics {
+            db.clear_metrics()?;
+        }
+        if settings.history_retention_days != 0
+            && (previous.history_retention_days == 0
+                || settings.history_retention_days < previous.history_retention_days)
+        {
+            db.prune_history_older_than(settings.history_retention_days)?;
+        }
+        if settings.window_mode != previous_mode {
+            crate::platform::window_mode::apply_window_mode(&app_handle, settings.window_mode);
+        }
         db.get_update_count().unwrap_or(0)
     };
 
-    // Apply tray visibility and tooltip
-    if let Some(tray) = app_handle.tray_by_id("main-tray") {
-        let _ = tray.set_visible(settings.show_menu_bar_icon);
+    crate::utils::http_client::apply_timeout_settings(&settings);
+    crate::utils::staging::apply_staging_settings(&settings);
+    crate::updaters::apply_source_settings(&settings);
+    crate::utils::metrics::apply_metrics_settings(&settings);
+    crate::utils::apply_cask_blocklist(&settings);
+    crate::utils::brew::apply_brew_settings(&settings);
 
-        let tooltip = if settings.show_badge_count && update_count > 0 {
-            format!("macPlus — {} update{}", update_count, if update_count == 1 { "" } else { "s" })
-        } else {
-            "macPlus".to_string()
-        };
-        let _ = tray.set_tooltip(Some(&tooltip));
+    if settings.language != previous.language {
+        crate::utils::i18n::apply_language(&settings);
+        crate::platform::tray_menu::refresh_tray_menu(&app_handle).await;
     }
 
+    // Apply tray visibility, tooltip and icon style
+    crate::platform::tray_badge::update_tray_icon(&app_handle, &settings, update_count);
+
+    crate::platform::dock_badge::set_dock_badge(
+        &app_handle,
+        if settings.show_badge_count { update_count } else { 0 },
+    );
+
+    // Re-register global shortcuts; a conflict is reported after the settings are saved
+    crate::platform::global_shortcuts::apply_global_shortcuts(&app_handle, &settings)?;
+
     Ok(())
 }
 
@@ -6,9 +6,13 @@ use std::process::Command;
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::platform::{icon_extractor, permissions};
+use crate::models::OfflineState;
+use crate::platform::permissions::{self, PermissionKind, PermissionState};
+use crate::platform::{automation, icon_extractor};
+use crate::scheduler::offline;
 use crate::utils::askpass;
 use crate::utils::brew;
+use crate::utils::brew_health;
 use crate::utils::{self, AppError};
 
 // ---------------------------------------------------------------------------
@@ -24,11 +28,22 @@ pub struct ConnectivityStatus {
     pub overall: String,
 }
 
+/// Ping the update sources. When none answer, macPlus switches to offline mode
+/// until they do again.
 #[tauri::command]
 pub async fn check_connectivity(
+    app_handle: tauri::AppHandle,
     http_client: State<'_, reqwest::Client>,
 ) -> Result<ConnectivityStatus, AppError> {
-    Ok(check_connectivity_inner(http_client.inner()).await)
+    let status = check_connectivity_inner(http_client.inner()).await;
+    offline::set_offline(&app_handle, status.overall == "disconnected");
+    Ok(status)
+}
+
+/// Whether macPlus is in offline mode and what it queued in the meantime.
+#[tauri::command]
+pub async fn get_offline_state() -> Result<OfflineState, AppError> {
+    Ok(offline::state())
 }
 
 async fn ping_url(client: &reqwest::Client, url: &str, timeout: std::time::Duration) -> bool {
@@ -48,52 +63,148 @@ pub struct PermissionsStatus {
     pub full_disk_access: bool,
     pub app_management: bool,
     pub notifications: bool,
+    /// Every permission with its state and where to grant it
+    pub matrix: Vec<PermissionEntry>,
 }
 
-#[tauri::command]
-pub async fn get_permissions_status() -> Result<PermissionsStatus, AppError> {
+/// One row of the permissions matrix.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionEntry {
+    pub kind: PermissionKind,
+    /// "granted", "denied" or "unknown"
+    pub state: String,
+    /// Updates fail without it
+    pub required: bool,
+    pub settings_url: String,
+}
+
+impl PermissionsStatus {
+    fn new(
+        automation: PermissionState,
+        full_disk_access: PermissionState,
+        app_management: PermissionState,
+        notifications: bool,
+    ) -> Self {
+        let notifications_state = if notifications {
+            PermissionState::Granted
+        } else {
+            PermissionState::Denied
+        };
+        let matrix = PermissionKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let state = match kind {
+                    PermissionKind::Automation => automation,
+                    PermissionKind::FullDiskAccess => full_disk_access,
+                    PermissionKind::AppManagement => app_management,
+                    PermissionKind::Notifications => notifications_state,
+                };
+                PermissionEntry {
+                    kind,
+                    state: state.as_str().to_string(),
+                    required: kind.required(),
+                    settings_url: kind.settings_url().to_string(),
+                }
+            })
+            .collect();
+        Self {
+            automation: automation.is_granted(),
+            automation_state: automation.as_str().to_string(),
+            full_disk_access: full_disk_access.is_granted(),
+            app_management: app_management.is_granted(),
+            notifications,
+            matrix,
+        }
+    }
+}
+
+/// Passive checks of every permission, run in parallel: TCC database reads and
+/// probes of protected paths, none of which shows a dialog.
+async fn check_permissions() -> PermissionsStatus {
     let (automation_state, full_disk_access, app_management, notifications) = tokio::join!(
         tokio::task::spawn_blocking(permissions::check_automation_passive),
-        tokio::task::spawn_blocking(permissions::has_full_disk_access),
-        tokio::task::spawn_blocking(permissions::has_app_management),
+        tokio::task::spawn_blocking(permissions::full_disk_access_state),
+        tokio::task::spawn_blocking(permissions::app_management_state),
         tokio::task::spawn_blocking(|| permissions::has_notification_permission("com.macplus.app")),
     );
-    let automation_state = automation_state.unwrap_or(permissions::PermissionState::Unknown);
-    let full_disk_access = full_disk_access.unwrap_or(false);
-    let app_management = app_management.unwrap_or(false);
-    let notifications = notifications.unwrap_or(false);
-
-    Ok(PermissionsStatus {
-        automation: automation_state.is_granted(),
-        automation_state: automation_state.as_str().to_string(),
-        full_disk_access,
-        app_management,
-        notifications,
-    })
+    PermissionsStatus::new(
+        automation_state.unwrap_or(PermissionState::Unknown),
+        full_disk_access.unwrap_or(PermissionState::Unknown),
+        app_management.unwrap_or(PermissionState::Unknown),
+        notifications.unwrap_or(false),
+    )
+}
+
+#[tauri::command]
+pub async fn get_permissions_status() -> Result<PermissionsStatus, AppError> {
+    Ok(check_permissions().await)
 }
 
 /// Lightweight passive-only permission check. No dialogs, no connectivity checks,
 /// no Homebrew detection. Used by the PermissionBanner on mount and visibility changes.
 #[tauri::command]
 pub async fn get_permissions_passive() -> Result<PermissionsStatus, AppError> {
-    let (automation_state, full_disk_access, app_management, notifications) = tokio::join!(
-        tokio::task::spawn_blocking(permissions::check_automation_passive),
-        tokio::task::spawn_blocking(permissions::has_full_disk_access),
-        tokio::task::spawn_blocking(permissions::has_app_management),
-        tokio::task::spawn_blocking(|| permissions::has_notification_permission("com.macplus.app")),
-    );
-    let automation_state = automation_state.unwrap_or(permissions::PermissionState::Unknown);
-    let full_disk_access = full_disk_access.unwrap_or(false);
-    let app_management = app_management.unwrap_or(false);
-    let notifications = notifications.unwrap_or(false);
-
-    Ok(PermissionsStatus {
-        automation: automation_state.is_granted(),
-        automation_state: automation_state.as_str().to_string(),
-        full_disk_access,
-        app_management,
-        notifications,
+    Ok(check_permissions().await)
+}
+
+/// An app with a pending update, and whether macPlus may quit it with AppleScript.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationTarget {
+    pub bundle_id: String,
+    pub display_name: String,
+    /// "granted", "denied" or "unknown"; denied apps are quit with SIGTERM
+    pub state: String,
+}
+
+/// Automation of the apps updates may have to quit: those with a pending update.
+async fn automation_targets(
+    db: &Mutex<Database>,
+    request: bool,
+) -> Result<Vec<AutomationTarget>, AppError> {
+    let apps: Vec<(String, String)> = db
+        .lock()
+        .await
+        .get_all_apps()?
+        .into_iter()
+        .filter(|app| app.has_update && !app.is_ignored)
+        .map(|app| (app.bundle_id, app.display_name))
+        .collect();
+    tokio::task::spawn_blocking(move || {
+        apps.into_iter()
+            .map(|(bundle_id, display_name)| {
+                let state = if request {
+                    automation::request(&bundle_id)
+                } else {
+                    automation::target_state(&bundle_id)
+                };
+                AutomationTarget {
+                    bundle_id,
+                    display_name,
+                    state: state.as_str().to_string(),
+                }
+            })
+            .collect()
     })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_automation_targets(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<AutomationTarget>, AppError> {
+    automation_targets(&db, false).await
+}
+
+/// Ask for Automation of the running apps with pending updates, one macOS prompt
+/// each, so updates don't stop to ask later.
+#[tauri::command]
+pub async fn request_automation_for_updates(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<AutomationTarget>, AppError> {
+    automation_targets(&db, true).await
 }
 
 /// Intentionally trigger the macOS Automation permission dialog.
@@ -112,13 +223,9 @@ pub async fn trigger_automation_permission() -> Result<bool, AppError> {
 
 #[tauri::command]
 pub async fn open_system_preferences(pane: String) -> Result<(), AppError> {
-    let url = match pane.as_str() {
-        "automation" => "x-apple.systempreferences:com.apple.preference.security?Privacy_Automation",
-        "full_disk_access" => "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles",
-        "app_management" => "x-apple.systempreferences:com.apple.preference.security?Privacy_AppManagement",
-        "notifications" => "x-apple.systempreferences:com.apple.Notifications-Settings.extension",
-        _ => return Err(AppError::CommandFailed(format!("Unknown pane: {}", pane))),
-    };
+    let url = PermissionKind::from_pane(&pane)
+        .ok_or_else(|| AppError::CommandFailed(format!("Unknown pane: {}", pane)))?
+        .settings_url();
 
     Command::new("open")
         .arg(url)
@@ -159,14 +266,54 @@ pub async fn get_app_icon(
     let icons_dir = cache_dir.join("icons");
     std::fs::create_dir_all(&icons_dir)?;
 
-    let result = icon_extractor::extract_icon_png(Path::new(&app_path), &icons_dir)?;
+    let display_name = db
+        .lock()
+        .await
+        .get_app_detail(&bundle_id)
+        .map(|d| d.display_name)
+        .unwrap_or_else(|_| bundle_id.clone());
+    let icon_path =
+        icon_extractor::icon_or_monogram(Path::new(&app_path), &bundle_id, &display_name, &icons_dir)?;
+
+    let db_guard = db.lock().await;
+    let _ = db_guard.update_icon_cache_path(&bundle_id, &icon_path);
+
+    Ok(Some(icon_path))
+}
 
-    if let Some(ref icon_path) = result {
-        let db_guard = db.lock().await;
-        let _ = db_guard.update_icon_cache_path(&bundle_id, icon_path);
+/// Drop an app's cached icon and extract it again, e.g. after the app changed
+/// its icon or a monogram was generated while the bundle was broken.
+#[tauri::command]
+pub async fn refresh_app_icon(
+    bundle_id: String,
+    app_handle: tauri::AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<String, AppError> {
+    let detail = db.lock().await.get_app_detail(&bundle_id)?;
+    let cache_dir = app_handle
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+    let icons_dir = cache_dir.join("icons");
+    std::fs::create_dir_all(&icons_dir)?;
+    let _ = std::fs::remove_file(icons_dir.join(format!("{}.png", bundle_id)));
+    if let Some(ref old) = detail.icon_cache_path {
+        let _ = std::fs::remove_file(old);
     }
 
-    Ok(result)
+    let icon_path = tokio::task::spawn_blocking(move || {
+        icon_extractor::icon_or_monogram(
+            Path::new(&detail.app_path),
+            &detail.bundle_id,
+            &detail.display_name,
+            &icons_dir,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Custom(e.to_string()))??;
+
+    db.lock().await.update_icon_cache_path(&bundle_id, &icon_path)?;
+    Ok(icon_path)
 }
 
 // ---------------------------------------------------------------------------
@@ -230,7 +377,7 @@ pub async fn check_setup_status(
 
     let result = tokio::time::timeout(timeout_dur, async {
         // Run independent checks in parallel
-        let (brew_result, automation_state, xcode, fda, app_mgmt, notif, connectivity) = tokio::join!(
+        let (brew_result, xcode, permissions, connectivity) = tokio::join!(
             // Homebrew: version + path (blocking shell call)
             tokio::task::spawn_blocking(|| {
                 let brew_installed = brew::brew_path().is_some();
@@ -242,28 +389,16 @@ pub async fn check_setup_status(
                 let brew_path_str = brew::brew_path().map(|p| p.display().to_string());
                 (brew_installed, brew_version, brew_path_str)
             }),
-            // Automation permission (passive TCC.db read — no dialog)
-            tokio::task::spawn_blocking(permissions::check_automation_passive),
             // Xcode CLT (blocking shell call)
             tokio::task::spawn_blocking(utils::is_xcode_clt_installed),
-            // Full Disk Access (subprocess check)
-            tokio::task::spawn_blocking(permissions::has_full_disk_access),
-            // App Management (subprocess check)
-            tokio::task::spawn_blocking(permissions::has_app_management),
-            // Notification permission (blocking plist check)
-            tokio::task::spawn_blocking(|| {
-                permissions::has_notification_permission("com.macplus.app")
-            }),
+            // Automation, Full Disk Access, App Management, notifications (passive)
+            check_permissions(),
             // Connectivity (async HTTP pings)
             check_connectivity_inner(&client),
         );
 
         let (brew_installed, brew_version, brew_path_str) = brew_result.unwrap_or((false, None, None));
-        let automation_state = automation_state.unwrap_or(permissions::PermissionState::Unknown);
         let xcode_clt = xcode.unwrap_or(false);
-        let fda = fda.unwrap_or(false);
-        let app_mgmt = app_mgmt.unwrap_or(false);
-        let notifications = notif.unwrap_or(false);
 
         let ap_installed = askpass::is_askpass_installed();
         let ap_path = askpass::askpass_path().map(|p| p.display().to_string());
@@ -275,13 +410,7 @@ pub async fn check_setup_status(
             askpass_installed: ap_installed,
             askpass_path: ap_path,
             xcode_clt_installed: xcode_clt,
-            permissions: PermissionsStatus {
-                automation: automation_state.is_granted(),
-                automation_state: automation_state.as_str().to_string(),
-                full_disk_access: fda,
-                app_management: app_mgmt,
-                notifications,
-            },
+            permissions,
             connectivity,
         }
     })
@@ -294,7 +423,7 @@ pub async fn check_setup_status(
 }
 
 /// Internal connectivity check reusable by both `check_connectivity` and `check_setup_status`.
-async fn check_connectivity_inner(client: &reqwest::Client) -> ConnectivityStatus {
+pub(crate) async fn check_connectivity_inner(client: &reqwest::Client) -> ConnectivityStatus {
     let timeout = std::time::Duration::from_secs(3);
     let (github, homebrew, itunes) = tokio::join!(
         ping_url(client, "https://api.github.com", timeout),
@@ -311,6 +440,26 @@ async fn check_connectivity_inner(client: &reqwest::Client) -> ConnectivityStatu
     ConnectivityStatus { github, homebrew, itunes, overall }
 }
 
+// ---------------------------------------------------------------------------
+// Homebrew health
+// ---------------------------------------------------------------------------
+
+/// Run `brew doctor` and `brew config` and check the command line tools and
+/// folder permissions, for when Homebrew updates keep failing.
+#[tauri::command]
+pub async fn check_homebrew_health() -> Result<brew_health::HomebrewHealth, AppError> {
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(90),
+        tokio::task::spawn_blocking(|| brew_health::check(true)),
+    )
+    .await;
+
+    match result {
+        Ok(health) => health.map_err(|e| AppError::Custom(e.to_string())),
+        Err(_) => Err(AppError::Custom("Homebrew health check timed out".to_string())),
+    }
+}
+
 #[tauri::command]
 pub async fn ensure_askpass_helper(
     app_handle: tauri::AppHandle,
@@ -331,6 +480,7 @@ pub async fn ensure_askpass_helper(
     Ok(askpass::askpass_path().map(|p| p.display().to_string()))
 }
 
+/// Run a command in a new Terminal window, e.g. the fix command of a failed update.
 #[tauri::command]
 pub async fn open_terminal_with_command(command: String) -> Result<(), AppError> {
     let script = format!(
@@ -338,7 +488,7 @@ pub async fn open_terminal_with_command(command: String) -> Result<(), AppError>
     activate
     do script "{}"
 end tell"#,
-        command.replace('"', "\\\"")
+        command.replace('\\', "\\\\").replace('"', "\\\"")
     );
 
     Command::new("osascript")
@@ -1,59 +1,36 @@
-use std::path::Path;
-use std::process::Command;
-use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::models::{AssociatedFile, AssociatedFiles, UninstallProgress, UninstallResult};
+use crate::platform::login_items::{self, LoginItem};
+use crate::platform::system_extensions::{self, SystemExtensionInfo};
+use crate::models::{
+    AssociatedFile, AssociatedFiles, LeftoverFile, LeftoverFiles, OrphanFormula, OrphanFormulae,
+    RemovalMethod, TrashedItem, UninstallProgress, UninstallResult,
+};
 use crate::utils::brew::{brew_command, brew_path};
 use crate::utils::sudo_session::run_elevated_shell;
+use crate::utils::trash::{move_to_trash, move_to_trash_elevated, trash_item};
+use crate::utils::i18n::{tr, tr_args};
 use crate::utils::AppError;
 
 fn emit_uninstall_progress(app: &AppHandle, phase: &str, percent: u8) {
     let _ = app.emit(
         "uninstall-progress",
         UninstallProgress {
-            phase: phase.to_string(),
+            phase: tr(phase),
             percent,
         },
     );
 }
 
-/// Move a path to Trash via Finder AppleScript (reversible).
-fn move_to_trash(path: &str) -> Result<(), String> {
-    let output = Command::new("osascript")
-        .current_dir("/tmp")
-        .args([
-            "-e",
-            &format!(
-                "tell application \"Finder\" to move POSIX file \"{}\" to trash",
-                path.replace('\\', "\\\\").replace('"', "\\\"")
-            ),
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run osascript: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Finder trash failed: {}", stderr.trim()))
-    }
-}
-
-/// Move a path to Trash with elevated privileges.
-fn move_to_trash_elevated(path: &str) -> Result<(), String> {
-    let script = format!(
-        "tell application \"Finder\" to move POSIX file \"{}\" to trash",
-        path.replace('\\', "\\\\").replace('"', "\\\"")
-    );
-    let shell_cmd = format!("osascript -e '{}'", script.replace('\'', "'\\''"));
-    run_elevated_shell(&shell_cmd)
-        .map_err(|e| format!("Elevated trash failed: {}", e))?;
-    Ok(())
-}
-
 /// Check if an app is currently running by its app path.
 fn is_app_running(app_path: &str) -> bool {
     let output = Command::new("pgrep")
@@ -66,36 +43,95 @@ fn is_app_running(app_path: &str) -> bool {
     }
 }
 
+/// Directory sizes are cached for this long, so reopening the uninstall sheet is instant.
+const SIZE_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// `du` is abandoned after this long; huge Containers are reported as unknown (0).
+const DU_TIMEOUT: Duration = Duration::from_secs(15);
+
+fn size_cache() -> &'static std::sync::Mutex<HashMap<PathBuf, (u64, Instant)>> {
+    static CACHE: OnceLock<std::sync::Mutex<HashMap<PathBuf, (u64, Instant)>>> = OnceLock::new();
+    CACHE.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
 /// Compute the total size of a directory or file in bytes.
-fn path_size(path: &Path) -> u64 {
+pub(crate) fn path_size(path: &Path) -> u64 {
     if path.is_file() {
         path.metadata().map(|m| m.len()).unwrap_or(0)
     } else if path.is_dir() {
-        walkdir_size(path)
+        if let Ok(cache) = size_cache().lock() {
+            if let Some((size, at)) = cache.get(path) {
+                if at.elapsed() < SIZE_CACHE_TTL {
+                    return *size;
+                }
+            }
+        }
+        match du_size(path) {
+            Some(size) => {
+                if let Ok(mut cache) = size_cache().lock() {
+                    cache.insert(path.to_path_buf(), (size, Instant::now()));
+                }
+                size
+            }
+            None => 0,
+        }
     } else {
         0
     }
 }
 
-fn walkdir_size(dir: &Path) -> u64 {
-    let mut total = 0u64;
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let ft = entry.file_type();
-            if let Ok(ft) = ft {
-                if ft.is_file() {
-                    total += entry.metadata().map(|m| m.len()).unwrap_or(0);
-                } else if ft.is_dir() {
-                    total += walkdir_size(&entry.path());
-                }
+/// Parse the kilobyte total from `du -sk` output (`<kb>\t<path>`).
+fn parse_du_output(output: &str) -> Option<u64> {
+    let kb: u64 = output.split_whitespace().next()?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Disk usage of a directory via `du -sk`, which is far faster than walking it with
+/// `read_dir` for folders with hundreds of thousands of files. Returns `None` if `du`
+/// can't run or doesn't finish within `DU_TIMEOUT`.
+fn du_size(dir: &Path) -> Option<u64> {
+    let mut child = Command::new("du")
+        .arg("-sk")
+        .arg(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let deadline = Instant::now() + DU_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            // du exits non-zero on unreadable subfolders but still prints a total
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                log::warn!("du timed out for {}", dir.display());
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
             }
         }
     }
-    total
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    parse_du_output(&output)
 }
 
-/// Scan ~/Library subdirectories for files associated with a bundle_id/display_name.
-fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedFile> {
+/// Scan ~/Library subdirectories for files associated with a bundle_id/display_name,
+/// including their sizes.
+pub(crate) fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedFile> {
+    list_associated_files(bundle_id, display_name)
+        .into_iter()
+        .map(|mut f| {
+            f.size_bytes = path_size(Path::new(&f.path));
+            f
+        })
+        .collect()
+}
+
+/// Like `find_associated_files`, but without computing sizes (`size_bytes` is 0).
+fn list_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedFile> {
     let home = match dirs::home_dir() {
         Some(h) => h,
         None => return Vec::new(),
@@ -152,10 +188,9 @@ fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedF
         for path_str in paths {
             let path = Path::new(path_str);
             if path.exists() {
-                let size = path_size(path);
                 files.push(AssociatedFile {
                     path: path_str.clone(),
-                    size_bytes: size,
+                    size_bytes: 0,
                     kind: kind.to_string(),
                 });
             }
@@ -171,10 +206,9 @@ fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedF
                 let name = name.to_string_lossy();
                 if name.starts_with(bundle_id) && name.ends_with(".plist") {
                     let full_path = entry.path();
-                    let size = full_path.metadata().map(|m| m.len()).unwrap_or(0);
                     files.push(AssociatedFile {
                         path: full_path.to_string_lossy().to_string(),
-                        size_bytes: size,
+                        size_bytes: 0,
                         kind: "preferences".to_string(),
                     });
                 }
@@ -192,10 +226,9 @@ fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedF
                 let name = name.to_string_lossy();
                 if name.ends_with(&suffix) {
                     let full_path = entry.path();
-                    let size = path_size(&full_path);
                     files.push(AssociatedFile {
                         path: full_path.to_string_lossy().to_string(),
-                        size_bytes: size,
+                        size_bytes: 0,
                         kind: "group_containers".to_string(),
                     });
                 }
@@ -206,6 +239,254 @@ fn find_associated_files(bundle_id: &str, display_name: &str) -> Vec<AssociatedF
     files
 }
 
+/// Directory holding privileged helper tools installed via SMJobBless.
+const PRIVILEGED_HELPER_DIR: &str = "/Library/PrivilegedHelperTools";
+
+/// Background components an app installs outside its bundle and ~/Library data.
+enum BackgroundItem {
+    Launchd(LoginItem),
+    PrivilegedHelper(PathBuf),
+    SystemExtension(SystemExtensionInfo),
+    /// Legacy login item registered with System Events: (name, path)
+    LegacyLoginItem(String, String),
+}
+
+impl BackgroundItem {
+    fn path(&self) -> String {
+        match self {
+            Self::Launchd(item) => item.path.clone(),
+            Self::PrivilegedHelper(path) => path.to_string_lossy().to_string(),
+            Self::SystemExtension(ext) => ext.identifier.clone(),
+            Self::LegacyLoginItem(_, path) => path.clone(),
+        }
+    }
+
+    fn to_associated_file(&self) -> AssociatedFile {
+        let (kind, size_bytes) = match self {
+            Self::Launchd(item) => (item.kind.clone(), path_size(Path::new(&item.path))),
+            Self::PrivilegedHelper(path) => ("privileged_helper".to_string(), path_size(path)),
+            Self::SystemExtension(_) => ("system_extension".to_string(), 0),
+            Self::LegacyLoginItem(..) => ("login_item".to_string(), 0),
+        };
+        AssociatedFile {
+            path: self.path(),
+            size_bytes,
+            kind,
+        }
+    }
+}
+
+/// List login items registered with System Events as `name<TAB>path` lines.
+async fn legacy_login_items() -> Vec<(String, String)> {
+    let script = "tell application \"System Events\"\n\
+        set out to \"\"\n\
+        repeat with li in login items\n\
+        set out to out & (name of li) & tab & (path of li) & linefeed\n\
+        end repeat\n\
+        end tell\n\
+        return out";
+    match crate::utils::command::run_command_with_timeout("osascript", &["-e", script], 5).await {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|l| {
+                let (name, path) = l.split_once('\t')?;
+                Some((name.trim().to_string(), path.trim().to_string()))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Find launch agents/daemons, privileged helpers, system extensions and login items
+/// belonging to an app. Items bundled inside the app itself are excluded — they go
+/// away with the bundle.
+async fn find_background_items(bundle_id: &str, display_name: &str, app_path: &str) -> Vec<BackgroundItem> {
+    let app = vec![(bundle_id.to_string(), display_name.to_string(), app_path.to_string())];
+    let mut items = Vec::new();
+
+    let launchd: Vec<LoginItem> = login_items::list_login_items(&app)
+        .await
+        .into_iter()
+        .filter(|i| i.scope != "app" && i.parent_bundle_id.as_deref() == Some(bundle_id))
+        .collect();
+
+    // Privileged helpers: named after the bundle ID or launched by one of the app's daemons
+    let daemon_programs: HashSet<String> = launchd
+        .iter()
+        .filter_map(|i| i.program.clone())
+        .collect();
+    if let Ok(entries) = std::fs::read_dir(PRIVILEGED_HELPER_DIR) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let owned = name == bundle_id
+                || name.starts_with(&format!("{}.", bundle_id))
+                || daemon_programs.contains(&path.to_string_lossy().to_string());
+            if owned {
+                items.push(BackgroundItem::PrivilegedHelper(path));
+            }
+        }
+    }
+    items.extend(launchd.into_iter().map(BackgroundItem::Launchd));
+
+    let mut extensions = system_extensions::list_system_extensions().await;
+    system_extensions::assign_parents(&mut extensions, &app);
+    items.extend(
+        extensions
+            .into_iter()
+            .filter(|e| e.parent_bundle_id.is_some())
+            .map(BackgroundItem::SystemExtension),
+    );
+
+    let prefix = format!("{}/", app_path);
+    items.extend(
+        legacy_login_items()
+            .await
+            .into_iter()
+            .filter(|(_, path)| path == app_path || path.starts_with(&prefix))
+            .map(|(name, path)| BackgroundItem::LegacyLoginItem(name, path)),
+    );
+
+    items
+}
+
+/// Stop and remove a background item. System extensions are deactivated by macOS
+/// when the owning app is moved to Trash, so they only need the app removal itself.
+fn remove_background_item(item: &BackgroundItem) -> Result<(), String> {
+    match item {
+        BackgroundItem::Launchd(login_item) => {
+            login_items::bootout_login_item(login_item);
+            if login_item.scope == "user" {
+                move_to_trash(&login_item.path)
+            } else {
+                move_to_trash_elevated(&login_item.path)
+            }
+        }
+        BackgroundItem::PrivilegedHelper(path) => {
+            move_to_trash_elevated(&path.to_string_lossy())
+        }
+        BackgroundItem::SystemExtension(ext) => {
+            log::info!(
+                "System extension {} will be deactivated when its app is trashed",
+                ext.identifier
+            );
+            Ok(())
+        }
+        BackgroundItem::LegacyLoginItem(name, _) => {
+            let output = Command::new("osascript")
+                .current_dir("/tmp")
+                .args([
+                    "-e",
+                    &format!(
+                        "tell application \"System Events\" to delete login item \"{}\"",
+                        name.replace('\\', "\\\\").replace('"', "\\\"")
+                    ),
+                ])
+                .output()
+                .map_err(|e| format!("Failed to run osascript: {}", e))?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Failed to remove login item: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ))
+            }
+        }
+    }
+}
+
+/// ~/Library folders scanned for leftovers, with the kind reported for each.
+const LEFTOVER_DIRS: &[(&str, &str)] = &[
+    ("Application Support", "application_support"),
+    ("Caches", "caches"),
+    ("Preferences", "preferences"),
+    ("Containers", "containers"),
+];
+
+/// Whether a file/folder name looks like a reverse-DNS bundle ID (e.g. `com.vendor.app`).
+fn looks_like_bundle_id(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() >= 3
+        && parts.iter().all(|p| {
+            !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// A bundle ID is owned if an installed app has the same ID, or one is a
+/// dot-separated prefix of the other (helpers, `.mac` suffixed variants).
+fn is_owned_bundle_id(candidate: &str, installed: &HashSet<String>) -> bool {
+    let candidate = candidate.to_lowercase();
+    if installed.contains(&candidate) {
+        return true;
+    }
+    installed.iter().any(|bid| {
+        candidate.starts_with(&format!("{}.", bid)) || bid.starts_with(&format!("{}.", candidate))
+    })
+}
+
+/// Walk ~/Library support folders for items named after bundle IDs that no installed
+/// app owns — `find_associated_files` inverted.
+fn find_leftover_files(installed: &HashSet<String>) -> Vec<LeftoverFile> {
+    let library = match dirs::home_dir() {
+        Some(h) => h.join("Library"),
+        None => return Vec::new(),
+    };
+
+    let mut leftovers = Vec::new();
+    for (dir, kind) in LEFTOVER_DIRS {
+        let entries = match std::fs::read_dir(library.join(dir)) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let candidate = if *kind == "preferences" {
+                match name.strip_suffix(".plist") {
+                    Some(stem) => stem.to_string(),
+                    None => continue,
+                }
+            } else {
+                name.clone()
+            };
+
+            if candidate.starts_with("com.apple.")
+                || candidate.starts_with("com.macplus.")
+                || !looks_like_bundle_id(&candidate)
+                || is_owned_bundle_id(&candidate, installed)
+            {
+                continue;
+            }
+
+            let path = entry.path();
+            leftovers.push(LeftoverFile {
+                path: path.to_string_lossy().to_string(),
+                bundle_id: candidate,
+                size_bytes: path_size(&path),
+                kind: kind.to_string(),
+            });
+        }
+    }
+
+    leftovers.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    leftovers
+}
+
+/// Only direct children of the scanned ~/Library folders may be trashed as leftovers.
+fn is_leftover_location(path: &Path) -> bool {
+    let library = match dirs::home_dir() {
+        Some(h) => h.join("Library"),
+        None => return false,
+    };
+    let parent = match path.parent() {
+        Some(p) => p,
+        None => return false,
+    };
+    LEFTOVER_DIRS
+        .iter()
+        .any(|(dir, _)| parent == library.join(dir).as_path())
+}
+
 /// Uninstall an app by bundle_id.
 fn uninstall_homebrew_cask(token: &str) -> Result<String, String> {
     let brew = brew_path().ok_or("Homebrew not found")?;
@@ -297,19 +578,160 @@ fn uninstall_homebrew_formula(name: &str) -> Result<String, String> {
     Err(format!("brew uninstall failed: {}", force_stderr.trim()))
 }
 
+/// Expand a cask `zap` path to the existing paths it names. `~` is the home folder and
+/// `*` globs are matched within the final path component only. Shallow paths (the home
+/// folder, ~/Library and the folders directly in it, top-level folders) are never returned.
+fn expand_zap_path(pattern: &str) -> Vec<PathBuf> {
+    let expanded = match pattern.strip_prefix("~/") {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest),
+            None => return Vec::new(),
+        },
+        None => PathBuf::from(pattern),
+    };
+    let (parent, name) = match (expanded.parent(), expanded.file_name().and_then(|n| n.to_str())) {
+        (Some(p), Some(n)) => (p.to_path_buf(), n.to_string()),
+        _ => return Vec::new(),
+    };
+    if !expanded.is_absolute() || parent.to_string_lossy().contains('*') {
+        return Vec::new();
+    }
+    let home = dirs::home_dir();
+    let home_depth = home.as_ref().map_or(0, |h| h.components().count());
+    let in_library = home.is_some_and(|h| parent == h.join("Library"));
+    if parent.components().count() <= home_depth.max(2) || in_library {
+        return Vec::new();
+    }
+
+    if !name.contains('*') {
+        return if expanded.exists() { vec![expanded] } else { Vec::new() };
+    }
+
+    let re = format!(
+        "^{}$",
+        name.split('*').map(regex::escape).collect::<Vec<_>>().join(".*")
+    );
+    let re = match regex::Regex::new(&re) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+    std::fs::read_dir(&parent)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_str().map_or(false, |n| re.is_match(n)))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a folder holds nothing but Finder's `.DS_Store`.
+fn is_empty_folder(path: &Path) -> bool {
+    let only_ds_store = |mut entries: std::fs::ReadDir| {
+        entries.all(|e| e.is_ok_and(|e| e.file_name() == ".DS_Store"))
+    };
+    path.is_dir() && std::fs::read_dir(path).is_ok_and(only_ds_store)
+}
+
+/// Files named by the cask's Homebrew `zap` stanza that exist on disk. Folders
+/// the stanza only `rmdir`s are included while they're empty.
+async fn find_zap_files(app_handle: &AppHandle, token: &str) -> Vec<AssociatedFile> {
+    let client = app_handle.state::<reqwest::Client>().inner().clone();
+    let zap = crate::updaters::homebrew_api::fetch_cask_zap_paths(&client, token).await;
+    if zap.trash.is_empty() && zap.rmdir.is_empty() {
+        return Vec::new();
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut seen = HashSet::new();
+        let empty_folders = zap
+            .rmdir
+            .iter()
+            .flat_map(|p| expand_zap_path(p))
+            .filter(|p| is_empty_folder(p));
+        zap.trash
+            .iter()
+            .flat_map(|p| expand_zap_path(p))
+            .chain(empty_folders)
+            .filter(|p| seen.insert(p.clone()))
+            .map(|p| AssociatedFile {
+                size_bytes: path_size(&p),
+                path: p.to_string_lossy().to_string(),
+                kind: "zap".to_string(),
+            })
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
 #[tauri::command]
-pub async fn scan_associated_files(bundle_id: String) -> Result<AssociatedFiles, AppError> {
+pub async fn scan_associated_files(
+    app_handle: AppHandle,
+    bundle_id: String,
+) -> Result<AssociatedFiles, AppError> {
+    let detail = {
+        let db = app_handle.state::<Arc<Mutex<Database>>>();
+        let db_guard = db.lock().await;
+        db_guard.get_app_detail(&bundle_id).ok()
+    };
     // Use bundle_id's last component as fallback display_name
-    let display_name = bundle_id
-        .rsplit('.')
-        .next()
-        .unwrap_or(&bundle_id)
-        .to_string();
-
-    let files =
-        tokio::task::spawn_blocking(move || find_associated_files(&bundle_id, &display_name))
-            .await
-            .map_err(|e| AppError::Custom(format!("Scan task failed: {}", e)))?;
+    let (display_name, app_path, cask_token) = match detail {
+        Some(d) => (d.display_name, d.app_path, d.homebrew_cask_token),
+        None => (
+            bundle_id.rsplit('.').next().unwrap_or(&bundle_id).to_string(),
+            String::new(),
+            None,
+        ),
+    };
+
+    let (bid, dname) = (bundle_id.clone(), display_name.clone());
+    let listed = tokio::task::spawn_blocking(move || list_associated_files(&bid, &dname))
+        .await
+        .map_err(|e| AppError::Custom(format!("Scan task failed: {}", e)))?;
+
+    // Size entries a few at a time, streaming each result so the sheet can fill in
+    // progressively instead of waiting on the largest Container.
+    let mut files: Vec<AssociatedFile> = stream::iter(listed.into_iter().map(|mut file| {
+        let handle = app_handle.clone();
+        let bid = bundle_id.clone();
+        async move {
+            let path = file.path.clone();
+            file.size_bytes = tokio::task::spawn_blocking(move || path_size(Path::new(&path)))
+                .await
+                .unwrap_or(0);
+            let _ = handle.emit(
+                "associated-file-sized",
+                serde_json::json!({ "bundleId": bid, "file": file }),
+            );
+            file
+        }
+    }))
+    .buffered(4)
+    .collect()
+    .await;
+
+    // Background items (agents, daemons, helpers, extensions, login items) are listed
+    // alongside data files so the UI can confirm each one individually.
+    if !app_path.is_empty() {
+        let background = find_background_items(&bundle_id, &display_name, &app_path).await;
+        let background_files = tokio::task::spawn_blocking(move || {
+            background.iter().map(BackgroundItem::to_associated_file).collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+        files.extend(background_files);
+    }
+
+    // Homebrew's zap stanza lists vendor-curated leftovers the heuristic scan can miss
+    if let Some(token) = cask_token {
+        for zap in find_zap_files(&app_handle, &token).await {
+            if !files.iter().any(|f| f.path == zap.path) {
+                files.push(zap);
+            }
+        }
+    }
 
     let total_size_bytes = files.iter().map(|f| f.size_bytes).sum();
 
@@ -323,14 +745,17 @@ pub async fn scan_associated_files(bundle_id: String) -> Result<AssociatedFiles,
 pub async fn uninstall_app(
     app_handle: AppHandle,
     bundle_id: String,
-    cleanup_associated: bool,
+    paths: Vec<String>,
+    archive: Option<bool>,
+    archive_support_files: Option<bool>,
 ) -> Result<UninstallResult, AppError> {
     // Safety check: block system apps
     let db = app_handle.state::<Arc<Mutex<Database>>>();
-    let (display_name, app_path, homebrew_cask_token, homebrew_formula_name, icon_cache_path) = {
+    let (app_snapshot, display_name, app_path, homebrew_cask_token, homebrew_formula_name, icon_cache_path) = {
         let db_guard = db.lock().await;
         let detail = db_guard.get_app_detail(&bundle_id)?;
         (
+            detail.clone(),
             detail.display_name,
             detail.app_path,
             detail.homebrew_cask_token,
@@ -347,7 +772,9 @@ pub async fn uninstall_app(
             message: Some("System apps cannot be uninstalled.".to_string()),
             running: false,
             cleaned_paths: Vec::new(),
+            leftover_files: Vec::new(),
             protected: true,
+            removal: None,
         });
     }
 
@@ -359,7 +786,9 @@ pub async fn uninstall_app(
             message: Some("macPlus cannot uninstall itself.".to_string()),
             running: false,
             cleaned_paths: Vec::new(),
+            leftover_files: Vec::new(),
             protected: true,
+            removal: None,
         });
     }
 
@@ -377,38 +806,142 @@ pub async fn uninstall_app(
             message: Some(format!("{} is currently running. Quit it first, then try again.", display_name)),
             running: true,
             cleaned_paths: Vec::new(),
+            leftover_files: Vec::new(),
             protected: false,
+            removal: None,
         });
     }
 
     // Route to uninstall method
     emit_uninstall_progress(&app_handle, "Preparing...", 0);
+
+    // `paths` is the user-confirmed subset of `scan_associated_files` results. Each
+    // entry is re-validated against a fresh scan so only paths we found ourselves
+    // are ever removed.
+    let selected: HashSet<String> = paths.into_iter().collect();
+
+    // Optional zip snapshot before anything is removed; a failed archive aborts the uninstall
+    if archive.unwrap_or(false) {
+        emit_uninstall_progress(&app_handle, "Archiving...", 2);
+        let dir = {
+            let db_guard = db.lock().await;
+            let settings = crate::scheduler::load_settings_from_db(&db_guard);
+            super::archive::archive_dir(settings.archive_dir.as_deref())
+        };
+        let support_paths: Vec<String> = if archive_support_files.unwrap_or(false) {
+            let (bid, dname) = (bundle_id.clone(), display_name.clone());
+            tokio::task::spawn_blocking(move || list_associated_files(&bid, &dname))
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|f| f.path)
+                .filter(|p| selected.contains(p))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let (dname, apath) = (display_name.clone(), app_path.clone());
+        let result = match dir {
+            Some(dir) => tokio::task::spawn_blocking(move || {
+                super::archive::create_archive(&dname, &apath, &support_paths, &dir)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task failed: {}", e))),
+            None => Err("No archive folder available".to_string()),
+        };
+
+        match result {
+            Ok((zip_path, archived)) => {
+                let size = std::fs::metadata(&zip_path).map(|m| m.len()).unwrap_or(0);
+                let db_guard = db.lock().await;
+                db_guard.insert_app_archive(
+                    &bundle_id,
+                    &display_name,
+                    &app_path,
+                    &zip_path.to_string_lossy(),
+                    size,
+                    &archived,
+                )?;
+            }
+            Err(e) => {
+                return Ok(UninstallResult {
+                    bundle_id,
+                    success: false,
+                    message: Some(format!("Archive failed, nothing was removed: {}", e)),
+                    running: false,
+                    cleaned_paths: Vec::new(),
+                    leftover_files: Vec::new(),
+                    protected: false,
+                    removal: None,
+                });
+            }
+        }
+    }
+
+    // Stop and remove confirmed background items first so daemons aren't left
+    // running against a deleted app.
+    let mut cleaned_paths = Vec::new();
+    if !selected.is_empty() {
+        emit_uninstall_progress(&app_handle, "Stopping background items...", 5);
+        let items = find_background_items(&bundle_id, &display_name, &app_path).await;
+        let confirmed = selected.clone();
+        let removed = tokio::task::spawn_blocking(move || {
+            let mut removed = Vec::new();
+            for item in items.iter().filter(|i| confirmed.contains(&i.path())) {
+                match remove_background_item(item) {
+                    Ok(()) => removed.push(item.path()),
+                    Err(e) => log::warn!("Failed to remove background item {}: {}", item.path(), e),
+                }
+            }
+            removed
+        })
+        .await
+        .unwrap_or_default();
+        cleaned_paths.extend(removed);
+    }
+
     emit_uninstall_progress(&app_handle, &format!("Uninstalling {}...", display_name), 20);
 
+    // Homebrew removes files itself; only Finder trash moves can be undone later
+    let removal = if homebrew_cask_token.is_some() || homebrew_formula_name.is_some() {
+        RemovalMethod::Homebrew
+    } else {
+        RemovalMethod::Trash
+    };
     let uninstall_result = if let Some(ref token) = homebrew_cask_token {
         let token = token.clone();
-        tokio::task::spawn_blocking(move || uninstall_homebrew_cask(&token)).await
+        tokio::task::spawn_blocking(move || uninstall_homebrew_cask(&token).map(|m| (m, None))).await
     } else if let Some(ref name) = homebrew_formula_name {
         let name = name.clone();
-        tokio::task::spawn_blocking(move || uninstall_homebrew_formula(&name)).await
+        tokio::task::spawn_blocking(move || uninstall_homebrew_formula(&name).map(|m| (m, None))).await
     } else {
         // Direct / MAS / unknown — move .app to Trash via Finder
         let path = app_path.clone();
         tokio::task::spawn_blocking(move || {
-            match move_to_trash(&path) {
-                Ok(()) => Ok(format!("Moved {} to Trash", path)),
+            match trash_item(&path) {
+                Ok(location) => Ok((format!("Moved {} to Trash", path), location)),
                 Err(_) => {
                     // Retry with elevation
                     move_to_trash_elevated(&path)
-                        .map(|()| format!("Moved {} to Trash (elevated)", path))
+                        .map(|()| (format!("Moved {} to Trash (elevated)", path), None))
                 }
             }
         })
         .await
     };
 
+    let mut trashed_items = Vec::new();
     let (success, message) = match uninstall_result {
-        Ok(Ok(msg)) => (true, Some(msg)),
+        Ok(Ok((msg, location))) => {
+            if let Some(trash_path) = location {
+                trashed_items.push(TrashedItem {
+                    original_path: app_path.clone(),
+                    trash_path,
+                });
+            }
+            (true, Some(msg))
+        }
         Ok(Err(err)) => (false, Some(err)),
         Err(e) => (false, Some(format!("Task failed: {}", e))),
     };
@@ -421,33 +954,67 @@ pub async fn uninstall_app(
     emit_uninstall_progress(&app_handle, &phase_msg, 50);
 
     // Associated file cleanup
-    let mut cleaned_paths = Vec::new();
-    if success && cleanup_associated {
+    if success && !selected.is_empty() {
         emit_uninstall_progress(&app_handle, "Scanning associated files...", 55);
         let bid = bundle_id.clone();
         let dname = display_name.clone();
-        let associated =
-            tokio::task::spawn_blocking(move || find_associated_files(&bid, &dname))
+        let mut associated: Vec<AssociatedFile> =
+            tokio::task::spawn_blocking(move || list_associated_files(&bid, &dname))
                 .await
                 .unwrap_or_default();
+        if let Some(ref token) = homebrew_cask_token {
+            associated.extend(find_zap_files(&app_handle, token).await);
+        }
+        let mut seen = HashSet::new();
+        associated.retain(|f| selected.contains(&f.path) && seen.insert(f.path.clone()));
 
         let file_count = associated.len();
         for (i, file) in associated.iter().enumerate() {
-            let pct = 60 + ((i as u8) * 25 / (file_count.max(1) as u8)).min(25);
+            let pct = 60 + (i * 25 / file_count.max(1)).min(25) as u8;
             let short_path = file.path.rsplit('/').next().unwrap_or(&file.path);
             emit_uninstall_progress(&app_handle, &format!("Cleaning up {}...", short_path), pct);
             let path = file.path.clone();
-            let result = tokio::task::spawn_blocking(move || move_to_trash(&path)).await;
-            if let Ok(Ok(())) = result {
+            let result = tokio::task::spawn_blocking(move || trash_item(&path)).await;
+            if let Ok(Ok(location)) = result {
+                if let Some(trash_path) = location {
+                    trashed_items.push(TrashedItem {
+                        original_path: file.path.clone(),
+                        trash_path,
+                    });
+                }
                 cleaned_paths.push(file.path.clone());
             }
         }
     }
 
+    // Report what's still on disk — Homebrew in particular leaves ~/Library data behind
+    let mut leftover_files = Vec::new();
+    if success {
+        emit_uninstall_progress(&app_handle, "Checking for leftovers...", 87);
+        let (bid, dname) = (bundle_id.clone(), display_name.clone());
+        let cleaned: HashSet<String> = cleaned_paths.iter().cloned().collect();
+        leftover_files = tokio::task::spawn_blocking(move || find_associated_files(&bid, &dname))
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|f| !cleaned.contains(&f.path) && Path::new(&f.path).exists())
+            .collect();
+        if !leftover_files.is_empty() {
+            log::info!(
+                "{} associated item(s) remain after uninstalling {}",
+                leftover_files.len(),
+                bundle_id
+            );
+        }
+    }
+
     // Database cleanup
     emit_uninstall_progress(&app_handle, "Cleaning database...", 90);
     if success {
         let db_guard = db.lock().await;
+        if let Err(e) = db_guard.insert_uninstall_record(&app_snapshot, &trashed_items) {
+            log::warn!("Failed to record uninstall of {}: {}", bundle_id, e);
+        }
         let _ = db_guard.delete_app(&bundle_id);
 
         // Clean up icon cache file
@@ -460,21 +1027,13 @@ pub async fn uninstall_app(
 
     // Native notification
     if success {
-        use tauri_plugin_notification::NotificationExt;
         let db_guard = db.lock().await;
         let settings = crate::scheduler::load_settings_from_db(&db_guard);
         drop(db_guard);
 
-        if settings.notification_on_updates {
-            let mut builder = app_handle
-                .notification()
-                .builder()
-                .title("macPlus")
-                .body(&format!("{} has been uninstalled", display_name));
-            if settings.notification_sound {
-                builder = builder.sound("Glass");
-            }
-            let _ = builder.show();
+        if settings.notification_on_updates && settings.notification_policy.on_uninstall {
+            let body = tr_args("{app} has been uninstalled", &[("app", display_name.as_str())]);
+            crate::scheduler::notifications::send_update_notification(&app_handle, &settings, &body, false);
         }
     }
 
@@ -495,6 +1054,277 @@ pub async fn uninstall_app(
         message,
         running: false,
         cleaned_paths,
+        leftover_files,
         protected: false,
+        removal: success.then_some(removal),
+    })
+}
+
+/// Scan ~/Library for support files whose owning app is no longer installed.
+#[tauri::command]
+pub async fn scan_leftover_files(app_handle: AppHandle) -> Result<LeftoverFiles, AppError> {
+    let installed: HashSet<String> = {
+        let db = app_handle.state::<Arc<Mutex<Database>>>();
+        let db_guard = db.lock().await;
+        db_guard
+            .get_app_paths()?
+            .into_iter()
+            .map(|(bundle_id, _, _)| bundle_id.to_lowercase())
+            .collect()
+    };
+
+    let files = tokio::task::spawn_blocking(move || find_leftover_files(&installed))
+        .await
+        .map_err(|e| AppError::Custom(format!("Scan task failed: {}", e)))?;
+
+    let total_size_bytes = files.iter().map(|f| f.size_bytes).sum();
+    Ok(LeftoverFiles {
+        files,
+        total_size_bytes,
+    })
+}
+
+/// Move the selected leftover paths to Trash. Returns the paths that were trashed.
+#[tauri::command]
+pub async fn trash_leftover_files(paths: Vec<String>) -> Result<Vec<String>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .filter(|p| {
+                let allowed = is_leftover_location(&PathBuf::from(p));
+                if !allowed {
+                    log::warn!("Refusing to trash path outside leftover locations: {}", p);
+                }
+                allowed
+            })
+            .filter(|p| match move_to_trash(p) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Failed to trash {}: {}", p, e);
+                    false
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::Custom(format!("Trash task failed: {}", e)))
+}
+
+/// Formula names from `brew autoremove --dry-run`, which lists them one per line
+/// under a `==> Would autoremove N unneeded formulae:` header.
+fn parse_autoremove_dry_run(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("==> Would autoremove"))
+        .skip(1)
+        .map(str::trim)
+        .take_while(|line| !line.is_empty() && !line.starts_with("==>"))
+        .flat_map(str::split_whitespace)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Formulae installed only as dependencies of formulae that are gone.
+fn find_orphan_formulae() -> Result<Vec<OrphanFormula>, String> {
+    let brew = brew_path().ok_or("Homebrew not found")?;
+    let output = brew_command(brew)
+        .args(["autoremove", "--dry-run"])
+        .output()
+        .map_err(|e| format!("Failed to run brew: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("brew autoremove failed: {}", stderr.trim()));
+    }
+    let names = parse_autoremove_dry_run(&String::from_utf8_lossy(&output.stdout));
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cellar = brew_command(brew)
+        .arg("--cellar")
+        .output()
+        .ok()
+        .map(|out| PathBuf::from(String::from_utf8_lossy(&out.stdout).trim()));
+    Ok(names
+        .into_iter()
+        .map(|name| OrphanFormula {
+            size_bytes: cellar.as_ref().map_or(0, |c| path_size(&c.join(&name))),
+            name,
+        })
+        .collect())
+}
+
+/// List Homebrew formulae nothing installed depends on anymore, with their sizes.
+#[tauri::command]
+pub async fn scan_orphan_formulae() -> Result<OrphanFormulae, AppError> {
+    let formulae = tokio::task::spawn_blocking(find_orphan_formulae)
+        .await
+        .map_err(|e| AppError::Custom(format!("Scan task failed: {}", e)))?
+        .map_err(AppError::Custom)?;
+    let total_size_bytes = formulae.iter().map(|f| f.size_bytes).sum();
+    Ok(OrphanFormulae {
+        formulae,
+        total_size_bytes,
+    })
+}
+
+/// Uninstall the selected orphaned formulae. Names that are no longer orphaned
+/// (something installed since needs them) are skipped. Returns the names removed.
+#[tauri::command]
+pub async fn remove_orphan_formulae(names: Vec<String>) -> Result<Vec<String>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let orphans: HashSet<String> = find_orphan_formulae()?
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        let names: Vec<String> = names
+            .into_iter()
+            .filter(|name| {
+                let orphaned = orphans.contains(name);
+                if !orphaned {
+                    log::warn!("Refusing to remove {}: no longer an orphaned dependency", name);
+                }
+                orphaned
+            })
+            .collect();
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let brew = brew_path().ok_or("Homebrew not found")?;
+        let output = brew_command(brew)
+            .args(["uninstall", "--formula"])
+            .args(&names)
+            .output()
+            .map_err(|e| format!("Failed to run brew: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("brew uninstall failed: {}", stderr.trim()));
+        }
+        let _ = brew_command(brew).arg("cleanup").output();
+        Ok(names)
     })
+    .await
+    .map_err(|e| AppError::Custom(format!("Remove task failed: {}", e)))?
+    .map_err(AppError::Custom)
+}
+
+/// How long after an uninstall `undo_uninstall` is offered.
+const UNDO_WINDOW_HOURS: i64 = 24;
+
+/// Put trashed items back where they came from. Items whose Trash copy is gone
+/// (Trash emptied) or whose original location is occupied again are skipped.
+fn restore_from_trash(items: &[TrashedItem]) -> Vec<String> {
+    let mut restored = Vec::new();
+    for item in items {
+        let src = Path::new(&item.trash_path);
+        let dest = Path::new(&item.original_path);
+        if !src.exists() || dest.exists() {
+            log::warn!("Cannot restore {} from Trash", item.original_path);
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let moved = std::fs::rename(src, dest).is_ok() || {
+            let cmd = format!(
+                "/bin/mv '{}' '{}'",
+                item.trash_path.replace('\'', "'\\''"),
+                item.original_path.replace('\'', "'\\''")
+            );
+            matches!(run_elevated_shell(&cmd), Ok(out) if out.status.success())
+        };
+        if moved {
+            restored.push(item.original_path.clone());
+        }
+    }
+    restored
+}
+
+/// Undo the most recent uninstall of `bundle_id` within the undo window: move the
+/// bundle and cleaned files back out of the Trash and re-register the app row.
+#[tauri::command]
+pub async fn undo_uninstall(
+    app_handle: AppHandle,
+    bundle_id: String,
+) -> Result<Vec<String>, AppError> {
+    let db = app_handle.state::<Arc<Mutex<Database>>>();
+    let record = {
+        let db_guard = db.lock().await;
+        db_guard.get_undoable_uninstall(&bundle_id, UNDO_WINDOW_HOURS)?
+    }
+    .ok_or_else(|| AppError::NotFound(format!("No recent uninstall of {} to undo", bundle_id)))?;
+
+    if record.trashed_items.is_empty() {
+        return Err(AppError::CommandFailed(
+            "Nothing from this uninstall is in the Trash (Homebrew removals can't be undone)".to_string(),
+        ));
+    }
+
+    let items = record.trashed_items.clone();
+    let restored = tokio::task::spawn_blocking(move || restore_from_trash(&items))
+        .await
+        .map_err(|e| AppError::Custom(format!("task join: {}", e)))?;
+    // Leave the record restorable so the undo can be tried again
+    if restored.is_empty() {
+        return Err(AppError::CommandFailed(
+            "Nothing could be put back from the Trash \u{2014} it may have been emptied".to_string(),
+        ));
+    }
+
+    // Re-register the app from the snapshot, refreshed from the restored bundle
+    let app = &record.app;
+    let bundle = crate::detection::bundle_reader::read_bundle(Path::new(&app.app_path));
+    {
+        let db_guard = db.lock().await;
+        if let Some(bundle) = bundle {
+            db_guard.upsert_app(&crate::models::DetectedApp {
+                bundle_id: app.bundle_id.clone(),
+                display_name: app.display_name.clone(),
+                app_path: app.app_path.clone(),
+                installed_version: bundle.installed_version,
+                bundle_version: bundle.bundle_version,
+                install_source: crate::models::AppSource::from_str(&app.install_source),
+                obtained_from: app.obtained_from.clone(),
+                homebrew_cask_token: app.homebrew_cask_token.clone(),
+                architectures: bundle.architectures,
+                sparkle_feed_url: bundle.sparkle_feed_url,
+                mas_app_id: app.mas_app_id.clone(),
+                homebrew_formula_name: app.homebrew_formula_name.clone(),
+                helper_apps: app.helper_apps.clone(),
+            })?;
+        }
+        db_guard.mark_uninstall_undone(record.id)?;
+    }
+
+    let _ = app_handle.emit(
+        "app-restored",
+        serde_json::json!({
+            "bundleId": record.bundle_id,
+            "displayName": app.display_name,
+            "restoredPaths": restored,
+        }),
+    );
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_du_output() {
+        assert_eq!(parse_du_output("2048\t/Users/me/Library/Containers/com.example\n"), Some(2048 * 1024));
+        assert_eq!(parse_du_output(""), None);
+        assert_eq!(parse_du_output("du: cannot access"), None);
+    }
+
+    #[test]
+    fn test_parse_autoremove_dry_run() {
+        let output = "==> Would autoremove 3 unneeded formulae:\nlibyaml\noniguruma\nxz\n";
+        assert_eq!(parse_autoremove_dry_run(output), vec!["libyaml", "oniguruma", "xz"]);
+        assert!(parse_autoremove_dry_run("").is_empty());
+        assert!(parse_autoremove_dry_run("Warning: something\n").is_empty());
+    }
 }
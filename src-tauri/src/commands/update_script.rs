@@ -0,0 +1,404 @@
+//! Pending updates as a shell script, for users who run the changes from
+//! Terminal themselves and use macPlus to see what's outdated, and the
+//! command that retries a failed update in Terminal.
+
+use std::sync::Arc;
+
+use tauri::State;
+use tokio::sync::Mutex;
+
+use super::execute::{brew_job, BrewJob};
+use crate::db::Database;
+use crate::executor::microsoft_autoupdate_executor::{MicrosoftAutoUpdateExecutor, MSUPDATE_PATH};
+use crate::models::{AppDetail, AppSource, UpdateErrorCode, UpdateScriptExport};
+use crate::updaters::adobe_cc::{bundle_to_sap_code, RUM_PATH};
+use crate::utils::command::shell_quote;
+use crate::utils::AppError;
+
+/// How a pending update is installed from a shell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScriptStep {
+    Cask(String),
+    Formula(String),
+    /// App Store ID for `mas upgrade`
+    Mas(String),
+    /// Microsoft AutoUpdate app ID
+    Msupdate(String),
+    /// Adobe SAP code for Remote Update Manager
+    AdobeRum(String),
+    /// No command installs it; what the user has to do instead
+    Manual(String),
+}
+
+#[derive(Debug, Clone)]
+struct ScriptEntry {
+    name: String,
+    from_version: Option<String>,
+    to_version: String,
+    step: ScriptStep,
+}
+
+impl ScriptStep {
+    /// Index into `SECTIONS` and the command argument, for steps a command runs.
+    fn scripted(&self) -> Option<(usize, &str)> {
+        match self {
+            ScriptStep::Cask(token) => Some((0, token)),
+            ScriptStep::Formula(name) => Some((1, name)),
+            ScriptStep::Mas(id) => Some((2, id)),
+            ScriptStep::Msupdate(id) => Some((3, id)),
+            ScriptStep::AdobeRum(code) => Some((4, code)),
+            ScriptStep::Manual(_) => None,
+        }
+    }
+}
+
+/// Script sections, in the order they run.
+const SECTIONS: [&str; 5] = [
+    "Homebrew casks",
+    "Homebrew formulae",
+    "Mac App Store",
+    "Microsoft AutoUpdate",
+    "Adobe Remote Update Manager (asks for your password)",
+];
+
+/// The command of a section, run once for all its updates.
+fn section_command(section: usize, args: &[&str]) -> String {
+    let quoted = || {
+        args.iter()
+            .map(|a| shell_quote(a))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    match section {
+        0 => format!("brew upgrade --cask {}", quoted()),
+        1 => format!("brew upgrade {}", quoted()),
+        2 => format!("mas upgrade {}", quoted()),
+        3 => format!(
+            "{} --install --apps {}",
+            shell_quote(MSUPDATE_PATH),
+            quoted()
+        ),
+        _ => format!(
+            "sudo {} --action=install --productVersions={}",
+            shell_quote(RUM_PATH),
+            shell_quote(&args.join(","))
+        ),
+    }
+}
+
+impl ScriptEntry {
+    fn comment(&self) -> String {
+        comment_text(&format!(
+            "# {} {} \u{2192} {}",
+            self.name,
+            self.from_version.as_deref().unwrap_or("?"),
+            self.to_version
+        ))
+    }
+}
+
+/// `text` with control characters replaced by spaces, so names, versions and
+/// URLs from update feeds can't end a `#` comment and add commands to the script.
+fn comment_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// The command that installs an app's pending update, following the routing of
+/// an update run from macPlus where a CLI exists for it.
+fn script_step(detail: &AppDetail) -> ScriptStep {
+    // Apps Homebrew installed stay with Homebrew, even when macPlus would download them
+    match AppSource::from_str(&detail.install_source) {
+        AppSource::Homebrew if detail.homebrew_cask_token.is_some() => {
+            return ScriptStep::Cask(detail.homebrew_cask_token.clone().unwrap_or_default())
+        }
+        AppSource::HomebrewFormula if detail.homebrew_formula_name.is_some() => {
+            return ScriptStep::Formula(detail.homebrew_formula_name.clone().unwrap_or_default())
+        }
+        _ => {}
+    }
+    match brew_job(detail) {
+        Some(BrewJob::Cask(token)) => return ScriptStep::Cask(token),
+        Some(BrewJob::Formula(name)) => return ScriptStep::Formula(name),
+        None => {}
+    }
+
+    let update = detail.available_update.as_ref();
+    match update.map(|u| u.source_type.as_str()) {
+        Some("microsoft_autoupdate") => {
+            if let Some(id) = MicrosoftAutoUpdateExecutor::msupdate_app_id(&detail.bundle_id) {
+                return ScriptStep::Msupdate(id.to_string());
+            }
+        }
+        Some("adobe_cc") => {
+            if let Some(code) = bundle_to_sap_code(&detail.bundle_id) {
+                return ScriptStep::AdobeRum(code.to_string());
+            }
+        }
+        _ => {}
+    }
+    let from_mas = update.is_some_and(|u| u.source_type == "mas")
+        || AppSource::from_str(&detail.install_source) == AppSource::MacAppStore;
+    if let Some(id) = detail.mas_app_id.clone().filter(|_| from_mas) {
+        return ScriptStep::Mas(id);
+    }
+
+    ScriptStep::Manual(match update.and_then(|u| u.download_url.as_deref()) {
+        Some(url) => format!("download and install {}", url),
+        None => "update it from within the app".to_string(),
+    })
+}
+
+/// The command a "Fix in Terminal" action runs for a failed update, built from
+/// its error code: brew and mas prompt for a password in Terminal where macPlus
+/// couldn't ask, and a stale download is removed before brew fetches it again.
+/// `None` when a command wouldn't help, e.g. after the user cancelled.
+pub(crate) fn fix_command(
+    detail: &AppDetail,
+    code: UpdateErrorCode,
+    message: Option<&str>,
+) -> Option<String> {
+    step_fix_command(&script_step(detail), code, message)
+}
+
+fn step_fix_command(
+    step: &ScriptStep,
+    code: UpdateErrorCode,
+    message: Option<&str>,
+) -> Option<String> {
+    let lower = message.unwrap_or_default().to_lowercase();
+    if lower.contains("xcode-select --install") || lower.contains("command line tools") {
+        return Some("xcode-select --install".to_string());
+    }
+    if !matches!(
+        code,
+        UpdateErrorCode::NeedsElevation
+            | UpdateErrorCode::ChecksumMismatch
+            | UpdateErrorCode::VersionUnchanged
+            | UpdateErrorCode::Unknown
+    ) {
+        return None;
+    }
+
+    let command = match (step, code) {
+        (ScriptStep::Cask(token), UpdateErrorCode::ChecksumMismatch) => format!(
+            "rm -f \"$(brew --cache --cask {0})\" && brew upgrade --cask {0}",
+            shell_quote(token)
+        ),
+        (ScriptStep::Cask(token), UpdateErrorCode::VersionUnchanged) => {
+            format!("brew reinstall --cask {}", shell_quote(token))
+        }
+        (ScriptStep::Formula(name), UpdateErrorCode::ChecksumMismatch) => format!(
+            "rm -f \"$(brew --cache {0})\" && brew upgrade {0}",
+            shell_quote(name)
+        ),
+        (ScriptStep::Formula(name), UpdateErrorCode::VersionUnchanged) => {
+            format!("brew reinstall {}", shell_quote(name))
+        }
+        (ScriptStep::Mas(id), _) if lower.contains("sign in") || lower.contains("signed in") => {
+            format!("mas signin && mas upgrade {}", shell_quote(id))
+        }
+        (ScriptStep::Mas(id), UpdateErrorCode::NeedsElevation) => {
+            format!("sudo mas upgrade {}", shell_quote(id))
+        }
+        _ => {
+            let (section, arg) = step.scripted()?;
+            section_command(section, &[arg])
+        }
+    };
+    Some(command)
+}
+
+/// A POSIX shell script with one command per update mechanism. Updates that
+/// can't be scripted are listed as comments at the end.
+fn render_update_script(entries: &[ScriptEntry], generated_at: &str) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n\
+         # Pending updates exported by macPlus on {}.\n\
+         # Review before running: each command changes installed apps.\n",
+        generated_at
+    );
+
+    for (section, title) in SECTIONS.iter().enumerate() {
+        let selected: Vec<(&ScriptEntry, &str)> = entries
+            .iter()
+            .filter_map(|entry| match entry.step.scripted() {
+                Some((index, arg)) if index == section => Some((entry, arg)),
+                _ => None,
+            })
+            .collect();
+        if selected.is_empty() {
+            continue;
+        }
+        script.push_str(&format!("\n# {}\n", title));
+        for (entry, _) in &selected {
+            script.push_str(&entry.comment());
+            script.push('\n');
+        }
+        let args: Vec<&str> = selected.iter().map(|(_, arg)| *arg).collect();
+        script.push_str(&section_command(section, &args));
+        script.push('\n');
+    }
+
+    let manual: Vec<&ScriptEntry> = entries
+        .iter()
+        .filter(|entry| matches!(entry.step, ScriptStep::Manual(_)))
+        .collect();
+    if !manual.is_empty() {
+        script.push_str("\n# Not scriptable\n");
+        for entry in manual {
+            if let ScriptStep::Manual(ref how) = entry.step {
+                script.push_str(&format!("{}: {}\n", entry.comment(), comment_text(how)));
+            }
+        }
+    }
+    script
+}
+
+/// Write the pending updates the user hasn't ignored to an executable shell
+/// script at `path`.
+#[tauri::command]
+pub async fn export_update_script(
+    path: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<UpdateScriptExport, AppError> {
+    let entries: Vec<ScriptEntry> = {
+        let db = db.lock().await;
+        db.get_all_apps()?
+            .into_iter()
+            .filter(|app| app.has_update && !app.is_ignored)
+            .filter_map(|app| db.get_app_detail(&app.bundle_id).ok())
+            .filter_map(|detail| {
+                let update = detail.available_update.as_ref()?;
+                Some(ScriptEntry {
+                    name: detail.display_name.clone(),
+                    from_version: detail.installed_version.clone(),
+                    to_version: update.available_version.clone(),
+                    step: script_step(&detail),
+                })
+            })
+            .collect()
+    };
+
+    let generated_at = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    tokio::fs::write(&path, render_update_script(&entries, &generated_at)).await?;
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+
+    let manual = entries
+        .iter()
+        .filter(|entry| matches!(entry.step, ScriptStep::Manual(_)))
+        .count();
+    Ok(UpdateScriptExport {
+        scripted: entries.len() - manual,
+        manual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, step: ScriptStep) -> ScriptEntry {
+        ScriptEntry {
+            name: name.to_string(),
+            from_version: Some("1.0".to_string()),
+            to_version: "2.0".to_string(),
+            step,
+        }
+    }
+
+    #[test]
+    fn groups_updates_by_command() {
+        let script = render_update_script(
+            &[
+                entry("Firefox", ScriptStep::Cask("firefox".into())),
+                entry("wget", ScriptStep::Formula("wget".into())),
+                entry("Zoom", ScriptStep::Cask("zoom".into())),
+                entry("Xcode", ScriptStep::Mas("497799835".into())),
+                entry("Word", ScriptStep::Msupdate("MSWD2019".into())),
+                entry("Photoshop", ScriptStep::AdobeRum("PHSP".into())),
+                entry(
+                    "Tool",
+                    ScriptStep::Manual("update it from within the app".into()),
+                ),
+            ],
+            "2026-10-16 09:00",
+        );
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("\nbrew upgrade --cask firefox zoom\n"));
+        assert!(script.contains("\nbrew upgrade wget\n"));
+        assert!(script.contains("\nmas upgrade 497799835\n"));
+        assert!(script.contains(
+            "\n'/Library/Application Support/Microsoft/MAU2.0/Microsoft AutoUpdate.app/Contents/MacOS/msupdate' --install --apps MSWD2019\n"
+        ));
+        assert!(script.contains("--action=install --productVersions=PHSP\n"));
+        assert!(script.contains("# Firefox 1.0 \u{2192} 2.0\n"));
+        assert!(script.contains("# Tool 1.0 \u{2192} 2.0: update it from within the app\n"));
+        assert!(!script.contains("--apps MSWD2019 PHSP"));
+    }
+
+    #[test]
+    fn keeps_feed_text_inside_comments() {
+        let mut tool = entry(
+            "Tool\nrm -rf ~",
+            ScriptStep::Manual("download and install https://example.com/\r\ncurl x | sh".into()),
+        );
+        tool.to_version = "2.0\ntouch /tmp/x".to_string();
+        let script = render_update_script(&[tool], "2026-10-16 09:00");
+        assert!(script
+            .lines()
+            .all(|line| line.is_empty() || line.starts_with('#')));
+        assert!(script.contains("# Tool rm -rf ~ 1.0 \u{2192} 2.0 touch /tmp/x: "));
+    }
+
+    #[test]
+    fn builds_fix_command_from_error_code() {
+        let cask = ScriptStep::Cask("firefox".into());
+        assert_eq!(
+            step_fix_command(&cask, UpdateErrorCode::NeedsElevation, None).as_deref(),
+            Some("brew upgrade --cask firefox")
+        );
+        assert_eq!(
+            step_fix_command(&cask, UpdateErrorCode::ChecksumMismatch, None).as_deref(),
+            Some("rm -f \"$(brew --cache --cask firefox)\" && brew upgrade --cask firefox")
+        );
+        assert_eq!(
+            step_fix_command(&cask, UpdateErrorCode::VersionUnchanged, None).as_deref(),
+            Some("brew reinstall --cask firefox")
+        );
+        assert_eq!(
+            step_fix_command(&cask, UpdateErrorCode::UserCancelled, None),
+            None
+        );
+        assert_eq!(
+            step_fix_command(
+                &ScriptStep::Mas("497799835".into()),
+                UpdateErrorCode::NeedsElevation,
+                None
+            )
+            .as_deref(),
+            Some("sudo mas upgrade 497799835")
+        );
+        assert_eq!(
+            step_fix_command(
+                &ScriptStep::Manual("update it from within the app".into()),
+                UpdateErrorCode::Unknown,
+                None
+            ),
+            None
+        );
+        assert_eq!(
+            step_fix_command(
+                &cask,
+                UpdateErrorCode::Unknown,
+                Some("Xcode Command Line Tools required. Install with: xcode-select --install")
+            )
+            .as_deref(),
+            Some("xcode-select --install")
+        );
+    }
+}
@@ -40,16 +40,22 @@ pub async fn check_single_update(
         .await
         .map(std::sync::Arc::new);
 
-    // Load GitHub mapping for this specific app
-    let github_repo = {
-        let db_guard = db.lock().await;
-        let mappings = db_guard.get_github_mappings();
-        mappings.get(&bundle_id).cloned()
-    };
+    // User mappings win, then the community feed, then what was detected
+    let mapping = db.lock().await.get_app_mappings().remove(&bundle_id).unwrap_or_default();
+    let electron_feed = db.lock().await.get_electron_update_feeds().remove(&bundle_id);
+    let community = crate::updaters::community_mappings::lookup(&bundle_id).unwrap_or_default();
+    let github_repo = mapping.github_repo.or(community.github_repo);
 
     let context = crate::updaters::AppCheckContext {
-        homebrew_cask_token: app.homebrew_cask_token.clone(),
-        sparkle_feed_url: app.sparkle_feed_url.clone(),
+        homebrew_cask_token: mapping
+            .homebrew_cask_token
+            .or(community.cask_token)
+            .or_else(|| app.homebrew_cask_token.clone()),
+        sparkle_feed_url: mapping
+            .custom_feed_url
+            .or(community.appcast_url)
+            .or_else(|| app.sparkle_feed_url.clone()),
+        electron_feed,
         obtained_from: app.obtained_from.clone(),
         brew_outdated: None,
         brew_outdated_formulae: None,
@@ -58,9 +64,10 @@ pub async fn check_single_update(
         homebrew_formula_name: app.homebrew_formula_name.clone(),
         xcode_clt_installed: None,
         db: Some(db.inner().clone()),
+        negative_cache: false,
     };
 
-    let result = dispatcher
+    let mut result = dispatcher
         .check_update(
             &app.bundle_id,
             &app.app_path,
@@ -71,17 +78,32 @@ pub async fn check_single_update(
         )
         .await?;
 
-    if let Some(ref update) = result {
+    if let Some(ref mut update) = result {
+        let installed = app.installed_version.as_deref();
+        if crate::scheduler::is_no_op_update(update, installed, &app.app_path) {
+            return Ok(None);
+        }
+        crate::scheduler::resolve_download_size(db.inner(), http_client.inner(), app.id, update).await;
+        let is_security =
+            crate::updaters::macadmins_feed::is_security_update(&app.bundle_id, update);
         let db_guard = db.lock().await;
+        if !crate::scheduler::load_settings_from_db(&db_guard).cache_release_notes {
+            update.release_notes = None;
+        }
         let _ = db_guard.upsert_available_update(app.id, update);
+        let _ = db_guard.set_update_security(app.id, &update.available_version, is_security);
     }
 
     Ok(result)
 }
 
+/// Run every checker for the app and report what each found. With `feed`, the
+/// app's Sparkle appcast is also fetched and checked item by item, to tell why
+/// it offers no update.
 #[tauri::command]
 pub async fn debug_update_check(
     bundle_id: String,
+    feed: Option<bool>,
     db: State<'_, Arc<Mutex<Database>>>,
     http_client: State<'_, reqwest::Client>,
 ) -> Result<crate::updaters::UpdateCheckDiagnostic, AppError> {
@@ -101,15 +123,22 @@ pub async fn debug_update_check(
         .await
         .map(std::sync::Arc::new);
 
-    let github_repo = {
-        let db_guard = db.lock().await;
-        let mappings = db_guard.get_github_mappings();
-        mappings.get(&bundle_id).cloned()
-    };
+    // User mappings win, then the community feed, then what was detected
+    let mapping = db.lock().await.get_app_mappings().remove(&bundle_id).unwrap_or_default();
+    let electron_feed = db.lock().await.get_electron_update_feeds().remove(&bundle_id);
+    let community = crate::updaters::community_mappings::lookup(&bundle_id).unwrap_or_default();
+    let github_repo = mapping.github_repo.or(community.github_repo);
 
     let context = crate::updaters::AppCheckContext {
-        homebrew_cask_token: app.homebrew_cask_token.clone(),
-        sparkle_feed_url: app.sparkle_feed_url.clone(),
+        homebrew_cask_token: mapping
+            .homebrew_cask_token
+            .or(community.cask_token)
+            .or_else(|| app.homebrew_cask_token.clone()),
+        sparkle_feed_url: mapping
+            .custom_feed_url
+            .or(community.appcast_url)
+            .or_else(|| app.sparkle_feed_url.clone()),
+        electron_feed,
         obtained_from: app.obtained_from.clone(),
         brew_outdated: None,
         brew_outdated_formulae: None,
@@ -118,6 +147,7 @@ pub async fn debug_update_check(
         homebrew_formula_name: app.homebrew_formula_name.clone(),
         xcode_clt_installed: None,
         db: Some(db.inner().clone()),
+        negative_cache: false,
     };
 
     let checkers_tried = dispatcher
@@ -131,6 +161,29 @@ pub async fn debug_update_check(
         )
         .await;
 
+    let feed_url = feed
+        .unwrap_or(false)
+        .then(|| {
+            context.sparkle_feed_url.clone().or_else(|| {
+                crate::updaters::sparkle::discover_feed_url(
+                    &app.bundle_id,
+                    std::path::Path::new(&app.app_path),
+                )
+            })
+        })
+        .flatten();
+    let sparkle_feed = match feed_url {
+        Some(url) => Some(
+            crate::updaters::sparkle::diagnose_feed(
+                &url,
+                app.installed_version.as_deref(),
+                http_client.inner(),
+            )
+            .await,
+        ),
+        None => None,
+    };
+
     Ok(crate::updaters::UpdateCheckDiagnostic {
         bundle_id: app.bundle_id.clone(),
         app_path: app.app_path.clone(),
@@ -138,9 +191,26 @@ pub async fn debug_update_check(
         install_source: app.install_source.clone(),
         homebrew_cask_token: app.homebrew_cask_token.clone(),
         checkers_tried,
+        sparkle_feed,
     })
 }
 
+/// How long each update source took and how often it failed, over the checks
+/// run since launch, slowest first.
+#[tauri::command]
+pub async fn get_checker_metrics() -> Result<Vec<crate::updaters::CheckerMetrics>, AppError> {
+    Ok(crate::updaters::checker_metrics())
+}
+
+/// Security releases of macOS this Mac is missing, or `None` when the SOFA
+/// feed couldn't be read.
+#[tauri::command]
+pub async fn get_os_security_status(
+    http_client: State<'_, reqwest::Client>,
+) -> Result<Option<crate::models::OsSecurityStatus>, AppError> {
+    Ok(crate::updaters::macadmins_feed::check_os_security(http_client.inner()).await)
+}
+
 #[tauri::command]
 pub async fn get_update_count(
     db: State<'_, Arc<Mutex<Database>>>,
@@ -149,6 +219,27 @@ pub async fn get_update_count(
     db.get_update_count()
 }
 
+/// When each version of an app was first seen or installed, and which updates
+/// failed, newest first.
+#[tauri::command]
+pub async fn get_app_timeline(
+    bundle_id: String,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<crate::models::AppTimelineEvent>, AppError> {
+    let db = db.lock().await;
+    db.get_app_timeline(&bundle_id)
+}
+
+/// Updates, uninstalls, self-updates and newly found apps, newest first.
+#[tauri::command]
+pub async fn get_recent_activity(
+    limit: Option<i64>,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<crate::models::ActivityEntry>, AppError> {
+    let db = db.lock().await;
+    db.get_recent_activity(limit.unwrap_or(50))
+}
+
 #[tauri::command]
 pub async fn get_update_history(
     limit: Option<i64>,
@@ -181,7 +272,8 @@ pub async fn get_update_history(
                 .prepare(
                     "SELECT h.id, a.bundle_id, a.display_name, a.icon_cache_path,
                             h.from_version, h.to_version, h.source_type,
-                            h.status, h.error_message, h.started_at, h.completed_at
+                            h.status, h.error_message, h.started_at, h.completed_at, h.gatekeeper_verdict,
+                            h.attempts, h.license_accepted
                      FROM update_history h
                      JOIN apps a ON a.id = h.app_id
                      ORDER BY h.started_at DESC
@@ -203,6 +295,9 @@ pub async fn get_update_history(
                         error_message: row.get(8)?,
                         started_at: row.get(9)?,
                         completed_at: row.get(10)?,
+                        gatekeeper_verdict: row.get(11)?,
+                        attempts: row.get(12)?,
+                        license_accepted: row.get(13)?,
                     })
                 })
                 .map_err(|e| AppError::Custom(format!("query: {e}")))?
@@ -0,0 +1,97 @@
+use crate::db::Database;
+use crate::models::{ActivityEntry, ActivityKind};
+use crate::utils::AppResult;
+
+/// Settings key holding the macPlus version of the previous launch.
+const LAST_RUN_VERSION_KEY: &str = "last_run_version";
+
+impl Database {
+    /// Record that a scan found an app that wasn't installed before.
+    pub fn record_app_added(
+        &self,
+        bundle_id: &str,
+        display_name: &str,
+        version: Option<&str>,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO activity_events (kind, bundle_id, display_name, version)
+             VALUES ('app_added', ?1, ?2, ?3)",
+            rusqlite::params![bundle_id, display_name, version],
+        )?;
+        Ok(())
+    }
+
+    /// Record a self-update when macPlus launches at a different version than
+    /// last time. The first launch only remembers the version.
+    pub fn record_launch_version(&self, version: &str) -> AppResult<()> {
+        let last: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                [LAST_RUN_VERSION_KEY],
+                |row| row.get(0),
+            )
+            .ok();
+        if last.as_deref() == Some(version) {
+            return Ok(());
+        }
+        if last.is_some() {
+            self.conn.execute(
+                "INSERT INTO activity_events (kind, display_name, version)
+                 VALUES ('self_updated', 'macPlus', ?1)",
+                [version],
+            )?;
+        }
+        self.conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+            rusqlite::params![LAST_RUN_VERSION_KEY, version],
+        )?;
+        Ok(())
+    }
+
+    /// Finished updates, uninstalls that weren't undone, self-updates and newly
+    /// found apps, newest first.
+    pub fn get_recent_activity(&self, limit: i64) -> AppResult<Vec<ActivityEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT CASE h.status WHEN 'completed' THEN 'updated' ELSE 'update_failed' END,
+                    a.bundle_id, a.display_name, a.icon_cache_path,
+                    h.to_version, h.from_version, h.error_message,
+                    COALESCE(h.completed_at, h.started_at) AS at
+             FROM update_history h
+             JOIN apps a ON a.id = h.app_id
+             WHERE h.status IN ('completed', 'failed')
+             UNION ALL
+             SELECT 'uninstalled', u.bundle_id,
+                    COALESCE(json_extract(u.app_snapshot, '$.displayName'), u.bundle_id),
+                    NULL, json_extract(u.app_snapshot, '$.installedVersion'), NULL, NULL,
+                    u.uninstalled_at
+             FROM uninstall_records u
+             WHERE u.undone_at IS NULL
+             UNION ALL
+             SELECT e.kind, e.bundle_id, e.display_name, a.icon_cache_path,
+                    e.version, NULL, NULL, e.at
+             FROM activity_events e
+             LEFT JOIN apps a ON a.bundle_id = e.bundle_id
+             ORDER BY at DESC
+             LIMIT ?1",
+        )?;
+        let entries = stmt
+            .query_map([limit], |row| {
+                let kind: String = row.get(0)?;
+                Ok(ActivityEntry {
+                    kind: ActivityKind::from_str(&kind),
+                    bundle_id: row.get(1)?,
+                    display_name: row.get(2)?,
+                    icon_cache_path: row.get(3)?,
+                    version: row.get(4)?,
+                    from_version: row.get(5)?,
+                    error_message: row.get(6)?,
+                    at: row.get(7)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(entries)
+    }
+}
@@ -1,14 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::db::Database;
-use crate::models::{AppDetail, AppSummary, AvailableUpdateInfo, DetectedApp, UpdateSourceInfo};
+use crate::models::{
+    license_covers, AppDetail, AppSummary, AvailableUpdateInfo, DetectedApp, DiskUsageEntry,
+    ElectronUpdateFeed, RelaunchPolicy, UnusedApp, UpdateSourceInfo,
+};
 use crate::utils::AppResult;
 
 impl Database {
     pub fn upsert_app(&self, app: &DetectedApp) -> AppResult<i64> {
         self.conn.execute(
-            "INSERT INTO apps (bundle_id, display_name, app_path, installed_version, bundle_version, install_source, obtained_from, homebrew_cask_token, architectures, sparkle_feed_url, mas_app_id, homebrew_formula_name, last_seen_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, datetime('now'))
+            "INSERT INTO apps (bundle_id, display_name, app_path, installed_version, bundle_version, install_source, obtained_from, homebrew_cask_token, architectures, sparkle_feed_url, mas_app_id, homebrew_formula_name, helper_apps, last_seen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, datetime('now'))
              ON CONFLICT(bundle_id) DO UPDATE SET
                 display_name = excluded.display_name,
                 app_path = excluded.app_path,
@@ -21,6 +24,7 @@ impl Database {
                 sparkle_feed_url = COALESCE(excluded.sparkle_feed_url, apps.sparkle_feed_url),
                 mas_app_id = COALESCE(excluded.mas_app_id, apps.mas_app_id),
                 homebrew_formula_name = COALESCE(excluded.homebrew_formula_name, apps.homebrew_formula_name),
+                helper_apps = excluded.helper_apps,
                 last_seen_at = datetime('now')",
             rusqlite::params![
                 app.bundle_id,
@@ -35,6 +39,11 @@ impl Database {
                 app.sparkle_feed_url,
                 app.mas_app_id,
                 app.homebrew_formula_name,
+                if app.helper_apps.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&app.helper_apps).ok()
+                },
             ],
         )?;
 
@@ -43,10 +52,22 @@ impl Database {
             [&app.bundle_id],
             |row| row.get(0),
         )?;
+        if let Some(ref version) = app.installed_version {
+            self.record_version_seen(id, version)?;
+        }
 
         Ok(id)
     }
 
+    /// Note that `version` of an app is installed, keeping the time it was first seen.
+    fn record_version_seen(&self, app_id: i64, version: &str) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO app_versions (app_id, version) VALUES (?1, ?2)",
+            rusqlite::params![app_id, version],
+        )?;
+        Ok(())
+    }
+
     pub fn get_all_apps(&self) -> AppResult<Vec<AppSummary>> {
         let mut stmt = self.conn.prepare(
             "SELECT a.id, a.bundle_id, a.display_name, a.app_path, a.installed_version,
@@ -55,7 +76,8 @@ impl Database {
                     a.homebrew_cask_token, a.sparkle_feed_url, a.obtained_from,
                     a.homebrew_formula_name,
                     au.release_notes, au.release_notes_url, au.notes,
-                    a.description
+                    a.description, au.download_size, a.last_used_at,
+                    au.is_paid_upgrade, a.licensed_version, au.is_security
              FROM apps a
              LEFT JOIN (
                  SELECT au1.* FROM available_updates au1
@@ -94,6 +116,14 @@ impl Database {
                     release_notes_url: row.get(15)?,
                     update_notes: row.get(16)?,
                     description: row.get(17)?,
+                    download_size: row.get::<_, Option<i64>>(18)?.map(|n| n.max(0) as u64),
+                    last_used_at: row.get(19)?,
+                    needs_license: row.get::<_, Option<i32>>(20)?.unwrap_or(0) != 0
+                        && !row
+                            .get::<_, Option<String>>(21)?
+                            .zip(row.get::<_, Option<String>>(8)?)
+                            .is_some_and(|(licensed, available)| license_covers(&licensed, &available)),
+                    is_security_update: row.get::<_, Option<i32>>(22)?.unwrap_or(0) != 0,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -107,7 +137,7 @@ impl Database {
             "SELECT id, bundle_id, display_name, app_path, installed_version, bundle_version,
                     icon_cache_path, architectures, install_source, obtained_from,
                     homebrew_cask_token, is_ignored, first_seen_at, last_seen_at, mas_app_id,
-                    homebrew_formula_name, description
+                    homebrew_formula_name, description, helper_apps, relaunch_policy
              FROM apps WHERE bundle_id = ?1",
             [bundle_id],
             |row| {
@@ -132,6 +162,12 @@ impl Database {
                     description: row.get(16)?,
                     update_sources: Vec::new(),
                     available_update: None,
+                    helper_apps: row
+                        .get::<_, Option<String>>(17)?
+                        .and_then(|j| serde_json::from_str(&j).ok())
+                        .unwrap_or_default(),
+                    relaunch_policy: RelaunchPolicy::from_str(&row.get::<_, String>(18)?),
+                    location_issue: None,
                 })
             },
         )?;
@@ -155,15 +191,21 @@ impl Database {
         let available_update: Option<AvailableUpdateInfo> = self
             .conn
             .query_row(
-                "SELECT available_version, source_type, release_notes_url, download_url,
-                        release_notes, is_paid_upgrade, detected_at, notes
-                 FROM available_updates
-                 WHERE app_id = ?1 AND dismissed_at IS NULL
-                 ORDER BY detected_at DESC LIMIT 1",
+                "SELECT au.available_version, au.source_type, au.release_notes_url, au.download_url,
+                        au.release_notes, au.is_paid_upgrade, au.detected_at, au.notes,
+                        au.download_size, a.licensed_version
+                 FROM available_updates au
+                 JOIN apps a ON a.id = au.app_id
+                 WHERE au.app_id = ?1 AND au.dismissed_at IS NULL
+                 ORDER BY au.detected_at DESC LIMIT 1",
                 [app.id],
                 |row| {
+                    let available_version: String = row.get(0)?;
+                    let licensed_version: Option<String> = row.get(9)?;
                     Ok(AvailableUpdateInfo {
-                        available_version: row.get(0)?,
+                        license_confirmed: licensed_version
+                            .is_some_and(|licensed| license_covers(&licensed, &available_version)),
+                        available_version,
                         source_type: row.get(1)?,
                         release_notes_url: row.get(2)?,
                         download_url: row.get(3)?,
@@ -171,6 +213,7 @@ impl Database {
                         is_paid_upgrade: row.get::<_, i32>(5)? != 0,
                         detected_at: row.get(6)?,
                         notes: row.get(7)?,
+                        download_size: row.get::<_, Option<i64>>(8)?.map(|n| n.max(0) as u64),
                     })
                 },
             )
@@ -183,6 +226,15 @@ impl Database {
         })
     }
 
+    /// Record that the user owns a license covering the paid upgrade to `version`.
+    pub fn set_licensed_version(&self, bundle_id: &str, version: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET licensed_version = ?1 WHERE bundle_id = ?2",
+            rusqlite::params![version, bundle_id],
+        )?;
+        Ok(())
+    }
+
     pub fn set_app_ignored(&self, bundle_id: &str, ignored: bool) -> AppResult<()> {
         self.conn.execute(
             "UPDATE apps SET is_ignored = ?1 WHERE bundle_id = ?2",
@@ -191,6 +243,28 @@ impl Database {
         Ok(())
     }
 
+    /// Ignore or un-ignore several apps at once. Returns how many apps were changed.
+    pub fn set_apps_ignored(&self, bundle_ids: &[String], ignored: bool) -> AppResult<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut changed = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE apps SET is_ignored = ?1 WHERE bundle_id = ?2")?;
+            for bundle_id in bundle_ids {
+                changed += stmt.execute(rusqlite::params![ignored as i32, bundle_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    pub fn set_app_relaunch_policy(&self, bundle_id: &str, policy: RelaunchPolicy) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET relaunch_policy = ?1 WHERE bundle_id = ?2",
+            rusqlite::params![policy.as_str(), bundle_id],
+        )?;
+        Ok(())
+    }
+
     pub fn update_icon_cache_path(&self, bundle_id: &str, path: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE apps SET icon_cache_path = ?1 WHERE bundle_id = ?2",
@@ -207,24 +281,41 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_github_mappings(&self) -> HashMap<String, String> {
-        let mut mappings = HashMap::new();
-        let mut stmt = match self.conn.prepare(
-            "SELECT bundle_id, github_repo FROM app_mappings WHERE github_repo IS NOT NULL",
-        ) {
-            Ok(s) => s,
-            Err(_) => return mappings,
-        };
+    /// Point an app at a cask the user picked, replacing any detected token.
+    pub fn replace_cask_token(&self, bundle_id: &str, token: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET homebrew_cask_token = ?1 WHERE bundle_id = ?2",
+            rusqlite::params![token, bundle_id],
+        )?;
+        Ok(())
+    }
 
-        if let Ok(rows) = stmt.query_map([], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-        }) {
-            for row in rows.flatten() {
-                mappings.insert(row.0, row.1);
-            }
-        }
+    /// Forget the cask an app was matched to, along with the updates found through
+    /// it. Apps Homebrew actually installed keep their token and cask updates.
+    pub fn clear_cask_match(&self, bundle_id: &str) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM available_updates WHERE app_id IN (
+                SELECT id FROM apps WHERE bundle_id = ?1
+            ) AND (source_type = 'homebrew_api' OR (source_type = 'homebrew_cask' AND app_id IN (
+                SELECT id FROM apps WHERE bundle_id = ?1 AND install_source IS NOT 'homebrew'
+            )))",
+            [bundle_id],
+        )?;
+        tx.execute(
+            "UPDATE apps SET homebrew_cask_token = NULL WHERE bundle_id = ?1 AND install_source IS NOT 'homebrew'",
+            [bundle_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        mappings
+    pub fn update_app_path(&self, app_id: i64, app_path: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET app_path = ?1 WHERE id = ?2",
+            rusqlite::params![app_path, app_id],
+        )?;
+        Ok(())
     }
 
     pub fn update_installed_version(&self, app_id: i64, version: &str) -> AppResult<()> {
@@ -232,9 +323,64 @@ impl Database {
             "UPDATE apps SET installed_version = ?1 WHERE id = ?2",
             rusqlite::params![version, app_id],
         )?;
+        self.record_version_seen(app_id, version)
+    }
+
+    /// The code-signing Team ID recorded for the installed version of an app.
+    pub fn get_team_id(&self, app_id: i64) -> Option<String> {
+        self.conn
+            .query_row("SELECT team_id FROM apps WHERE id = ?1", [app_id], |row| row.get(0))
+            .ok()
+            .flatten()
+    }
+
+    pub fn set_team_id(&self, app_id: i64, team_id: Option<&str>) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET team_id = ?1 WHERE id = ?2",
+            rusqlite::params![team_id, app_id],
+        )?;
         Ok(())
     }
 
+    /// Store the update feed an Electron app's app-update.yml names, or clear it.
+    pub fn set_electron_update_feed(
+        &self,
+        bundle_id: &str,
+        feed: Option<&ElectronUpdateFeed>,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET electron_update_feed = ?1 WHERE bundle_id = ?2",
+            rusqlite::params![feed.and_then(|f| serde_json::to_string(f).ok()), bundle_id],
+        )?;
+        Ok(())
+    }
+
+    /// Electron update feeds found during detection, keyed by bundle ID.
+    pub fn get_electron_update_feeds(&self) -> HashMap<String, ElectronUpdateFeed> {
+        let Ok(mut stmt) = self.conn.prepare(
+            "SELECT bundle_id, electron_update_feed FROM apps WHERE electron_update_feed IS NOT NULL",
+        ) else {
+            return HashMap::new();
+        };
+        stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map(|rows| {
+                rows.flatten()
+                    .filter_map(|(id, json)| Some((id, serde_json::from_str(&json).ok()?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Bundle IDs of every app in the database.
+    pub fn get_bundle_ids(&self) -> AppResult<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT bundle_id FROM apps")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(ids)
+    }
+
     pub fn get_app_count(&self) -> AppResult<usize> {
         let count: i64 = self
             .conn
@@ -242,6 +388,116 @@ impl Database {
         Ok(count as usize)
     }
 
+    /// Get (bundle_id, display_name, app_path) for every tracked app with a path on disk.
+    pub fn get_app_paths(&self) -> AppResult<Vec<(String, String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT bundle_id, display_name, app_path FROM apps WHERE app_path != ''",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    /// Get (bundle_id, display_name, app_path) for apps whose cached sizes are
+    /// missing or older than a day. Formulae have no bundle and are skipped.
+    pub fn get_apps_needing_sizes(&self) -> AppResult<Vec<(String, String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT bundle_id, display_name, app_path FROM apps
+             WHERE app_path != '' AND install_source != 'homebrew_formula'
+               AND (size_computed_at IS NULL OR size_computed_at < datetime('now', '-1 day'))",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    pub fn update_app_sizes(&self, bundle_id: &str, bundle_bytes: u64, data_bytes: u64) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET bundle_size_bytes = ?1, data_size_bytes = ?2, size_computed_at = datetime('now')
+             WHERE bundle_id = ?3",
+            rusqlite::params![bundle_bytes as i64, data_bytes as i64, bundle_id],
+        )?;
+        Ok(())
+    }
+
+    /// All apps with cached sizes, largest total footprint first.
+    pub fn get_disk_usage(&self) -> AppResult<Vec<DiskUsageEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT bundle_id, display_name, app_path, icon_cache_path,
+                    COALESCE(bundle_size_bytes, 0), COALESCE(data_size_bytes, 0), size_computed_at
+             FROM apps
+             WHERE size_computed_at IS NOT NULL
+             ORDER BY COALESCE(bundle_size_bytes, 0) + COALESCE(data_size_bytes, 0) DESC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                let bundle_size_bytes = row.get::<_, i64>(4)?.max(0) as u64;
+                let data_size_bytes = row.get::<_, i64>(5)?.max(0) as u64;
+                Ok(DiskUsageEntry {
+                    bundle_id: row.get(0)?,
+                    display_name: row.get(1)?,
+                    app_path: row.get(2)?,
+                    icon_cache_path: row.get(3)?,
+                    bundle_size_bytes,
+                    data_size_bytes,
+                    total_size_bytes: bundle_size_bytes + data_size_bytes,
+                    size_computed_at: row.get(6)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    /// Record when the app at `app_path` was last opened.
+    pub fn update_last_used(&self, app_path: &str, last_used_at: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET last_used_at = ?1 WHERE app_path = ?2",
+            rusqlite::params![last_used_at, app_path],
+        )?;
+        Ok(())
+    }
+
+    /// Apps not opened in the last `days` days, least recently used first. Apps
+    /// Spotlight never saw opened count once they've been installed that long.
+    pub fn get_unused_apps(&self, days: u32) -> AppResult<Vec<UnusedApp>> {
+        let cutoff = format!("-{} days", days);
+        let mut stmt = self.conn.prepare(
+            "SELECT bundle_id, display_name, app_path, icon_cache_path, last_used_at,
+                    CASE WHEN size_computed_at IS NULL THEN NULL
+                         ELSE COALESCE(bundle_size_bytes, 0) + COALESCE(data_size_bytes, 0) END
+             FROM apps
+             WHERE app_path != '' AND install_source != 'homebrew_formula'
+               AND bundle_id NOT LIKE 'com.apple.%'
+               AND (last_used_at < datetime('now', ?1)
+                    OR (last_used_at IS NULL AND first_seen_at < datetime('now', ?1)))
+             ORDER BY last_used_at IS NOT NULL, last_used_at,
+                      COALESCE(bundle_size_bytes, 0) + COALESCE(data_size_bytes, 0) DESC",
+        )?;
+        let rows = stmt
+            .query_map([cutoff], |row| {
+                Ok(UnusedApp {
+                    bundle_id: row.get(0)?,
+                    display_name: row.get(1)?,
+                    app_path: row.get(2)?,
+                    icon_cache_path: row.get(3)?,
+                    last_used_at: row.get(4)?,
+                    total_size_bytes: row.get::<_, Option<i64>>(5)?.map(|n| n.max(0) as u64),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
     /// Get the cached SHA-256 for a cask token (used for "latest" cask change detection).
     pub fn get_cask_sha(&self, cask_token: &str) -> Option<String> {
         self.conn
@@ -285,6 +541,16 @@ impl Database {
         Ok(())
     }
 
+    /// Remove rows for helper bundles nested inside another app (recorded by
+    /// earlier scans before helpers were folded into their parent).
+    pub fn delete_nested_helper_apps(&self) -> AppResult<usize> {
+        let count = self.conn.execute(
+            "DELETE FROM apps WHERE app_path LIKE '%.app/Contents/%'",
+            [],
+        )?;
+        Ok(count)
+    }
+
     /// Remove apps that were not re-detected during the latest scan and no longer exist on disk.
     /// Skips ignored apps and apps on external volumes (which may be temporarily unmounted).
     /// Returns (deleted_count, deleted_bundle_ids).
@@ -317,6 +583,23 @@ impl Database {
         Ok((deleted_ids.len(), deleted_ids))
     }
 
+    /// Delete apps for which `is_excluded(bundle_id, app_path)` is true.
+    /// Returns the deleted bundle IDs.
+    pub fn delete_excluded_apps(&self, is_excluded: impl Fn(&str, &str) -> bool) -> AppResult<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT bundle_id, app_path FROM apps")?;
+        let excluded: Vec<String> = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .filter_map(|r| r.ok())
+            .filter(|(bundle_id, app_path)| is_excluded(bundle_id, app_path))
+            .map(|(bundle_id, _)| bundle_id)
+            .collect();
+
+        for bundle_id in &excluded {
+            self.conn.execute("DELETE FROM apps WHERE bundle_id = ?1", [bundle_id])?;
+        }
+        Ok(excluded)
+    }
+
     /// Store or update the SHA-256 for a cask token.
     pub fn set_cask_sha(&self, cask_token: &str, sha256: &str) -> AppResult<()> {
         self.conn.execute(
@@ -0,0 +1,76 @@
+use crate::db::Database;
+use crate::models::{AppArchive, ArchivedFile};
+use crate::utils::AppResult;
+
+const ARCHIVE_COLUMNS: &str = "id, bundle_id, display_name, app_path, archive_path, size_bytes,
+                               support_files, created_at, restored_at";
+
+fn row_to_archive(row: &rusqlite::Row) -> rusqlite::Result<AppArchive> {
+    let support_json: Option<String> = row.get(6)?;
+    Ok(AppArchive {
+        id: row.get(0)?,
+        bundle_id: row.get(1)?,
+        display_name: row.get(2)?,
+        app_path: row.get(3)?,
+        archive_path: row.get(4)?,
+        size_bytes: row.get::<_, i64>(5)? as u64,
+        support_files: support_json
+            .and_then(|j| serde_json::from_str(&j).ok())
+            .unwrap_or_default(),
+        created_at: row.get(7)?,
+        restored_at: row.get(8)?,
+    })
+}
+
+impl Database {
+    pub fn insert_app_archive(
+        &self,
+        bundle_id: &str,
+        display_name: &str,
+        app_path: &str,
+        archive_path: &str,
+        size_bytes: u64,
+        support_files: &[ArchivedFile],
+    ) -> AppResult<i64> {
+        let support_json = if support_files.is_empty() {
+            None
+        } else {
+            serde_json::to_string(support_files).ok()
+        };
+        self.conn.execute(
+            "INSERT INTO app_archives (bundle_id, display_name, app_path, archive_path, size_bytes, support_files)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![bundle_id, display_name, app_path, archive_path, size_bytes as i64, support_json],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn get_app_archives(&self) -> AppResult<Vec<AppArchive>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM app_archives ORDER BY created_at DESC",
+            ARCHIVE_COLUMNS
+        ))?;
+        let archives = stmt
+            .query_map([], row_to_archive)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(archives)
+    }
+
+    pub fn get_app_archive(&self, id: i64) -> AppResult<AppArchive> {
+        let archive = self.conn.query_row(
+            &format!("SELECT {} FROM app_archives WHERE id = ?1", ARCHIVE_COLUMNS),
+            [id],
+            row_to_archive,
+        )?;
+        Ok(archive)
+    }
+
+    pub fn mark_archive_restored(&self, id: i64) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE app_archives SET restored_at = datetime('now') WHERE id = ?1",
+            [id],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use crate::db::Database;
+use crate::models::DetectionConflict;
+use crate::utils::AppResult;
+
+impl Database {
+    /// Replace the stored conflicts with those found by the latest full scan.
+    pub fn replace_detection_conflicts(&self, conflicts: &[DetectionConflict]) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM detection_conflicts", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT INTO detection_conflicts (bundle_id, conflict) VALUES (?1, ?2)")?;
+            for conflict in conflicts {
+                let Ok(json) = serde_json::to_string(conflict) else {
+                    continue;
+                };
+                stmt.execute(rusqlite::params![conflict.bundle_id, json])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Conflicts from the last full scan, for apps that are still installed.
+    pub fn get_detection_conflicts(&self) -> AppResult<Vec<DetectionConflict>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dc.conflict, a.preferred_detector FROM detection_conflicts dc
+             JOIN apps a ON a.bundle_id = dc.bundle_id
+             ORDER BY a.display_name COLLATE NOCASE",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)))?
+            .filter_map(|r| r.ok())
+            .filter_map(|(json, preferred)| {
+                let mut conflict: DetectionConflict = serde_json::from_str(&json).ok()?;
+                conflict.preferred_detector = preferred;
+                Some(conflict)
+            })
+            .collect();
+        Ok(rows)
+    }
+
+    /// Bundle ID → detector the user picked, for the detection engine.
+    pub fn get_preferred_detectors(&self) -> HashMap<String, String> {
+        let Ok(mut stmt) = self
+            .conn
+            .prepare("SELECT bundle_id, preferred_detector FROM apps WHERE preferred_detector IS NOT NULL")
+        else {
+            return HashMap::new();
+        };
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map(|rows| rows.flatten().collect())
+            .unwrap_or_default()
+    }
+
+    /// Pick the detector whose values win for an app, or None to merge as usual.
+    pub fn set_preferred_detector(&self, bundle_id: &str, detector: Option<&str>) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE apps SET preferred_detector = ?1 WHERE bundle_id = ?2",
+            rusqlite::params![detector, bundle_id],
+        )?;
+        Ok(())
+    }
+}
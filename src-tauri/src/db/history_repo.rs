@@ -1,5 +1,6 @@
 use crate::db::Database;
-use crate::models::UpdateHistoryEntry;
+use crate::models::{AppTimelineEvent, TimelineEventKind, UpdateHistoryEntry};
+use crate::updaters::version_compare::strip_brew_version_token;
 use crate::utils::AppResult;
 
 impl Database {
@@ -7,7 +8,8 @@ impl Database {
         let mut stmt = self.conn.prepare(
             "SELECT h.id, a.bundle_id, a.display_name, a.icon_cache_path,
                     h.from_version, h.to_version, h.source_type,
-                    h.status, h.error_message, h.started_at, h.completed_at
+                    h.status, h.error_message, h.started_at, h.completed_at, h.gatekeeper_verdict,
+                    h.attempts, h.license_accepted
              FROM update_history h
              JOIN apps a ON a.id = h.app_id
              ORDER BY h.started_at DESC
@@ -28,6 +30,9 @@ impl Database {
                     error_message: row.get(8)?,
                     started_at: row.get(9)?,
                     completed_at: row.get(10)?,
+                    gatekeeper_verdict: row.get(11)?,
+                    attempts: row.get(12)?,
+                    license_accepted: row.get(13)?,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -36,6 +41,68 @@ impl Database {
         Ok(entries)
     }
 
+    /// Versions of an app as scans first saw them, plus its finished updates, newest
+    /// first. A version an update installed isn't listed again as first seen.
+    pub fn get_app_timeline(&self, bundle_id: &str) -> AppResult<Vec<AppTimelineEvent>> {
+        let app_id: i64 =
+            self.conn
+                .query_row("SELECT id FROM apps WHERE bundle_id = ?1", [bundle_id], |row| row.get(0))?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT from_version, to_version, source_type, status, error_message,
+                    COALESCE(completed_at, started_at)
+             FROM update_history
+             WHERE app_id = ?1 AND status IN ('completed', 'failed')
+               AND COALESCE(completed_at, started_at) IS NOT NULL",
+        )?;
+        let mut events: Vec<AppTimelineEvent> = stmt
+            .query_map([app_id], |row| {
+                let status: String = row.get(3)?;
+                Ok(AppTimelineEvent {
+                    kind: if status == "completed" {
+                        TimelineEventKind::Updated
+                    } else {
+                        TimelineEventKind::UpdateFailed
+                    },
+                    version: row.get(1)?,
+                    from_version: row.get(0)?,
+                    source_type: row.get(2)?,
+                    error_message: row.get(4)?,
+                    at: row.get(5)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT version, first_seen_at FROM app_versions WHERE app_id = ?1",
+        )?;
+        let seen: Vec<(String, String)> = stmt
+            .query_map([app_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        for (version, first_seen_at) in seen {
+            let installed_by_update = events
+                .iter()
+                .any(|e| e.kind == TimelineEventKind::Updated && e.version == version);
+            if !installed_by_update {
+                events.push(AppTimelineEvent {
+                    kind: TimelineEventKind::FirstSeen,
+                    version,
+                    from_version: None,
+                    source_type: None,
+                    error_message: None,
+                    at: first_seen_at,
+                });
+            }
+        }
+
+        // SQLite datetimes sort chronologically as strings
+        events.sort_by(|a, b| b.at.cmp(&a.at));
+        Ok(events)
+    }
+
+    /// Versions are recorded as they're shown, without Homebrew tokens.
     pub fn record_update_start(
         &self,
         app_id: i64,
@@ -46,7 +113,12 @@ impl Database {
         self.conn.execute(
             "INSERT INTO update_history (app_id, from_version, to_version, source_type, status, started_at)
              VALUES (?1, ?2, ?3, ?4, 'in_progress', datetime('now'))",
-            rusqlite::params![app_id, from_version, to_version, source_type],
+            rusqlite::params![
+                app_id,
+                strip_brew_version_token(from_version),
+                strip_brew_version_token(to_version),
+                source_type
+            ],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -69,6 +141,41 @@ impl Database {
         Ok(())
     }
 
+    pub fn record_update_attempts(&self, history_id: i64, attempts: u32) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE update_history SET attempts = ?1 WHERE id = ?2",
+            rusqlite::params![attempts, history_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_update_license_accepted(&self, history_id: i64) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE update_history SET license_accepted = 1 WHERE id = ?1",
+            [history_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_update_gatekeeper(&self, history_id: i64, verdict: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE update_history SET gatekeeper_verdict = ?1 WHERE id = ?2",
+            rusqlite::params![verdict, history_id],
+        )?;
+        Ok(())
+    }
+
+    /// Number of updates that completed since `since` (an SQLite `datetime` string).
+    pub fn count_completed_updates_since(&self, since: &str) -> AppResult<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM update_history
+             WHERE status = 'completed' AND completed_at >= ?1",
+            [since],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     pub fn record_update_failed(&self, history_id: i64, error: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE update_history SET status = 'failed', error_message = ?1, completed_at = datetime('now')
@@ -77,4 +184,46 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Mark updates still `in_progress` as failed. At launch these can only be
+    /// left over from a run that quit or crashed mid-update.
+    /// Returns the bundle IDs of their apps.
+    pub fn fail_interrupted_updates(&self, error: &str) -> AppResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT a.bundle_id FROM update_history h
+             JOIN apps a ON a.id = h.app_id
+             WHERE h.status = 'in_progress'",
+        )?;
+        let bundle_ids = stmt
+            .query_map([], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        self.conn.execute(
+            "UPDATE update_history SET status = 'failed', error_message = ?1, completed_at = datetime('now')
+             WHERE status = 'in_progress'",
+            [error],
+        )?;
+        Ok(bundle_ids)
+    }
+
+    /// Delete finished update history, uninstall records and other activity
+    /// older than `days`.
+    /// Returns the number of rows removed.
+    pub fn prune_history_older_than(&self, days: u32) -> AppResult<usize> {
+        let cutoff = format!("-{} days", days);
+        let history = self.conn.execute(
+            "DELETE FROM update_history
+             WHERE status != 'in_progress' AND COALESCE(completed_at, started_at) < datetime('now', ?1)",
+            [&cutoff],
+        )?;
+        let uninstalls = self.conn.execute(
+            "DELETE FROM uninstall_records WHERE uninstalled_at < datetime('now', ?1)",
+            [&cutoff],
+        )?;
+        let activity = self.conn.execute(
+            "DELETE FROM activity_events WHERE at < datetime('now', ?1)",
+            [&cutoff],
+        )?;
+        Ok(history + uninstalls + activity)
+    }
 }
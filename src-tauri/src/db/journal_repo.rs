@@ -0,0 +1,66 @@
+use crate::db::Database;
+use crate::models::{JournalStep, UpdateJournalEntry};
+use crate::utils::AppResult;
+
+impl Database {
+    /// Record that the bundle at `app_path` is about to be replaced with the one
+    /// at `staged_path`. Returns the journal entry's ID.
+    pub fn journal_begin(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        staged_path: &str,
+    ) -> AppResult<i64> {
+        self.conn.execute(
+            "INSERT INTO update_journal (bundle_id, app_path, staged_path) VALUES (?1, ?2, ?3)",
+            rusqlite::params![bundle_id, app_path, staged_path],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Record a finished step, and where the old bundle went in the Trash once known.
+    pub fn journal_step(
+        &self,
+        id: i64,
+        step: JournalStep,
+        trashed_path: Option<&str>,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE update_journal SET step = ?2, trashed_path = COALESCE(?3, trashed_path)
+             WHERE id = ?1",
+            rusqlite::params![id, step.as_str(), trashed_path],
+        )?;
+        Ok(())
+    }
+
+    /// Drop a journal entry once its replacement finished or was rolled back.
+    pub fn journal_finish(&self, id: i64) -> AppResult<()> {
+        self.conn
+            .execute("DELETE FROM update_journal WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Replacements that were interrupted, oldest first.
+    pub fn get_journal_entries(&self) -> AppResult<Vec<UpdateJournalEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, bundle_id, app_path, staged_path, trashed_path, step, started_at
+             FROM update_journal ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                let step: String = row.get(5)?;
+                Ok(UpdateJournalEntry {
+                    id: row.get(0)?,
+                    bundle_id: row.get(1)?,
+                    app_path: row.get(2)?,
+                    staged_path: row.get(3)?,
+                    trashed_path: row.get(4)?,
+                    step: JournalStep::from_str(&step),
+                    started_at: row.get(6)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+}
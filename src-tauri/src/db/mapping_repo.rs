@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::db::Database;
+use crate::models::AppMapping;
+use crate::utils::AppResult;
+
+const MAPPING_COLUMNS: &str = "bundle_id, github_repo, custom_feed_url, homebrew_cask_token, mas_id";
+
+fn mapping_from_row(row: &rusqlite::Row) -> rusqlite::Result<AppMapping> {
+    Ok(AppMapping {
+        bundle_id: row.get(0)?,
+        github_repo: row.get(1)?,
+        custom_feed_url: row.get(2)?,
+        homebrew_cask_token: row.get(3)?,
+        mas_id: row.get(4)?,
+    })
+}
+
+impl Database {
+    /// All app mappings, keyed by bundle ID. These take priority over the
+    /// community feed and built-in lookups when checking for updates.
+    pub fn get_app_mappings(&self) -> HashMap<String, AppMapping> {
+        let Ok(mut stmt) = self.conn.prepare(&format!("SELECT {} FROM app_mappings", MAPPING_COLUMNS)) else {
+            return HashMap::new();
+        };
+        stmt.query_map([], mapping_from_row)
+            .map(|rows| rows.flatten().map(|m| (m.bundle_id.clone(), m)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Mappings the user set up themselves, for export.
+    pub fn get_user_mappings(&self) -> AppResult<Vec<AppMapping>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM app_mappings WHERE is_user_defined = 1 ORDER BY bundle_id",
+            MAPPING_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], mapping_from_row)?.filter_map(|r| r.ok()).collect();
+        Ok(rows)
+    }
+
+    /// Insert or replace a user-defined mapping.
+    pub fn upsert_user_mapping(&self, mapping: &AppMapping) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO app_mappings (bundle_id, github_repo, custom_feed_url, homebrew_cask_token, mas_id, is_user_defined)
+             VALUES (?1, ?2, ?3, ?4, ?5, 1)
+             ON CONFLICT(bundle_id) DO UPDATE SET
+                github_repo = excluded.github_repo,
+                custom_feed_url = excluded.custom_feed_url,
+                homebrew_cask_token = excluded.homebrew_cask_token,
+                mas_id = excluded.mas_id,
+                is_user_defined = 1",
+            rusqlite::params![
+                mapping.bundle_id,
+                mapping.github_repo,
+                mapping.custom_feed_url,
+                mapping.homebrew_cask_token,
+                mapping.mas_id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Set or remove the cask token in an app's user-defined mapping, keeping its
+    /// other fields. A mapping left with nothing in it is deleted.
+    pub fn set_user_cask_token(&self, bundle_id: &str, token: Option<&str>) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO app_mappings (bundle_id, homebrew_cask_token, is_user_defined) VALUES (?1, ?2, 1)
+             ON CONFLICT(bundle_id) DO UPDATE SET
+                homebrew_cask_token = excluded.homebrew_cask_token,
+                is_user_defined = 1",
+            rusqlite::params![bundle_id, token],
+        )?;
+        tx.execute(
+            "DELETE FROM app_mappings WHERE bundle_id = ?1 AND homebrew_cask_token IS NULL
+             AND github_repo IS NULL AND custom_feed_url IS NULL AND mas_id IS NULL",
+            [bundle_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert or replace several user-defined mappings at once.
+    pub fn upsert_user_mappings(&self, mappings: &[AppMapping]) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for mapping in mappings {
+            self.upsert_user_mapping(mapping)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::db::Database;
+use crate::models::{FailureCount, MetricsSummary, SourceHitRate};
+use crate::utils::metrics::{MetricSample, CHECKER, CHECKER_ERROR, CYCLE, DOWNLOAD, FAILURE};
+use crate::utils::AppResult;
+
+impl Database {
+    pub fn insert_metrics(&self, samples: &[MetricSample]) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for sample in samples {
+            tx.execute(
+                "INSERT INTO metrics (kind, name, count, total) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![
+                    sample.kind,
+                    sample.name,
+                    sample.count as i64,
+                    sample.total as i64
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn prune_metrics_older_than(&self, days: u32) -> AppResult<usize> {
+        let cutoff = format!("-{} days", days);
+        Ok(self.conn.execute(
+            "DELETE FROM metrics WHERE recorded_at < datetime('now', ?1)",
+            [&cutoff],
+        )?)
+    }
+
+    pub fn clear_metrics(&self) -> AppResult<()> {
+        self.conn.execute("DELETE FROM metrics", [])?;
+        Ok(())
+    }
+
+    /// Everything kept in the metrics table, summed. `enabled` is left for the
+    /// caller to fill in.
+    pub fn get_metrics_summary(&self) -> AppResult<MetricsSummary> {
+        let since: Option<String> =
+            self.conn
+                .query_row("SELECT MIN(recorded_at) FROM metrics", [], |row| row.get(0))?;
+
+        let (cycles, avg_cycle_ms, max_cycle_ms): (i64, f64, i64) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(AVG(total), 0), COALESCE(MAX(total), 0)
+             FROM metrics WHERE kind = ?1",
+            [CYCLE],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let checkers = self
+            .conn
+            .prepare(
+                "SELECT name,
+                        COALESCE(SUM(CASE WHEN kind = ?1 THEN count END), 0),
+                        COALESCE(SUM(CASE WHEN kind = ?1 THEN total END), 0),
+                        COALESCE(SUM(CASE WHEN kind = ?2 THEN count END), 0)
+                 FROM metrics WHERE kind IN (?1, ?2)
+                 GROUP BY name ORDER BY 2 DESC",
+            )?
+            .query_map([CHECKER, CHECKER_ERROR], |row| {
+                Ok(SourceHitRate {
+                    source: row.get(0)?,
+                    runs: row.get::<_, i64>(1)? as u64,
+                    found: row.get::<_, i64>(2)? as u64,
+                    errors: row.get::<_, i64>(3)? as u64,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let (downloads, download_bytes): (i64, i64) = self.conn.query_row(
+            "SELECT COALESCE(SUM(count), 0), COALESCE(SUM(total), 0) FROM metrics WHERE kind = ?1",
+            [DOWNLOAD],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let failures = self
+            .conn
+            .prepare(
+                "SELECT name, SUM(count) FROM metrics WHERE kind = ?1
+                 GROUP BY name ORDER BY 2 DESC",
+            )?
+            .query_map([FAILURE], |row| {
+                Ok(FailureCount {
+                    category: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as u64,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(MetricsSummary {
+            enabled: false,
+            since,
+            cycles: cycles as u64,
+            avg_cycle_ms: avg_cycle_ms.round() as u64,
+            max_cycle_ms: max_cycle_ms as u64,
+            checkers,
+            downloads: downloads as u64,
+            download_bytes: download_bytes as u64,
+            failures,
+        })
+    }
+}
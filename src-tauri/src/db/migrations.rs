@@ -116,6 +116,174 @@ const MIGRATIONS: &[&str] = &[
     "
     ALTER TABLE apps ADD COLUMN description TEXT;
     ",
+    // Migration 10: Add helper_apps column (JSON) for helper bundles folded into their parent
+    "
+    ALTER TABLE apps ADD COLUMN helper_apps TEXT;
+    ",
+    // Migration 11: Cache app bundle and associated data sizes for the disk usage view
+    "
+    ALTER TABLE apps ADD COLUMN bundle_size_bytes INTEGER;
+    ALTER TABLE apps ADD COLUMN data_size_bytes INTEGER;
+    ALTER TABLE apps ADD COLUMN size_computed_at TEXT;
+    ",
+    // Migration 12: Zip archives taken before uninstalling, for restore_archived_app
+    "
+    CREATE TABLE IF NOT EXISTS app_archives (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        bundle_id TEXT NOT NULL,
+        display_name TEXT NOT NULL,
+        app_path TEXT NOT NULL,
+        archive_path TEXT NOT NULL,
+        size_bytes INTEGER NOT NULL DEFAULT 0,
+        support_files TEXT,
+        created_at TEXT NOT NULL DEFAULT (datetime('now')),
+        restored_at TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_app_archives_bundle_id ON app_archives(bundle_id);
+    ",
+    // Migration 13: Record trashed paths and the removed app row so uninstalls can be undone
+    "
+    CREATE TABLE IF NOT EXISTS uninstall_records (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        bundle_id TEXT NOT NULL,
+        app_snapshot TEXT NOT NULL,
+        trashed_items TEXT NOT NULL,
+        uninstalled_at TEXT NOT NULL DEFAULT (datetime('now')),
+        undone_at TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_uninstall_records_bundle_id ON uninstall_records(bundle_id);
+    ",
+    // Migration 14: Replace the notificationSound flag with a notification policy,
+    // keeping sounds off for users who had turned them off
+    "
+    UPDATE settings
+    SET value = json_set(value, '$.notificationPolicy', json_object('sound', 'none'))
+    WHERE key = 'app_settings' AND json_extract(value, '$.notificationSound') = 0;
+    UPDATE settings
+    SET value = json_remove(value, '$.notificationSound')
+    WHERE key = 'app_settings';
+    ",
+    // Migration 15: Per-app choice of whether updaters may quit and relaunch the app
+    "
+    ALTER TABLE apps ADD COLUMN relaunch_policy TEXT NOT NULL DEFAULT 'automatic';
+    ",
+    // Migration 16: Expected download size of each pending update, in bytes
+    "
+    ALTER TABLE available_updates ADD COLUMN download_size INTEGER;
+    ",
+    // Migration 17: Code-signing Team ID of the installed version, to spot publisher changes
+    "
+    ALTER TABLE apps ADD COLUMN team_id TEXT;
+    ",
+    // Migration 18: Gatekeeper's verdict on the bundle an update installed
+    "
+    ALTER TABLE update_history ADD COLUMN gatekeeper_verdict TEXT;
+    ",
+    // Migration 19: When each installed version of an app was first seen, seeded from
+    // completed updates and the versions installed now
+    "
+    CREATE TABLE IF NOT EXISTS app_versions (
+        app_id          INTEGER REFERENCES apps(id) ON DELETE CASCADE,
+        version         TEXT NOT NULL,
+        first_seen_at   TEXT NOT NULL DEFAULT (datetime('now')),
+        UNIQUE(app_id, version)
+    );
+    INSERT OR IGNORE INTO app_versions (app_id, version, first_seen_at)
+        SELECT app_id, to_version, completed_at FROM update_history
+        WHERE status = 'completed' AND completed_at IS NOT NULL
+        ORDER BY completed_at;
+    INSERT OR IGNORE INTO app_versions (app_id, version)
+        SELECT id, installed_version FROM apps WHERE installed_version IS NOT NULL;
+    ",
+    // Migration 20: When each app was last opened, from Spotlight's kMDItemLastUsedDate
+    "
+    ALTER TABLE apps ADD COLUMN last_used_at TEXT;
+    ",
+    // Migration 21: Paid-upgrade version the user has confirmed owning a license for
+    "
+    ALTER TABLE apps ADD COLUMN licensed_version TEXT;
+    ",
+    // Migration 22: Disagreements between detectors from the last scan, and the
+    // detector the user picked to resolve them
+    "
+    CREATE TABLE IF NOT EXISTS detection_conflicts (
+        bundle_id   TEXT PRIMARY KEY,
+        conflict    TEXT NOT NULL,
+        detected_at TEXT DEFAULT (datetime('now'))
+    );
+
+    ALTER TABLE apps ADD COLUMN preferred_detector TEXT;
+    ",
+    // Migration 23: How many times an update was tried, counting retries after
+    // transient network failures
+    "
+    ALTER TABLE update_history ADD COLUMN attempts INTEGER NOT NULL DEFAULT 1;
+    ",
+    // Migration 24: Updates the user deferred to a later time, e.g. overnight
+    "
+    CREATE TABLE IF NOT EXISTS scheduled_updates (
+        bundle_id    TEXT PRIMARY KEY,
+        run_at       TEXT NOT NULL,
+        scheduled_at TEXT DEFAULT (datetime('now'))
+    );
+    ",
+    // Migration 25: Activity not recorded elsewhere, i.e. apps that appeared after
+    // the first scan and updates of macPlus itself
+    "
+    CREATE TABLE IF NOT EXISTS activity_events (
+        id           INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind         TEXT NOT NULL,
+        bundle_id    TEXT,
+        display_name TEXT NOT NULL,
+        version      TEXT,
+        at           TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+    CREATE INDEX IF NOT EXISTS idx_activity_events_at ON activity_events(at);
+    ",
+    // Migration 26: Update feed from an Electron app's app-update.yml (JSON)
+    "
+    ALTER TABLE apps ADD COLUMN electron_update_feed TEXT;
+    ",
+    // Migration 27: Pending updates that fix security issues
+    "
+    ALTER TABLE available_updates ADD COLUMN is_security INTEGER NOT NULL DEFAULT 0;
+    ",
+    // Migration 28: Write-ahead journal of app bundle replacements in progress
+    "
+    CREATE TABLE IF NOT EXISTS update_journal (
+        id           INTEGER PRIMARY KEY AUTOINCREMENT,
+        bundle_id    TEXT NOT NULL,
+        app_path     TEXT NOT NULL,
+        staged_path  TEXT NOT NULL,
+        trashed_path TEXT,
+        step         TEXT NOT NULL DEFAULT 'started',
+        started_at   TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+    ",
+    // Migration 29: Whether an update accepted a disk image license agreement by itself
+    "
+    ALTER TABLE update_history ADD COLUMN license_accepted INTEGER NOT NULL DEFAULT 0;
+    ",
+    // Migration 30: Opt-in local usage metrics
+    "
+    CREATE TABLE IF NOT EXISTS metrics (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind        TEXT NOT NULL,
+        name        TEXT NOT NULL DEFAULT '',
+        count       INTEGER NOT NULL,
+        total       INTEGER NOT NULL,
+        recorded_at TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+    CREATE INDEX IF NOT EXISTS idx_metrics_kind ON metrics(kind);
+    ",
+    // Migration 31: Apps whose network checkers last found no update source
+    "
+    CREATE TABLE IF NOT EXISTS negative_checks (
+        bundle_id   TEXT PRIMARY KEY,
+        fingerprint TEXT NOT NULL,
+        checked_at  TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+    ",
 ];
 
 pub fn run_migrations(db: &mut Database) -> AppResult<()> {
@@ -1,12 +1,21 @@
+pub mod activity_repo;
 pub mod app_repo;
+pub mod archive_repo;
+pub mod conflict_repo;
 pub mod history_repo;
+pub mod journal_repo;
+pub mod mapping_repo;
+pub mod metrics_repo;
 pub mod migrations;
+pub mod schedule_repo;
+pub mod uninstall_repo;
 pub mod update_repo;
 
 use rusqlite::Connection;
 use std::path::Path;
 
-use crate::utils::AppResult;
+use crate::models::AppSettings;
+use crate::utils::{AppError, AppResult};
 
 pub struct Database {
     pub conn: Connection,
@@ -20,14 +29,7 @@ impl Database {
         migrations::run_migrations(&mut db)?;
 
         // Purge stale update records where available == installed version
-        let purged: usize = match db.conn.execute(
-            "DELETE FROM available_updates WHERE id IN (
-                SELECT au.id FROM available_updates au
-                JOIN apps a ON a.id = au.app_id
-                WHERE au.available_version = a.installed_version
-            )",
-            [],
-        ) {
+        let purged: usize = match db.purge_installed_updates() {
             Ok(count) => count,
             Err(e) => {
                 log::warn!("Failed to purge stale updates at startup: {}", e);
@@ -55,4 +57,47 @@ impl Database {
 
         Ok(db)
     }
+
+    /// Empty every table except the migration log, then compact the file so
+    /// deleted rows don't linger on disk.
+    pub fn delete_all_data(&self) -> AppResult<()> {
+        let tables: Vec<String> = self
+            .conn
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != '_migrations'",
+            )?
+            .query_map([], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        // Foreign keys are off for the wipe so table order doesn't matter
+        self.conn.execute_batch("PRAGMA foreign_keys=OFF;")?;
+        let result = (|| -> AppResult<()> {
+            let tx = self.conn.unchecked_transaction()?;
+            for table in &tables {
+                tx.execute(&format!("DELETE FROM \"{}\"", table.replace('"', "\"\"")), [])?;
+            }
+            tx.commit()?;
+            Ok(())
+        })();
+        self.conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+        result?;
+
+        self.conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+        log::info!("Deleted all data from {} tables", tables.len());
+        Ok(())
+    }
+
+    /// Store `settings` as the app's settings.
+    pub fn save_settings(&self, settings: &AppSettings) -> AppResult<()> {
+        let json = serde_json::to_string(settings)
+            .map_err(|e| AppError::Custom(format!("Failed to serialize settings: {}", e)))?;
+        self.conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES ('app_settings', ?1, datetime('now'))
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+            [&json],
+        )?;
+        Ok(())
+    }
 }
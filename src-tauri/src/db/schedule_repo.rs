@@ -0,0 +1,86 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::db::Database;
+use crate::models::ScheduledUpdate;
+use crate::utils::AppResult;
+
+/// Format of `run_at`, matching SQLite's `datetime('now')` so due updates can
+/// be found with a plain comparison.
+const RUN_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl Database {
+    /// Schedule updates of `bundle_ids` for `run_at`, replacing any earlier
+    /// schedule for the same apps.
+    pub fn schedule_updates(&self, bundle_ids: &[String], run_at: DateTime<Utc>) -> AppResult<()> {
+        let run_at = run_at.format(RUN_AT_FORMAT).to_string();
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO scheduled_updates (bundle_id, run_at) VALUES (?1, ?2)
+                 ON CONFLICT(bundle_id) DO UPDATE SET
+                    run_at = excluded.run_at,
+                    scheduled_at = datetime('now')",
+            )?;
+            for bundle_id in bundle_ids {
+                stmt.execute(rusqlite::params![bundle_id, run_at])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Pending scheduled updates, soonest first.
+    pub fn get_scheduled_updates(&self) -> AppResult<Vec<ScheduledUpdate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.bundle_id, COALESCE(a.display_name, s.bundle_id), s.run_at
+             FROM scheduled_updates s
+             LEFT JOIN apps a ON a.bundle_id = s.bundle_id
+             ORDER BY s.run_at, a.display_name COLLATE NOCASE",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                let run_at: String = row.get(2)?;
+                Ok(ScheduledUpdate {
+                    bundle_id: row.get(0)?,
+                    display_name: row.get(1)?,
+                    run_at: NaiveDateTime::parse_from_str(&run_at, RUN_AT_FORMAT)
+                        .map(|t| t.and_utc().to_rfc3339())
+                        .unwrap_or(run_at),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    /// Remove scheduled updates of `bundle_ids`. Returns how many were removed.
+    pub fn cancel_scheduled_updates(&self, bundle_ids: &[String]) -> AppResult<usize> {
+        let mut removed = 0;
+        for bundle_id in bundle_ids {
+            removed += self.conn.execute(
+                "DELETE FROM scheduled_updates WHERE bundle_id = ?1",
+                [bundle_id],
+            )?;
+        }
+        Ok(removed)
+    }
+
+    /// Remove and return the scheduled updates that are due.
+    pub fn take_due_scheduled_updates(&self) -> AppResult<Vec<String>> {
+        let now = Utc::now().format(RUN_AT_FORMAT).to_string();
+        let tx = self.conn.unchecked_transaction()?;
+        let due: Vec<String> = {
+            let mut stmt = tx.prepare(
+                "SELECT bundle_id FROM scheduled_updates WHERE run_at <= ?1 ORDER BY run_at",
+            )?;
+            let rows = stmt
+                .query_map([&now], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            rows
+        };
+        tx.execute("DELETE FROM scheduled_updates WHERE run_at <= ?1", [&now])?;
+        tx.commit()?;
+        Ok(due)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::db::Database;
+use crate::models::{AppDetail, TrashedItem, UninstallRecord};
+use crate::utils::{AppError, AppResult};
+
+impl Database {
+    pub fn insert_uninstall_record(
+        &self,
+        app: &AppDetail,
+        trashed_items: &[TrashedItem],
+    ) -> AppResult<i64> {
+        let snapshot = serde_json::to_string(app)
+            .map_err(|e| AppError::Custom(format!("serialize app snapshot: {}", e)))?;
+        let trashed = serde_json::to_string(trashed_items)
+            .map_err(|e| AppError::Custom(format!("serialize trashed items: {}", e)))?;
+        self.conn.execute(
+            "INSERT INTO uninstall_records (bundle_id, app_snapshot, trashed_items)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![app.bundle_id, snapshot, trashed],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// The most recent uninstall of `bundle_id` that hasn't been undone and is
+    /// younger than `window_hours`.
+    pub fn get_undoable_uninstall(
+        &self,
+        bundle_id: &str,
+        window_hours: i64,
+    ) -> AppResult<Option<UninstallRecord>> {
+        let row = self.conn.query_row(
+            "SELECT id, bundle_id, app_snapshot, trashed_items, uninstalled_at
+             FROM uninstall_records
+             WHERE bundle_id = ?1 AND undone_at IS NULL
+               AND uninstalled_at >= datetime('now', ?2)
+             ORDER BY uninstalled_at DESC, id DESC
+             LIMIT 1",
+            rusqlite::params![bundle_id, format!("-{} hours", window_hours)],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            },
+        );
+
+        let (id, bundle_id, snapshot, trashed, uninstalled_at) = match row {
+            Ok(r) => r,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let app = serde_json::from_str(&snapshot)
+            .map_err(|e| AppError::Custom(format!("parse app snapshot: {}", e)))?;
+        Ok(Some(UninstallRecord {
+            id,
+            bundle_id,
+            app,
+            trashed_items: serde_json::from_str(&trashed).unwrap_or_default(),
+            uninstalled_at,
+        }))
+    }
+
+    pub fn mark_uninstall_undone(&self, id: i64) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE uninstall_records SET undone_at = datetime('now') WHERE id = ?1",
+            [id],
+        )?;
+        Ok(())
+    }
+}
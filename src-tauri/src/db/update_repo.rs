@@ -1,5 +1,6 @@
 use crate::db::Database;
 use crate::models::UpdateInfo;
+use crate::updaters::version_compare::{self, strip_brew_version_token};
 use crate::utils::AppResult;
 
 impl Database {
@@ -22,6 +23,38 @@ impl Database {
         Ok(())
     }
 
+    /// Delete pending updates to the version that's installed. Versions are
+    /// compared without Homebrew tokens, so "1.1.3363,ee42…" matches "1.1.3363".
+    pub fn purge_installed_updates(&self) -> AppResult<usize> {
+        let installed: Vec<i64> = self
+            .conn
+            .prepare(
+                "SELECT au.id, au.available_version, a.installed_version
+                 FROM available_updates au
+                 JOIN apps a ON a.id = au.app_id
+                 WHERE au.dismissed_at IS NULL AND a.installed_version IS NOT NULL",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .filter(|(_, available, installed)| {
+                strip_brew_version_token(available) == strip_brew_version_token(installed)
+            })
+            .map(|(id, _, _)| id)
+            .collect();
+
+        for id in &installed {
+            self.conn
+                .execute("DELETE FROM available_updates WHERE id = ?1", [id])?;
+        }
+        Ok(installed.len())
+    }
+
     pub fn upsert_available_update(&self, app_id: i64, update: &UpdateInfo) -> AppResult<()> {
         // Clean up stale undismissed updates for a different version
         self.conn.execute(
@@ -32,15 +65,16 @@ impl Database {
         let clean_notes = update.release_notes.as_deref().map(crate::utils::sanitize::sanitize_release_notes);
 
         self.conn.execute(
-            "INSERT INTO available_updates (app_id, source_type, available_version, release_notes_url, download_url, release_notes, is_paid_upgrade, notes)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "INSERT INTO available_updates (app_id, source_type, available_version, release_notes_url, download_url, release_notes, is_paid_upgrade, notes, download_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
              ON CONFLICT(app_id, available_version) DO UPDATE SET
                 source_type = excluded.source_type,
                 release_notes_url = COALESCE(excluded.release_notes_url, available_updates.release_notes_url),
                 download_url = COALESCE(excluded.download_url, available_updates.download_url),
                 release_notes = COALESCE(excluded.release_notes, available_updates.release_notes),
                 is_paid_upgrade = excluded.is_paid_upgrade,
-                notes = excluded.notes",
+                notes = excluded.notes,
+                download_size = COALESCE(excluded.download_size, available_updates.download_size)",
             rusqlite::params![
                 app_id,
                 update.source_type.as_str(),
@@ -50,11 +84,35 @@ impl Database {
                 clean_notes,
                 update.is_paid_upgrade as i32,
                 update.notes,
+                update.download_size.map(|n| n as i64),
             ],
         )?;
         Ok(())
     }
 
+    /// Flag a pending update as fixing security issues, or clear the flag.
+    pub fn set_update_security(&self, app_id: i64, version: &str, is_security: bool) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE available_updates SET is_security = ?3 WHERE app_id = ?1 AND available_version = ?2",
+            rusqlite::params![app_id, version, is_security as i32],
+        )?;
+        Ok(())
+    }
+
+    /// Download size already recorded for this version and URL, to avoid re-measuring it.
+    pub fn known_download_size(&self, app_id: i64, version: &str, download_url: &str) -> Option<u64> {
+        self.conn
+            .query_row(
+                "SELECT download_size FROM available_updates
+                 WHERE app_id = ?1 AND available_version = ?2 AND download_url = ?3",
+                rusqlite::params![app_id, version, download_url],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .ok()
+            .flatten()
+            .map(|n| n.max(0) as u64)
+    }
+
     pub fn clear_available_updates(&self, app_id: i64) -> AppResult<()> {
         self.conn.execute(
             "DELETE FROM available_updates WHERE app_id = ?1",
@@ -73,6 +131,49 @@ impl Database {
         Ok(())
     }
 
+    /// Drop stored release notes; the release notes URL is kept.
+    pub fn clear_cached_release_notes(&self) -> AppResult<usize> {
+        let cleared = self.conn.execute(
+            "UPDATE available_updates SET release_notes = NULL WHERE release_notes IS NOT NULL",
+            [],
+        )?;
+        Ok(cleared)
+    }
+
+    /// What the app's last "no update source" result was based on, if it was
+    /// stored within the last `hours`.
+    pub fn get_negative_check(&self, bundle_id: &str, hours: u32) -> Option<String> {
+        let cutoff = format!("-{} hours", hours);
+        self.conn
+            .query_row(
+                "SELECT fingerprint FROM negative_checks
+                 WHERE bundle_id = ?1 AND checked_at > datetime('now', ?2)",
+                rusqlite::params![bundle_id, cutoff],
+                |row| row.get(0),
+            )
+            .ok()
+    }
+
+    pub fn set_negative_check(&self, bundle_id: &str, fingerprint: &str) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO negative_checks (bundle_id, fingerprint, checked_at)
+             VALUES (?1, ?2, datetime('now'))
+             ON CONFLICT(bundle_id) DO UPDATE SET
+                fingerprint = excluded.fingerprint,
+                checked_at = datetime('now')",
+            rusqlite::params![bundle_id, fingerprint],
+        )?;
+        Ok(())
+    }
+
+    pub fn clear_negative_check(&self, bundle_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "DELETE FROM negative_checks WHERE bundle_id = ?1",
+            [bundle_id],
+        )?;
+        Ok(())
+    }
+
     pub fn dismiss_update(&self, app_id: i64, version: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE available_updates SET dismissed_at = datetime('now')
@@ -93,4 +194,40 @@ impl Database {
         )?;
         Ok(count as usize)
     }
+
+    /// Apps with a pending update as (bundle_id, display_name, available_version),
+    /// sorted by name. Uses the same filters as `get_update_count`. An app with
+    /// updates from several sources is listed once, with the newest version.
+    pub fn get_pending_updates(&self, limit: Option<usize>) -> AppResult<Vec<(String, String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.id, a.bundle_id, a.display_name, au.available_version FROM available_updates au
+             JOIN apps a ON a.id = au.app_id
+             WHERE au.dismissed_at IS NULL AND a.is_ignored = 0
+               AND (a.installed_version IS NULL OR au.available_version != a.installed_version)
+             ORDER BY a.display_name COLLATE NOCASE, a.id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .filter_map(|r| r.ok());
+
+        // Versions don't sort as text ("1.10" < "1.9"), so pick the newest here
+        let mut pending: Vec<(i64, (String, String, String))> = Vec::new();
+        for (app_id, bundle_id, display_name, version) in rows {
+            match pending.last_mut() {
+                Some((id, (_, _, newest))) if *id == app_id => {
+                    if version_compare::is_newer(newest, &version) {
+                        *newest = version;
+                    }
+                }
+                _ => pending.push((app_id, (bundle_id, display_name, version))),
+            }
+        }
+        Ok(pending
+            .into_iter()
+            .map(|(_, update)| update)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
+    }
 }
@@ -26,7 +26,7 @@ pub fn read_bundle(app_path: &Path) -> Option<BundleInfo> {
         icon_file: get_string(&dict, "CFBundleIconFile")
             .or_else(|| get_string(&dict, "CFBundleIconName")),
         architectures: None,
-        sparkle_feed_url: get_string(&dict, "SUFeedURL"),
+        sparkle_feed_url: crate::updaters::sparkle::discover_feed_url(&bundle_id, app_path),
         min_system_version: get_string(&dict, "LSMinimumSystemVersion"),
     })
 }
@@ -37,8 +37,13 @@ pub fn has_sparkle_framework(app_path: &Path) -> bool {
         .exists()
 }
 
+/// Whether the bundle came from the App Store. iPhone and iPad apps on Apple
+/// silicon are wrapped (`Foo.app/Wrapper/Foo.app`, linked as `WrappedBundle`)
+/// and keep their App Store metadata in the wrapper instead of `Contents`.
 pub fn has_mas_receipt(app_path: &Path) -> bool {
     app_path.join("Contents/_MASReceipt/receipt").exists()
+        || app_path.join("Wrapper/iTunesMetadata.plist").exists()
+        || app_path.join("WrappedBundle/_MASReceipt/receipt").exists()
 }
 
 pub fn is_electron_app(app_path: &Path) -> bool {
@@ -1,11 +1,21 @@
 use std::collections::HashMap;
+use std::path::Path;
 
-use crate::models::{AppSource, DetectedApp};
+use super::bundle_reader;
+use crate::models::{AppSource, DetectedApp, DetectionCandidate, DetectionConflict, HelperApp};
+use crate::updaters::version_compare::strip_brew_version_token;
 
-pub fn deduplicate(apps: Vec<DetectedApp>) -> Vec<DetectedApp> {
-    let mut by_bundle_id: HashMap<String, DetectedApp> = HashMap::new();
+/// Merge detections of the same app. `apps` pairs each detection with the name
+/// of the detector that made it; `preferred` maps bundle IDs to the detector the
+/// user picked when detectors disagree. Returns the merged apps and the
+/// disagreements found along the way.
+pub fn deduplicate(
+    apps: Vec<(String, DetectedApp)>,
+    preferred: &HashMap<String, String>,
+) -> (Vec<DetectedApp>, Vec<DetectionConflict>) {
+    let mut by_bundle_id: HashMap<String, Vec<(String, DetectedApp)>> = HashMap::new();
 
-    for app in apps {
+    for (detector, mut app) in apps {
         if app.bundle_id.is_empty() {
             continue;
         }
@@ -13,20 +23,168 @@ pub fn deduplicate(apps: Vec<DetectedApp>) -> Vec<DetectedApp> {
         if app.bundle_id.starts_with("com.apple.") {
             continue;
         }
+        if app.install_source == AppSource::MacAppStore {
+            let bundle = Path::new(&app.app_path);
+            if !app.app_path.is_empty() && bundle.exists() {
+                confirm_app_store_source(&mut app, bundle_reader::has_mas_receipt(bundle));
+            }
+        }
+        by_bundle_id.entry(app.bundle_id.clone()).or_default().push((detector, app));
+    }
+
+    let mut merged = Vec::with_capacity(by_bundle_id.len());
+    let mut conflicts = Vec::new();
+    for (bundle_id, candidates) in by_bundle_id {
+        let mut app = candidates[0].1.clone();
+        for (_, other) in &candidates[1..] {
+            merge_into(&mut app, other);
+        }
+
+        let preferred_detector = preferred
+            .get(&bundle_id)
+            .filter(|detector| candidates.iter().any(|(name, _)| name == *detector));
+        if let Some((_, chosen)) = preferred_detector.and_then(|d| candidates.iter().find(|(name, _)| name == d)) {
+            apply_preferred(&mut app, chosen);
+        }
+
+        if let Some(conflict) = find_conflict(&app, &candidates, preferred_detector) {
+            conflicts.push(conflict);
+        }
+        merged.push(app);
+    }
+
+    let mut result = fold_helper_apps(merged);
+    result.sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
+    (result, conflicts)
+}
+
+/// Only an App Store receipt in the bundle shows an app came from the App Store.
+/// `mas list` and system_profiler can still claim one that was deleted and
+/// downloaded again from its developer, which the App Store then fails to update.
+fn confirm_app_store_source(app: &mut DetectedApp, has_receipt: bool) {
+    if app.install_source != AppSource::MacAppStore || has_receipt {
+        return;
+    }
+    log::info!("{} has no App Store receipt; treating it as a direct download", app.bundle_id);
+    app.install_source = AppSource::Direct;
+    app.obtained_from = Some("unknown".into());
+    app.mas_app_id = None;
+}
+
+/// Sources that claim to manage an app. Spotlight and directory scans only report
+/// Direct or Unknown, which a package manager's claim overrides without conflict.
+fn is_managed_source(source: &AppSource) -> bool {
+    matches!(source, AppSource::MacAppStore | AppSource::Homebrew | AppSource::HomebrewFormula)
+}
+
+fn has_several<'a>(values: impl Iterator<Item = &'a str>) -> bool {
+    let mut values: Vec<&str> = values.collect();
+    values.sort_unstable();
+    values.dedup();
+    values.len() > 1
+}
+
+/// Record where the detections of one app disagree. Helper apps are folded into
+/// their parent afterwards, so their disagreements don't matter.
+fn find_conflict(
+    merged: &DetectedApp,
+    candidates: &[(String, DetectedApp)],
+    preferred_detector: Option<&String>,
+) -> Option<DetectionConflict> {
+    if candidates.len() < 2 || enclosing_app_path(&merged.app_path).is_some() {
+        return None;
+    }
+    let apps = || candidates.iter().map(|(_, app)| app);
+
+    let mut fields = Vec::new();
+    if has_several(apps().map(|a| a.app_path.as_str()).filter(|p| !p.is_empty())) {
+        fields.push("path".to_string());
+    }
+    if has_several(apps().filter_map(|a| a.installed_version.as_deref()).map(|v| strip_brew_version_token(v).trim())) {
+        fields.push("version".to_string());
+    }
+    if has_several(apps().map(|a| &a.install_source).filter(|s| is_managed_source(s)).map(|s| s.as_str())) {
+        fields.push("source".to_string());
+    }
+    if fields.is_empty() {
+        return None;
+    }
 
-        match by_bundle_id.get_mut(&app.bundle_id) {
-            Some(existing) => {
-                merge_into(existing, &app);
+    Some(DetectionConflict {
+        bundle_id: merged.bundle_id.clone(),
+        display_name: merged.display_name.clone(),
+        fields,
+        candidates: candidates
+            .iter()
+            .map(|(detector, app)| DetectionCandidate {
+                detector: detector.clone(),
+                app_path: app.app_path.clone(),
+                installed_version: app.installed_version.clone(),
+                install_source: app.install_source.as_str().to_string(),
+            })
+            .collect(),
+        app_path: merged.app_path.clone(),
+        installed_version: merged.installed_version.clone(),
+        install_source: merged.install_source.as_str().to_string(),
+        preferred_detector: preferred_detector.cloned(),
+    })
+}
+
+/// Use the path, version and source from the detection the user picked instead
+/// of the merged ones.
+fn apply_preferred(app: &mut DetectedApp, chosen: &DetectedApp) {
+    if !chosen.app_path.is_empty() {
+        app.app_path = chosen.app_path.clone();
+    }
+    if chosen.installed_version.is_some() {
+        app.installed_version = chosen.installed_version.clone();
+        app.bundle_version = chosen.bundle_version.clone();
+    }
+    if chosen.install_source != AppSource::Unknown {
+        app.install_source = chosen.install_source.clone();
+    }
+}
+
+/// If `app_path` lies inside another app bundle's `Contents/` directory
+/// (e.g. `Foo.app/Contents/Frameworks/Foo Helper.app`), return the outermost app path.
+fn enclosing_app_path(app_path: &str) -> Option<&str> {
+    let idx = app_path.find(".app/Contents/")?;
+    Some(&app_path[..idx + ".app".len()])
+}
+
+/// Fold embedded helper apps (updaters, crash handlers, login helpers) into their
+/// parent app. Helpers whose parent was not detected (e.g. filtered Apple apps) are dropped.
+fn fold_helper_apps(apps: Vec<DetectedApp>) -> Vec<DetectedApp> {
+    let (helpers, mut top_level): (Vec<DetectedApp>, Vec<DetectedApp>) = apps
+        .into_iter()
+        .partition(|app| enclosing_app_path(&app.app_path).is_some());
+
+    let parent_index: HashMap<String, usize> = top_level
+        .iter()
+        .enumerate()
+        .map(|(idx, app)| (app.app_path.clone(), idx))
+        .collect();
+
+    for helper in helpers {
+        let parent_path = enclosing_app_path(&helper.app_path).unwrap_or_default();
+        match parent_index.get(parent_path) {
+            Some(&idx) => {
+                let parent = &mut top_level[idx];
+                if !parent.helper_apps.iter().any(|h| h.bundle_id == helper.bundle_id) {
+                    parent.helper_apps.push(HelperApp {
+                        bundle_id: helper.bundle_id,
+                        display_name: helper.display_name,
+                        app_path: helper.app_path,
+                    });
+                }
             }
             None => {
-                by_bundle_id.insert(app.bundle_id.clone(), app);
+                log::debug!("Dropping helper app without a detected parent: {}", helper.app_path);
             }
         }
     }
 
-    let mut result: Vec<DetectedApp> = by_bundle_id.into_values().collect();
-    result.sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
-    result
+    top_level
 }
 
 fn merge_into(existing: &mut DetectedApp, new: &DetectedApp) {
@@ -86,3 +244,86 @@ fn merge_into(existing: &mut DetectedApp, new: &DetectedApp) {
         existing.homebrew_formula_name = new.homebrew_formula_name.clone();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(source: AppSource, path: &str, version: &str) -> DetectedApp {
+        DetectedApp {
+            bundle_id: "com.example.foo".to_string(),
+            display_name: "Foo".to_string(),
+            app_path: path.to_string(),
+            installed_version: Some(version.to_string()),
+            bundle_version: None,
+            install_source: source,
+            obtained_from: None,
+            homebrew_cask_token: None,
+            architectures: None,
+            sparkle_feed_url: None,
+            mas_app_id: None,
+            homebrew_formula_name: None,
+            helper_apps: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn app_store_claim_needs_a_receipt() {
+        let mut app = detected(AppSource::MacAppStore, "/Applications/Foo.app", "2.0");
+        app.mas_app_id = Some("123456".to_string());
+        confirm_app_store_source(&mut app, true);
+        assert_eq!(app.install_source, AppSource::MacAppStore);
+
+        confirm_app_store_source(&mut app, false);
+        assert_eq!(app.install_source, AppSource::Direct);
+        assert_eq!(app.mas_app_id, None);
+    }
+
+    #[test]
+    fn homebrew_claim_over_direct_is_not_a_conflict() {
+        let apps = vec![
+            ("Spotlight".to_string(), detected(AppSource::Direct, "/Applications/Foo.app", "2.0")),
+            ("Homebrew".to_string(), detected(AppSource::Homebrew, "/Applications/Foo.app", "2.0,123")),
+        ];
+        let (merged, conflicts) = deduplicate(apps, &HashMap::new());
+        assert_eq!(merged[0].install_source, AppSource::Homebrew);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn disagreements_are_recorded_and_preference_wins() {
+        let apps = || {
+            vec![
+                ("Spotlight".to_string(), detected(AppSource::Direct, "/Applications/Foo.app", "2.0")),
+                ("Directory Scan".to_string(), detected(AppSource::Direct, "/Users/me/Applications/Foo.app", "1.0")),
+            ]
+        };
+        let (merged, conflicts) = deduplicate(apps(), &HashMap::new());
+        assert_eq!(merged[0].app_path, "/Applications/Foo.app");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].fields, vec!["path".to_string(), "version".to_string()]);
+        assert_eq!(conflicts[0].candidates.len(), 2);
+        assert_eq!(conflicts[0].preferred_detector, None);
+
+        let preferred = HashMap::from([("com.example.foo".to_string(), "Directory Scan".to_string())]);
+        let (merged, conflicts) = deduplicate(apps(), &preferred);
+        assert_eq!(merged[0].app_path, "/Users/me/Applications/Foo.app");
+        assert_eq!(merged[0].installed_version.as_deref(), Some("1.0"));
+        assert_eq!(conflicts[0].app_path, "/Users/me/Applications/Foo.app");
+        assert_eq!(conflicts[0].preferred_detector.as_deref(), Some("Directory Scan"));
+    }
+
+    #[test]
+    fn test_enclosing_app_path() {
+        assert_eq!(
+            enclosing_app_path("/Applications/Foo.app/Contents/Frameworks/Foo Helper.app"),
+            Some("/Applications/Foo.app")
+        );
+        assert_eq!(
+            enclosing_app_path("/Applications/Foo.app/Contents/Library/LoginItems/Launcher.app"),
+            Some("/Applications/Foo.app")
+        );
+        assert_eq!(enclosing_app_path("/Applications/Foo.app"), None);
+        assert_eq!(enclosing_app_path("/Applications/Utilities/Bar.app"), None);
+    }
+}
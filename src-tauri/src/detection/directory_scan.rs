@@ -4,8 +4,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::bundle_reader;
+use super::volumes;
 use super::AppDetector;
-use crate::models::DetectedApp;
+use crate::models::{DetectedApp, ScanLocation};
 use crate::utils::AppResult;
 
 #[derive(Debug, Clone, Serialize)]
@@ -17,12 +18,12 @@ pub struct ScanDirResult {
 }
 
 pub struct DirectoryScanDetector {
-    extra_locations: Vec<String>,
+    extra_locations: Vec<ScanLocation>,
     scan_depth: u32,
 }
 
 impl DirectoryScanDetector {
-    pub fn new(extra_locations: Vec<String>, scan_depth: u32) -> Self {
+    pub fn new(extra_locations: Vec<ScanLocation>, scan_depth: u32) -> Self {
         Self { extra_locations, scan_depth }
     }
 }
@@ -36,11 +37,19 @@ impl Default for DirectoryScanDetector {
     }
 }
 
-fn scan_directory(dir: &Path, max_depth: u32) -> Vec<PathBuf> {
-    scan_directory_recursive(dir, 0, max_depth)
+/// A directory to scan with its effective depth and symlink handling.
+#[derive(Clone)]
+struct ScanRoot {
+    dir: PathBuf,
+    depth: u32,
+    follow_symlinks: bool,
 }
 
-fn scan_directory_recursive(dir: &Path, current_depth: u32, max_depth: u32) -> Vec<PathBuf> {
+fn scan_directory(root: &ScanRoot) -> Vec<PathBuf> {
+    scan_directory_recursive(&root.dir, 0, root.depth, root.follow_symlinks)
+}
+
+fn scan_directory_recursive(dir: &Path, current_depth: u32, max_depth: u32, follow_symlinks: bool) -> Vec<PathBuf> {
     let mut apps = Vec::new();
     if current_depth > max_depth {
         return apps;
@@ -51,10 +60,13 @@ fn scan_directory_recursive(dir: &Path, current_depth: u32, max_depth: u32) -> V
             if path.extension().map_or(false, |ext| ext == "app") {
                 apps.push(path);
             } else if path.is_dir() && current_depth < max_depth {
+                if !follow_symlinks && entry.file_type().map_or(false, |t| t.is_symlink()) {
+                    continue;
+                }
                 // Skip hidden directories and .app bundles (which are directories internally)
                 let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
                 if !name.starts_with('.') && !name.ends_with(".app") {
-                    apps.extend(scan_directory_recursive(&path, current_depth + 1, max_depth));
+                    apps.extend(scan_directory_recursive(&path, current_depth + 1, max_depth, follow_symlinks));
                 }
             }
         }
@@ -72,6 +84,10 @@ fn discover_volume_app_dirs() -> Vec<PathBuf> {
     if let Ok(entries) = fs::read_dir(&volumes) {
         for entry in entries.flatten() {
             let vol_path = entry.path();
+            // An unreachable network share would block the `is_dir` below
+            if !volumes::is_reachable(&vol_path) {
+                continue;
+            }
             let apps_dir = vol_path.join("Applications");
             if apps_dir.is_dir() {
                 dirs.push(apps_dir);
@@ -81,85 +97,129 @@ fn discover_volume_app_dirs() -> Vec<PathBuf> {
     dirs
 }
 
-/// Expand `~` prefix to the user's home directory.
-fn expand_tilde(path: &str) -> PathBuf {
-    if let Some(rest) = path.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(rest);
-        }
+/// Find and read the apps under one scan root. None when the directory doesn't exist.
+fn read_root(root: &ScanRoot) -> Option<Vec<DetectedApp>> {
+    if !root.dir.exists() {
+        return None;
     }
-    PathBuf::from(path)
+    let apps = scan_directory(root)
+        .into_iter()
+        .filter_map(|app_path| {
+            let bundle = bundle_reader::read_bundle(&app_path)?;
+            let source = bundle_reader::detect_install_source(&app_path);
+            Some(DetectedApp {
+                bundle_id: bundle.bundle_id,
+                display_name: bundle.display_name,
+                app_path: bundle.app_path,
+                installed_version: bundle.installed_version,
+                bundle_version: bundle.bundle_version,
+                install_source: source,
+                obtained_from: None,
+                homebrew_cask_token: None,
+                architectures: bundle.architectures,
+                sparkle_feed_url: bundle.sparkle_feed_url,
+                mas_app_id: None,
+                homebrew_formula_name: None,
+                helper_apps: Vec::new(),
+            })
+        })
+        .collect();
+    Some(apps)
 }
 
-#[async_trait]
-impl AppDetector for DirectoryScanDetector {
-    fn name(&self) -> &str {
-        "Directory Scan"
+/// Scan one root. Locations on mounted volumes are checked for reachability first
+/// and scanned under a time limit, so a dropped SMB/NFS share can't stall the scan.
+fn scan_root(root: &ScanRoot) -> Option<Vec<DetectedApp>> {
+    if volumes::volume_root(&root.dir).is_none() {
+        return read_root(root);
+    }
+    if !volumes::is_reachable(&root.dir) {
+        log::warn!("DirectoryScan: skipping unreachable volume location {}", root.dir.display());
+        return None;
     }
 
-    async fn detect(&self) -> AppResult<Vec<DetectedApp>> {
-        let mut dirs = vec![
-            PathBuf::from("/Applications"),
-        ];
+    let owned = root.clone();
+    let result = volumes::with_timeout(volumes::VOLUME_SCAN_TIMEOUT, move || read_root(&owned));
+    if result.is_none() {
+        log::warn!(
+            "DirectoryScan: gave up on {} after {}s ({} volume)",
+            root.dir.display(),
+            volumes::VOLUME_SCAN_TIMEOUT.as_secs(),
+            if volumes::is_network_volume(&root.dir) { "network" } else { "external" }
+        );
+    }
+    result.flatten()
+}
+
+impl DirectoryScanDetector {
+    /// Default app folders, user-configured locations (whose depth and symlink
+    /// settings override the defaults for the same folder) and volume app folders.
+    fn scan_roots(&self) -> Vec<ScanRoot> {
+        let default_root = |dir: PathBuf| ScanRoot {
+            dir,
+            depth: self.scan_depth,
+            follow_symlinks: true,
+        };
 
+        let mut roots = vec![default_root(PathBuf::from("/Applications"))];
         if let Some(home) = dirs::home_dir() {
-            dirs.push(home.join("Applications"));
+            roots.push(default_root(home.join("Applications")));
         }
 
-        // Add user-configured extra locations
         for loc in &self.extra_locations {
-            let expanded = expand_tilde(loc);
-            if expanded.is_dir() && !dirs.contains(&expanded) {
-                dirs.push(expanded);
+            let dir = crate::utils::expand_tilde(&loc.path);
+            let depth = loc.depth.unwrap_or(self.scan_depth);
+            match roots.iter_mut().find(|r| r.dir == dir) {
+                Some(root) => {
+                    root.depth = depth;
+                    root.follow_symlinks = loc.follow_symlinks;
+                }
+                None => roots.push(ScanRoot {
+                    dir,
+                    depth,
+                    follow_symlinks: loc.follow_symlinks,
+                }),
             }
         }
 
-        // Auto-discover Applications dirs on mounted volumes
         for vol_dir in discover_volume_app_dirs() {
-            if !dirs.contains(&vol_dir) {
+            if !roots.iter().any(|r| r.dir == vol_dir) {
                 log::info!("Auto-discovered volume app dir: {}", vol_dir.display());
-                dirs.push(vol_dir);
+                roots.push(default_root(vol_dir));
             }
         }
+        roots
+    }
+}
+
+#[async_trait]
+impl AppDetector for DirectoryScanDetector {
+    fn name(&self) -> &str {
+        "Directory Scan"
+    }
+
+    async fn detect(&self) -> AppResult<Vec<DetectedApp>> {
+        let roots = self.scan_roots();
 
         log::info!(
             "DirectoryScan: scanning {} directories: {:?}",
-            dirs.len(),
-            dirs.iter().map(|d| d.display().to_string()).collect::<Vec<_>>()
+            roots.len(),
+            roots
+                .iter()
+                .map(|r| format!("{} (depth {})", r.dir.display(), r.depth))
+                .collect::<Vec<_>>()
         );
 
         let mut apps = Vec::new();
-        for dir in &dirs {
-            let dir_exists = dir.exists();
-            let mut apps_in_dir = 0usize;
-            if dir_exists {
-                for app_path in scan_directory(dir, self.scan_depth) {
-                    if let Some(bundle) = bundle_reader::read_bundle(&app_path) {
-                        let source = bundle_reader::detect_install_source(&app_path);
-                        apps.push(DetectedApp {
-                            bundle_id: bundle.bundle_id,
-                            display_name: bundle.display_name,
-                            app_path: bundle.app_path,
-                            installed_version: bundle.installed_version,
-                            bundle_version: bundle.bundle_version,
-                            install_source: source,
-                            obtained_from: None,
-                            homebrew_cask_token: None,
-                            architectures: bundle.architectures,
-                            sparkle_feed_url: bundle.sparkle_feed_url,
-                            mas_app_id: None,
-                            homebrew_formula_name: None,
-                        });
-                        apps_in_dir += 1;
-                    }
-                }
-            }
+        for root in &roots {
+            let found = scan_root(root);
             log::info!(
                 "DirectoryScan: {} found {} apps in {}",
-                if dir_exists { "✓" } else { "✗" },
-                apps_in_dir,
-                dir.display()
+                if found.is_some() { "✓" } else { "✗" },
+                found.as_ref().map_or(0, Vec::len),
+                root.dir.display()
             );
+            apps.extend(found.unwrap_or_default());
         }
 
         Ok(apps)
@@ -170,64 +230,26 @@ impl AppDetector for DirectoryScanDetector {
 impl DirectoryScanDetector {
     /// Run scan and return per-directory diagnostics alongside detected apps.
     pub async fn detect_with_stats(&self) -> AppResult<(Vec<DetectedApp>, Vec<ScanDirResult>)> {
-        let mut dirs = vec![
-            PathBuf::from("/Applications"),
-        ];
-
-        if let Some(home) = dirs::home_dir() {
-            dirs.push(home.join("Applications"));
-        }
-
-        for loc in &self.extra_locations {
-            let expanded = expand_tilde(loc);
-            if !dirs.contains(&expanded) {
-                dirs.push(expanded);
-            }
-        }
-
-        for vol_dir in discover_volume_app_dirs() {
-            if !dirs.contains(&vol_dir) {
-                dirs.push(vol_dir);
-            }
-        }
+        let roots = self.scan_roots();
 
         let mut apps = Vec::new();
         let mut stats = Vec::new();
 
-        for dir in &dirs {
-            let dir_exists = dir.exists();
-            let mut dir_apps = Vec::new();
-
-            if dir_exists {
-                for app_path in scan_directory(dir, self.scan_depth) {
-                    if let Some(bundle) = bundle_reader::read_bundle(&app_path) {
-                        let name = bundle.display_name.clone();
-                        let source = bundle_reader::detect_install_source(&app_path);
-                        apps.push(DetectedApp {
-                            bundle_id: bundle.bundle_id,
-                            display_name: bundle.display_name,
-                            app_path: bundle.app_path,
-                            installed_version: bundle.installed_version,
-                            bundle_version: bundle.bundle_version,
-                            install_source: source,
-                            obtained_from: None,
-                            homebrew_cask_token: None,
-                            architectures: bundle.architectures,
-                            sparkle_feed_url: bundle.sparkle_feed_url,
-                            mas_app_id: None,
-                            homebrew_formula_name: None,
-                        });
-                        dir_apps.push(name);
-                    }
-                }
-            }
+        for root in &roots {
+            let found = scan_root(root);
+            let dir_apps: Vec<String> = found
+                .iter()
+                .flatten()
+                .map(|app| app.display_name.clone())
+                .collect();
 
             stats.push(ScanDirResult {
-                path: dir.display().to_string(),
-                exists: dir_exists,
+                path: root.dir.display().to_string(),
+                exists: found.is_some(),
                 app_count: dir_apps.len(),
                 apps_found: dir_apps,
             });
+            apps.extend(found.unwrap_or_default());
         }
 
         Ok((apps, stats))
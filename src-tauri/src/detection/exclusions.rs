@@ -0,0 +1,121 @@
+use glob::{MatchOptions, Pattern};
+use std::path::Path;
+
+use crate::models::AppSettings;
+use crate::utils::expand_tilde;
+
+/// `*` and `?` stay within one path component; `**` spans directories.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// User-configured rules that keep apps out of the database entirely.
+#[derive(Debug, Clone, Default)]
+pub struct ExclusionRules {
+    path_patterns: Vec<Pattern>,
+    bundle_id_prefixes: Vec<String>,
+}
+
+impl ExclusionRules {
+    /// Build rules from `excluded_paths` (globs, `~/` allowed) and
+    /// `excluded_bundle_ids` (prefixes). Invalid globs are logged and skipped.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::new(&settings.excluded_paths, &settings.excluded_bundle_ids)
+    }
+
+    pub fn new(paths: &[String], bundle_id_prefixes: &[String]) -> Self {
+        let path_patterns = paths
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| {
+                let expanded = expand_tilde(p.trim_end_matches('/'));
+                match Pattern::new(&expanded.to_string_lossy()) {
+                    Ok(pattern) => Some(pattern),
+                    Err(e) => {
+                        log::warn!("Ignoring invalid exclusion pattern \"{}\": {}", p, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        let bundle_id_prefixes = bundle_id_prefixes
+            .iter()
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect();
+        Self { path_patterns, bundle_id_prefixes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path_patterns.is_empty() && self.bundle_id_prefixes.is_empty()
+    }
+
+    /// Whether a path is excluded. A pattern that matches a directory also
+    /// excludes everything inside it, so `~/Archive` covers `~/Archive/Old/Foo.app`.
+    pub fn excludes_path(&self, path: &Path) -> bool {
+        self.path_patterns.iter().any(|pattern| {
+            path.ancestors()
+                .take_while(|a| !a.as_os_str().is_empty() && *a != Path::new("/"))
+                .any(|a| pattern.matches_path_with(a, MATCH_OPTIONS))
+        })
+    }
+
+    pub fn excludes_bundle_id(&self, bundle_id: &str) -> bool {
+        let bundle_id = bundle_id.to_lowercase();
+        self.bundle_id_prefixes.iter().any(|p| bundle_id.starts_with(p.as_str()))
+    }
+
+    pub fn excludes(&self, bundle_id: &str, app_path: &str) -> bool {
+        self.excludes_bundle_id(bundle_id) || (!app_path.is_empty() && self.excludes_path(Path::new(app_path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(paths: &[&str], prefixes: &[&str]) -> ExclusionRules {
+        let paths: Vec<String> = paths.iter().map(|s| s.to_string()).collect();
+        let prefixes: Vec<String> = prefixes.iter().map(|s| s.to_string()).collect();
+        ExclusionRules::new(&paths, &prefixes)
+    }
+
+    #[test]
+    fn directory_pattern_excludes_contents() {
+        let r = rules(&["/Applications/Archive/"], &[]);
+        assert!(r.excludes("com.foo", "/Applications/Archive/Foo.app"));
+        assert!(r.excludes("com.foo", "/Applications/Archive/2023/Foo.app"));
+        assert!(!r.excludes("com.foo", "/Applications/Foo.app"));
+        assert!(!r.excludes("com.foo", "/Applications/Archived Foo.app"));
+    }
+
+    #[test]
+    fn single_star_stays_in_one_component() {
+        let r = rules(&["/Applications/Clients/*/Tool.app"], &[]);
+        assert!(r.excludes("com.tool", "/Applications/Clients/Acme/Tool.app"));
+        assert!(!r.excludes("com.tool", "/Applications/Clients/Acme/Sub/Tool.app"));
+    }
+
+    #[test]
+    fn double_star_spans_directories() {
+        let r = rules(&["/Users/*/Old Apps/**/*.app"], &[]);
+        assert!(r.excludes("com.x", "/Users/me/Old Apps/a/b/X.app"));
+        assert!(!r.excludes("com.x", "/Users/me/Apps/X.app"));
+    }
+
+    #[test]
+    fn bundle_id_prefix_is_case_insensitive() {
+        let r = rules(&[], &["com.Example."]);
+        assert!(r.excludes("com.example.App", "/Applications/App.app"));
+        assert!(!r.excludes("com.examples.App", "/Applications/App.app"));
+    }
+
+    #[test]
+    fn blank_and_invalid_rules_are_ignored() {
+        let r = rules(&["", "  ", "/Applications/[abc"], &[" "]);
+        assert!(r.is_empty());
+    }
+}
@@ -153,6 +153,7 @@ impl AppDetector for HomebrewDetector {
                     sparkle_feed_url: None,
                     mas_app_id: None,
                     homebrew_formula_name: None,
+                    helper_apps: Vec::new(),
                 });
                 continue;
             }
@@ -204,6 +205,7 @@ impl AppDetector for HomebrewDetector {
                     sparkle_feed_url: bundle.sparkle_feed_url,
                     mas_app_id: None,
                     homebrew_formula_name: None,
+                    helper_apps: Vec::new(),
                 });
             }
         }
@@ -115,5 +115,6 @@ fn make_formula_app(name: &str, version: Option<&str>) -> DetectedApp {
         sparkle_feed_url: None,
         mas_app_id: None,
         homebrew_formula_name: Some(name.to_string()),
+        helper_apps: Vec::new(),
     }
 }
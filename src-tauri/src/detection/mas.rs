@@ -92,6 +92,7 @@ impl AppDetector for MasDetector {
                     sparkle_feed_url,
                     mas_app_id: Some(apple_id),
                     homebrew_formula_name: None,
+                    helper_apps: Vec::new(),
                 })
             })
             .collect();
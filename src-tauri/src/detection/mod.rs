@@ -1,19 +1,26 @@
 pub mod bundle_reader;
 pub mod deduplicator;
 pub mod directory_scan;
+pub mod exclusions;
 pub mod homebrew;
 pub mod homebrew_formula;
 pub mod mas;
 pub mod pkg_receipts;
+pub mod plugins;
 pub mod spotlight;
 pub mod system_profiler;
+pub mod volumes;
+
+use std::collections::HashMap;
 
 use async_trait::async_trait;
 use futures::stream::{FuturesUnordered, StreamExt};
 
-use crate::models::DetectedApp;
+use crate::models::{DetectedApp, DetectionConflict, ScanLocation};
 use crate::utils::AppResult;
 
+use exclusions::ExclusionRules;
+
 #[async_trait]
 pub trait AppDetector: Send + Sync {
     fn name(&self) -> &str;
@@ -22,6 +29,9 @@ pub trait AppDetector: Send + Sync {
 
 pub struct DetectionEngine {
     detectors: Vec<Box<dyn AppDetector>>,
+    exclusions: ExclusionRules,
+    /// Bundle ID → detector whose path/version/source wins when detectors disagree
+    preferred_detectors: HashMap<String, String>,
 }
 
 impl DetectionEngine {
@@ -29,7 +39,7 @@ impl DetectionEngine {
         Self::with_scan_locations(Vec::new(), 2)
     }
 
-    pub fn with_scan_locations(scan_locations: Vec<String>, scan_depth: u32) -> Self {
+    pub fn with_scan_locations(scan_locations: Vec<ScanLocation>, scan_depth: u32) -> Self {
         Self {
             detectors: vec![
                 Box::new(spotlight::SpotlightDetector),
@@ -38,14 +48,34 @@ impl DetectionEngine {
                 Box::new(homebrew::HomebrewDetector),
                 Box::new(homebrew_formula::HomebrewFormulaDetector),
                 Box::new(mas::MasDetector),
+                Box::new(plugins::PluginBundleDetector),
             ],
+            exclusions: ExclusionRules::default(),
+            preferred_detectors: HashMap::new(),
         }
     }
 
+    /// Drop apps matching these rules from every detector's results.
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Resolve disagreements between detectors with the user's picks.
+    pub fn with_preferred_detectors(mut self, preferred: HashMap<String, String>) -> Self {
+        self.preferred_detectors = preferred;
+        self
+    }
+
+    /// Number of registered detectors (used as the scan progress total).
+    pub fn detector_count(&self) -> usize {
+        self.detectors.len()
+    }
+
     pub async fn detect_all(
         &self,
         on_progress: impl Fn(&str, usize, usize),
-    ) -> AppResult<Vec<DetectedApp>> {
+    ) -> AppResult<(Vec<DetectedApp>, Vec<DetectionConflict>)> {
         let total = self.detectors.len();
 
         // Run all detectors concurrently with FuturesUnordered for real-time progress
@@ -67,7 +97,7 @@ impl DetectionEngine {
             match result {
                 Ok(apps) => {
                     log::info!("{} found {} apps", name, apps.len());
-                    all_apps.extend(apps);
+                    all_apps.extend(apps.into_iter().map(|app| (name.clone(), app)));
                 }
                 Err(e) => {
                     log::warn!("{} failed: {}", name, e);
@@ -75,7 +105,18 @@ impl DetectionEngine {
             }
         }
 
-        let deduped = deduplicator::deduplicate(all_apps);
-        Ok(deduped)
+        // Filter before deduplicating so an excluded copy can't supply the path
+        // of an app that also lives somewhere allowed
+        if !self.exclusions.is_empty() {
+            let before = all_apps.len();
+            all_apps.retain(|(_, app)| !self.exclusions.excludes(&app.bundle_id, &app.app_path));
+            log::info!("Exclusion rules dropped {} detections", before - all_apps.len());
+        }
+
+        let (deduped, conflicts) = deduplicator::deduplicate(all_apps, &self.preferred_detectors);
+        if !conflicts.is_empty() {
+            log::info!("Detectors disagreed about {} apps", conflicts.len());
+        }
+        Ok((deduped, conflicts))
     }
 }
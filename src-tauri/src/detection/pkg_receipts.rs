@@ -1,10 +1,16 @@
 use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use super::AppDetector;
 use crate::models::DetectedApp;
 use crate::utils::AppResult;
 
+/// Maximum number of payload files checked per receipt when no app bundle is listed.
+const PAYLOAD_SAMPLE_LIMIT: usize = 200;
+
 pub struct PkgReceiptsDetector;
 
 #[async_trait]
@@ -41,3 +47,171 @@ pub fn get_pkg_version(package_id: &str) -> Option<String> {
         }
     })
 }
+
+/// Fields of interest from `pkgutil --pkg-info`.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PkgInfo {
+    pub version: Option<String>,
+    pub volume: Option<String>,
+    pub location: Option<String>,
+    pub install_time: Option<i64>,
+}
+
+pub(crate) fn parse_pkg_info(output: &str) -> PkgInfo {
+    let mut info = PkgInfo::default();
+    for line in output.lines() {
+        let (key, value) = match line.split_once(": ") {
+            Some(kv) => kv,
+            None => continue,
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "version" => info.version = Some(value),
+            "volume" => info.volume = Some(value),
+            "location" => info.location = Some(value),
+            "install-time" => info.install_time = value.parse().ok(),
+            _ => {}
+        }
+    }
+    info
+}
+
+/// A package receipt whose payload appears to be gone.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedReceipt {
+    pub package_id: String,
+    pub version: Option<String>,
+    /// Unix timestamp of the install
+    pub install_time: Option<i64>,
+    /// Install root (volume + location)
+    pub install_root: String,
+    pub file_count: usize,
+    /// App bundles the package installed (none of which exist anymore)
+    pub app_paths: Vec<String>,
+    /// "app_removed" or "payload_missing"
+    pub reason: String,
+}
+
+/// Outermost `.app` bundle paths referenced by a receipt's file list.
+fn app_bundles_in(files: &[&str], root: &Path) -> Vec<PathBuf> {
+    let mut bundles = BTreeSet::new();
+    for file in files {
+        if let Some(idx) = file.find(".app") {
+            let end = idx + ".app".len();
+            if end == file.len() || file[end..].starts_with('/') {
+                bundles.insert(root.join(&file[..end]));
+            }
+        }
+    }
+    bundles.into_iter().collect()
+}
+
+/// Audit a single receipt. Returns `Some` if its app was removed or none of its
+/// (sampled) payload files exist.
+fn audit_receipt(package_id: &str) -> Option<OrphanedReceipt> {
+    let info_out = Command::new("pkgutil")
+        .args(["--pkg-info", package_id])
+        .output()
+        .ok()?;
+    if !info_out.status.success() {
+        return None;
+    }
+    let info = parse_pkg_info(&String::from_utf8_lossy(&info_out.stdout));
+
+    let volume = info.volume.clone().unwrap_or_else(|| "/".to_string());
+    let location = info.location.clone().unwrap_or_default();
+    let root = Path::new(&volume).join(location.trim_start_matches('/'));
+
+    // Receipts on external volumes can't be judged while the volume is unmounted
+    if !Path::new(&volume).exists() {
+        return None;
+    }
+
+    let files_out = Command::new("pkgutil")
+        .args(["--files", package_id])
+        .output()
+        .ok()?;
+    if !files_out.status.success() {
+        return None;
+    }
+    let files_text = String::from_utf8_lossy(&files_out.stdout).to_string();
+    let files: Vec<&str> = files_text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if files.is_empty() {
+        return None;
+    }
+
+    let apps = app_bundles_in(&files, &root);
+    let reason = if !apps.is_empty() {
+        if apps.iter().any(|p| p.exists()) {
+            return None;
+        }
+        "app_removed"
+    } else {
+        let any_present = files
+            .iter()
+            .take(PAYLOAD_SAMPLE_LIMIT)
+            .any(|f| root.join(f).exists());
+        if any_present {
+            return None;
+        }
+        "payload_missing"
+    };
+
+    Some(OrphanedReceipt {
+        package_id: package_id.to_string(),
+        version: info.version,
+        install_time: info.install_time,
+        install_root: root.to_string_lossy().to_string(),
+        file_count: files.len(),
+        app_paths: apps.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        reason: reason.to_string(),
+    })
+}
+
+/// List third-party package receipts whose app was uninstalled or whose payload is gone.
+/// Blocking — call from `spawn_blocking`.
+pub fn find_orphaned_receipts() -> Vec<OrphanedReceipt> {
+    let output = match Command::new("pkgutil").arg("--pkgs").output() {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && !id.starts_with("com.apple."))
+        .filter_map(audit_receipt)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pkg_info() {
+        let output = "package-id: com.vendor.tool.pkg\n\
+version: 3.1.0\n\
+volume: /\n\
+location: Applications\n\
+install-time: 1690000000\n";
+        let info = parse_pkg_info(output);
+        assert_eq!(info.version.as_deref(), Some("3.1.0"));
+        assert_eq!(info.volume.as_deref(), Some("/"));
+        assert_eq!(info.location.as_deref(), Some("Applications"));
+        assert_eq!(info.install_time, Some(1690000000));
+    }
+
+    #[test]
+    fn test_app_bundles_in() {
+        let files = [
+            "Tool.app",
+            "Tool.app/Contents",
+            "Tool.app/Contents/Frameworks/Helper.app/Contents/Info.plist",
+            "usr/local/bin/tool",
+        ];
+        let apps = app_bundles_in(&files, Path::new("/Applications"));
+        assert_eq!(apps, vec![PathBuf::from("/Applications/Tool.app")]);
+    }
+}
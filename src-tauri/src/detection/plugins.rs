@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::bundle_reader;
+use super::AppDetector;
+use crate::models::{AppSource, DetectedApp};
+use crate::utils::AppResult;
+
+/// Bundle extensions for standalone plugins that ship their own updates.
+const PLUGIN_EXTENSIONS: &[&str] = &["qlgenerator", "saver"];
+
+/// Detects QuickLook generators (`.qlgenerator`) and screen savers (`.saver`)
+/// installed in the user and system Library folders. These bundles carry a
+/// regular Info.plist, so they flow through the same cask/GitHub matching as apps.
+pub struct PluginBundleDetector;
+
+/// Library folders that hold plugin bundles (user-level first).
+fn plugin_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join("Library/QuickLook"));
+        dirs.push(home.join("Library/Screen Savers"));
+    }
+    dirs.push(PathBuf::from("/Library/QuickLook"));
+    dirs.push(PathBuf::from("/Library/Screen Savers"));
+    dirs
+}
+
+fn is_plugin_bundle(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map_or(false, |ext| PLUGIN_EXTENSIONS.contains(&ext))
+}
+
+#[async_trait]
+impl AppDetector for PluginBundleDetector {
+    fn name(&self) -> &str {
+        "Plugins"
+    }
+
+    async fn detect(&self) -> AppResult<Vec<DetectedApp>> {
+        let mut apps = Vec::new();
+
+        for dir in plugin_dirs() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !is_plugin_bundle(&path) {
+                    continue;
+                }
+                if let Some(bundle) = bundle_reader::read_bundle(&path) {
+                    apps.push(DetectedApp {
+                        bundle_id: bundle.bundle_id,
+                        display_name: bundle.display_name,
+                        app_path: bundle.app_path,
+                        installed_version: bundle.installed_version,
+                        bundle_version: bundle.bundle_version,
+                        install_source: AppSource::Direct,
+                        obtained_from: None,
+                        homebrew_cask_token: None,
+                        architectures: bundle.architectures,
+                        sparkle_feed_url: bundle.sparkle_feed_url,
+                        mas_app_id: None,
+                        homebrew_formula_name: None,
+                        helper_apps: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        log::info!("Plugins: found {} QuickLook/screen saver bundles", apps.len());
+        Ok(apps)
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::DateTime;
 use std::path::Path;
 
 use super::bundle_reader;
@@ -61,6 +62,7 @@ impl AppDetector for SpotlightDetector {
                     sparkle_feed_url: bundle.sparkle_feed_url,
                     mas_app_id: None,
                     homebrew_formula_name: None,
+                    helper_apps: Vec::new(),
                 })
             })
             .collect();
@@ -68,3 +70,66 @@ impl AppDetector for SpotlightDetector {
         Ok(apps)
     }
 }
+
+/// Most paths passed to one `mdls` call, keeping the argument list well short of ARG_MAX.
+const MDLS_BATCH: usize = 200;
+
+/// Parse `mdls -raw -name kMDItemLastUsedDate` output for several files: one value
+/// per file, NUL-separated, `(null)` when the file was never opened. Dates come back
+/// as `2024-05-01 12:34:56 +0000` and are returned in SQLite's UTC format.
+pub(crate) fn parse_last_used_dates(stdout: &str) -> Vec<Option<String>> {
+    stdout
+        .strip_suffix('\0')
+        .unwrap_or(stdout)
+        .split('\0')
+        .map(|value| {
+            DateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S %z")
+                .ok()
+                .map(|date| date.naive_utc().format("%Y-%m-%d %H:%M:%S").to_string())
+        })
+        .collect()
+}
+
+/// When each of `app_paths` was last opened, per Spotlight, in the same order.
+/// Batches whose output can't be matched up with their paths are left as None.
+pub async fn last_used_dates(app_paths: &[String]) -> Vec<Option<String>> {
+    let mut dates = Vec::with_capacity(app_paths.len());
+    for batch in app_paths.chunks(MDLS_BATCH) {
+        let mut args = vec!["-raw", "-name", "kMDItemLastUsedDate"];
+        args.extend(batch.iter().map(String::as_str));
+        let parsed = match run_command_with_timeout("mdls", &args, 30).await {
+            Ok(output) if output.status.success() => {
+                parse_last_used_dates(&String::from_utf8_lossy(&output.stdout))
+            }
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                log::warn!("mdls failed: {}", e);
+                Vec::new()
+            }
+        };
+        if parsed.len() == batch.len() {
+            dates.extend(parsed);
+        } else {
+            dates.extend(std::iter::repeat(None).take(batch.len()));
+        }
+    }
+    dates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_last_used_dates() {
+        let stdout = "2024-05-01 12:34:56 +0000\0(null)\02024-05-01 09:00:00 +0200\0";
+        assert_eq!(
+            parse_last_used_dates(stdout),
+            vec![
+                Some("2024-05-01 12:34:56".to_string()),
+                None,
+                Some("2024-05-01 07:00:00".to_string()),
+            ]
+        );
+    }
+}
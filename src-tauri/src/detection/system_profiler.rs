@@ -84,6 +84,7 @@ impl AppDetector for SystemProfilerDetector {
                     sparkle_feed_url: bundle.sparkle_feed_url,
                     mas_app_id: None,
                     homebrew_formula_name: None,
+                    helper_apps: Vec::new(),
                 })
             })
             .collect();
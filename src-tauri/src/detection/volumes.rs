@@ -0,0 +1,92 @@
+use std::ffi::CStr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long a volume may take to answer a `stat` before it's treated as unreachable.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Most time spent scanning one location on an external or network volume.
+pub const VOLUME_SCAN_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// Filesystem types (`statfs.f_fstypename`) served over the network.
+const NETWORK_FS_TYPES: &[&str] = &["smbfs", "nfs", "afpfs", "webdav", "ftp", "cifs"];
+
+/// The `/Volumes/<name>` mount point containing `path`, if it's on a mounted volume.
+pub fn volume_root(path: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    match (components.next(), components.next(), components.next()) {
+        (Some(Component::RootDir), Some(Component::Normal(v)), Some(Component::Normal(name))) if v == "Volumes" => {
+            Some(Path::new("/Volumes").join(name))
+        }
+        _ => None,
+    }
+}
+
+/// Run `f` on a worker thread, giving up after `timeout`. A thread blocked on an
+/// unreachable share can't be cancelled, so it's left to finish (or hang) on its own.
+pub fn with_timeout<T, F>(timeout: Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("volume-io".into())
+        .spawn(move || {
+            let _ = tx.send(f());
+        })
+        .ok()?;
+    rx.recv_timeout(timeout).ok()
+}
+
+/// Whether the volume holding `path` is mounted and answering. Paths outside
+/// `/Volumes` are always considered reachable.
+pub fn is_reachable(path: &Path) -> bool {
+    let Some(root) = volume_root(path) else {
+        return true;
+    };
+    with_timeout(PROBE_TIMEOUT, move || root.is_dir()).unwrap_or_else(|| {
+        log::warn!("Volume {} did not respond within {}s", path.display(), PROBE_TIMEOUT.as_secs());
+        false
+    })
+}
+
+/// Filesystem type of the volume holding `path` (e.g. "apfs", "smbfs").
+pub fn filesystem_type(path: &Path) -> Option<String> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out-pointer.
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // SAFETY: f_fstypename is a NUL-terminated C string filled in by statfs.
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// Whether `path` is on an SMB/NFS/AFP/WebDAV share. Unreachable volumes count
+/// as network volumes, since that's the usual reason a mount stops answering.
+pub fn is_network_volume(path: &Path) -> bool {
+    let path = path.to_path_buf();
+    with_timeout(PROBE_TIMEOUT, move || filesystem_type(&path))
+        .map_or(true, |fs| fs.map_or(false, |fs| NETWORK_FS_TYPES.contains(&fs.as_str())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_root_of_nested_path() {
+        assert_eq!(
+            volume_root(Path::new("/Volumes/Share/Apps/Foo.app")),
+            Some(PathBuf::from("/Volumes/Share"))
+        );
+        assert_eq!(volume_root(Path::new("/Volumes/Share")), Some(PathBuf::from("/Volumes/Share")));
+        assert_eq!(volume_root(Path::new("/Volumes")), None);
+        assert_eq!(volume_root(Path::new("/Applications/Foo.app")), None);
+    }
+}
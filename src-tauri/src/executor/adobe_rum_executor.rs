@@ -0,0 +1,235 @@
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::UpdateExecutor;
+use crate::detection::bundle_reader;
+use crate::models::UpdateResult;
+use crate::updaters::adobe_cc::{bundle_to_sap_code, RUM_PATH};
+use crate::utils::brew::output_streaming;
+use crate::utils::sudo_session::{run_elevated, ElevatedError};
+use crate::utils::{AppError, AppResult};
+
+/// Installs Adobe updates with Remote Update Manager, which needs root.
+pub struct AdobeRumExecutor {
+    sap_code: String,
+    display_name: String,
+    pre_version: Option<String>,
+}
+
+/// The percentage in RUM's download lines, e.g. `[45%]`.
+static RE_PERCENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d{1,3}(?:\.\d+)?)\s*%").unwrap());
+
+/// A step RUM reports while installing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RumProgress {
+    Downloading(Option<f64>),
+    Installing,
+}
+
+/// Read progress from a line of RUM output, e.g. `Downloading (PHSP/26.1) [45%]`
+/// or `Installing (PHSP/26.1) ...`.
+fn parse_rum_line(line: &str) -> Option<RumProgress> {
+    let lower = line.trim().to_lowercase();
+    if lower.starts_with("download") {
+        let percent = RE_PERCENT
+            .captures(&lower)
+            .and_then(|caps| caps[1].parse::<f64>().ok())
+            .map(|p| p.min(100.0));
+        Some(RumProgress::Downloading(percent))
+    } else if lower.starts_with("install") {
+        Some(RumProgress::Installing)
+    } else {
+        None
+    }
+}
+
+/// Whether RUM's output reports a failed install. RUM can exit with status 0
+/// when some of the requested updates failed.
+fn rum_reported_failure(output: &str) -> bool {
+    let lower = output.to_lowercase();
+    lower.contains("failed to install") || lower.contains("updates failed")
+}
+
+impl AdobeRumExecutor {
+    /// An executor for the app, if RUM is installed and knows the app's SAP code.
+    pub fn for_app(bundle_id: &str, display_name: String) -> Option<Self> {
+        if !Path::new(RUM_PATH).exists() {
+            return None;
+        }
+        Some(Self {
+            sap_code: bundle_to_sap_code(bundle_id)?.to_string(),
+            display_name,
+            pre_version: None,
+        })
+    }
+
+    pub fn with_pre_version(mut self, version: Option<String>) -> Self {
+        self.pre_version = version;
+        self
+    }
+
+    /// Run RUM as root. With the askpass helper, `sudo -A` lets its output stream
+    /// in as progress; otherwise the macOS password dialog runs it in one go.
+    fn run_install(
+        &self,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> Result<(bool, String), ElevatedError> {
+        let product = format!("--productVersions={}", self.sap_code);
+        let args = ["--action=install", product.as_str()];
+
+        let Some(askpass) = crate::utils::askpass::askpass_path() else {
+            on_progress(20, "Installing update...", None);
+            return match run_elevated(RUM_PATH, &args) {
+                Ok(output) => Ok((true, String::from_utf8_lossy(&output.stdout).to_string())),
+                Err(ElevatedError::CommandFailed(msg)) => Ok((false, msg)),
+                Err(e) => Err(e),
+            };
+        };
+
+        let output = output_streaming(
+            Command::new("sudo")
+                .current_dir("/tmp")
+                .env("SUDO_ASKPASS", askpass)
+                .arg("-A")
+                .arg(RUM_PATH)
+                .args(args),
+            |line| match parse_rum_line(line) {
+                Some(RumProgress::Downloading(Some(percent))) => {
+                    on_progress(10 + (percent * 0.6) as u8, "Downloading update...", None)
+                }
+                Some(RumProgress::Downloading(None)) => {
+                    on_progress(10, "Downloading update...", None)
+                }
+                Some(RumProgress::Installing) => on_progress(75, "Installing update...", None),
+                None => {}
+            },
+        )?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if stderr.contains("cancelled") || stderr.contains("User canceled") {
+            return Err(ElevatedError::UserCancelled);
+        }
+        Ok((output.status.success(), format!("{}\n{}", stdout, stderr)))
+    }
+}
+
+impl UpdateExecutor for AdobeRumExecutor {
+    async fn execute(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> AppResult<UpdateResult> {
+        on_progress(5, "Starting Adobe Remote Update Manager...", None);
+        log::info!(
+            "Adobe RUM: installing {} ({}) for {}",
+            self.sap_code,
+            self.display_name,
+            bundle_id
+        );
+
+        let result = |success: bool, message: String, to_version: Option<String>| UpdateResult {
+            bundle_id: bundle_id.to_string(),
+            success,
+            message: Some(message),
+            source_type: "adobe_cc".to_string(),
+            from_version: self.pre_version.clone(),
+            to_version,
+            handled_relaunch: false,
+            delegated: false,
+            removal: None,
+            team_id_change: None,
+            gatekeeper: None,
+            license: None,
+            error_code: None,
+        };
+
+        let (exited_ok, output) = match self.run_install(on_progress) {
+            Ok(done) => done,
+            Err(ElevatedError::UserCancelled) => {
+                on_progress(100, "Update cancelled", None);
+                return Ok(result(
+                    false,
+                    "Update cancelled \u{2014} administrator approval is required for Adobe updates"
+                        .to_string(),
+                    None,
+                ));
+            }
+            Err(e) => {
+                return Err(AppError::CommandFailed(format!(
+                    "Failed to run RemoteUpdateManager: {}",
+                    e
+                )))
+            }
+        };
+        log::debug!("Adobe RUM output for {}: {}", bundle_id, output.trim());
+
+        if !exited_ok || rum_reported_failure(&output) {
+            let detail = output
+                .lines()
+                .map(str::trim)
+                .rfind(|line| !line.is_empty() && !line.starts_with('*'))
+                .unwrap_or("no output")
+                .to_string();
+            let msg = format!("Adobe Remote Update Manager failed: {}", detail);
+            on_progress(100, &msg, None);
+            return Ok(result(false, msg, None));
+        }
+
+        let new_version =
+            bundle_reader::read_bundle(Path::new(app_path)).and_then(|b| b.installed_version);
+        if let (Some(old), Some(new)) = (&self.pre_version, &new_version) {
+            if old == new {
+                let msg = format!(
+                    "Remote Update Manager finished but {} is still at version {}. \
+                     Open Creative Cloud to apply the update.",
+                    self.display_name, old
+                );
+                on_progress(100, &msg, None);
+                return Ok(result(false, msg, new_version));
+            }
+        }
+
+        on_progress(100, "Update complete", None);
+        Ok(result(
+            true,
+            format!("Updated {} with Remote Update Manager", self.display_name),
+            new_version,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rum_progress() {
+        assert_eq!(
+            parse_rum_line("Downloading (PHSP/26.1) [45%]"),
+            Some(RumProgress::Downloading(Some(45.0)))
+        );
+        assert_eq!(
+            parse_rum_line("Downloading (ILST/29.0)"),
+            Some(RumProgress::Downloading(None))
+        );
+        assert_eq!(
+            parse_rum_line("Installing (PHSP/26.1) ..."),
+            Some(RumProgress::Installing)
+        );
+        assert_eq!(
+            parse_rum_line("RemoteUpdateManager version is : 5.1.0.3"),
+            None
+        );
+        assert!(rum_reported_failure(
+            "Following Updates failed to Install :\n(PHSP/26.1)\n"
+        ));
+        assert!(!rum_reported_failure(
+            "Following Updates were successfully installed :\n(PHSP/26.1)\n"
+        ));
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{FormulaImpact, PackageChange};
+use crate::updaters::homebrew_cask::fetch_brew_outdated_formulae;
+use crate::updaters::BrewOutdatedFormula;
+use crate::utils::brew::{
+    brew_command, brew_path, output_streaming, parse_progress_line, BrewProgress,
+};
+
+/// Something `brew upgrade --cask` reported about one of the casks it upgrades.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaskEvent {
+    Progress {
+        token: String,
+        percent: u8,
+        phase: &'static str,
+    },
+    Upgraded(String),
+    Failed {
+        token: String,
+        message: String,
+    },
+}
+
+/// Attributes the lines of a batched `brew upgrade --cask` to the cask brew is
+/// working on. brew upgrades casks one after the other, announcing each with
+/// `==> Upgrading <token>`.
+pub(crate) struct CaskOutputParser<'a> {
+    tokens: &'a [String],
+    current: Option<String>,
+}
+
+impl<'a> CaskOutputParser<'a> {
+    pub(crate) fn new(tokens: &'a [String]) -> Self {
+        Self {
+            tokens,
+            current: None,
+        }
+    }
+
+    fn known(&self, token: &str) -> Option<String> {
+        self.tokens.iter().find(|t| *t == token).cloned()
+    }
+
+    pub(crate) fn parse_line(&mut self, line: &str) -> Option<CaskEvent> {
+        let line = line.trim();
+        if let Some(token) = line
+            .strip_prefix("==> Upgrading ")
+            .and_then(|t| self.known(t.trim()))
+        {
+            self.current = Some(token.clone());
+            return Some(CaskEvent::Progress {
+                token,
+                percent: 20,
+                phase: "Downloading update...",
+            });
+        }
+        if let Some(token) = line
+            .strip_suffix(" was successfully upgraded!")
+            .and_then(|rest| rest.split_whitespace().last())
+            .and_then(|t| self.known(t))
+        {
+            return Some(CaskEvent::Upgraded(token));
+        }
+        // `Error: <token>: <message>`, or `<token>: <message>` under
+        // `Error: Problems with multiple casks:`
+        let error = line.strip_prefix("Error: ").unwrap_or(line);
+        if let Some((token, message)) = error.split_once(": ") {
+            if let Some(token) = self.known(token.trim()) {
+                return Some(CaskEvent::Failed {
+                    token,
+                    message: message.trim().to_string(),
+                });
+            }
+        }
+        let (percent, phase) = match parse_progress_line(line)? {
+            BrewProgress::Phase(phase @ "Downloading update...") => (30, phase),
+            BrewProgress::Phase(phase @ "Running cleanup...") => (90, phase),
+            BrewProgress::Phase(phase) => (70, phase),
+            BrewProgress::Download { percent, .. } => {
+                (30 + (percent * 0.3) as u8, "Downloading update...")
+            }
+        };
+        self.current.clone().map(|token| CaskEvent::Progress {
+            token,
+            percent,
+            phase,
+        })
+    }
+}
+
+/// Parse `brew deps --for-each` output (`name: dep dep ...`, one line per
+/// formula) into each formula's dependencies.
+pub(crate) fn parse_deps_for_each(output: &str) -> HashMap<String, Vec<String>> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, deps)| {
+            let deps = deps.split_whitespace().map(str::to_string).collect();
+            (name.trim().to_string(), deps)
+        })
+        .collect()
+}
+
+/// Order `names` so every formula comes after the selected formulae it depends
+/// on, keeping the original order otherwise. Cycles keep their original order.
+pub(crate) fn dependency_order(
+    names: &[String],
+    deps: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut remaining: Vec<&String> = names.iter().collect();
+    let mut ordered: Vec<String> = Vec::with_capacity(names.len());
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|name| {
+            deps.get(*name).map_or(true, |d| {
+                d.iter()
+                    .all(|dep| dep == *name || !remaining.contains(&dep))
+            })
+        });
+        // A dependency cycle: take the first one as-is
+        let next = remaining.remove(ready.unwrap_or(0));
+        ordered.push(next.clone());
+    }
+    ordered
+}
+
+/// Installed version of each formula in `brew info --json=v2` output.
+pub(crate) fn parse_installed_versions(json: &str) -> HashMap<String, String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(json) else {
+        return HashMap::new();
+    };
+    json["formulae"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|formula| {
+            let name = formula["name"].as_str()?;
+            let version = formula["installed"].as_array()?.last()?["version"].as_str()?;
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// What else upgrading `name` touches: its outdated dependencies, which brew
+/// upgrades first, and the installed formulae that depend on it, which brew
+/// upgrades when outdated and otherwise checks for broken linkage.
+pub(crate) fn upgrade_impact(
+    name: &str,
+    deps: &[String],
+    dependents: &[String],
+    outdated: &HashMap<String, BrewOutdatedFormula>,
+) -> FormulaImpact {
+    let change = |dep: &String| {
+        outdated.get(dep).map(|o| PackageChange {
+            name: dep.clone(),
+            from_version: o.installed_version.clone(),
+            to_version: o.current_version.clone(),
+        })
+    };
+    let mut impact = FormulaImpact {
+        dependency_upgrades: deps
+            .iter()
+            .filter(|d| *d != name)
+            .filter_map(change)
+            .collect(),
+        ..Default::default()
+    };
+    for dependent in dependents.iter().filter(|d| *d != name) {
+        match change(dependent) {
+            Some(upgrade) => impact.dependent_upgrades.push(upgrade),
+            None => impact.dependents_checked.push(dependent.clone()),
+        }
+    }
+    impact
+}
+
+/// Ask brew what upgrading formula `name` will pull in along with it.
+pub fn formula_upgrade_impact(name: &str) -> FormulaImpact {
+    let Some(brew) = brew_path() else {
+        return FormulaImpact::default();
+    };
+    let stdout = |args: &[&str]| {
+        brew_command(brew)
+            .args(args)
+            .arg(name)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).to_string())
+            .unwrap_or_default()
+    };
+    let deps = parse_deps_for_each(&stdout(&["deps", "--installed", "--for-each"]))
+        .remove(name)
+        .unwrap_or_default();
+    let dependents: Vec<String> = stdout(&["uses", "--installed", "--recursive"])
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    upgrade_impact(name, &deps, &dependents, &fetch_brew_outdated_formulae())
+}
+
+/// Sort formulae so dependencies are upgraded before the formulae that use them.
+pub fn order_formulae(names: &[String]) -> Vec<String> {
+    let Some(brew) = brew_path() else {
+        return names.to_vec();
+    };
+    let output = brew_command(brew)
+        .args(["deps", "--installed", "--for-each"])
+        .args(names)
+        .output();
+    match output {
+        Ok(out) if out.status.success() => dependency_order(
+            names,
+            &parse_deps_for_each(&String::from_utf8_lossy(&out.stdout)),
+        ),
+        _ => names.to_vec(),
+    }
+}
+
+/// Upgrade `names` with a single `brew upgrade --formula` and return the
+/// version of each formula installed afterwards. Formulae that failed, e.g.
+/// because they need administrator privileges, keep their old version and are
+/// left for the caller to retry one at a time.
+pub fn upgrade_formulae(names: &[String]) -> HashMap<String, String> {
+    let Some(brew) = brew_path() else {
+        return HashMap::new();
+    };
+    match brew_command(brew)
+        .args(["upgrade", "--formula"])
+        .args(names)
+        .output()
+    {
+        Ok(out) if !out.status.success() => log::warn!(
+            "Batched brew upgrade of {} formulae failed: {}",
+            names.len(),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to run brew: {}", e),
+    }
+    let versions = brew_command(brew)
+        .args(["info", "--json=v2", "--formula"])
+        .args(names)
+        .output()
+        .map(|out| parse_installed_versions(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default();
+    let _ = brew_command(brew).arg("cleanup").args(names).output();
+    versions
+}
+
+/// Upgrade `tokens` with a single `brew upgrade --cask`, reporting each cask's
+/// progress from brew's output as it runs. Returns the casks brew reported as
+/// upgraded; the rest are left for the caller to retry one at a time.
+pub fn upgrade_casks(tokens: &[String], mut on_event: impl FnMut(CaskEvent)) -> HashSet<String> {
+    let Some(brew) = brew_path() else {
+        return HashSet::new();
+    };
+    let mut parser = CaskOutputParser::new(tokens);
+    let mut upgraded = HashSet::new();
+    let output = output_streaming(
+        brew_command(brew).args(["upgrade", "--cask"]).args(tokens),
+        |line| {
+            let Some(event) = parser.parse_line(line) else {
+                return;
+            };
+            match event {
+                CaskEvent::Upgraded(ref token) => {
+                    upgraded.insert(token.clone());
+                }
+                CaskEvent::Failed {
+                    ref token,
+                    ref message,
+                } => log::warn!("Batched upgrade of cask {} failed: {}", token, message),
+                CaskEvent::Progress { .. } => {}
+            }
+            on_event(event);
+        },
+    );
+    if let Err(e) = output {
+        log::warn!("Failed to run brew: {}", e);
+    }
+    if !upgraded.is_empty() {
+        let _ = brew_command(brew).arg("cleanup").args(&upgraded).output();
+    }
+    upgraded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn dependencies_upgrade_first() {
+        let deps = parse_deps_for_each(
+            "ffmpeg: lame libvpx openssl@3 x264\nopenssl@3: ca-certificates\nx264:\nwget: libidn2 openssl@3\n",
+        );
+        assert_eq!(deps["x264"], Vec::<String>::new());
+        assert_eq!(
+            dependency_order(&names(&["wget", "ffmpeg", "x264", "openssl@3"]), &deps),
+            names(&["x264", "openssl@3", "wget", "ffmpeg"])
+        );
+
+        let cycle = HashMap::from([
+            ("a".to_string(), names(&["b"])),
+            ("b".to_string(), names(&["a"])),
+        ]);
+        assert_eq!(
+            dependency_order(&names(&["a", "b", "c"]), &cycle),
+            names(&["c", "a", "b"])
+        );
+    }
+
+    #[test]
+    fn cask_output_is_attributed_per_cask() {
+        let tokens = names(&["firefox", "slack"]);
+        let mut parser = CaskOutputParser::new(&tokens);
+        let output = "==> Upgrading 2 outdated packages:
+firefox 130.0 -> 131.0
+slack 4.40.0 -> 4.41.0
+==> Upgrading firefox
+==> Downloading https://download-installer.cdn.mozilla.net/firefox-131.0.dmg
+==> Moving App 'Firefox.app' to '/Applications/Firefox.app'
+==> Purging files for version 130.0 of Cask firefox
+\u{1F37A}  firefox was successfully upgraded!
+==> Upgrading slack
+==> Downloading https://downloads.slack-edge.com/slack-4.41.0.dmg
+Error: slack: Download failed on Cask 'slack' with message: Timeout was reached
+";
+        let events: Vec<CaskEvent> = output
+            .lines()
+            .filter_map(|l| parser.parse_line(l))
+            .collect();
+        let progress = |token: &str, percent, phase| CaskEvent::Progress {
+            token: token.to_string(),
+            percent,
+            phase,
+        };
+        assert_eq!(
+            events,
+            vec![
+                progress("firefox", 20, "Downloading update..."),
+                progress("firefox", 30, "Downloading update..."),
+                progress("firefox", 70, "Installing update..."),
+                progress("firefox", 90, "Running cleanup..."),
+                CaskEvent::Upgraded("firefox".to_string()),
+                progress("slack", 20, "Downloading update..."),
+                progress("slack", 30, "Downloading update..."),
+                CaskEvent::Failed {
+                    token: "slack".to_string(),
+                    message: "Download failed on Cask 'slack' with message: Timeout was reached"
+                        .to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn upgrade_impact_splits_dependents() {
+        let outdated = HashMap::from([
+            (
+                "openssl@3".to_string(),
+                BrewOutdatedFormula {
+                    installed_version: "3.3.1".to_string(),
+                    current_version: "3.3.2".to_string(),
+                },
+            ),
+            (
+                "curl".to_string(),
+                BrewOutdatedFormula {
+                    installed_version: "8.9.0".to_string(),
+                    current_version: "8.10.1".to_string(),
+                },
+            ),
+        ]);
+        let impact = upgrade_impact(
+            "libssh2",
+            &names(&["ca-certificates", "openssl@3"]),
+            &names(&["curl", "git", "libssh2"]),
+            &outdated,
+        );
+        assert_eq!(
+            impact.dependency_upgrades,
+            vec![PackageChange {
+                name: "openssl@3".to_string(),
+                from_version: "3.3.1".to_string(),
+                to_version: "3.3.2".to_string(),
+            }]
+        );
+        assert_eq!(impact.dependent_upgrades.len(), 1);
+        assert_eq!(impact.dependent_upgrades[0].name, "curl");
+        assert_eq!(impact.dependents_checked, names(&["git"]));
+    }
+
+    #[test]
+    fn installed_versions_from_brew_info() {
+        let json = r#"{"formulae":[
+            {"name":"wget","installed":[{"version":"1.24.5"},{"version":"1.25.0"}]},
+            {"name":"gone","installed":[]}
+        ],"casks":[]}"#;
+        let versions = parse_installed_versions(json);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions["wget"], "1.25.0");
+        assert!(parse_installed_versions("not json").is_empty());
+    }
+}
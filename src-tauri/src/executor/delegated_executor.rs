@@ -43,6 +43,11 @@ impl UpdateExecutor for DelegatedExecutor {
                 to_version: None,
                 handled_relaunch: false,
                 delegated: true,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -50,14 +55,12 @@ impl UpdateExecutor for DelegatedExecutor {
             on_progress(100, &msg, None);
 
             Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(format!("Failed to open {}: {}", app_path, stderr)),
-                source_type: "sparkle".to_string(),
-                from_version: None,
-                to_version: None,
-                handled_relaunch: false,
                 delegated: true,
+                ..UpdateResult::failed(
+                    bundle_id,
+                    "sparkle",
+                    format!("Failed to open {}: {}", app_path, stderr),
+                )
             })
         }
     }
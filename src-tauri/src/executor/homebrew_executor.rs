@@ -4,19 +4,27 @@ use std::process::Command;
 use regex::Regex;
 
 use crate::detection::bundle_reader;
-use crate::models::UpdateResult;
-use crate::utils::brew::{brew_command, brew_path};
+use crate::models::{UpdateErrorCode, UpdateResult};
+use crate::utils::brew::{brew_command, brew_path, output_streaming, progress_forwarder};
+use crate::utils::brew_health;
 use crate::utils::{AppError, AppResult};
 use super::UpdateExecutor;
 
 pub struct HomebrewExecutor {
     cask_token: String,
     pre_version: Option<String>,
+    /// Reinstall the current version (to repair a damaged bundle) instead of upgrading
+    reinstall: bool,
 }
 
 impl HomebrewExecutor {
     pub fn new(cask_token: String) -> Self {
-        Self { cask_token, pre_version: None }
+        Self { cask_token, pre_version: None, reinstall: false }
+    }
+
+    pub fn with_reinstall(mut self) -> Self {
+        self.reinstall = true;
+        self
     }
 
     pub fn with_pre_version(mut self, version: Option<String>) -> Self {
@@ -25,6 +33,27 @@ impl HomebrewExecutor {
     }
 }
 
+/// Read the bundle at `app_path` after brew ran: whether it changed from
+/// `pre_version`, by version or, for a build-only update, by build number
+/// (`CFBundleVersion`), and the version now installed. A version that can't be
+/// read counts as changed, trusting brew's exit code.
+pub(crate) fn bundle_changed(
+    app_path: &str,
+    pre_version: Option<&str>,
+    pre_build: Option<&str>,
+) -> (bool, Option<String>) {
+    let bundle = bundle_reader::read_bundle(Path::new(app_path));
+    let new_build = bundle.as_ref().and_then(|b| b.bundle_version.clone());
+    let new_version = bundle.and_then(|b| b.installed_version);
+    let changed = match (pre_version, new_version.as_deref()) {
+        (Some(old), Some(new)) => {
+            old != new || (pre_build.is_some() && pre_build != new_build.as_deref())
+        }
+        _ => true,
+    };
+    (changed, new_version)
+}
+
 /// Extract a .pkg path from brew error output (e.g. `/opt/homebrew/Caskroom/…/foo.pkg`).
 fn extract_pkg_path(error_msg: &str) -> Option<String> {
     let re = Regex::new(r#"(/opt/homebrew/Caskroom/[^\s'"]+\.pkg|/usr/local/Caskroom/[^\s'"]+\.pkg)"#)
@@ -43,8 +72,8 @@ impl HomebrewExecutor {
     }
 }
 
-impl UpdateExecutor for HomebrewExecutor {
-    async fn execute(
+impl HomebrewExecutor {
+    async fn run(
         &self,
         bundle_id: &str,
         _app_path: &str,
@@ -58,13 +87,15 @@ impl UpdateExecutor for HomebrewExecutor {
             bundle_reader::read_bundle(Path::new(_app_path))
                 .and_then(|b| b.installed_version)
         });
+        let pre_build = bundle_reader::read_bundle(Path::new(_app_path))
+            .and_then(|b| b.bundle_version);
 
         // If the cask is already installed via Homebrew, upgrade it.
         // Otherwise, install it (this handles apps installed directly outside of brew).
         on_progress(5, "Checking cask status...", None);
 
         let (action, action_past) = if self.is_cask_installed(brew) {
-            ("upgrade", "upgraded")
+            if self.reinstall { ("reinstall", "reinstalled") } else { ("upgrade", "upgraded") }
         } else {
             ("install", "installed")
         };
@@ -80,10 +111,11 @@ impl UpdateExecutor for HomebrewExecutor {
 
         on_progress(20, &format!("Running brew {}...", action), None);
 
-        let output = brew_command(brew)
-            .args(&args)
-            .output()
-            .map_err(|e| AppError::CommandFailed(format!("Failed to run brew: {}", e)))?;
+        let output = output_streaming(
+            brew_command(brew).args(&args),
+            progress_forwarder(on_progress, 20, 50),
+        )
+        .map_err(|e| AppError::CommandFailed(format!("Failed to run brew: {}", e)))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -92,13 +124,9 @@ impl UpdateExecutor for HomebrewExecutor {
             on_progress(50, "Brew command completed", None);
 
             // Re-read bundle to check if version actually changed
-            let new_version = bundle_reader::read_bundle(Path::new(_app_path))
-                .and_then(|b| b.installed_version);
-
-            let actually_changed = match (&pre_version, &new_version) {
-                (Some(old), Some(new)) => old != new,
-                _ => true, // If we can't compare, trust the exit code
-            };
+            let (changed, new_version) =
+                bundle_changed(_app_path, pre_version.as_deref(), pre_build.as_deref());
+            let actually_changed = self.reinstall || changed;
 
             if !actually_changed {
                 let msg = format!(
@@ -110,14 +138,9 @@ impl UpdateExecutor for HomebrewExecutor {
                 );
                 on_progress(100, &msg, None);
                 return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(msg),
-                    source_type: "homebrew_cask".to_string(),
                     from_version: pre_version,
                     to_version: new_version,
-                    handled_relaunch: false,
-                    delegated: false,
+                    ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                 });
             }
 
@@ -138,6 +161,11 @@ impl UpdateExecutor for HomebrewExecutor {
                 to_version: new_version,
                 handled_relaunch: false,
                 delegated: false,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let error_msg = if stderr.is_empty() { &stdout } else { &stderr };
@@ -172,13 +200,9 @@ impl UpdateExecutor for HomebrewExecutor {
 
                             on_progress(70, "Verifying installation...", None);
 
-                            let new_version = bundle_reader::read_bundle(Path::new(_app_path))
-                                .and_then(|b| b.installed_version);
-
-                            let actually_changed = match (&pre_version, &new_version) {
-                                (Some(old), Some(new)) => old != new,
-                                _ => true,
-                            };
+                            let (changed, new_version) =
+                                bundle_changed(_app_path, pre_version.as_deref(), pre_build.as_deref());
+                            let actually_changed = self.reinstall || changed;
 
                             if !actually_changed {
                                 let msg = format!(
@@ -190,14 +214,9 @@ impl UpdateExecutor for HomebrewExecutor {
                                 );
                                 on_progress(100, &msg, None);
                                 return Ok(UpdateResult {
-                                    bundle_id: bundle_id.to_string(),
-                                    success: false,
-                                    message: Some(msg),
-                                    source_type: "homebrew_cask".to_string(),
                                     from_version: pre_version,
                                     to_version: new_version,
-                                    handled_relaunch: false,
-                                    delegated: false,
+                                    ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                                 });
                             }
 
@@ -219,6 +238,11 @@ impl UpdateExecutor for HomebrewExecutor {
                                 to_version: new_version,
                                 handled_relaunch: false,
                                 delegated: false,
+                                removal: None,
+                                team_id_change: None,
+                                gatekeeper: None,
+                                license: None,
+                                error_code: None,
                             });
                         }
                         Ok(_) | Err(crate::utils::sudo_session::ElevatedError::CommandFailed(_))
@@ -230,28 +254,17 @@ impl UpdateExecutor for HomebrewExecutor {
                             );
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_cask".to_string(),
                                 from_version: pre_version,
-                                to_version: None,
-                                handled_relaunch: false,
-                                delegated: false,
+                                ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                             });
                         }
                         Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
                             let msg = "Update cancelled \u{2014} administrator approval is required for this cask".to_string();
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_cask".to_string(),
                                 from_version: pre_version,
-                                to_version: None,
-                                handled_relaunch: false,
-                                delegated: false,
+                                error_code: Some(UpdateErrorCode::UserCancelled),
+                                ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                             });
                         }
                     }
@@ -273,15 +286,12 @@ impl UpdateExecutor for HomebrewExecutor {
                     }
                     retry_cmd.args(&retry_args);
 
-                    if let Ok(retry_out) = retry_cmd.output() {
+                    if let Ok(retry_out) = output_streaming(&mut retry_cmd, progress_forwarder(on_progress, 30, 60)) {
                         if retry_out.status.success() {
                             on_progress(60, "Brew command completed", None);
-                            let new_version = bundle_reader::read_bundle(Path::new(_app_path))
-                                .and_then(|b| b.installed_version);
-                            let actually_changed = match (&pre_version, &new_version) {
-                                (Some(old), Some(new)) => old != new,
-                                _ => true,
-                            };
+                            let (changed, new_version) =
+                                bundle_changed(_app_path, pre_version.as_deref(), pre_build.as_deref());
+                            let actually_changed = self.reinstall || changed;
                             if actually_changed {
                                 on_progress(90, "Running cleanup...", None);
                                 let _ = brew_command(brew)
@@ -300,6 +310,11 @@ impl UpdateExecutor for HomebrewExecutor {
                                     to_version: new_version,
                                     handled_relaunch: false,
                                     delegated: false,
+                                    removal: None,
+                                    team_id_change: None,
+                                    gatekeeper: None,
+                                    license: None,
+                                    error_code: None,
                                 });
                             }
                         }
@@ -334,13 +349,9 @@ impl UpdateExecutor for HomebrewExecutor {
                     Ok(osa_output) if osa_output.status.success() => {
                         on_progress(60, "Brew command completed", None);
 
-                        let new_version = bundle_reader::read_bundle(Path::new(_app_path))
-                            .and_then(|b| b.installed_version);
-
-                        let actually_changed = match (&pre_version, &new_version) {
-                            (Some(old), Some(new)) => old != new,
-                            _ => true,
-                        };
+                        let (changed, new_version) =
+                            bundle_changed(_app_path, pre_version.as_deref(), pre_build.as_deref());
+                        let actually_changed = self.reinstall || changed;
 
                         if !actually_changed {
                             let msg = format!(
@@ -352,14 +363,9 @@ impl UpdateExecutor for HomebrewExecutor {
                             );
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_cask".to_string(),
                                 from_version: pre_version,
                                 to_version: new_version,
-                                handled_relaunch: false,
-                                delegated: false,
+                                ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                             });
                         }
 
@@ -380,49 +386,39 @@ impl UpdateExecutor for HomebrewExecutor {
                             to_version: new_version,
                             handled_relaunch: false,
                             delegated: false,
+                            removal: None,
+                            team_id_change: None,
+                            gatekeeper: None,
+                            license: None,
+                            error_code: None,
                         });
                     }
                     Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
                         let msg = "Update cancelled \u{2014} administrator approval is required for this cask".to_string();
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_cask".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            error_code: Some(UpdateErrorCode::UserCancelled),
+                            ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                         });
                     }
                     Ok(osa_output) => {
                         let osa_stderr = String::from_utf8_lossy(&osa_output.stderr).to_string();
-                        let msg = format!("Homebrew {} failed (elevated): {}", action, osa_stderr);
+                        let msg = brew_health::record_failure().unwrap_or_else(|| {
+                            format!("Homebrew {} failed (elevated): {}", action, osa_stderr)
+                        });
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_cask".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                         });
                     }
                     Err(e) => {
                         let msg = format!("Homebrew {} failed: could not request admin privileges: {}", action, e);
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_cask".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                         });
                     }
                 }
@@ -435,34 +431,41 @@ impl UpdateExecutor for HomebrewExecutor {
                      Privacy & Security > App Management, then try again.".to_string();
                 on_progress(100, &msg, None);
                 return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(msg),
-                    source_type: "homebrew_cask".to_string(),
                     from_version: pre_version,
-                    to_version: None,
-                    handled_relaunch: false,
-                    delegated: false,
+                    ..UpdateResult::failed(bundle_id, "homebrew_cask", msg)
                 });
             }
 
-            // Non-sudo error — return as-is
-            let msg = format!("Homebrew {} failed: {}", action, error_msg);
+            // Non-sudo error — return as-is, unless brew keeps failing and the
+            // health check finds out why
+            let explanation = brew_health::record_failure();
+            let msg = explanation
+                .clone()
+                .unwrap_or_else(|| format!("Homebrew {} failed: {}", action, error_msg));
             on_progress(100, &msg, None);
 
+            let message = explanation.unwrap_or_else(|| {
+                format!("Failed to {} cask '{}': {}", action, self.cask_token, error_msg)
+            });
             Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(format!(
-                    "Failed to {} cask '{}': {}",
-                    action, self.cask_token, error_msg
-                )),
-                source_type: "homebrew_cask".to_string(),
                 from_version: pre_version,
-                to_version: None,
-                handled_relaunch: false,
-                delegated: false,
+                ..UpdateResult::failed(bundle_id, "homebrew_cask", message)
             })
         }
     }
 }
+
+impl UpdateExecutor for HomebrewExecutor {
+    async fn execute(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> AppResult<UpdateResult> {
+        let result = self.run(bundle_id, app_path, on_progress).await?;
+        if result.success {
+            brew_health::record_success();
+        }
+        Ok(result)
+    }
+}
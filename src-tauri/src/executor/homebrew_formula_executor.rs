@@ -2,8 +2,8 @@ use std::process::Command;
 
 use regex::Regex;
 
-use crate::models::UpdateResult;
-use crate::utils::brew::{brew_command, brew_path};
+use crate::models::{UpdateErrorCode, UpdateResult};
+use crate::utils::brew::{brew_command, brew_path, output_streaming, progress_forwarder};
 use crate::utils::{is_xcode_clt_installed, AppError, AppResult};
 use super::UpdateExecutor;
 
@@ -67,16 +67,7 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
         if !is_xcode_clt_installed() {
             let msg = "Xcode Command Line Tools required. Install with: xcode-select --install";
             on_progress(100, &msg, None);
-            return Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(msg.to_string()),
-                source_type: "homebrew_formula".to_string(),
-                from_version: None,
-                to_version: None,
-                handled_relaunch: false,
-                delegated: false,
-            });
+            return Ok(UpdateResult::failed(bundle_id, "homebrew_formula", msg));
         }
 
         // Capture pre-install version
@@ -88,10 +79,11 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
         on_progress(10, &format!("Preparing to upgrade {}...", self.formula_name), None);
         on_progress(20, &format!("Running brew upgrade {}...", self.formula_name), None);
 
-        let output = brew_command(&brew)
-            .args(["upgrade", &self.formula_name])
-            .output()
-            .map_err(|e| AppError::CommandFailed(format!("Failed to run brew: {}", e)))?;
+        let output = output_streaming(
+            brew_command(&brew).args(["upgrade", &self.formula_name]),
+            progress_forwarder(on_progress, 20, 50),
+        )
+        .map_err(|e| AppError::CommandFailed(format!("Failed to run brew: {}", e)))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -116,14 +108,9 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                 );
                 on_progress(100, &msg, None);
                 return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(msg),
-                    source_type: "homebrew_formula".to_string(),
                     from_version: pre_version,
                     to_version: new_version,
-                    handled_relaunch: false,
-                    delegated: false,
+                    ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                 });
             }
 
@@ -143,6 +130,11 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                 to_version: new_version,
                 handled_relaunch: false,
                 delegated: false,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let error_msg = if stderr.is_empty() { &stdout } else { &stderr };
@@ -193,14 +185,9 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                                 );
                                 on_progress(100, &msg, None);
                                 return Ok(UpdateResult {
-                                    bundle_id: bundle_id.to_string(),
-                                    success: false,
-                                    message: Some(msg),
-                                    source_type: "homebrew_formula".to_string(),
                                     from_version: pre_version,
                                     to_version: new_version,
-                                    handled_relaunch: false,
-                                    delegated: false,
+                                    ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                                 });
                             }
 
@@ -222,20 +209,20 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                                 to_version: new_version,
                                 handled_relaunch: false,
                                 delegated: false,
+                                removal: None,
+                                team_id_change: None,
+                                gatekeeper: None,
+                                license: None,
+                                error_code: None,
                             });
                         }
                         Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
                             let msg = "Upgrade cancelled \u{2014} administrator approval is required".to_string();
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_formula".to_string(),
                                 from_version: pre_version,
-                                to_version: None,
-                                handled_relaunch: false,
-                                delegated: false,
+                                error_code: Some(UpdateErrorCode::UserCancelled),
+                                ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                             });
                         }
                         Ok(_) | Err(_) => {
@@ -246,14 +233,8 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                             );
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_formula".to_string(),
                                 from_version: pre_version,
-                                to_version: None,
-                                handled_relaunch: false,
-                                delegated: false,
+                                ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                             });
                         }
                     }
@@ -270,7 +251,7 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                     }
                     retry_cmd.args(["-A", brew.to_str().unwrap_or("brew"), "upgrade", &self.formula_name]);
 
-                    if let Ok(retry_out) = retry_cmd.output() {
+                    if let Ok(retry_out) = output_streaming(&mut retry_cmd, progress_forwarder(on_progress, 30, 60)) {
                         if retry_out.status.success() {
                             on_progress(60, "Brew command completed", None);
                             let new_version = Self::get_formula_version(&brew, &self.formula_name);
@@ -296,6 +277,11 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                                     to_version: new_version,
                                     handled_relaunch: false,
                                     delegated: false,
+                                    removal: None,
+                                    team_id_change: None,
+                                    gatekeeper: None,
+                                    license: None,
+                                    error_code: None,
                                 });
                             }
                         }
@@ -336,14 +322,9 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                             );
                             on_progress(100, &msg, None);
                             return Ok(UpdateResult {
-                                bundle_id: bundle_id.to_string(),
-                                success: false,
-                                message: Some(msg),
-                                source_type: "homebrew_formula".to_string(),
                                 from_version: pre_version,
                                 to_version: new_version,
-                                handled_relaunch: false,
-                                delegated: false,
+                                ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                             });
                         }
 
@@ -365,6 +346,11 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                             to_version: new_version,
                             handled_relaunch: false,
                             delegated: false,
+                            removal: None,
+                            team_id_change: None,
+                            gatekeeper: None,
+                            license: None,
+                            error_code: None,
                         });
                     }
                     Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
@@ -374,14 +360,9 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                         );
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_formula".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            error_code: Some(UpdateErrorCode::UserCancelled),
+                            ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                         });
                     }
                     Ok(osa_output) => {
@@ -389,28 +370,16 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                         let msg = format!("Homebrew upgrade failed (elevated): {}", osa_stderr);
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_formula".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                         });
                     }
                     Err(e) => {
                         let msg = format!("Homebrew upgrade failed: could not request admin privileges: {}", e);
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: "homebrew_formula".to_string(),
                             from_version: pre_version,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                         });
                     }
                 }
@@ -423,14 +392,8 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
                      Privacy & Security > App Management, then try again.".to_string();
                 on_progress(100, &msg, None);
                 return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(msg),
-                    source_type: "homebrew_formula".to_string(),
                     from_version: pre_version,
-                    to_version: None,
-                    handled_relaunch: false,
-                    delegated: false,
+                    ..UpdateResult::failed(bundle_id, "homebrew_formula", msg)
                 });
             }
 
@@ -438,17 +401,15 @@ impl UpdateExecutor for HomebrewFormulaExecutor {
             on_progress(100, &msg, None);
 
             Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(format!(
-                    "Failed to upgrade formula '{}': {}",
-                    self.formula_name, error_msg
-                )),
-                source_type: "homebrew_formula".to_string(),
                 from_version: pre_version,
-                to_version: None,
-                handled_relaunch: false,
-                delegated: false,
+                ..UpdateResult::failed(
+                    bundle_id,
+                    "homebrew_formula",
+                    format!(
+                        "Failed to upgrade formula '{}': {}",
+                        self.formula_name, error_msg
+                    ),
+                )
             })
         }
     }
@@ -0,0 +1,79 @@
+use std::process::Command;
+
+use super::UpdateExecutor;
+use crate::models::UpdateResult;
+use crate::utils::{AppError, AppResult};
+
+/// Hands updates of Toolbox-installed IDEs to JetBrains Toolbox, which keeps
+/// track of their versions and settings itself.
+pub struct JetBrainsToolboxExecutor {
+    display_name: String,
+    pre_version: Option<String>,
+}
+
+impl JetBrainsToolboxExecutor {
+    pub fn new(display_name: String) -> Self {
+        Self {
+            display_name,
+            pre_version: None,
+        }
+    }
+
+    pub fn with_pre_version(mut self, version: Option<String>) -> Self {
+        self.pre_version = version;
+        self
+    }
+}
+
+impl UpdateExecutor for JetBrainsToolboxExecutor {
+    async fn execute(
+        &self,
+        bundle_id: &str,
+        _app_path: &str,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> AppResult<UpdateResult> {
+        on_progress(10, "Opening JetBrains Toolbox...", None);
+
+        // Toolbox shows the pending update in its tool list and starts it from there
+        let output = Command::new("open")
+            .current_dir("/tmp")
+            .args(["-b", "com.jetbrains.toolbox"])
+            .output()
+            .map_err(|e| {
+                AppError::CommandFailed(format!("Failed to open JetBrains Toolbox: {}", e))
+            })?;
+
+        let success = output.status.success();
+        let message = if success {
+            on_progress(100, "Opened JetBrains Toolbox", None);
+            format!(
+                "Opened JetBrains Toolbox \u{2014} click Update next to {}",
+                self.display_name
+            )
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            on_progress(
+                100,
+                &format!("Failed to open JetBrains Toolbox: {}", stderr),
+                None,
+            );
+            format!("Failed to open JetBrains Toolbox: {}", stderr.trim())
+        };
+
+        Ok(UpdateResult {
+            bundle_id: bundle_id.to_string(),
+            success,
+            message: Some(message),
+            source_type: "jetbrains_toolbox".to_string(),
+            from_version: self.pre_version.clone(),
+            to_version: None,
+            handled_relaunch: false,
+            delegated: true,
+            removal: None,
+            team_id_change: None,
+            gatekeeper: None,
+            license: None,
+            error_code: None,
+        })
+    }
+}
@@ -118,6 +118,11 @@ impl UpdateExecutor for MasExecutor {
                         to_version: new_version,
                         handled_relaunch: false,
                         delegated: false,
+                        removal: None,
+                        team_id_change: None,
+                        gatekeeper: None,
+                        license: None,
+                        error_code: None,
                     });
                 }
 
@@ -188,6 +193,11 @@ impl UpdateExecutor for MasExecutor {
                         to_version: new_version,
                         handled_relaunch: false,
                         delegated: false,
+                        removal: None,
+                        team_id_change: None,
+                        gatekeeper: None,
+                        license: None,
+                        error_code: None,
                     });
                 }
 
@@ -250,6 +260,11 @@ impl MasExecutor {
                 to_version: None,
                 handled_relaunch: false,
                 delegated: true,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -289,6 +304,11 @@ impl MasExecutor {
                 to_version: None,
                 handled_relaunch: false,
                 delegated: true,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -1,14 +1,17 @@
 use std::path::Path;
 use std::process::Command;
+use std::time::{Duration, Instant};
 
+use crate::detection::bundle_reader;
 use crate::models::UpdateResult;
 use crate::updaters::microsoft_autoupdate::lookup_hardcoded_token;
+use crate::utils::command::run_command_with_timeout;
 use crate::utils::{AppError, AppResult};
 use super::homebrew_executor::HomebrewExecutor;
 use super::UpdateExecutor;
 
 /// Path to the Microsoft AutoUpdate `msupdate` CLI binary.
-const MSUPDATE_PATH: &str = "/Library/Application Support/Microsoft/MAU2.0/Microsoft AutoUpdate.app/Contents/MacOS/msupdate";
+pub(crate) const MSUPDATE_PATH: &str = "/Library/Application Support/Microsoft/MAU2.0/Microsoft AutoUpdate.app/Contents/MacOS/msupdate";
 
 /// Maps bundle IDs to the `msupdate --apps` identifiers.
 const MSUPDATE_APP_IDS: &[(&str, &str)] = &[
@@ -24,10 +27,29 @@ const MSUPDATE_APP_IDS: &[(&str, &str)] = &[
     ("com.microsoft.VSCode", "VSCO01"),
 ];
 
+/// How often `msupdate --list` is polled while an install runs.
+const LIST_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Give up on an `msupdate --install` that hasn't finished by then. Office
+/// updates download a gigabyte or more.
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(45 * 60);
+
+/// App IDs with a pending update in `msupdate --list` output, which lists each
+/// update as an indented block with an `Application ID: MSWD2019` line.
+fn pending_app_ids(list_output: &str) -> Vec<String> {
+    list_output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Application ID:"))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
 pub struct MicrosoftAutoUpdateExecutor {
     cask_token: Option<String>,
     pre_version: Option<String>,
     display_name: String,
+    homebrew_managed: bool,
 }
 
 impl MicrosoftAutoUpdateExecutor {
@@ -36,6 +58,7 @@ impl MicrosoftAutoUpdateExecutor {
             cask_token: None,
             pre_version: None,
             display_name,
+            homebrew_managed: false,
         }
     }
 
@@ -49,6 +72,13 @@ impl MicrosoftAutoUpdateExecutor {
         self
     }
 
+    /// Whether the app was installed with `brew install --cask`. Homebrew then
+    /// goes first so its cask record stays in step with the app.
+    pub fn with_homebrew_managed(mut self, managed: bool) -> Self {
+        self.homebrew_managed = managed;
+        self
+    }
+
     /// Resolve a cask token from the detail or the hardcoded mapping.
     fn resolve_cask_token(&self, bundle_id: &str) -> Option<String> {
         self.cask_token.clone().or_else(|| {
@@ -57,7 +87,7 @@ impl MicrosoftAutoUpdateExecutor {
     }
 
     /// Look up the msupdate app ID for a given bundle ID.
-    fn msupdate_app_id(bundle_id: &str) -> Option<&'static str> {
+    pub(crate) fn msupdate_app_id(bundle_id: &str) -> Option<&'static str> {
         MSUPDATE_APP_IDS
             .iter()
             .find(|(bid, _)| *bid == bundle_id)
@@ -68,6 +98,147 @@ impl MicrosoftAutoUpdateExecutor {
     fn mau_installed() -> bool {
         Path::new(MSUPDATE_PATH).exists()
     }
+
+    /// Whether `msupdate --list` still shows an update for `app_id`, or `None`
+    /// when MAU didn't answer.
+    async fn update_pending(app_id: &str) -> Option<bool> {
+        let output = run_command_with_timeout(MSUPDATE_PATH, &["--list", "--apps", app_id], 60)
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Some(pending_app_ids(&stdout).iter().any(|id| id == app_id))
+    }
+
+    /// Install the update with `msupdate --install`, which runs without the MAU
+    /// window, polling `msupdate --list` for progress until it exits. `Err`
+    /// carries why the next tier should be tried.
+    async fn install_with_msupdate(
+        &self,
+        bundle_id: &str,
+        app_id: &str,
+        app_path: &str,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> Result<UpdateResult, String> {
+        on_progress(10, "Installing with Microsoft AutoUpdate...", None);
+        log::info!("Microsoft executor: msupdate --install --apps {}", app_id);
+
+        let install = tokio::process::Command::new(MSUPDATE_PATH)
+            .current_dir("/tmp")
+            .args(["--install", "--apps", app_id])
+            .kill_on_drop(true)
+            .output();
+        tokio::pin!(install);
+
+        let started = Instant::now();
+        let mut poll = tokio::time::interval(LIST_POLL_INTERVAL);
+        poll.tick().await;
+        let output = loop {
+            tokio::select! {
+                output = &mut install => break output,
+                _ = poll.tick() => {
+                    if started.elapsed() > INSTALL_TIMEOUT {
+                        return Err(format!(
+                            "msupdate didn't finish within {} minutes",
+                            INSTALL_TIMEOUT.as_secs() / 60
+                        ));
+                    }
+                    // MAU reports no percentage; the update leaving the list
+                    // means the files are in place and MAU is wrapping up
+                    match Self::update_pending(app_id).await {
+                        Some(false) => on_progress(90, "Finishing update...", None),
+                        _ => {
+                            let waited = started.elapsed().as_secs() / 10;
+                            let percent = 15 + waited.min(70) as u8;
+                            on_progress(percent, "Downloading and installing update...", None);
+                        }
+                    }
+                }
+            }
+        }
+        .map_err(|e| format!("failed to run msupdate: {}", e))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        log::debug!("Microsoft executor: msupdate output for {}: {}", bundle_id, stdout.trim());
+        if !output.status.success() {
+            return Err(format!(
+                "msupdate exited with {}: {}",
+                output.status.code().unwrap_or(-1),
+                stderr.trim()
+            ));
+        }
+
+        // msupdate also exits 0 when MAU deferred the update, e.g. because the
+        // app is open; only a changed bundle version counts
+        let new_version =
+            bundle_reader::read_bundle(Path::new(app_path)).and_then(|b| b.installed_version);
+        if let (Some(old), Some(new)) = (&self.pre_version, &new_version) {
+            if old == new {
+                return Err(format!("{} is still at version {}", self.display_name, old));
+            }
+        }
+
+        on_progress(100, "Microsoft AutoUpdate completed", None);
+        Ok(UpdateResult {
+            bundle_id: bundle_id.to_string(),
+            success: true,
+            message: Some(format!(
+                "Updated {} via Microsoft AutoUpdate",
+                self.display_name
+            )),
+            source_type: "microsoft_autoupdate".to_string(),
+            from_version: self.pre_version.clone(),
+            to_version: new_version,
+            handled_relaunch: false,
+            delegated: false,
+            removal: None,
+            team_id_change: None,
+            gatekeeper: None,
+            license: None,
+            error_code: None,
+        })
+    }
+
+    /// Update with Homebrew, or `None` when the app has no cask or brew failed.
+    async fn install_with_homebrew(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> Option<AppResult<UpdateResult>> {
+        let Some(token) = self.resolve_cask_token(bundle_id) else {
+            log::info!("Microsoft executor: no cask token for {}, skipping Homebrew", bundle_id);
+            return None;
+        };
+        on_progress(5, "Trying Homebrew update...", None);
+        log::info!("Microsoft executor: trying brew upgrade --cask {}", token);
+
+        let result = HomebrewExecutor::new(token)
+            .with_pre_version(self.pre_version.clone())
+            .execute(bundle_id, app_path, on_progress)
+            .await;
+
+        match &result {
+            Ok(r) if r.success => {
+                log::info!("Microsoft executor: Homebrew succeeded for {}", bundle_id);
+                return Some(result);
+            }
+            Ok(r) => {
+                log::info!(
+                    "Microsoft executor: Homebrew failed for {} ({})",
+                    bundle_id,
+                    r.message.as_deref().unwrap_or("unknown error")
+                );
+            }
+            Err(e) => {
+                log::info!("Microsoft executor: Homebrew error for {} ({})", bundle_id, e);
+            }
+        }
+        None
+    }
 }
 
 impl UpdateExecutor for MicrosoftAutoUpdateExecutor {
@@ -77,91 +248,31 @@ impl UpdateExecutor for MicrosoftAutoUpdateExecutor {
         app_path: &str,
         on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
     ) -> AppResult<UpdateResult> {
-        // === Tier 1: Try Homebrew ===
-        if let Some(token) = self.resolve_cask_token(bundle_id) {
-            on_progress(5, "Trying Homebrew update...", None);
-            log::info!("Microsoft executor: Tier 1 — trying brew upgrade --cask {}", token);
-
-            let result = HomebrewExecutor::new(token.clone())
-                .with_pre_version(self.pre_version.clone())
-                .execute(bundle_id, app_path, on_progress)
-                .await;
-
-            match &result {
-                Ok(r) if r.success => {
-                    log::info!("Microsoft executor: Tier 1 succeeded for {}", bundle_id);
-                    return result;
-                }
-                Ok(r) => {
-                    log::info!(
-                        "Microsoft executor: Tier 1 failed for {} ({}), trying Tier 2",
-                        bundle_id,
-                        r.message.as_deref().unwrap_or("unknown error")
-                    );
-                }
-                Err(e) => {
-                    log::info!(
-                        "Microsoft executor: Tier 1 error for {} ({}), trying Tier 2",
-                        bundle_id, e
-                    );
-                }
-            }
-        } else {
-            log::info!("Microsoft executor: no cask token for {}, skipping Tier 1", bundle_id);
+        let msupdate_app_id = Self::msupdate_app_id(bundle_id).filter(|_| Self::mau_installed());
+        if msupdate_app_id.is_none() {
+            log::info!("Microsoft executor: msupdate unavailable for {}", bundle_id);
         }
 
-        // === Tier 2: Try msupdate CLI ===
-        if Self::mau_installed() {
-            if let Some(app_id) = Self::msupdate_app_id(bundle_id) {
-                on_progress(30, "Trying Microsoft AutoUpdate CLI...", None);
-                log::info!("Microsoft executor: Tier 2 — trying msupdate --install --apps {}", app_id);
-
-                let output = Command::new(MSUPDATE_PATH)
-                    .args(["--install", "--apps", app_id])
-                    .output();
-
-                match output {
-                    Ok(o) if o.status.success() => {
-                        let stdout = String::from_utf8_lossy(&o.stdout);
-                        log::info!("Microsoft executor: Tier 2 succeeded for {}: {}", bundle_id, stdout.trim());
-                        on_progress(100, "Microsoft AutoUpdate completed", None);
-
-                        return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: true,
-                            message: Some(format!(
-                                "Updated {} via Microsoft AutoUpdate",
-                                self.display_name
-                            )),
-                            source_type: "microsoft_autoupdate".to_string(),
-                            from_version: self.pre_version.clone(),
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
-                        });
-                    }
-                    Ok(o) => {
-                        let stderr = String::from_utf8_lossy(&o.stderr);
-                        log::info!(
-                            "Microsoft executor: Tier 2 failed for {} (exit {}): {}",
-                            bundle_id,
-                            o.status.code().unwrap_or(-1),
-                            stderr.trim()
-                        );
-                    }
-                    Err(e) => {
-                        log::info!("Microsoft executor: Tier 2 error for {}: {}", bundle_id, e);
+        // === Tier 1: Homebrew for casks it installed, msupdate otherwise ===
+        // === Tier 2: whichever of the two didn't run first ===
+        let brew_first = self.homebrew_managed || msupdate_app_id.is_none();
+        for use_brew in [brew_first, !brew_first] {
+            if use_brew {
+                if let Some(result) = self.install_with_homebrew(bundle_id, app_path, on_progress).await {
+                    return result;
+                }
+            } else if let Some(app_id) = msupdate_app_id {
+                match self.install_with_msupdate(bundle_id, app_id, app_path, on_progress).await {
+                    Ok(result) => return Ok(result),
+                    Err(reason) => {
+                        log::info!("Microsoft executor: msupdate failed for {}: {}", bundle_id, reason);
                     }
                 }
-            } else {
-                log::info!("Microsoft executor: no msupdate app ID for {}, skipping Tier 2", bundle_id);
             }
-        } else {
-            log::info!("Microsoft executor: MAU not installed, skipping Tier 2");
         }
 
         // === Tier 3: Open Microsoft AutoUpdate app (or the app itself) ===
-        on_progress(50, "Opening Microsoft AutoUpdate...", None);
+        on_progress(95, "Opening Microsoft AutoUpdate...", None);
 
         if Self::mau_installed() {
             log::info!("Microsoft executor: Tier 3 — opening MAU app");
@@ -185,6 +296,11 @@ impl UpdateExecutor for MicrosoftAutoUpdateExecutor {
                         to_version: None,
                         handled_relaunch: false,
                         delegated: true,
+                        removal: None,
+                        team_id_change: None,
+                        gatekeeper: None,
+                        license: None,
+                        error_code: None,
                     });
                 }
                 _ => {
@@ -215,20 +331,44 @@ impl UpdateExecutor for MicrosoftAutoUpdateExecutor {
                 to_version: None,
                 handled_relaunch: false,
                 delegated: true,
+                removal: None,
+                team_id_change: None,
+                gatekeeper: None,
+                license: None,
+                error_code: None,
             })
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
             on_progress(100, &format!("Failed to open app: {}", stderr), None);
             Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(format!("Failed to open {}: {}", app_path, stderr)),
-                source_type: "microsoft_autoupdate".to_string(),
                 from_version: self.pre_version.clone(),
-                to_version: None,
-                handled_relaunch: false,
                 delegated: true,
+                ..UpdateResult::failed(
+                    bundle_id,
+                    "microsoft_autoupdate",
+                    format!("Failed to open {}: {}", app_path, stderr),
+                )
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_pending_updates_from_list() {
+        let output = "Checking for updates...\n\
+                      Updates available:\n\
+                      \tMicrosoft Word\n\
+                      \t\tApplication ID: MSWD2019\n\
+                      \t\tVersion: 16.89.24091630\n\
+                      \t\tSize: 1.1 GB\n\
+                      \tMicrosoft Teams\n\
+                      \t\tApplication ID: TEAMS21\n\
+                      \t\tVersion: 24243.1309.3132.617\n";
+        assert_eq!(pending_app_ids(output), vec!["MSWD2019", "TEAMS21"]);
+        assert!(pending_app_ids("Checking for updates...\nNo updates available\n").is_empty());
+    }
+}
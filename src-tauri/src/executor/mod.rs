@@ -1,11 +1,19 @@
+pub mod adobe_rum_executor;
+pub mod brew_batch;
 pub mod homebrew_executor;
 pub mod homebrew_formula_executor;
+pub mod jetbrains_toolbox_executor;
 pub mod mas_executor;
 pub mod delegated_executor;
 pub mod sparkle_executor;
 pub mod microsoft_autoupdate_executor;
+pub mod recovery;
 
-use crate::models::UpdateResult;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::models::{UpdateErrorCode, UpdateResult};
 use crate::utils::AppResult;
 
 pub trait UpdateExecutor: Send + Sync {
@@ -16,3 +24,106 @@ pub trait UpdateExecutor: Send + Sync {
         on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
     ) -> AppResult<UpdateResult>;
 }
+
+/// Error text that points at a network problem worth retrying rather than a
+/// problem with the update itself.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "timed out",
+    "timeout",
+    "dns error",
+    "failed to lookup address",
+    "could not resolve host",
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "error sending request",
+    "network is unreachable",
+    "temporarily unavailable",
+];
+
+/// An HTTP 5xx status in error text, from the server or from curl inside brew.
+static RE_SERVER_ERROR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:http[/\d.]*|returned error:|status)\s*5\d\d\b").unwrap());
+
+/// Whether an update failed for a transient reason: a timeout, a DNS or
+/// connection failure, or an HTTP 5xx from the server or from curl inside brew.
+pub fn is_transient_failure(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    if TRANSIENT_MARKERS.iter().any(|marker| lower.contains(marker)) {
+        return true;
+    }
+    RE_SERVER_ERROR.is_match(&lower)
+}
+
+/// Error code for a failure an executor only described in text, e.g. brew's
+/// own error output. Only a fallback: failures macPlus words itself (and may
+/// localize) set their `error_code` where they're created.
+pub fn classify_failure(message: &str) -> UpdateErrorCode {
+    let lower = message.to_lowercase();
+    if is_transient_failure(message) {
+        UpdateErrorCode::NetworkError
+    } else if lower.contains("sha256 mismatch") || lower.contains("checksum") {
+        UpdateErrorCode::ChecksumMismatch
+    } else if lower.contains("cancelled") || lower.contains("user canceled") {
+        UpdateErrorCode::UserCancelled
+    } else if ["app management", "operation not permitted", "permission denied", "administrator", "sudo"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+    {
+        UpdateErrorCode::NeedsElevation
+    } else if lower.contains("still at version") {
+        UpdateErrorCode::VersionUnchanged
+    } else {
+        UpdateErrorCode::Unknown
+    }
+}
+
+/// Fill in `error_code` for a failed update whose executor didn't set one.
+pub fn set_error_code(result: &mut UpdateResult) {
+    if result.error_code.is_some() || result.success || result.delegated {
+        return;
+    }
+    result.error_code = Some(if result.team_id_change.is_some() {
+        UpdateErrorCode::TeamIdMismatch
+    } else {
+        classify_failure(result.message.as_deref().unwrap_or_default())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_transient_failures() {
+        assert!(is_transient_failure("Download failed: error sending request for url (https://x/y.dmg)"));
+        assert!(is_transient_failure("Download returned HTTP 503 Service Unavailable"));
+        assert!(is_transient_failure("curl: (22) The requested URL returned error: 502"));
+        assert!(is_transient_failure("curl: (6) Could not resolve host: github.com"));
+        assert!(!is_transient_failure("Download returned HTTP 404 Not Found"));
+        assert!(!is_transient_failure("Failed to replace app: Permission denied"));
+        assert!(!is_transient_failure("Unsupported archive format: foo.tar.xz"));
+    }
+
+    #[test]
+    fn classifies_failure_messages() {
+        assert_eq!(
+            classify_failure("Update cancelled \u{2014} administrator approval is required for this cask"),
+            UpdateErrorCode::UserCancelled
+        );
+        assert_eq!(
+            classify_failure("Error: SHA256 mismatch\nExpected: abc\n  Actual: def"),
+            UpdateErrorCode::ChecksumMismatch
+        );
+        assert_eq!(
+            classify_failure("Failed to replace app: Operation not permitted"),
+            UpdateErrorCode::NeedsElevation
+        );
+        assert_eq!(
+            classify_failure("Homebrew reported success but foo is still at version 1.0."),
+            UpdateErrorCode::VersionUnchanged
+        );
+        assert_eq!(classify_failure("Download returned HTTP 502 Bad Gateway"), UpdateErrorCode::NetworkError);
+        assert_eq!(classify_failure("No .app bundle found in archive"), UpdateErrorCode::Unknown);
+    }
+}
@@ -0,0 +1,364 @@
+//! Finishing or rolling back app bundle replacements that a crash or power loss
+//! interrupted, from the update journal the Sparkle executor writes, and
+//! finding what else such an interruption left behind.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use crate::db::Database;
+use crate::detection::bundle_reader;
+use crate::models::{JournalStep, StartupIssue, StartupIssueKind, UpdateJournalEntry};
+use crate::platform::translocation;
+use crate::updaters::version_compare;
+use crate::utils::{codesign, disk_image, staging, trash};
+
+/// What to do about an interrupted replacement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Recovery {
+    /// The old bundle is untouched, or the new one is fully in place
+    Discard,
+    /// The new bundle is in place but may still be quarantined
+    ClearQuarantine,
+    /// Copy the staged new bundle into place again
+    Reinstall,
+    /// Put the old bundle back from the Trash
+    Restore,
+    /// Neither the staged nor the old bundle is left to recover from
+    Lost,
+}
+
+fn recovery_for(
+    step: JournalStep,
+    app_present: bool,
+    staged_present: bool,
+    trashed_present: bool,
+) -> Recovery {
+    match step {
+        JournalStep::QuarantineCleared => Recovery::Discard,
+        JournalStep::NewCopied => Recovery::ClearQuarantine,
+        JournalStep::Started if app_present => Recovery::Discard,
+        // A copy that didn't finish leaves an incomplete bundle behind, so
+        // whatever is at the app path now can't be trusted
+        _ if staged_present => Recovery::Reinstall,
+        _ if trashed_present => Recovery::Restore,
+        _ => Recovery::Lost,
+    }
+}
+
+/// Whether a complete, validly signed bundle was put at the app path after the
+/// interrupted replacement started, e.g. by a retry or by the user. Restoring
+/// or reinstalling over it would replace a good app with an older one.
+fn installed_since(entry: &UpdateJournalEntry, trashed_path: Option<&str>) -> bool {
+    let dest = Path::new(&entry.app_path);
+    if !dest.exists() || codesign::verify_signature(dest).is_err() {
+        return false;
+    }
+    let version = |path: &Path| bundle_reader::read_bundle(path).and_then(|b| b.installed_version);
+    let old_version = trashed_path.and_then(|p| version(Path::new(p)));
+    let newer_version = match (version(dest), old_version) {
+        (Some(installed), Some(old)) => version_compare::is_newer(&old, &installed),
+        _ => false,
+    };
+    let started = chrono::NaiveDateTime::parse_from_str(&entry.started_at, "%Y-%m-%d %H:%M:%S")
+        .map(|t| t.and_utc());
+    let modified = std::fs::metadata(dest)
+        .and_then(|m| m.modified())
+        .map(chrono::DateTime::<chrono::Utc>::from);
+    let modified_since = matches!((modified, started), (Ok(m), Ok(s)) if m > s);
+    newer_version || modified_since
+}
+
+fn clear_quarantine(app_path: &str) {
+    let _ = Command::new("xattr")
+        .current_dir("/tmp")
+        .args(["-rd", "com.apple.quarantine", app_path])
+        .output();
+}
+
+fn reinstall(entry: &UpdateJournalEntry) -> Result<(), String> {
+    let dest = Path::new(&entry.app_path);
+    if dest.exists() {
+        std::fs::remove_dir_all(dest).map_err(|e| e.to_string())?;
+    }
+    let output = Command::new("cp")
+        .current_dir("/tmp")
+        .args(["-R", &entry.staged_path, &entry.app_path])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    clear_quarantine(&entry.app_path);
+    let _ = std::fs::remove_dir_all(&entry.staged_path);
+    Ok(())
+}
+
+fn restore(entry: &UpdateJournalEntry, trashed_path: &str) -> Result<(), String> {
+    let dest = Path::new(&entry.app_path);
+    if dest.exists() {
+        std::fs::remove_dir_all(dest).map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(trashed_path, dest).map_err(|e| e.to_string())
+}
+
+/// Finish or roll back the replacements left in the journal by an earlier run.
+/// Every entry is dropped afterwards, whatever the outcome, so a bundle that
+/// can't be recovered isn't tried again at every launch. Returns the bundle IDs
+/// of apps that are still missing or broken.
+pub fn recover_interrupted_updates(db: &Database) -> Vec<String> {
+    let entries = match db.get_journal_entries() {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Failed to read the update journal: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut unrecovered = Vec::new();
+    for entry in entries {
+        let trashed = entry
+            .trashed_path
+            .as_deref()
+            .filter(|path| Path::new(path).exists());
+        let action = match recovery_for(
+            entry.step,
+            Path::new(&entry.app_path).exists(),
+            Path::new(&entry.staged_path).exists(),
+            trashed.is_some(),
+        ) {
+            Recovery::Reinstall | Recovery::Restore if installed_since(&entry, trashed) => {
+                Recovery::Discard
+            }
+            action => action,
+        };
+        log::info!(
+            "Update of {} was interrupted at step {}: {:?}",
+            entry.bundle_id,
+            entry.step.as_str(),
+            action
+        );
+
+        let outcome = match action {
+            Recovery::Discard => Ok(()),
+            Recovery::ClearQuarantine => {
+                clear_quarantine(&entry.app_path);
+                Ok(())
+            }
+            Recovery::Reinstall => reinstall(&entry),
+            Recovery::Restore => restore(&entry, trashed.unwrap_or_default()),
+            Recovery::Lost => Err("no copy of the app is left to restore".to_string()),
+        };
+        if let Err(e) = outcome {
+            log::warn!(
+                "Could not recover {} at {}: {}",
+                entry.bundle_id,
+                entry.app_path,
+                e
+            );
+            unrecovered.push(entry.bundle_id.clone());
+        }
+        let _ = db.journal_finish(entry.id);
+    }
+    unrecovered
+}
+
+/// What the launch scan found; later scans only report what is still there.
+struct LaunchFindings {
+    /// Bundle IDs of apps whose update didn't finish
+    interrupted: Vec<String>,
+    /// Update disk images left mounted by the earlier run
+    mounts: Vec<String>,
+}
+
+static FOUND_AT_LAUNCH: Mutex<LaunchFindings> = Mutex::new(LaunchFindings {
+    interrupted: Vec::new(),
+    mounts: Vec::new(),
+});
+
+/// Where an update may have set the app at `app_path` aside: self-update
+/// swaps the extension, older builds appended to the bundle name.
+fn backup_paths(app_path: &Path) -> [PathBuf; 2] {
+    let mut appended = app_path.as_os_str().to_owned();
+    appended.push(".update-backup");
+    [
+        app_path.with_extension("update-backup"),
+        PathBuf::from(appended),
+    ]
+}
+
+/// Note which apps an earlier run was updating when it stopped, and which
+/// update disk images it left mounted, for [`find_startup_issues`]. Call once
+/// at launch, before any update starts.
+pub fn record_launch_findings(db: &Database, unrecovered: Vec<String>) {
+    let mut interrupted = unrecovered;
+    match db.fail_interrupted_updates("Interrupted before it finished") {
+        Ok(bundle_ids) => interrupted.extend(bundle_ids),
+        Err(e) => log::warn!("Failed to read interrupted updates: {}", e),
+    }
+    interrupted.sort();
+    interrupted.dedup();
+    let mounts: Vec<String> = translocation::disk_image_mounts()
+        .into_iter()
+        .filter(|mount| disk_image::is_update_mount(mount))
+        .collect();
+    if !interrupted.is_empty() || !mounts.is_empty() {
+        log::info!(
+            "Earlier run left {} interrupted updates and {} disk images mounted",
+            interrupted.len(),
+            mounts.len()
+        );
+    }
+    *FOUND_AT_LAUNCH.lock().unwrap_or_else(|e| e.into_inner()) = LaunchFindings {
+        interrupted,
+        mounts,
+    };
+}
+
+/// Remove what a self-update of macPlus left behind. Nothing else uses these
+/// files, so they go without asking.
+pub fn clean_self_update_leftovers() {
+    if let Ok(exe) = std::env::current_exe() {
+        let bundle = exe
+            .ancestors()
+            .find(|p| p.extension().is_some_and(|ext| ext == "app"));
+        for backup in bundle.map(backup_paths).into_iter().flatten() {
+            if backup.exists() {
+                let _ = std::fs::remove_dir_all(&backup);
+            }
+        }
+    }
+    // Downloads are staged in the download folder; older versions used /tmp
+    let mut dirs = vec![staging::staging_dir(), PathBuf::from("/tmp")];
+    dirs.dedup();
+    for entries in dirs.iter().filter_map(|dir| std::fs::read_dir(dir).ok()) {
+        for entry in entries.flatten().take(200) {
+            if entry.file_name().to_string_lossy().starts_with("macplus-") {
+                let _ = std::fs::remove_dir_all(entry.path());
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// Problems left by interrupted updates among `apps` (bundle ID, display name,
+/// app path). Missing apps are reported as not repairable; whether one can be
+/// reinstalled depends on its update source.
+pub fn find_startup_issues(apps: &[(String, String, String)]) -> Vec<StartupIssue> {
+    let (interrupted, launch_mounts) = {
+        let found = FOUND_AT_LAUNCH.lock().unwrap_or_else(|e| e.into_inner());
+        (found.interrupted.clone(), found.mounts.clone())
+    };
+
+    let mut issues = Vec::new();
+    for (bundle_id, display_name, app_path) in apps {
+        let path = Path::new(app_path);
+        if interrupted.contains(bundle_id) && !path.exists() {
+            issues.push(StartupIssue {
+                kind: StartupIssueKind::MissingApp,
+                path: app_path.clone(),
+                bundle_id: Some(bundle_id.clone()),
+                display_name: Some(display_name.clone()),
+                repairable: false,
+            });
+        }
+        for backup in backup_paths(path).iter().filter(|b| b.exists()) {
+            issues.push(StartupIssue {
+                kind: StartupIssueKind::LeftoverBackup,
+                path: backup.to_string_lossy().to_string(),
+                bundle_id: Some(bundle_id.clone()),
+                display_name: Some(display_name.clone()),
+                repairable: true,
+            });
+        }
+    }
+
+    if !launch_mounts.is_empty() {
+        let mounted = translocation::disk_image_mounts();
+        for mount in launch_mounts.into_iter().filter(|m| mounted.contains(m)) {
+            issues.push(StartupIssue {
+                kind: StartupIssueKind::StaleMount,
+                path: mount,
+                bundle_id: None,
+                display_name: None,
+                repairable: true,
+            });
+        }
+    }
+    issues
+}
+
+/// Fix a leftover backup or stale mount. A backup replaces its app when the
+/// app is gone and is moved to the Trash otherwise; a mount is detached and
+/// its staging folder removed.
+pub fn repair_startup_issue(issue: &StartupIssue, app_path: Option<&str>) -> Result<(), String> {
+    match issue.kind {
+        StartupIssueKind::LeftoverBackup => {
+            let app_path = app_path.ok_or("the app this backup belongs to is unknown")?;
+            if Path::new(app_path).exists() {
+                trash::move_to_trash(&issue.path)
+            } else {
+                std::fs::rename(&issue.path, app_path).map_err(|e| e.to_string())
+            }
+        }
+        StartupIssueKind::StaleMount => {
+            disk_image::detach(Path::new(&issue.path))?;
+            if let Some(staging) = Path::new(&issue.path).parent() {
+                let _ = std::fs::remove_dir_all(staging);
+            }
+            FOUND_AT_LAUNCH
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .mounts
+                .retain(|m| *m != issue.path);
+            Ok(())
+        }
+        StartupIssueKind::MissingApp => Err("missing apps are reinstalled, not repaired".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_recovery_by_journal_step() {
+        use JournalStep::*;
+        assert_eq!(recovery_for(Started, true, true, false), Recovery::Discard);
+        assert_eq!(
+            recovery_for(Started, false, true, true),
+            Recovery::Reinstall
+        );
+        assert_eq!(
+            recovery_for(OldRemoved, true, false, true),
+            Recovery::Restore
+        );
+        assert_eq!(
+            recovery_for(OldRemoved, true, true, true),
+            Recovery::Reinstall
+        );
+        assert_eq!(
+            recovery_for(OldRemoved, false, false, false),
+            Recovery::Lost
+        );
+        assert_eq!(
+            recovery_for(NewCopied, true, false, true),
+            Recovery::ClearQuarantine
+        );
+        assert_eq!(
+            recovery_for(QuarantineCleared, true, false, false),
+            Recovery::Discard
+        );
+    }
+
+    #[test]
+    fn finds_backup_next_to_app() {
+        assert_eq!(
+            backup_paths(Path::new("/Applications/Foo.app")),
+            [
+                PathBuf::from("/Applications/Foo.update-backup"),
+                PathBuf::from("/Applications/Foo.app.update-backup"),
+            ]
+        );
+    }
+}
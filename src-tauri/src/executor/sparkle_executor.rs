@@ -1,29 +1,277 @@
 use std::io::{Read as _, Write as _};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::StreamExt;
-
-use crate::models::UpdateResult;
-use crate::utils::{AppError, AppResult};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::{
+    DmgLicensePolicy, JournalStep, LicenseAgreement, PermanentDeletePolicy, RelaunchPolicy,
+    RemovalMethod, TeamIdChange, UpdateErrorCode, UpdateResult,
+};
+use crate::utils::i18n::tr_args;
+use crate::utils::{codesign, disk_image, http_client, trash, AppError, AppResult};
 use super::UpdateExecutor;
 
 pub struct SparkleExecutor {
     download_url: String,
     app_name: String,
     source_type: String,
+    relaunch_policy: RelaunchPolicy,
+    permanent_delete: PermanentDeletePolicy,
+    /// Team ID the installed app is signed with; downloads signed by anyone else are held back
+    expected_team_id: Option<String>,
+    /// Team ID change the user agreed to install anyway
+    approved_change: Option<TeamIdChange>,
+    /// Database holding the update journal, so a replacement interrupted by a
+    /// crash or power loss can be finished or rolled back at the next launch
+    journal: Option<Arc<Mutex<Database>>>,
+    /// What to do when the downloaded disk image shows a license agreement
+    dmg_license: DmgLicensePolicy,
 }
 
 impl SparkleExecutor {
     pub fn new(download_url: String, app_name: String) -> Self {
-        Self { download_url, app_name, source_type: "sparkle".to_string() }
+        Self {
+            download_url,
+            app_name,
+            source_type: "sparkle".to_string(),
+            relaunch_policy: RelaunchPolicy::default(),
+            permanent_delete: PermanentDeletePolicy::default(),
+            expected_team_id: None,
+            approved_change: None,
+            journal: None,
+            dmg_license: DmgLicensePolicy::default(),
+        }
     }
 
     pub fn with_source_type(mut self, source_type: &str) -> Self {
         self.source_type = source_type.to_string();
         self
     }
+
+    pub fn with_relaunch_policy(mut self, policy: RelaunchPolicy) -> Self {
+        self.relaunch_policy = policy;
+        self
+    }
+
+    pub fn with_permanent_delete(mut self, policy: PermanentDeletePolicy) -> Self {
+        self.permanent_delete = policy;
+        self
+    }
+
+    pub fn with_team_id_check(mut self, expected: Option<String>, approved: Option<TeamIdChange>) -> Self {
+        self.expected_team_id = expected;
+        self.approved_change = approved;
+        self
+    }
+
+    pub fn with_journal(mut self, db: Arc<Mutex<Database>>) -> Self {
+        self.journal = Some(db);
+        self
+    }
+
+    pub fn with_dmg_license(mut self, policy: DmgLicensePolicy) -> Self {
+        self.dmg_license = policy;
+        self
+    }
+
+    /// Apply the license setting to a downloaded disk image. Returns the
+    /// agreement that will be accepted, if the image has one, or a failed result
+    /// when the setting holds the update back.
+    fn check_dmg_license(
+        &self,
+        bundle_id: &str,
+        dmg_path: &Path,
+    ) -> Result<Option<LicenseAgreement>, UpdateResult> {
+        if !disk_image::has_license(dmg_path) {
+            return Ok(None);
+        }
+        let (message, license) = match self.dmg_license {
+            DmgLicensePolicy::Accept => {
+                log::info!("Accepting the disk image license agreement of {}", bundle_id);
+                return Ok(Some(LicenseAgreement {
+                    text: disk_image::license_text(dmg_path),
+                    accepted: true,
+                }));
+            }
+            DmgLicensePolicy::Prompt => (
+                tr_args(
+                    "{app}'s disk image asks you to accept a license agreement",
+                    &[("app", self.app_name.as_str())],
+                ),
+                Some(LicenseAgreement {
+                    text: disk_image::license_text(dmg_path),
+                    accepted: false,
+                }),
+            ),
+            DmgLicensePolicy::Abort => (
+                tr_args(
+                    "{app}'s disk image asks to accept a license agreement, which your settings don't allow",
+                    &[("app", self.app_name.as_str())],
+                ),
+                None,
+            ),
+        };
+        Err(UpdateResult {
+            license,
+            error_code: Some(UpdateErrorCode::LicenseAgreement),
+            ..UpdateResult::failed(bundle_id, &self.source_type, message)
+        })
+    }
+
+    /// Record that the bundle replacement begins. Failing to write the journal
+    /// doesn't stop the update.
+    async fn journal_begin(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        new_app_path: &Path,
+    ) -> Option<i64> {
+        let db = self.journal.as_ref()?.lock().await;
+        db.journal_begin(bundle_id, app_path, &new_app_path.to_string_lossy())
+            .map_err(|e| log::warn!("Failed to write update journal for {}: {}", bundle_id, e))
+            .ok()
+    }
+
+    async fn journal_step(&self, id: Option<i64>, step: JournalStep, trashed_path: Option<&str>) {
+        if let (Some(id), Some(db)) = (id, self.journal.as_ref()) {
+            if let Err(e) = db.lock().await.journal_step(id, step, trashed_path) {
+                log::warn!("Failed to write update journal: {}", e);
+            }
+        }
+    }
+
+    async fn journal_finish(&self, id: Option<i64>) {
+        if let (Some(id), Some(db)) = (id, self.journal.as_ref()) {
+            let _ = db.lock().await.journal_finish(id);
+        }
+    }
+
+    /// Copy the new bundle into place, asking for administrator privileges when
+    /// the app's folder needs them. Returns the failed result when it couldn't.
+    fn copy_new_bundle(
+        &self,
+        bundle_id: &str,
+        app_path: &str,
+        new_app_path: &Path,
+        on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    ) -> AppResult<Option<UpdateResult>> {
+        let cp_output = Command::new("cp")
+            .current_dir("/tmp")
+            .args(["-R", &new_app_path.to_string_lossy(), app_path])
+            .output()
+            .map_err(|e| AppError::CommandFailed(format!("Failed to copy app: {}", e)))?;
+
+        if !cp_output.status.success() {
+            let stderr = String::from_utf8_lossy(&cp_output.stderr);
+            let needs_elevation = stderr.contains("Permission denied")
+                || stderr.contains("Operation not permitted");
+
+            if needs_elevation {
+                // Retry with administrator privileges
+                on_progress(80, "Requesting administrator privileges...", None);
+
+                let elevated_cmd = format!(
+                    "rm -rf '{}' && cp -R '{}' '{}'",
+                    app_path.replace('\'', "'\\''"),
+                    new_app_path.to_string_lossy().replace('\'', "'\\''"),
+                    app_path.replace('\'', "'\\''"),
+                );
+
+                match crate::utils::sudo_session::run_elevated_shell(&elevated_cmd) {
+                    Ok(out) if out.status.success() => {
+                        // Elevated copy succeeded — continue to quarantine removal + relaunch
+                    }
+                    Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
+                        let msg = "Update cancelled \u{2014} administrator approval is required to replace this app".to_string();
+                        on_progress(100, &msg, None);
+                        return Ok(Some(UpdateResult {
+                            error_code: Some(UpdateErrorCode::UserCancelled),
+                            ..UpdateResult::failed(bundle_id, &self.source_type, msg)
+                        }));
+                    }
+                    Ok(out) => {
+                        let osa_stderr = String::from_utf8_lossy(&out.stderr).to_string();
+                        let msg = format!("Failed to replace app (elevated): {}", osa_stderr);
+                        on_progress(100, &msg, None);
+                        return Ok(Some(UpdateResult::failed(
+                            bundle_id,
+                            &self.source_type,
+                            msg,
+                        )));
+                    }
+                    Err(e) => {
+                        let msg = format!("Failed to request admin privileges: {}", e);
+                        on_progress(100, &msg, None);
+                        return Ok(Some(UpdateResult {
+                            error_code: Some(UpdateErrorCode::NeedsElevation),
+                            ..UpdateResult::failed(bundle_id, &self.source_type, msg)
+                        }));
+                    }
+                }
+            } else {
+                return Ok(Some(UpdateResult::failed(
+                    bundle_id,
+                    &self.source_type,
+                    format!("Failed to replace app: {}", stderr),
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Undo a replacement whose new bundle couldn't be copied into place: put the
+    /// old bundle back from the Trash, then drop the journal entry so it isn't
+    /// recovered again at the next launch.
+    async fn roll_back(&self, journal_id: Option<i64>, app_path: &str, trashed_path: Option<&str>) {
+        if let Some(trashed) = trashed_path.filter(|p| Path::new(p).exists()) {
+            let dest = Path::new(app_path);
+            if dest.exists() {
+                let _ = std::fs::remove_dir_all(dest);
+            }
+            if let Err(e) = std::fs::rename(trashed, dest) {
+                log::warn!("Could not put {} back from the Trash: {}", app_path, e);
+            }
+        }
+        self.journal_finish(journal_id).await;
+    }
+
+    /// The Team ID change between the installed app and `new_app_path`, unless
+    /// they match or the user already approved this change.
+    fn unapproved_team_id_change(&self, new_app_path: &Path) -> Option<TeamIdChange> {
+        let installed = self.expected_team_id.clone()?;
+        let downloaded = codesign::team_id(new_app_path);
+        if downloaded.as_deref() == Some(installed.as_str()) {
+            return None;
+        }
+        let change = TeamIdChange { installed, downloaded };
+        (self.approved_change.as_ref() != Some(&change)).then_some(change)
+    }
+
+    /// A failed result when the app is open and its relaunch policy forbids quitting it.
+    fn blocked_by_running_app(&self, bundle_id: &str) -> Option<UpdateResult> {
+        if self.relaunch_policy != RelaunchPolicy::NeverQuit
+            || !crate::utils::app_lifecycle::is_app_running(bundle_id)
+        {
+            return None;
+        }
+        Some(UpdateResult {
+            error_code: Some(UpdateErrorCode::AppRunning),
+            ..UpdateResult::failed(
+                bundle_id,
+                &self.source_type,
+                format!(
+                    "{} is open \u{2014} quit it to install this update",
+                    self.app_name
+                ),
+            )
+        })
+    }
 }
 
 impl UpdateExecutor for SparkleExecutor {
@@ -33,30 +281,38 @@ impl UpdateExecutor for SparkleExecutor {
         app_path: &str,
         on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
     ) -> AppResult<UpdateResult> {
-        let tmp_dir = tempfile::tempdir()
+        // Don't download an update that can't be installed without quitting the app
+        if let Some(result) = self.blocked_by_running_app(bundle_id) {
+            return Ok(result);
+        }
+
+        let tmp_dir = crate::utils::staging::tempdir()
             .map_err(|e| AppError::CommandFailed(format!("Failed to create temp dir: {}", e)))?;
 
         // 1. Download the file
         on_progress(2, "Requesting download...", None);
 
-        let client = reqwest::Client::builder()
+        let client = http_client::download_client_builder()
             .redirect(reqwest::redirect::Policy::limited(10))
             .build()
             .map_err(|e| AppError::CommandFailed(format!("Failed to create HTTP client: {}", e)))?;
 
-        let response = client.get(&self.download_url).send().await
+        let response = http_client::send_download(client.get(&self.download_url)).await
             .map_err(|e| AppError::CommandFailed(format!("Download failed: {}", e)))?;
 
         if !response.status().is_success() {
+            let code = if response.status().is_server_error() {
+                UpdateErrorCode::NetworkError
+            } else {
+                UpdateErrorCode::Unknown
+            };
             return Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some(format!("Download returned HTTP {}", response.status())),
-                source_type: self.source_type.clone(),
-                from_version: None,
-                to_version: None,
-                handled_relaunch: false,
-                delegated: false,
+                error_code: Some(code),
+                ..UpdateResult::failed(
+                    bundle_id,
+                    &self.source_type,
+                    format!("Download returned HTTP {}", response.status()),
+                )
             });
         }
 
@@ -70,16 +326,11 @@ impl UpdateExecutor for SparkleExecutor {
 
         // Reject HTML/text responses — these aren't installer files
         if content_type.contains("text/html") || content_type.contains("text/plain") {
-            return Ok(UpdateResult {
-                bundle_id: bundle_id.to_string(),
-                success: false,
-                message: Some("Download URL returned HTML instead of an installer file".to_string()),
-                source_type: self.source_type.clone(),
-                from_version: None,
-                to_version: None,
-                handled_relaunch: false,
-                delegated: false,
-            });
+            return Ok(UpdateResult::failed(
+                bundle_id,
+                &self.source_type,
+                "Download URL returned HTML instead of an installer file",
+            ));
         }
 
         // Determine filename from URL or Content-Disposition
@@ -130,6 +381,7 @@ impl UpdateExecutor for SparkleExecutor {
             }
         }
         drop(file);
+        crate::utils::metrics::record_download(downloaded);
 
         on_progress(50, "Download complete, extracting...", None);
 
@@ -143,8 +395,25 @@ impl UpdateExecutor for SparkleExecutor {
         };
         let file_type = detect_file_type(&content_type, &filename, &magic_buf[..magic_len]);
 
+        let mut license = None;
         let new_app_path = match file_type {
-            FileType::Dmg => extract_from_dmg(&download_path, tmp_dir.path(), on_progress, &self.app_name)?,
+            FileType::Dmg => {
+                license = match self.check_dmg_license(bundle_id, &download_path) {
+                    Ok(license) => license,
+                    Err(held_back) => {
+                        on_progress(100, held_back.message.as_deref().unwrap_or_default(), None);
+                        return Ok(held_back);
+                    }
+                };
+                let accept = self.dmg_license == DmgLicensePolicy::Accept;
+                extract_from_dmg(
+                    &download_path,
+                    tmp_dir.path(),
+                    on_progress,
+                    &self.app_name,
+                    accept,
+                )?
+            }
             FileType::Zip => extract_from_zip(&download_path, tmp_dir.path())?,
             FileType::Pkg => {
                 on_progress(60, "Installing package (requesting admin privileges)...", None);
@@ -163,68 +432,70 @@ impl UpdateExecutor for SparkleExecutor {
                             to_version: None,
                             handled_relaunch: false,
                             delegated: false,
+                            removal: None,
+                            team_id_change: None,
+                            gatekeeper: None,
+                            license: None,
+                            error_code: None,
                         });
                     }
                     Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
                         let msg = "Update cancelled \u{2014} administrator approval is required to install this package".to_string();
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            error_code: Some(UpdateErrorCode::UserCancelled),
+                            ..UpdateResult::failed(bundle_id, &self.source_type, msg)
                         });
                     }
                     Ok(pkg_output) => {
                         let pkg_stderr = String::from_utf8_lossy(&pkg_output.stderr).to_string();
                         let msg = format!("Package installation failed: {}", pkg_stderr);
                         on_progress(100, &msg, None);
-                        return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
-                        });
+                        return Ok(UpdateResult::failed(bundle_id, &self.source_type, msg));
                     }
                     Err(e) => {
                         let msg = format!("Failed to request admin privileges: {}", e);
                         on_progress(100, &msg, None);
                         return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
+                            error_code: Some(UpdateErrorCode::NeedsElevation),
+                            ..UpdateResult::failed(bundle_id, &self.source_type, msg)
                         });
                     }
                 }
             }
             FileType::Unknown => {
-                return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(format!("Unsupported archive format: {}", filename)),
-                    source_type: self.source_type.clone(),
-                    from_version: None,
-                    to_version: None,
-                    handled_relaunch: false,
-                    delegated: false,
-                });
+                return Ok(UpdateResult::failed(
+                    bundle_id,
+                    &self.source_type,
+                    format!("Unsupported archive format: {}", filename),
+                ));
             }
         };
 
-        // 3. Check if app is running and quit gracefully before replacing
+        // A different signer may mean the publisher changed hands or the download was
+        // tampered with, so hold the update until the user confirms it.
+        if let Some(change) = self.unapproved_team_id_change(&new_app_path) {
+            let msg = tr_args(
+                "{app}'s update is signed by a different developer ({downloaded}) than the installed version ({installed})",
+                &[
+                    ("app", self.app_name.as_str()),
+                    ("downloaded", change.downloaded.as_deref().unwrap_or("unsigned")),
+                    ("installed", change.installed.as_str()),
+                ],
+            );
+            on_progress(100, &msg, None);
+            return Ok(UpdateResult {
+                team_id_change: Some(change),
+                error_code: Some(UpdateErrorCode::TeamIdMismatch),
+                ..UpdateResult::failed(bundle_id, &self.source_type, msg)
+            });
+        }
+
+        // 3. Check if app is running and quit gracefully before replacing.
+        // The app may have been opened while the update downloaded.
+        if let Some(result) = self.blocked_by_running_app(bundle_id) {
+            return Ok(result);
+        }
         let was_running = crate::utils::app_lifecycle::is_app_running(bundle_id);
         if was_running {
             on_progress(60, &format!("\u{26a0} {} is open \u{2014} closing to update...", self.app_name), None);
@@ -238,111 +509,85 @@ impl UpdateExecutor for SparkleExecutor {
 
         on_progress(75, &format!("Replacing {}", self.app_name), None);
 
-        // 4. Replace the app bundle
+        // 4. Replace the app bundle, moving the old one to the Trash (safer) and
+        // only deleting it outright when the permanent-delete policy allows.
+        // Each step is journaled before the next one starts.
+        let journal_id = self.journal_begin(bundle_id, app_path, &new_app_path).await;
         let dest = Path::new(app_path);
+        let mut removal = None;
+        let mut trashed_path = None;
         if dest.exists() {
-            // Move old app to trash instead of deleting (safer)
-            let trash_result = Command::new("osascript")
-                .current_dir("/tmp")
-                .args([
-                    "-e",
-                    &format!(
-                        "tell application \"Finder\" to move POSIX file \"{}\" to trash",
-                        app_path
-                    ),
-                ])
-                .output();
-
-            if trash_result.is_err() || !trash_result.unwrap().status.success() {
-                // Fallback: remove directly
-                let _ = std::fs::remove_dir_all(dest);
-            }
-        }
-
-        let cp_output = Command::new("cp")
-            .current_dir("/tmp")
-            .args(["-R", &new_app_path.to_string_lossy(), app_path])
-            .output()
-            .map_err(|e| AppError::CommandFailed(format!("Failed to copy app: {}", e)))?;
-
-        if !cp_output.status.success() {
-            let stderr = String::from_utf8_lossy(&cp_output.stderr);
-            let needs_elevation = stderr.contains("Permission denied")
-                || stderr.contains("Operation not permitted");
-
-            if needs_elevation {
-                // Retry with administrator privileges
-                on_progress(80, "Requesting administrator privileges...", None);
-
-                let elevated_cmd = format!(
-                    "rm -rf '{}' && cp -R '{}' '{}'",
-                    app_path.replace('\'', "'\\''"),
-                    new_app_path.to_string_lossy().replace('\'', "'\\''"),
-                    app_path.replace('\'', "'\\''"),
-                );
-
-                match crate::utils::sudo_session::run_elevated_shell(&elevated_cmd) {
-                    Ok(out) if out.status.success() => {
-                        // Elevated copy succeeded — continue to quarantine removal + relaunch
-                    }
-                    Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
-                        let msg = "Update cancelled \u{2014} administrator approval is required to replace this app".to_string();
-                        on_progress(100, &msg, None);
-                        return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
-                        });
-                    }
-                    Ok(out) => {
-                        let osa_stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                        let msg = format!("Failed to replace app (elevated): {}", osa_stderr);
-                        on_progress(100, &msg, None);
-                        return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
-                        });
-                    }
-                    Err(e) => {
-                        let msg = format!("Failed to request admin privileges: {}", e);
-                        on_progress(100, &msg, None);
-                        return Ok(UpdateResult {
-                            bundle_id: bundle_id.to_string(),
-                            success: false,
-                            message: Some(msg),
-                            source_type: self.source_type.clone(),
-                            from_version: None,
-                            to_version: None,
-                            handled_relaunch: false,
-                            delegated: false,
-                        });
+            match trash::trash_item(app_path) {
+                Ok(trashed) => {
+                    removal = Some(RemovalMethod::Trash);
+                    trashed_path = trashed;
+                }
+                Err(e) => {
+                    log::warn!("Could not move {} to Trash: {}", app_path, e);
+                    if self.permanent_delete != PermanentDeletePolicy::Allow
+                        && trash::move_to_trash_elevated(app_path).is_ok()
+                    {
+                        removal = Some(RemovalMethod::Trash);
+                    } else {
+                        let permitted = match self.permanent_delete {
+                            PermanentDeletePolicy::Allow => true,
+                            PermanentDeletePolicy::Ask => {
+                                // The dialog waits for the user, so keep it off the runtime
+                                let (app_name, path) = (self.app_name.clone(), app_path.to_string());
+                                tokio::task::spawn_blocking(move || {
+                                    trash::confirm_permanent_delete(&app_name, &path)
+                                })
+                                .await
+                                .unwrap_or(false)
+                            }
+                            PermanentDeletePolicy::Never => false,
+                        };
+                        if !permitted {
+                            // The message is localized, so the code is set here rather than
+                            // classified from its text
+                            let (template, code) = if self.permanent_delete == PermanentDeletePolicy::Never {
+                                (
+                                    "Couldn't move the old version of {app} to the Trash, and permanent deletion is turned off",
+                                    UpdateErrorCode::NeedsElevation,
+                                )
+                            } else {
+                                (
+                                    "Update cancelled \u{2014} the old version of {app} was kept",
+                                    UpdateErrorCode::UserCancelled,
+                                )
+                            };
+                            let msg = tr_args(template, &[("app", self.app_name.as_str())]);
+                            on_progress(100, &msg, None);
+                            self.journal_finish(journal_id).await;
+                            return Ok(UpdateResult {
+                                error_code: Some(code),
+                                ..UpdateResult::failed(bundle_id, &self.source_type, msg)
+                            });
+                        }
+                        // If this fails for lack of permission, the elevated copy below deletes it
+                        let _ = std::fs::remove_dir_all(dest);
+                        removal = Some(RemovalMethod::Deleted);
                     }
                 }
-            } else {
-                return Ok(UpdateResult {
-                    bundle_id: bundle_id.to_string(),
-                    success: false,
-                    message: Some(format!("Failed to replace app: {}", stderr)),
-                    source_type: self.source_type.clone(),
-                    from_version: None,
-                    to_version: None,
-                    handled_relaunch: false,
-                    delegated: false,
-                });
+            }
+        }
+        self.journal_step(journal_id, JournalStep::OldRemoved, trashed_path.as_deref()).await;
+
+        let copied = self.copy_new_bundle(bundle_id, app_path, &new_app_path, on_progress);
+        match copied {
+            Ok(None) => {}
+            Ok(Some(failed)) => {
+                self.roll_back(journal_id, app_path, trashed_path.as_deref()).await;
+                return Ok(failed);
+            }
+            Err(e) => {
+                self.roll_back(journal_id, app_path, trashed_path.as_deref()).await;
+                return Err(e);
             }
         }
 
+        self.journal_step(journal_id, JournalStep::NewCopied, None).await;
+
         // Remove quarantine attribute (best-effort, try elevated if needed)
         let xattr_output = Command::new("xattr")
             .current_dir("/tmp")
@@ -357,13 +602,15 @@ impl UpdateExecutor for SparkleExecutor {
                 );
             }
         }
+        self.journal_step(journal_id, JournalStep::QuarantineCleared, None).await;
 
-        // Relaunch if the app was running before the update
-        if was_running {
+        // Relaunch if the app was running before the update, unless it should stay closed
+        if was_running && self.relaunch_policy == RelaunchPolicy::Automatic {
             on_progress(95, &format!("Relaunching {}", self.app_name), None);
             crate::utils::app_lifecycle::relaunch_app(app_path);
         }
 
+        self.journal_finish(journal_id).await;
         on_progress(100, &format!("{} updated successfully", self.app_name), None);
 
         Ok(UpdateResult {
@@ -375,6 +622,11 @@ impl UpdateExecutor for SparkleExecutor {
             to_version: None,
             handled_relaunch: was_running,
             delegated: false,
+            removal,
+            team_id_change: None,
+            gatekeeper: None,
+            license,
+            error_code: None,
         })
     }
 }
@@ -384,6 +636,7 @@ pub(crate) fn extract_from_dmg(
     tmp_dir: &Path,
     on_progress: &(dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
     app_name: &str,
+    accept_license: bool,
 ) -> AppResult<PathBuf> {
     let mount_point = tmp_dir.join("dmg_mount");
     std::fs::create_dir_all(&mount_point)
@@ -391,40 +644,11 @@ pub(crate) fn extract_from_dmg(
 
     on_progress(52, &format!("Mounting disk image for {}...", app_name), None);
 
-    // Use spawn + stdin pipe to auto-accept embedded license agreements
-    let mut child = Command::new("hdiutil")
-        .current_dir("/tmp")
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .args([
-            "attach",
-            "-nobrowse",
-            "-noverify",
-            "-noautoopen",
-            "-mountpoint",
-            &mount_point.to_string_lossy(),
-            &dmg_path.to_string_lossy(),
-        ])
-        .spawn()
-        .map_err(|e| AppError::CommandFailed(format!("Failed to mount DMG: {}", e)))?;
-
-    // Write "Y\n" to accept any embedded license agreement
-    if let Some(mut stdin) = child.stdin.take() {
-        let _ = stdin.write_all(b"Y\n");
-    }
-
-    let output = child
-        .wait_with_output()
-        .map_err(|e| AppError::CommandFailed(format!("Failed to mount DMG: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AppError::CommandFailed(format!("hdiutil attach failed: {}", stderr)));
-    }
+    // Detached when dropped, so every error return below unmounts it too
+    let mount = disk_image::attach(dmg_path, &mount_point, accept_license)?;
 
     // Find the .app inside the mounted volume
-    let app_path = find_app_in_dir(&mount_point)?;
+    let app_path = find_app_in_dir(mount.path())?;
 
     on_progress(60, &format!("Copying {} from disk image...", app_name), None);
 
@@ -438,25 +662,16 @@ pub(crate) fn extract_from_dmg(
 
     if !cp_output.status.success() {
         let stderr = String::from_utf8_lossy(&cp_output.stderr);
-        let _ = Command::new("hdiutil")
-            .current_dir("/tmp")
-            .args(["detach", &mount_point.to_string_lossy(), "-quiet"])
-            .output();
         return Err(AppError::CommandFailed(format!("cp from DMG failed: {}", stderr)));
     }
 
     on_progress(68, "Unmounting disk image...", None);
-
-    // Unmount
-    let _ = Command::new("hdiutil")
-        .current_dir("/tmp")
-        .args(["detach", &mount_point.to_string_lossy(), "-quiet"])
-        .output();
+    drop(mount);
 
     Ok(dest)
 }
 
-fn extract_from_zip(zip_path: &Path, tmp_dir: &Path) -> AppResult<PathBuf> {
+pub(crate) fn extract_from_zip(zip_path: &Path, tmp_dir: &Path) -> AppResult<PathBuf> {
     let extract_dir = tmp_dir.join("zip_extract");
     std::fs::create_dir_all(&extract_dir)
         .map_err(|e| AppError::CommandFailed(format!("Failed to create extract dir: {}", e)))?;
@@ -475,6 +690,45 @@ fn extract_from_zip(zip_path: &Path, tmp_dir: &Path) -> AppResult<PathBuf> {
     find_app_in_dir(&extract_dir)
 }
 
+/// Expand a flat installer package (`pkgutil --expand-full`) and return the `.app`
+/// from its payload, without running the installer. Used where the bundle should go
+/// through the same verification and replacement as DMG/ZIP downloads.
+pub(crate) fn extract_from_pkg(pkg_path: &Path, tmp_dir: &Path) -> AppResult<PathBuf> {
+    // pkgutil requires that the destination doesn't exist yet
+    let expand_dir = tmp_dir.join("pkg_expand");
+    let output = Command::new("pkgutil")
+        .current_dir("/tmp")
+        .args(["--expand-full", &pkg_path.to_string_lossy(), &expand_dir.to_string_lossy()])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to expand package: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::CommandFailed(format!("pkgutil expand failed: {}", stderr)));
+    }
+
+    // Layout is <component>.pkg/Payload/[Applications/]<App>.app
+    find_app_recursive(&expand_dir, 4)
+        .ok_or_else(|| AppError::NotFound("No .app bundle found in package payload".to_string()))
+}
+
+fn find_app_recursive(dir: &Path, depth: u32) -> Option<PathBuf> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).collect();
+    if let Some(app) = entries
+        .iter()
+        .find(|p| p.extension().and_then(|e| e.to_str()) == Some("app"))
+    {
+        return Some(app.clone());
+    }
+    if depth == 0 {
+        return None;
+    }
+    entries
+        .iter()
+        .filter(|p| p.is_dir())
+        .find_map(|p| find_app_recursive(p, depth - 1))
+}
+
 pub(crate) fn find_app_in_dir(dir: &Path) -> AppResult<PathBuf> {
     // Look for .app bundles at the top level
     if let Ok(entries) = std::fs::read_dir(dir) {
@@ -0,0 +1,203 @@
+//! `--headless check|update-all|report`: run one operation without the window or
+//! the tray icon, print the outcome as JSON on stdout, and exit. For scripts and
+//! remote management sessions.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::AppSummary;
+use crate::utils::AppResult;
+
+/// Flag that selects headless mode; the operation follows it.
+pub const HEADLESS_ARG: &str = "--headless";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadlessCommand {
+    /// Check for updates and list the apps that have one
+    Check,
+    /// Check, then install every update "Update All" in the tray would
+    UpdateAll,
+    /// List every tracked app and its update status as of the last check
+    Report,
+}
+
+impl HeadlessCommand {
+    /// The operation requested on the command line: `None` without
+    /// `--headless`, an error message for a missing or unknown operation.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<Result<Self, String>> {
+        let mut args = args.into_iter().skip_while(|a| a != HEADLESS_ARG);
+        args.next()?;
+        Some(match args.next().as_deref() {
+            Some("check") => Ok(HeadlessCommand::Check),
+            Some("update-all") => Ok(HeadlessCommand::UpdateAll),
+            Some("report") => Ok(HeadlessCommand::Report),
+            Some(other) => Err(format!(
+                "Unknown headless operation '{}'. Use check, update-all or report.",
+                other
+            )),
+            None => Err("Missing headless operation. Use check, update-all or report.".to_string()),
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            HeadlessCommand::Check => "check",
+            HeadlessCommand::UpdateAll => "update-all",
+            HeadlessCommand::Report => "report",
+        }
+    }
+}
+
+/// An app as printed by `check` and `report`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadlessApp {
+    bundle_id: String,
+    display_name: String,
+    installed_version: Option<String>,
+    has_update: bool,
+    available_version: Option<String>,
+    install_source: String,
+    update_source: Option<String>,
+    is_ignored: bool,
+    needs_license: bool,
+}
+
+impl From<AppSummary> for HeadlessApp {
+    fn from(app: AppSummary) -> Self {
+        HeadlessApp {
+            bundle_id: app.bundle_id,
+            display_name: app.display_name,
+            installed_version: app.installed_version,
+            has_update: app.has_update,
+            available_version: app.available_version,
+            install_source: app.install_source,
+            update_source: app.update_source,
+            is_ignored: app.is_ignored,
+            needs_license: app.needs_license,
+        }
+    }
+}
+
+/// Apps with an update the user hasn't ignored.
+fn pending_updates(db: &Database) -> AppResult<Vec<AppSummary>> {
+    Ok(db
+        .get_all_apps()?
+        .into_iter()
+        .filter(|app| app.has_update && !app.is_ignored)
+        .collect())
+}
+
+/// Scan first when nothing has been scanned yet, e.g. on a freshly set up Mac.
+async fn check(app_handle: &AppHandle, db: &Arc<Mutex<Database>>) -> AppResult<Vec<AppSummary>> {
+    if db.lock().await.get_app_count()? == 0 {
+        crate::scheduler::run_full_scan(app_handle, db).await?;
+    }
+    let client = app_handle.state::<reqwest::Client>().inner().clone();
+    crate::scheduler::run_update_check(app_handle, db, &client).await?;
+    pending_updates(&*db.lock().await)
+}
+
+/// Run `command` and return the JSON to print and the exit code: 0 when it
+/// worked, 1 when it failed or some updates did.
+async fn execute(
+    app_handle: &AppHandle,
+    command: HeadlessCommand,
+) -> AppResult<(serde_json::Value, i32)> {
+    let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+    match command {
+        HeadlessCommand::Check => {
+            let updates: Vec<HeadlessApp> = check(app_handle, &db)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            Ok((json!({ "count": updates.len(), "updates": updates }), 0))
+        }
+        HeadlessCommand::UpdateAll => {
+            check(app_handle, &db).await?;
+            let bundle_ids = crate::commands::execute::pending_bulk_updates(&*db.lock().await)?;
+            let results = if bundle_ids.is_empty() {
+                Vec::new()
+            } else {
+                crate::commands::execute::execute_bulk_update(
+                    bundle_ids,
+                    app_handle.clone(),
+                    app_handle.state(),
+                )
+                .await?
+            };
+            let summary = crate::commands::execute::bulk_summary(&results, &*db.lock().await);
+            let code = if summary.failed > 0 { 1 } else { 0 };
+            Ok((json!({ "summary": summary, "results": results }), code))
+        }
+        HeadlessCommand::Report => {
+            let apps: Vec<HeadlessApp> = db
+                .lock()
+                .await
+                .get_all_apps()?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            let updates = apps
+                .iter()
+                .filter(|app| app.has_update && !app.is_ignored)
+                .count();
+            Ok((json!({ "apps": apps, "updateCount": updates }), 0))
+        }
+    }
+}
+
+/// Run `command` in the background, print its JSON and exit.
+pub fn start(app_handle: AppHandle, command: HeadlessCommand) {
+    tauri::async_runtime::spawn(async move {
+        let (mut output, code) = match execute(&app_handle, command).await {
+            Ok(done) => done,
+            Err(e) => (json!({ "error": e.to_string() }), 1),
+        };
+        output["command"] = json!(command.name());
+        output["version"] = json!(env!("CARGO_PKG_VERSION"));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        );
+        app_handle.exit(code);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Option<Result<HeadlessCommand, String>> {
+        HeadlessCommand::from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn headless_operation_follows_the_flag() {
+        assert_eq!(parse(&["macplus"]), None);
+        assert_eq!(parse(&["macplus", "--autostarted"]), None);
+        assert_eq!(
+            parse(&["macplus", "--headless", "check"]),
+            Some(Ok(HeadlessCommand::Check))
+        );
+        assert_eq!(
+            parse(&["macplus", "--headless", "update-all"]),
+            Some(Ok(HeadlessCommand::UpdateAll))
+        );
+        assert_eq!(
+            parse(&["macplus", "--headless", "report"]),
+            Some(Ok(HeadlessCommand::Report))
+        );
+        assert!(matches!(parse(&["macplus", "--headless"]), Some(Err(_))));
+        assert!(matches!(
+            parse(&["macplus", "--headless", "upgrade"]),
+            Some(Err(_))
+        ));
+    }
+}
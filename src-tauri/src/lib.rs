@@ -2,6 +2,7 @@ pub mod commands;
 pub mod db;
 pub mod detection;
 pub mod executor;
+mod headless;
 pub mod models;
 pub mod platform;
 pub mod scheduler;
@@ -10,52 +11,17 @@ pub mod utils;
 
 use std::sync::Arc;
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager, PhysicalPosition,
+    Manager,
 };
 use tokio::sync::Mutex;
 
 use db::Database;
 use utils::http_client;
 
-/// Managed state holding the tray "update count" menu item for runtime text updates.
-pub struct UpdateCountMenuItem(pub tauri::menu::MenuItem<tauri::Wry>);
-
-/// Position the window centered below the given tray icon rectangle.
-fn position_window_below_tray(window: &tauri::WebviewWindow, tray_rect: &tauri::Rect) {
-    let scale = window.scale_factor().unwrap_or(1.0);
-    // Window width in logical pixels (from tauri.conf.json) → physical
-    let win_width = 640.0 * scale;
-
-    // Convert tray rect position/size to physical pixels
-    let tray_pos = tray_rect.position.to_physical::<i32>(scale);
-    let tray_size = tray_rect.size.to_physical::<u32>(scale);
-
-    // Tray icon center X (physical pixels)
-    let tray_center_x = tray_pos.x as f64 + tray_size.width as f64 / 2.0;
-    let window_x = tray_center_x - win_width / 2.0;
-    let window_y = tray_pos.y as f64 + tray_size.height as f64;
-
-    // Clamp to screen bounds
-    if let Some(monitor) = window
-        .current_monitor()
-        .ok()
-        .flatten()
-        .or_else(|| window.primary_monitor().ok().flatten())
-    {
-        let screen_pos = monitor.position();
-        let screen_size = monitor.size();
-        let screen_right = screen_pos.x as f64 + screen_size.width as f64;
-        let clamped_x = window_x.max(screen_pos.x as f64).min(screen_right - win_width);
-        let _ = window.set_position(PhysicalPosition::new(clamped_x as i32, window_y as i32));
-    } else {
-        let _ = window.set_position(PhysicalPosition::new(window_x as i32, window_y as i32));
-    }
-}
-
 /// Toggle the main window: show+focus if hidden/unfocused, hide if visible+focused.
-/// Positions the window below the tray icon when showing.
+/// Positions the window below the tray icon when showing, unless it runs as a
+/// standard window.
 fn toggle_main_window(app: &tauri::AppHandle, tray_rect: tauri::Rect) {
     if let Some(window) = app.get_webview_window("main") {
         let is_visible = window.is_visible().unwrap_or(false);
@@ -63,7 +29,26 @@ fn toggle_main_window(app: &tauri::AppHandle, tray_rect: tauri::Rect) {
         if is_visible && is_focused {
             let _ = window.hide();
         } else {
-            position_window_below_tray(&window, &tray_rect);
+            if !platform::window_mode::is_standard_mode() {
+                platform::window_geometry::place_popover(&window, &tray_rect);
+            }
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// Toggle the main window from a global shortcut, positioning it below the tray icon
+/// when the tray is visible.
+fn toggle_main_window_from_shortcut(app: &tauri::AppHandle) {
+    if let Some(rect) = app.tray_by_id("main-tray").and_then(|tray| tray.rect().ok().flatten()) {
+        toggle_main_window(app, rect);
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
             let _ = window.show();
             let _ = window.set_focus();
         }
@@ -71,40 +56,117 @@ fn toggle_main_window(app: &tauri::AppHandle, tray_rect: tauri::Rect) {
 }
 
 /// Show the main window below the tray icon (always shows, never toggles).
+/// A standard window keeps its own position.
 fn show_main_window_below_tray(app: &tauri::AppHandle, tray_rect: &tauri::Rect) {
     if let Some(window) = app.get_webview_window("main") {
-        position_window_below_tray(&window, tray_rect);
+        if !platform::window_mode::is_standard_mode() {
+            platform::window_geometry::place_popover(&window, tray_rect);
+        }
         let _ = window.show();
         let _ = window.set_focus();
     }
 }
 
+/// Argument passed by the login item so a launch at login can be told apart from
+/// the user opening the app.
+const AUTOSTART_ARG: &str = "--autostarted";
+
 pub fn run() {
     env_logger::init();
 
-    tauri::Builder::default()
+    let headless = match headless::HeadlessCommand::from_args(std::env::args()) {
+        Some(Ok(command)) => Some(command),
+        Some(Err(message)) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+        None => None,
+    };
+    let mut context = tauri::generate_context!();
+    if headless.is_some() {
+        // No window: the operation's JSON on stdout is the only output
+        context.config_mut().app.windows.clear();
+    }
+
+    let mut builder = tauri::Builder::default();
+    // The signed updater flow is only available in release builds that embed a pubkey
+    if let Some(pubkey) = commands::self_update::UPDATER_PUBKEY {
+        builder = builder.plugin(tauri_plugin_updater::Builder::new().pubkey(pubkey).build());
+    }
+
+    builder
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
-            None,
+            Some(vec![AUTOSTART_ARG]),
         ))
         .plugin(tauri_plugin_store::Builder::default().build())
-        // .plugin(tauri_plugin_updater::Builder::new().build()) // TODO: enable when pubkey is configured
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    if event.state() != tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        return;
+                    }
+                    match platform::global_shortcuts::action_for(shortcut) {
+                        Some(platform::global_shortcuts::ShortcutAction::ToggleWindow) => {
+                            toggle_main_window_from_shortcut(app);
+                        }
+                        Some(platform::global_shortcuts::ShortcutAction::CheckForUpdates) => {
+                            scheduler::spawn_update_check(app);
+                        }
+                        None => {}
+                    }
+                })
+                .build(),
+        )
         .invoke_handler(tauri::generate_handler![
             commands::apps::get_all_apps,
             commands::apps::get_app_detail,
             commands::apps::trigger_full_scan,
+            commands::apps::rescan_app,
             commands::apps::set_app_ignored,
+            commands::apps::report_bad_match,
+            commands::apps::get_detection_conflicts,
+            commands::apps::set_preferred_detector,
+            commands::apps::set_apps_ignored,
+            commands::apps::move_app_to_applications,
+            commands::apps::set_app_relaunch_policy,
+            commands::apps::get_disk_usage,
+            commands::apps::get_unused_apps,
+            commands::apps::confirm_upgrade_license,
+            commands::mappings::export_mappings,
+            commands::mappings::import_mappings,
+            commands::mappings::set_cask_token,
+            commands::mappings::clear_cask_token,
+            commands::metrics::get_metrics,
+            commands::metrics::export_metrics,
+            commands::metrics::clear_metrics,
             commands::updates::check_all_updates,
             commands::updates::check_single_update,
             commands::updates::debug_update_check,
+            commands::updates::get_checker_metrics,
             commands::updates::get_update_count,
             commands::updates::get_update_history,
+            commands::updates::get_recent_activity,
+            commands::updates::get_os_security_status,
+            commands::update_script::export_update_script,
+            commands::updates::get_app_timeline,
+            commands::execute::preview_update,
             commands::execute::execute_update,
+            commands::execute::get_bulk_update_ids,
             commands::execute::execute_bulk_update,
+            commands::execute::confirm_update_despite_warning,
+            commands::execute::accept_license_and_update,
+            commands::execute::schedule_updates,
+            commands::execute::get_scheduled_updates,
+            commands::execute::cancel_scheduled_updates,
+            commands::integrity::verify_app_integrity,
+            commands::integrity::repair_app,
+            commands::integrity::get_startup_issues,
+            commands::integrity::repair_startup_issue,
             commands::execute::relaunch_app,
             commands::settings::get_settings,
             commands::settings::update_settings,
@@ -112,30 +174,60 @@ pub fn run() {
             commands::system::open_app,
             commands::system::reveal_in_finder,
             commands::system::get_app_icon,
+            commands::system::refresh_app_icon,
             commands::system::get_permissions_status,
+            commands::system::get_automation_targets,
+            commands::system::request_automation_for_updates,
             commands::system::get_permissions_passive,
             commands::system::trigger_automation_permission,
             commands::system::open_system_preferences,
             commands::system::check_setup_status,
+            commands::system::check_homebrew_health,
             commands::system::ensure_askpass_helper,
             commands::system::open_terminal_with_command,
             commands::system::check_connectivity,
+            commands::system::get_offline_state,
+            commands::secrets::set_secret,
+            commands::secrets::get_secret,
+            commands::secrets::delete_secret,
+            commands::privacy::delete_all_data,
             commands::self_update::check_self_update,
             commands::self_update::execute_self_update,
             commands::self_update::relaunch_self,
             commands::uninstall::uninstall_app,
             commands::uninstall::scan_associated_files,
+            commands::uninstall::scan_leftover_files,
+            commands::uninstall::trash_leftover_files,
+            commands::uninstall::scan_orphan_formulae,
+            commands::uninstall::remove_orphan_formulae,
+            commands::uninstall::undo_uninstall,
+            commands::archive::list_app_archives,
+            commands::archive::restore_archived_app,
+            commands::inventory::get_system_extensions,
+            commands::inventory::list_login_items,
+            commands::inventory::enable_login_item,
+            commands::inventory::disable_login_item,
+            commands::inventory::audit_pkg_receipts,
+            commands::inventory::forget_pkg_receipts,
         ])
         // Part 2: Hide main window on close instead of quitting
         .on_window_event(|window, event| {
+            platform::window_geometry::handle_window_event(window, event);
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 if window.label() == "main" {
                     api.prevent_close();
+                    // Remember where the standard window was before it goes away
+                    if platform::window_mode::is_standard_mode() {
+                        let app = window.app_handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            platform::window_geometry::save_window_geometry(&app).await;
+                        });
+                    }
                     let _ = window.hide();
                 }
             }
         })
-        .setup(|app| {
+        .setup(move |app| {
             // Tray-only mode: remove from Dock and Cmd+Tab switcher
             app.set_activation_policy(tauri::ActivationPolicy::Accessory);
 
@@ -145,32 +237,37 @@ pub fn run() {
             let db_path = app_data_dir.join("macplus.db");
             let database = Database::new(&db_path)
                 .expect("Failed to initialize database");
+            if let Err(e) = database.record_launch_version(env!("CARGO_PKG_VERSION")) {
+                log::warn!("Failed to record launch version: {}", e);
+            }
+            // Finish or roll back app replacements a crash or power loss interrupted. A
+            // headless run can start while the app is open, and that instance's updates
+            // in progress would look interrupted, so leave this to a normal launch
+            if headless.is_none() {
+                let unrecovered = executor::recovery::recover_interrupted_updates(&database);
+                if !unrecovered.is_empty() {
+                    log::warn!("Interrupted updates left these apps broken: {}", unrecovered.join(", "));
+                }
+                executor::recovery::record_launch_findings(&database, unrecovered);
+            }
             let db = Arc::new(Mutex::new(database));
             app.manage(db.clone());
 
+            // Restore saved window frames and switch to the standard window if configured
+            {
+                let db_guard = db.blocking_lock();
+                platform::window_geometry::load_saved_geometry(&db_guard);
+                let mode = scheduler::load_settings_from_db(&db_guard).window_mode;
+                if mode != models::WindowMode::Popover && headless.is_none() {
+                    platform::window_mode::apply_window_mode(app.handle(), mode);
+                }
+            }
+
             // Initialize askpass helper
             if let Ok(resource_dir) = app.path().resource_dir() {
                 crate::utils::askpass::init_askpass_path(resource_dir);
             }
 
-            // Clean up stale self-update artifacts from previous runs
-            {
-                let backup = std::path::Path::new("/Applications/macPlus.app.update-backup");
-                if backup.exists() {
-                    let _ = std::fs::remove_dir_all(backup);
-                }
-                if let Ok(entries) = std::fs::read_dir("/tmp") {
-                    for entry in entries.flatten().take(200) {
-                        let name = entry.file_name();
-                        let name = name.to_string_lossy();
-                        if name.starts_with("macplus-update-") || name.starts_with("macplus-self-update-") {
-                            let _ = std::fs::remove_dir_all(entry.path());
-                            let _ = std::fs::remove_file(entry.path());
-                        }
-                    }
-                }
-            }
-
             // Add icon cache directory to asset protocol scope
             if let Ok(cache_dir) = app.path().app_cache_dir() {
                 let icons_dir = cache_dir.join("icons");
@@ -178,18 +275,42 @@ pub fn run() {
                 let _ = app.asset_protocol_scope().allow_directory(&icons_dir, true);
             }
 
-            // Validate settings — prune stale scan locations from migrated databases
+            // Validate settings — prune stale scan locations from migrated databases,
+            // then drop history past the retention period
             {
                 let db_clone = db.clone();
                 tauri::async_runtime::spawn(async move {
                     scheduler::validate_settings(&db_clone).await;
+                    scheduler::prune_history(&db_clone).await;
                 });
             }
 
-            // Initialize HTTP client
-            let client = http_client::create_http_client();
+            // Initialize HTTP client with the configured proxy and CA bundle, and pick
+            // the language for backend strings before the tray menu is built
+            let client = {
+                let db_guard = db.blocking_lock();
+                let settings = scheduler::load_settings_from_db(&db_guard);
+                utils::i18n::apply_language(&settings);
+                utils::staging::apply_staging_settings(&settings);
+                updaters::apply_source_settings(&settings);
+                utils::metrics::apply_metrics_settings(&settings);
+                utils::apply_cask_blocklist(&settings);
+                utils::brew::apply_brew_settings(&settings);
+                updaters::community_mappings::load(&db_guard);
+                http_client::create_http_client(&settings)
+            };
             app.manage(client.clone());
 
+            if let Some(command) = headless {
+                headless::start(app.handle().clone(), command);
+                return Ok(());
+            }
+
+            // Clean up stale self-update artifacts from previous runs, once the download
+            // folder is known (not headless, for the same reason as recovery above: they
+            // may belong to a running instance)
+            executor::recovery::clean_self_update_leftovers();
+
             // Apply vibrancy to main window
             if let Some(window) = app.get_webview_window("main") {
                 #[cfg(target_os = "macos")]
@@ -206,23 +327,19 @@ pub fn run() {
                 scheduler::load_settings_interval(&db_guard)
             };
 
-            // Setup system tray
-            let check_now = MenuItemBuilder::with_id("check_now", "Check for Updates")
-                .build(app)?;
-            let update_count_item = MenuItemBuilder::with_id("update_count", "No updates available")
-                .enabled(false)
-                .build(app)?;
-            let separator = PredefinedMenuItem::separator(app)?;
-            let open_app = MenuItemBuilder::with_id("open_app", "Open macPlus")
-                .build(app)?;
-            let separator2 = PredefinedMenuItem::separator(app)?;
-            let quit = MenuItemBuilder::with_id("quit", "Quit macPlus")
-                .build(app)?;
-            let menu = MenuBuilder::new(app)
-                .items(&[&check_now, &update_count_item, &separator, &open_app, &separator2, &quit])
-                .build()?;
+            // Register configured global shortcuts
+            {
+                let settings = {
+                    let db_guard = db.blocking_lock();
+                    scheduler::load_settings_from_db(&db_guard)
+                };
+                if let Err(e) = platform::global_shortcuts::apply_global_shortcuts(app.handle(), &settings) {
+                    log::warn!("Global shortcuts not fully registered: {}", e);
+                }
+            }
 
-            app.manage(UpdateCountMenuItem(update_count_item));
+            // Setup system tray — the pending-updates submenu is rebuilt after each check
+            let menu = platform::tray_menu::build_tray_menu(app.handle(), &[], 0, &[])?;
 
             let tray_icon_path = app.path().resolve(
                 "icons/tray-icon.png",
@@ -239,7 +356,7 @@ pub fn run() {
                 .tooltip("macPlus")
                 .on_menu_event(move |app, event| match event.id().as_ref() {
                     "check_now" => {
-                        let _ = app.emit("trigger-check", ());
+                        scheduler::spawn_update_check(app);
                         if let Some(tray) = app.tray_by_id("main-tray") {
                             if let Ok(Some(rect)) = tray.rect() {
                                 show_main_window_below_tray(app, &rect);
@@ -268,7 +385,9 @@ pub fn run() {
                     "quit" => {
                         app.exit(0);
                     }
-                    _ => {}
+                    other => {
+                        platform::tray_menu::handle_menu_event(app, other);
+                    }
                 })
                 .on_tray_icon_event(|tray, event| {
                     match event {
@@ -285,6 +404,22 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // The window starts hidden (tauri.conf.json) to avoid a flash on login;
+            // show it unless this is a login launch and the user wants to stay in the tray
+            {
+                let autostarted = std::env::args().any(|a| a == AUTOSTART_ARG);
+                let autostart_hidden = {
+                    let db_guard = db.blocking_lock();
+                    scheduler::load_settings_from_db(&db_guard).autostart_hidden
+                };
+                if autostarted && autostart_hidden {
+                    log::info!("Launched at login — starting hidden in the menu bar");
+                } else if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+
             // Start FSEvents watcher
             scheduler::fs_watcher::start_fs_watcher(app.handle().clone());
 
@@ -296,6 +431,21 @@ pub fn run() {
                 check_interval,
             );
 
+            // Switch to offline mode when the update sources can't be reached
+            scheduler::offline::start_connectivity_monitor(app.handle().clone(), client.clone());
+
+            // Run updates the user deferred, e.g. to tonight, once they're due
+            scheduler::scheduled_updates::start_scheduled_update_runner(app.handle().clone());
+
+            // Install security updates after each check when the policy says so
+            scheduler::security_updates::start_security_update_runner(app.handle().clone());
+
+            // Deliver notifications held back during Focus once it ends
+            scheduler::notifications::start_deferred_notification_flusher(app.handle().clone());
+
+            // Detach disk images that failed or interrupted updates left mounted
+            scheduler::mount_sweeper::start_mount_sweeper();
+
             // Lightweight self-update poller — checks GitHub every 5 min
             scheduler::start_self_update_poller(
                 app.handle().clone(),
@@ -304,7 +454,7 @@ pub fn run() {
 
             Ok(())
         })
-        .build(tauri::generate_context!())
+        .build(context)
         .expect("error while building macPlus")
         .run(|app_handle, event| {
             match event {
@@ -318,6 +468,13 @@ pub fn run() {
                         }
                     }
                 }
+                tauri::RunEvent::Exit => {
+                    if platform::window_mode::is_standard_mode() {
+                        tauri::async_runtime::block_on(platform::window_geometry::save_window_geometry(app_handle));
+                    }
+                    // Install a silently staged self-update on the way out
+                    commands::self_update::apply_staged_update();
+                }
                 tauri::RunEvent::Reopen { has_visible_windows, .. } => {
                     if !has_visible_windows {
                         if let Some(window) = app_handle.get_webview_window("main") {
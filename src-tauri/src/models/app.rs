@@ -32,6 +32,37 @@ impl AppSource {
     }
 }
 
+/// What an updater may do with an app that's open when its update installs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RelaunchPolicy {
+    /// Quit the app to update it and reopen it afterwards
+    #[default]
+    Automatic,
+    /// Quit the app to update it but leave it closed
+    KeepClosed,
+    /// Never quit the app; updates that need it closed wait until the user quits it
+    NeverQuit,
+}
+
+impl RelaunchPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RelaunchPolicy::Automatic => "automatic",
+            RelaunchPolicy::KeepClosed => "keep_closed",
+            RelaunchPolicy::NeverQuit => "never_quit",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "keep_closed" => RelaunchPolicy::KeepClosed,
+            "never_quit" => RelaunchPolicy::NeverQuit,
+            _ => RelaunchPolicy::Automatic,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleInfo {
     pub bundle_id: String,
@@ -45,6 +76,18 @@ pub struct BundleInfo {
     pub min_system_version: Option<String>,
 }
 
+/// Where an Electron app looks for updates, from electron-builder's
+/// `Contents/Resources/app-update.yml`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectronUpdateFeed {
+    /// "github", "generic", "s3", ...
+    pub provider: String,
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DetectedApp {
@@ -60,6 +103,45 @@ pub struct DetectedApp {
     pub sparkle_feed_url: Option<String>,
     pub mas_app_id: Option<String>,
     pub homebrew_formula_name: Option<String>,
+    /// Helper apps embedded in this app's bundle (folded in by the deduplicator)
+    pub helper_apps: Vec<HelperApp>,
+}
+
+/// An app bundle nested inside another app (updaters, crash handlers, login helpers).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HelperApp {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub app_path: String,
+}
+
+/// What one detector reported for an app.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionCandidate {
+    /// Detector name, e.g. "Spotlight" or "Homebrew"
+    pub detector: String,
+    pub app_path: String,
+    pub installed_version: Option<String>,
+    pub install_source: String,
+}
+
+/// Detectors that disagreed about an app's path, version or source during the
+/// last scan, and the values that were kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionConflict {
+    pub bundle_id: String,
+    pub display_name: String,
+    /// Which values disagreed: "path", "version" and/or "source"
+    pub fields: Vec<String>,
+    pub candidates: Vec<DetectionCandidate>,
+    pub app_path: String,
+    pub installed_version: Option<String>,
+    pub install_source: String,
+    /// Detector the user picked for this app, if any
+    pub preferred_detector: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +166,14 @@ pub struct AppSummary {
     pub release_notes_url: Option<String>,
     pub update_notes: Option<String>,
     pub description: Option<String>,
+    /// Expected download size of the pending update in bytes
+    pub download_size: Option<u64>,
+    /// When the app was last opened (UTC, per Spotlight)
+    pub last_used_at: Option<String>,
+    /// The pending update is a paid upgrade the user hasn't confirmed a license for
+    pub needs_license: bool,
+    /// The pending update fixes security issues
+    pub is_security_update: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,6 +198,97 @@ pub struct AppDetail {
     pub description: Option<String>,
     pub update_sources: Vec<UpdateSourceInfo>,
     pub available_update: Option<AvailableUpdateInfo>,
+    pub helper_apps: Vec<HelperApp>,
+    #[serde(default)]
+    pub relaunch_policy: RelaunchPolicy,
+    /// Set when the app runs from somewhere updates can't reliably replace it
+    #[serde(default)]
+    pub location_issue: Option<LocationIssue>,
+}
+
+/// Outcome of checking an app bundle's code signature and sealed resources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub bundle_id: String,
+    pub valid: bool,
+    /// What `codesign --verify` found wrong, e.g. modified or missing files
+    pub problems: Vec<String>,
+    /// How the app can be reinstalled, if it can be done automatically
+    pub repair: Option<RepairMethod>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairMethod {
+    /// `brew reinstall --cask`
+    Homebrew,
+    /// Download the installer again from the app's update URL
+    Download,
+}
+
+/// A sign, found at launch, that an update in an earlier run was cut short.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupIssueKind {
+    /// An app whose update was interrupted is gone from its path
+    MissingApp,
+    /// The copy of an app set aside during an update was never removed or put back
+    LeftoverBackup,
+    /// A disk image mounted for an update download is still attached
+    StaleMount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupIssue {
+    pub kind: StartupIssueKind,
+    /// The missing app, the backup or the mount point
+    pub path: String,
+    pub bundle_id: Option<String>,
+    pub display_name: Option<String>,
+    /// Whether macPlus can fix it without the user's help
+    pub repairable: bool,
+}
+
+/// Why an app's location gets in the way of updating it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationIssue {
+    /// Running from a randomized read-only copy because it's still quarantined
+    Translocated,
+    /// Installed on a mounted disk image
+    DiskImage,
+    /// Left in ~/Downloads
+    Downloads,
+}
+
+/// Cached on-disk footprint of an app: its bundle plus associated ~/Library data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageEntry {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub app_path: String,
+    pub icon_cache_path: Option<String>,
+    pub bundle_size_bytes: u64,
+    pub data_size_bytes: u64,
+    pub total_size_bytes: u64,
+    pub size_computed_at: Option<String>,
+}
+
+/// An app that hasn't been opened in a while, with its cached footprint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedApp {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub app_path: String,
+    pub icon_cache_path: Option<String>,
+    /// None when Spotlight has no record of the app ever being opened
+    pub last_used_at: Option<String>,
+    /// Bundle plus associated data, once sizes have been computed
+    pub total_size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -128,6 +309,90 @@ pub struct AvailableUpdateInfo {
     pub download_url: Option<String>,
     pub release_notes: Option<String>,
     pub is_paid_upgrade: bool,
+    /// The user has confirmed owning a license that covers this paid upgrade
+    pub license_confirmed: bool,
     pub detected_at: Option<String>,
     pub notes: Option<String>,
+    pub download_size: Option<u64>,
+}
+
+/// User-defined overrides of where an app's updates come from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppMapping {
+    pub bundle_id: String,
+    /// GitHub "owner/repo" slug
+    pub github_repo: Option<String>,
+    pub custom_feed_url: Option<String>,
+    pub homebrew_cask_token: Option<String>,
+    pub mas_id: Option<i64>,
+}
+
+/// A shareable file of user-defined mappings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingPack {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub mappings: Vec<AppMapping>,
+}
+
+/// An imported mapping that disagrees with one the user already has.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingConflict {
+    pub current: AppMapping,
+    pub incoming: AppMapping,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingImportReport {
+    pub added: usize,
+    /// Existing mappings replaced because the import was told to overwrite
+    pub replaced: usize,
+    pub unchanged: usize,
+    /// Existing mappings kept because the import disagreed with them
+    pub conflicts: Vec<MappingConflict>,
+    /// Bundle IDs whose entries were malformed and skipped
+    pub invalid: Vec<String>,
+}
+
+impl AvailableUpdateInfo {
+    /// A paid upgrade the user hasn't said they own a license for. These are left
+    /// out of bulk updates and can't be installed until confirmed.
+    pub fn needs_license(&self) -> bool {
+        self.is_paid_upgrade && !self.license_confirmed
+    }
+}
+
+/// Leading number of a version string, e.g. 6 for "v6.2.1".
+fn major_version(version: &str) -> Option<u64> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let end = version.find(|c: char| !c.is_ascii_digit()).unwrap_or(version.len());
+    version[..end].parse().ok()
+}
+
+/// Whether a license confirmed for `licensed_version` covers `version`. Paid
+/// upgrades are sold per major version, so a 6.0 license covers 6.2 but not 7.0.
+pub fn license_covers(licensed_version: &str, version: &str) -> bool {
+    match (major_version(licensed_version), major_version(version)) {
+        (Some(licensed), Some(major)) => licensed >= major,
+        _ => licensed_version == version,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn licenses_cover_their_major_version() {
+        assert!(license_covers("6.0", "6.2.1"));
+        assert!(license_covers("v6", "6.0.3"));
+        assert!(!license_covers("6.0", "7.0"));
+        assert!(license_covers("2024.1", "2024.1.2"));
+        assert!(license_covers("Build A", "Build A"));
+        assert!(!license_covers("Build A", "Build B"));
+    }
 }
@@ -22,6 +22,21 @@ pub struct UpdateCheckProgress {
     pub checked: usize,
     pub total: usize,
     pub current_app: Option<String>,
+    pub phase: CheckPhase,
+    /// Estimated seconds until the check finishes, once a previous check has been timed
+    pub eta_secs: Option<u64>,
+}
+
+/// Stage of an update check. `BrewOutdated` and `CaskIndex` overlap; `CaskIndex`
+/// is reported once `brew outdated` is done but the index is still downloading.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckPhase {
+    Preparing,
+    BrewUpdate,
+    BrewOutdated,
+    CaskIndex,
+    AppChecks,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +63,16 @@ pub struct UpdateExecuteProgress {
     pub percent: u8,
     pub downloaded_bytes: Option<u64>,
     pub total_bytes: Option<u64>,
+    /// The update is waiting on the user, e.g. for an administrator password
+    pub waiting: bool,
+}
+
+/// Whether a progress phase, as an executor reports it before translation, waits
+/// on the user to approve a password prompt.
+pub fn is_waiting_phase(phase: &str) -> bool {
+    ["administrator", "admin privileges", "askpass"]
+        .iter()
+        .any(|marker| phase.contains(marker))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +86,86 @@ pub struct UpdateExecuteComplete {
     pub app_path: Option<String>,
     #[serde(default)]
     pub delegated: bool,
+    #[serde(default)]
+    pub gatekeeper: Option<crate::models::GatekeeperVerdict>,
+    #[serde(default)]
+    pub error_code: Option<crate::models::UpdateErrorCode>,
+    /// Command that retries a failed update in Terminal, for "Fix in Terminal"
+    #[serde(default)]
+    pub fix_command: Option<String>,
+}
+
+/// An app listed in a `BulkUpdateSummary`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSummaryApp {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub message: Option<String>,
+    /// Set for apps that need a relaunch
+    pub app_path: Option<String>,
+    /// Set for failed updates a Terminal command can retry
+    #[serde(default)]
+    pub fix_command: Option<String>,
+}
+
+/// Apps of a bulk update that failed for the same reason.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkFailureGroup {
+    pub error_code: crate::models::UpdateErrorCode,
+    pub apps: Vec<BulkSummaryApp>,
+}
+
+/// Sent once a bulk update has finished, after the `update-execute-complete`
+/// events of all its apps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Handed off to the app itself or the App Store; included in `succeeded`
+    pub delegated: usize,
+    pub failures: Vec<BulkFailureGroup>,
+    /// Updated while running; the new version starts after a relaunch
+    pub needs_relaunch: Vec<BulkSummaryApp>,
+    /// Delegated updates to finish in the app, and failures the user has to
+    /// fix before a retry can work
+    pub needs_action: Vec<BulkSummaryApp>,
+}
+
+/// Sent before a single update starts, so the UI can say up front whether a
+/// password prompt is coming.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateElevationPreflight {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub needs_password: bool,
+    /// Why a password will probably be needed, e.g. a folder the user can't write to
+    pub reason: Option<String>,
+}
+
+/// An update was held back because it's signed by a different developer than the
+/// installed app. `confirm_update_despite_warning` installs it anyway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTrustWarning {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub installed_team_id: String,
+    pub downloaded_team_id: Option<String>,
+}
+
+/// Emitted when an update was held back for the user to read the license
+/// agreement of its disk image. `accept_license_and_update` installs it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLicensePrompt {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub text: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +180,30 @@ pub struct UpdateHistoryEntry {
     pub source_type: String,
     pub status: String,
     pub error_message: Option<String>,
+    /// Gatekeeper's verdict on the installed bundle, e.g. "rejected: Unnotarized Developer ID"
+    pub gatekeeper_verdict: Option<String>,
+    /// Whether macPlus accepted a disk image license agreement without asking
+    pub license_accepted: bool,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
+    /// 1, or more when the update was retried after a transient failure
+    pub attempts: u32,
+}
+
+/// Something the user asked for while offline, run once connectivity returns.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QueuedAction {
+    UpdateCheck,
+    #[serde(rename_all = "camelCase")]
+    Update { bundle_id: String },
+}
+
+/// Payload of `offline-state`, emitted when connectivity is lost or comes back
+/// and whenever an action is queued.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineState {
+    pub offline: bool,
+    pub queued: Vec<QueuedAction>,
 }
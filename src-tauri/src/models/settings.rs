@@ -5,15 +5,139 @@ use serde::{Deserialize, Serialize};
 pub struct AppSettings {
     pub check_interval_minutes: u32,
     pub launch_at_login: bool,
+    /// When launched at login, stay in the menu bar instead of opening the window
+    pub autostart_hidden: bool,
     pub show_menu_bar_icon: bool,
     pub notification_on_updates: bool,
     pub auto_check_on_launch: bool,
     pub theme: ThemeMode,
     pub ignored_bundle_ids: Vec<String>,
-    pub scan_locations: Vec<String>,
+    /// Apps matching any of these are skipped by update checks, like ignored apps
+    pub ignore_rules: Vec<IgnoreRule>,
+    /// Update sources that are never checked, for any app
+    pub disabled_sources: Vec<crate::models::UpdateSourceType>,
+    pub scan_locations: Vec<ScanLocation>,
+    /// Default depth for scan locations that don't set their own
     pub scan_depth: u32,
+    /// Glob patterns for app paths to leave out of scans; a matching folder excludes its contents
+    pub excluded_paths: Vec<String>,
+    /// Bundle ID prefixes to leave out of scans (e.g. "com.example.")
+    pub excluded_bundle_ids: Vec<String>,
+    /// Bundle IDs never matched to Homebrew casks, on top of the built-in list
+    pub cask_blocklist: Vec<String>,
     pub show_badge_count: bool,
-    pub notification_sound: bool,
+    pub tray_icon_style: TrayIconStyle,
+    pub tray_badge_style: TrayBadgeStyle,
+    /// Hide the menu bar icon while there are no pending updates
+    pub hide_tray_when_idle: bool,
+    /// Use a smaller, narrower menu bar icon
+    pub compact_tray_icon: bool,
+    /// Sound and which events notify, beyond `notification_on_updates`
+    pub notification_policy: NotificationPolicy,
+    /// Folder for zip archives taken before uninstalling (None = ~/Documents/macPlus Archives)
+    pub archive_dir: Option<String>,
+    /// Folder update downloads are staged in (None = the system temp folder)
+    pub download_dir: Option<String>,
+    pub update_channel: UpdateChannel,
+    /// Download macPlus updates in the background and install them on quit
+    pub auto_self_update: bool,
+    /// Which app updates install right after a check, without waiting for the user
+    pub auto_update_policy: AutoUpdatePolicy,
+    /// Replace per-check notifications with a periodic summary
+    pub notification_digest: NotificationDigest,
+    /// Hold notifications while a Focus mode is on and deliver them afterwards
+    pub defer_notifications_during_focus: bool,
+    /// Hold scheduled updates, which relaunch apps, and macPlus's own background
+    /// download while a Focus mode is on
+    pub defer_auto_updates_during_focus: bool,
+    /// Global hotkey (Tauri accelerator, e.g. "CommandOrControl+Shift+M") that toggles the window
+    pub toggle_window_shortcut: Option<String>,
+    /// Global hotkey that starts an update check
+    pub check_updates_shortcut: Option<String>,
+    pub window_mode: WindowMode,
+    /// Proxy used for update checks and downloads; takes effect after a restart
+    pub proxy_mode: ProxyMode,
+    /// Manual proxy URL: http://, https://, socks5:// or socks5h://
+    pub proxy_url: Option<String>,
+    /// Comma-separated hosts that bypass the manual proxy (e.g. "localhost,.corp.example")
+    pub proxy_bypass: Option<String>,
+    /// Username for an authenticating proxy; the password is kept in the Keychain
+    pub proxy_username: Option<String>,
+    /// PEM bundle of extra root certificates (e.g. a TLS-inspecting proxy's CA)
+    pub custom_ca_path: Option<String>,
+    /// Seconds allowed for a single HTTP request (5–300)
+    pub request_timeout_secs: u32,
+    /// Seconds one update source may spend checking one app, retries included (10–600)
+    pub checker_timeout_secs: u32,
+    /// Extra attempts for requests that fail with a timeout, connection error, 429 or 5xx (0–5)
+    pub network_retries: u32,
+    /// Days of update and uninstall history to keep (0 = keep forever)
+    pub history_retention_days: u32,
+    /// Store release notes for pending updates in the database
+    pub cache_release_notes: bool,
+    /// Keep local usage metrics (check times, hit rates, downloads, failures)
+    pub collect_metrics: bool,
+    /// Language for notifications, the tray menu, progress phases and errors
+    pub language: Language,
+    /// What to do with an app being replaced when it can't be moved to the Trash
+    pub permanent_delete: PermanentDeletePolicy,
+    /// What to do when a downloaded disk image asks to accept a license agreement
+    pub dmg_license: DmgLicensePolicy,
+    /// Environment for the `brew` commands macPlus runs
+    pub brew_environment: BrewEnvironment,
+}
+
+/// A folder searched for apps. Settings saved by older versions list plain path
+/// strings; those load with the default depth and symlinks followed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", from = "ScanLocationRepr")]
+pub struct ScanLocation {
+    pub path: String,
+    /// How many folder levels to search (1 = top level only); None uses `scan_depth`
+    pub depth: Option<u32>,
+    /// Descend into symlinked folders
+    pub follow_symlinks: bool,
+}
+
+impl ScanLocation {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            depth: None,
+            follow_symlinks: true,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScanLocationRepr {
+    Path(String),
+    #[serde(rename_all = "camelCase")]
+    Full {
+        path: String,
+        #[serde(default)]
+        depth: Option<u32>,
+        #[serde(default = "default_true")]
+        follow_symlinks: bool,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl From<ScanLocationRepr> for ScanLocation {
+    fn from(repr: ScanLocationRepr) -> Self {
+        match repr {
+            ScanLocationRepr::Path(path) => ScanLocation::new(path),
+            ScanLocationRepr::Full { path, depth, follow_symlinks } => ScanLocation {
+                path,
+                depth,
+                follow_symlinks,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -24,23 +148,349 @@ pub enum ThemeMode {
     Dark,
 }
 
+/// Menu bar icon rendering: a template image that follows the menu bar appearance,
+/// or a colored icon.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayIconStyle {
+    #[default]
+    Monochrome,
+    Colored,
+}
+
+/// How pending updates are marked on the menu bar icon.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayBadgeStyle {
+    #[default]
+    Number,
+    Dot,
+}
+
+/// How the main window is presented: anchored below the tray icon, or as a regular
+/// resizable window that shows in the Dock and Cmd+Tab.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowMode {
+    #[default]
+    Popover,
+    Standard,
+}
+
+/// Whether an updater may delete an old app bundle outright when moving it to
+/// the Trash fails.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PermanentDeletePolicy {
+    /// Fail the update instead of deleting anything
+    Never,
+    /// Ask before each permanent delete
+    Ask,
+    #[default]
+    Allow,
+}
+
+/// What updaters do when a downloaded disk image shows a license agreement
+/// before it mounts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DmgLicensePolicy {
+    /// Accept it, like clicking Agree when opening the image in Finder
+    #[default]
+    Accept,
+    /// Hold the update back until the user has read and accepted it
+    Prompt,
+    /// Fail the update
+    Abort,
+}
+
+/// Which pending app updates macPlus installs by itself after an update check.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoUpdatePolicy {
+    /// Every update waits for the user
+    #[default]
+    Manual,
+    /// Updates that fix security issues install right away; feature updates wait
+    /// for review
+    SecurityOnly,
+}
+
+/// Variables set for `brew`, for setups the defaults don't fit (e.g. casks
+/// installed with a custom `--appdir`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BrewEnvironment {
+    /// Set HOMEBREW_NO_AUTO_UPDATE so each command doesn't update Homebrew first
+    pub no_auto_update: bool,
+    /// HOMEBREW_CASK_OPTS, e.g. "--appdir=/Volumes/Apps/Applications"
+    pub cask_opts: Option<String>,
+    /// Colon-separated folders searched before the default PATH, e.g. for tools a cask's scripts need
+    pub extra_path: Option<String>,
+}
+
+/// Where HTTP requests are routed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyMode {
+    /// Use the proxy configured in System Settings
+    #[default]
+    System,
+    /// Connect directly, ignoring any system proxy
+    None,
+    /// Use `proxy_url`
+    Manual,
+}
+
+/// A rule that ignores every app it matches, e.g. all App Store apps.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum IgnoreRule {
+    /// Install source as stored on the app ("mas", "homebrew", "direct", ...)
+    Source(String),
+    /// Bundle ID prefix, case-insensitive (e.g. "com.adobe.")
+    BundleIdPrefix(String),
+}
+
+impl IgnoreRule {
+    pub fn matches(&self, bundle_id: &str, install_source: &str) -> bool {
+        match self {
+            IgnoreRule::Source(source) => source == install_source,
+            IgnoreRule::BundleIdPrefix(prefix) => {
+                let prefix = prefix.trim().to_lowercase();
+                !prefix.is_empty() && bundle_id.to_lowercase().starts_with(&prefix)
+            }
+        }
+    }
+}
+
+/// Which events notify and how they sound. `notification_on_updates` stays the
+/// master switch for all of them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationPolicy {
+    pub sound: NotificationSound,
+    /// Which finished updates notify
+    pub update_results: UpdateResultNotifications,
+    /// Notify when an uninstall finishes
+    pub on_uninstall: bool,
+}
+
+impl Default for NotificationPolicy {
+    fn default() -> Self {
+        Self {
+            sound: NotificationSound::Glass,
+            update_results: UpdateResultNotifications::All,
+            on_uninstall: true,
+        }
+    }
+}
+
+impl NotificationPolicy {
+    pub fn notifies_update_result(&self, success: bool) -> bool {
+        match self.update_results {
+            UpdateResultNotifications::All => true,
+            UpdateResultNotifications::FailuresOnly => !success,
+            UpdateResultNotifications::Off => false,
+        }
+    }
+}
+
+/// A sound from /System/Library/Sounds, or silence.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSound {
+    None,
+    Basso,
+    Blow,
+    Bottle,
+    Frog,
+    Funk,
+    #[default]
+    Glass,
+    Hero,
+    Morse,
+    Ping,
+    Pop,
+    Purr,
+    Sosumi,
+    Submarine,
+    Tink,
+}
+
+impl NotificationSound {
+    /// The system sound name, or None for silent notifications.
+    pub fn sound_name(&self) -> Option<&'static str> {
+        Some(match self {
+            NotificationSound::None => return None,
+            NotificationSound::Basso => "Basso",
+            NotificationSound::Blow => "Blow",
+            NotificationSound::Bottle => "Bottle",
+            NotificationSound::Frog => "Frog",
+            NotificationSound::Funk => "Funk",
+            NotificationSound::Glass => "Glass",
+            NotificationSound::Hero => "Hero",
+            NotificationSound::Morse => "Morse",
+            NotificationSound::Ping => "Ping",
+            NotificationSound::Pop => "Pop",
+            NotificationSound::Purr => "Purr",
+            NotificationSound::Sosumi => "Sosumi",
+            NotificationSound::Submarine => "Submarine",
+            NotificationSound::Tink => "Tink",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateResultNotifications {
+    /// Successes, failures and hand-offs to the app's own updater
+    #[default]
+    All,
+    FailuresOnly,
+    Off,
+}
+
+/// Language for strings produced by the backend.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// Follow the first supported language in the macOS language list
+    #[default]
+    System,
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+/// Release channel for macPlus's own updates. Beta includes GitHub prereleases.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+}
+
+/// How often update notifications are batched into a single digest.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationDigest {
+    /// Notify after every check that finds new updates
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl NotificationDigest {
+    /// Time between digests, or None when digests are off.
+    pub fn period(&self) -> Option<chrono::Duration> {
+        match self {
+            NotificationDigest::Off => None,
+            NotificationDigest::Daily => Some(chrono::Duration::days(1)),
+            NotificationDigest::Weekly => Some(chrono::Duration::weeks(1)),
+        }
+    }
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             check_interval_minutes: 60,
             launch_at_login: false,
+            autostart_hidden: true,
             show_menu_bar_icon: true,
             notification_on_updates: true,
             auto_check_on_launch: true,
             theme: ThemeMode::System,
             ignored_bundle_ids: Vec::new(),
+            ignore_rules: Vec::new(),
+            disabled_sources: Vec::new(),
             scan_locations: vec![
-                "/Applications".into(),
-                "~/Applications".into(),
+                ScanLocation::new("/Applications"),
+                ScanLocation::new("~/Applications"),
             ],
             scan_depth: 2,
+            excluded_paths: Vec::new(),
+            excluded_bundle_ids: Vec::new(),
+            cask_blocklist: Vec::new(),
             show_badge_count: true,
-            notification_sound: true,
+            tray_icon_style: TrayIconStyle::Monochrome,
+            tray_badge_style: TrayBadgeStyle::Number,
+            hide_tray_when_idle: false,
+            compact_tray_icon: false,
+            notification_policy: NotificationPolicy::default(),
+            archive_dir: None,
+            download_dir: None,
+            update_channel: UpdateChannel::Stable,
+            auto_self_update: false,
+            auto_update_policy: AutoUpdatePolicy::Manual,
+            notification_digest: NotificationDigest::Off,
+            defer_notifications_during_focus: true,
+            defer_auto_updates_during_focus: true,
+            toggle_window_shortcut: None,
+            check_updates_shortcut: None,
+            window_mode: WindowMode::Popover,
+            proxy_mode: ProxyMode::System,
+            proxy_url: None,
+            proxy_bypass: None,
+            proxy_username: None,
+            custom_ca_path: None,
+            request_timeout_secs: 30,
+            checker_timeout_secs: 60,
+            network_retries: 1,
+            history_retention_days: 0,
+            cache_release_notes: true,
+            collect_metrics: false,
+            language: Language::System,
+            permanent_delete: PermanentDeletePolicy::Allow,
+            dmg_license: DmgLicensePolicy::Accept,
+            brew_environment: BrewEnvironment::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_locations_accept_legacy_strings() {
+        let settings: AppSettings = serde_json::from_str(
+            r#"{"scanLocations": ["/Applications", {"path": "~/Dev", "depth": 4, "followSymlinks": false}]}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.scan_locations[0], ScanLocation::new("/Applications"));
+        assert_eq!(
+            settings.scan_locations[1],
+            ScanLocation {
+                path: "~/Dev".into(),
+                depth: Some(4),
+                follow_symlinks: false,
+            }
+        );
+    }
+
+    #[test]
+    fn ignore_rules_match_source_or_prefix() {
+        let rules: Vec<IgnoreRule> = serde_json::from_str(
+            r#"[{"kind": "source", "value": "mas"}, {"kind": "bundle_id_prefix", "value": "com.Adobe."}]"#,
+        )
+        .unwrap();
+        assert!(rules[0].matches("com.example.app", "mas"));
+        assert!(!rules[0].matches("com.example.app", "direct"));
+        assert!(rules[1].matches("com.adobe.Photoshop", "direct"));
+        assert!(!IgnoreRule::BundleIdPrefix("  ".into()).matches("com.adobe.Photoshop", "direct"));
+    }
+}
@@ -15,7 +15,11 @@ pub struct UninstallResult {
     pub message: Option<String>,
     pub running: bool,
     pub cleaned_paths: Vec<String>,
+    /// Associated files still present after the uninstall (e.g. data Homebrew doesn't remove)
+    pub leftover_files: Vec<AssociatedFile>,
     pub protected: bool,
+    /// How the app bundle itself was removed, when it was
+    pub removal: Option<crate::models::RemovalMethod>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,3 +36,79 @@ pub struct AssociatedFile {
     pub size_bytes: u64,
     pub kind: String,
 }
+
+/// A ~/Library item whose owning bundle ID doesn't match any installed app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeftoverFile {
+    pub path: String,
+    pub bundle_id: String,
+    pub size_bytes: u64,
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeftoverFiles {
+    pub files: Vec<LeftoverFile>,
+    pub total_size_bytes: u64,
+}
+
+/// A Homebrew formula installed only as a dependency of formulae that are gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanFormula {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanFormulae {
+    pub formulae: Vec<OrphanFormula>,
+    pub total_size_bytes: u64,
+}
+
+/// A support file stored in an uninstall archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedFile {
+    pub original_path: String,
+    /// Path of the copy inside the archive, relative to its root folder
+    pub entry: String,
+}
+
+/// A zip snapshot of an app (and optionally its support files) taken before uninstalling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppArchive {
+    pub id: i64,
+    pub bundle_id: String,
+    pub display_name: String,
+    pub app_path: String,
+    pub archive_path: String,
+    pub size_bytes: u64,
+    pub support_files: Vec<ArchivedFile>,
+    pub created_at: String,
+    pub restored_at: Option<String>,
+}
+
+/// A path moved to the Trash during an uninstall, with its location in the Trash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedItem {
+    pub original_path: String,
+    pub trash_path: String,
+}
+
+/// What an uninstall removed, kept so `undo_uninstall` can put it back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallRecord {
+    pub id: i64,
+    pub bundle_id: String,
+    /// The app row as it was before removal
+    pub app: crate::models::AppDetail,
+    pub trashed_items: Vec<TrashedItem>,
+    pub uninstalled_at: String,
+}
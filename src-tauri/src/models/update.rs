@@ -63,6 +63,12 @@ pub struct UpdateInfo {
     pub release_notes: Option<String>,
     pub is_paid_upgrade: bool,
     pub notes: Option<String>,
+    /// Expected size of the download in bytes, when the source or a HEAD request reports it
+    #[serde(default)]
+    pub download_size: Option<u64>,
+    /// Build number (`CFBundleVersion`) of the available version, when the source has one
+    #[serde(default)]
+    pub available_build: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +84,277 @@ pub struct UpdateResult {
     pub handled_relaunch: bool,
     #[serde(default)]
     pub delegated: bool,
+    /// How the replaced app bundle was disposed of, when the executor replaced it itself
+    #[serde(default)]
+    pub removal: Option<RemovalMethod>,
+    /// Set when the download was signed by a different team than the installed app
+    /// and the update was held back for confirmation
+    #[serde(default)]
+    pub team_id_change: Option<TeamIdChange>,
+    /// Gatekeeper's assessment of the installed bundle, for updates that installed one
+    #[serde(default)]
+    pub gatekeeper: Option<GatekeeperVerdict>,
+    /// Set when the downloaded disk image asked to accept a license agreement
+    #[serde(default)]
+    pub license: Option<LicenseAgreement>,
+    /// Why the update failed, or `GatekeeperBlocked` for one that installed a
+    /// version Gatekeeper would block. `message` has the details.
+    #[serde(default)]
+    pub error_code: Option<UpdateErrorCode>,
+}
+
+impl UpdateResult {
+    /// A failed update, with `message` saying why. Callers set whatever else they
+    /// know, e.g. `from_version` or `error_code`, with struct update syntax.
+    pub fn failed(
+        bundle_id: impl Into<String>,
+        source_type: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            bundle_id: bundle_id.into(),
+            success: false,
+            message: Some(message.into()),
+            source_type: source_type.into(),
+            from_version: None,
+            to_version: None,
+            handled_relaunch: false,
+            delegated: false,
+            removal: None,
+            team_id_change: None,
+            gatekeeper: None,
+            license: None,
+            error_code: None,
+        }
+    }
+}
+
+/// What went wrong with an update, so the frontend doesn't have to read `message`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateErrorCode {
+    /// Administrator rights or the App Management permission are needed
+    NeedsElevation,
+    /// macOS denies macPlus the App Management permission, so it can't replace
+    /// the app; found before the update started
+    AppManagementDenied,
+    /// The user dismissed a password or confirmation prompt
+    UserCancelled,
+    /// Timeout, DNS or connection failure, or a server error
+    NetworkError,
+    /// The installed version would be blocked by Gatekeeper on first launch
+    GatekeeperBlocked,
+    /// The app is open and its relaunch policy forbids quitting it
+    AppRunning,
+    /// The download didn't match its expected checksum
+    ChecksumMismatch,
+    /// The download is signed by a different developer than the installed app
+    TeamIdMismatch,
+    /// The downloaded disk image asks to accept a license agreement and the
+    /// license setting doesn't allow accepting it without the user
+    LicenseAgreement,
+    /// The installer reported success but the app's version didn't change
+    VersionUnchanged,
+    /// A paid upgrade the user hasn't confirmed owning a license for
+    PaidUpgrade,
+    /// The update comes from a source the app wasn't installed from, e.g. an
+    /// App Store update of an app downloaded from its developer
+    SourceMismatch,
+    /// Queued until macPlus is back online
+    Offline,
+    Unknown,
+}
+
+impl UpdateErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateErrorCode::NeedsElevation => "needs_elevation",
+            UpdateErrorCode::AppManagementDenied => "app_management_denied",
+            UpdateErrorCode::UserCancelled => "user_cancelled",
+            UpdateErrorCode::NetworkError => "network_error",
+            UpdateErrorCode::GatekeeperBlocked => "gatekeeper_blocked",
+            UpdateErrorCode::AppRunning => "app_running",
+            UpdateErrorCode::ChecksumMismatch => "checksum_mismatch",
+            UpdateErrorCode::TeamIdMismatch => "team_id_mismatch",
+            UpdateErrorCode::LicenseAgreement => "license_agreement",
+            UpdateErrorCode::VersionUnchanged => "version_unchanged",
+            UpdateErrorCode::PaidUpgrade => "paid_upgrade",
+            UpdateErrorCode::SourceMismatch => "source_mismatch",
+            UpdateErrorCode::Offline => "offline",
+            UpdateErrorCode::Unknown => "unknown",
+        }
+    }
+
+    /// Failures the user has to do something about before a retry can work,
+    /// rather than ones that may go away by themselves.
+    pub fn needs_user_action(self) -> bool {
+        matches!(
+            self,
+            UpdateErrorCode::NeedsElevation
+                | UpdateErrorCode::AppManagementDenied
+                | UpdateErrorCode::GatekeeperBlocked
+                | UpdateErrorCode::AppRunning
+                | UpdateErrorCode::TeamIdMismatch
+                | UpdateErrorCode::LicenseAgreement
+                | UpdateErrorCode::PaidUpgrade
+                | UpdateErrorCode::SourceMismatch
+        )
+    }
+}
+
+/// Result of `spctl --assess` on an installed bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GatekeeperVerdict {
+    /// False when Gatekeeper would block the app on its first launch
+    pub accepted: bool,
+    /// Why, e.g. "Notarized Developer ID" or "Unnotarized Developer ID"
+    pub source: Option<String>,
+}
+
+impl GatekeeperVerdict {
+    /// One-line form stored in the update history.
+    pub fn summary(&self) -> String {
+        let verdict = if self.accepted { "accepted" } else { "rejected" };
+        match self.source {
+            Some(ref source) => format!("{}: {}", verdict, source),
+            None => verdict.to_string(),
+        }
+    }
+}
+
+/// A license agreement shown by a downloaded disk image before it mounts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseAgreement {
+    pub text: String,
+    /// False when the update was held back for the user to read it
+    pub accepted: bool,
+}
+
+/// The signing Team IDs of an installed app and of a downloaded update that differ.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamIdChange {
+    pub installed: String,
+    /// None when the download is unsigned or ad-hoc signed
+    pub downloaded: Option<String>,
+}
+
+/// One entry in an app's version timeline, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTimelineEvent {
+    pub kind: TimelineEventKind,
+    pub version: String,
+    /// Version the app was updated from, for update events
+    pub from_version: Option<String>,
+    pub source_type: Option<String>,
+    pub error_message: Option<String>,
+    /// SQLite datetime (UTC)
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    /// A scan found this version installed for the first time
+    FirstSeen,
+    /// An update installed this version
+    Updated,
+    /// An update to this version failed
+    UpdateFailed,
+}
+
+/// A Homebrew package an upgrade moves from one version to another.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageChange {
+    pub name: String,
+    pub from_version: String,
+    pub to_version: String,
+}
+
+/// What else a formula upgrade touches besides the formula itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormulaImpact {
+    /// Outdated dependencies brew upgrades first
+    pub dependency_upgrades: Vec<PackageChange>,
+    /// Outdated installed formulae that depend on this one, upgraded along with it
+    pub dependent_upgrades: Vec<PackageChange>,
+    /// Up-to-date dependents brew checks for broken linkage and may rebuild
+    pub dependents_checked: Vec<String>,
+}
+
+/// What an update will do, shown before it runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreview {
+    pub bundle_id: String,
+    pub display_name: String,
+    pub from_version: Option<String>,
+    pub to_version: String,
+    pub source_type: UpdateSourceType,
+    pub download_size: Option<u64>,
+    /// Why a password will probably be needed
+    pub elevation_reason: Option<String>,
+    /// Set for Homebrew formulae
+    pub formula_impact: Option<FormulaImpact>,
+}
+
+/// One entry of the recent activity feed, across all apps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub kind: ActivityKind,
+    /// Not set for self-updates
+    pub bundle_id: Option<String>,
+    pub display_name: String,
+    pub icon_cache_path: Option<String>,
+    /// Version installed, found, or removed
+    pub version: Option<String>,
+    /// Version the app was updated from, for update entries
+    pub from_version: Option<String>,
+    pub error_message: Option<String>,
+    /// SQLite datetime (UTC)
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Updated,
+    UpdateFailed,
+    Uninstalled,
+    /// macPlus itself was updated
+    SelfUpdated,
+    /// A scan found an app that wasn't there before
+    AppAdded,
+}
+
+impl ActivityKind {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "update_failed" => ActivityKind::UpdateFailed,
+            "uninstalled" => ActivityKind::Uninstalled,
+            "self_updated" => ActivityKind::SelfUpdated,
+            "app_added" => ActivityKind::AppAdded,
+            _ => ActivityKind::Updated,
+        }
+    }
+}
+
+/// How an app bundle was removed during an update or uninstall.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalMethod {
+    /// Moved to the Trash; can be put back
+    Trash,
+    /// Deleted outright after moving to the Trash failed
+    Deleted,
+    /// Removed by `brew uninstall`
+    Homebrew,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -99,3 +376,136 @@ impl UpdateStatus {
         }
     }
 }
+
+/// How far an app bundle replacement got, recorded before moving on to the
+/// next step so an interrupted one can be finished or rolled back.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStep {
+    /// About to move the old bundle out of the way
+    Started,
+    /// The old bundle is in the Trash or deleted; the new one isn't in place yet
+    OldRemoved,
+    /// The new bundle was copied into place
+    NewCopied,
+    /// The quarantine flag was cleared from the new bundle
+    QuarantineCleared,
+}
+
+impl JournalStep {
+    pub fn as_str(&self) -> &str {
+        match self {
+            JournalStep::Started => "started",
+            JournalStep::OldRemoved => "old_removed",
+            JournalStep::NewCopied => "new_copied",
+            JournalStep::QuarantineCleared => "quarantine_cleared",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "old_removed" => JournalStep::OldRemoved,
+            "new_copied" => JournalStep::NewCopied,
+            "quarantine_cleared" => JournalStep::QuarantineCleared,
+            _ => JournalStep::Started,
+        }
+    }
+}
+
+/// An app bundle replacement that hasn't finished, from the update journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateJournalEntry {
+    pub id: i64,
+    pub bundle_id: String,
+    pub app_path: String,
+    /// The new bundle, extracted in the staging folder
+    pub staged_path: String,
+    /// Where the old bundle went in the Trash, when Finder reported it
+    pub trashed_path: Option<String>,
+    pub step: JournalStep,
+    pub started_at: String,
+}
+
+/// An update deferred to a later time, e.g. tonight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledUpdate {
+    pub bundle_id: String,
+    pub display_name: String,
+    /// When it runs (RFC 3339)
+    pub run_at: String,
+}
+
+/// Outcome of scheduling updates for later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSchedule {
+    /// When the updates run (RFC 3339)
+    pub run_at: String,
+    pub scheduled: usize,
+    /// Whether macPlus starts at login. Without it, the updates only run if
+    /// macPlus is still open at `run_at` or opened after it.
+    pub launch_at_login: bool,
+}
+
+/// Security releases of macOS that this Mac hasn't installed, per the SOFA feed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OsSecurityStatus {
+    pub installed_version: String,
+    /// Newest security release for the installed major version, when it is newer
+    /// than the installed one
+    pub latest_version: Option<String>,
+    /// Distinct CVEs fixed by the releases not yet installed
+    pub missing_cve_count: usize,
+    /// How many of those Apple reports as actively exploited
+    pub actively_exploited_count: usize,
+    /// Apple's security notes for `latest_version`
+    pub security_info_url: Option<String>,
+}
+
+/// Outcome of exporting the pending updates as a shell script.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateScriptExport {
+    /// Updates the script installs with a command
+    pub scripted: usize,
+    /// Updates only listed in a comment, e.g. ones installed from within the app
+    pub manual: usize,
+}
+
+/// Local usage metrics over the days kept, from `get_metrics`. Counts only;
+/// nothing in it identifies an app or the Mac.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSummary {
+    pub enabled: bool,
+    /// When the oldest metric kept was recorded
+    pub since: Option<String>,
+    pub cycles: u64,
+    pub avg_cycle_ms: u64,
+    pub max_cycle_ms: u64,
+    pub checkers: Vec<SourceHitRate>,
+    pub downloads: u64,
+    pub download_bytes: u64,
+    pub failures: Vec<FailureCount>,
+}
+
+/// How often one update source was checked and found an update.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHitRate {
+    pub source: String,
+    pub runs: u64,
+    pub found: u64,
+    pub errors: u64,
+}
+
+/// How many updates failed with one error code.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureCount {
+    pub category: String,
+    pub count: u64,
+}
@@ -0,0 +1,129 @@
+//! Automation (Apple Events) permission per target app. macOS asks once for
+//! every app macPlus sends Apple events to, e.g. to quit it before an update,
+//! and remembers the answer for that app only.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+
+use super::permissions::PermissionState;
+use crate::utils::app_lifecycle;
+
+/// Answers seen this session, by target bundle ID. The TCC database only has
+/// the ones the user gave in an earlier session.
+static TARGETS: Mutex<Option<HashMap<String, PermissionState>>> = Mutex::new(None);
+
+fn remembered(bundle_id: &str) -> Option<PermissionState> {
+    let targets = TARGETS.lock().unwrap_or_else(|e| e.into_inner());
+    targets.as_ref()?.get(bundle_id).copied()
+}
+
+fn remember(bundle_id: &str, state: PermissionState) {
+    let mut targets = TARGETS.lock().unwrap_or_else(|e| e.into_inner());
+    targets
+        .get_or_insert_with(HashMap::new)
+        .insert(bundle_id.to_string(), state);
+}
+
+/// The user's answer for `bundle_id` in the TCC database, without asking.
+fn tcc_state(bundle_id: &str) -> PermissionState {
+    let Some(db_path) =
+        dirs::home_dir().map(|h| h.join("Library/Application Support/com.apple.TCC/TCC.db"))
+    else {
+        return PermissionState::Unknown;
+    };
+    let flags =
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let Ok(conn) = rusqlite::Connection::open_with_flags(&db_path, flags) else {
+        return PermissionState::Unknown;
+    };
+    match conn.query_row(
+        "SELECT auth_value FROM access WHERE service = 'kTCCServiceAppleEvents' \
+         AND client = 'com.macplus.app' AND indirect_object_identifier = ?1",
+        [bundle_id],
+        |row| row.get::<_, i64>(0),
+    ) {
+        Ok(2) => PermissionState::Granted,
+        Ok(_) => PermissionState::Denied,
+        Err(_) => PermissionState::Unknown,
+    }
+}
+
+/// Whether macPlus may send Apple events to the app. `Unknown` until the user
+/// has been asked.
+pub fn target_state(bundle_id: &str) -> PermissionState {
+    remembered(bundle_id).unwrap_or_else(|| tcc_state(bundle_id))
+}
+
+/// What an osascript run says about Automation of its target. Errors the
+/// target itself returned (-1708 "doesn't understand", -1728 "can't get")
+/// mean the event got through; -1743 means the user denied it.
+fn script_outcome(success: bool, stderr: &str) -> PermissionState {
+    if success || stderr.contains("(-1708)") || stderr.contains("(-1728)") {
+        PermissionState::Granted
+    } else if stderr.contains("(-1743)") || stderr.contains("Not authorized to send Apple events") {
+        PermissionState::Denied
+    } else {
+        PermissionState::Unknown
+    }
+}
+
+/// Send `command` to the app with AppleScript, e.g. `quit`, and record what the
+/// outcome says about Automation of it. Shows the macOS prompt the first time.
+pub fn tell_app(bundle_id: &str, command: &str) -> PermissionState {
+    let script = format!("tell application id \"{}\" to {}", bundle_id, command);
+    let state = match Command::new("osascript")
+        .current_dir("/tmp")
+        .args(["-e", &script])
+        .output()
+    {
+        Ok(output) => script_outcome(
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(_) => PermissionState::Unknown,
+    };
+    if state != PermissionState::Unknown {
+        remember(bundle_id, state);
+    }
+    state
+}
+
+/// Ask for Automation of the app if the user hasn't answered yet, so the prompt
+/// comes up in setup rather than in the middle of an update. Apps that aren't
+/// running are skipped: telling them anything would launch them.
+pub fn request(bundle_id: &str) -> PermissionState {
+    let state = target_state(bundle_id);
+    if state != PermissionState::Unknown || !app_lifecycle::is_app_running(bundle_id) {
+        return state;
+    }
+    tell_app(bundle_id, "count windows")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_automation_from_osascript_errors() {
+        assert_eq!(script_outcome(true, ""), PermissionState::Granted);
+        assert_eq!(
+            script_outcome(
+                false,
+                "execution error: Not authorized to send Apple events to Zoom. (-1743)"
+            ),
+            PermissionState::Denied
+        );
+        assert_eq!(
+            script_outcome(
+                false,
+                "execution error: Slack got an error: Can't get count of window. (-1728)"
+            ),
+            PermissionState::Granted
+        );
+        assert_eq!(
+            script_outcome(false, "execution error: Application isn't running. (-600)"),
+            PermissionState::Unknown
+        );
+    }
+}
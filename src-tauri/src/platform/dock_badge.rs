@@ -0,0 +1,35 @@
+use objc2::MainThreadMarker;
+use objc2_app_kit::NSApplication;
+use objc2_foundation::NSString;
+use tauri::AppHandle;
+
+/// Label shown on the Dock tile for `count` pending updates, matching the tray badge
+/// (`99+` above two digits). `None` clears the badge.
+fn badge_label(count: usize) -> Option<String> {
+    match count {
+        0 => None,
+        1..=99 => Some(count.to_string()),
+        _ => Some("99+".to_string()),
+    }
+}
+
+/// Mirror the update count onto the Dock icon via `NSApp.dockTile.badgeLabel`.
+///
+/// AppKit must be touched from the main thread, so the update is dispatched there.
+/// The badge is only visible while macPlus has a Dock icon, but it's kept in sync
+/// regardless so it's correct as soon as one appears.
+pub fn set_dock_badge(app_handle: &AppHandle, count: usize) {
+    let label = badge_label(count);
+    let result = app_handle.run_on_main_thread(move || {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let app = NSApplication::sharedApplication(mtm);
+        let tile = app.dockTile();
+        let ns_label = label.as_deref().map(NSString::from_str);
+        tile.setBadgeLabel(ns_label.as_deref());
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to update Dock badge: {}", e);
+    }
+}
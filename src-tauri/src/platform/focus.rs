@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+/// Focus assertions written by the DoNotDisturb daemon. Reading it requires Full Disk
+/// Access; without it Focus is reported as inactive.
+fn assertions_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join("Library/DoNotDisturb/DB/Assertions.json"))
+}
+
+/// Parse `Assertions.json`. Focus is on when any store holds an assertion record,
+/// e.g. `{"data":[{"storeAssertionRecords":[{"assertionDetails":{…}}]}]}`.
+pub(crate) fn parse_focus_assertions(json: &str) -> bool {
+    let value: serde_json::Value = match serde_json::from_str(json) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+    value
+        .get("data")
+        .and_then(|d| d.as_array())
+        .map_or(false, |stores| {
+            stores.iter().any(|store| {
+                store
+                    .get("storeAssertionRecords")
+                    .and_then(|r| r.as_array())
+                    .map_or(false, |records| !records.is_empty())
+            })
+        })
+}
+
+/// Whether a Focus mode (including Do Not Disturb) is currently turned on manually.
+/// Schedule-triggered Focus isn't recorded as an assertion and isn't detected.
+pub fn is_focus_active() -> bool {
+    assertions_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map_or(false, |json| parse_focus_assertions(&json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_focus_assertions() {
+        let active = r#"{"data":[{"storeAssertionRecords":[{"assertionDetails":{"assertionDetailsModeIdentifier":"com.apple.donotdisturb.mode.default"}}]}],"header":{"version":3}}"#;
+        let inactive = r#"{"data":[{}],"header":{"version":3}}"#;
+        let empty = r#"{"data":[{"storeAssertionRecords":[]}]}"#;
+        assert!(parse_focus_assertions(active));
+        assert!(!parse_focus_assertions(inactive));
+        assert!(!parse_focus_assertions(empty));
+        assert!(!parse_focus_assertions("not json"));
+    }
+}
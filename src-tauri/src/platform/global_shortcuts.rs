@@ -0,0 +1,71 @@
+use std::sync::Mutex as StdMutex;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::models::AppSettings;
+use crate::utils::{AppError, AppResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    ToggleWindow,
+    CheckForUpdates,
+}
+
+/// Currently registered shortcuts, keyed by `Shortcut::id()`.
+static REGISTERED: StdMutex<Vec<(u32, ShortcutAction)>> = StdMutex::new(Vec::new());
+
+/// The action bound to a pressed shortcut, if it's one of ours.
+pub fn action_for(shortcut: &Shortcut) -> Option<ShortcutAction> {
+    let registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    registered
+        .iter()
+        .find(|(id, _)| *id == shortcut.id())
+        .map(|(_, action)| *action)
+}
+
+/// Replace the registered global shortcuts with the ones in `settings`.
+/// Accelerators use the Tauri format, e.g. `CommandOrControl+Shift+U`.
+/// Every valid shortcut is registered; the first failure is returned.
+pub fn apply_global_shortcuts(app: &AppHandle, settings: &AppSettings) -> AppResult<()> {
+    let global_shortcut = app.global_shortcut();
+    let mut registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Err(e) = global_shortcut.unregister_all() {
+        log::warn!("Failed to unregister global shortcuts: {}", e);
+    }
+    registered.clear();
+
+    let mut first_error = None;
+    for (accelerator, action) in [
+        (&settings.toggle_window_shortcut, ShortcutAction::ToggleWindow),
+        (&settings.check_updates_shortcut, ShortcutAction::CheckForUpdates),
+    ] {
+        let Some(accelerator) = accelerator.as_deref().map(str::trim).filter(|a| !a.is_empty()) else {
+            continue;
+        };
+        let result = accelerator
+            .parse::<Shortcut>()
+            .map_err(|e| AppError::Custom(format!("Invalid shortcut \"{}\": {}", accelerator, e)))
+            .and_then(|shortcut| {
+                if registered.iter().any(|(id, _)| *id == shortcut.id()) {
+                    return Err(AppError::Custom(format!(
+                        "Shortcut \"{}\" is assigned twice",
+                        accelerator
+                    )));
+                }
+                global_shortcut.register(shortcut).map_err(|e| {
+                    AppError::Custom(format!("Couldn't register \"{}\" — it may be in use: {}", accelerator, e))
+                })?;
+                Ok(shortcut)
+            });
+
+        match result {
+            Ok(shortcut) => registered.push((shortcut.id(), action)),
+            Err(e) => {
+                log::warn!("{}", e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
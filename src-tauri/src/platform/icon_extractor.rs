@@ -42,6 +42,25 @@ pub fn extract_icon_png(app_path: &Path, output_dir: &Path) -> AppResult<Option<
     Ok(None)
 }
 
+/// Like [`extract_icon_png`], but falls back to a generated monogram icon so
+/// every app has one, including formulae and bundles without a usable icon.
+pub fn icon_or_monogram(
+    app_path: &Path,
+    bundle_id: &str,
+    display_name: &str,
+    output_dir: &Path,
+) -> AppResult<String> {
+    // Formulae have no bundle to extract from
+    if app_path.is_dir() {
+        match extract_icon_png(app_path, output_dir) {
+            Ok(Some(path)) => return Ok(path),
+            Ok(None) => {}
+            Err(e) => log::debug!("[{}] Icon extraction failed: {}", bundle_id, e),
+        }
+    }
+    super::monogram_icon::write_monogram_png(bundle_id, display_name, output_dir)
+}
+
 /// Strategy 1: Use CFBundleIconFile (NOT CFBundleIconName) to find a .icns file
 /// in Contents/Resources/, then convert with sips.
 fn try_sips_cfbundle_icon_file(app_path: &Path, output_path: &Path, bundle_id: &str) -> Option<String> {
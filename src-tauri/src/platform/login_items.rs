@@ -0,0 +1,366 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{AppError, AppResult};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginItem {
+    pub label: String,
+    /// "launch_agent", "launch_daemon" or "login_item"
+    pub kind: String,
+    /// "user" (~/Library), "system" (/Library) or "app" (bundled with an app via SMAppService)
+    pub scope: String,
+    /// Path to the launchd plist (or the helper app for bundled login items)
+    pub path: String,
+    pub program: Option<String>,
+    pub enabled: bool,
+    pub run_at_load: bool,
+    pub keep_alive: bool,
+    pub parent_bundle_id: Option<String>,
+    pub parent_app_path: Option<String>,
+    /// True when the item points at an executable that no longer exists.
+    pub orphaned: bool,
+}
+
+impl LoginItem {
+    /// The launchd domain this item is loaded into.
+    fn domain(&self) -> String {
+        if self.kind == "launch_daemon" {
+            "system".to_string()
+        } else {
+            format!("gui/{}", unsafe { libc::getuid() })
+        }
+    }
+}
+
+/// launchd plist locations: (directory, kind, scope).
+fn launchd_dirs() -> Vec<(PathBuf, &'static str, &'static str)> {
+    let mut dirs = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs.push((home.join("Library/LaunchAgents"), "launch_agent", "user"));
+    }
+    dirs.push((PathBuf::from("/Library/LaunchAgents"), "launch_agent", "system"));
+    dirs.push((PathBuf::from("/Library/LaunchDaemons"), "launch_daemon", "system"));
+    dirs
+}
+
+/// Build a `LoginItem` from a launchd plist. `bundle_root` is set for plists shipped
+/// inside an app bundle, where `BundleProgram` is relative to the bundle.
+fn read_launchd_plist(
+    plist_path: &Path,
+    kind: &str,
+    scope: &str,
+    bundle_root: Option<&Path>,
+) -> Option<(LoginItem, Vec<String>)> {
+    let dict = plist::Value::from_file(plist_path).ok()?.into_dictionary()?;
+    let label = dict.get("Label")?.as_string()?.to_string();
+
+    let program = dict
+        .get("Program")
+        .and_then(|v| v.as_string())
+        .map(String::from)
+        .or_else(|| {
+            dict.get("ProgramArguments")
+                .and_then(|v| v.as_array())
+                .and_then(|a| a.first())
+                .and_then(|v| v.as_string())
+                .map(String::from)
+        })
+        .or_else(|| {
+            let rel = dict.get("BundleProgram").and_then(|v| v.as_string())?;
+            bundle_root.map(|root| root.join(rel).to_string_lossy().to_string())
+        });
+
+    let run_at_load = dict
+        .get("RunAtLoad")
+        .and_then(|v| v.as_boolean())
+        .unwrap_or(false);
+    // KeepAlive is either a bool or a dictionary of conditions
+    let keep_alive = match dict.get("KeepAlive") {
+        Some(plist::Value::Boolean(b)) => *b,
+        Some(plist::Value::Dictionary(_)) => true,
+        _ => false,
+    };
+    let disabled = dict
+        .get("Disabled")
+        .and_then(|v| v.as_boolean())
+        .unwrap_or(false);
+
+    let associated: Vec<String> = match dict.get("AssociatedBundleIdentifiers") {
+        Some(plist::Value::String(s)) => vec![s.clone()],
+        Some(plist::Value::Array(a)) => a
+            .iter()
+            .filter_map(|v| v.as_string().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    // Only absolute program paths can be checked for existence
+    let orphaned = program
+        .as_deref()
+        .map_or(false, |p| p.starts_with('/') && !Path::new(p).exists());
+
+    Some((
+        LoginItem {
+            label,
+            kind: kind.to_string(),
+            scope: scope.to_string(),
+            path: plist_path.to_string_lossy().to_string(),
+            program,
+            enabled: !disabled,
+            run_at_load,
+            keep_alive,
+            parent_bundle_id: None,
+            parent_app_path: None,
+            orphaned,
+        },
+        associated,
+    ))
+}
+
+/// Parse `launchctl print-disabled <domain>` output into the set of disabled labels.
+///
+/// Lines look like `"com.vendor.agent" => disabled` (older macOS: `=> true`).
+pub(crate) fn parse_print_disabled(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (label, state) = line.split_once("=>")?;
+            let state = state.trim();
+            if state == "disabled" || state == "true" {
+                Some(label.trim().trim_matches('"').to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+async fn disabled_labels(domain: &str) -> HashSet<String> {
+    match crate::utils::command::run_command_with_timeout("launchctl", &["print-disabled", domain], 5).await {
+        Ok(output) => parse_print_disabled(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            log::debug!("launchctl print-disabled {} failed: {}", domain, e);
+            HashSet::new()
+        }
+    }
+}
+
+/// Enumerate launch agents, launch daemons and app-bundled (SMAppService) login items,
+/// associating each with a tracked app. `apps` is a list of (bundle_id, display_name, app_path).
+pub async fn list_login_items(apps: &[(String, String, String)]) -> Vec<LoginItem> {
+    let gui_domain = format!("gui/{}", unsafe { libc::getuid() });
+    let (gui_disabled, system_disabled) =
+        tokio::join!(disabled_labels(&gui_domain), disabled_labels("system"));
+
+    let apps_owned = apps.to_vec();
+    let items = tokio::task::spawn_blocking(move || collect_login_items(&apps_owned))
+        .await
+        .unwrap_or_default();
+
+    items
+        .into_iter()
+        .map(|mut item| {
+            let disabled = if item.kind == "launch_daemon" {
+                &system_disabled
+            } else {
+                &gui_disabled
+            };
+            if disabled.contains(&item.label) {
+                item.enabled = false;
+            }
+            item
+        })
+        .collect()
+}
+
+fn collect_login_items(apps: &[(String, String, String)]) -> Vec<LoginItem> {
+    let mut items: Vec<(LoginItem, Vec<String>)> = Vec::new();
+
+    // 1. Standalone launchd plists in the Library folders
+    for (dir, kind, scope) in launchd_dirs() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("plist") {
+                continue;
+            }
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with("com.apple.") {
+                continue;
+            }
+            if let Some(item) = read_launchd_plist(&path, kind, scope, None) {
+                items.push(item);
+            }
+        }
+    }
+
+    // 2. Items bundled inside tracked apps (SMAppService agents/daemons and login helpers)
+    for (bundle_id, _, app_path) in apps {
+        let root = Path::new(app_path);
+        for (sub, kind) in [
+            ("Contents/Library/LaunchAgents", "launch_agent"),
+            ("Contents/Library/LaunchDaemons", "launch_daemon"),
+        ] {
+            if let Ok(entries) = std::fs::read_dir(root.join(sub)) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) != Some("plist") {
+                        continue;
+                    }
+                    if let Some((mut item, associated)) = read_launchd_plist(&path, kind, "app", Some(root)) {
+                        item.parent_bundle_id = Some(bundle_id.clone());
+                        item.parent_app_path = Some(app_path.clone());
+                        items.push((item, associated));
+                    }
+                }
+            }
+        }
+
+        if let Ok(entries) = std::fs::read_dir(root.join("Contents/Library/LoginItems")) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("app") {
+                    continue;
+                }
+                let label = match crate::detection::bundle_reader::read_bundle(&path) {
+                    Some(b) => b.bundle_id,
+                    None => continue,
+                };
+                items.push((
+                    LoginItem {
+                        label,
+                        kind: "login_item".to_string(),
+                        scope: "app".to_string(),
+                        path: path.to_string_lossy().to_string(),
+                        program: None,
+                        enabled: true,
+                        run_at_load: true,
+                        keep_alive: false,
+                        parent_bundle_id: Some(bundle_id.clone()),
+                        parent_app_path: Some(app_path.clone()),
+                        orphaned: false,
+                    },
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+
+    // 3. Associate standalone items with tracked apps
+    items
+        .into_iter()
+        .map(|(mut item, associated)| {
+            if item.parent_bundle_id.is_none() {
+                let owner = apps
+                    .iter()
+                    .position(|(bid, _, _)| associated.iter().any(|a| a == bid))
+                    .or_else(|| {
+                        let program = item.program.as_deref()?;
+                        apps.iter().position(|(_, _, path)| {
+                            !path.is_empty() && program.starts_with(&format!("{}/", path))
+                        })
+                    })
+                    .or_else(|| super::owning_app_by_prefix(&item.label, apps));
+                if let Some(idx) = owner {
+                    item.parent_bundle_id = Some(apps[idx].0.clone());
+                    item.parent_app_path = Some(apps[idx].2.clone());
+                }
+            }
+            item
+        })
+        .collect()
+}
+
+/// Unload a launchd job (`launchctl bootout`) so it stops running before its files
+/// are removed. Best-effort: the job may not be loaded.
+pub fn bootout_login_item(item: &LoginItem) {
+    if item.kind == "login_item" {
+        return;
+    }
+    let target = format!("{}/{}", item.domain(), item.label);
+    if item.kind == "launch_daemon" {
+        let _ = crate::utils::sudo_session::run_elevated("launchctl", &["bootout", &target]);
+    } else {
+        let _ = std::process::Command::new("launchctl")
+            .args(["bootout", &target])
+            .output();
+    }
+}
+
+/// Enable or disable a login item via `launchctl`. Disabling also unloads the job;
+/// enabling re-bootstraps it from its plist. System-domain items require elevation.
+pub fn set_login_item_enabled(item: &LoginItem, enabled: bool) -> AppResult<()> {
+    let domain = item.domain();
+    let target = format!("{}/{}", domain, item.label);
+
+    let (toggle, load_args): (&str, Vec<&str>) = if enabled {
+        ("enable", vec!["bootstrap", domain.as_str(), item.path.as_str()])
+    } else {
+        ("disable", vec!["bootout", target.as_str()])
+    };
+
+    if domain == "system" {
+        match crate::utils::sudo_session::run_elevated("launchctl", &[toggle, &target]) {
+            Ok(out) if out.status.success() => {}
+            Ok(out) => {
+                return Err(AppError::CommandFailed(format!(
+                    "launchctl {} failed: {}",
+                    toggle,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                )));
+            }
+            Err(crate::utils::sudo_session::ElevatedError::UserCancelled) => {
+                return Err(AppError::CommandFailed(
+                    "Administrator approval is required to change system launch daemons".to_string(),
+                ));
+            }
+            Err(e) => return Err(AppError::CommandFailed(e.to_string())),
+        }
+        // Best-effort load/unload — the job may already be in the requested state
+        if item.kind != "login_item" {
+            let _ = crate::utils::sudo_session::run_elevated("launchctl", &load_args);
+        }
+        return Ok(());
+    }
+
+    let output = std::process::Command::new("launchctl")
+        .args([toggle, &target])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to run launchctl: {}", e)))?;
+    if !output.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "launchctl {} failed: {}",
+            toggle,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    if item.kind != "login_item" {
+        let _ = std::process::Command::new("launchctl").args(&load_args).output();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_print_disabled() {
+        let output = "disabled services = {\n\
+\t\"com.vendor.agent\" => disabled\n\
+\t\"com.vendor.helper\" => enabled\n\
+\t\"com.legacy.agent\" => true\n\
+}\n";
+        let disabled = parse_print_disabled(output);
+        assert!(disabled.contains("com.vendor.agent"));
+        assert!(disabled.contains("com.legacy.agent"));
+        assert!(!disabled.contains("com.vendor.helper"));
+    }
+}
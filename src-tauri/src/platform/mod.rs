@@ -1,3 +1,29 @@
+pub mod automation;
+pub mod dock_badge;
+pub mod focus;
+pub mod global_shortcuts;
 pub mod icon_extractor;
+pub mod login_items;
+pub mod monogram_icon;
 pub mod permissions;
+pub mod system_extensions;
 pub mod tray_badge;
+pub mod translocation;
+pub mod tray_menu;
+pub mod window_geometry;
+pub mod window_mode;
+
+/// Find the tracked app whose bundle ID is the longest dot-separated prefix of
+/// `identifier` (e.g. `com.vendor.vpn.netext` → `com.vendor.vpn`), or equal to it.
+/// `apps` is a list of (bundle_id, display_name, app_path); returns the index.
+pub(crate) fn owning_app_by_prefix(identifier: &str, apps: &[(String, String, String)]) -> Option<usize> {
+    apps.iter()
+        .enumerate()
+        .filter(|(_, (bid, _, _))| {
+            !bid.is_empty()
+                && identifier.starts_with(bid.as_str())
+                && (identifier.len() == bid.len() || identifier[bid.len()..].starts_with('.'))
+        })
+        .max_by_key(|(_, (bid, _, _))| bid.len())
+        .map(|(idx, _)| idx)
+}
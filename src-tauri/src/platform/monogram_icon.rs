@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use image::{Rgba, RgbaImage};
+
+use crate::utils::{AppError, AppResult};
+
+/// Side length of generated icons, matching the PNGs `icon_extractor` writes.
+const ICON_SIZE: u32 = 128;
+
+/// Background colors, picked by a hash of the bundle ID.
+const PALETTE: [Rgba<u8>; 8] = [
+    Rgba([10, 132, 255, 255]),
+    Rgba([48, 176, 199, 255]),
+    Rgba([52, 199, 89, 255]),
+    Rgba([255, 149, 0, 255]),
+    Rgba([255, 59, 48, 255]),
+    Rgba([175, 82, 222, 255]),
+    Rgba([255, 45, 85, 255]),
+    Rgba([142, 142, 147, 255]),
+];
+
+/// Bitmap font: 5×7 pixel patterns for A-Z, stored like the tray badge digits
+/// as 7 rows of 5 bits (MSB-first).
+#[rustfmt::skip]
+const LETTER_FONT: [[u8; 7]; 26] = [
+    [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // A
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110], // B
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110], // C
+    [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110], // D
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111], // E
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000], // F
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111], // G
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // H
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // I
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100], // J
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001], // K
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // L
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001], // M
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001], // N
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // O
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000], // P
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101], // Q
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001], // R
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110], // S
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // T
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // U
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // V
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010], // W
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001], // X
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100], // Y
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111], // Z
+];
+
+/// Shown for names without a Latin letter or digit.
+const FALLBACK_GLYPH: [u8; 7] = [
+    0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100,
+];
+
+/// The character a monogram shows: the first ASCII letter or digit of the name.
+pub(crate) fn monogram_char(display_name: &str) -> Option<char> {
+    display_name
+        .chars()
+        .find(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+}
+
+fn glyph(c: Option<char>) -> [u8; 7] {
+    match c {
+        Some(c @ 'A'..='Z') => LETTER_FONT[(c as u8 - b'A') as usize],
+        Some(c @ '0'..='9') => super::tray_badge::DIGIT_FONT[(c as u8 - b'0') as usize],
+        _ => FALLBACK_GLYPH,
+    }
+}
+
+/// Background color for `bundle_id`. FNV-1a keeps it the same across runs and
+/// Rust versions, unlike the standard library's hasher.
+pub(crate) fn monogram_color(bundle_id: &str) -> Rgba<u8> {
+    let hash = bundle_id.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    PALETTE[(hash % PALETTE.len() as u64) as usize]
+}
+
+/// A rounded square in the app's color with the first letter of its name.
+pub(crate) fn render_monogram(bundle_id: &str, display_name: &str) -> RgbaImage {
+    let size = ICON_SIZE;
+    let mut img = RgbaImage::new(size, size);
+    let color = monogram_color(bundle_id);
+
+    // Leave a margin like macOS app icons, with rounded corners
+    let inset = size / 10;
+    let side = size - inset * 2;
+    let radius = side as f32 * 0.225;
+    let (near, far) = (radius, side as f32 - radius);
+    for y in 0..side {
+        for x in 0..side {
+            // Distance from the pixel center to the square shrunk by the radius
+            let (cx, cy) = (x as f32 + 0.5, y as f32 + 0.5);
+            let dx = (near - cx).max(cx - far).max(0.0);
+            let dy = (near - cy).max(cy - far).max(0.0);
+            if dx * dx + dy * dy <= radius * radius {
+                img.put_pixel(inset + x, inset + y, color);
+            }
+        }
+    }
+
+    let pattern = glyph(monogram_char(display_name));
+    let scale = side / 2 / 7;
+    let (glyph_w, glyph_h) = (5 * scale, 7 * scale);
+    let (left, top) = ((size - glyph_w) / 2, (size - glyph_h) / 2);
+    let white = Rgba([255, 255, 255, 255]);
+    for (row, bits) in pattern.iter().enumerate() {
+        for col in 0..5u32 {
+            if bits & (1 << (4 - col)) == 0 {
+                continue;
+            }
+            for sy in 0..scale {
+                for sx in 0..scale {
+                    img.put_pixel(
+                        left + col * scale + sx,
+                        top + row as u32 * scale + sy,
+                        white,
+                    );
+                }
+            }
+        }
+    }
+    img
+}
+
+/// Write a monogram icon for an app to `<output_dir>/<bundle_id>.png`, where
+/// extracted icons go, and return its path.
+pub fn write_monogram_png(
+    bundle_id: &str,
+    display_name: &str,
+    output_dir: &Path,
+) -> AppResult<String> {
+    let output_path = output_dir.join(format!("{}.png", bundle_id));
+    render_monogram(bundle_id, display_name)
+        .save_with_format(&output_path, image::ImageFormat::Png)
+        .map_err(|e| AppError::Custom(format!("write monogram icon: {}", e)))?;
+    log::debug!("[{}] Generated monogram icon", bundle_id);
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monogram_uses_first_letter_or_digit() {
+        assert_eq!(monogram_char("ffmpeg"), Some('F'));
+        assert_eq!(monogram_char("1Password 7"), Some('1'));
+        assert_eq!(monogram_char("  (beta) Tool"), Some('B'));
+        assert_eq!(monogram_char("微信"), None);
+        assert_eq!(glyph(monogram_char("微信")), FALLBACK_GLYPH);
+    }
+
+    #[test]
+    fn monogram_is_deterministic() {
+        assert_eq!(
+            monogram_color("homebrew.formula.wget"),
+            monogram_color("homebrew.formula.wget")
+        );
+        assert_eq!(
+            render_monogram("com.example.app", "Example"),
+            render_monogram("com.example.app", "Example")
+        );
+
+        let img = render_monogram("com.example.app", "Example");
+        assert_eq!(img.dimensions(), (ICON_SIZE, ICON_SIZE));
+        // Transparent margin, colored background, white glyph
+        assert_eq!(img.get_pixel(0, 0)[3], 0);
+        assert_eq!(
+            *img.get_pixel(ICON_SIZE / 2, ICON_SIZE - ICON_SIZE / 10 - 2),
+            monogram_color("com.example.app")
+        );
+        assert!(img.pixels().any(|p| *p == Rgba([255, 255, 255, 255])));
+    }
+}
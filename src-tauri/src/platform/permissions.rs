@@ -1,7 +1,10 @@
-use std::path::{Path, PathBuf};
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use serde::Serialize;
+
 /// Three-state permission result for UI display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PermissionState {
@@ -24,6 +27,73 @@ impl PermissionState {
     }
 }
 
+/// A privacy permission macPlus asks for, and its System Settings pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionKind {
+    Automation,
+    FullDiskAccess,
+    AppManagement,
+    Notifications,
+}
+
+impl PermissionKind {
+    pub const ALL: [PermissionKind; 4] = [
+        Self::Automation,
+        Self::FullDiskAccess,
+        Self::AppManagement,
+        Self::Notifications,
+    ];
+
+    pub fn from_pane(pane: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.pane() == pane)
+    }
+
+    pub fn pane(&self) -> &'static str {
+        match self {
+            Self::Automation => "automation",
+            Self::FullDiskAccess => "full_disk_access",
+            Self::AppManagement => "app_management",
+            Self::Notifications => "notifications",
+        }
+    }
+
+    /// Deep link to the System Settings pane where the permission is granted.
+    pub fn settings_url(&self) -> &'static str {
+        match self {
+            Self::Automation => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Automation"
+            }
+            Self::FullDiskAccess => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles"
+            }
+            Self::AppManagement => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_AppManagement"
+            }
+            Self::Notifications => {
+                "x-apple.systempreferences:com.apple.Notifications-Settings.extension"
+            }
+        }
+    }
+
+    /// Permissions updates can't do without: quitting apps before replacing them,
+    /// and replacing bundles in /Applications.
+    pub fn required(&self) -> bool {
+        matches!(self, Self::Automation | Self::AppManagement)
+    }
+}
+
+/// What a probe of a TCC-guarded path says about the permission guarding it.
+/// TCC denies with EPERM; other errors (missing path, POSIX permissions,
+/// read-only volume) say nothing about it.
+fn probe_state<T>(result: &std::io::Result<T>) -> Option<PermissionState> {
+    match result {
+        Ok(_) => Some(PermissionState::Granted),
+        Err(e) if e.raw_os_error() == Some(libc::EPERM) => Some(PermissionState::Denied),
+        Err(_) => None,
+    }
+}
+
 /// Cache: once we know Automation is granted, remember it across TCC re-reads.
 static AUTOMATION_KNOWN_GRANTED: AtomicBool = AtomicBool::new(false);
 
@@ -52,10 +122,31 @@ fn clear_automation_cache() {
     }
 }
 
-/// Check if the app has Full Disk Access by probing the system TCC database.
-/// If the app can open it, FDA is granted.
+/// Check if the app has Full Disk Access.
 pub fn has_full_disk_access() -> bool {
-    std::fs::File::open("/Library/Application Support/com.apple.TCC/TCC.db").is_ok()
+    full_disk_access_state().is_granted()
+}
+
+/// Full Disk Access from opening files only it unlocks: the system TCC database,
+/// then the user's Safari and Mail data. `Unknown` when none of them exists.
+pub fn full_disk_access_state() -> PermissionState {
+    let mut probes = vec![PathBuf::from(
+        "/Library/Application Support/com.apple.TCC/TCC.db",
+    )];
+    if let Some(home) = dirs::home_dir() {
+        probes.push(home.join("Library/Safari"));
+        probes.push(home.join("Library/Mail"));
+    }
+    probes
+        .iter()
+        .find_map(|path| {
+            if path.is_dir() {
+                probe_state(&std::fs::read_dir(path))
+            } else {
+                probe_state(&std::fs::File::open(path))
+            }
+        })
+        .unwrap_or(PermissionState::Unknown)
 }
 
 /// Passively check Automation (Apple Events) permission by reading the user TCC database.
@@ -289,18 +380,58 @@ pub fn has_notification_permission(bundle_id: &str) -> bool {
     false
 }
 
-/// Check if the app has App Management permission by probing a system app bundle.
-/// If the app can create a file inside Safari.app, App Management is granted.
+/// Check if the app has App Management permission by probing an app bundle.
 pub fn has_app_management() -> bool {
-    let probe = Path::new("/Applications/Safari.app/Contents/.macplus_probe");
-    if !Path::new("/Applications/Safari.app/Contents").exists() {
-        return false;
-    }
-    match std::fs::File::create(probe) {
-        Ok(_) => {
-            let _ = std::fs::remove_file(probe);
-            true
-        }
-        Err(_) => false,
+    app_management_state().is_granted()
+}
+
+/// App Management from creating a file inside another app's bundle in
+/// /Applications. Only bundles the user owns are probed, so that a refusal comes
+/// from TCC rather than from file permissions or the sealed system volume.
+/// `Unknown` when there's no such bundle.
+pub fn app_management_state() -> PermissionState {
+    let uid = unsafe { libc::getuid() };
+    let Ok(entries) = std::fs::read_dir("/Applications") else {
+        return PermissionState::Unknown;
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .filter(|path| path.file_name().is_some_and(|name| name != "macPlus.app"))
+        .map(|path| path.join("Contents"))
+        .filter(|contents| {
+            std::fs::metadata(contents).is_ok_and(|meta| meta.is_dir() && meta.uid() == uid)
+        })
+        .take(5)
+        .find_map(|contents| {
+            let probe = contents.join(".macplus_probe");
+            let result = std::fs::File::create(&probe);
+            if result.is_ok() {
+                let _ = std::fs::remove_file(&probe);
+            }
+            probe_state(&result)
+        })
+        .unwrap_or(PermissionState::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_tcc_refusals_from_probe_errors() {
+        let denied: std::io::Result<()> = Err(std::io::Error::from_raw_os_error(libc::EPERM));
+        let missing: std::io::Result<()> = Err(std::io::Error::from_raw_os_error(libc::ENOENT));
+        let unix_mode: std::io::Result<()> = Err(std::io::Error::from_raw_os_error(libc::EACCES));
+        assert_eq!(probe_state(&Ok(())), Some(PermissionState::Granted));
+        assert_eq!(probe_state(&denied), Some(PermissionState::Denied));
+        assert_eq!(probe_state(&missing), None);
+        assert_eq!(probe_state(&unix_mode), None);
+        assert_eq!(
+            PermissionKind::from_pane("app_management"),
+            Some(PermissionKind::AppManagement)
+        );
+        assert_eq!(PermissionKind::from_pane("camera"), None);
     }
 }
@@ -0,0 +1,261 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::utils::plist_parser::{get_string, read_info_plist};
+
+/// Directory holding third-party (legacy) kernel extensions.
+const THIRD_PARTY_KEXT_DIR: &str = "/Library/Extensions";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemExtensionInfo {
+    pub identifier: String,
+    pub name: String,
+    /// "system_extension" or "kext"
+    pub kind: String,
+    /// Extension point, e.g. "network_extension" or "endpoint_security" (system extensions only)
+    pub category: Option<String>,
+    pub team_id: Option<String>,
+    pub version: Option<String>,
+    /// Raw state as reported by the system, e.g. "activated enabled"
+    pub state: Option<String>,
+    pub enabled: bool,
+    pub path: Option<String>,
+    pub parent_bundle_id: Option<String>,
+    pub parent_app_path: Option<String>,
+    /// True when no tracked app owns this extension (its parent was likely removed).
+    pub orphaned: bool,
+}
+
+/// Parse `systemextensionsctl list` output.
+///
+/// The output is grouped by extension point (`--- com.apple.system_extension.<category>`),
+/// followed by a header row and tab-separated entries:
+/// `enabled  active  teamID  bundleID (version/build)  name  [state]`.
+pub(crate) fn parse_systemextensionsctl(output: &str) -> Vec<SystemExtensionInfo> {
+    let mut extensions = Vec::new();
+    let mut category: Option<String> = None;
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("--- ") {
+            category = Some(
+                rest.trim()
+                    .trim_start_matches("com.apple.system_extension.")
+                    .to_string(),
+            );
+            continue;
+        }
+
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols.len() < 6 || cols[2].trim() == "teamID" {
+            continue;
+        }
+
+        let (identifier, version) = match cols[3].trim().split_once(" (") {
+            Some((id, ver)) => {
+                let ver = ver.trim_end_matches(')');
+                // "1.2.3/456" — keep the marketing version
+                let short = ver.split('/').next().unwrap_or(ver);
+                (id.trim().to_string(), Some(short.to_string()))
+            }
+            None => (cols[3].trim().to_string(), None),
+        };
+        if identifier.is_empty() {
+            continue;
+        }
+
+        let state = cols[5]
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let team_id = cols[2].trim();
+
+        extensions.push(SystemExtensionInfo {
+            identifier,
+            name: cols[4].trim().to_string(),
+            kind: "system_extension".to_string(),
+            category: category.clone(),
+            team_id: if team_id.is_empty() { None } else { Some(team_id.to_string()) },
+            version,
+            enabled: cols[0].trim() == "*",
+            state: if state.is_empty() { None } else { Some(state) },
+            path: None,
+            parent_bundle_id: None,
+            parent_app_path: None,
+            orphaned: false,
+        });
+    }
+
+    extensions
+}
+
+/// List system extensions registered with the system (via `systemextensionsctl`).
+pub async fn list_system_extensions() -> Vec<SystemExtensionInfo> {
+    match crate::utils::command::run_command_with_timeout("systemextensionsctl", &["list"], 10).await {
+        Ok(output) => parse_systemextensionsctl(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            log::warn!("systemextensionsctl list failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// List third-party kexts in `/Library/Extensions`, marking the ones currently loaded.
+pub async fn list_kexts() -> Vec<SystemExtensionInfo> {
+    let loaded = match crate::utils::command::run_command_with_timeout("kmutil", &["showloaded", "--list-only"], 15).await {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(e) => {
+            log::debug!("kmutil showloaded failed: {}", e);
+            String::new()
+        }
+    };
+
+    let mut kexts = Vec::new();
+    let entries = match std::fs::read_dir(THIRD_PARTY_KEXT_DIR) {
+        Ok(e) => e,
+        Err(_) => return kexts,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("kext") {
+            continue;
+        }
+        let dict = match read_info_plist(&path) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+        let identifier = match get_string(&dict, "CFBundleIdentifier") {
+            Some(id) => id,
+            None => continue,
+        };
+        if identifier.starts_with("com.apple.") {
+            continue;
+        }
+
+        let name = get_string(&dict, "CFBundleName").unwrap_or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Unknown")
+                .to_string()
+        });
+        let is_loaded = loaded.contains(&identifier);
+
+        kexts.push(SystemExtensionInfo {
+            identifier,
+            name,
+            kind: "kext".to_string(),
+            category: None,
+            team_id: None,
+            version: get_string(&dict, "CFBundleShortVersionString")
+                .or_else(|| get_string(&dict, "CFBundleVersion")),
+            state: Some(if is_loaded { "loaded" } else { "not loaded" }.to_string()),
+            enabled: is_loaded,
+            path: Some(path.to_string_lossy().to_string()),
+            parent_bundle_id: None,
+            parent_app_path: None,
+            orphaned: false,
+        });
+    }
+
+    kexts
+}
+
+/// Bundle IDs of extensions embedded in an app bundle (`Contents/Library/SystemExtensions`
+/// and `Contents/Library/Extensions`).
+fn embedded_extension_ids(app_path: &Path) -> Vec<String> {
+    let mut ids = Vec::new();
+    for sub in ["Contents/Library/SystemExtensions", "Contents/Library/Extensions"] {
+        if let Ok(entries) = std::fs::read_dir(app_path.join(sub)) {
+            for entry in entries.flatten() {
+                if let Ok(dict) = read_info_plist(&entry.path()) {
+                    if let Some(id) = get_string(&dict, "CFBundleIdentifier") {
+                        ids.push(id);
+                    }
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Attach each extension to the tracked app that owns it and flag orphans.
+///
+/// An extension belongs to an app if the app embeds it, or — failing that — if the
+/// app's bundle ID is a dot-separated prefix of the extension's identifier.
+/// `apps` is a list of (bundle_id, display_name, app_path).
+pub fn assign_parents(extensions: &mut [SystemExtensionInfo], apps: &[(String, String, String)]) {
+    let mut embedded: HashMap<String, usize> = HashMap::new();
+    for (idx, (_, _, app_path)) in apps.iter().enumerate() {
+        for id in embedded_extension_ids(Path::new(app_path)) {
+            embedded.entry(id).or_insert(idx);
+        }
+    }
+
+    for ext in extensions.iter_mut() {
+        let parent = embedded
+            .get(&ext.identifier)
+            .copied()
+            .or_else(|| super::owning_app_by_prefix(&ext.identifier, apps));
+
+        match parent {
+            Some(idx) => {
+                ext.parent_bundle_id = Some(apps[idx].0.clone());
+                ext.parent_app_path = Some(apps[idx].2.clone());
+                ext.orphaned = false;
+            }
+            None => {
+                // Extensions already queued for removal are not orphans.
+                let pending_removal = ext
+                    .state
+                    .as_deref()
+                    .map_or(false, |s| s.contains("uninstall"));
+                ext.orphaned = !pending_removal;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "2 extension(s)\n\
+--- com.apple.system_extension.network_extension\n\
+enabled\tactive\tteamID\tbundleID (version)\tname\t[state]\n\
+*\t*\tABCDE12345\tcom.vendor.vpn.netext (2.4.1/241)\tVendor VPN\t[activated enabled]\n\
+--- com.apple.system_extension.endpoint_security\n\
+enabled\tactive\tteamID\tbundleID (version)\tname\t[state]\n\
+\t\tFGHIJ67890\tcom.other.agent.es (1.0/1)\tOther Agent\t[terminated waiting to uninstall on reboot]\n";
+
+    #[test]
+    fn test_parse_systemextensionsctl() {
+        let exts = parse_systemextensionsctl(SAMPLE);
+        assert_eq!(exts.len(), 2);
+        assert_eq!(exts[0].identifier, "com.vendor.vpn.netext");
+        assert_eq!(exts[0].version.as_deref(), Some("2.4.1"));
+        assert_eq!(exts[0].category.as_deref(), Some("network_extension"));
+        assert_eq!(exts[0].team_id.as_deref(), Some("ABCDE12345"));
+        assert!(exts[0].enabled);
+        assert!(!exts[1].enabled);
+        assert_eq!(exts[1].state.as_deref(), Some("terminated waiting to uninstall on reboot"));
+    }
+
+    #[test]
+    fn test_assign_parents_by_prefix() {
+        let mut exts = parse_systemextensionsctl(SAMPLE);
+        let apps = vec![(
+            "com.vendor.vpn".to_string(),
+            "Vendor VPN".to_string(),
+            "/nonexistent/Vendor VPN.app".to_string(),
+        )];
+        assign_parents(&mut exts, &apps);
+        assert_eq!(exts[0].parent_bundle_id.as_deref(), Some("com.vendor.vpn"));
+        assert!(!exts[0].orphaned);
+        // No parent, but already pending removal
+        assert!(exts[1].parent_bundle_id.is_none());
+        assert!(!exts[1].orphaned);
+    }
+}
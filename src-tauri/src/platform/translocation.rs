@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::models::LocationIssue;
+use crate::utils::command::shell_quote;
+use crate::utils::{sudo_session, AppError, AppResult};
+
+/// Path component macOS inserts when it runs a quarantined app from a randomized,
+/// read-only location instead of where it really is.
+const TRANSLOCATION_MARKER: &str = "/AppTranslocation/";
+
+/// Classify where an app lives. `disk_image_mounts` are the mount points of
+/// attached disk images (from `hdiutil info`).
+pub(crate) fn classify_path(path: &str, home: Option<&Path>, disk_image_mounts: &[String]) -> Option<LocationIssue> {
+    if path.contains(TRANSLOCATION_MARKER) {
+        return Some(LocationIssue::Translocated);
+    }
+    if is_on_disk_image(path, disk_image_mounts) {
+        return Some(LocationIssue::DiskImage);
+    }
+    let downloads = home?.join("Downloads");
+    Path::new(path).starts_with(downloads).then_some(LocationIssue::Downloads)
+}
+
+/// Whether `path` is inside one of the disk images mounted at `disk_image_mounts`.
+pub(crate) fn is_on_disk_image(path: &str, disk_image_mounts: &[String]) -> bool {
+    disk_image_mounts
+        .iter()
+        .any(|mount| path.strip_prefix(mount.as_str()).is_some_and(|rest| rest.starts_with('/')))
+}
+
+/// Mount points listed in `hdiutil info` output. Each attached image lists its
+/// partitions as `/dev/diskNsM<TAB>type<TAB>/Volumes/Name`.
+pub(crate) fn parse_hdiutil_mounts(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with("/dev/"))
+        .filter_map(|line| line.split('\t').nth(2))
+        .map(str::trim)
+        .filter(|mount| mount.starts_with('/'))
+        .map(String::from)
+        .collect()
+}
+
+pub(crate) fn disk_image_mounts() -> Vec<String> {
+    Command::new("hdiutil")
+        .current_dir("/tmp")
+        .arg("info")
+        .output()
+        .map(|out| parse_hdiutil_mounts(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+/// Where the running copy of an app was launched from, per Launch Services.
+fn running_bundle_path(bundle_id: &str) -> Option<String> {
+    let output = Command::new("lsappinfo")
+        .current_dir("/tmp")
+        .args(["info", "-only", "bundlepath", bundle_id])
+        .output()
+        .ok()?;
+    // Prints `"LSBundlePath"="/path/to/Foo.app"`, or nothing if it isn't running
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (_, value) = stdout.trim().split_once('=')?;
+    let value = value.trim().trim_matches('"');
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Whether an app is running translocated, or installed somewhere updates can't
+/// reliably replace it (a mounted disk image or ~/Downloads).
+pub fn location_issue(bundle_id: &str, app_path: &str) -> Option<LocationIssue> {
+    let mounts = disk_image_mounts();
+    let home = dirs::home_dir();
+    running_bundle_path(bundle_id)
+        .and_then(|running| classify_path(&running, home.as_deref(), &mounts))
+        .filter(|issue| *issue == LocationIssue::Translocated)
+        .or_else(|| classify_path(app_path, home.as_deref(), &mounts))
+}
+
+/// Install the app at `app_path` in /Applications: copied off a mounted disk image
+/// (which is read-only, whether or not the app also runs translocated), moved
+/// anywhere else. The quarantine flag that triggers translocation is cleared.
+/// Returns the new path.
+pub fn move_to_applications(app_path: &str) -> AppResult<PathBuf> {
+    let from_disk_image = is_on_disk_image(app_path, &disk_image_mounts());
+    let source = Path::new(app_path);
+    let name = source
+        .file_name()
+        .ok_or_else(|| AppError::Custom(format!("Not an app bundle: {}", app_path)))?;
+    let dest = Path::new("/Applications").join(name);
+    if dest.exists() {
+        return Err(AppError::Custom(format!("{} already exists", dest.display())));
+    }
+    let dest_str = dest.to_string_lossy().to_string();
+
+    // ditto preserves the bundle's signature, extended attributes and symlinks
+    let command = if from_disk_image {
+        format!("ditto {} {}", shell_quote(app_path), shell_quote(&dest_str))
+    } else {
+        format!("mv {} {}", shell_quote(app_path), shell_quote(&dest_str))
+    };
+    let status = Command::new("sh")
+        .current_dir("/tmp")
+        .args(["-c", &command])
+        .status()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to run {}: {}", command, e)))?;
+    if !status.success() {
+        match sudo_session::run_elevated_shell(&command) {
+            Ok(out) if out.status.success() => {}
+            Err(sudo_session::ElevatedError::UserCancelled) => {
+                return Err(AppError::Custom(
+                    "Administrator approval is required to move this app".to_string(),
+                ));
+            }
+            Ok(out) => {
+                return Err(AppError::CommandFailed(format!(
+                    "Failed to move app: {}",
+                    String::from_utf8_lossy(&out.stderr).trim()
+                )));
+            }
+            Err(e) => return Err(AppError::CommandFailed(format!("Failed to move app: {}", e))),
+        }
+    }
+
+    let _ = Command::new("xattr")
+        .current_dir("/tmp")
+        .args(["-dr", "com.apple.quarantine", &dest_str])
+        .output();
+    Ok(dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_app_locations() {
+        let home = Path::new("/Users/me");
+        let mounts = vec!["/Volumes/Foo Installer".to_string()];
+        assert_eq!(
+            classify_path(
+                "/private/var/folders/ab/T/AppTranslocation/1234-ABCD/d/Foo.app",
+                Some(home),
+                &mounts
+            ),
+            Some(LocationIssue::Translocated)
+        );
+        assert_eq!(
+            classify_path("/Volumes/Foo Installer/Foo.app", Some(home), &mounts),
+            Some(LocationIssue::DiskImage)
+        );
+        assert_eq!(classify_path("/Volumes/Foo Installer 2/Foo.app", Some(home), &mounts), None);
+        assert_eq!(
+            classify_path("/Users/me/Downloads/Foo.app", Some(home), &mounts),
+            Some(LocationIssue::Downloads)
+        );
+        assert_eq!(classify_path("/Applications/Foo.app", Some(home), &mounts), None);
+    }
+
+    #[test]
+    fn apps_on_disk_images_are_copied_even_when_translocated() {
+        let mounts = vec!["/Volumes/Foo Installer".to_string()];
+        // Launched from the disk image: the running copy is translocated, the
+        // recorded path is still on the read-only volume
+        let running = "/private/var/folders/ab/T/AppTranslocation/1234-ABCD/d/Foo.app";
+        let recorded = "/Volumes/Foo Installer/Foo.app";
+        assert_eq!(classify_path(running, None, &mounts), Some(LocationIssue::Translocated));
+        assert!(is_on_disk_image(recorded, &mounts));
+        assert!(!is_on_disk_image("/Volumes/Foo Installer 2/Foo.app", &mounts));
+        assert!(!is_on_disk_image("/Users/me/Downloads/Foo.app", &mounts));
+    }
+
+    #[test]
+    fn parses_disk_image_mounts() {
+        let info = "framework       : 671\n\
+================================================\n\
+image-path      : /Users/me/Downloads/Foo.dmg\n\
+/dev/disk4\tGUID_partition_scheme\t\n\
+/dev/disk4s1\tApple_HFS\t/Volumes/Foo Installer\n";
+        assert_eq!(parse_hdiutil_mounts(info), vec!["/Volumes/Foo Installer".to_string()]);
+    }
+}
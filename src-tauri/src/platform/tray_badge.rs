@@ -1,9 +1,21 @@
-use image::{ImageBuffer, Rgba, RgbaImage};
+use image::{imageops, ImageBuffer, Rgba, RgbaImage};
 use std::io::Cursor;
+use tauri::{AppHandle, Manager};
+
+use crate::models::{AppSettings, TrayBadgeStyle, TrayIconStyle};
+
+/// Accent used for the colored (non-template) tray icon.
+const ICON_TINT: Rgba<u8> = Rgba([10, 132, 255, 255]);
+
+/// Badge fill color.
+const BADGE_RED: Rgba<u8> = Rgba([230, 50, 50, 255]);
+
+/// Share of the icon height the glyph occupies in the compact variant.
+const COMPACT_SCALE: f32 = 0.75;
 
 /// Bitmap font: 5×7 pixel patterns for digits 0-9.
 /// Each digit is stored as 7 rows of 5 bits (MSB-first).
-const DIGIT_FONT: [[u8; 7]; 10] = [
+pub(crate) const DIGIT_FONT: [[u8; 7]; 10] = [
     // 0
     [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
     // 1
@@ -26,16 +38,124 @@ const DIGIT_FONT: [[u8; 7]; 10] = [
     [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00001, 0b01110],
 ];
 
-/// Render a tray icon with a red badge showing the update count.
+/// Render the tray icon for `count` pending updates in the configured style:
+/// optionally the compact variant, tinted when colored, with a number or dot badge.
 /// Returns PNG bytes suitable for `tauri::image::Image::from_bytes`.
-pub fn render_tray_icon_with_badge(base_png: &[u8], count: usize) -> Option<Vec<u8>> {
+pub fn render_tray_icon(base_png: &[u8], count: usize, settings: &AppSettings) -> Option<Vec<u8>> {
     let base = image::load_from_memory_with_format(base_png, image::ImageFormat::Png).ok()?;
     let mut img: RgbaImage = base.to_rgba8();
+
+    if settings.compact_tray_icon {
+        img = compact_icon(&img);
+    }
+    if settings.tray_icon_style == TrayIconStyle::Colored {
+        tint(&mut img, ICON_TINT);
+    }
+    if settings.show_badge_count && count > 0 {
+        match settings.tray_badge_style {
+            TrayBadgeStyle::Number => draw_count_badge(&mut img, count),
+            TrayBadgeStyle::Dot => draw_dot_badge(&mut img),
+        }
+    }
+
+    Some(encode_png(&img))
+}
+
+/// Apply the update count to the tray: visibility, tooltip, icon and template mode.
+pub fn update_tray_icon(app_handle: &AppHandle, settings: &AppSettings, count: usize) {
+    let Some(tray) = app_handle.tray_by_id("main-tray") else {
+        return;
+    };
+
+    let visible = settings.show_menu_bar_icon && !(settings.hide_tray_when_idle && count == 0);
+    let _ = tray.set_visible(visible);
+
+    let tooltip = if settings.show_badge_count && count > 0 {
+        crate::utils::i18n::tr_count("macPlus — {count} update", "macPlus — {count} updates", count, &[])
+    } else {
+        "macPlus".to_string()
+    };
+    let _ = tray.set_tooltip(Some(&tooltip));
+
+    // Without a badge, show the static update icon so pending updates still stand out
+    let icon_name = if !settings.show_badge_count && count > 0 {
+        "icons/tray-icon-update.png"
+    } else {
+        "icons/tray-icon.png"
+    };
+    let base_bytes = match app_handle
+        .path()
+        .resolve(icon_name, tauri::path::BaseDirectory::Resource)
+        .ok()
+        .and_then(|p| std::fs::read(p).ok())
+    {
+        Some(b) => b,
+        None => return,
+    };
+    let icon_bytes = render_tray_icon(&base_bytes, count, settings).unwrap_or(base_bytes);
+    if let Ok(icon) = tauri::image::Image::from_bytes(&icon_bytes) {
+        let _ = tray.set_icon(Some(icon.to_owned()));
+    }
+    let _ = tray.set_icon_as_template(settings.tray_icon_style == TrayIconStyle::Monochrome);
+}
+
+/// Recolor every opaque pixel, keeping its alpha.
+fn tint(img: &mut RgbaImage, color: Rgba<u8>) {
+    for pixel in img.pixels_mut() {
+        let alpha = pixel[3];
+        *pixel = Rgba([color[0], color[1], color[2], alpha]);
+    }
+}
+
+/// Crop the glyph to its visible bounds and scale it down on a narrower canvas of
+/// the same height, so it takes less menu bar space.
+fn compact_icon(img: &RgbaImage) -> RgbaImage {
     let (w, h) = (img.width(), img.height());
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (w, h, 0, 0);
+    for (x, y, pixel) in img.enumerate_pixels() {
+        if pixel[3] > 0 {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+    if min_x > max_x || min_y > max_y {
+        return img.clone();
+    }
+
+    let glyph = imageops::crop_imm(img, min_x, min_y, max_x - min_x + 1, max_y - min_y + 1).to_image();
+    let target_h = ((h as f32 * COMPACT_SCALE).round() as u32).max(1);
+    let target_w = ((glyph.width() as f32 * target_h as f32 / glyph.height() as f32).round() as u32).max(1);
+    let glyph = imageops::resize(&glyph, target_w, target_h, imageops::FilterType::Lanczos3);
 
-    if count == 0 {
-        return Some(encode_png(&img));
+    // Leave room on the right for a badge
+    let canvas_w = (target_w + h / 4).min(w);
+    let mut canvas = RgbaImage::new(canvas_w, h);
+    imageops::overlay(&mut canvas, &glyph, 0, ((h - target_h) / 2) as i64);
+    canvas
+}
+
+/// Draw a small red dot in the top-right corner.
+fn draw_dot_badge(img: &mut RgbaImage) {
+    let (w, h) = (img.width(), img.height());
+    let diameter = ((h as f32 * 0.3).round() as u32).max(2);
+    let r = diameter as f32 / 2.0;
+    let (cx, cy) = (w as f32 - r, r);
+    for y in 0..diameter.min(h) {
+        for x in w.saturating_sub(diameter)..w {
+            let dx = x as f32 + 0.5 - cx;
+            let dy = y as f32 + 0.5 - cy;
+            if dx * dx + dy * dy <= r * r {
+                img.put_pixel(x, y, BADGE_RED);
+            }
+        }
     }
+}
+
+/// Draw a red badge showing the update count in the top-right corner.
+fn draw_count_badge(img: &mut RgbaImage, count: usize) {
+    let (w, h) = (img.width(), img.height());
 
     let label = if count > 99 {
         "99+".to_string()
@@ -60,7 +180,6 @@ pub fn render_tray_icon_with_badge(base_png: &[u8], count: usize) -> Option<Vec<
     let badge_x = w.saturating_sub(badge_w);
     let badge_y = 0u32;
 
-    let red = Rgba([230u8, 50, 50, 255]);
     let white = Rgba([255u8, 255, 255, 255]);
 
     // Draw filled red rounded rectangle (approximate with corner radius)
@@ -68,7 +187,7 @@ pub fn render_tray_icon_with_badge(base_png: &[u8], count: usize) -> Option<Vec<
     for y in badge_y..badge_y + badge_h {
         for x in badge_x..badge_x + badge_w {
             if x < w && y < h && is_inside_rounded_rect(x - badge_x, y - badge_y, badge_w, badge_h, radius) {
-                img.put_pixel(x, y, red);
+                img.put_pixel(x, y, BADGE_RED);
             }
         }
     }
@@ -84,14 +203,12 @@ pub fn render_tray_icon_with_badge(base_png: &[u8], count: usize) -> Option<Vec<
             let plus: [u8; 7] = [
                 0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000,
             ];
-            draw_char(&mut img, cx, text_y, &plus, scale, white, w, h);
+            draw_char(img, cx, text_y, &plus, scale, white, w, h);
         } else if let Some(digit) = ch.to_digit(10) {
-            draw_char(&mut img, cx, text_y, &DIGIT_FONT[digit as usize], scale, white, w, h);
+            draw_char(img, cx, text_y, &DIGIT_FONT[digit as usize], scale, white, w, h);
         }
         cx += scaled_char_w + char_gap;
     }
-
-    Some(encode_png(&img))
 }
 
 fn is_inside_rounded_rect(x: u32, y: u32, w: u32, h: u32, r: u32) -> bool {
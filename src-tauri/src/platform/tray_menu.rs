@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder};
+use tauri::{AppHandle, Manager, Wry};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::{ActivityEntry, ActivityKind};
+use crate::utils::i18n::{tr, tr_args, tr_count};
+
+/// Maximum number of apps listed in the tray's pending-updates submenu.
+const MAX_TRAY_UPDATES: usize = 10;
+
+/// Maximum number of entries in the tray's recent-activity submenu.
+const MAX_TRAY_ACTIVITY: i64 = 8;
+
+/// Menu ID prefix for per-app update items; the rest of the ID is the bundle ID.
+const UPDATE_APP_PREFIX: &str = "update_app:";
+const UPDATE_ALL_ID: &str = "update_all";
+
+/// One line of the recent-activity submenu.
+fn activity_label(entry: &ActivityEntry) -> String {
+    let name = entry.display_name.as_str();
+    let version = entry.version.as_deref().unwrap_or("?");
+    match entry.kind {
+        ActivityKind::Updated => tr_args("Updated {name} to {version}", &[("name", name), ("version", version)]),
+        ActivityKind::UpdateFailed => tr_args("Update of {name} failed", &[("name", name)]),
+        ActivityKind::Uninstalled => tr_args("Uninstalled {name}", &[("name", name)]),
+        ActivityKind::SelfUpdated => tr_args("macPlus updated to {version}", &[("version", version)]),
+        ActivityKind::AppAdded => tr_args("Found {name}", &[("name", name)]),
+    }
+}
+
+/// Build the tray menu. `pending` is a list of (bundle_id, display_name, available_version)
+/// shown in an "updates available" submenu; `total` is the full update count. `recent`
+/// fills a recent-activity submenu, which is left out when empty.
+pub fn build_tray_menu(
+    app: &AppHandle,
+    pending: &[(String, String, String)],
+    total: usize,
+    recent: &[ActivityEntry],
+) -> tauri::Result<Menu<Wry>> {
+    let check_now = MenuItemBuilder::with_id("check_now", tr("Check for Updates")).build(app)?;
+    let mut builder = MenuBuilder::new(app).item(&check_now);
+
+    if total == 0 {
+        let none = MenuItemBuilder::with_id("update_count", tr("No updates available"))
+            .enabled(false)
+            .build(app)?;
+        builder = builder.item(&none);
+    } else {
+        let title = tr_count("{count} update available", "{count} updates available", total, &[]);
+        let mut submenu = SubmenuBuilder::with_id(app, "update_count", &title);
+        for (bundle_id, display_name, version) in pending {
+            submenu = submenu.text(
+                format!("{}{}", UPDATE_APP_PREFIX, bundle_id),
+                format!("{} \u{2192} {}", display_name, version),
+            );
+        }
+        if total > pending.len() {
+            let more = MenuItemBuilder::with_id("update_more", tr_args("and {count} more…", &[("count", &(total - pending.len()).to_string())]))
+                .enabled(false)
+                .build(app)?;
+            submenu = submenu.item(&more);
+        }
+        let submenu = submenu.separator().text(UPDATE_ALL_ID, tr("Update All")).build()?;
+        builder = builder.item(&submenu);
+    }
+
+    if !recent.is_empty() {
+        let mut submenu = SubmenuBuilder::with_id(app, "recent_activity", tr("Recent Activity"));
+        for (i, entry) in recent.iter().enumerate() {
+            let item = MenuItemBuilder::with_id(format!("recent_activity:{}", i), activity_label(entry))
+                .enabled(false)
+                .build(app)?;
+            submenu = submenu.item(&item);
+        }
+        builder = builder.item(&submenu.build()?);
+    }
+
+    builder
+        .separator()
+        .text("open_app", tr("Open macPlus"))
+        .separator()
+        .text("quit", tr("Quit macPlus"))
+        .build()
+}
+
+/// Rebuild the tray menu from the current pending updates in the database.
+pub async fn refresh_tray_menu(app_handle: &AppHandle) {
+    let (pending, total, recent) = {
+        let db = app_handle.state::<Arc<Mutex<Database>>>();
+        let db_guard = db.lock().await;
+        (
+            db_guard.get_pending_updates(Some(MAX_TRAY_UPDATES)).unwrap_or_default(),
+            db_guard.get_update_count().unwrap_or(0),
+            db_guard.get_recent_activity(MAX_TRAY_ACTIVITY).unwrap_or_default(),
+        )
+    };
+
+    let Some(tray) = app_handle.tray_by_id("main-tray") else {
+        return;
+    };
+    match build_tray_menu(app_handle, &pending, total, &recent) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+/// Handle the update items in the pending-updates submenu. Returns false for IDs
+/// that aren't ours.
+pub fn handle_menu_event(app: &AppHandle, id: &str) -> bool {
+    if let Some(bundle_id) = id.strip_prefix(UPDATE_APP_PREFIX) {
+        let app = app.clone();
+        let bundle_id = bundle_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            let db = app.state::<Arc<Mutex<Database>>>();
+            if let Err(e) = crate::commands::execute::execute_update(bundle_id.clone(), app.clone(), db).await {
+                log::warn!("Tray update of {} failed: {}", bundle_id, e);
+            }
+            refresh_tray_menu(&app).await;
+        });
+        return true;
+    }
+
+    if id == UPDATE_ALL_ID {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let bundle_ids = {
+                let db = app.state::<Arc<Mutex<Database>>>();
+                let db_guard = db.lock().await;
+                crate::commands::execute::pending_bulk_updates(&db_guard).unwrap_or_default()
+            };
+            if bundle_ids.is_empty() {
+                return;
+            }
+            let db = app.state::<Arc<Mutex<Database>>>();
+            if let Err(e) = crate::commands::execute::execute_bulk_update(bundle_ids, app.clone(), db).await {
+                log::warn!("Tray bulk update failed: {}", e);
+            }
+            refresh_tray_menu(&app).await;
+        });
+        return true;
+    }
+
+    false
+}
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewWindow};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+
+/// How far (physical pixels) the popover must be dragged from its tray anchor
+/// before it counts as detached and keeps its own position.
+const DETACH_THRESHOLD: i32 = 24;
+
+/// Minimum visible overlap (physical pixels) for a saved frame to count as on-screen.
+const MIN_VISIBLE: i64 = 80;
+
+/// Quiet period after the last move/resize before the frame is written to the database.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Last known frame of the main window, in physical pixels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Name of the display the window was on
+    #[serde(default)]
+    pub monitor: Option<String>,
+    /// True when the popover was dragged away from the tray (always true for the standard window)
+    #[serde(default)]
+    pub detached: bool,
+}
+
+/// Saved frames for the standard window and the popover, kept apart so switching
+/// modes doesn't carry one's position over to the other.
+static SAVED_STANDARD: StdMutex<Option<WindowGeometry>> = StdMutex::new(None);
+static SAVED_POPOVER: StdMutex<Option<WindowGeometry>> = StdMutex::new(None);
+/// Where the popover was last anchored below the tray.
+static ANCHOR: StdMutex<Option<(i32, i32)>> = StdMutex::new(None);
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn slot(standard: bool) -> (&'static StdMutex<Option<WindowGeometry>>, &'static str) {
+    if standard {
+        (&SAVED_STANDARD, "window_geometry")
+    } else {
+        (&SAVED_POPOVER, "popover_geometry")
+    }
+}
+
+/// Load the saved frames from the settings table into memory. Call once at startup.
+pub fn load_saved_geometry(db: &Database) {
+    for standard in [true, false] {
+        let (saved, key) = slot(standard);
+        let geometry = db
+            .conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+                row.get::<_, String>(0)
+            })
+            .ok()
+            .and_then(|json| serde_json::from_str::<WindowGeometry>(&json).ok());
+        *saved.lock().unwrap_or_else(|e| e.into_inner()) = geometry;
+    }
+}
+
+/// Last saved frame for the standard window (`standard`) or the popover.
+pub fn saved_geometry(standard: bool) -> Option<WindowGeometry> {
+    slot(standard).0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn monitor_contains(monitor: &Monitor, x: f64, y: f64) -> bool {
+    let pos = monitor.position();
+    let size = monitor.size();
+    x >= pos.x as f64
+        && x < pos.x as f64 + size.width as f64
+        && y >= pos.y as f64
+        && y < pos.y as f64 + size.height as f64
+}
+
+/// Visible overlap between a frame and a monitor, as (width, height).
+fn overlap(monitor: &Monitor, g: &WindowGeometry) -> (i64, i64) {
+    let pos = monitor.position();
+    let size = monitor.size();
+    let left = (g.x as i64).max(pos.x as i64);
+    let right = (g.x as i64 + g.width as i64).min(pos.x as i64 + size.width as i64);
+    let top = (g.y as i64).max(pos.y as i64);
+    let bottom = (g.y as i64 + g.height as i64).min(pos.y as i64 + size.height as i64);
+    (right - left, bottom - top)
+}
+
+/// Whether a saved frame is still usable: its display is connected and enough of
+/// the window would be visible on it.
+fn is_on_screen(window: &WebviewWindow, g: &WindowGeometry) -> bool {
+    let monitors = window.available_monitors().unwrap_or_default();
+    monitors
+        .iter()
+        .filter(|m| g.monitor.is_none() || m.name() == g.monitor.as_ref())
+        .any(|m| {
+            let (w, h) = overlap(m, g);
+            w >= MIN_VISIBLE && h >= MIN_VISIBLE
+        })
+}
+
+/// Move (and, for a resizable window, resize) the window to a saved frame.
+/// Returns false when the frame is off-screen or its display is gone.
+pub fn restore_geometry(window: &WebviewWindow, g: &WindowGeometry) -> bool {
+    if !is_on_screen(window, g) {
+        return false;
+    }
+    if window.is_resizable().unwrap_or(false) {
+        let _ = window.set_size(PhysicalSize::new(g.width, g.height));
+    }
+    let _ = window.set_position(PhysicalPosition::new(g.x, g.y));
+    true
+}
+
+/// Position the window centered below the given tray icon rectangle, on the display
+/// that holds the tray, kept inside that display's usable area. This also covers an
+/// auto-hiding menu bar, where the tray rect can sit above the visible screen.
+pub fn anchor_below_tray(window: &WebviewWindow, tray_rect: &tauri::Rect) {
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let tray_pos = tray_rect.position.to_physical::<f64>(scale);
+    let tray_size = tray_rect.size.to_physical::<f64>(scale);
+    let tray_center_x = tray_pos.x + tray_size.width / 2.0;
+    let tray_center_y = tray_pos.y + tray_size.height / 2.0;
+
+    let monitors = window.available_monitors().unwrap_or_default();
+    let monitor = monitors
+        .iter()
+        .find(|m| monitor_contains(m, tray_center_x, tray_center_y))
+        // Menu bar hidden: match on the horizontal position alone
+        .or_else(|| {
+            monitors.iter().find(|m| {
+                let pos = m.position();
+                tray_center_x >= pos.x as f64 && tray_center_x < pos.x as f64 + m.size().width as f64
+            })
+        })
+        .cloned()
+        .or_else(|| window.current_monitor().ok().flatten())
+        .or_else(|| window.primary_monitor().ok().flatten());
+
+    let size = window.outer_size().unwrap_or(PhysicalSize::new(640, 740));
+    let (win_w, win_h) = (size.width as f64, size.height as f64);
+    let mut x = tray_center_x - win_w / 2.0;
+    let mut y = tray_pos.y + tray_size.height;
+
+    if let Some(monitor) = monitor {
+        let area = monitor.work_area();
+        let left = area.position.x as f64;
+        let top = area.position.y as f64;
+        let right = left + area.size.width as f64;
+        let bottom = top + area.size.height as f64;
+        x = x.max(left).min((right - win_w).max(left));
+        y = y.max(top).min((bottom - win_h).max(top));
+    }
+
+    // Record the anchor first so the resulting Moved event isn't taken for a drag
+    let (x, y) = (x as i32, y as i32);
+    *ANCHOR.lock().unwrap_or_else(|e| e.into_inner()) = Some((x, y));
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+}
+
+/// Show position for the popover: the saved frame if the user dragged it away from
+/// the tray and it's still on-screen, otherwise anchored below the tray.
+pub fn place_popover(window: &WebviewWindow, tray_rect: &tauri::Rect) {
+    if let Some(g) = saved_geometry(false).filter(|g| g.detached) {
+        if restore_geometry(window, &g) {
+            return;
+        }
+    }
+    anchor_below_tray(window, tray_rect);
+}
+
+/// Whether a frame at (`x`, `y`) counts as dragged away from the tray anchor. With
+/// no anchor yet (the popover was put back at its saved frame, e.g. after launch)
+/// the saved flag stands.
+fn detached_from_anchor(
+    standard: bool,
+    (x, y): (i32, i32),
+    anchor: Option<(i32, i32)>,
+    saved_detached: bool,
+) -> bool {
+    if standard {
+        return true;
+    }
+    match anchor {
+        Some((ax, ay)) => (x - ax).abs() > DETACH_THRESHOLD || (y - ay).abs() > DETACH_THRESHOLD,
+        None => saved_detached,
+    }
+}
+
+fn is_detached(standard: bool, x: i32, y: i32) -> bool {
+    let anchor = *ANCHOR.lock().unwrap_or_else(|e| e.into_inner());
+    let saved_detached = saved_geometry(standard).is_some_and(|g| g.detached);
+    detached_from_anchor(standard, (x, y), anchor, saved_detached)
+}
+
+/// Persist the main window's current frame and display for the active window mode.
+pub async fn save_window_geometry(app: &AppHandle) {
+    let standard = super::window_mode::is_standard_mode();
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let (Ok(pos), Ok(size)) = (window.outer_position(), window.outer_size()) else {
+        return;
+    };
+    let geometry = WindowGeometry {
+        x: pos.x,
+        y: pos.y,
+        width: size.width,
+        height: size.height,
+        monitor: window
+            .current_monitor()
+            .ok()
+            .flatten()
+            .and_then(|m| m.name().cloned()),
+        detached: is_detached(standard, pos.x, pos.y),
+    };
+    let Ok(json) = serde_json::to_string(&geometry) else {
+        return;
+    };
+    let (saved, key) = slot(standard);
+    *saved.lock().unwrap_or_else(|e| e.into_inner()) = Some(geometry);
+
+    let db = app.state::<Arc<Mutex<Database>>>();
+    let db_guard = db.lock().await;
+    let _ = db_guard.conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+        [key, json.as_str()],
+    );
+}
+
+/// Save the frame shortly after the user stops moving or resizing the main window.
+pub fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+    if !matches!(event, tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)) {
+        return;
+    }
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let app = window.app_handle().clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+        if SAVE_GENERATION.load(Ordering::Relaxed) == generation {
+            save_window_geometry(&app).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popover_at(x: i32, y: i32, detached: bool) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width: 640,
+            height: 740,
+            monitor: Some("Built-in Retina Display".to_string()),
+            detached,
+        }
+    }
+
+    /// Save a frame the way `save_window_geometry` does and read it back the way
+    /// `load_saved_geometry` does.
+    fn save_and_restore(geometry: &WindowGeometry) -> WindowGeometry {
+        serde_json::from_str(&serde_json::to_string(geometry).unwrap()).unwrap()
+    }
+
+    /// `detached_from_anchor` for the popover.
+    fn popover_detached(at: (i32, i32), anchor: Option<(i32, i32)>, saved: bool) -> bool {
+        detached_from_anchor(false, at, anchor, saved)
+    }
+
+    #[test]
+    fn detached_popover_survives_a_restart() {
+        // Dragged away from the tray anchor, then saved
+        let detached = popover_detached((900, 300), Some((400, 30)), false);
+        let restored = save_and_restore(&popover_at(900, 300, detached));
+        assert!(restored.detached);
+
+        // After relaunch there's no anchor: moving the restored frame keeps it detached
+        assert!(popover_detached((920, 310), None, restored.detached));
+    }
+
+    #[test]
+    fn anchored_popover_stays_attached() {
+        let anchor = Some((400, 30));
+        assert!(!popover_detached((410, 30), anchor, false));
+        // Put back below the tray: an earlier detached flag no longer applies
+        assert!(!popover_detached((400, 30), anchor, true));
+
+        let restored = save_and_restore(&popover_at(400, 30, false));
+        assert!(!popover_detached((400, 30), None, restored.detached));
+    }
+
+    #[test]
+    fn standard_window_is_always_detached() {
+        assert!(detached_from_anchor(true, (0, 0), Some((0, 0)), false));
+        assert!(detached_from_anchor(true, (0, 0), None, false));
+    }
+
+    #[test]
+    fn frames_saved_before_detaching_existed_load_attached() {
+        let json = r#"{"x":10,"y":20,"width":640,"height":740}"#;
+        let geometry: WindowGeometry = serde_json::from_str(json).unwrap();
+        assert!(!geometry.detached);
+        assert_eq!(geometry.monitor, None);
+    }
+}
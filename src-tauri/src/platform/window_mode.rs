@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+use crate::models::WindowMode;
+
+use super::window_geometry;
+
+/// Size of the tray popover, in logical pixels (matches tauri.conf.json).
+const POPOVER_SIZE: (f64, f64) = (640.0, 740.0);
+
+/// Smallest size the standard window can be resized to, in logical pixels.
+const STANDARD_MIN_SIZE: (f64, f64) = (560.0, 480.0);
+
+static STANDARD_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Whether macPlus currently runs as a regular window rather than a tray popover.
+pub fn is_standard_mode() -> bool {
+    STANDARD_MODE.load(Ordering::Relaxed)
+}
+
+/// Switch between the tray popover and a regular window. The standard window is
+/// resizable, shows in the Dock and Cmd+Tab, and restores its last saved frame.
+pub fn apply_window_mode(app: &AppHandle, mode: WindowMode) {
+    let standard = mode == WindowMode::Standard;
+    STANDARD_MODE.store(standard, Ordering::Relaxed);
+
+    let _ = app.set_activation_policy(if standard {
+        tauri::ActivationPolicy::Regular
+    } else {
+        tauri::ActivationPolicy::Accessory
+    });
+
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let _ = window.set_resizable(standard);
+
+    if standard {
+        let _ = window.set_min_size(Some(tauri::LogicalSize::new(STANDARD_MIN_SIZE.0, STANDARD_MIN_SIZE.1)));
+        // Restore the last frame if its display is still connected
+        let restored = window_geometry::saved_geometry(true)
+            .map_or(false, |g| window_geometry::restore_geometry(&window, &g));
+        if !restored {
+            let _ = window.center();
+        }
+    } else {
+        let _ = window.set_min_size(None::<tauri::LogicalSize<f64>>);
+        let _ = window.set_size(tauri::LogicalSize::new(POPOVER_SIZE.0, POPOVER_SIZE.1));
+    }
+}
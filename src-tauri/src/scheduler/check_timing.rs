@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::models::CheckPhase;
+
+/// Settings-table key the timings are stored under.
+const TIMINGS_KEY: &str = "update_check_timings";
+
+/// Weight of the newest measurement in the running averages.
+const SMOOTHING: f64 = 0.3;
+
+/// Running averages of how long each part of an update check takes, used to
+/// estimate the time remaining in the next one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CheckTimings {
+    /// Seconds per phase before the per-app checks
+    phases: HashMap<CheckPhase, f64>,
+    /// Seconds to check one app, by install source
+    per_app: HashMap<String, f64>,
+}
+
+fn smooth(previous: Option<f64>, sample: f64) -> f64 {
+    match previous {
+        Some(avg) => avg + SMOOTHING * (sample - avg),
+        None => sample,
+    }
+}
+
+impl CheckTimings {
+    pub fn load(db: &Database) -> Self {
+        db.conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", [TIMINGS_KEY], |row| {
+                row.get::<_, String>(0)
+            })
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, db: &Database) {
+        let Ok(json) = serde_json::to_string(self) else {
+            return;
+        };
+        let _ = db.conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+            rusqlite::params![TIMINGS_KEY, json],
+        );
+    }
+
+    /// Whether any check has been timed yet; estimates are only made after one has.
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty() && self.per_app.is_empty()
+    }
+
+    pub fn record_phase(&mut self, phase: CheckPhase, elapsed: Duration) {
+        let avg = smooth(self.phases.get(&phase).copied(), elapsed.as_secs_f64());
+        self.phases.insert(phase, avg);
+    }
+
+    /// Fold in the mean time per app for each install source from one check.
+    pub fn record_apps(&mut self, samples: &HashMap<String, (Duration, u32)>) {
+        for (source, (total, count)) in samples {
+            if *count == 0 {
+                continue;
+            }
+            let mean = total.as_secs_f64() / *count as f64;
+            let avg = smooth(self.per_app.get(source).copied(), mean);
+            self.per_app.insert(source.clone(), avg);
+        }
+    }
+
+    fn phase_secs(&self, phase: CheckPhase) -> f64 {
+        self.phases.get(&phase).copied().unwrap_or(0.0)
+    }
+
+    /// Expected seconds to check one app from `source`; sources not seen before
+    /// use the average across all sources.
+    pub fn app_secs(&self, source: &str) -> f64 {
+        self.per_app.get(source).copied().unwrap_or_else(|| {
+            if self.per_app.is_empty() {
+                0.0
+            } else {
+                self.per_app.values().sum::<f64>() / self.per_app.len() as f64
+            }
+        })
+    }
+
+    /// Seconds the phases from `phase` up to the per-app checks are expected to
+    /// take. `brew outdated` and the cask index are fetched side by side, so only
+    /// the slower of the two counts.
+    pub fn prelude_secs(&self, phase: CheckPhase, has_brew: bool) -> f64 {
+        let fetch = self.phase_secs(CheckPhase::BrewOutdated).max(self.phase_secs(CheckPhase::CaskIndex));
+        match phase {
+            CheckPhase::Preparing | CheckPhase::BrewUpdate if has_brew => {
+                self.phase_secs(CheckPhase::BrewUpdate) + fetch
+            }
+            CheckPhase::Preparing | CheckPhase::BrewUpdate | CheckPhase::BrewOutdated => fetch,
+            CheckPhase::CaskIndex => self.phase_secs(CheckPhase::CaskIndex),
+            CheckPhase::AppChecks => 0.0,
+        }
+    }
+}
+
+/// Whole seconds remaining, given the seconds of work left and how many app
+/// checks run at once.
+pub fn eta_secs(prelude_secs: f64, app_secs: f64, concurrency: usize) -> u64 {
+    (prelude_secs + app_secs / concurrency.max(1) as f64).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_favor_history() {
+        let mut t = CheckTimings::default();
+        t.record_phase(CheckPhase::BrewUpdate, Duration::from_secs(10));
+        assert_eq!(t.phase_secs(CheckPhase::BrewUpdate), 10.0);
+        t.record_phase(CheckPhase::BrewUpdate, Duration::from_secs(20));
+        assert!((t.phase_secs(CheckPhase::BrewUpdate) - 13.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_source_uses_mean() {
+        let mut t = CheckTimings::default();
+        assert_eq!(t.app_secs("mas"), 0.0);
+        let samples = HashMap::from([
+            ("homebrew".to_string(), (Duration::from_secs(4), 2)),
+            ("direct".to_string(), (Duration::from_secs(4), 1)),
+        ]);
+        t.record_apps(&samples);
+        assert_eq!(t.app_secs("homebrew"), 2.0);
+        assert_eq!(t.app_secs("mas"), 3.0);
+    }
+
+    #[test]
+    fn parallel_fetches_count_once() {
+        let mut t = CheckTimings::default();
+        t.record_phase(CheckPhase::BrewUpdate, Duration::from_secs(5));
+        t.record_phase(CheckPhase::BrewOutdated, Duration::from_secs(8));
+        t.record_phase(CheckPhase::CaskIndex, Duration::from_secs(3));
+        assert_eq!(t.prelude_secs(CheckPhase::Preparing, true), 13.0);
+        assert_eq!(t.prelude_secs(CheckPhase::Preparing, false), 8.0);
+        assert_eq!(t.prelude_secs(CheckPhase::CaskIndex, true), 3.0);
+        assert_eq!(eta_secs(8.0, 25.0, 10), 11);
+    }
+}
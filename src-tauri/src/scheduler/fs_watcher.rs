@@ -1,7 +1,63 @@
 use notify::{Event, EventKind, RecursiveMode, Watcher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-use tauri::{AppHandle, Emitter};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::detection::volumes;
+
+/// Time to let a newly mounted volume settle (Finder, Spotlight) before scanning it.
+const MOUNT_SETTLE_DELAY: Duration = Duration::from_secs(5);
+
+/// Volumes with a rescan already scheduled; FSEvents can report one mount twice.
+static PENDING_MOUNTS: StdMutex<Option<HashSet<PathBuf>>> = StdMutex::new(None);
+
+/// Re-scan after a volume mounts if it holds a configured scan location or an
+/// `Applications` folder, so apps on it show up without a manual scan.
+fn rescan_if_relevant(app_handle: AppHandle, volume: PathBuf) {
+    {
+        let mut pending = PENDING_MOUNTS.lock().unwrap_or_else(|e| e.into_inner());
+        if !pending.get_or_insert_with(HashSet::new).insert(volume.clone()) {
+            return;
+        }
+    }
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(MOUNT_SETTLE_DELAY).await;
+        if let Some(pending) = PENDING_MOUNTS.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            pending.remove(&volume);
+        }
+
+        let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+        let settings = {
+            let db_guard = db.lock().await;
+            super::load_settings_from_db(&db_guard)
+        };
+        let has_location = settings
+            .scan_locations
+            .iter()
+            .any(|loc| volumes::volume_root(Path::new(&loc.path)).as_deref() == Some(volume.as_path()));
+
+        let probe = volume.clone();
+        let has_apps_dir = tokio::task::spawn_blocking(move || {
+            volumes::is_reachable(&probe) && probe.join("Applications").is_dir()
+        })
+        .await
+        .unwrap_or(false);
+
+        if !has_location && !has_apps_dir {
+            return;
+        }
+        log::info!("Volume mounted: {} — rescanning", volume.display());
+        if let Err(e) = super::run_full_scan(&app_handle, &db).await {
+            log::warn!("Rescan after mounting {} failed: {}", volume.display(), e);
+        }
+    });
+}
 
 pub fn start_fs_watcher(app_handle: AppHandle) {
     std::thread::spawn(move || {
@@ -31,14 +87,21 @@ pub fn start_fs_watcher(app_handle: AppHandle) {
             }
         }
 
-        log::info!("FSEvents watcher started for /Applications");
+        // Mount points appear and disappear directly under /Volumes
+        if let Err(e) = watcher.watch(Path::new("/Volumes"), RecursiveMode::NonRecursive) {
+            log::warn!("Failed to watch /Volumes: {}", e);
+        }
+
+        log::info!("FSEvents watcher started for /Applications and /Volumes");
 
         for result in rx {
             match result {
                 Ok(event) => match event.kind {
                     EventKind::Create(_) => {
                         for path in &event.paths {
-                            if path.extension().map_or(false, |e| e == "app") {
+                            if path.parent() == Some(Path::new("/Volumes")) {
+                                rescan_if_relevant(app_handle.clone(), path.clone());
+                            } else if path.extension().map_or(false, |e| e == "app") {
                                 log::info!("New app detected: {:?}", path);
                                 let _ = app_handle.emit("app-installed", path.to_string_lossy().to_string());
                             }
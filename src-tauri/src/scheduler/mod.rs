@@ -1,22 +1,36 @@
+pub mod check_timing;
 pub mod fs_watcher;
+pub mod mount_sweeper;
+pub mod notifications;
+pub mod offline;
 pub mod scan_scheduler;
+pub mod scheduled_updates;
+pub mod security_updates;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
 use std::time::Duration;
 use futures::stream::{self, StreamExt};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::detection::DetectionEngine;
-use crate::models::{AppSettings, AppSource, ScanComplete, ScanProgress, UpdateCheckComplete, UpdateFound};
+use crate::detection::exclusions::ExclusionRules;
+use crate::detection::{volumes, DetectionEngine};
+use crate::models::{
+    AppDetail, AppSettings, AppSource, CheckPhase, DetectedApp, QueuedAction, ScanComplete, ScanProgress,
+    UpdateCheckComplete, UpdateFound, UpdateInfo,
+};
 use crate::platform::icon_extractor;
+use crate::updaters::{github_releases, version_compare};
 use crate::updaters::homebrew_api::{self, HomebrewCaskIndex};
 use crate::updaters::homebrew_cask::{fetch_brew_outdated, fetch_brew_outdated_formulae};
 use crate::updaters::{AppCheckContext, BrewOutdatedCask, BrewOutdatedFormula, UpdateDispatcher};
 use crate::utils::brew::{brew_command, brew_path};
-use crate::utils::{is_browser_extension, is_xcode_clt_installed, AppResult};
+use crate::utils::i18n::{tr, tr_count};
+use crate::utils::{is_cask_blocked, is_xcode_clt_installed, AppError, AppResult};
+use check_timing::CheckTimings;
 
 /// Load the check interval (in minutes) from settings for use at startup.
 pub fn load_settings_interval(db: &crate::db::Database) -> u64 {
@@ -39,6 +53,65 @@ pub fn load_settings_from_db(db: &crate::db::Database) -> AppSettings {
     }
 }
 
+/// SQLite `datetime('now')` format, used for the digest timestamp.
+const SQLITE_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Digest body, e.g. "7 updates pending, 3 applied".
+fn format_digest(pending: usize, applied: usize) -> String {
+    let pending_text = tr_count("{count} update pending", "{count} updates pending", pending, &[]);
+    if applied == 0 {
+        pending_text
+    } else {
+        tr_count("{pending}, {count} applied", "{pending}, {count} applied", applied, &[("pending", pending_text.as_str())])
+    }
+}
+
+/// Send the digest notification once `period` has passed since the previous one.
+/// The last digest time is kept in the settings table.
+async fn send_digest_if_due(
+    app_handle: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    settings: &AppSettings,
+    period: chrono::Duration,
+    pending: usize,
+) {
+    let now = chrono::Utc::now().naive_utc();
+    let applied = {
+        let db_guard = db.lock().await;
+        let last = db_guard
+            .conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = 'last_notification_digest'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+            .and_then(|v| chrono::NaiveDateTime::parse_from_str(&v, SQLITE_DATETIME_FORMAT).ok());
+        if last.map_or(false, |last| now - last < period) {
+            return;
+        }
+
+        let since = last.unwrap_or(now - period).format(SQLITE_DATETIME_FORMAT).to_string();
+        let applied = db_guard.count_completed_updates_since(&since).unwrap_or(0);
+
+        // Roll the window forward even when there's nothing to report
+        let _ = db_guard.conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES ('last_notification_digest', datetime('now'), datetime('now'))
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+            [],
+        );
+        applied
+    };
+
+    if pending == 0 && applied == 0 {
+        return;
+    }
+
+    let body = format_digest(pending, applied);
+    log::info!("Sending notification digest: {}", body);
+    notifications::send_update_notification(app_handle, settings, &body, false);
+}
+
 pub async fn run_full_scan(
     app_handle: &AppHandle,
     db: &Arc<Mutex<Database>>,
@@ -46,27 +119,31 @@ pub async fn run_full_scan(
     let start = std::time::Instant::now();
     let scan_started_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-    let (scan_locations, scan_depth) = {
+    let (scan_locations, scan_depth, exclusions, preferred_detectors) = {
         let db_guard = db.lock().await;
         let settings = load_settings_from_db(&db_guard);
-        (settings.scan_locations, settings.scan_depth)
+        let exclusions = ExclusionRules::from_settings(&settings);
+        (settings.scan_locations, settings.scan_depth, exclusions, db_guard.get_preferred_detectors())
     };
 
-    let engine = DetectionEngine::with_scan_locations(scan_locations, scan_depth);
+    let engine = DetectionEngine::with_scan_locations(scan_locations, scan_depth)
+        .with_exclusions(exclusions.clone())
+        .with_preferred_detectors(preferred_detectors);
+    let phase_total = engine.detector_count();
 
     // Emit initial progress event immediately so the UI shows activity right away
     let _ = app_handle.emit(
         "scan-progress",
         ScanProgress {
-            phase: "Starting".to_string(),
+            phase: tr("Starting"),
             current: 0,
-            total: 6,
+            total: phase_total,
             app_name: None,
         },
     );
 
     let handle = app_handle.clone();
-    let apps = engine
+    let (apps, conflicts) = engine
         .detect_all(|phase, current, total| {
             let _ = handle.emit(
                 "scan-progress",
@@ -81,13 +158,64 @@ pub async fn run_full_scan(
         .await?;
 
     let count = apps.len();
+    let app_paths: Vec<String> = apps
+        .iter()
+        .filter(|app| app.install_source != AppSource::HomebrewFormula && !app.app_path.is_empty())
+        .map(|app| app.app_path.clone())
+        .collect();
+    let last_used = crate::detection::spotlight::last_used_dates(&app_paths).await;
+    // The feed each Electron app updates itself from, for the Electron checker
+    let electron_feeds: Vec<_> = apps
+        .iter()
+        .filter(|app| app.install_source != AppSource::HomebrewFormula && !app.app_path.is_empty())
+        .map(|app| {
+            let feed = crate::updaters::electron::read_update_feed(std::path::Path::new(&app.app_path));
+            (app.bundle_id.as_str(), feed)
+        })
+        .collect();
     {
         let db_guard = db.lock().await;
+        // Apps that appear after the first scan show up in the activity feed
+        let known = db_guard.get_bundle_ids().unwrap_or_default();
         let _ = db_guard.conn.execute_batch("BEGIN");
         for app in &apps {
             let _ = db_guard.upsert_app(app);
+            if !known.is_empty() && !known.contains(&app.bundle_id) {
+                let _ = db_guard.record_app_added(
+                    &app.bundle_id,
+                    &app.display_name,
+                    app.installed_version.as_deref(),
+                );
+            }
+        }
+        for (bundle_id, feed) in &electron_feeds {
+            let _ = db_guard.set_electron_update_feed(bundle_id, feed.as_ref());
+        }
+        for (app_path, last_used_at) in app_paths.iter().zip(&last_used) {
+            if let Some(last_used_at) = last_used_at {
+                let _ = db_guard.update_last_used(app_path, last_used_at);
+            }
         }
         let _ = db_guard.conn.execute_batch("COMMIT");
+        if let Err(e) = db_guard.replace_detection_conflicts(&conflicts) {
+            log::warn!("Failed to store detection conflicts: {}", e);
+        }
+
+        // Drop apps recorded before an exclusion rule covered them
+        if !exclusions.is_empty() {
+            match db_guard.delete_excluded_apps(|bundle_id, app_path| exclusions.excludes(bundle_id, app_path)) {
+                Ok(removed) if !removed.is_empty() => log::info!("Removed {} excluded apps: {:?}", removed.len(), removed),
+                Ok(_) => {}
+                Err(e) => log::warn!("Excluded app cleanup failed: {}", e),
+            }
+        }
+
+        // Drop helper bundles left over from scans that predate parent grouping
+        match db_guard.delete_nested_helper_apps() {
+            Ok(count) if count > 0 => log::info!("Removed {} nested helper app rows", count),
+            Ok(_) => {}
+            Err(e) => log::warn!("Nested helper cleanup failed: {}", e),
+        }
 
         // Remove stale apps that were not re-detected and no longer exist on disk
         match db_guard.delete_stale_apps(&scan_started_at) {
@@ -103,30 +231,30 @@ pub async fn run_full_scan(
         let _ = app_handle.emit(
             "scan-progress",
             ScanProgress {
-                phase: "Finalising apps".to_string(),
-                current: 6,
-                total: 6,
+                phase: tr("Finalising apps"),
+                current: phase_total,
+                total: phase_total,
                 app_name: None,
             },
         );
 
-        // Extract icons for non-formula apps
+        // Extract icons, generating monograms for formulae and apps without one
         if let Ok(cache_dir) = app_handle.path().app_cache_dir() {
             let icons_dir = cache_dir.join("icons");
             if std::fs::create_dir_all(&icons_dir).is_ok() {
                 // First pass: update DB for apps that already have cached icons
-                let mut apps_needing_icons: Vec<(String, String)> = Vec::new();
+                let mut apps_needing_icons: Vec<(String, String, String)> = Vec::new();
                 for app in &apps {
-                    if app.install_source == AppSource::HomebrewFormula {
-                        continue;
-                    }
-
                     let expected_path = icons_dir.join(format!("{}.png", app.bundle_id));
                     if expected_path.exists() {
                         let path_str = expected_path.to_string_lossy().to_string();
                         let _ = db_guard.update_icon_cache_path(&app.bundle_id, &path_str);
                     } else {
-                        apps_needing_icons.push((app.bundle_id.clone(), app.app_path.clone()));
+                        apps_needing_icons.push((
+                            app.bundle_id.clone(),
+                            app.app_path.clone(),
+                            app.display_name.clone(),
+                        ));
                     }
                 }
                 drop(db_guard);
@@ -137,24 +265,27 @@ pub async fn run_full_scan(
                 let icon_results: Arc<Mutex<Vec<(String, String)>>> = Arc::new(Mutex::new(Vec::new()));
 
                 stream::iter(apps_needing_icons)
-                    .for_each_concurrent(16, |(bundle_id, app_path)| {
+                    .for_each_concurrent(16, |(bundle_id, app_path, display_name)| {
                         let icons_dir = icons_dir.clone();
                         let icon_results = icon_results.clone();
                         async move {
                             let app_path = std::path::Path::new(&app_path).to_path_buf();
                             let icons_dir_inner = icons_dir.clone();
+                            let task_bundle_id = bundle_id.clone();
                             let task = tokio::task::spawn_blocking(move || {
-                                icon_extractor::extract_icon_png(&app_path, &icons_dir_inner)
+                                icon_extractor::icon_or_monogram(
+                                    &app_path,
+                                    &task_bundle_id,
+                                    &display_name,
+                                    &icons_dir_inner,
+                                )
                             });
                             let result = tokio::time::timeout(Duration::from_secs(10), task).await;
 
                             match result {
-                                Ok(Ok(Ok(Some(icon_path)))) => {
+                                Ok(Ok(Ok(icon_path))) => {
                                     icon_results.lock().await.push((bundle_id, icon_path));
                                 }
-                                Ok(Ok(Ok(None))) => {
-                                    log::debug!("No icon found for {}", bundle_id);
-                                }
                                 Ok(Ok(Err(e))) => {
                                     log::debug!("Icon extraction failed for {}: {}", bundle_id, e);
                                 }
@@ -189,9 +320,9 @@ pub async fn run_full_scan(
     let _ = app_handle.emit(
         "scan-progress",
         ScanProgress {
-            phase: "Indexing".to_string(),
-            current: 6,
-            total: 6,
+            phase: tr("Indexing"),
+            current: phase_total,
+            total: phase_total,
             app_name: None,
         },
     );
@@ -210,9 +341,75 @@ pub async fn run_full_scan(
         },
     );
 
+    // Refresh cached disk usage in the background — walking bundles is slow
+    let db_for_sizes = db.clone();
+    tauri::async_runtime::spawn(async move {
+        refresh_app_sizes(&db_for_sizes).await;
+    });
+
     Ok(count)
 }
 
+/// Recompute bundle and associated-data sizes for apps with stale or missing
+/// cached sizes (up to 4 apps at a time, 60s per app).
+pub async fn refresh_app_sizes(db: &Arc<Mutex<Database>>) {
+    let apps = {
+        let db_guard = db.lock().await;
+        match db_guard.get_apps_needing_sizes() {
+            Ok(apps) => apps,
+            Err(e) => {
+                log::warn!("Disk usage: failed to load apps: {}", e);
+                return;
+            }
+        }
+    };
+    if apps.is_empty() {
+        return;
+    }
+
+    let start = std::time::Instant::now();
+    let total = apps.len();
+    let results: Arc<Mutex<Vec<(String, u64, u64)>>> = Arc::new(Mutex::new(Vec::new()));
+
+    stream::iter(apps)
+        .for_each_concurrent(4, |(bundle_id, display_name, app_path)| {
+            let results = results.clone();
+            async move {
+                let bid = bundle_id.clone();
+                let task = tokio::task::spawn_blocking(move || {
+                    let bundle_bytes = crate::commands::uninstall::path_size(std::path::Path::new(&app_path));
+                    let data_bytes: u64 = crate::commands::uninstall::find_associated_files(&bid, &display_name)
+                        .iter()
+                        .map(|f| f.size_bytes)
+                        .sum();
+                    (bundle_bytes, data_bytes)
+                });
+                match tokio::time::timeout(Duration::from_secs(60), task).await {
+                    Ok(Ok((bundle_bytes, data_bytes))) => {
+                        results.lock().await.push((bundle_id, bundle_bytes, data_bytes));
+                    }
+                    _ => log::debug!("Disk usage: sizing timed out or failed for {}", bundle_id),
+                }
+            }
+        })
+        .await;
+
+    let results = results.lock().await;
+    let db_guard = db.lock().await;
+    let _ = db_guard.conn.execute_batch("BEGIN");
+    for (bundle_id, bundle_bytes, data_bytes) in results.iter() {
+        let _ = db_guard.update_app_sizes(bundle_id, *bundle_bytes, *data_bytes);
+    }
+    let _ = db_guard.conn.execute_batch("COMMIT");
+
+    log::info!(
+        "Disk usage: sized {}/{} apps in {}ms",
+        results.len(),
+        total,
+        start.elapsed().as_millis()
+    );
+}
+
 /// Validate settings on startup: remove non-existent scan locations
 /// (except /Volumes/ paths which may be temporarily unmounted).
 pub async fn validate_settings(db: &Arc<Mutex<Database>>) {
@@ -223,16 +420,23 @@ pub async fn validate_settings(db: &Arc<Mutex<Database>>) {
     let mut pruned = Vec::new();
     let mut removed = Vec::new();
     for loc in &settings.scan_locations {
-        let expanded = std::path::Path::new(loc);
-        if expanded.exists() {
+        let expanded = std::path::Path::new(&loc.path);
+        if loc.path.starts_with("/Volumes/") {
+            // Keep volume paths without touching them — the drive might be disconnected,
+            // and stat on an unreachable share can block for a long time
+            let path = expanded.to_path_buf();
+            let reachable = tokio::task::spawn_blocking(move || volumes::is_reachable(&path))
+                .await
+                .unwrap_or(false);
+            if !reachable {
+                log::warn!("Settings: scan location '{}' not reachable (keeping — may be unmounted volume)", loc.path);
+            }
             pruned.push(loc.clone());
-        } else if loc.starts_with("/Volumes/") {
-            // Keep unmounted volume paths — drive might be temporarily disconnected
-            log::warn!("Settings: scan location '{}' not found (keeping — may be unmounted volume)", loc);
+        } else if expanded.exists() {
             pruned.push(loc.clone());
         } else {
-            log::warn!("Settings: removing stale scan location '{}' (path does not exist)", loc);
-            removed.push(loc.clone());
+            log::warn!("Settings: removing stale scan location '{}' (path does not exist)", loc.path);
+            removed.push(loc.path.clone());
         }
     }
 
@@ -240,81 +444,210 @@ pub async fn validate_settings(db: &Arc<Mutex<Database>>) {
         let mut updated = settings.clone();
         // If all locations were pruned, reset to defaults
         if pruned.is_empty() {
-            updated.scan_locations = vec!["/Applications".to_string(), "~/Applications".to_string()];
+            updated.scan_locations = AppSettings::default().scan_locations;
             log::info!("Settings: all scan locations were stale — reset to defaults");
         } else {
             updated.scan_locations = pruned;
         }
 
-        let json = match serde_json::to_string(&updated) {
-            Ok(j) => j,
-            Err(e) => {
-                log::warn!("Settings: failed to serialize pruned settings: {}", e);
-                return;
-            }
-        };
-
         let db_guard = db.lock().await;
-        let _ = db_guard.conn.execute(
-            "INSERT INTO settings (key, value, updated_at) VALUES ('app_settings', ?1, datetime('now'))
-             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
-            [&json],
-        );
+        if let Err(e) = db_guard.save_settings(&updated) {
+            log::warn!("Settings: failed to save pruned settings: {}", e);
+            return;
+        }
         log::info!("Settings: removed {} stale scan locations", removed.len());
     }
 }
 
+/// Delete history older than the configured retention period, if one is set.
+pub async fn prune_history(db: &Arc<Mutex<Database>>) {
+    let db_guard = db.lock().await;
+    let days = load_settings_from_db(&db_guard).history_retention_days;
+    if days == 0 {
+        return;
+    }
+    match db_guard.prune_history_older_than(days) {
+        Ok(0) => {}
+        Ok(n) => log::info!("Pruned {} history records older than {} days", n, days),
+        Err(e) => log::warn!("Failed to prune history: {}", e),
+    }
+}
+
+/// Whether `update` offers the version that's already installed. One with the
+/// same version but a newer build than the app on disk is kept, labelled with
+/// its build so it isn't taken for the installed version.
+pub fn is_no_op_update(
+    update: &mut UpdateInfo,
+    installed_version: Option<&str>,
+    app_path: &str,
+) -> bool {
+    let same_version = [installed_version, update.current_version.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|v| version_compare::strip_brew_version_token(v) == update.available_version);
+    if !same_version {
+        return false;
+    }
+    let Some(build) = update.available_build.clone() else {
+        return true;
+    };
+    let installed_build =
+        crate::detection::bundle_reader::read_bundle(std::path::Path::new(app_path))
+            .and_then(|b| b.bundle_version);
+    if !version_compare::is_newer_build_number(installed_build.as_deref(), Some(&build)) {
+        return true;
+    }
+    update.available_version = version_compare::with_build(&update.available_version, &build);
+    false
+}
+
+/// Fill in the expected download size when the checker didn't report one, reusing
+/// a size measured on an earlier check of the same version before asking the server.
+pub async fn resolve_download_size(
+    db: &Arc<Mutex<Database>>,
+    http_client: &reqwest::Client,
+    app_id: i64,
+    update: &mut UpdateInfo,
+) {
+    if update.download_size.is_some() {
+        return;
+    }
+    let Some(url) = update.download_url.clone() else {
+        return;
+    };
+    let known = db.lock().await.known_download_size(app_id, &update.available_version, &url);
+    update.download_size = match known {
+        Some(size) => Some(size),
+        None => crate::utils::http_client::content_length(http_client, &url).await,
+    };
+}
+
+/// Apps checked at once during an update check.
+const CHECK_CONCURRENCY: usize = 10;
+
+fn emit_check_progress(
+    app_handle: &AppHandle,
+    checked: usize,
+    total: usize,
+    current: String,
+    phase: CheckPhase,
+    eta_secs: Option<u64>,
+) {
+    let _ = app_handle.emit(
+        "update-check-progress",
+        crate::models::UpdateCheckProgress {
+            checked,
+            total,
+            current_app: Some(current),
+            phase,
+            eta_secs,
+        },
+    );
+}
+
+/// Whether an update check is waiting for the GitHub rate limit to reset.
+static GITHUB_RECHECK_PENDING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Check again shortly after the GitHub rate limit resets at `reset_at` (Unix
+/// time), for the repos deferred this cycle. Those were checked longest ago, so
+/// the next cycle's budget goes to them first.
+fn recheck_after_github_reset(app_handle: &AppHandle, reset_at: i64) {
+    if GITHUB_RECHECK_PENDING.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        return;
+    }
+    let wait = (reset_at - chrono::Utc::now().timestamp()).max(0) as u64 + 10;
+    log::info!("Checking deferred GitHub repos again in {}s", wait);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+        GITHUB_RECHECK_PENDING.store(false, std::sync::atomic::Ordering::Relaxed);
+        let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+        let client = app_handle.state::<reqwest::Client>().inner().clone();
+        if let Err(e) = run_update_check(&app_handle, &db, &client).await {
+            log::warn!("Deferred update check failed: {}", e);
+        }
+    });
+}
+
+/// Start an update check in the background, e.g. from the tray or a global shortcut.
+pub fn spawn_update_check(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+        let client = app_handle.state::<reqwest::Client>().inner().clone();
+        if let Err(e) = run_update_check(&app_handle, &db, &client).await {
+            log::warn!("Update check failed: {}", e);
+        }
+    });
+}
+
 pub async fn run_update_check(
     app_handle: &AppHandle,
     db: &Arc<Mutex<Database>>,
     http_client: &reqwest::Client,
 ) -> AppResult<usize> {
+    // Offline: keep showing what the last check found and check again once back online
+    if offline::is_offline() {
+        offline::enqueue(app_handle, QueuedAction::UpdateCheck);
+        return db.lock().await.get_update_count();
+    }
+
     let start = std::time::Instant::now();
     let dispatcher = Arc::new(UpdateDispatcher::new());
 
-    // Reset GitHub rate-limit flag for this cycle
-    crate::updaters::github_releases::reset_rate_limit_flag();
-
-    let apps = {
+    let (apps, cache_release_notes, ignore_rules) = {
         let db = db.lock().await;
-        db.get_all_apps()?
+        let settings = load_settings_from_db(&db);
+        (db.get_all_apps()?, settings.cache_release_notes, settings.ignore_rules)
+    };
+    // Apps skipped this cycle: ignored individually, matched by an ignore rule, or Apple's own
+    let skipped = |app: &crate::models::AppSummary| {
+        app.is_ignored
+            || app.bundle_id.starts_with("com.apple.")
+            || ignore_rules.iter().any(|rule| rule.matches(&app.bundle_id, &app.install_source))
     };
 
     let total = apps.len();
     let checked = Arc::new(std::sync::atomic::AtomicUsize::new(0));
     let updates_found = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let security_found = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+    let timings = {
+        let db = db.lock().await;
+        CheckTimings::load(&db)
+    };
+    let has_brew = brew_path().is_some();
+    // Expected seconds for all per-app checks, from each app's install source
+    let app_checks_secs: f64 = apps
+        .iter()
+        .filter(|app| !skipped(app))
+        .map(|app| timings.app_secs(&app.install_source))
+        .sum();
+    let prelude_eta = |phase: CheckPhase| {
+        (!timings.is_empty())
+            .then(|| check_timing::eta_secs(timings.prelude_secs(phase, has_brew), app_checks_secs, CHECK_CONCURRENCY))
+    };
+    let mut new_timings = timings.clone();
 
     // Emit initial progress event immediately
-    let _ = app_handle.emit(
-        "update-check-progress",
-        crate::models::UpdateCheckProgress {
-            checked: 0,
-            total,
-            current_app: Some("Preparing...".to_string()),
-        },
-    );
+    emit_check_progress(app_handle, 0, total, tr("Preparing..."), CheckPhase::Preparing, prelude_eta(CheckPhase::Preparing));
 
-    // Emit progress: fetching Homebrew data
-    let _ = app_handle.emit(
-        "update-check-progress",
-        crate::models::UpdateCheckProgress {
-            checked: 0,
-            total,
-            current_app: Some("Fetching Homebrew data...".to_string()),
-        },
-    );
+    // Pick up new community mappings before any app is checked
+    crate::updaters::community_mappings::refresh(db, http_client).await;
 
     // Refresh the local Homebrew index so `brew outdated` sees the latest versions
     if let Some(brew) = brew_path() {
-        let _ = app_handle.emit(
-            "update-check-progress",
-            crate::models::UpdateCheckProgress {
-                checked: 0,
-                total,
-                current_app: Some("Updating Homebrew index...".to_string()),
-            },
+        emit_check_progress(
+            app_handle,
+            0,
+            total,
+            tr("Updating Homebrew index..."),
+            CheckPhase::BrewUpdate,
+            prelude_eta(CheckPhase::BrewUpdate),
         );
         let brew = brew.clone();
+        let phase_start = std::time::Instant::now();
         let _ = tokio::task::spawn_blocking(move || {
             let output = brew_command(&brew).arg("update").output();
             match output {
@@ -324,15 +657,52 @@ pub async fn run_update_check(
             }
         })
         .await;
+        new_timings.record_phase(CheckPhase::BrewUpdate, phase_start.elapsed());
     }
 
     // Pre-compute brew outdated, formulae, and cask index concurrently
-    let http_for_index = http_client.clone();
-    let (brew_outdated_res, brew_outdated_formulae_res, cask_index_res) = tokio::join!(
-        tokio::task::spawn_blocking(fetch_brew_outdated),
-        tokio::task::spawn_blocking(fetch_brew_outdated_formulae),
-        homebrew_api::fetch_cask_index(&http_for_index),
+    emit_check_progress(
+        app_handle,
+        0,
+        total,
+        tr("Fetching Homebrew data..."),
+        CheckPhase::BrewOutdated,
+        prelude_eta(CheckPhase::BrewOutdated),
     );
+    let index_done = std::sync::atomic::AtomicBool::new(false);
+    let brew_fetch = async {
+        let phase_start = std::time::Instant::now();
+        let results = tokio::join!(
+            tokio::task::spawn_blocking(fetch_brew_outdated),
+            tokio::task::spawn_blocking(fetch_brew_outdated_formulae),
+        );
+        if !index_done.load(std::sync::atomic::Ordering::Relaxed) {
+            emit_check_progress(
+                app_handle,
+                0,
+                total,
+                tr("Downloading Homebrew cask index..."),
+                CheckPhase::CaskIndex,
+                prelude_eta(CheckPhase::CaskIndex),
+            );
+        }
+        (results, phase_start.elapsed())
+    };
+    let http_for_index = http_client.clone();
+    let index_fetch = async {
+        let phase_start = std::time::Instant::now();
+        let index = homebrew_api::fetch_cask_index(&http_for_index).await;
+        index_done.store(true, std::sync::atomic::Ordering::Relaxed);
+        (index, phase_start.elapsed())
+    };
+    let (((brew_outdated_res, brew_outdated_formulae_res), brew_elapsed), (cask_index_res, index_elapsed)) =
+        tokio::join!(brew_fetch, index_fetch);
+    if has_brew {
+        new_timings.record_phase(CheckPhase::BrewOutdated, brew_elapsed);
+    }
+    if cask_index_res.is_some() {
+        new_timings.record_phase(CheckPhase::CaskIndex, index_elapsed);
+    }
 
     let brew_outdated: Arc<HashMap<String, BrewOutdatedCask>> =
         Arc::new(brew_outdated_res.unwrap_or_default());
@@ -374,69 +744,129 @@ pub async fn run_update_check(
         drop(db_guard);
     }
 
-    // Load GitHub repo mappings from database once for all apps
-    let github_mappings: HashMap<String, String> = {
+    // Load GitHub repo mappings and Electron feeds from database once for all apps
+    let (app_mappings, electron_feeds) = {
         let db_guard = db.lock().await;
-        db_guard.get_github_mappings()
+        (
+            Arc::new(db_guard.get_app_mappings()),
+            Arc::new(db_guard.get_electron_update_feeds()),
+        )
     };
 
-    let github_mappings = Arc::new(github_mappings);
-
-    let check_apps: Vec<_> = apps
+    let mut check_apps: Vec<_> = apps
         .iter()
-        .filter(|app| !app.is_ignored)
-        .filter(|app| !app.bundle_id.starts_with("com.apple."))
+        .filter(|app| !skipped(app))
         .collect();
+    // Check the apps used most recently first, so their updates show up soonest
+    check_apps.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at));
+
+    // GitHub repo per app: user mapping, then community feed, then the cask's homepage
+    let github_repos: Arc<HashMap<i64, String>> = Arc::new(
+        check_apps
+            .iter()
+            .filter_map(|app| {
+                let repo = app_mappings
+                    .get(&app.bundle_id)
+                    .and_then(|m| m.github_repo.clone())
+                    .or_else(|| {
+                        crate::updaters::community_mappings::lookup(&app.bundle_id)
+                            .and_then(|c| c.github_repo)
+                    })
+                    .or_else(|| {
+                        cask_index
+                            .as_ref()
+                            .and_then(|idx| {
+                                idx.lookup_github_repo(&app.bundle_id, std::path::Path::new(&app.app_path))
+                            })
+                            .map(String::from)
+                    })?;
+                Some((app.id, repo))
+            })
+            .collect(),
+    );
+    // Spend what's left of the GitHub rate limit on the repos checked longest ago
+    github_releases::plan_cycle(
+        check_apps
+            .iter()
+            .filter_map(|app| {
+                github_releases::repo_for(&app.bundle_id, github_repos.get(&app.id).map(String::as_str))
+            })
+            .collect(),
+    )
+    .await;
 
     let updated_app_ids: Arc<Mutex<std::collections::HashSet<i64>>> =
         Arc::new(Mutex::new(std::collections::HashSet::new()));
     let successfully_checked_ids: Arc<Mutex<std::collections::HashSet<i64>>> =
         Arc::new(Mutex::new(std::collections::HashSet::new()));
 
+    // App checks finished so far, in expected seconds, and measured time per source
+    let done_app_millis = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let app_samples: Arc<StdMutex<HashMap<String, (Duration, u32)>>> = Arc::new(StdMutex::new(HashMap::new()));
+    let timings = Arc::new(timings);
+
     stream::iter(check_apps)
-        .for_each_concurrent(10, |app| {
+        .for_each_concurrent(CHECK_CONCURRENCY, |app| {
             let dispatcher = dispatcher.clone();
             let app_handle = app_handle.clone();
             let db = db.clone();
             let http_client = http_client.clone();
             let checked = checked.clone();
             let updates_found = updates_found.clone();
+            let security_found = security_found.clone();
             let brew_outdated = brew_outdated.clone();
             let brew_outdated_formulae = brew_outdated_formulae.clone();
             let cask_index = cask_index.clone();
-            let github_mappings = github_mappings.clone();
+            let app_mappings = app_mappings.clone();
+            let electron_feeds = electron_feeds.clone();
+            let github_repos = github_repos.clone();
             let xcode_clt_installed = xcode_clt_installed;
             let updated_app_ids = updated_app_ids.clone();
             let successfully_checked_ids = successfully_checked_ids.clone();
+            let done_app_millis = done_app_millis.clone();
+            let app_samples = app_samples.clone();
+            let timings = timings.clone();
 
             async move {
                 let count = checked.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                let _ = app_handle.emit(
-                    "update-check-progress",
-                    crate::models::UpdateCheckProgress {
-                        checked: count,
-                        total,
-                        current_app: Some(app.display_name.clone()),
-                    },
+                let done_secs = done_app_millis.load(std::sync::atomic::Ordering::Relaxed) as f64 / 1000.0;
+                let eta = (!timings.is_empty()).then(|| {
+                    check_timing::eta_secs(0.0, (app_checks_secs - done_secs).max(0.0), CHECK_CONCURRENCY)
+                });
+                emit_check_progress(
+                    &app_handle,
+                    count,
+                    total,
+                    app.display_name.clone(),
+                    CheckPhase::AppChecks,
+                    eta,
                 );
+                let check_start = std::time::Instant::now();
 
                 let install_source = crate::models::AppSource::from_str(&app.install_source);
+                // User mappings win, then the community feed, then what we can work out
+                let mapping = app_mappings.get(&app.bundle_id).cloned().unwrap_or_default();
+                let community = crate::updaters::community_mappings::lookup(&app.bundle_id).unwrap_or_default();
                 let context = AppCheckContext {
-                    homebrew_cask_token: app.homebrew_cask_token.clone(),
-                    sparkle_feed_url: app.sparkle_feed_url.clone(),
+                    homebrew_cask_token: mapping.homebrew_cask_token
+                        .or(community.cask_token)
+                        .or_else(|| app.homebrew_cask_token.clone()),
+                    sparkle_feed_url: mapping.custom_feed_url
+                        .or(community.appcast_url)
+                        .or_else(|| app.sparkle_feed_url.clone()),
+                    electron_feed: electron_feeds.get(&app.bundle_id).cloned(),
                     obtained_from: app.obtained_from.clone(),
                     brew_outdated: Some(brew_outdated.clone()),
                     brew_outdated_formulae: Some(brew_outdated_formulae.clone()),
                     homebrew_cask_index: cask_index.clone(),
-                    github_repo: github_mappings.get(&app.bundle_id).cloned()
-                        .or_else(|| cask_index.as_ref()
-                            .and_then(|idx| idx.github_repos.get(&app.bundle_id.to_lowercase()).cloned())),
+                    github_repo: github_repos.get(&app.id).cloned(),
                     homebrew_formula_name: app.homebrew_formula_name.clone(),
                     xcode_clt_installed,
                     db: Some(db.clone()),
+                    negative_cache: true,
                 };
 
-                match dispatcher
+                let check_result = dispatcher
                     .check_update(
                         &app.bundle_id,
                         &app.app_path,
@@ -445,22 +875,26 @@ pub async fn run_update_check(
                         &http_client,
                         &context,
                     )
-                    .await
+                    .await;
                 {
-                    Ok(Some(update)) => {
+                    let mut samples = app_samples.lock().unwrap_or_else(|e| e.into_inner());
+                    let entry = samples.entry(app.install_source.clone()).or_default();
+                    entry.0 += check_start.elapsed();
+                    entry.1 += 1;
+                }
+                let expected_millis = (timings.app_secs(&app.install_source) * 1000.0) as u64;
+                done_app_millis.fetch_add(expected_millis, std::sync::atomic::Ordering::Relaxed);
+
+                match check_result {
+                    Ok(Some(mut update)) => {
                         successfully_checked_ids.lock().await.insert(app.id);
+                        // Read before the release notes may be dropped below
+                        let is_security = crate::updaters::macadmins_feed::is_security_update(&app.bundle_id, &update);
+                        if !cache_release_notes {
+                            update.release_notes = None;
+                        }
 
-                        let dominated = {
-                            let db_match = app.installed_version.as_ref()
-                                .map(|iv| update.available_version == *iv)
-                                .unwrap_or(false);
-                            let fresh_match = update.current_version.as_ref()
-                                .map(|cv| update.available_version == *cv)
-                                .unwrap_or(false);
-                            db_match || fresh_match
-                        };
-
-                        if dominated {
+                        if is_no_op_update(&mut update, app.installed_version.as_deref(), &app.app_path) {
                             log::info!(
                                 "Skipping no-op update for {}: available '{}' == installed",
                                 app.bundle_id, update.available_version,
@@ -476,11 +910,16 @@ pub async fn run_update_check(
                                 },
                             );
 
+                            resolve_download_size(&db, &http_client, app.id, &mut update).await;
                             {
                                 let db = db.lock().await;
                                 let _ = db.upsert_available_update(app.id, &update);
+                                let _ = db.set_update_security(app.id, &update.available_version, is_security);
                             }
                             updates_found.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            if is_security {
+                                security_found.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            }
                             updated_app_ids.lock().await.insert(app.id);
                         }
                     }
@@ -502,11 +941,23 @@ pub async fn run_update_check(
     ).await;
 
     // Check for macPlus self-update and emit event if available
-    crate::updaters::github_releases::reset_rate_limit_flag();
-    if let Some(info) = crate::commands::self_update::check_self_update_inner(http_client).await {
+    let channel = crate::commands::self_update::self_update_channel(app_handle).await;
+    if let Some(info) = crate::commands::self_update::check_self_update_inner(http_client, channel).await {
         let _ = app_handle.emit("self-update-available", &info);
     }
 
+    if let Some(reset_at) = github_releases::deferred_until() {
+        recheck_after_github_reset(app_handle, reset_at);
+    }
+
+    new_timings.record_apps(&app_samples.lock().unwrap_or_else(|e| e.into_inner()));
+    crate::utils::metrics::record_cycle(start.elapsed(), total);
+    {
+        let db_guard = db.lock().await;
+        new_timings.save(&db_guard);
+        crate::utils::metrics::flush(&db_guard);
+    }
+
     let found_this_cycle = updates_found.load(std::sync::atomic::Ordering::Relaxed);
     log::info!("Update check found {} new updates this cycle", found_this_cycle);
 
@@ -541,18 +992,8 @@ pub async fn run_update_check(
         }
 
         // Step 2: Purge updates where available_version now matches the (freshly updated)
-        // installed_version. Also matches comma-containing Homebrew versions where the
-        // numeric prefix equals the installed version (e.g. "1.1.3363,abc..." == "1.1.3363").
-        let purged = db_guard.conn.execute(
-            "DELETE FROM available_updates WHERE id IN (
-                SELECT au.id FROM available_updates au
-                JOIN apps a ON a.id = au.app_id
-                WHERE au.dismissed_at IS NULL
-                  AND (au.available_version = a.installed_version
-                       OR (au.available_version LIKE a.installed_version || ',%'))
-            )",
-            [],
-        ).unwrap_or(0);
+        // installed_version.
+        let purged = db_guard.purge_installed_updates().unwrap_or(0);
 
         // Step 3: Clear remaining stale updates for apps that were successfully checked
         // this cycle but received no update. Apps whose checkers errored are excluded
@@ -568,6 +1009,16 @@ pub async fn run_update_check(
             }
         }
 
+        // Step 4: Pending updates for apps an ignore rule now covers are no longer wanted
+        for app in apps.iter().filter(|app| {
+            !app.is_ignored && ignore_rules.iter().any(|rule| rule.matches(&app.bundle_id, &app.install_source))
+        }) {
+            cleared += db_guard.conn.execute(
+                "DELETE FROM available_updates WHERE app_id = ?1 AND dismissed_at IS NULL",
+                [app.id],
+            ).unwrap_or(0);
+        }
+
         if purged > 0 || cleared > 0 {
             log::info!(
                 "Post-cycle cleanup: {} version-matched purged, {} stale cleared",
@@ -596,68 +1047,46 @@ pub async fn run_update_check(
         load_settings_from_db(&db_guard)
     };
 
-    // Send native notification if updates were found and notifications are enabled
-    if found_this_cycle > 0 && settings.notification_on_updates {
-        use tauri_plugin_notification::NotificationExt;
-        let body = if found_this_cycle == 1 {
-            "1 app update available".to_string()
-        } else {
-            format!("{} app updates available", found_this_cycle)
-        };
-        let mut builder = app_handle
-            .notification()
-            .builder()
-            .title("macPlus")
-            .body(&body);
-        if settings.notification_sound {
-            builder = builder.sound("Glass");
-        }
-        match builder.show() {
-            Ok(_) => log::info!("Sent native notification: {} updates", found_this_cycle),
-            Err(e) => log::warn!("Failed to send notification: {}", e),
+    // Send native notification if updates were found and notifications are enabled,
+    // or a periodic digest instead when one is configured
+    if settings.notification_on_updates {
+        if let Some(period) = settings.notification_digest.period() {
+            send_digest_if_due(app_handle, db, &settings, period, db_count).await;
+        } else if found_this_cycle > 0 {
+            let security = security_found.load(std::sync::atomic::Ordering::Relaxed);
+            let body = if security > 0 {
+                tr_count(
+                    "{count} app update available, {security} with security fixes",
+                    "{count} app updates available, {security} with security fixes",
+                    found_this_cycle,
+                    &[("security", &security.to_string())],
+                )
+            } else {
+                tr_count(
+                    "{count} app update available",
+                    "{count} app updates available",
+                    found_this_cycle,
+                    &[],
+                )
+            };
+            notifications::send_update_notification(app_handle, &settings, &body, false);
         }
     }
 
-    // Update tray tooltip, icon, and menu item with update count
-    if let Some(tray) = app_handle.tray_by_id("main-tray") {
-        let tooltip = if settings.show_badge_count && db_count > 0 {
-            format!("macPlus — {} update{}", db_count, if db_count == 1 { "" } else { "s" })
-        } else {
-            "macPlus".to_string()
-        };
-        let _ = tray.set_tooltip(Some(&tooltip));
-
-        // Render tray icon — with numbered badge if enabled and updates available
-        let base_icon_path = app_handle.path().resolve("icons/tray-icon.png", tauri::path::BaseDirectory::Resource);
-        if let Ok(path) = base_icon_path {
-            if let Ok(base_bytes) = std::fs::read(&path) {
-                let icon_bytes = if settings.show_badge_count && db_count > 0 {
-                    crate::platform::tray_badge::render_tray_icon_with_badge(&base_bytes, db_count)
-                        .unwrap_or_else(|| base_bytes.clone())
-                } else if db_count > 0 {
-                    // Fallback: use static update icon when badge count is disabled
-                    let update_path = app_handle.path().resolve("icons/tray-icon-update.png", tauri::path::BaseDirectory::Resource);
-                    update_path.ok().and_then(|p| std::fs::read(p).ok()).unwrap_or(base_bytes.clone())
-                } else {
-                    base_bytes.clone()
-                };
-                if let Ok(icon) = tauri::image::Image::from_bytes(&icon_bytes) {
-                    let _ = tray.set_icon(Some(icon.to_owned()));
-                }
-            }
-        }
-    }
+    // Update tray visibility, tooltip and icon with the update count
+    crate::platform::tray_badge::update_tray_icon(app_handle, &settings, db_count);
 
-    // Update the tray menu item text
-    {
-        let state = app_handle.state::<crate::UpdateCountMenuItem>();
-        let text = if db_count > 0 {
-            format!("{} update{} available", db_count, if db_count == 1 { "" } else { "s" })
-        } else {
-            "No updates available".to_string()
-        };
-        let _ = state.0.set_text(&text);
-    }
+    // Mirror the count onto the Dock icon
+    crate::platform::dock_badge::set_dock_badge(
+        app_handle,
+        if settings.show_badge_count { db_count } else { 0 },
+    );
+
+    // Rebuild the tray menu with the pending-updates submenu
+    crate::platform::tray_menu::refresh_tray_menu(app_handle).await;
+
+    // Install what the auto-update policy allows
+    security_updates::check_finished();
 
     Ok(db_count)
 }
@@ -684,8 +1113,8 @@ async fn backfill_cask_tokens(
             continue;
         }
 
-        // Browser extensions must not be matched to Homebrew casks
-        if is_browser_extension(&app.bundle_id) {
+        // Browser extensions and blocklisted apps must not be matched to Homebrew casks
+        if is_cask_blocked(&app.bundle_id) {
             continue;
         }
 
@@ -709,6 +1138,131 @@ async fn backfill_cask_tokens(
     }
 }
 
+/// Re-detect one app without a full scan: re-read its bundle, work out where it
+/// was installed from, refresh its icon and re-match it to a cask and GitHub repo.
+pub async fn rescan_app(
+    app_handle: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    bundle_id: &str,
+) -> AppResult<AppDetail> {
+    let detail = db.lock().await.get_app_detail(bundle_id)?;
+    if detail.app_path.is_empty() || detail.install_source == AppSource::HomebrewFormula.as_str() {
+        return Err(AppError::Custom(format!(
+            "{} has no app bundle to re-detect; run a full scan instead",
+            detail.display_name
+        )));
+    }
+    let app_path = std::path::PathBuf::from(&detail.app_path);
+    let bundle = crate::detection::bundle_reader::read_bundle(&app_path).ok_or_else(|| {
+        AppError::Custom(format!(
+            "{} is no longer at {}; run a full scan to find it",
+            detail.display_name, detail.app_path
+        ))
+    })?;
+    if bundle.bundle_id != bundle_id {
+        return Err(AppError::Custom(format!(
+            "A different app is now at {}; run a full scan instead",
+            detail.app_path
+        )));
+    }
+
+    // Same precedence as the deduplicator: App Store receipt, then Homebrew, then direct
+    let client = app_handle.state::<reqwest::Client>();
+    let index = homebrew_api::fetch_cask_index(client.inner()).await;
+    let cask_token = detail.homebrew_cask_token.clone().or_else(|| {
+        index
+            .as_ref()
+            .filter(|_| !is_cask_blocked(bundle_id))
+            .and_then(|idx| idx.lookup_token(bundle_id, &app_path))
+            .map(String::from)
+    });
+    let brew_installed = cask_token.as_deref().is_some_and(crate::utils::brew::is_cask_installed);
+    let install_source = match crate::detection::bundle_reader::detect_install_source(&app_path) {
+        AppSource::MacAppStore => AppSource::MacAppStore,
+        _ if brew_installed => AppSource::Homebrew,
+        source => source,
+    };
+
+    let app = DetectedApp {
+        bundle_id: bundle.bundle_id,
+        display_name: bundle.display_name,
+        app_path: bundle.app_path,
+        installed_version: bundle.installed_version,
+        bundle_version: bundle.bundle_version,
+        obtained_from: match install_source {
+            AppSource::MacAppStore => Some("mac_app_store".into()),
+            AppSource::Homebrew => Some("homebrew".into()),
+            _ => None,
+        },
+        install_source,
+        homebrew_cask_token: cask_token,
+        architectures: bundle.architectures,
+        sparkle_feed_url: bundle.sparkle_feed_url,
+        mas_app_id: detail.mas_app_id.clone(),
+        homebrew_formula_name: None,
+        helper_apps: detail.helper_apps.clone(),
+    };
+    let last_used = crate::detection::spotlight::last_used_dates(&[app.app_path.clone()]).await;
+
+    // Replace the cached icon in case the app changed it
+    let icon_path = match app_handle.path().app_cache_dir() {
+        Ok(cache_dir) => {
+            let icons_dir = cache_dir.join("icons");
+            let _ = std::fs::remove_file(icons_dir.join(format!("{}.png", bundle_id)));
+            let icon_app_path = app_path.clone();
+            let icon_bundle_id = bundle_id.to_string();
+            let display_name = app.display_name.clone();
+            let task = tokio::task::spawn_blocking(move || {
+                std::fs::create_dir_all(&icons_dir)?;
+                icon_extractor::icon_or_monogram(&icon_app_path, &icon_bundle_id, &display_name, &icons_dir)
+                    .map(Some)
+            });
+            match tokio::time::timeout(Duration::from_secs(10), task).await {
+                Ok(Ok(Ok(path))) => path,
+                _ => {
+                    log::debug!("Icon extraction failed for {}", bundle_id);
+                    None
+                }
+            }
+        }
+        Err(_) => None,
+    };
+
+    let mapping = db.lock().await.get_app_mappings().remove(bundle_id).unwrap_or_default();
+    let community = crate::updaters::community_mappings::lookup(bundle_id).unwrap_or_default();
+    let github_repo = mapping.github_repo.or(community.github_repo).or_else(|| {
+        index
+            .as_ref()
+            .and_then(|idx| idx.lookup_github_repo(bundle_id, &app_path))
+            .map(String::from)
+    });
+
+    let db_guard = db.lock().await;
+    let app_id = db_guard.upsert_app(&app)?;
+    let _ = db_guard.set_electron_update_feed(
+        bundle_id,
+        crate::updaters::electron::read_update_feed(&app_path).as_ref(),
+    );
+    if let Some(Some(last_used_at)) = last_used.first() {
+        let _ = db_guard.update_last_used(&app.app_path, last_used_at);
+    }
+    if let Some(ref icon_path) = icon_path {
+        let _ = db_guard.update_icon_cache_path(bundle_id, icon_path);
+    }
+    if let Some(ref repo) = github_repo {
+        let _ = db_guard.upsert_update_source(app_id, "github", Some(&format!("https://github.com/{}", repo)), false);
+    }
+    log::info!(
+        "Re-detected {}: {} ({}), cask {:?}, GitHub {:?}",
+        bundle_id,
+        app.installed_version.as_deref().unwrap_or("unknown version"),
+        app.install_source.as_str(),
+        app.homebrew_cask_token,
+        github_repo
+    );
+    db_guard.get_app_detail(bundle_id)
+}
+
 pub fn start_periodic_checks(
     app_handle: AppHandle,
     db: Arc<Mutex<Database>>,
@@ -726,6 +1280,7 @@ pub fn start_periodic_checks(
                 Ok(count) => log::info!("Periodic check found {} updates", count),
                 Err(e) => log::warn!("Periodic check failed: {}", e),
             }
+            prune_history(&db).await;
 
             // Re-read interval from settings for the next cycle (hot-reload)
             let new_interval = {
@@ -755,16 +1310,34 @@ pub fn start_self_update_poller(
         tokio::time::sleep(Duration::from_secs(30)).await;
 
         loop {
-            crate::updaters::github_releases::reset_rate_limit_flag();
-
+            if offline::is_offline() {
+                tokio::time::sleep(Duration::from_secs(5 * 60)).await;
+                continue;
+            }
+            let channel = crate::commands::self_update::self_update_channel(&app_handle).await;
             if let Some(info) =
-                crate::commands::self_update::check_self_update_inner(&http_client).await
+                crate::commands::self_update::check_self_update_inner(&http_client, channel).await
             {
                 log::info!(
                     "Self-update poller: v{} available (current: v{})",
                     info.available_version, info.current_version
                 );
                 let _ = app_handle.emit("self-update-available", &info);
+
+                let settings = {
+                    let db = app_handle.state::<Arc<Mutex<Database>>>();
+                    let db_guard = db.lock().await;
+                    load_settings_from_db(&db_guard)
+                };
+                // While Focus holds automatic updates, staging waits for a later poll
+                if settings.auto_self_update && !notifications::should_defer_auto_update(&settings, false) {
+                    match crate::commands::self_update::stage_self_update(&info).await {
+                        Ok(()) => {
+                            let _ = app_handle.emit("self-update-staged", &info);
+                        }
+                        Err(e) => log::warn!("Self-update poller: staging failed: {}", e),
+                    }
+                }
             }
 
             let _ = tokio::time::timeout(
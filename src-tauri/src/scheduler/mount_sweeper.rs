@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use crate::utils::disk_image;
+
+/// How often disk images left mounted by failed or interrupted updates are detached.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Periodically detach macPlus disk images no update is using, so a failed
+/// extraction doesn't leave a phantom volume in Finder. Mounts left by an earlier
+/// run are first offered for repair at launch and swept at the next interval.
+pub fn start_mount_sweeper() {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+            let detached = tokio::task::spawn_blocking(disk_image::sweep_stale_mounts)
+                .await
+                .unwrap_or(0);
+            if detached > 0 {
+                log::info!("Detached {} stale disk images", detached);
+            }
+        }
+    });
+}
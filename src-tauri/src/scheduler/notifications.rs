@@ -0,0 +1,93 @@
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::models::{AppSettings, NotificationSound};
+use crate::platform::focus;
+
+/// Notification group for update notifications, so Notification Center stacks them
+/// instead of listing each one separately.
+pub const UPDATES_NOTIFICATION_GROUP: &str = "com.macplus.updates";
+
+/// Most notifications kept while Focus is on; older ones are dropped.
+const MAX_DEFERRED: usize = 5;
+
+/// How often the flusher checks whether Focus has ended.
+const FOCUS_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Notifications held back while Focus was on: (body, sound).
+static DEFERRED: StdMutex<Vec<(String, NotificationSound)>> = StdMutex::new(Vec::new());
+
+fn show(app_handle: &AppHandle, body: &str, sound: NotificationSound) {
+    let mut builder = app_handle
+        .notification()
+        .builder()
+        .title("macPlus")
+        .body(body)
+        .group(UPDATES_NOTIFICATION_GROUP);
+    if let Some(name) = sound.sound_name() {
+        builder = builder.sound(name);
+    }
+    if let Err(e) = builder.show() {
+        log::warn!("Failed to send notification: {}", e);
+    }
+}
+
+/// Whether non-critical work should wait for Focus to end.
+fn focus_blocks(enabled: bool, critical: bool) -> bool {
+    enabled && !critical && focus::is_focus_active()
+}
+
+/// Send an update notification, or hold it until Focus ends when the user asked
+/// macPlus to respect Focus. `critical` (security updates) bypasses Focus.
+/// Callers are responsible for checking `notification_on_updates` and the
+/// notification policy.
+pub fn send_update_notification(app_handle: &AppHandle, settings: &AppSettings, body: &str, critical: bool) {
+    if focus_blocks(settings.defer_notifications_during_focus, critical) {
+        let mut deferred = DEFERRED.lock().unwrap_or_else(|e| e.into_inner());
+        deferred.retain(|(b, _)| b != body);
+        deferred.push((body.to_string(), settings.notification_policy.sound));
+        let overflow = deferred.len().saturating_sub(MAX_DEFERRED);
+        deferred.drain(..overflow);
+        log::info!("Focus is on — deferring notification: {}", body);
+        return;
+    }
+    show(app_handle, body, settings.notification_policy.sound);
+}
+
+/// Whether an automatic update (a scheduled update, which relaunches apps, or the
+/// background download of macPlus's own update) should wait until Focus ends.
+/// `critical` (security updates) bypasses Focus.
+pub fn should_defer_auto_update(settings: &AppSettings, critical: bool) -> bool {
+    focus_blocks(settings.defer_auto_updates_during_focus, critical)
+}
+
+/// Poll Focus state and deliver held notifications once it ends.
+pub fn start_deferred_notification_flusher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(FOCUS_POLL_INTERVAL).await;
+
+            let pending = DEFERRED.lock().map_or(false, |d| !d.is_empty());
+            if !pending {
+                continue;
+            }
+            let active = tokio::task::spawn_blocking(focus::is_focus_active)
+                .await
+                .unwrap_or(false);
+            if active {
+                continue;
+            }
+
+            let deferred: Vec<(String, NotificationSound)> = {
+                let mut guard = DEFERRED.lock().unwrap_or_else(|e| e.into_inner());
+                std::mem::take(&mut *guard)
+            };
+            log::info!("Focus ended — sending {} deferred notification(s)", deferred.len());
+            for (body, sound) in deferred {
+                show(&app_handle, &body, sound);
+            }
+        }
+    });
+}
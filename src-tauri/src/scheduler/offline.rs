@@ -0,0 +1,119 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::{OfflineState, QueuedAction};
+
+/// How often connectivity is checked while online.
+const ONLINE_POLL_INTERVAL: Duration = Duration::from_secs(120);
+
+/// How often connectivity is checked while offline, so queued actions run soon
+/// after the connection comes back.
+const OFFLINE_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Set while none of the update sources can be reached.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Update checks and updates requested while offline, oldest first.
+static QUEUE: StdMutex<Vec<QueuedAction>> = StdMutex::new(Vec::new());
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+pub fn state() -> OfflineState {
+    OfflineState {
+        offline: is_offline(),
+        queued: QUEUE.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
+fn emit_state(app_handle: &AppHandle) {
+    let _ = app_handle.emit("offline-state", state());
+}
+
+/// Queue `action` to run when connectivity returns. Queuing the same action
+/// twice runs it once.
+pub fn enqueue(app_handle: &AppHandle, action: QueuedAction) {
+    {
+        let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+        if queue.contains(&action) {
+            return;
+        }
+        log::info!("Offline: queued {:?}", action);
+        queue.push(action);
+    }
+    emit_state(app_handle);
+}
+
+/// Record whether the update sources are reachable. Going back online runs
+/// everything queued in the meantime.
+pub fn set_offline(app_handle: &AppHandle, offline: bool) {
+    if OFFLINE.swap(offline, Ordering::Relaxed) == offline {
+        return;
+    }
+    if offline {
+        log::warn!("Offline: no update source is reachable, queuing checks and updates");
+    } else {
+        log::info!("Back online");
+    }
+    emit_state(app_handle);
+    if !offline {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move { flush(&app_handle).await });
+    }
+}
+
+/// Run the queued updates, then a queued update check.
+async fn flush(app_handle: &AppHandle) {
+    let queued = std::mem::take(&mut *QUEUE.lock().unwrap_or_else(|e| e.into_inner()));
+    if queued.is_empty() {
+        return;
+    }
+    emit_state(app_handle);
+    log::info!("Back online: running {} queued actions", queued.len());
+
+    let bundle_ids: Vec<String> = queued
+        .iter()
+        .filter_map(|action| match action {
+            QueuedAction::Update { bundle_id } => Some(bundle_id.clone()),
+            QueuedAction::UpdateCheck => None,
+        })
+        .collect();
+    if !bundle_ids.is_empty() {
+        let db = app_handle.state::<Arc<Mutex<Database>>>();
+        if let Err(e) =
+            crate::commands::execute::execute_bulk_update(bundle_ids, app_handle.clone(), db).await
+        {
+            log::warn!("Queued updates failed: {}", e);
+        }
+    }
+
+    if queued.contains(&QueuedAction::UpdateCheck) {
+        let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+        let client = app_handle.state::<reqwest::Client>().inner().clone();
+        if let Err(e) = super::run_update_check(app_handle, &db, &client).await {
+            log::warn!("Queued update check failed: {}", e);
+        }
+    }
+}
+
+/// Check connectivity in the background, switching offline mode on and off.
+pub fn start_connectivity_monitor(app_handle: AppHandle, http_client: reqwest::Client) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let status = crate::commands::system::check_connectivity_inner(&http_client).await;
+            set_offline(&app_handle, status.overall == "disconnected");
+            let interval = if is_offline() {
+                OFFLINE_POLL_INTERVAL
+            } else {
+                ONLINE_POLL_INTERVAL
+            };
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
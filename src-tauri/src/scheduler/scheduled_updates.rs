@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveTime, TimeZone};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::utils::i18n::tr_args;
+
+/// How often the runner looks for scheduled updates that are due.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The next time the clock shows `time` ("HH:MM"), after `now`: later today,
+/// or tomorrow if that time has passed.
+pub(crate) fn next_run_at<Tz: TimeZone>(now: &DateTime<Tz>, time: &str) -> Option<DateTime<Tz>> {
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+    let today = now.date_naive();
+    [today, today.succ_opt()?]
+        .into_iter()
+        .filter_map(|day| {
+            now.timezone()
+                .from_local_datetime(&day.and_time(time))
+                .earliest()
+        })
+        .find(|at| at > now)
+}
+
+/// `next_run_at` in the local time zone.
+pub fn next_local_run_at(time: &str) -> Option<DateTime<Local>> {
+    next_run_at(&Local::now(), time)
+}
+
+/// Send the current list of scheduled updates to the UI.
+pub fn emit_changed(app_handle: &AppHandle, db: &Database) {
+    if let Ok(scheduled) = db.get_scheduled_updates() {
+        let _ = app_handle.emit("scheduled-updates-changed", scheduled);
+    }
+}
+
+/// Run scheduled updates once they're due, then notify with the results. Updates
+/// whose time passed while macPlus wasn't running run soon after it starts.
+pub fn start_scheduled_update_runner(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+            let due = {
+                let db_guard = db.lock().await;
+                // Updates due during Focus stay due and run once it ends
+                let settings = super::load_settings_from_db(&db_guard);
+                if super::notifications::should_defer_auto_update(&settings, false) {
+                    continue;
+                }
+                let mut due = match db_guard.take_due_scheduled_updates() {
+                    Ok(due) if !due.is_empty() => due,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        log::warn!("Failed to read scheduled updates: {}", e);
+                        continue;
+                    }
+                };
+                emit_changed(&app_handle, &db_guard);
+                // Skip apps that were updated some other way in the meantime
+                due.retain(|bundle_id| {
+                    db_guard
+                        .get_app_detail(bundle_id)
+                        .is_ok_and(|detail| detail.available_update.is_some())
+                });
+                due
+            };
+            if due.is_empty() {
+                continue;
+            }
+
+            log::info!("Running {} scheduled updates", due.len());
+            let results = match crate::commands::execute::execute_bulk_update(
+                due,
+                app_handle.clone(),
+                app_handle.state(),
+            )
+            .await
+            {
+                Ok(results) => results,
+                Err(e) => {
+                    log::warn!("Scheduled updates failed: {}", e);
+                    continue;
+                }
+            };
+
+            let succeeded = results.iter().filter(|r| r.success).count();
+            let failed = results
+                .iter()
+                .filter(|r| !r.success && !r.delegated)
+                .count();
+            let settings = {
+                let db_guard = db.lock().await;
+                super::load_settings_from_db(&db_guard)
+            };
+            let notify = settings.notification_on_updates
+                && settings
+                    .notification_policy
+                    .notifies_update_result(failed == 0);
+            if notify && (succeeded > 0 || failed > 0) {
+                let body = tr_args(
+                    "Scheduled updates finished: {updated} updated, {failed} failed",
+                    &[
+                        ("updated", &succeeded.to_string()),
+                        ("failed", &failed.to_string()),
+                    ],
+                );
+                super::notifications::send_update_notification(
+                    &app_handle,
+                    &settings,
+                    &body,
+                    false,
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate};
+
+    #[test]
+    fn runs_tonight_or_tomorrow() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let at = |d: u32, h: u32, m: u32| {
+            tz.from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 3, d)
+                    .unwrap()
+                    .and_hms_opt(h, m, 0)
+                    .unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(next_run_at(&at(10, 21, 30), "02:00"), Some(at(11, 2, 0)));
+        assert_eq!(next_run_at(&at(11, 1, 15), "02:00"), Some(at(11, 2, 0)));
+        assert_eq!(next_run_at(&at(11, 2, 0), "02:00"), Some(at(12, 2, 0)));
+        assert_eq!(next_run_at(&at(10, 21, 30), "23:45"), Some(at(10, 23, 45)));
+        assert_eq!(next_run_at(&at(10, 21, 30), "25:00"), None);
+        assert_eq!(next_run_at(&at(10, 21, 30), "tonight"), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::{Mutex, Notify};
+
+use crate::db::Database;
+use crate::models::AutoUpdatePolicy;
+use crate::utils::i18n::tr_args;
+
+/// Signalled at the end of each update check.
+fn check_finished_signal() -> &'static Notify {
+    static SIGNAL: OnceLock<Notify> = OnceLock::new();
+    SIGNAL.get_or_init(Notify::new)
+}
+
+/// (bundle ID, version) pairs already tried this session, so a failing update
+/// isn't retried, and doesn't ask for a password again, after every check.
+static ATTEMPTED: StdMutex<Option<HashSet<(String, String)>>> = StdMutex::new(None);
+
+/// Let the runner look for security updates to install.
+pub fn check_finished() {
+    check_finished_signal().notify_one();
+}
+
+/// Install pending security updates after each update check when the auto-update
+/// policy is `SecurityOnly`. Not started in headless mode, which updates by itself.
+pub fn start_security_update_runner(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check_finished_signal().notified().await;
+            apply_security_updates(&app_handle).await;
+        }
+    });
+}
+
+async fn apply_security_updates(app_handle: &AppHandle) {
+    let db = app_handle.state::<Arc<Mutex<Database>>>().inner().clone();
+    let (settings, apps) = {
+        let db_guard = db.lock().await;
+        (
+            super::load_settings_from_db(&db_guard),
+            db_guard.get_all_apps(),
+        )
+    };
+    if settings.auto_update_policy != AutoUpdatePolicy::SecurityOnly {
+        return;
+    }
+    let apps = match apps {
+        Ok(apps) => apps,
+        Err(e) => {
+            log::warn!("Failed to read apps for security updates: {}", e);
+            return;
+        }
+    };
+
+    // Paid upgrades wait for the user to confirm the license, like in "Update All"
+    let due: Vec<String> = {
+        let mut attempted = ATTEMPTED.lock().unwrap_or_else(|e| e.into_inner());
+        let attempted = attempted.get_or_insert_with(HashSet::new);
+        apps.into_iter()
+            .filter(|app| {
+                app.has_update && app.is_security_update && !app.is_ignored && !app.needs_license
+            })
+            .filter(|app| {
+                let version = app.available_version.clone().unwrap_or_default();
+                attempted.insert((app.bundle_id.clone(), version))
+            })
+            .map(|app| app.bundle_id)
+            .collect()
+    };
+    if due.is_empty() {
+        return;
+    }
+
+    log::info!("Installing {} security updates automatically", due.len());
+    let results = match crate::commands::execute::execute_bulk_update(
+        due,
+        app_handle.clone(),
+        app_handle.state(),
+    )
+    .await
+    {
+        Ok(results) => results,
+        Err(e) => {
+            log::warn!("Automatic security updates failed: {}", e);
+            return;
+        }
+    };
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let failed = results
+        .iter()
+        .filter(|r| !r.success && !r.delegated)
+        .count();
+    let notify = settings.notification_on_updates
+        && settings
+            .notification_policy
+            .notifies_update_result(failed == 0);
+    if notify && (succeeded > 0 || failed > 0) {
+        let body = tr_args(
+            "Security updates installed automatically: {updated} updated, {failed} failed",
+            &[
+                ("updated", &succeeded.to_string()),
+                ("failed", &failed.to_string()),
+            ],
+        );
+        super::notifications::send_update_notification(app_handle, &settings, &body, true);
+    }
+}
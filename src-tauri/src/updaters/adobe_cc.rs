@@ -83,7 +83,7 @@ impl UpdateChecker for AdobeCCChecker {
         let cc_cache_exists = home.as_ref().map_or(false, |h| {
             h.join("Library/Application Support/Adobe").is_dir()
         });
-        let rum_exists = Path::new("/usr/local/bin/RemoteUpdateManager").exists();
+        let rum_exists = Path::new(RUM_PATH).exists();
         let has_brew_index = context.homebrew_cask_index.is_some();
         let has_cask_token = context.homebrew_cask_token.is_some()
             || (has_brew_index && context.homebrew_cask_index.as_ref()
@@ -160,6 +160,8 @@ impl UpdateChecker for AdobeCCChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: None,
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
             }
@@ -208,6 +210,8 @@ impl UpdateChecker for AdobeCCChecker {
                                 release_notes: None,
                                 is_paid_upgrade: false,
                                 notes: Some("Update available via Homebrew".to_string()),
+                                download_size: None,
+                                available_build: None,
                             }));
                         }
                     }
@@ -243,6 +247,8 @@ impl UpdateChecker for AdobeCCChecker {
                 release_notes: None,
                 is_paid_upgrade: false,
                 notes: None,
+                download_size: None,
+                available_build: None,
             }));
         }
 
@@ -308,6 +314,8 @@ impl AdobeCCChecker {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes: Some("Update detected via cask SHA change — reinstall via Homebrew or Creative Cloud".to_string()),
+                    download_size: None,
+                    available_build: None,
                 }))
             }
             CaskShaResult::NoCheck => {
@@ -373,8 +381,11 @@ fn extract_xml_element(xml: &str, tag: &str) -> Option<String> {
     }
 }
 
+/// Where Adobe's Remote Update Manager CLI is installed.
+pub const RUM_PATH: &str = "/usr/local/bin/RemoteUpdateManager";
+
 /// Map Adobe bundle IDs to SAP codes used in CC Desktop's update cache.
-fn bundle_to_sap_code(bundle_id: &str) -> Option<&str> {
+pub(crate) fn bundle_to_sap_code(bundle_id: &str) -> Option<&str> {
     match bundle_id {
         "com.adobe.Photoshop" => Some("PHSP"),
         "com.adobe.Illustrator" => Some("ILST"),
@@ -731,6 +742,8 @@ fn try_json_files_in_dir(dir: &Path, bundle_id: &str, current_version: &str) ->
                 release_notes: None,
                 is_paid_upgrade: false,
                 notes: Some("Update available via Creative Cloud Desktop".to_string()),
+                download_size: None,
+                available_build: None,
             });
         } else {
             log::debug!(
@@ -746,7 +759,7 @@ fn try_json_files_in_dir(dir: &Path, bundle_id: &str, current_version: &str) ->
 /// Check Adobe's Remote Update Manager (RUM) for available updates.
 /// RUM is Adobe's CLI tool typically at /usr/local/bin/RemoteUpdateManager.
 async fn check_rum_updates(bundle_id: &str, current_version: &str) -> Option<UpdateInfo> {
-    let rum_path = Path::new("/usr/local/bin/RemoteUpdateManager");
+    let rum_path = Path::new(RUM_PATH);
     if !rum_path.exists() {
         log::debug!("Adobe CC: RUM not found at {}", rum_path.display());
         return None;
@@ -772,7 +785,7 @@ async fn check_rum_updates(bundle_id: &str, current_version: &str) -> Option<Upd
 
 /// Run the RUM CLI and parse its output (synchronous, called via spawn_blocking).
 fn run_rum_check(bundle_id: &str, current_version: &str) -> Option<UpdateInfo> {
-    let output = match std::process::Command::new("/usr/local/bin/RemoteUpdateManager")
+    let output = match std::process::Command::new(RUM_PATH)
         .arg("--action=list")
         .output()
     {
@@ -817,6 +830,8 @@ fn run_rum_check(bundle_id: &str, current_version: &str) -> Option<UpdateInfo> {
                             release_notes: None,
                             is_paid_upgrade: false,
                             notes: Some("Update available (detected via Adobe Remote Update Manager)".to_string()),
+                            download_size: None,
+                            available_build: None,
                         });
                     }
                 }
@@ -856,6 +871,8 @@ fn run_rum_check(bundle_id: &str, current_version: &str) -> Option<UpdateInfo> {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes: Some("Update available (detected via Adobe Remote Update Manager)".to_string()),
+                    download_size: None,
+                    available_build: None,
                 });
             }
         }
@@ -882,6 +899,8 @@ fn run_rum_check(bundle_id: &str, current_version: &str) -> Option<UpdateInfo> {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes: Some("Update available (detected via Adobe Remote Update Manager)".to_string()),
+                    download_size: None,
+                    available_build: None,
                 });
             }
         }
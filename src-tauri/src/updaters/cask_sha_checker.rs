@@ -2,7 +2,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::utils::http_client::APP_USER_AGENT;
+use crate::utils::http_client::{self, APP_USER_AGENT};
 
 /// Result of a SHA-256 change detection check for a "latest" cask.
 #[derive(Debug, Clone)]
@@ -39,12 +39,7 @@ pub async fn check_cask_sha(
         first_letter, cask_token
     );
 
-    let resp = match client
-        .get(&url)
-        .header("User-Agent", APP_USER_AGENT)
-        .send()
-        .await
-    {
+    let resp = match http_client::send(client.get(&url).header("User-Agent", APP_USER_AGENT)).await {
         Ok(r) => r,
         Err(e) => return CaskShaResult::Error(format!("fetch failed: {}", e)),
     };
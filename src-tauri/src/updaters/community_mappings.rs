@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::version_compare::VersionScheme;
+use crate::db::Database;
+use crate::utils::{codesign, http_client};
+
+/// Community-maintained overrides, published in the project repo alongside a
+/// minisign signature (`<url>.sig`) made with the release signing key.
+const FEED_URL: &str =
+    "https://raw.githubusercontent.com/smallsimplesuper/macplus/main/mappings/community.json";
+
+/// Settings-table key the last verified feed is stored under.
+const FEED_KEY: &str = "community_mappings";
+
+/// How often the feed is fetched again.
+const REFRESH_HOURS: i64 = 12;
+
+/// Where to look for updates for one app, overriding what macPlus works out itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommunityMapping {
+    /// GitHub "owner/repo" slug
+    pub github_repo: Option<String>,
+    pub appcast_url: Option<String>,
+    pub cask_token: Option<String>,
+    /// How the app's versions compare, when the usual way gets them wrong
+    pub version_scheme: Option<VersionScheme>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MappingFeed {
+    /// Increases with every published change; older feeds are rejected
+    pub version: u64,
+    pub mappings: HashMap<String, CommunityMapping>,
+}
+
+/// The stored feed plus when it was last fetched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct CachedFeed {
+    feed: MappingFeed,
+    fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn current() -> &'static RwLock<Arc<MappingFeed>> {
+    static FEED: OnceLock<RwLock<Arc<MappingFeed>>> = OnceLock::new();
+    FEED.get_or_init(|| RwLock::new(Arc::new(MappingFeed::default())))
+}
+
+/// Version of the feed in use, 0 before one was loaded.
+pub fn feed_version() -> u64 {
+    current().read().unwrap_or_else(|e| e.into_inner()).version
+}
+
+/// The community mapping for an app, if the feed has one.
+pub fn lookup(bundle_id: &str) -> Option<CommunityMapping> {
+    current().read().unwrap_or_else(|e| e.into_inner()).mappings.get(bundle_id).cloned()
+}
+
+fn load_cached(db: &Database) -> CachedFeed {
+    db.conn
+        .query_row("SELECT value FROM settings WHERE key = ?1", [FEED_KEY], |row| row.get::<_, String>(0))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_cached(db: &Database, cached: &CachedFeed) {
+    let Ok(json) = serde_json::to_string(cached) else {
+        return;
+    };
+    let _ = db.conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, datetime('now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+        rusqlite::params![FEED_KEY, json],
+    );
+}
+
+/// Make the last verified feed available to lookups. Called once at startup.
+pub fn load(db: &Database) {
+    let cached = load_cached(db);
+    if !cached.feed.mappings.is_empty() {
+        log::info!(
+            "Community mappings: v{} with {} apps",
+            cached.feed.version,
+            cached.feed.mappings.len()
+        );
+    }
+    *current().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(cached.feed);
+}
+
+/// Check `body` against its minisign signature and parse it.
+fn verify_feed(body: &[u8], signature_b64: &str, pubkey: &str) -> Result<MappingFeed, String> {
+    codesign::verify_minisign(body, signature_b64, pubkey)?;
+    serde_json::from_slice(body).map_err(|e| format!("invalid JSON: {}", e))
+}
+
+async fn fetch_bytes(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let resp = http_client::send(client.get(url)).await.map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("status {}", resp.status()));
+    }
+    resp.bytes().await.map(|b| b.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Fetch the feed when the stored copy is older than the refresh interval, and
+/// keep it if the signature checks out and it's newer than what we have. Builds
+/// without the release signing key can't verify the feed, so never sync it.
+pub async fn refresh(db: &Arc<Mutex<Database>>, client: &reqwest::Client) {
+    let Some(pubkey) = crate::commands::self_update::UPDATER_PUBKEY else {
+        return;
+    };
+    let mut cached = load_cached(&*db.lock().await);
+    if cached.fetched_at.is_some_and(|at| chrono::Utc::now() - at < chrono::Duration::hours(REFRESH_HOURS)) {
+        return;
+    }
+
+    let sig_url = format!("{}.sig", FEED_URL);
+    let (body, signature) = match tokio::join!(fetch_bytes(client, FEED_URL), fetch_bytes(client, &sig_url)) {
+        (Ok(body), Ok(signature)) => (body, String::from_utf8_lossy(&signature).to_string()),
+        (Err(e), _) | (_, Err(e)) => {
+            log::warn!("Community mappings: fetch failed: {}", e);
+            return;
+        }
+    };
+    let feed = match verify_feed(&body, &signature, pubkey) {
+        Ok(feed) => feed,
+        Err(e) => {
+            log::warn!("Community mappings: rejected feed: {}", e);
+            return;
+        }
+    };
+
+    cached.fetched_at = Some(chrono::Utc::now());
+    if feed.version > cached.feed.version {
+        log::info!(
+            "Community mappings: updated to v{} ({} apps)",
+            feed.version,
+            feed.mappings.len()
+        );
+        *current().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(feed.clone());
+        cached.feed = feed;
+    } else if feed.version < cached.feed.version {
+        log::warn!(
+            "Community mappings: ignoring v{}, older than stored v{}",
+            feed.version,
+            cached.feed.version
+        );
+    }
+    save_cached(&*db.lock().await, &cached);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_feed_with_partial_mappings() {
+        let json = r#"{
+            "version": 3,
+            "mappings": {
+                "com.example.Foo": { "githubRepo": "example/foo" },
+                "com.example.Bar": { "appcastUrl": "https://example.com/bar.xml", "caskToken": "bar", "note": "x" }
+            }
+        }"#;
+        let feed: MappingFeed = serde_json::from_str(json).unwrap();
+        assert_eq!(feed.version, 3);
+        assert_eq!(feed.mappings["com.example.Foo"].github_repo.as_deref(), Some("example/foo"));
+        assert_eq!(feed.mappings["com.example.Foo"].cask_token, None);
+        assert_eq!(feed.mappings["com.example.Bar"].cask_token.as_deref(), Some("bar"));
+    }
+
+    #[test]
+    fn rejects_unsigned_feed() {
+        assert!(verify_feed(b"{}", "", "").is_err());
+    }
+}
@@ -4,8 +4,8 @@ use std::path::Path;
 use super::version_compare;
 use super::UpdateChecker;
 use crate::detection::bundle_reader;
-use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::AppResult;
+use crate::models::{AppSource, ElectronUpdateFeed, UpdateInfo, UpdateSourceType};
+use crate::utils::{http_client, AppResult};
 
 pub struct ElectronChecker;
 
@@ -15,16 +15,8 @@ impl ElectronChecker {
     }
 }
 
-/// Parsed electron-builder update configuration from app-update.yml.
-struct ElectronUpdateConfig {
-    provider: String,
-    owner: Option<String>,
-    repo: Option<String>,
-    url: Option<String>,
-}
-
 /// Parse a simple YAML key-value file (electron-builder's app-update.yml is flat).
-fn parse_update_yml(content: &str) -> Option<ElectronUpdateConfig> {
+fn parse_update_yml(content: &str) -> Option<ElectronUpdateFeed> {
     let mut provider = None;
     let mut owner = None;
     let mut repo = None;
@@ -45,7 +37,7 @@ fn parse_update_yml(content: &str) -> Option<ElectronUpdateConfig> {
         }
     }
 
-    Some(ElectronUpdateConfig {
+    Some(ElectronUpdateFeed {
         provider: provider?,
         owner,
         repo,
@@ -53,6 +45,16 @@ fn parse_update_yml(content: &str) -> Option<ElectronUpdateConfig> {
     })
 }
 
+/// Read the update feed an Electron app checks itself, from app-update.yml or,
+/// failing that, dev-app-update.yml.
+pub fn read_update_feed(app_path: &Path) -> Option<ElectronUpdateFeed> {
+    let resources = app_path.join("Contents/Resources");
+    ["app-update.yml", "dev-app-update.yml"]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(resources.join(name)).ok())
+        .and_then(|content| parse_update_yml(&content))
+}
+
 #[async_trait]
 impl UpdateChecker for ElectronChecker {
     fn source_type(&self) -> UpdateSourceType {
@@ -69,30 +71,19 @@ impl UpdateChecker for ElectronChecker {
         app_path: &Path,
         current_version: Option<&str>,
         client: &reqwest::Client,
-        _context: &super::AppCheckContext,
+        context: &super::AppCheckContext,
     ) -> AppResult<Option<UpdateInfo>> {
         let current = match current_version {
             Some(v) => v,
             None => return Ok(None),
         };
 
-        let resources = app_path.join("Contents/Resources");
-
-        // Try app-update.yml first, then dev-app-update.yml
-        let yml_path = if resources.join("app-update.yml").exists() {
-            resources.join("app-update.yml")
-        } else if resources.join("dev-app-update.yml").exists() {
-            resources.join("dev-app-update.yml")
-        } else {
-            return Ok(None);
-        };
-
-        let content = match std::fs::read_to_string(&yml_path) {
-            Ok(c) => c,
-            Err(_) => return Ok(None),
-        };
-
-        let config = match parse_update_yml(&content) {
+        // The feed stored at detection, else read it from the bundle now
+        let config = match context
+            .electron_feed
+            .clone()
+            .or_else(|| read_update_feed(app_path))
+        {
             Some(c) => c,
             None => return Ok(None),
         };
@@ -131,7 +122,7 @@ impl UpdateChecker for ElectronChecker {
 
                 // Fetch latest-mac.yml from the generic update server
                 let yml_url = format!("{}/latest-mac.yml", base_url);
-                let resp = match client.get(&yml_url).send().await {
+                let resp = match http_client::send(client.get(&yml_url)).await {
                     Ok(r) => r,
                     Err(_) => return Ok(None),
                 };
@@ -169,6 +160,8 @@ impl UpdateChecker for ElectronChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: None,
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
 
@@ -1,15 +1,15 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
 use std::sync::OnceLock;
-use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::RwLock;
 
 use super::version_compare;
 use super::UpdateChecker;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::http_client::APP_USER_AGENT;
+use crate::utils::http_client::{self, APP_USER_AGENT};
 use crate::utils::AppResult;
 
 pub struct GitHubReleasesChecker;
@@ -30,6 +30,8 @@ struct GitHubAsset {
     browser_download_url: String,
     #[allow(dead_code)]
     content_type: Option<String>,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 // --- ETag cache for GitHub API rate limit mitigation ---
@@ -38,6 +40,9 @@ struct GitHubAsset {
 struct ETagCacheEntry {
     etag: String,
     response_body: String,
+    /// Unix time the response was last fetched or confirmed unchanged
+    #[serde(default)]
+    fetched_at: i64,
 }
 
 /// In-memory ETag cache keyed by "owner/repo".
@@ -49,12 +54,131 @@ fn etag_cache() -> &'static RwLock<HashMap<String, ETagCacheEntry>> {
     })
 }
 
-/// Whether we've been rate-limited this cycle (skip remaining GitHub checks).
-static RATE_LIMITED: AtomicBool = AtomicBool::new(false);
+/// Requests kept back from a cycle's budget for macPlus's own update check and
+/// release notes.
+const RATE_RESERVE: u32 = 5;
+
+/// What's left of the GitHub API rate limit, from the headers of the last response.
+#[derive(Debug)]
+struct RateBudget {
+    /// Requests left before the reset, from `x-ratelimit-remaining`
+    remaining: Option<u32>,
+    /// Unix time the limit resets, from `x-ratelimit-reset`
+    reset_at: Option<i64>,
+    /// Repos held back until the reset
+    deferred: BTreeSet<String>,
+}
+
+impl RateBudget {
+    /// Whether the counts still hold, i.e. the limit hasn't reset since.
+    fn in_effect(&self) -> bool {
+        self.reset_at.is_some_and(|reset_at| reset_at > unix_now())
+    }
+}
+
+static BUDGET: StdMutex<RateBudget> = StdMutex::new(RateBudget {
+    remaining: None,
+    reset_at: None,
+    deferred: BTreeSet::new(),
+});
+
+fn unix_now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Note the rate-limit headers GitHub sends with every API response.
+fn note_rate_limit(headers: &reqwest::header::HeaderMap) {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok())
+    };
+    let (Some(remaining), Some(reset_at)) =
+        (header("x-ratelimit-remaining"), header("x-ratelimit-reset"))
+    else {
+        return;
+    };
+    let mut remaining = remaining.max(0) as u32;
+    let mut budget = BUDGET.lock().unwrap_or_else(|e| e.into_inner());
+    // Responses to concurrent requests arrive out of order
+    if budget.reset_at == Some(reset_at) {
+        remaining = remaining.min(budget.remaining.unwrap_or(u32::MAX));
+    }
+    if remaining == 0 && budget.remaining != Some(0) {
+        log::warn!("GitHub API rate limit reached, deferring GitHub checks until it resets");
+    }
+    budget.remaining = Some(remaining);
+    budget.reset_at = Some(reset_at);
+}
 
-/// Reset the rate-limit flag at the start of each check cycle.
-pub fn reset_rate_limit_flag() {
-    RATE_LIMITED.store(false, Ordering::Relaxed);
+/// Whether a request for `repo` may go out now: not while the rate limit is
+/// used up or the repo was deferred, until the limit resets. A repo turned away
+/// is deferred, to be checked after the reset.
+fn may_request(repo: &str) -> bool {
+    let mut budget = BUDGET.lock().unwrap_or_else(|e| e.into_inner());
+    if !budget.in_effect() {
+        return true;
+    }
+    if budget.remaining != Some(0) && !budget.deferred.contains(repo) {
+        return true;
+    }
+    budget.deferred.insert(repo.to_string());
+    false
+}
+
+/// The repos left over once `budget` requests go to those fetched longest ago.
+/// `repos` holds each repo with the Unix time it was last fetched, 0 for never.
+fn repos_to_defer(mut repos: Vec<(String, i64)>, budget: u32) -> BTreeSet<String> {
+    repos.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    repos.dedup_by(|a, b| a.0 == b.0);
+    repos
+        .into_iter()
+        .skip(budget as usize)
+        .map(|(repo, _)| repo)
+        .collect()
+}
+
+/// Budget the GitHub requests of a check cycle over `repos` ("owner/repo"). When
+/// fewer requests are left before the rate limit resets than there are repos,
+/// the repos checked longest ago get them and the rest are deferred until the reset.
+pub async fn plan_cycle(repos: Vec<String>) {
+    let repos: Vec<(String, i64)> = {
+        let cache = etag_cache().read().await;
+        repos
+            .into_iter()
+            .map(|repo| {
+                let fetched_at = cache.get(&repo).map_or(0, |e| e.fetched_at);
+                (repo, fetched_at)
+            })
+            .collect()
+    };
+
+    let mut budget = BUDGET.lock().unwrap_or_else(|e| e.into_inner());
+    budget.deferred.clear();
+    if !budget.in_effect() {
+        return;
+    }
+    let Some(remaining) = budget.remaining else {
+        return;
+    };
+    budget.deferred = repos_to_defer(repos, remaining.saturating_sub(RATE_RESERVE));
+    if !budget.deferred.is_empty() {
+        log::info!(
+            "GitHub API: {} requests left, deferring {} repos until the rate limit resets",
+            remaining,
+            budget.deferred.len()
+        );
+    }
+}
+
+/// When the repos deferred this cycle can be checked (Unix time), if any were.
+pub fn deferred_until() -> Option<i64> {
+    let budget = BUDGET.lock().unwrap_or_else(|e| e.into_inner());
+    if budget.deferred.is_empty() {
+        return None;
+    }
+    budget.reset_at
 }
 
 fn etag_cache_path() -> Option<PathBuf> {
@@ -83,6 +207,14 @@ pub async fn save_etag_cache() {
     }
 }
 
+/// Forget all cached responses, in memory and on disk.
+pub async fn clear_etag_cache() {
+    etag_cache().write().await.clear();
+    if let Some(path) = etag_cache_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
 /// Built-in mapping of macOS bundle IDs to GitHub "owner/repo" slugs.
 fn github_mappings() -> &'static HashMap<&'static str, &'static str> {
     static MAPPINGS: OnceLock<HashMap<&str, &str>> = OnceLock::new();
@@ -311,12 +443,7 @@ impl UpdateChecker for GitHubReleasesChecker {
         client: &reqwest::Client,
         context: &super::AppCheckContext,
     ) -> AppResult<Option<UpdateInfo>> {
-        // Resolve repo: user override (from context) > built-in map
-        let repo_slug = if let Some(ref repo) = context.github_repo {
-            repo.clone()
-        } else if let Some(slug) = github_mappings().get(bundle_id) {
-            slug.to_string()
-        } else {
+        let Some(repo_slug) = repo_for(bundle_id, context.github_repo.as_deref()) else {
             return Ok(None);
         };
 
@@ -329,40 +456,59 @@ impl UpdateChecker for GitHubReleasesChecker {
     }
 }
 
-pub async fn check_github_release(
-    owner: &str,
-    repo: &str,
-    bundle_id: &str,
-    current_version: Option<&str>,
-    client: &reqwest::Client,
-) -> AppResult<Option<UpdateInfo>> {
-    // Skip if we've been rate-limited this cycle
-    if RATE_LIMITED.load(Ordering::Relaxed) {
-        return Ok(None);
+/// The "owner/repo" an app's releases are checked in: the user override or
+/// community feed (`github_repo`), then the built-in map.
+pub fn repo_for(bundle_id: &str, github_repo: Option<&str>) -> Option<String> {
+    github_repo
+        .or_else(|| github_mappings().get(bundle_id).copied())
+        .map(String::from)
+}
+
+/// Attach the user's GitHub token from the Keychain, if one is configured. The
+/// token is cached after the first read, but that read can block on the Keychain,
+/// so it runs off the async workers.
+async fn with_github_auth(req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let token = tokio::task::spawn_blocking(crate::utils::keychain::github_token)
+        .await
+        .ok()
+        .flatten();
+    match token {
+        Some(token) => req.bearer_auth(token),
+        None => req,
     }
+}
 
-    let cache_key = format!("{}/{}", owner, repo);
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/releases/latest",
-        owner, repo
-    );
+/// GET a GitHub API URL through the ETag cache and return the response body.
+/// While the rate limit holds back requests for `repo`, the last cached body
+/// stands in, so an update found before isn't dropped.
+async fn get_cached(
+    client: &reqwest::Client,
+    url: &str,
+    cache_key: &str,
+    repo: &str,
+) -> AppResult<Option<String>> {
+    if !may_request(repo) {
+        http_client::note_deferred();
+        return Ok(cached_body(cache_key).await);
+    }
 
     // Check for cached ETag
     let cached_etag = {
         let cache = etag_cache().read().await;
-        cache.get(&cache_key).map(|e| e.etag.clone())
+        cache.get(cache_key).map(|e| e.etag.clone())
     };
 
     let mut req = client
-        .get(&url)
+        .get(url)
         .header("Accept", "application/vnd.github+json")
         .header("User-Agent", APP_USER_AGENT);
+    req = with_github_auth(req).await;
 
     if let Some(ref etag) = cached_etag {
         req = req.header("If-None-Match", etag.as_str());
     }
 
-    let resp = match req.send().await {
+    let resp = match http_client::send(req).await {
         Ok(r) => r,
         Err(e) => {
             log::debug!("GitHub API request failed for {}: {}", cache_key, e);
@@ -370,32 +516,26 @@ pub async fn check_github_release(
         }
     };
 
+    note_rate_limit(resp.headers());
     let status = resp.status();
 
-    // Handle rate limiting (403 with X-RateLimit-Remaining: 0)
-    if status == reqwest::StatusCode::FORBIDDEN {
-        let remaining = resp
-            .headers()
-            .get("x-ratelimit-remaining")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<u32>().ok());
-
-        if remaining == Some(0) {
-            log::warn!("GitHub API rate limit reached, skipping remaining GitHub checks");
-            RATE_LIMITED.store(true, Ordering::Relaxed);
+    // Rate limited (403 or 429 with X-RateLimit-Remaining: 0), or no access to the repo
+    let refused = status == reqwest::StatusCode::FORBIDDEN
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    if refused {
+        if may_request(repo) {
+            return Ok(None);
         }
-        return Ok(None);
+        return Ok(cached_body(cache_key).await);
     }
 
     // 304 Not Modified -- use cached response (doesn't count against rate limit)
     if status == reqwest::StatusCode::NOT_MODIFIED {
-        let cache = etag_cache().read().await;
-        if let Some(entry) = cache.get(&cache_key) {
-            if let Ok(release) = serde_json::from_str::<GitHubRelease>(&entry.response_body) {
-                return parse_github_release(release, bundle_id, current_version, owner, repo);
-            }
-        }
-        return Ok(None);
+        let mut cache = etag_cache().write().await;
+        return Ok(cache.get_mut(cache_key).map(|entry| {
+            entry.fetched_at = unix_now();
+            entry.response_body.clone()
+        }));
     }
 
     if !status.is_success() {
@@ -415,78 +555,106 @@ pub async fn check_github_release(
     if let Some(etag) = new_etag {
         let mut cache = etag_cache().write().await;
         cache.insert(
-            cache_key,
+            cache_key.to_string(),
             ETagCacheEntry {
                 etag,
                 response_body: body.clone(),
+                fetched_at: unix_now(),
             },
         );
     }
 
+    Ok(Some(body))
+}
+
+async fn cached_body(cache_key: &str) -> Option<String> {
+    let cache = etag_cache().read().await;
+    cache.get(cache_key).map(|e| e.response_body.clone())
+}
+
+pub async fn check_github_release(
+    owner: &str,
+    repo: &str,
+    bundle_id: &str,
+    current_version: Option<&str>,
+    client: &reqwest::Client,
+) -> AppResult<Option<UpdateInfo>> {
+    let cache_key = format!("{}/{}", owner, repo);
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases/latest",
+        owner, repo
+    );
+
+    let Some(body) = get_cached(client, &url, &cache_key, &cache_key).await? else {
+        return Ok(None);
+    };
+
     let release: GitHubRelease = serde_json::from_str(&body)
         .map_err(|e| crate::utils::AppError::Custom(format!("GitHub JSON parse error: {}", e)))?;
-    parse_github_release(release, bundle_id, current_version, owner, repo)
+    parse_github_release(release, bundle_id, current_version, owner, repo, false)
+}
+
+/// Like `check_github_release`, but considers prereleases too: picks the newest
+/// non-draft release from the recent releases list. Returns the update and whether
+/// it is a prerelease.
+pub async fn check_github_release_with_prereleases(
+    owner: &str,
+    repo: &str,
+    bundle_id: &str,
+    current_version: Option<&str>,
+    client: &reqwest::Client,
+) -> AppResult<Option<(UpdateInfo, bool)>> {
+    let repo_slug = format!("{}/{}", owner, repo);
+    // Separate cache key: entries under "owner/repo" hold a single release object
+    let cache_key = format!("{}#releases", repo_slug);
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases?per_page=10",
+        owner, repo
+    );
+
+    let Some(body) = get_cached(client, &url, &cache_key, &repo_slug).await? else {
+        return Ok(None);
+    };
+
+    let releases: Vec<GitHubRelease> = serde_json::from_str(&body)
+        .map_err(|e| crate::utils::AppError::Custom(format!("GitHub JSON parse error: {}", e)))?;
+
+    // Newest version wins regardless of publish order
+    let newest = releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .max_by(|a, b| {
+            let va = a.tag_name.strip_prefix('v').unwrap_or(&a.tag_name);
+            let vb = b.tag_name.strip_prefix('v').unwrap_or(&b.tag_name);
+            version_compare::flexible_compare(va, vb)
+        });
+
+    let Some(release) = newest else {
+        return Ok(None);
+    };
+    let prerelease = release.prerelease;
+    Ok(parse_github_release(release, bundle_id, current_version, owner, repo, true)?
+        .map(|info| (info, prerelease)))
 }
 
 /// Fetch release notes text for a given GitHub repo, reusing the ETag cache.
 /// Returns the body of the latest release, truncated to 2000 chars.
 pub async fn fetch_release_notes(repo_slug: &str, client: &reqwest::Client) -> Option<String> {
-    if RATE_LIMITED.load(Ordering::Relaxed) {
-        return None;
-    }
-
     let parts: Vec<&str> = repo_slug.splitn(2, '/').collect();
     if parts.len() != 2 {
         return None;
     }
 
     // Check ETag cache first — the checker may have already fetched this repo
-    let cache_key = repo_slug.to_string();
-    {
-        let cache = etag_cache().read().await;
-        if let Some(entry) = cache.get(&cache_key) {
-            if let Ok(release) = serde_json::from_str::<GitHubRelease>(&entry.response_body) {
-                return release.body.map(|b| truncate_notes(&b, 2000));
-            }
+    let body = match cached_body(repo_slug).await {
+        Some(body) => body,
+        None => {
+            // Fall back to fetching the latest release
+            let url = format!("https://api.github.com/repos/{}/releases/latest", repo_slug);
+            let fetched = get_cached(client, &url, repo_slug, repo_slug).await;
+            fetched.ok()??
         }
-    }
-
-    // Fall back to fetching the latest release
-    let url = format!("https://api.github.com/repos/{}/releases/latest", repo_slug);
-    let resp = client
-        .get(&url)
-        .header("Accept", "application/vnd.github+json")
-        .header("User-Agent", APP_USER_AGENT)
-        .send()
-        .await
-        .ok()?;
-
-    if resp.status() == reqwest::StatusCode::FORBIDDEN {
-        let remaining = resp.headers()
-            .get("x-ratelimit-remaining")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<u32>().ok());
-        if remaining == Some(0) {
-            RATE_LIMITED.store(true, Ordering::Relaxed);
-        }
-        return None;
-    }
-
-    if !resp.status().is_success() {
-        return None;
-    }
-
-    let new_etag = resp.headers()
-        .get("etag")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from);
-
-    let body = resp.text().await.ok()?;
-
-    if let Some(etag) = new_etag {
-        let mut cache = etag_cache().write().await;
-        cache.insert(cache_key, ETagCacheEntry { etag, response_body: body.clone() });
-    }
+    };
 
     let release: GitHubRelease = serde_json::from_str(&body).ok()?;
     release.body.map(|b| truncate_notes(&b, 2000))
@@ -510,8 +678,9 @@ fn parse_github_release(
     current_version: Option<&str>,
     owner: &str,
     repo: &str,
+    allow_prerelease: bool,
 ) -> AppResult<Option<UpdateInfo>> {
-    if release.draft || release.prerelease {
+    if release.draft || (release.prerelease && !allow_prerelease) {
         return Ok(None);
     }
 
@@ -522,8 +691,8 @@ fn parse_github_release(
 
     if let Some(current) = current_version {
         if version_compare::is_newer(current, version) {
-            let download_url = find_macos_asset(&release.assets)
-                .map(|a| a.browser_download_url.clone());
+            let asset = find_macos_asset(&release.assets);
+            let download_url = asset.map(|a| a.browser_download_url.clone());
 
             log::info!(
                 "GitHub: {} has update {} -> {} ({}/{})",
@@ -544,9 +713,34 @@ fn parse_github_release(
                 release_notes: release.body,
                 is_paid_upgrade: false,
                 notes: None,
+                download_size: asset.and_then(|a| a.size),
+                available_build: None,
             }));
         }
     }
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defers_the_most_recently_fetched_repos() {
+        let repos = vec![
+            ("iina/iina".to_string(), 300),
+            ("rxhanson/Rectangle".to_string(), 100),
+            ("p0deje/Maccy".to_string(), 0),
+            ("rxhanson/Rectangle".to_string(), 100),
+            ("zed-industries/zed".to_string(), 200),
+        ];
+
+        let deferred = repos_to_defer(repos.clone(), 2);
+        assert_eq!(
+            deferred.into_iter().collect::<Vec<_>>(),
+            vec!["iina/iina".to_string(), "zed-industries/zed".to_string()]
+        );
+        assert!(repos_to_defer(repos, 10).is_empty());
+    }
+}
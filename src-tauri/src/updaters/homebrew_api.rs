@@ -7,7 +7,7 @@ use tokio::sync::RwLock;
 use super::version_compare;
 use super::UpdateChecker;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::{is_browser_extension, AppResult};
+use crate::utils::{http_client, is_cask_blocked, plist_parser, AppResult};
 
 struct CaskIndexCache {
     etag: Option<String>,
@@ -34,6 +34,8 @@ fn cask_cache() -> &'static RwLock<CaskIndexCache> {
 pub struct CaskVersionInfo {
     pub token: String,
     pub version: String,
+    /// Build number from the cask version ("2.4.1,2041"), when it has one
+    pub build: Option<String>,
     pub url: Option<String>,
     pub sha256: Option<String>,
 }
@@ -54,18 +56,25 @@ pub struct HomebrewCaskIndex {
     pub url_by_token: HashMap<String, String>,
     /// Bundle ID → GitHub "owner/repo" slug, auto-extracted from cask download URLs/homepages
     pub github_repos: HashMap<String, String>,
+    /// Cask token → GitHub "owner/repo" slug (covers casks without bundle IDs, e.g. plugins)
+    pub github_repos_by_token: HashMap<String, String>,
     /// Cask token → description text from the cask JSON
     pub desc_by_token: HashMap<String, String>,
 }
 
-/// Normalize an app name for matching: lowercase, strip ".app" suffix.
+/// Bundle suffixes stripped when normalizing artifact names for matching.
+const BUNDLE_SUFFIXES: &[&str] = &[".app", ".qlgenerator", ".saver"];
+
+/// Cask artifact keys whose entries name an installed bundle.
+const BUNDLE_ARTIFACT_KEYS: &[&str] = &["app", "qlplugin", "screen_saver"];
+
+/// Normalize an app name for matching: lowercase, strip ".app" (or plugin bundle) suffix.
 fn normalize_app_name(name: &str) -> String {
     let s = name.trim();
-    let s = if let Some(stripped) = s.strip_suffix(".app") {
-        stripped
-    } else {
-        s
-    };
+    let s = BUNDLE_SUFFIXES
+        .iter()
+        .find_map(|suffix| s.strip_suffix(suffix))
+        .unwrap_or(s);
     s.to_lowercase()
 }
 
@@ -107,6 +116,7 @@ fn build_index(json: &[serde_json::Value]) -> HomebrewCaskIndex {
     let mut all_tokens_by_app_name = HashMap::new();
     let mut url_by_token = HashMap::new();
     let mut github_repos: HashMap<String, String> = HashMap::new();
+    let mut github_repos_by_token: HashMap<String, String> = HashMap::new();
     let mut desc_by_token: HashMap<String, String> = HashMap::new();
 
     for cask in json {
@@ -161,6 +171,7 @@ fn build_index(json: &[serde_json::Value]) -> HomebrewCaskIndex {
             Some(CaskVersionInfo {
                 token: token.to_string(),
                 version: version.to_string(),
+                build: version_compare::brew_version_build(raw_version).map(String::from),
                 url: url.clone(),
                 sha256,
             })
@@ -172,8 +183,11 @@ fn build_index(json: &[serde_json::Value]) -> HomebrewCaskIndex {
         let mut cask_bundle_ids: Vec<String> = Vec::new();
 
         for artifact in artifacts {
-            // Extract app names from artifact "app" arrays
-            if let Some(apps) = artifact.get("app").and_then(|v| v.as_array()) {
+            // Extract bundle names from "app", "qlplugin" and "screen_saver" arrays
+            let bundle_entries = BUNDLE_ARTIFACT_KEYS
+                .iter()
+                .filter_map(|key| artifact.get(*key).and_then(|v| v.as_array()));
+            for apps in bundle_entries {
                 for app_entry in apps {
                     if let Some(app_name) = app_entry.as_str() {
                         let normalized = normalize_app_name(app_name);
@@ -256,6 +270,7 @@ fn build_index(json: &[serde_json::Value]) -> HomebrewCaskIndex {
             for bid in &cask_bundle_ids {
                 github_repos.entry(bid.clone()).or_insert_with(|| slug.clone());
             }
+            github_repos_by_token.insert(token.to_string(), slug.clone());
         }
     }
 
@@ -276,6 +291,7 @@ fn build_index(json: &[serde_json::Value]) -> HomebrewCaskIndex {
         all_tokens_by_app_name,
         url_by_token,
         github_repos,
+        github_repos_by_token,
         desc_by_token,
     }
 }
@@ -308,7 +324,7 @@ pub async fn fetch_cask_index(client: &reqwest::Client) -> Option<HomebrewCaskIn
         req = req.header("If-None-Match", etag.as_str());
     }
 
-    let resp = match req.send().await {
+    let resp = match http_client::send(req).await {
         Ok(r) => r,
         Err(e) => {
             log::warn!("Failed to fetch Homebrew cask index: {}", e);
@@ -366,6 +382,75 @@ pub async fn fetch_cask_index(client: &reqwest::Client) -> Option<HomebrewCaskIn
     Some(index)
 }
 
+/// Paths listed in a cask's `zap` stanza.
+#[derive(Debug, Default)]
+pub struct ZapPaths {
+    /// `trash` and `delete` paths, removed with everything in them
+    pub trash: Vec<String>,
+    /// `rmdir` paths: folders, often shared by a vendor's apps, that are only
+    /// removed once they're empty
+    pub rmdir: Vec<String>,
+}
+
+/// Collect the paths listed in a cask's `zap` stanza (`trash`, `delete` and `rmdir`).
+/// Each directive takes either a single path or a list of paths.
+pub(crate) fn parse_zap_paths(cask: &serde_json::Value) -> ZapPaths {
+    let mut paths = ZapPaths::default();
+    let artifacts = match cask.get("artifacts").and_then(|a| a.as_array()) {
+        Some(a) => a,
+        None => return paths,
+    };
+
+    for stanza in artifacts.iter().filter_map(|a| a.get("zap")?.as_array()) {
+        for directive in stanza {
+            for key in ["trash", "delete", "rmdir"] {
+                let list = match key {
+                    "rmdir" => &mut paths.rmdir,
+                    _ => &mut paths.trash,
+                };
+                match directive.get(key) {
+                    Some(serde_json::Value::String(p)) => list.push(p.clone()),
+                    Some(serde_json::Value::Array(values)) => {
+                        list.extend(values.iter().filter_map(|v| v.as_str().map(String::from)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    for list in [&mut paths.trash, &mut paths.rmdir] {
+        list.sort();
+        list.dedup();
+    }
+    paths
+}
+
+/// Fetch a single cask's `zap` paths from the Homebrew Formulae API.
+/// Returns an empty list when the cask has no zap stanza or the request fails.
+pub async fn fetch_cask_zap_paths(client: &reqwest::Client, token: &str) -> ZapPaths {
+    let url = format!("https://formulae.brew.sh/api/cask/{}.json", token);
+    let resp = match http_client::send(client.get(&url)).await {
+        Ok(r) if r.status().is_success() => r,
+        Ok(r) => {
+            log::warn!("Homebrew cask API returned status {} for {}", r.status(), token);
+            return ZapPaths::default();
+        }
+        Err(e) => {
+            log::warn!("Failed to fetch cask {}: {}", token, e);
+            return ZapPaths::default();
+        }
+    };
+
+    match resp.json::<serde_json::Value>().await {
+        Ok(json) => parse_zap_paths(&json),
+        Err(e) => {
+            log::warn!("Failed to parse cask {} JSON: {}", token, e);
+            ZapPaths::default()
+        }
+    }
+}
+
 pub struct HomebrewApiChecker;
 
 impl HomebrewCaskIndex {
@@ -389,6 +474,22 @@ impl HomebrewCaskIndex {
         None
     }
 
+    /// Look up the GitHub "owner/repo" slug for an app: by bundle ID first, then
+    /// via the matched cask token (needed for plugins, whose casks list no bundle IDs).
+    pub fn lookup_github_repo(&self, bundle_id: &str, app_path: &Path) -> Option<&str> {
+        if let Some(slug) = self.github_repos.get(&bundle_id.to_lowercase()) {
+            return Some(slug.as_str());
+        }
+        self.lookup_token(bundle_id, app_path)
+            .and_then(|token| self.github_repos_by_token.get(token))
+            .map(|s| s.as_str())
+    }
+
+    /// Whether a cask with this token exists, including "latest" casks.
+    pub fn has_token(&self, token: &str) -> bool {
+        self.url_by_token.contains_key(token)
+    }
+
     /// Look up the description for a cask by token.
     pub fn lookup_desc(&self, token: &str) -> Option<&str> {
         self.desc_by_token.get(token).map(|s| s.as_str())
@@ -453,8 +554,8 @@ impl UpdateChecker for HomebrewApiChecker {
         _client: &reqwest::Client,
         context: &super::AppCheckContext,
     ) -> AppResult<Option<UpdateInfo>> {
-        // Browser extensions must not match Homebrew casks
-        if is_browser_extension(bundle_id) {
+        // Browser extensions and blocklisted apps must not match Homebrew casks
+        if is_cask_blocked(bundle_id) {
             return Ok(None);
         }
 
@@ -484,7 +585,19 @@ impl UpdateChecker for HomebrewApiChecker {
             }
         }
 
-        if version_compare::is_newer(current, &cask_info.version) {
+        // The installed build only matters when the cask has one to compare with
+        let current_build = cask_info
+            .build
+            .as_ref()
+            .and_then(|_| plist_parser::read_info_plist(app_path).ok())
+            .and_then(|dict| plist_parser::get_string(&dict, "CFBundleVersion"));
+
+        if version_compare::is_newer_build(
+            current,
+            current_build.as_deref(),
+            &cask_info.version,
+            cask_info.build.as_deref(),
+        ) {
             log::debug!(
                 "Homebrew API: {} has update {} -> {} (cask: {})",
                 bundle_id,
@@ -504,9 +617,34 @@ impl UpdateChecker for HomebrewApiChecker {
                 release_notes: None,
                 is_paid_upgrade: false,
                 notes: None,
+                download_size: None,
+                available_build: cask_info.build.clone(),
             }));
         }
 
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zap_paths() {
+        let cask = serde_json::json!({
+            "artifacts": [
+                { "app": ["Example.app"] },
+                { "zap": [{
+                    "trash": ["~/Library/Caches/com.example.app", "~/Library/Preferences/com.example.app*.plist"],
+                    "rmdir": "~/Library/Application Support/Example"
+                }] }
+            ]
+        });
+        let paths = parse_zap_paths(&cask);
+        assert_eq!(paths.trash.len(), 2);
+        assert_eq!(paths.rmdir, vec!["~/Library/Application Support/Example".to_string()]);
+        let none = parse_zap_paths(&serde_json::json!({ "artifacts": [] }));
+        assert!(none.trash.is_empty() && none.rmdir.is_empty());
+    }
+}
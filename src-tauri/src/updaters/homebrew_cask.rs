@@ -1,11 +1,10 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::path::Path;
-use std::process::Command;
 
-use super::{version_compare, BrewOutdatedCask, BrewOutdatedFormula, UpdateChecker};
+use super::{BrewOutdatedCask, BrewOutdatedFormula, UpdateChecker};
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::brew::brew_path;
+use crate::utils::brew::{brew_command, brew_path};
 use crate::utils::AppResult;
 
 /// Cask tokens for macOS system components that Homebrew tracks but cannot
@@ -62,6 +61,8 @@ impl UpdateChecker for HomebrewCaskChecker {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes: None,
+                    download_size: None,
+                    available_build: None,
                 }));
             }
             // Cask token exists but not in outdated list — up to date
@@ -87,7 +88,7 @@ pub fn fetch_brew_outdated() -> HashMap<String, BrewOutdatedCask> {
         }
     };
 
-    let output = match Command::new(brew)
+    let output = match brew_command(brew)
         .args(["outdated", "--cask", "--greedy", "--json=v2"])
         .output()
     {
@@ -135,11 +136,10 @@ pub fn fetch_brew_outdated() -> HashMap<String, BrewOutdatedCask> {
             None => continue,
         };
 
-        let current_version = version_compare::strip_brew_version_token(
-            c.get("current_version")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-        ).to_string();
+        let current_version = c.get("current_version")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
 
         // Handle installed_versions as array of strings gracefully
         let installed_versions = c.get("installed_versions")
@@ -169,7 +169,7 @@ pub fn fetch_brew_outdated_formulae() -> HashMap<String, BrewOutdatedFormula> {
         None => return HashMap::new(),
     };
 
-    let output = match Command::new(brew)
+    let output = match brew_command(brew)
         .args(["outdated", "--formula", "--json=v2"])
         .output()
     {
@@ -48,6 +48,8 @@ impl UpdateChecker for HomebrewFormulaChecker {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes,
+                    download_size: None,
+                    available_build: None,
                 }));
             }
         }
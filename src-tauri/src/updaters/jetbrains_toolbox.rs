@@ -1,12 +1,12 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use super::version_compare;
 use super::UpdateChecker;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::http_client::APP_USER_AGENT;
+use crate::utils::http_client::{self, APP_USER_AGENT};
 use crate::utils::AppResult;
 
 /// Maps bundle IDs to JetBrains product codes used by the releases API.
@@ -31,6 +31,49 @@ fn jetbrains_product_codes() -> &'static HashMap<&'static str, &'static str> {
     })
 }
 
+/// Key of this Mac's build in the releases API `downloads` object.
+const MAC_DOWNLOAD_KEY: &str = if cfg!(target_arch = "aarch64") { "macM1" } else { "mac" };
+
+/// Folder older Toolbox versions install IDEs into, relative to the home folder.
+const TOOLBOX_APPS_DIR: &str = "Library/Application Support/JetBrains/Toolbox/apps";
+
+/// Toolbox's record of the tools it installed, relative to the home folder.
+const TOOLBOX_STATE: &str = "Library/Application Support/JetBrains/Toolbox/state.json";
+
+/// The Toolbox app, if installed in either Applications folder.
+pub fn toolbox_app_path() -> Option<PathBuf> {
+    let home_apps = dirs::home_dir().map(|home| home.join("Applications"));
+    [Some(PathBuf::from("/Applications")), home_apps]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("JetBrains Toolbox.app"))
+        .find(|path| path.exists())
+}
+
+/// Whether Toolbox's `state.json` lists a tool installed at `app_path`.
+fn state_lists_app(state_json: &str, app_path: &Path) -> bool {
+    let Ok(state) = serde_json::from_str::<serde_json::Value>(state_json) else {
+        return false;
+    };
+    state
+        .get("tools")
+        .and_then(|tools| tools.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool.get("installLocation")?.as_str())
+        .any(|location| app_path.starts_with(location))
+}
+
+/// Whether Toolbox installed, and so updates, the IDE at `app_path`.
+pub fn is_toolbox_managed(app_path: &Path) -> bool {
+    let Some(home) = dirs::home_dir() else {
+        return false;
+    };
+    app_path.starts_with(home.join(TOOLBOX_APPS_DIR))
+        || std::fs::read_to_string(home.join(TOOLBOX_STATE))
+            .is_ok_and(|json| state_lists_app(&json, app_path))
+}
+
 pub struct JetBrainsToolboxChecker;
 
 impl JetBrainsToolboxChecker {
@@ -72,11 +115,7 @@ impl UpdateChecker for JetBrainsToolboxChecker {
             product_code
         );
 
-        let resp = client
-            .get(&url)
-            .header("User-Agent", APP_USER_AGENT)
-            .send()
-            .await?;
+        let resp = http_client::send(client.get(&url).header("User-Agent", APP_USER_AGENT)).await?;
 
         if !resp.status().is_success() {
             return Ok(None);
@@ -97,12 +136,17 @@ impl UpdateChecker for JetBrainsToolboxChecker {
             .and_then(|arr| arr.as_array())
             .and_then(|arr| arr.first());
 
-        let download_url = release_obj
+        // The DMG for this Mac's architecture
+        let download = release_obj
             .and_then(|rel| rel.get("downloads"))
-            .and_then(|dl| dl.get("mac"))
+            .and_then(|dl| dl.get(MAC_DOWNLOAD_KEY).or_else(|| dl.get("mac")));
+        let download_url = download
             .and_then(|mac| mac.get("link"))
             .and_then(|v| v.as_str())
             .map(String::from);
+        let download_size = download
+            .and_then(|mac| mac.get("size"))
+            .and_then(|v| v.as_u64());
 
         let notes_link = release_obj
             .and_then(|rel| rel.get("notesLink"))
@@ -125,6 +169,8 @@ impl UpdateChecker for JetBrainsToolboxChecker {
                     release_notes: None,
                     is_paid_upgrade: false,
                     notes: None,
+                    download_size,
+                    available_build: None,
                 }));
             }
         }
@@ -132,3 +178,22 @@ impl UpdateChecker for JetBrainsToolboxChecker {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolbox_state_lists_installed_ides() {
+        let state = r#"{"tools":[
+            {"toolId":"IDEA-U","installLocation":"/Users/me/Applications/IntelliJ IDEA Ultimate.app"},
+            {"toolId":"PyCharm-P","installLocation":"/Users/me/Applications/PyCharm Professional Edition.app"}
+        ]}"#;
+        assert!(state_lists_app(
+            state,
+            Path::new("/Users/me/Applications/IntelliJ IDEA Ultimate.app")
+        ));
+        assert!(!state_lists_app(state, Path::new("/Applications/GoLand.app")));
+        assert!(!state_lists_app("not json", Path::new("/Applications/GoLand.app")));
+    }
+}
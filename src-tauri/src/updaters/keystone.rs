@@ -5,16 +5,66 @@ use std::path::Path;
 use super::version_compare;
 use super::UpdateChecker;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::http_client::APP_USER_AGENT;
-use crate::utils::AppResult;
+use crate::utils::http_client::{self, APP_USER_AGENT};
+use crate::utils::{plist_parser, AppResult};
 
 const KEYSTONE_BUNDLE_IDS: &[&str] = &[
     "com.google.Chrome",
+    "com.google.Chrome.beta",
+    "com.google.Chrome.dev",
     "com.google.Chrome.canary",
     "com.google.drivefs",
     "com.google.GoogleUpdater",
+    // Stable Edge is checked through Microsoft AutoUpdate
+    "com.microsoft.edgemac.Beta",
+    "com.microsoft.edgemac.Dev",
+    "com.microsoft.edgemac.Canary",
 ];
 
+/// Release channel of a Chromium-based browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BrowserChannel {
+    Stable,
+    Beta,
+    Dev,
+    Canary,
+}
+
+impl BrowserChannel {
+    /// Channel name as Chromium Dash and Edge's release API spell it.
+    fn as_str(self) -> &'static str {
+        match self {
+            BrowserChannel::Stable => "Stable",
+            BrowserChannel::Beta => "Beta",
+            BrowserChannel::Dev => "Dev",
+            BrowserChannel::Canary => "Canary",
+        }
+    }
+
+    /// A pre-release channel named by `KSChannelID` or a bundle ID suffix, which
+    /// may carry an architecture too ("beta", "arm64-dev", "com.google.Chrome.canary").
+    fn from_id(id: &str) -> Option<Self> {
+        id.split(['-', '.'])
+            .find_map(|part| match part.to_ascii_lowercase().as_str() {
+                "beta" => Some(BrowserChannel::Beta),
+                "dev" => Some(BrowserChannel::Dev),
+                "canary" => Some(BrowserChannel::Canary),
+                _ => None,
+            })
+    }
+}
+
+/// The channel an installed browser follows. Keystone records it in Info.plist
+/// as `KSChannelID`; side-by-side channels also have their own bundle ID.
+fn installed_channel(bundle_id: &str, app_path: &Path) -> BrowserChannel {
+    plist_parser::read_info_plist(app_path)
+        .ok()
+        .and_then(|dict| plist_parser::get_string(&dict, "KSChannelID"))
+        .and_then(|id| BrowserChannel::from_id(&id))
+        .or_else(|| BrowserChannel::from_id(bundle_id.rsplit('.').next().unwrap_or_default()))
+        .unwrap_or(BrowserChannel::Stable)
+}
+
 pub struct KeystoneChecker;
 
 impl KeystoneChecker {
@@ -28,6 +78,32 @@ struct ChromiumRelease {
     version: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EdgeProduct {
+    product: String,
+    releases: Vec<EdgeRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EdgeRelease {
+    platform: String,
+    product_version: String,
+}
+
+/// The newest macOS version of `channel` in Edge's release list.
+fn edge_mac_version(products: &[EdgeProduct], channel: BrowserChannel) -> Option<String> {
+    products
+        .iter()
+        .find(|p| p.product.eq_ignore_ascii_case(channel.as_str()))?
+        .releases
+        .iter()
+        .filter(|r| r.platform == "MacOS")
+        .map(|r| r.product_version.clone())
+        .reduce(|best, v| if version_compare::is_newer(&best, &v) { v } else { best })
+}
+
 #[async_trait]
 impl UpdateChecker for KeystoneChecker {
     fn source_type(&self) -> UpdateSourceType {
@@ -52,35 +128,27 @@ impl UpdateChecker for KeystoneChecker {
             None => return Ok(None),
         };
 
-        // For Chrome variants, query the Chromium Dash API
-        if bundle_id == "com.google.Chrome" || bundle_id == "com.google.Chrome.canary" {
-            let channel = if bundle_id == "com.google.Chrome.canary" {
-                "Canary"
-            } else {
-                "Stable"
-            };
-
+        // For Chrome variants, query the Chromium Dash API for the installed channel
+        if bundle_id.starts_with("com.google.Chrome") {
+            let channel = installed_channel(bundle_id, app_path);
             let url = format!(
                 "https://chromiumdash.appspot.com/fetch_releases?channel={}&platform=Mac&num=1",
-                channel
+                channel.as_str()
             );
 
-            let resp = client
-                .get(&url)
-                .header("User-Agent", APP_USER_AGENT)
-                .send()
-                .await?;
-
-            if !resp.status().is_success() {
+            let request = client.get(&url).header("User-Agent", APP_USER_AGENT);
+            let Some(body) = http_client::get_cached(request).await? else {
                 return Ok(None);
-            }
+            };
 
-            let releases: Vec<ChromiumRelease> = resp.json().await?;
+            let releases: Vec<ChromiumRelease> = serde_json::from_str(&body).map_err(|e| {
+                crate::utils::AppError::Custom(format!("Chromium Dash JSON parse error: {}", e))
+            })?;
             if let Some(release) = releases.first() {
                 if version_compare::is_newer(current, &release.version) {
                     log::info!(
-                        "Keystone: {} has update {} -> {}",
-                        bundle_id, current, release.version
+                        "Keystone: {} ({}) has update {} -> {}",
+                        bundle_id, channel.as_str(), current, release.version
                     );
                     return Ok(Some(UpdateInfo {
                         bundle_id: bundle_id.to_string(),
@@ -92,6 +160,51 @@ impl UpdateChecker for KeystoneChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: None,
+                        download_size: None,
+                        available_build: None,
+                    }));
+                }
+            }
+            return Ok(None);
+        }
+
+        // Edge pre-release channels: Microsoft's release list has every channel
+        if bundle_id.starts_with("com.microsoft.edgemac") {
+            let channel = installed_channel(bundle_id, app_path);
+            let Some(body) = http_client::get_cached(
+                client
+                    .get("https://edgeupdates.microsoft.com/api/products")
+                    .header("User-Agent", APP_USER_AGENT),
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+
+            let products: Vec<EdgeProduct> = serde_json::from_str(&body).map_err(|e| {
+                crate::utils::AppError::Custom(format!("Edge products JSON parse error: {}", e))
+            })?;
+            if let Some(version) = edge_mac_version(&products, channel) {
+                if version_compare::is_newer(current, &version) {
+                    log::info!(
+                        "Keystone: {} ({}) has update {} -> {}",
+                        bundle_id, channel.as_str(), current, version
+                    );
+                    return Ok(Some(UpdateInfo {
+                        bundle_id: bundle_id.to_string(),
+                        current_version: Some(current.to_string()),
+                        available_version: version,
+                        source_type: UpdateSourceType::Keystone,
+                        download_url: None,
+                        release_notes_url: Some(format!(
+                            "https://learn.microsoft.com/en-us/deployedge/microsoft-edge-relnote-{}-channel",
+                            channel.as_str().to_lowercase()
+                        )),
+                        release_notes: None,
+                        is_paid_upgrade: false,
+                        notes: None,
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
             }
@@ -116,6 +229,8 @@ impl UpdateChecker for KeystoneChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: None,
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
             }
@@ -124,3 +239,49 @@ impl UpdateChecker for KeystoneChecker {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_from_ks_channel_id_or_bundle_id() {
+        assert_eq!(BrowserChannel::from_id("beta"), Some(BrowserChannel::Beta));
+        assert_eq!(BrowserChannel::from_id("arm64-dev"), Some(BrowserChannel::Dev));
+        assert_eq!(BrowserChannel::from_id("Canary"), Some(BrowserChannel::Canary));
+        assert_eq!(BrowserChannel::from_id("arm64"), None);
+        assert_eq!(BrowserChannel::from_id(""), None);
+        assert_eq!(
+            installed_channel("com.google.Chrome.beta", Path::new("/nonexistent")),
+            BrowserChannel::Beta
+        );
+        assert_eq!(
+            installed_channel("com.google.Chrome", Path::new("/nonexistent")),
+            BrowserChannel::Stable
+        );
+    }
+
+    #[test]
+    fn edge_version_for_channel() {
+        let products: Vec<EdgeProduct> = serde_json::from_str(
+            r#"[
+                {"Product":"Stable","Releases":[
+                    {"Platform":"Windows","ProductVersion":"131.0.2903.51"},
+                    {"Platform":"MacOS","ProductVersion":"130.0.2849.80"},
+                    {"Platform":"MacOS","ProductVersion":"131.0.2903.51"}]},
+                {"Product":"Beta","Releases":[
+                    {"Platform":"MacOS","ProductVersion":"132.0.2957.11"}]}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            edge_mac_version(&products, BrowserChannel::Stable).as_deref(),
+            Some("131.0.2903.51")
+        );
+        assert_eq!(
+            edge_mac_version(&products, BrowserChannel::Beta).as_deref(),
+            Some("132.0.2957.11")
+        );
+        assert_eq!(edge_mac_version(&products, BrowserChannel::Dev), None);
+    }
+}
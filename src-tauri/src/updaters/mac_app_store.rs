@@ -1,16 +1,12 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::Path;
-use std::time::Duration;
 
 use super::version_compare;
 use super::UpdateChecker;
 use crate::detection::bundle_reader;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::AppResult;
-
-/// Per-request timeout for iTunes API calls.
-const ITUNES_TIMEOUT_SECS: u64 = 15;
+use crate::utils::{http_client, AppResult};
 
 pub struct MacAppStoreChecker;
 
@@ -55,17 +51,14 @@ impl UpdateChecker for MacAppStoreChecker {
             bundle_id
         );
 
-        let resp = match tokio::time::timeout(
-            Duration::from_secs(ITUNES_TIMEOUT_SECS),
-            client.get(&url).send(),
-        ).await {
-            Ok(Ok(r)) => r,
-            Ok(Err(e)) => {
-                log::info!("MAS checker: HTTP error for {}: {}", bundle_id, e);
+        let resp = match http_client::send(client.get(&url)).await {
+            Ok(r) => r,
+            Err(e) if e.is_timeout() => {
+                log::info!("MAS checker: request timed out for {}", bundle_id);
                 return Ok(None);
             }
-            Err(_) => {
-                log::info!("MAS checker: request timed out after {}s for {}", ITUNES_TIMEOUT_SECS, bundle_id);
+            Err(e) => {
+                log::info!("MAS checker: HTTP error for {}: {}", bundle_id, e);
                 return Ok(None);
             }
         };
@@ -103,6 +96,8 @@ impl UpdateChecker for MacAppStoreChecker {
                     release_notes: result.release_notes.clone(),
                     is_paid_upgrade: false,
                     notes: None,
+                    download_size: None,
+                    available_build: None,
                 }));
             }
         }
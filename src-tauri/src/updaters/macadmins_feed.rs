@@ -1,4 +1,32 @@
-use crate::utils::http_client::APP_USER_AGENT;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::version_compare;
+use crate::models::{OsSecurityStatus, UpdateInfo};
+use crate::utils::http_client::{self, APP_USER_AGENT};
+
+/// SOFA, the macadmins feed of macOS releases and the CVEs each one fixes.
+const SOFA_FEED_URL: &str = "https://sofafeed.macadmins.io/v1/macos_data_feed.json";
+
+/// A CVE ID in lowercased release notes.
+static RE_CVE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"cve-\d{4}-\d{4,}").unwrap());
+
+/// Browsers ship security fixes in nearly every stable release, and their
+/// updaters rarely provide release notes to read that from.
+const BROWSER_BUNDLE_PREFIXES: &[&str] = &[
+    "com.google.Chrome",
+    "com.microsoft.edgemac",
+    "org.mozilla.firefox",
+    "com.brave.Browser",
+    "org.chromium.Chromium",
+    "com.vivaldi.Vivaldi",
+    "com.operasoftware.Opera",
+    "company.thebrowser.Browser",
+];
 
 /// Extract the latest version for a given app key or bundle ID from macadmins.software/latest.xml.
 /// Returns (version, download_url) if found.
@@ -7,12 +35,13 @@ pub async fn check_macadmins_version(
     bundle_id: &str,
     client: &reqwest::Client,
 ) -> Option<String> {
-    let resp = client
-        .get("https://macadmins.software/latest.xml")
-        .header("User-Agent", APP_USER_AGENT)
-        .send()
-        .await
-        .ok()?;
+    let resp = http_client::send(
+        client
+            .get("https://macadmins.software/latest.xml")
+            .header("User-Agent", APP_USER_AGENT),
+    )
+    .await
+    .ok()?;
 
     if !resp.status().is_success() {
         log::info!(
@@ -115,3 +144,196 @@ fn extract_version_from_xml(xml: &str, app_key: &str, bundle_id: &str) -> Option
 
     None
 }
+
+#[derive(Debug, Deserialize)]
+struct SofaFeed {
+    #[serde(rename = "OSVersions", default)]
+    os_versions: Vec<SofaOsVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SofaOsVersion {
+    #[serde(default)]
+    security_releases: Vec<SofaSecurityRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SofaSecurityRelease {
+    product_version: String,
+    security_info: Option<String>,
+    /// CVE ID → whether it is actively exploited
+    #[serde(rename = "CVEs", default)]
+    cves: HashMap<String, bool>,
+    #[serde(rename = "ActivelyExploitedCVEs", default)]
+    actively_exploited_cves: Vec<String>,
+}
+
+/// Whether a pending update should count as a security update: a browser
+/// release, or release notes that cite a CVE or mention a security fix.
+pub fn is_security_update(bundle_id: &str, update: &UpdateInfo) -> bool {
+    let is_browser = BROWSER_BUNDLE_PREFIXES.iter().any(|prefix| {
+        bundle_id
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || (rest.starts_with('.') && !rest.contains(".app.")))
+    });
+    is_browser || update.release_notes.as_deref().is_some_and(notes_mention_security)
+}
+
+fn notes_mention_security(notes: &str) -> bool {
+    let lower = notes.to_lowercase();
+    RE_CVE.is_match(&lower)
+        || [
+            "security fix",
+            "security update",
+            "security issue",
+            "security patch",
+            "vulnerabilit",
+        ]
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+}
+
+/// The version of macOS this Mac runs.
+pub(crate) fn installed_macos_version() -> Option<String> {
+    let plist = plist::Value::from_file(Path::new(
+        "/System/Library/CoreServices/SystemVersion.plist",
+    ))
+    .ok()?;
+    plist
+        .as_dictionary()?
+        .get("ProductVersion")?
+        .as_string()
+        .map(String::from)
+}
+
+/// Security releases of the installed macOS major version that are newer
+/// than `installed`, per SOFA.
+fn os_security_status(feed: &SofaFeed, installed: &str) -> Option<OsSecurityStatus> {
+    let major = installed.split('.').next()?;
+    let releases = feed.os_versions.iter().find_map(|os| {
+        os.security_releases
+            .iter()
+            .any(|r| r.product_version.split('.').next() == Some(major))
+            .then_some(&os.security_releases)
+    })?;
+
+    let missing: Vec<&SofaSecurityRelease> = releases
+        .iter()
+        .filter(|r| version_compare::is_newer(installed, &r.product_version))
+        .collect();
+    let latest = missing
+        .iter()
+        .max_by(|a, b| version_compare::flexible_compare(&a.product_version, &b.product_version));
+
+    let cves: BTreeSet<&str> = missing
+        .iter()
+        .flat_map(|r| r.cves.keys().map(String::as_str))
+        .collect();
+    let exploited: BTreeSet<&str> = missing
+        .iter()
+        .flat_map(|r| {
+            r.cves
+                .iter()
+                .filter(|(_, exploited)| **exploited)
+                .map(|(cve, _)| cve.as_str())
+                .chain(r.actively_exploited_cves.iter().map(String::as_str))
+        })
+        .collect();
+
+    Some(OsSecurityStatus {
+        installed_version: installed.to_string(),
+        latest_version: latest.map(|r| r.product_version.clone()),
+        missing_cve_count: cves.len(),
+        actively_exploited_count: exploited.len(),
+        security_info_url: latest.and_then(|r| r.security_info.clone()),
+    })
+}
+
+/// How far behind on security releases this Mac's macOS is, from the SOFA feed.
+pub async fn check_os_security(client: &reqwest::Client) -> Option<OsSecurityStatus> {
+    let installed = installed_macos_version()?;
+    let resp = http_client::send(client.get(SOFA_FEED_URL).header("User-Agent", APP_USER_AGENT))
+        .await
+        .ok()?;
+    if !resp.status().is_success() {
+        log::info!("SOFA feed: fetch returned status {}", resp.status());
+        return None;
+    }
+    let feed: SofaFeed = resp
+        .json()
+        .await
+        .map_err(|e| log::warn!("SOFA feed: failed to parse: {}", e))
+        .ok()?;
+    os_security_status(&feed, &installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_cves_of_missed_security_releases() {
+        let feed: SofaFeed = serde_json::from_str(
+            r#"{"OSVersions": [
+                {"OSVersion": "Sequoia 15", "SecurityReleases": [
+                    {"ProductVersion": "15.2", "SecurityInfo": "https://support.apple.com/121839",
+                     "CVEs": {"CVE-2024-54498": false, "CVE-2024-54527": true},
+                     "ActivelyExploitedCVEs": ["CVE-2024-54527"]},
+                    {"ProductVersion": "15.1.1", "SecurityInfo": "https://support.apple.com/121753",
+                     "CVEs": {"CVE-2024-44308": true, "CVE-2024-54498": false}},
+                    {"ProductVersion": "15.1", "CVEs": {"CVE-2024-44255": false}}
+                ]},
+                {"OSVersion": "Sonoma 14", "SecurityReleases": [
+                    {"ProductVersion": "14.7.2", "CVEs": {"CVE-2024-54498": false}}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let status = os_security_status(&feed, "15.1").unwrap();
+        assert_eq!(status.latest_version.as_deref(), Some("15.2"));
+        assert_eq!(status.missing_cve_count, 3);
+        assert_eq!(status.actively_exploited_count, 2);
+        assert_eq!(
+            status.security_info_url.as_deref(),
+            Some("https://support.apple.com/121839")
+        );
+
+        let current = os_security_status(&feed, "15.2").unwrap();
+        assert_eq!((current.latest_version, current.missing_cve_count), (None, 0));
+        assert_eq!(os_security_status(&feed, "14.7.2").unwrap().missing_cve_count, 0);
+        assert!(os_security_status(&feed, "13.7").is_none());
+    }
+
+    #[test]
+    fn tags_browser_and_cve_updates_as_security() {
+        let update = |notes: Option<&str>| UpdateInfo {
+            bundle_id: String::new(),
+            current_version: None,
+            available_version: "2.0".to_string(),
+            source_type: crate::models::UpdateSourceType::Sparkle,
+            download_url: None,
+            release_notes_url: None,
+            release_notes: notes.map(String::from),
+            is_paid_upgrade: false,
+            notes: None,
+            download_size: None,
+            available_build: None,
+        };
+        assert!(is_security_update("com.google.Chrome", &update(None)));
+        assert!(is_security_update("org.mozilla.firefox", &update(None)));
+        assert!(!is_security_update("com.google.Chrome.app.abcdef", &update(None)));
+        assert!(!is_security_update("com.google.Chromecast", &update(None)));
+        assert!(is_security_update("com.example.app", &update(Some("Fixes CVE-2025-1234"))));
+        assert!(is_security_update(
+            "com.example.app",
+            &update(Some("Addressed a vulnerability in the PDF parser"))
+        ));
+        assert!(!is_security_update(
+            "com.example.app",
+            &update(Some("New Security & Privacy settings page"))
+        ));
+    }
+}
@@ -112,6 +112,8 @@ impl UpdateChecker for MicrosoftAutoUpdateChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: None,
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
             }
@@ -138,6 +140,8 @@ impl UpdateChecker for MicrosoftAutoUpdateChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: Some("Update available via Homebrew".to_string()),
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
             }
@@ -161,6 +165,8 @@ impl UpdateChecker for MicrosoftAutoUpdateChecker {
                         release_notes: None,
                         is_paid_upgrade: false,
                         notes: Some("Update detected via cask SHA change".to_string()),
+                        download_size: None,
+                        available_build: None,
                     }));
                 }
                 CaskShaResult::Error(e) => {
@@ -229,6 +235,8 @@ async fn check_macadmins_xml(
                 release_notes: None,
                 is_paid_upgrade: false,
                 notes: None,
+                download_size: None,
+                available_build: None,
             }));
         }
     } else {
@@ -1,5 +1,6 @@
 pub mod adobe_cc;
 pub mod cask_sha_checker;
+pub mod community_mappings;
 pub mod electron;
 pub mod github_releases;
 pub mod homebrew_api;
@@ -19,16 +20,20 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::time::Instant;
 
 use tokio::sync::Mutex;
 
 use crate::db::Database;
-use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::AppResult;
+use crate::models::{AppSettings, AppSource, ElectronUpdateFeed, UpdateInfo, UpdateSourceType};
+use crate::utils::{http_client, AppError, AppResult};
 
 /// Cached info from `brew outdated --cask --greedy --json=v2`
 #[derive(Debug, Clone)]
 pub struct BrewOutdatedCask {
+    /// Latest version as brew reports it, e.g. "1.1.3363,ee42…"; updates found
+    /// from it are normalized by [`run_checker`]
     pub current_version: String,
     pub installed_versions: String,
 }
@@ -43,6 +48,8 @@ pub struct BrewOutdatedFormula {
 pub struct AppCheckContext {
     pub homebrew_cask_token: Option<String>,
     pub sparkle_feed_url: Option<String>,
+    /// Feed from the app's app-update.yml, read during detection
+    pub electron_feed: Option<ElectronUpdateFeed>,
     pub obtained_from: Option<String>,
     pub brew_outdated: Option<Arc<HashMap<String, BrewOutdatedCask>>>,
     pub brew_outdated_formulae: Option<Arc<HashMap<String, BrewOutdatedFormula>>>,
@@ -53,6 +60,9 @@ pub struct AppCheckContext {
     pub xcode_clt_installed: Option<bool>,
     /// Database handle for cask SHA cache lookups.
     pub db: Option<Arc<Mutex<Database>>>,
+    /// Whether a recent "no update source" result may skip the network checkers.
+    /// Off when the user checks a single app.
+    pub negative_cache: bool,
 }
 
 #[async_trait]
@@ -69,27 +79,40 @@ pub trait UpdateChecker: Send + Sync {
     ) -> AppResult<Option<UpdateInfo>>;
 }
 
+/// Sources the user turned off; their checkers are left out of new dispatchers.
+static DISABLED_SOURCES: StdMutex<Vec<UpdateSourceType>> = StdMutex::new(Vec::new());
+
+/// Store the disabled update sources from `settings`.
+pub fn apply_source_settings(settings: &AppSettings) {
+    *DISABLED_SOURCES.lock().unwrap_or_else(|e| e.into_inner()) = settings.disabled_sources.clone();
+}
+
 pub struct UpdateDispatcher {
     checkers: Vec<Box<dyn UpdateChecker>>,
 }
 
 impl UpdateDispatcher {
     pub fn new() -> Self {
+        let checkers: Vec<Box<dyn UpdateChecker>> = vec![
+            Box::new(sparkle::SparkleChecker),
+            Box::new(homebrew_cask::HomebrewCaskChecker),
+            Box::new(homebrew_api::HomebrewApiChecker),
+            Box::new(mac_app_store::MacAppStoreChecker),
+            Box::new(mozilla::MozillaChecker),
+            Box::new(github_releases::GitHubReleasesChecker),
+            Box::new(electron::ElectronChecker),
+            Box::new(keystone::KeystoneChecker),
+            Box::new(microsoft_autoupdate::MicrosoftAutoUpdateChecker),
+            Box::new(jetbrains_toolbox::JetBrainsToolboxChecker),
+            Box::new(adobe_cc::AdobeCCChecker),
+            Box::new(homebrew_formula::HomebrewFormulaChecker),
+        ];
+        let disabled = DISABLED_SOURCES.lock().unwrap_or_else(|e| e.into_inner()).clone();
         Self {
-            checkers: vec![
-                Box::new(sparkle::SparkleChecker),
-                Box::new(homebrew_cask::HomebrewCaskChecker),
-                Box::new(homebrew_api::HomebrewApiChecker),
-                Box::new(mac_app_store::MacAppStoreChecker),
-                Box::new(mozilla::MozillaChecker),
-                Box::new(github_releases::GitHubReleasesChecker),
-                Box::new(electron::ElectronChecker),
-                Box::new(keystone::KeystoneChecker),
-                Box::new(microsoft_autoupdate::MicrosoftAutoUpdateChecker),
-                Box::new(jetbrains_toolbox::JetBrainsToolboxChecker),
-                Box::new(adobe_cc::AdobeCCChecker),
-                Box::new(homebrew_formula::HomebrewFormulaChecker),
-            ],
+            checkers: checkers
+                .into_iter()
+                .filter(|c| !disabled.contains(&c.source_type()))
+                .collect(),
         }
     }
 
@@ -135,10 +158,27 @@ impl UpdateDispatcher {
 
         let mut tried: Vec<String> = Vec::new();
 
+        // Network checkers found no source for this app lately and nothing it depends on changed
+        let negative_db = context.db.as_ref().filter(|_| context.negative_cache);
+        let fingerprint = negative_fingerprint(effective_version, install_source, context);
+        if let Some(db) = negative_db {
+            let db = db.lock().await;
+            let cached = db.get_negative_check(bundle_id, NEGATIVE_TTL_HOURS);
+            if cached.as_deref() == Some(fingerprint.as_str()) && !network_checkers.is_empty() {
+                log::info!(
+                    "Update check for {}: skipping network checkers, none found a source in the last {}h",
+                    bundle_id, NEGATIVE_TTL_HOURS
+                );
+                network_checkers.clear();
+            }
+        }
+
         // Tier 1: Run brew checkers sequentially (they share brew cache)
         for checker in &brew_checkers {
             let source_name = checker.source_type().as_str().to_string();
-            match checker.check(bundle_id, path, effective_version, client, context).await {
+            let run = run_checker(*checker, bundle_id, path, effective_version, client, context).await;
+            record_checker_run(&source_name, &run);
+            match run.result {
                 Ok(Some(mut update)) => {
                     tried.push(source_name.clone());
                     log::info!(
@@ -161,15 +201,25 @@ impl UpdateDispatcher {
             let futures: Vec<_> = network_checkers.iter().map(|checker| {
                 let source_name = checker.source_type().as_str().to_string();
                 async move {
-                    let result = checker.check(bundle_id, path, effective_version, client, context).await;
-                    (source_name, result)
+                    let run = run_checker(*checker, bundle_id, path, effective_version, client, context).await;
+                    (source_name, run)
                 }
             }).collect();
 
             let results = futures::future::join_all(futures).await;
+            if let Some(db) = negative_db {
+                let runs: Vec<&CheckerRun> = results.iter().map(|(_, run)| run).collect();
+                let db = db.lock().await;
+                let _ = if found_no_source(&runs) {
+                    db.set_negative_check(bundle_id, &fingerprint)
+                } else {
+                    db.clear_negative_check(bundle_id)
+                };
+            }
             let mut found_update: Option<UpdateInfo> = None;
-            for (source_name, result) in results {
-                match result {
+            for (source_name, run) in results {
+                record_checker_run(&source_name, &run);
+                match run.result {
                     Ok(Some(update)) => {
                         if found_update.is_none() {
                             log::info!(
@@ -227,11 +277,14 @@ impl UpdateDispatcher {
                     source: source_name,
                     can_check: false,
                     result: "skipped".to_string(),
+                    elapsed_ms: None,
+                    http_status: None,
                 });
                 continue;
             }
 
-            let result_str = match checker.check(bundle_id, path, effective_version, client, context).await {
+            let run = run_checker(checker.as_ref(), bundle_id, path, effective_version, client, context).await;
+            let result_str = match run.result {
                 Ok(Some(update)) => format!("found: {}", update.available_version),
                 Ok(None) => "not_found".to_string(),
                 Err(e) => format!("error: {}", e),
@@ -241,6 +294,8 @@ impl UpdateDispatcher {
                 source: source_name,
                 can_check: true,
                 result: result_str,
+                elapsed_ms: Some(run.elapsed_ms),
+                http_status: run.http_status,
             });
         }
 
@@ -248,14 +303,190 @@ impl UpdateDispatcher {
     }
 }
 
+/// One checker run: its result, how long it took and the HTTP status of the
+/// last response it got.
+struct CheckerRun {
+    result: AppResult<Option<UpdateInfo>>,
+    elapsed_ms: u64,
+    http_status: Option<u16>,
+    timed_out: bool,
+    /// A request was held back for a rate limit, so the source wasn't asked
+    deferred: bool,
+}
+
+/// Run one checker, giving up once it exceeds the configured per-checker timeout.
+async fn run_checker(
+    checker: &dyn UpdateChecker,
+    bundle_id: &str,
+    path: &Path,
+    current_version: Option<&str>,
+    client: &reqwest::Client,
+    context: &AppCheckContext,
+) -> CheckerRun {
+    let current_version = current_version.map(version_compare::strip_brew_version_token);
+    let limit = http_client::checker_timeout();
+    let started = Instant::now();
+    let scheme = community_mappings::lookup(bundle_id)
+        .and_then(|m| m.version_scheme)
+        .unwrap_or_default();
+    let (outcome, http_status, deferred) = http_client::capture_status(version_compare::with_scheme(
+        scheme,
+        tokio::time::timeout(
+            limit,
+            checker.check(bundle_id, path, current_version, client, context),
+        ),
+    ))
+    .await;
+    let timed_out = outcome.is_err();
+    let timeout = || AppError::Custom(format!("timed out after {}s", limit.as_secs()));
+    CheckerRun {
+        result: outcome
+            .unwrap_or_else(|_| Err(timeout()))
+            .map(|found| found.map(normalize_versions)),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        http_status,
+        timed_out,
+        deferred,
+    }
+}
+
+/// Strip Homebrew version tokens from what a checker found, so every source
+/// reports "1.1.3363" for "1.1.3363,ee42…". A build number in the token is kept
+/// as the available build.
+fn normalize_versions(mut update: UpdateInfo) -> UpdateInfo {
+    if update.available_build.is_none() {
+        update.available_build =
+            version_compare::brew_version_build(&update.available_version).map(String::from);
+    }
+    update.available_version =
+        version_compare::strip_brew_version_token(&update.available_version).to_string();
+    update.current_version = update
+        .current_version
+        .map(|v| version_compare::strip_brew_version_token(&v).to_string());
+    update
+}
+
+/// How long a "no update source" result lets an app's network checkers be skipped.
+const NEGATIVE_TTL_HOURS: u32 = 24;
+
+/// What a stored "no update source" result depends on: the app's version and
+/// install source, where it's looked up, and the community feed. A change to
+/// any of them checks the app against every source again.
+fn negative_fingerprint(
+    version: Option<&str>,
+    install_source: &AppSource,
+    context: &AppCheckContext,
+) -> String {
+    format!(
+        "{}|{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{}",
+        version.unwrap_or(""),
+        install_source.as_str(),
+        context.homebrew_cask_token,
+        context.sparkle_feed_url,
+        context.github_repo,
+        context.electron_feed,
+        context.homebrew_formula_name,
+        context.obtained_from,
+        community_mappings::feed_version(),
+    )
+}
+
+/// Whether none of the network checkers reached a source that answered for the
+/// app: each found nothing after making no request or getting a client error,
+/// and none failed in a way worth trying again soon (a timeout, a connection or
+/// server error, a 408, or a 403/429 rate limit), or held back a request to stay
+/// within a rate limit.
+fn found_no_source(runs: &[&CheckerRun]) -> bool {
+    !runs.is_empty()
+        && runs.iter().all(|run| {
+            let no_answer = match run.http_status {
+                Some(status) => (400..500).contains(&status) && ![403, 408, 429].contains(&status),
+                None => !run.timed_out && !run.deferred,
+            };
+            no_answer && matches!(run.result, Ok(None))
+        })
+}
+
+/// How one update source has performed in the checks run since launch.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckerMetrics {
+    pub source: String,
+    pub runs: u64,
+    /// Runs that found an update
+    pub found: u64,
+    /// Runs that failed, timeouts included
+    pub errors: u64,
+    pub timeouts: u64,
+    pub avg_ms: u64,
+    pub max_ms: u64,
+    pub last_http_status: Option<u16>,
+    #[serde(skip)]
+    total_ms: u64,
+}
+
+impl CheckerMetrics {
+    fn record(&mut self, run: &CheckerRun) {
+        self.runs += 1;
+        match run.result {
+            Ok(Some(_)) => self.found += 1,
+            Ok(None) => {}
+            Err(_) => self.errors += 1,
+        }
+        if run.timed_out {
+            self.timeouts += 1;
+        }
+        self.total_ms += run.elapsed_ms;
+        self.avg_ms = self.total_ms / self.runs;
+        self.max_ms = self.max_ms.max(run.elapsed_ms);
+        if run.http_status.is_some() {
+            self.last_http_status = run.http_status;
+        }
+    }
+}
+
+/// Per-source metrics of update checks, by source name. Reset at launch.
+static CHECKER_METRICS: StdMutex<Option<HashMap<String, CheckerMetrics>>> = StdMutex::new(None);
+
+fn record_checker_run(source: &str, run: &CheckerRun) {
+    crate::utils::metrics::record_checker(
+        source,
+        matches!(run.result, Ok(Some(_))),
+        run.result.is_err(),
+    );
+    let mut metrics = CHECKER_METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    metrics
+        .get_or_insert_with(HashMap::new)
+        .entry(source.to_string())
+        .or_insert_with(|| CheckerMetrics {
+            source: source.to_string(),
+            ..Default::default()
+        })
+        .record(run);
+}
+
+/// Metrics of every source checked since launch, slowest on average first.
+pub fn checker_metrics() -> Vec<CheckerMetrics> {
+    let metrics = CHECKER_METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut all: Vec<CheckerMetrics> = metrics.iter().flat_map(|m| m.values().cloned()).collect();
+    all.sort_by(|a, b| b.avg_ms.cmp(&a.avg_ms));
+    all
+}
+
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CheckerDiagnostic {
     pub source: String,
     pub can_check: bool,
     pub result: String,
+    /// How long the check took; `None` when it was skipped
+    pub elapsed_ms: Option<u64>,
+    /// Status of the last HTTP response the checker got
+    pub http_status: Option<u16>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateCheckDiagnostic {
     pub bundle_id: String,
     pub app_path: String,
@@ -263,6 +494,8 @@ pub struct UpdateCheckDiagnostic {
     pub install_source: String,
     pub homebrew_cask_token: Option<String>,
     pub checkers_tried: Vec<CheckerDiagnostic>,
+    /// The appcast fetched and checked item by item, in feed diagnostic mode
+    pub sparkle_feed: Option<sparkle::FeedDiagnostic>,
 }
 
 /// Enrich an update with release notes if none were provided by the checker.
@@ -297,3 +530,93 @@ async fn enrich_release_notes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(
+        result: AppResult<Option<UpdateInfo>>,
+        elapsed_ms: u64,
+        status: Option<u16>,
+    ) -> CheckerRun {
+        CheckerRun {
+            timed_out: false,
+            deferred: false,
+            result,
+            elapsed_ms,
+            http_status: status,
+        }
+    }
+
+    fn found(available: &str) -> UpdateInfo {
+        UpdateInfo {
+            bundle_id: "com.example.foo".into(),
+            current_version: Some("2.4.0,2030".into()),
+            available_version: available.into(),
+            source_type: UpdateSourceType::HomebrewCask,
+            download_url: None,
+            release_notes_url: None,
+            release_notes: None,
+            is_paid_upgrade: false,
+            notes: None,
+            download_size: None,
+            available_build: None,
+        }
+    }
+
+    #[test]
+    fn sums_checker_runs() {
+        let mut metrics = CheckerMetrics::default();
+        metrics.record(&run(Ok(None), 100, Some(200)));
+        metrics.record(&CheckerRun {
+            timed_out: true,
+            ..run(Err(AppError::Custom("timed out".into())), 500, None)
+        });
+        metrics.record(&run(Err(AppError::NotFound("feed".into())), 300, Some(404)));
+
+        assert_eq!(metrics.runs, 3);
+        assert_eq!(metrics.found, 0);
+        assert_eq!(metrics.errors, 2);
+        assert_eq!(metrics.timeouts, 1);
+        assert_eq!(metrics.avg_ms, 300);
+        assert_eq!(metrics.max_ms, 500);
+        assert_eq!(metrics.last_http_status, Some(404));
+    }
+
+    #[test]
+    fn no_source_needs_every_checker_to_miss() {
+        let unmapped = run(Ok(None), 0, None);
+        let missing_repo = run(Ok(None), 120, Some(404));
+        let up_to_date = run(Ok(None), 200, Some(200));
+        let server_error = run(Ok(None), 300, Some(503));
+        let offline = run(Err(AppError::Custom("connection refused".into())), 50, None);
+        let rate_limited = run(Ok(None), 80, Some(403));
+        let found_after_miss = run(Ok(Some(found("1.0"))), 90, Some(404));
+        let deferred = CheckerRun {
+            deferred: true,
+            ..run(Ok(None), 0, None)
+        };
+
+        assert!(found_no_source(&[&unmapped, &missing_repo]));
+        assert!(!found_no_source(&[&unmapped, &up_to_date]));
+        assert!(!found_no_source(&[&missing_repo, &server_error]));
+        assert!(!found_no_source(&[&offline]));
+        assert!(!found_no_source(&[&unmapped, &rate_limited]));
+        assert!(!found_no_source(&[&unmapped, &found_after_miss]));
+        assert!(!found_no_source(&[&unmapped, &deferred]));
+        assert!(!found_no_source(&[]));
+    }
+
+    #[test]
+    fn normalizes_homebrew_version_tokens() {
+        let update = normalize_versions(found("2.4.1,2041"));
+        assert_eq!(update.available_version, "2.4.1");
+        assert_eq!(update.available_build.as_deref(), Some("2041"));
+        assert_eq!(update.current_version.as_deref(), Some("2.4.0"));
+
+        let update = normalize_versions(found("1.1.3363,ee424797ca4d37a06f6b4a1e48dc944838ac3b18"));
+        assert_eq!(update.available_version, "1.1.3363");
+        assert_eq!(update.available_build, None);
+    }
+}
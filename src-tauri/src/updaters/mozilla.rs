@@ -6,7 +6,7 @@ use std::sync::OnceLock;
 use super::version_compare;
 use super::UpdateChecker;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::AppResult;
+use crate::utils::{http_client, AppResult};
 
 pub struct MozillaChecker;
 
@@ -67,13 +67,14 @@ impl UpdateChecker for MozillaChecker {
             None => return Ok(None),
         };
 
-        let resp = client.get(product.api_url).send().await?;
-
-        if !resp.status().is_success() {
+        let Some(body) = http_client::get_cached(client.get(product.api_url)).await? else {
             return Ok(None);
-        }
+        };
 
-        let versions: HashMap<String, serde_json::Value> = resp.json().await?;
+        let versions: HashMap<String, serde_json::Value> =
+            serde_json::from_str(&body).map_err(|e| {
+                crate::utils::AppError::Custom(format!("Mozilla JSON parse error: {}", e))
+            })?;
 
         let available = match versions.get(product.version_key).and_then(|v| v.as_str()) {
             Some(v) => v,
@@ -98,6 +99,8 @@ impl UpdateChecker for MozillaChecker {
                 release_notes: None,
                 is_paid_upgrade: false,
                 notes: None,
+                download_size: None,
+                available_build: None,
             }));
         }
 
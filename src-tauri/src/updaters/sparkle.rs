@@ -1,11 +1,13 @@
 use async_trait::async_trait;
+use serde::Serialize;
+use std::cmp::Ordering;
 use std::path::Path;
 
-use super::version_compare;
+use super::version_compare::{self, VersionScheme};
 use super::UpdateChecker;
 use crate::detection::bundle_reader;
 use crate::models::{AppSource, UpdateInfo, UpdateSourceType};
-use crate::utils::{plist_parser, AppResult};
+use crate::utils::{http_client, plist_parser, AppResult};
 
 pub struct SparkleChecker;
 
@@ -15,16 +17,13 @@ impl UpdateChecker for SparkleChecker {
         UpdateSourceType::Sparkle
     }
 
-    fn can_check(&self, _bundle_id: &str, app_path: &Path, install_source: &AppSource) -> bool {
+    fn can_check(&self, bundle_id: &str, app_path: &Path, install_source: &AppSource) -> bool {
         if *install_source == AppSource::MacAppStore {
             return false;
         }
-        // Check for Sparkle framework or SUFeedURL
+        // Check for Sparkle framework or a feed URL from anywhere Sparkle reads one
         bundle_reader::has_sparkle_framework(app_path)
-            || plist_parser::read_info_plist(app_path)
-                .ok()
-                .and_then(|d| plist_parser::get_string(&d, "SUFeedURL"))
-                .is_some()
+            || discover_feed_url(bundle_id, app_path).is_some()
     }
 
     async fn check(
@@ -35,23 +34,80 @@ impl UpdateChecker for SparkleChecker {
         client: &reqwest::Client,
         context: &super::AppCheckContext,
     ) -> AppResult<Option<UpdateInfo>> {
-        // Prefer feed URL from context (DB), fall back to plist
-        let feed_url = if let Some(ref url) = context.sparkle_feed_url {
-            url.clone()
-        } else {
-            let dict = plist_parser::read_info_plist(app_path)?;
-            plist_parser::get_string(&dict, "SUFeedURL")
-                .ok_or_else(|| crate::utils::AppError::NotFound("No SUFeedURL found".into()))?
+        // Prefer feed URL from context (DB), fall back to discovering it again
+        let feed_url = match context.sparkle_feed_url {
+            Some(ref url) => url.clone(),
+            None => discover_feed_url(bundle_id, app_path)
+                .ok_or_else(|| crate::utils::AppError::NotFound("No SUFeedURL found".into()))?,
         };
 
-        let response = client.get(&feed_url).send().await?;
-        let body = response.text().await?;
+        // A feed that can't be fetched is a failed check, not "no update"
+        let Some(body) = http_client::get_cached(client.get(&feed_url)).await? else {
+            return Err(crate::utils::AppError::Custom(format!(
+                "Appcast request failed: {}",
+                feed_url
+            )));
+        };
 
-        let update = parse_appcast(&body, bundle_id, current_version)?;
+        let macos_version = super::macadmins_feed::installed_macos_version();
+        let current_build = plist_parser::read_info_plist(app_path)
+            .ok()
+            .and_then(|dict| plist_parser::get_string(&dict, "CFBundleVersion"));
+        let update = parse_appcast(
+            &body,
+            bundle_id,
+            current_version,
+            current_build.as_deref(),
+            macos_version.as_deref(),
+        )?;
         Ok(update)
     }
 }
 
+/// Keys a feed URL is stored under. Apps that switch feeds at runtime keep the
+/// one they shipped with in `SUOriginalFeedURL`.
+const FEED_URL_KEYS: &[&str] = &["SUFeedURL", "SUOriginalFeedURL"];
+
+/// Property lists in `Contents/Resources` that apps register as their defaults
+/// at launch. Sparkle reads `SUFeedURL` from the defaults before Info.plist.
+const BUNDLED_DEFAULTS: &[&str] = &["Defaults.plist", "UserDefaults.plist", "defaults.plist"];
+
+/// The first feed URL in `dict`, if it is an http(s) URL.
+fn feed_url_in(dict: &plist::Dictionary) -> Option<String> {
+    FEED_URL_KEYS
+        .iter()
+        .filter_map(|key| plist_parser::get_string(dict, key))
+        .map(|url| url.trim().to_string())
+        .find(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+fn feed_url_in_file(path: &Path) -> Option<String> {
+    let dict = plist::Value::from_file(path).ok()?.into_dictionary()?;
+    feed_url_in(&dict)
+}
+
+/// Find the appcast an app checks itself: Info.plist, then the defaults the app
+/// bundles or has saved (where apps without the key in Info.plist set it), then
+/// the community feed.
+pub fn discover_feed_url(bundle_id: &str, app_path: &Path) -> Option<String> {
+    let resources = app_path.join("Contents/Resources");
+    plist_parser::read_info_plist(app_path)
+        .ok()
+        .and_then(|dict| feed_url_in(&dict))
+        .or_else(|| {
+            BUNDLED_DEFAULTS
+                .iter()
+                .find_map(|name| feed_url_in_file(&resources.join(name)))
+        })
+        .or_else(|| {
+            let prefs = dirs::home_dir()?
+                .join("Library/Preferences")
+                .join(format!("{}.plist", bundle_id));
+            feed_url_in_file(&prefs)
+        })
+        .or_else(|| super::community_mappings::lookup(bundle_id)?.appcast_url)
+}
+
 /// Pre-release indicator strings (case-insensitive check)
 const PRE_RELEASE_INDICATORS: &[&str] = &[
     "beta", "alpha", "rc", "dev", "pre", "nightly", "canary",
@@ -80,28 +136,34 @@ fn parse_appcast(
     xml: &str,
     bundle_id: &str,
     current_version: Option<&str>,
+    current_build: Option<&str>,
+    macos_version: Option<&str>,
 ) -> AppResult<Option<UpdateInfo>> {
     // Primary: parse raw XML for Sparkle <enclosure> tags (correct download URLs)
-    let best_version = parse_sparkle_enclosures(xml, current_version);
+    let best_version = parse_sparkle_enclosures(xml, current_version, current_build, macos_version);
 
-    // Fallback: use feed-rs if enclosure parsing found nothing
-    let best_version = if best_version.is_some() {
-        best_version
-    } else {
-        parse_with_feed_rs(xml, current_version)?
+    // Fallback: use feed-rs if enclosure parsing found nothing. Feed titles carry no build.
+    let best_version = match best_version {
+        Some(best) => Some(best),
+        None => parse_with_feed_rs(xml, current_version)?
+            .map(|(version, url, notes_url)| (version, None, url, notes_url)),
     };
 
-    Ok(best_version.map(|(version, download_url, release_notes_url)| UpdateInfo {
-        bundle_id: bundle_id.to_string(),
-        current_version: current_version.map(String::from),
-        available_version: version,
-        source_type: UpdateSourceType::Sparkle,
-        download_url,
-        release_notes_url,
-        release_notes: None,
-        is_paid_upgrade: false,
-        notes: None,
-    }))
+    Ok(best_version.map(
+        |(version, build, download_url, release_notes_url)| UpdateInfo {
+            bundle_id: bundle_id.to_string(),
+            current_version: current_version.map(String::from),
+            available_version: version,
+            source_type: UpdateSourceType::Sparkle,
+            download_url,
+            release_notes_url,
+            release_notes: None,
+            is_paid_upgrade: false,
+            notes: None,
+            download_size: None,
+            available_build: build,
+        },
+    ))
 }
 
 /// Fallback parser using feed-rs for RSS/Atom feeds.
@@ -160,33 +222,42 @@ fn parse_with_feed_rs(
     Ok(best_version)
 }
 
+/// Version, build, download URL and release notes URL of an appcast item.
+type EnclosureItem = (String, Option<String>, Option<String>, Option<String>);
+
 /// Primary parser: extracts version and download URL from Sparkle <enclosure> tags.
 /// Handles both single-line and multiline <enclosure .../> elements.
 fn parse_sparkle_enclosures(
     xml: &str,
     current_version: Option<&str>,
-) -> Option<(String, Option<String>, Option<String>)> {
-    let mut best: Option<(String, Option<String>, Option<String>)> = None;
+    current_build: Option<&str>,
+    macos_version: Option<&str>,
+) -> Option<EnclosureItem> {
+    let mut best: Option<EnclosureItem> = None;
 
     // Collect enclosure element blocks (may span multiple lines)
     let enclosure_blocks = collect_enclosure_blocks(xml);
 
-    // Also extract releaseNotesLink from <item> blocks
-    let item_notes_links = collect_release_notes_links(xml);
+    // Also extract releaseNotesLink and the minimum macOS from <item> blocks
+    let item_notes_links = collect_item_elements(xml, "sparkle:releaseNotesLink");
+    let item_min_os = collect_item_elements(xml, "sparkle:minimumSystemVersion");
 
     for (idx, block) in enclosure_blocks.iter().enumerate() {
-        // Try sparkle:shortVersionString first, fall back to sparkle:version
-        let short_ver = extract_attr(block, "sparkle:shortVersionString")
-            .or_else(|| extract_attr(block, "sparkle:version"));
+        // Try sparkle:shortVersionString first, fall back to sparkle:version.
+        // With both, sparkle:version is the build number.
+        let short_ver = extract_attr(block, "sparkle:shortVersionString");
+        let build = extract_attr(block, "sparkle:version");
         let url = extract_attr(block, "url");
 
-        let ver = match short_ver {
-            Some(v) => v,
-            None => continue,
+        let (ver, build) = match (short_ver, build) {
+            (Some(v), build) => (v, build),
+            (None, Some(v)) => (v, None),
+            (None, None) => continue,
         };
 
-        // Filter pre-release versions
-        if is_pre_release(&ver, None) {
+        // Filter pre-release versions and ones this Mac can't run
+        let min_os = item_min_os.get(idx).cloned().flatten();
+        if is_pre_release(&ver, None) || requires_newer_macos(min_os.as_deref(), macos_version) {
             continue;
         }
 
@@ -195,22 +266,27 @@ fn parse_sparkle_enclosures(
             .or_else(|| item_notes_links.get(idx).cloned().flatten());
 
         if let Some(current) = current_version {
-            if version_compare::is_newer(current, &ver) {
+            if version_compare::is_newer_build(current, current_build, &ver, build.as_deref()) {
                 match &best {
-                    Some((existing, _, _)) => {
-                        if version_compare::is_newer(existing, &ver) {
-                            best = Some((ver, url, notes_url));
+                    Some((existing, existing_build, _, _)) => {
+                        if version_compare::is_newer_build(
+                            existing,
+                            existing_build.as_deref(),
+                            &ver,
+                            build.as_deref(),
+                        ) {
+                            best = Some((ver, build, url, notes_url));
                         }
                     }
                     None => {
-                        best = Some((ver, url, notes_url));
+                        best = Some((ver, build, url, notes_url));
                     }
                 }
             }
         } else {
             // No current version to compare, take the first one
             if best.is_none() {
-                best = Some((ver, url, notes_url));
+                best = Some((ver, build, url, notes_url));
             }
         }
     }
@@ -248,38 +324,183 @@ fn collect_enclosure_blocks(xml: &str) -> Vec<String> {
     blocks
 }
 
-/// Collects `<sparkle:releaseNotesLink>` URLs from each `<item>` block,
-/// indexed to match enclosure order.
-fn collect_release_notes_links(xml: &str) -> Vec<Option<String>> {
-    let mut links = Vec::new();
+/// The text of the first `<tag>` element in each `<item>` block, indexed to
+/// match enclosure order.
+fn collect_item_elements(xml: &str, tag: &str) -> Vec<Option<String>> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut values = Vec::new();
     let mut in_item = false;
-    let mut current_link: Option<String> = None;
+    let mut current: Option<String> = None;
 
     for line in xml.lines() {
         let trimmed = line.trim();
         if trimmed.starts_with("<item") {
             in_item = true;
-            current_link = None;
+            current = None;
         } else if trimmed == "</item>" {
             if in_item {
-                links.push(current_link.take());
+                values.push(current.take());
             }
             in_item = false;
-        } else if in_item && current_link.is_none() {
-            // Look for <sparkle:releaseNotesLink> element
-            if let Some(start) = trimmed.find("<sparkle:releaseNotesLink>") {
-                let after = &trimmed[start + "<sparkle:releaseNotesLink>".len()..];
-                if let Some(end) = after.find("</sparkle:releaseNotesLink>") {
-                    let url = after[..end].trim().to_string();
-                    if !url.is_empty() {
-                        current_link = Some(url);
+        } else if in_item && current.is_none() {
+            if let Some(start) = trimmed.find(&open) {
+                let after = &trimmed[start + open.len()..];
+                if let Some(end) = after.find(&close) {
+                    let value = after[..end].trim().to_string();
+                    if !value.is_empty() {
+                        current = Some(value);
                     }
                 }
             }
         }
     }
 
-    links
+    values
+}
+
+/// Whether an item's `sparkle:minimumSystemVersion` is above the macOS this
+/// Mac runs. Sparkle never offers such an item. macOS versions are compared the
+/// usual way, whatever the app's own scheme.
+fn requires_newer_macos(minimum: Option<&str>, macos_version: Option<&str>) -> bool {
+    match (minimum, macos_version) {
+        (Some(minimum), Some(macos)) => {
+            version_compare::compare_with(VersionScheme::Auto, macos, minimum) == Ordering::Less
+        }
+        _ => false,
+    }
+}
+
+/// How an appcast looked when fetched for `debug_update_check`, and why none
+/// of its items is offered as an update.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedDiagnostic {
+    pub url: String,
+    pub http_status: Option<u16>,
+    /// Where the feed stops being well-formed XML
+    pub xml_error: Option<String>,
+    /// Items with a download enclosure
+    pub items_found: usize,
+    pub pre_releases_skipped: usize,
+    /// Items whose `sparkle:minimumSystemVersion` is above `macos_version`
+    pub min_os_skipped: usize,
+    pub macos_version: Option<String>,
+    /// Newest version among the items that weren't skipped
+    pub newest_version: Option<String>,
+    /// Why the feed offers no update, or `None` when it does
+    pub no_match_reason: Option<String>,
+}
+
+/// The first error that makes `xml` malformed, with its byte offset.
+fn find_xml_error(xml: &str) -> Option<String> {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Eof) => return None,
+            Ok(_) => {}
+            Err(e) => return Some(format!("{} at byte {}", e, reader.error_position())),
+        }
+    }
+}
+
+/// Go through the appcast's items the way [`parse_appcast`] does, counting
+/// what each filter skipped.
+fn diagnose_appcast(
+    xml: &str,
+    current_version: Option<&str>,
+    macos_version: Option<&str>,
+) -> FeedDiagnostic {
+    let blocks = collect_enclosure_blocks(xml);
+    let item_min_os = collect_item_elements(xml, "sparkle:minimumSystemVersion");
+    let mut diag = FeedDiagnostic {
+        xml_error: find_xml_error(xml),
+        items_found: blocks.len(),
+        macos_version: macos_version.map(String::from),
+        ..Default::default()
+    };
+
+    let mut without_version = 0;
+    for (idx, block) in blocks.iter().enumerate() {
+        let Some(ver) = extract_attr(block, "sparkle:shortVersionString")
+            .or_else(|| extract_attr(block, "sparkle:version"))
+        else {
+            without_version += 1;
+            continue;
+        };
+        if is_pre_release(&ver, None) {
+            diag.pre_releases_skipped += 1;
+        } else if requires_newer_macos(
+            item_min_os.get(idx).cloned().flatten().as_deref(),
+            macos_version,
+        ) {
+            diag.min_os_skipped += 1;
+        } else if diag
+            .newest_version
+            .as_deref()
+            .map_or(true, |newest| version_compare::is_newer(newest, &ver))
+        {
+            diag.newest_version = Some(ver);
+        }
+    }
+
+    diag.no_match_reason = match parse_appcast(xml, "", current_version, None, macos_version) {
+        Ok(Some(_)) => None,
+        _ if diag.items_found == 0 => Some(match diag.xml_error {
+            Some(ref e) => format!("The feed isn't valid XML: {}", e),
+            None => "The feed has no items with a download enclosure".to_string(),
+        }),
+        _ => Some(match (diag.newest_version.as_deref(), current_version) {
+            (Some(newest), Some(current)) => format!(
+                "The newest version in the feed, {}, isn't newer than the installed {}",
+                newest, current
+            ),
+            _ => format!(
+                "None of the {} items can be offered: {} are pre-releases, {} need a newer macOS, {} have no version",
+                diag.items_found, diag.pre_releases_skipped, diag.min_os_skipped, without_version
+            ),
+        }),
+    };
+    diag
+}
+
+/// Fetch the appcast at `feed_url` and report what it offers, for
+/// `debug_update_check`. Unlike a check, an error status or unreadable feed is
+/// reported rather than returned.
+pub async fn diagnose_feed(
+    feed_url: &str,
+    current_version: Option<&str>,
+    client: &reqwest::Client,
+) -> FeedDiagnostic {
+    let failed = |reason: String, http_status: Option<u16>| FeedDiagnostic {
+        url: feed_url.to_string(),
+        http_status,
+        no_match_reason: Some(reason),
+        ..Default::default()
+    };
+    let response = match http_client::send(client.get(feed_url)).await {
+        Ok(response) => response,
+        Err(e) => return failed(format!("The feed couldn't be fetched: {}", e), None),
+    };
+    let status = response.status();
+    let http_status = Some(status.as_u16());
+    if !status.is_success() {
+        return failed(format!("The server answered {}", status), http_status);
+    }
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => return failed(format!("The feed couldn't be read: {}", e), http_status),
+    };
+
+    let macos_version = super::macadmins_feed::installed_macos_version();
+    FeedDiagnostic {
+        url: feed_url.to_string(),
+        http_status,
+        ..diagnose_appcast(&body, current_version, macos_version.as_deref())
+    }
 }
 
 /// Fetch the `<description>` or `<content:encoded>` from a Sparkle appcast feed.
@@ -288,11 +509,7 @@ pub async fn fetch_sparkle_description(
     feed_url: &str,
     client: &reqwest::Client,
 ) -> Option<String> {
-    let resp = client.get(feed_url).send().await.ok()?;
-    if !resp.status().is_success() {
-        return None;
-    }
-    let body = resp.text().await.ok()?;
+    let body = http_client::get_cached(client.get(feed_url)).await.ok()??;
     extract_item_description(&body)
 }
 
@@ -379,3 +596,106 @@ fn extract_version_from_title(title: &str) -> Option<String> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict(entries: &[(&str, &str)]) -> plist::Dictionary {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), plist::Value::String(v.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn feed_url_falls_back_to_original() {
+        assert_eq!(
+            feed_url_in(&dict(&[("SUFeedURL", "https://example.com/appcast.xml")])),
+            Some("https://example.com/appcast.xml".to_string())
+        );
+        assert_eq!(
+            feed_url_in(&dict(&[
+                ("SUFeedURL", "$(FEED_URL)"),
+                ("SUOriginalFeedURL", " https://example.com/original.xml "),
+            ])),
+            Some("https://example.com/original.xml".to_string())
+        );
+        assert_eq!(feed_url_in(&dict(&[("CFBundleName", "Example")])), None);
+    }
+
+    const APPCAST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:sparkle="http://www.andymatuschak.org/xml-namespaces/sparkle">
+<channel>
+<item>
+<title>Version 3.0</title>
+<sparkle:minimumSystemVersion>99.0</sparkle:minimumSystemVersion>
+<enclosure url="https://example.com/Foo-3.0.zip" sparkle:shortVersionString="3.0" />
+</item>
+<item>
+<title>Version 2.1 Beta</title>
+<enclosure url="https://example.com/Foo-2.1-beta.1.zip" sparkle:shortVersionString="2.1-beta.1" />
+</item>
+<item>
+<title>Version 2.0</title>
+<sparkle:minimumSystemVersion>12.0</sparkle:minimumSystemVersion>
+<enclosure url="https://example.com/Foo-2.0.zip" sparkle:shortVersionString="2.0" />
+</item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn skips_items_needing_newer_macos() {
+        let (version, _, url, _) =
+            parse_sparkle_enclosures(APPCAST, Some("1.0"), None, Some("14.5")).unwrap();
+        assert_eq!(version, "2.0");
+        assert_eq!(url.as_deref(), Some("https://example.com/Foo-2.0.zip"));
+        // Without a known macOS version nothing is skipped for it
+        let (version, _, _, _) =
+            parse_sparkle_enclosures(APPCAST, Some("1.0"), None, None).unwrap();
+        assert_eq!(version, "3.0");
+    }
+
+    #[test]
+    fn equal_versions_compare_by_build() {
+        let appcast = r#"<rss><channel>
+<item><enclosure url="https://example.com/Foo-2.0-457.zip" sparkle:shortVersionString="2.0" sparkle:version="457" /></item>
+<item><enclosure url="https://example.com/Foo-2.0-456.zip" sparkle:shortVersionString="2.0" sparkle:version="456" /></item>
+</channel></rss>"#;
+        let (version, build, url, _) =
+            parse_sparkle_enclosures(appcast, Some("2.0"), Some("456"), None).unwrap();
+        assert_eq!(version, "2.0");
+        assert_eq!(build.as_deref(), Some("457"));
+        assert_eq!(url.as_deref(), Some("https://example.com/Foo-2.0-457.zip"));
+
+        assert!(parse_sparkle_enclosures(appcast, Some("2.0"), Some("457"), None).is_none());
+        // Without the installed build, equal versions aren't an update
+        assert!(parse_sparkle_enclosures(appcast, Some("2.0"), None, None).is_none());
+    }
+
+    #[test]
+    fn diagnoses_why_no_item_matched() {
+        let diag = diagnose_appcast(APPCAST, Some("2.0"), Some("14.5"));
+        assert_eq!(diag.xml_error, None);
+        assert_eq!(diag.items_found, 3);
+        assert_eq!(diag.pre_releases_skipped, 1);
+        assert_eq!(diag.min_os_skipped, 1);
+        assert_eq!(diag.newest_version.as_deref(), Some("2.0"));
+        assert_eq!(
+            diag.no_match_reason.as_deref(),
+            Some("The newest version in the feed, 2.0, isn't newer than the installed 2.0")
+        );
+
+        assert_eq!(
+            diagnose_appcast(APPCAST, Some("1.0"), Some("14.5")).no_match_reason,
+            None
+        );
+
+        let broken = diagnose_appcast("<rss><channel><item></channel></rss>", Some("1.0"), None);
+        assert!(broken.xml_error.is_some());
+        assert!(broken
+            .no_match_reason
+            .unwrap()
+            .starts_with("The feed isn't valid XML"));
+    }
+}
@@ -1,47 +1,201 @@
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::future::Future;
 
 /// Strip Homebrew cask version tokens (comma-separated hash/qualifier).
 /// e.g. "1.1.3363,ee424797ca4d37a06f6b4a1e48dc944838ac3b18" → "1.1.3363"
+///
+/// This is the form versions are compared, stored as updates and shown in.
+/// The raw one is only kept where brew reported it, for the executors.
 pub fn strip_brew_version_token(version: &str) -> &str {
     version.split(',').next().unwrap_or(version)
 }
 
+/// The build number in a Homebrew cask version, when the part after the comma
+/// is one. e.g. "2.4.1,2041" → "2041"; a hash gives `None`.
+pub fn brew_version_build(version: &str) -> Option<&str> {
+    let (_, build) = version.split_once(',')?;
+    let build = build.split(',').next().unwrap_or(build);
+    let numeric = build.starts_with(|c: char| c.is_ascii_digit())
+        && build.chars().all(|c| c.is_ascii_digit() || c == '.');
+    numeric.then_some(build)
+}
+
+/// How an app numbers its versions, for apps the usual comparison gets wrong.
+/// Set per app in the community mappings feed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionScheme {
+    /// Calendar versions, where a two-digit year ("24.10") is the same as the
+    /// four-digit one ("2024.10")
+    Calendar,
+    /// Only the first three numbers count: a fourth is a build number
+    /// ("1.2.3.4567") that differs between sources for the same release
+    ThreePart,
+    /// Semantic versions where both parse as such, dotted numbers otherwise.
+    /// Schemes this version of macPlus doesn't know fall back to it.
+    #[default]
+    #[serde(other)]
+    Auto,
+}
+
+tokio::task_local! {
+    /// Scheme of the app being checked, set by [`with_scheme`].
+    static SCHEME: VersionScheme;
+}
+
+/// Run `fut` with [`is_newer`] and [`flexible_compare`] comparing by `scheme`.
+pub async fn with_scheme<F: Future>(scheme: VersionScheme, fut: F) -> F::Output {
+    SCHEME.scope(scheme, fut).await
+}
+
 /// Compare two version strings flexibly.
 /// Returns Ordering::Greater if `available` is newer than `current`.
 pub fn is_newer(current: &str, available: &str) -> bool {
     flexible_compare(current, available) == Ordering::Less
 }
 
+/// Like [`is_newer`], with build numbers (`CFBundleVersion`) breaking a tie
+/// between equal versions. Equal versions without both build numbers are the same.
+pub fn is_newer_build(
+    current: &str,
+    current_build: Option<&str>,
+    available: &str,
+    available_build: Option<&str>,
+) -> bool {
+    match flexible_compare(current, available) {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => is_newer_build_number(current_build, available_build),
+    }
+}
+
+/// Whether build number `available` is newer than `current`, when both are known.
+pub fn is_newer_build_number(current: Option<&str>, available: Option<&str>) -> bool {
+    match (current, available) {
+        (Some(current), Some(available)) => {
+            compare_with(VersionScheme::Auto, current, available) == Ordering::Less
+        }
+        _ => false,
+    }
+}
+
+/// A version labelled with its build number, e.g. "1.2.3 (456)", for an update
+/// that differs from the installed version only by build.
+pub fn with_build(version: &str, build: &str) -> String {
+    format!("{} ({})", version, build)
+}
+
+/// Compare two version strings by the scheme of the app being checked.
 pub fn flexible_compare(a: &str, b: &str) -> Ordering {
-    // Try semver first
-    if let (Ok(va), Ok(vb)) = (semver::Version::parse(a), semver::Version::parse(b)) {
-        return va.cmp(&vb);
+    let scheme = SCHEME.try_with(|scheme| *scheme).unwrap_or_default();
+    compare_with(scheme, a, b)
+}
+
+/// Compare two version strings by `scheme`. A leading "v" and build metadata
+/// after "+" are ignored, and a pre-release ("1.2.3-beta.4", "2.1b1", "3.0 RC 2")
+/// comes before its release.
+pub fn compare_with(scheme: VersionScheme, a: &str, b: &str) -> Ordering {
+    let (a, b) = (normalize(a), normalize(b));
+    if scheme == VersionScheme::Auto {
+        if let (Ok(va), Ok(vb)) = (semver::Version::parse(a), semver::Version::parse(b)) {
+            return va.cmp_precedence(&vb);
+        }
     }
 
-    // Component-by-component numeric comparison
-    let seg_a = split_segments(a);
-    let seg_b = split_segments(b);
+    // Component-by-component comparison, missing components counting as 0
+    let seg_a = segments(scheme, a);
+    let seg_b = segments(scheme, b);
+    let zero = Segment::Num(0);
 
     let max_len = seg_a.len().max(seg_b.len());
     for i in 0..max_len {
-        let sa = seg_a.get(i).map(|s| s.as_str()).unwrap_or("0");
-        let sb = seg_b.get(i).map(|s| s.as_str()).unwrap_or("0");
-
-        match (sa.parse::<u64>(), sb.parse::<u64>()) {
-            (Ok(na), Ok(nb)) => match na.cmp(&nb) {
-                Ordering::Equal => continue,
-                other => return other,
-            },
-            _ => match sa.cmp(sb) {
-                Ordering::Equal => continue,
-                other => return other,
-            },
+        let sa = seg_a.get(i).unwrap_or(&zero);
+        let sb = seg_b.get(i).unwrap_or(&zero);
+        match sa.cmp(sb) {
+            Ordering::Equal => continue,
+            other => return other,
         }
     }
 
     Ordering::Equal
 }
 
+fn normalize(version: &str) -> &str {
+    let version = version.trim();
+    let version = match version.strip_prefix(['v', 'V']) {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest,
+        _ => version,
+    };
+    version.split('+').next().unwrap_or(version)
+}
+
+/// One component of a version. The order of the variants is their order when
+/// compared: a pre-release tag comes before any number, so "1.2-beta" is older
+/// than "1.2" (= "1.2.0"), and text after a number ("1.1.1w") after it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    /// A pre-release tag, ranked dev < alpha < beta < preview < rc
+    Pre(u8),
+    Num(u64),
+    /// Any other text, lowercased
+    Text(String),
+}
+
+/// Rank of a pre-release tag. A lone "a" or "b" is only one when a number
+/// follows ("2.1b1"); otherwise it's a letter suffix ("1.1.1b").
+fn pre_release_rank(word: &str, before_number: bool) -> Option<u8> {
+    match word {
+        "dev" | "nightly" | "canary" | "snapshot" => Some(0),
+        "alpha" => Some(1),
+        "a" if before_number => Some(1),
+        "beta" => Some(2),
+        "b" if before_number => Some(2),
+        "pre" | "preview" => Some(3),
+        "rc" => Some(4),
+        _ => None,
+    }
+}
+
+fn segments(scheme: VersionScheme, version: &str) -> Vec<Segment> {
+    let raw = split_segments(version);
+    let mut segments: Vec<Segment> = raw
+        .iter()
+        .enumerate()
+        .map(|(i, seg)| {
+            if let Ok(n) = seg.parse::<u64>() {
+                return Segment::Num(n);
+            }
+            let word = seg.to_lowercase();
+            let before_number = raw
+                .get(i + 1)
+                .is_some_and(|next| next.parse::<u64>().is_ok());
+            match pre_release_rank(&word, before_number) {
+                Some(rank) => Segment::Pre(rank),
+                None => Segment::Text(word),
+            }
+        })
+        .collect();
+
+    match scheme {
+        VersionScheme::Auto => {}
+        VersionScheme::Calendar => {
+            if let Some(Segment::Num(year)) = segments.first_mut() {
+                if *year < 100 {
+                    *year += 2000;
+                }
+            }
+        }
+        VersionScheme::ThreePart => {
+            let numeric = |seg: &Segment| matches!(seg, Segment::Num(_));
+            if segments.len() > 3 && segments[..4].iter().all(numeric) {
+                segments.remove(3);
+            }
+        }
+    }
+    segments
+}
+
 fn split_segments(version: &str) -> Vec<String> {
     let mut segments = Vec::new();
     let mut current = String::new();
@@ -86,6 +240,78 @@ mod tests {
         assert!(!is_newer("2.0", "1.9"));
     }
 
+    /// (current, available, how current compares to available)
+    const AUTO_CASES: &[(&str, &str, Ordering)] = &[
+        // Semantic versions, pre-releases and build metadata
+        ("1.2.3-beta.4", "1.2.3", Ordering::Less),
+        ("1.2.3", "1.2.3-beta.4", Ordering::Greater),
+        ("1.2.3-beta.4", "1.2.3-beta.10", Ordering::Less),
+        ("1.2.3-rc.1", "1.2.3-beta.9", Ordering::Greater),
+        ("1.2.3+45", "1.2.3+46", Ordering::Equal),
+        ("v2.0.0", "2.0.0", Ordering::Equal),
+        // Pre-releases outside semver
+        ("1.2-beta4", "1.2", Ordering::Less),
+        ("2.1b1", "2.1", Ordering::Less),
+        ("2.1b1", "2.1b2", Ordering::Less),
+        ("3.0 RC 2", "3.0", Ordering::Less),
+        ("3.0 RC 2", "3.0 RC 3", Ordering::Less),
+        ("1.1.1", "1.1.1w", Ordering::Less),
+        // Date-based versions
+        ("2024.9", "2024.10", Ordering::Less),
+        ("2023.12", "2024.1", Ordering::Less),
+        ("2024.10.01", "2024.10.1", Ordering::Equal),
+        // Four-segment versions
+        ("1.2.3.4", "1.2.3.5", Ordering::Less),
+        ("1.2.3.0", "1.2.3", Ordering::Equal),
+        ("1.2.3.9", "1.2.4", Ordering::Less),
+        ("130.0.6723.92", "130.0.6723.117", Ordering::Less),
+        ("1.9", "1.10", Ordering::Less),
+    ];
+
+    const CALENDAR_CASES: &[(&str, &str, Ordering)] = &[
+        ("24.10", "2024.10", Ordering::Equal),
+        ("24.10", "2024.11", Ordering::Less),
+        ("2024.12", "25.1", Ordering::Less),
+    ];
+
+    const THREE_PART_CASES: &[(&str, &str, Ordering)] = &[
+        ("1.2.3", "1.2.3.4567", Ordering::Equal),
+        ("1.2.3.100", "1.2.3.99", Ordering::Equal),
+        ("1.2.3.4567", "1.2.4.12", Ordering::Less),
+    ];
+
+    #[test]
+    fn compares_version_schemes() {
+        let tables = [
+            (VersionScheme::Auto, AUTO_CASES),
+            (VersionScheme::Calendar, CALENDAR_CASES),
+            (VersionScheme::ThreePart, THREE_PART_CASES),
+        ];
+        for (scheme, cases) in tables {
+            for &(a, b, expected) in cases {
+                let case = format!("{:?}: {} vs {}", scheme, a, b);
+                assert_eq!(compare_with(scheme, a, b), expected, "{}", case);
+                let reversed = compare_with(scheme, b, a);
+                assert_eq!(reversed, expected.reverse(), "{} reversed", case);
+            }
+        }
+    }
+
+    #[test]
+    fn build_number_breaks_ties() {
+        assert!(is_newer_build("1.2.3", Some("455"), "1.2.3", Some("456")));
+        assert!(!is_newer_build("1.2.3", Some("456"), "1.2.3", Some("456")));
+        assert!(!is_newer_build("1.2.3", None, "1.2.3", Some("456")));
+        assert!(is_newer_build("1.2.3", Some("900"), "1.2.4", Some("12")));
+        assert!(!is_newer_build("1.2.4", Some("12"), "1.2.3", Some("900")));
+        assert_eq!(with_build("1.2.3", "456"), "1.2.3 (456)");
+        assert_eq!(brew_version_build("2.4.1,2041"), Some("2041"));
+        assert_eq!(brew_version_build("2.4.1,2041,abc"), Some("2041"));
+        assert_eq!(brew_version_build("1.1.3363,ee424797ca4d"), None);
+        assert_eq!(brew_version_build("2.4.1"), None);
+        assert!(is_newer("1.2.3", &with_build("1.2.3", "456")));
+    }
+
     #[test]
     fn test_different_lengths() {
         assert!(is_newer("1.0", "1.0.1"));
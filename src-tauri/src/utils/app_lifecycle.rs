@@ -1,5 +1,8 @@
 use std::process::Command;
 
+use crate::platform::automation;
+use crate::platform::permissions::PermissionState;
+
 /// Check if a GUI app with the given bundle ID is currently running.
 /// Uses `lsappinfo list` which is the most reliable method for GUI apps on macOS.
 pub fn is_app_running(bundle_id: &str) -> bool {
@@ -16,14 +19,43 @@ pub fn is_app_running(bundle_id: &str) -> bool {
     stdout.contains(bundle_id)
 }
 
+/// Read the pid from `lsappinfo info -only pid` output, e.g. `"pid"=1234`.
+fn parse_lsappinfo_pid(output: &str) -> Option<i32> {
+    let (_, value) = output.trim().split_once('=')?;
+    value.trim().parse().ok().filter(|pid| *pid > 0)
+}
+
+/// End a running app with SIGTERM. AppKit installs no handler for it, so the
+/// process exits right away without saving state or asking about unsaved changes.
+fn terminate_app(bundle_id: &str) {
+    let Some(pid) = Command::new("lsappinfo")
+        .current_dir("/tmp")
+        .args(["info", "-only", "pid", bundle_id])
+        .output()
+        .ok()
+        .and_then(|o| parse_lsappinfo_pid(&String::from_utf8_lossy(&o.stdout)))
+    else {
+        return;
+    };
+    unsafe {
+        libc::kill(pid, libc::SIGTERM);
+    }
+}
+
 /// Quit an app gracefully via AppleScript, falling back to pkill if needed.
-/// Returns true if the app was successfully quit (or wasn't running).
+/// Apps macPlus was denied Automation of get SIGTERM instead, so the user isn't
+/// prompted again, at the cost of any unsaved changes in them. Returns true if the app was successfully quit (or wasn't running).
 pub fn quit_app_gracefully(app_name: &str, bundle_id: &str) -> bool {
     // Try graceful quit via AppleScript
-    let _ = Command::new("osascript")
-        .current_dir("/tmp")
-        .args(["-e", &format!("tell application id \"{}\" to quit", bundle_id)])
-        .output();
+    let asked = automation::target_state(bundle_id) != PermissionState::Denied
+        && automation::tell_app(bundle_id, "quit") != PermissionState::Denied;
+    if !asked {
+        log::warn!(
+            "Automation of {} denied, quitting it with SIGTERM; unsaved changes in it may be lost",
+            bundle_id
+        );
+        terminate_app(bundle_id);
+    }
 
     // Wait for app to quit
     std::thread::sleep(std::time::Duration::from_secs(3));
@@ -51,3 +83,15 @@ pub fn relaunch_app(app_path: &str) {
         .args(["-g", app_path])
         .output();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_pid_from_lsappinfo() {
+        assert_eq!(parse_lsappinfo_pid("\"pid\"=4821\n"), Some(4821));
+        assert_eq!(parse_lsappinfo_pid(""), None);
+        assert_eq!(parse_lsappinfo_pid("\"pid\"=[ NULL ]"), None);
+    }
+}
@@ -1,61 +1,154 @@
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::sync::OnceLock;
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc;
+use std::sync::Mutex as StdMutex;
+use std::sync::{LazyLock, Once, OnceLock};
 
-use super::askpass;
+use regex::Regex;
 
-static BREW_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+use super::{askpass, expand_tilde};
+use crate::models::{AppSettings, BrewEnvironment};
 
-/// Returns the absolute path to the `brew` binary, resolved once and cached.
+/// The `brew` binary once found. A miss isn't kept, so Homebrew installed later,
+/// or a PATH folder configured later, is picked up on the next call.
+static BREW_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Download sizes curl prints, e.g. `1.2MB / 45.0MB`.
+static RE_DOWNLOAD_SIZES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([\d.]+)\s*([KMG]?B)\s*/\s*([\d.]+)\s*([KMG]?B)").unwrap());
+/// curl's `#` progress bar ending in a percentage.
+static RE_PROGRESS_BAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^#[#=O\-\s]*?(\d{1,3}(?:\.\d+)?)%$").unwrap());
+
+/// Environment settings applied to every `brew` command.
+static BREW_ENV: StdMutex<Option<BrewEnvironment>> = StdMutex::new(None);
+
+/// Store the Homebrew environment from `settings`.
+pub fn apply_brew_settings(settings: &AppSettings) {
+    *BREW_ENV.lock().unwrap_or_else(|e| e.into_inner()) = Some(settings.brew_environment.clone());
+}
+
+/// The configured extra PATH folders, with `~` expanded.
+fn extra_path_dirs(env: &BrewEnvironment) -> Vec<PathBuf> {
+    env.extra_path
+        .as_deref()
+        .unwrap_or_default()
+        .split(':')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(expand_tilde)
+        .collect()
+}
+
+/// Variables to set on `brew` commands: the configured ones plus a PATH with the
+/// extra folders in front of the inherited one.
+pub fn brew_env() -> Vec<(String, String)> {
+    let Some(env) = BREW_ENV.lock().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return Vec::new();
+    };
+    let mut vars = Vec::new();
+    if env.no_auto_update {
+        vars.push(("HOMEBREW_NO_AUTO_UPDATE".to_string(), "1".to_string()));
+    }
+    if let Some(opts) = env.cask_opts.as_deref().map(str::trim).filter(|o| !o.is_empty()) {
+        vars.push(("HOMEBREW_CASK_OPTS".to_string(), opts.to_string()));
+    }
+    let extra = extra_path_dirs(&env);
+    if !extra.is_empty() {
+        let inherited = std::env::var_os("PATH").unwrap_or_default();
+        let path = std::env::join_paths(extra.iter().cloned().chain(std::env::split_paths(&inherited)))
+            .map(|p| p.to_string_lossy().into_owned());
+        if let Ok(path) = path {
+            vars.push(("PATH".to_string(), path));
+        }
+    }
+    vars
+}
+
+/// Returns the absolute path to the `brew` binary, cached once found.
 ///
 /// Checks well-known locations first (works in GUI context where PATH is minimal),
 /// then falls back to `which brew` for non-standard installs.
 pub fn brew_path() -> Option<&'static PathBuf> {
-    BREW_PATH
-        .get_or_init(|| {
-            // Apple Silicon
-            let apple_silicon = PathBuf::from("/opt/homebrew/bin/brew");
-            if apple_silicon.exists() {
-                log::info!("Found brew at {}", apple_silicon.display());
-                return Some(apple_silicon);
-            }
+    if let Some(path) = BREW_PATH.get() {
+        return Some(path);
+    }
+    match find_brew() {
+        Some(path) => Some(BREW_PATH.get_or_init(|| path)),
+        None => {
+            static NOT_FOUND: Once = Once::new();
+            NOT_FOUND.call_once(|| log::warn!("Homebrew not found on this system"));
+            None
+        }
+    }
+}
 
-            // Intel Mac
-            let intel = PathBuf::from("/usr/local/bin/brew");
-            if intel.exists() {
-                log::info!("Found brew at {}", intel.display());
-                return Some(intel);
-            }
+fn find_brew() -> Option<PathBuf> {
+    // Apple Silicon
+    let apple_silicon = PathBuf::from("/opt/homebrew/bin/brew");
+    if apple_silicon.exists() {
+        log::info!("Found brew at {}", apple_silicon.display());
+        return Some(apple_silicon);
+    }
 
-            // Fallback: try `which brew` (works when PATH is available, e.g. cargo tauri dev)
-            if let Ok(output) = Command::new("/usr/bin/which").current_dir("/tmp").arg("brew").output() {
-                if output.status.success() {
-                    let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    if !path_str.is_empty() {
-                        let path = PathBuf::from(&path_str);
-                        if path.exists() {
-                            log::info!("Found brew via which: {}", path.display());
-                            return Some(path);
-                        }
-                    }
+    // Intel Mac
+    let intel = PathBuf::from("/usr/local/bin/brew");
+    if intel.exists() {
+        log::info!("Found brew at {}", intel.display());
+        return Some(intel);
+    }
+
+    // Custom installs in a configured PATH folder
+    let configured = BREW_ENV.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(brew) = configured
+        .map(|env| extra_path_dirs(&env))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dir| dir.join("brew"))
+        .find(|p| p.exists())
+    {
+        log::info!("Found brew in configured PATH: {}", brew.display());
+        return Some(brew);
+    }
+
+    // Fallback: try `which brew` (works when PATH is available, e.g. cargo tauri dev)
+    if let Ok(output) = Command::new("/usr/bin/which").current_dir("/tmp").arg("brew").output() {
+        if output.status.success() {
+            let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path_str.is_empty() {
+                let path = PathBuf::from(&path_str);
+                if path.exists() {
+                    log::info!("Found brew via which: {}", path.display());
+                    return Some(path);
                 }
             }
+        }
+    }
 
-            log::warn!("Homebrew not found on this system");
-            None
-        })
-        .as_ref()
+    None
+}
+
+/// Whether Homebrew has the cask `token` installed, from its Caskroom folder
+/// next to the `brew` binary's prefix. Avoids running `brew` for a single app.
+pub fn is_cask_installed(token: &str) -> bool {
+    brew_path()
+        .and_then(|brew| brew.parent()?.parent())
+        .map(|prefix| prefix.join("Caskroom").join(token).is_dir())
+        .unwrap_or(false)
 }
 
 /// Create a `Command` pre-configured for Homebrew invocations.
 ///
-/// Sets `current_dir("/tmp")` (so brew doesn't complain about cwd) and, when
+/// Sets `current_dir("/tmp")` (so brew doesn't complain about cwd), applies the
+/// configured Homebrew environment and, when
 /// the askpass helper is available, injects `SUDO_ASKPASS` so that any nested
 /// `sudo` calls inside brew can prompt the user via a native macOS dialog
 /// instead of requiring a TTY.
 pub fn brew_command(brew: &Path) -> Command {
     let mut cmd = Command::new(brew);
     cmd.current_dir("/tmp");
+    cmd.envs(brew_env());
     if let Some(ap) = askpass::askpass_path() {
         cmd.env("SUDO_ASKPASS", ap);
         cmd.env(
@@ -65,3 +158,174 @@ pub fn brew_command(brew: &Path) -> Command {
     }
     cmd
 }
+
+/// Progress read from one line of brew output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrewProgress {
+    /// brew started a new step, e.g. downloading or installing
+    Phase(&'static str),
+    /// How far a download is, with the bytes done and the total when brew prints them
+    Download {
+        percent: f64,
+        bytes: Option<(u64, Option<u64>)>,
+    },
+}
+
+/// A size like `10.1MB` in bytes.
+fn parse_size(number: &str, unit: &str) -> Option<u64> {
+    let multiplier = match unit.to_ascii_uppercase().as_str() {
+        "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        _ => return None,
+    };
+    Some((number.parse::<f64>().ok()? * multiplier) as u64)
+}
+
+/// Read progress from a line of brew output: `==>` step headings, curl's
+/// `####   42.0%` progress bar, and the `Downloaded 3.2MB/10.1MB` counts newer
+/// versions of brew print.
+pub fn parse_progress_line(line: &str) -> Option<BrewProgress> {
+    let line = line.trim();
+    if let Some(step) = line.strip_prefix("==> ") {
+        let phase = if step.starts_with("Downloading") || step.starts_with("Fetching") {
+            "Downloading update..."
+        } else if ["Installing", "Pouring", "Moving", "Backing", "Removing", "Linking"]
+            .iter()
+            .any(|verb| step.starts_with(verb))
+        {
+            "Installing update..."
+        } else if step.starts_with("Purging") || step.starts_with("Cleaning") {
+            "Running cleanup..."
+        } else {
+            return None;
+        };
+        return Some(BrewProgress::Phase(phase));
+    }
+
+    if let Some(caps) = RE_DOWNLOAD_SIZES.captures(line) {
+        let done = parse_size(&caps[1], &caps[2])?;
+        let total = parse_size(&caps[3], &caps[4]).filter(|t| *t > 0);
+        let percent = total.map_or(0.0, |t| (done as f64 / t as f64 * 100.0).min(100.0));
+        return Some(BrewProgress::Download {
+            percent,
+            bytes: Some((done, total)),
+        });
+    }
+    let percent = RE_PROGRESS_BAR.captures(line)?[1].parse::<f64>().ok()?;
+    Some(BrewProgress::Download {
+        percent: percent.min(100.0),
+        bytes: None,
+    })
+}
+
+/// Forward brew's progress to an executor's `on_progress`, mapping downloads
+/// onto `start..end` percent and later steps to `end`.
+pub fn progress_forwarder<'a>(
+    on_progress: &'a (dyn Fn(u8, &str, Option<(u64, Option<u64>)>) + Send + Sync),
+    start: u8,
+    end: u8,
+) -> impl FnMut(&str) + 'a {
+    move |line| match parse_progress_line(line) {
+        Some(BrewProgress::Phase(phase)) if phase == "Downloading update..." => {
+            on_progress(start, phase, None)
+        }
+        Some(BrewProgress::Phase(phase)) => on_progress(end, phase, None),
+        Some(BrewProgress::Download { percent, bytes }) => {
+            let span = f64::from(end.saturating_sub(start));
+            let percent = start + (span * percent / 100.0) as u8;
+            on_progress(percent, "Downloading update...", bytes)
+        }
+        None => {}
+    }
+}
+
+/// Run `cmd` like `Command::output`, passing each line of stdout and stderr to
+/// `on_line` as brew prints it. Progress bars redraw with `\r`, so that ends a
+/// line too.
+pub fn output_streaming(cmd: &mut Command, mut on_line: impl FnMut(&str)) -> io::Result<Output> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    let (tx, rx) = mpsc::channel::<(bool, String)>();
+    let stdout = child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>);
+    let stderr = child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>);
+    for (is_stderr, pipe) in [(false, stdout), (true, stderr)] {
+        let Some(mut pipe) = pipe else { continue };
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            let mut line = Vec::new();
+            while let Ok(n) = pipe.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                for &byte in &buf[..n] {
+                    if byte == b'\n' || byte == b'\r' {
+                        let text = String::from_utf8_lossy(&line).to_string();
+                        line.clear();
+                        if tx.send((is_stderr, text)).is_err() {
+                            return;
+                        }
+                    } else {
+                        line.push(byte);
+                    }
+                }
+            }
+            if !line.is_empty() {
+                let _ = tx.send((is_stderr, String::from_utf8_lossy(&line).to_string()));
+            }
+        });
+    }
+    drop(tx);
+
+    let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    for (is_stderr, line) in rx {
+        if line.is_empty() {
+            continue;
+        }
+        on_line(&line);
+        let out = if is_stderr { &mut stderr } else { &mut stdout };
+        out.extend_from_slice(line.as_bytes());
+        out.push(b'\n');
+    }
+    Ok(Output {
+        status: child.wait()?,
+        stdout,
+        stderr,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_from_brew_output() {
+        assert_eq!(
+            parse_progress_line("==> Downloading https://example.com/app-2.0.dmg"),
+            Some(BrewProgress::Phase("Downloading update..."))
+        );
+        assert_eq!(
+            parse_progress_line("==> Moving App 'App.app' to '/Applications/App.app'"),
+            Some(BrewProgress::Phase("Installing update..."))
+        );
+        assert_eq!(
+            parse_progress_line("######################                      42.5%"),
+            Some(BrewProgress::Download {
+                percent: 42.5,
+                bytes: None
+            })
+        );
+        assert_eq!(parse_progress_line("###O#- #  #"), None);
+        assert_eq!(
+            parse_progress_line("Bottle openssl@3 (3.4.0)  Downloaded   5.0MB/ 10.0MB"),
+            Some(BrewProgress::Download {
+                percent: 50.0,
+                bytes: Some((5_000_000, Some(10_000_000)))
+            })
+        );
+        assert_eq!(parse_progress_line("==> Caveats"), None);
+        assert_eq!(parse_progress_line("Warning: app is already up to date"), None);
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::Serialize;
+
+use super::brew::{brew_command, brew_path};
+
+/// Failed brew upgrades in a row before the quick health check runs.
+const FAILURES_BEFORE_CHECK: u32 = 2;
+
+/// Brew upgrades that failed since the last one that worked.
+static CONSECUTIVE_FAILURES: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSeverity {
+    /// Stops brew from installing or upgrading
+    Error,
+    /// Reported by `brew doctor`; may or may not cause failures
+    Warning,
+}
+
+/// One problem found with the Homebrew installation.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthFinding {
+    pub severity: HealthSeverity,
+    pub title: String,
+    pub detail: Option<String>,
+    /// Command that fixes it, to run in Terminal
+    pub fix_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HomebrewHealth {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub prefix: Option<String>,
+    /// Selected `brew config` entries, e.g. `HOMEBREW_PREFIX` or `CLT`
+    pub config: Vec<(String, String)>,
+    pub findings: Vec<HealthFinding>,
+}
+
+/// `brew config` entries worth showing; the rest is mostly build details.
+const CONFIG_KEYS: &[&str] = &[
+    "HOMEBREW_VERSION",
+    "HOMEBREW_PREFIX",
+    "Core tap JSON",
+    "CLT",
+    "Xcode",
+    "macOS",
+];
+
+/// Split `brew doctor` output into one finding per `Warning:` or `Error:` block.
+/// The boilerplate paragraph brew prints before the first warning is dropped.
+pub(crate) fn parse_doctor_output(output: &str) -> Vec<HealthFinding> {
+    let mut findings: Vec<HealthFinding> = Vec::new();
+    let mut detail: Vec<&str> = Vec::new();
+    let flush = |findings: &mut Vec<HealthFinding>, detail: &mut Vec<&str>| {
+        if let Some(last) = findings.last_mut() {
+            let text = detail.join("\n").trim().to_string();
+            last.detail = (!text.is_empty()).then_some(text);
+        }
+        detail.clear();
+    };
+    for line in output.lines() {
+        let heading = line
+            .strip_prefix("Warning: ")
+            .map(|t| (HealthSeverity::Warning, t))
+            .or_else(|| {
+                line.strip_prefix("Error: ")
+                    .map(|t| (HealthSeverity::Error, t))
+            });
+        match heading {
+            Some((severity, title)) => {
+                flush(&mut findings, &mut detail);
+                findings.push(HealthFinding {
+                    severity,
+                    title: title.trim().to_string(),
+                    detail: None,
+                    fix_command: None,
+                });
+            }
+            None if !findings.is_empty() => detail.push(line.trim_end()),
+            None => {}
+        }
+    }
+    flush(&mut findings, &mut detail);
+    findings
+}
+
+/// `Key: value` lines of `brew config`, keeping only the keys in [`CONFIG_KEYS`].
+pub(crate) fn parse_config_output(output: &str) -> Vec<(String, String)> {
+    let entries: HashMap<&str, &str> = output
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    CONFIG_KEYS
+        .iter()
+        .filter_map(|key| {
+            entries
+                .get(key)
+                .map(|value| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// One sentence explaining a failed upgrade from the health findings, preferring
+/// errors over `brew doctor` warnings.
+pub(crate) fn explain(findings: &[HealthFinding]) -> Option<String> {
+    let finding = findings
+        .iter()
+        .find(|f| f.severity == HealthSeverity::Error)
+        .or_else(|| findings.first())?;
+    let mut msg = format!(
+        "Homebrew isn't working properly: {}",
+        finding.title.trim_end_matches(['.', ':'])
+    );
+    if let Some(ref fix) = finding.fix_command {
+        msg.push_str(&format!(". Run '{}' in Terminal.app, then try again.", fix));
+    } else {
+        msg.push('.');
+    }
+    Some(msg)
+}
+
+/// Checks that don't run `brew` itself: the command line tools and whether
+/// the Homebrew folders are writable by this user.
+fn quick_findings(prefix: &Path) -> Vec<HealthFinding> {
+    let mut findings = Vec::new();
+    if !super::is_xcode_clt_installed() {
+        findings.push(HealthFinding {
+            severity: HealthSeverity::Error,
+            title: "The Xcode Command Line Tools aren't installed".to_string(),
+            detail: Some("Homebrew needs them to update itself and to build formulas.".to_string()),
+            fix_command: Some("xcode-select --install".to_string()),
+        });
+    }
+    let not_writable: Vec<String> = ["", "Caskroom", "bin", "var/homebrew"]
+        .iter()
+        .map(|dir| prefix.join(dir))
+        .filter(|dir| dir.exists() && !super::is_writable(dir))
+        .map(|dir| dir.display().to_string())
+        .collect();
+    if !not_writable.is_empty() {
+        findings.push(HealthFinding {
+            severity: HealthSeverity::Error,
+            title: format!("Your user can't write to {}", not_writable.join(", ")),
+            detail: Some(
+                "This usually happens after Homebrew was run with sudo or by another user."
+                    .to_string(),
+            ),
+            fix_command: Some(format!(
+                "sudo chown -R $(whoami) {}",
+                not_writable.join(" ")
+            )),
+        });
+    }
+    findings
+}
+
+fn brew_stdout(brew: &Path, args: &[&str]) -> Option<String> {
+    let output = brew_command(brew).args(args).output().ok()?;
+    // `brew doctor` exits non-zero whenever it has warnings, so keep the output either way
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+/// Check the Homebrew installation. `full` adds `brew doctor` and `brew config`,
+/// which can take several seconds; without it only the quick checks run.
+pub fn check(full: bool) -> HomebrewHealth {
+    let Some(brew) = brew_path() else {
+        return HomebrewHealth {
+            installed: false,
+            version: None,
+            prefix: None,
+            config: Vec::new(),
+            findings: Vec::new(),
+        };
+    };
+    let prefix = brew
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("/"));
+    let mut findings = quick_findings(prefix);
+    let mut config = Vec::new();
+    if full {
+        config = brew_stdout(brew, &["config"])
+            .map(|out| parse_config_output(&out))
+            .unwrap_or_default();
+        findings.extend(
+            brew_stdout(brew, &["doctor"])
+                .map(|out| parse_doctor_output(&out))
+                .unwrap_or_default(),
+        );
+    }
+    HomebrewHealth {
+        installed: true,
+        version: config
+            .iter()
+            .find(|(k, _)| k == "HOMEBREW_VERSION")
+            .map(|(_, v)| v.clone()),
+        prefix: Some(prefix.display().to_string()),
+        config,
+        findings,
+    }
+}
+
+/// Note that a brew upgrade worked, resetting the failure count.
+pub fn record_success() {
+    CONSECUTIVE_FAILURES.store(0, Ordering::Relaxed);
+}
+
+/// Note a failed brew upgrade. After several in a row, run the quick health
+/// check and return an explanation to show instead of brew's raw error output.
+pub fn record_failure() -> Option<String> {
+    let failures = CONSECUTIVE_FAILURES.fetch_add(1, Ordering::Relaxed) + 1;
+    if failures < FAILURES_BEFORE_CHECK {
+        return None;
+    }
+    let health = check(false);
+    let explanation = explain(&health.findings);
+    if let Some(ref msg) = explanation {
+        log::warn!("Homebrew failed {} times in a row: {}", failures, msg);
+    }
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCTOR: &str =
+        "Please note that these warnings are just used to help the Homebrew maintainers
+with debugging if you file an issue. If everything you use Homebrew for is
+working fine: please don't worry or file an issue; just ignore this. Thanks!
+
+Warning: The following directories are not writable by your user:
+/opt/homebrew/share/zsh
+
+You should change the ownership of these directories to your user.
+  sudo chown -R $(whoami) /opt/homebrew/share/zsh
+
+Warning: Some installed casks are deprecated or disabled.
+";
+
+    #[test]
+    fn doctor_output_splits_into_warnings() {
+        let findings = parse_doctor_output(DOCTOR);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].title,
+            "The following directories are not writable by your user:"
+        );
+        assert!(findings[0]
+            .detail
+            .as_deref()
+            .unwrap()
+            .starts_with("/opt/homebrew/share/zsh"));
+        assert!(findings[0]
+            .detail
+            .as_deref()
+            .unwrap()
+            .ends_with("sudo chown -R $(whoami) /opt/homebrew/share/zsh"));
+        assert_eq!(findings[1].detail, None);
+        assert!(parse_doctor_output("Your system is ready to brew.\n").is_empty());
+    }
+
+    #[test]
+    fn config_keeps_known_keys_in_order() {
+        let out = "HOMEBREW_VERSION: 4.4.2\nORIGIN: https://github.com/Homebrew/brew\n\
+HOMEBREW_PREFIX: /opt/homebrew\nCLT: 16.0.0.0.1.1724870825\nmacOS: 15.1-arm64\n";
+        assert_eq!(
+            parse_config_output(out),
+            vec![
+                ("HOMEBREW_VERSION".to_string(), "4.4.2".to_string()),
+                ("HOMEBREW_PREFIX".to_string(), "/opt/homebrew".to_string()),
+                ("CLT".to_string(), "16.0.0.0.1.1724870825".to_string()),
+                ("macOS".to_string(), "15.1-arm64".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn explanation_prefers_errors() {
+        let mut findings = parse_doctor_output(DOCTOR);
+        assert_eq!(
+            explain(&findings).unwrap(),
+            "Homebrew isn't working properly: The following directories are not writable by your user."
+        );
+        findings.push(HealthFinding {
+            severity: HealthSeverity::Error,
+            title: "The Xcode Command Line Tools aren't installed".to_string(),
+            detail: None,
+            fix_command: Some("xcode-select --install".to_string()),
+        });
+        assert_eq!(
+            explain(&findings).unwrap(),
+            "Homebrew isn't working properly: The Xcode Command Line Tools aren't installed. \
+Run 'xcode-select --install' in Terminal.app, then try again."
+        );
+        assert_eq!(explain(&[]), None);
+    }
+}
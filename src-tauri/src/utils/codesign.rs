@@ -0,0 +1,125 @@
+use std::path::Path;
+use std::process::Command;
+
+use base64::Engine;
+use minisign_verify::{PublicKey, Signature};
+
+use crate::models::GatekeeperVerdict;
+
+/// Extract `TeamIdentifier=` from `codesign -dv` output (printed on stderr).
+/// Ad-hoc and unsigned bundles report `TeamIdentifier=not set`, which yields `None`.
+pub(crate) fn parse_team_identifier(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("TeamIdentifier="))
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && *id != "not set")
+        .map(String::from)
+}
+
+/// The code-signing Team ID of a bundle, if it's signed with a Developer ID.
+pub fn team_id(path: &Path) -> Option<String> {
+    let output = Command::new("codesign")
+        .current_dir("/tmp")
+        .args(["-dv", "--verbose=2"])
+        .arg(path)
+        .output()
+        .ok()?;
+    parse_team_identifier(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Verify a bundle's code signature (`codesign --verify --deep --strict`).
+pub fn verify_signature(path: &Path) -> Result<(), String> {
+    let output = Command::new("codesign")
+        .current_dir("/tmp")
+        .args(["--verify", "--deep", "--strict"])
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run codesign: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Check `body` against its base64-encoded minisign signature (the format
+/// `tauri signer sign` writes). `pubkey` is the updater's base64-encoded public key.
+pub fn verify_minisign(body: &[u8], signature_b64: &str, pubkey: &str) -> Result<(), String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let decode = |b64: &str| {
+        engine
+            .decode(b64.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| "not valid base64".to_string())
+    };
+    let public_key = PublicKey::decode(&decode(pubkey)?).map_err(|e| format!("public key: {}", e))?;
+    let signature = Signature::decode(&decode(signature_b64)?).map_err(|e| format!("signature: {}", e))?;
+    public_key
+        .verify(body, &signature, true)
+        .map_err(|e| format!("signature mismatch: {}", e))
+}
+
+/// Parse `spctl --assess --verbose` output (printed on stderr), e.g.
+/// `/Applications/Foo.app: rejected` followed by `source=Unnotarized Developer ID`.
+/// Without a `source=` line, the parenthesized reason after the verdict is used.
+pub(crate) fn parse_spctl_output(output: &str, accepted: bool) -> GatekeeperVerdict {
+    let source = output
+        .lines()
+        .find_map(|line| line.strip_prefix("source="))
+        .or_else(|| {
+            output
+                .lines()
+                .find_map(|line| line.split_once(": rejected").map(|(_, rest)| rest))
+                .map(|rest| rest.trim().trim_start_matches('(').trim_end_matches(')'))
+        })
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+    GatekeeperVerdict { accepted, source }
+}
+
+/// Whether Gatekeeper would let a bundle launch. None if `spctl` couldn't be run.
+pub fn gatekeeper_assess(path: &Path) -> Option<GatekeeperVerdict> {
+    let output = Command::new("spctl")
+        .current_dir("/tmp")
+        .args(["--assess", "--verbose", "--type", "execute"])
+        .arg(path)
+        .output()
+        .ok()?;
+    Some(parse_spctl_output(&String::from_utf8_lossy(&output.stderr), output.status.success()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_team_identifier() {
+        let signed = "Executable=/Applications/macPlus.app/Contents/MacOS/macPlus\n\
+Identifier=com.macplus.app\n\
+Authority=Developer ID Application: Example (ABCDE12345)\n\
+TeamIdentifier=ABCDE12345\n";
+        assert_eq!(parse_team_identifier(signed).as_deref(), Some("ABCDE12345"));
+        assert_eq!(parse_team_identifier("Signature=adhoc\nTeamIdentifier=not set\n"), None);
+        assert_eq!(parse_team_identifier(""), None);
+    }
+
+    #[test]
+    fn test_parse_spctl_output() {
+        let accepted = "/Applications/Foo.app: accepted\nsource=Notarized Developer ID\n";
+        assert_eq!(
+            parse_spctl_output(accepted, true),
+            GatekeeperVerdict { accepted: true, source: Some("Notarized Developer ID".into()) }
+        );
+        let rejected = "/Applications/Foo.app: rejected\nsource=Unnotarized Developer ID\n";
+        assert_eq!(parse_spctl_output(rejected, false).source.as_deref(), Some("Unnotarized Developer ID"));
+        let no_source = "/Applications/Foo.app: rejected (the code is valid but does not seem to be an app)\n";
+        assert_eq!(
+            parse_spctl_output(no_source, false).source.as_deref(),
+            Some("the code is valid but does not seem to be an app")
+        );
+        assert_eq!(parse_spctl_output("", false).source, None);
+    }
+}
@@ -34,3 +34,27 @@ pub async fn run_command_with_timeout(
         ))),
     }
 }
+
+/// Quote a string for a `sh` command line, leaving plain words as they are.
+pub fn shell_quote(s: &str) -> String {
+    if s.chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/.-_,@+".contains(c))
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_shell_arguments() {
+        assert_eq!(shell_quote("visual-studio-code"), "visual-studio-code");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("/Volumes/A B/A.app"), "'/Volumes/A B/A.app'");
+    }
+}
@@ -0,0 +1,256 @@
+//! Disk images macPlus mounts to copy an app out of a download. Every mount is
+//! tracked until it's detached again, so one an error path or crash left
+//! behind can be told apart from one still in use and swept away.
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex as StdMutex;
+
+use crate::platform::translocation;
+use crate::utils::{AppError, AppResult};
+
+/// Mount points of disk images attached by this run and not yet detached.
+static ATTACHED: StdMutex<Vec<PathBuf>> = StdMutex::new(Vec::new());
+
+fn register(mount_point: &Path) {
+    ATTACHED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(mount_point.to_path_buf());
+}
+
+fn unregister(mount_point: &Path) {
+    ATTACHED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|p| p != mount_point);
+}
+
+fn is_attached_by_us(mount_point: &Path) -> bool {
+    ATTACHED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .any(|p| p == mount_point)
+}
+
+/// Whether `mount` is where macPlus attaches a downloaded disk image:
+/// `dmg_mount` inside one of its `macplus-` staging folders.
+pub fn is_update_mount(mount: &str) -> bool {
+    let mount = Path::new(mount);
+    let staging = mount
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().starts_with("macplus-"))
+        .unwrap_or(false);
+    staging && mount.file_name().is_some_and(|name| name == "dmg_mount")
+}
+
+/// Detach the disk image at `mount_point`, forcing it when a process still has
+/// files open on it.
+pub fn detach(mount_point: &Path) -> Result<(), String> {
+    let run = |force: bool| {
+        let mut cmd = Command::new("hdiutil");
+        cmd.current_dir("/tmp")
+            .args(["detach", &mount_point.to_string_lossy(), "-quiet"]);
+        if force {
+            cmd.arg("-force");
+        }
+        cmd.output()
+    };
+    let output = match run(false) {
+        Ok(output) if output.status.success() => output,
+        _ => run(true).map_err(|e| e.to_string())?,
+    };
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    unregister(mount_point);
+    Ok(())
+}
+
+/// A mounted disk image, detached when dropped.
+pub struct Mount {
+    point: PathBuf,
+}
+
+impl Mount {
+    pub fn path(&self) -> &Path {
+        &self.point
+    }
+}
+
+impl Drop for Mount {
+    fn drop(&mut self) {
+        if let Err(e) = detach(&self.point) {
+            log::warn!("Failed to detach {}: {}", self.point.display(), e);
+        }
+    }
+}
+
+/// Whether `hdiutil imageinfo` output says the image has a license agreement.
+fn parse_has_license(imageinfo: &str) -> bool {
+    imageinfo
+        .lines()
+        .any(|line| line.trim() == "Software License Agreement: true")
+}
+
+/// The agreement `hdiutil attach` prints before asking to accept it, without
+/// the question itself.
+fn parse_license_text(stdout: &str) -> String {
+    stdout
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("Agree Y/N?"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Whether the disk image shows a license agreement that has to be accepted
+/// before it mounts.
+pub fn has_license(dmg_path: &Path) -> bool {
+    Command::new("hdiutil")
+        .current_dir("/tmp")
+        .args(["imageinfo", &dmg_path.to_string_lossy()])
+        .output()
+        .map(|out| parse_has_license(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or(false)
+}
+
+/// The license agreement of the disk image, read by starting to attach it
+/// and declining.
+pub fn license_text(dmg_path: &Path) -> String {
+    let child = Command::new("hdiutil")
+        .current_dir("/tmp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .args([
+            "attach",
+            "-nobrowse",
+            "-noautoopen",
+            &dmg_path.to_string_lossy(),
+        ])
+        .spawn();
+    let Ok(mut child) = child else {
+        return String::new();
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"N\n");
+    }
+    child
+        .wait_with_output()
+        .map(|out| parse_license_text(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+fn run_attach(dmg_path: &Path, mount_point: &Path, accept_license: bool) -> AppResult<()> {
+    let mut child = Command::new("hdiutil")
+        .current_dir("/tmp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .args([
+            "attach",
+            "-nobrowse",
+            "-noverify",
+            "-noautoopen",
+            "-mountpoint",
+            &mount_point.to_string_lossy(),
+            &dmg_path.to_string_lossy(),
+        ])
+        .spawn()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to mount DMG: {}", e)))?;
+
+    // Answer the license agreement, if the image has one; without an answer
+    // hdiutil declines it
+    if let Some(mut stdin) = child.stdin.take() {
+        if accept_license {
+            let _ = stdin.write_all(b"Y\n");
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AppError::CommandFailed(format!("Failed to mount DMG: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::CommandFailed(format!(
+            "hdiutil attach failed: {}",
+            stderr
+        )));
+    }
+    Ok(())
+}
+
+/// Mount `dmg_path` at `mount_point`, an existing empty folder, hidden from
+/// Finder. A license agreement the image shows is accepted only with
+/// `accept_license`; the caller decides that per the license setting.
+pub fn attach(dmg_path: &Path, mount_point: &Path, accept_license: bool) -> AppResult<Mount> {
+    // hdiutil reports the resolved path, e.g. /private/var rather than /var
+    let point = std::fs::canonicalize(mount_point).unwrap_or_else(|_| mount_point.to_path_buf());
+    // Registered before hdiutil runs so the sweep never takes a mount that is
+    // still being set up
+    register(&point);
+    match run_attach(dmg_path, &point, accept_license) {
+        Ok(()) => Ok(Mount { point }),
+        Err(e) => {
+            unregister(&point);
+            Err(e)
+        }
+    }
+}
+
+/// Detach macPlus disk images that no update is using, e.g. ones a crash left
+/// mounted, and remove their staging folders. Returns how many were detached.
+pub fn sweep_stale_mounts() -> usize {
+    let mut detached = 0;
+    for mount in translocation::disk_image_mounts() {
+        let point = Path::new(&mount);
+        if !is_update_mount(&mount) || is_attached_by_us(point) {
+            continue;
+        }
+        match detach(point) {
+            Ok(()) => {
+                log::info!("Detached stale disk image at {}", mount);
+                if let Some(staging) = point.parent() {
+                    let _ = std::fs::remove_dir_all(staging);
+                }
+                detached += 1;
+            }
+            Err(e) => log::warn!("Failed to detach stale disk image at {}: {}", mount, e),
+        }
+    }
+    detached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_update_mounts() {
+        assert!(is_update_mount(
+            "/private/var/folders/x/T/macplus-a1B2c3/dmg_mount"
+        ));
+        assert!(is_update_mount("/tmp/macplus-self-update-1/dmg_mount"));
+        assert!(!is_update_mount("/Volumes/Foo"));
+        assert!(!is_update_mount("/tmp/other-a1B2c3/dmg_mount"));
+    }
+
+    #[test]
+    fn reads_license_from_hdiutil_output() {
+        let imageinfo = "Format: UDZO\nProperties:\n\tEncrypted: false\n\t\
+Software License Agreement: true\n\tCompressed: true\n";
+        assert!(parse_has_license(imageinfo));
+        assert!(!parse_has_license(
+            "Properties:\n\tSoftware License Agreement: false\n"
+        ));
+        assert_eq!(
+            parse_license_text("LICENSE\n\nUse at your own risk.\nAgree Y/N? "),
+            "LICENSE\n\nUse at your own risk."
+        );
+    }
+}
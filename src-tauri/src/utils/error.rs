@@ -31,12 +31,31 @@ pub enum AppError {
     Custom(String),
 }
 
+impl AppError {
+    /// The error message in the configured language, as sent to the frontend.
+    pub fn localized(&self) -> String {
+        use super::i18n::{tr, tr_args};
+        let (template, detail) = match self {
+            AppError::Database(e) => ("Database error: {error}", e.to_string()),
+            AppError::Network(e) => ("Network error: {error}", e.to_string()),
+            AppError::Io(e) => ("IO error: {error}", e.to_string()),
+            AppError::Plist(e) => ("Plist error: {error}", e.to_string()),
+            AppError::Xml(e) => ("XML parse error: {error}", e.clone()),
+            AppError::VersionParse(e) => ("Version parse error: {error}", e.clone()),
+            AppError::CommandFailed(e) => ("Command failed: {error}", tr(e)),
+            AppError::NotFound(e) => ("Not found: {error}", e.clone()),
+            AppError::Custom(e) => return tr(e),
+        };
+        tr_args(template, &[("error", detail.as_str())])
+    }
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        serializer.serialize_str(&self.localized())
     }
 }
 
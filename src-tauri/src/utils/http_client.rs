@@ -1,13 +1,332 @@
-use reqwest::Client;
+use reqwest::{Certificate, Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, Response, StatusCode};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+use crate::models::{AppSettings, ProxyMode};
+
+use super::{expand_tilde, keychain, AppError, AppResult};
+
 /// App user-agent string derived from Cargo.toml version at compile time.
 pub const APP_USER_AGENT: &str = concat!("macPlus/", env!("CARGO_PKG_VERSION"));
 
-pub fn create_http_client() -> Client {
-    Client::builder()
+/// Proxy and CA settings the shared client was built with, reused for download
+/// clients so every request takes the same route. Changes apply after a restart.
+#[derive(Debug, Clone, Default)]
+struct NetworkSettings {
+    proxy_mode: ProxyMode,
+    proxy_url: Option<String>,
+    proxy_bypass: Option<String>,
+    proxy_username: Option<String>,
+    custom_ca_path: Option<String>,
+}
+
+impl NetworkSettings {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            proxy_mode: settings.proxy_mode,
+            proxy_url: settings.proxy_url.clone(),
+            proxy_bypass: settings.proxy_bypass.clone(),
+            proxy_username: settings.proxy_username.clone(),
+            custom_ca_path: settings.custom_ca_path.clone(),
+        }
+    }
+}
+
+static NETWORK: OnceLock<NetworkSettings> = OnceLock::new();
+
+/// Timeouts and retries from settings, applied per request so changes take effect
+/// without rebuilding the client.
+static REQUEST_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(30);
+static CHECKER_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(60);
+static MAX_RETRIES: AtomicU32 = AtomicU32::new(1);
+
+/// Delay before the first retry; doubled for each further attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn apply_proxy(builder: ClientBuilder, network: &NetworkSettings) -> AppResult<ClientBuilder> {
+    match network.proxy_mode {
+        // reqwest reads the macOS system proxy configuration by default
+        ProxyMode::System => Ok(builder),
+        ProxyMode::None => Ok(builder.no_proxy()),
+        ProxyMode::Manual => {
+            let url = non_empty(&network.proxy_url)
+                .ok_or_else(|| AppError::Custom("Manual proxy is selected but no proxy URL is set".into()))?;
+            let mut proxy = Proxy::all(url)
+                .map_err(|e| AppError::Custom(format!("Invalid proxy URL \"{}\": {}", url, e)))?
+                .no_proxy(non_empty(&network.proxy_bypass).and_then(NoProxy::from_string));
+            if let Some(username) = non_empty(&network.proxy_username) {
+                let password = keychain::get_secret(keychain::PROXY_PASSWORD)?.unwrap_or_default();
+                proxy = proxy.basic_auth(username, &password);
+            }
+            Ok(builder.proxy(proxy))
+        }
+    }
+}
+
+/// Trust the certificates in a PEM bundle in addition to the built-in roots,
+/// for networks that inspect TLS with their own CA.
+fn apply_custom_ca(builder: ClientBuilder, network: &NetworkSettings) -> AppResult<ClientBuilder> {
+    let Some(path) = non_empty(&network.custom_ca_path) else {
+        return Ok(builder);
+    };
+    let path = expand_tilde(path);
+    let pem = std::fs::read(&path).map_err(|e| {
+        AppError::Custom(format!("Failed to read CA bundle {}: {}", path.display(), e))
+    })?;
+    let certs = Certificate::from_pem_bundle(&pem)
+        .map_err(|e| AppError::Custom(format!("Invalid CA bundle {}: {}", path.display(), e)))?;
+    if certs.is_empty() {
+        return Err(AppError::Custom(format!("No certificates found in {}", path.display())));
+    }
+    Ok(builder.tls_certs_merge(certs))
+}
+
+/// Apply the configured proxy and CA bundle. On a bad configuration the error is
+/// logged and the builder falls back to the system defaults so checks keep working
+/// where the network allows it.
+fn apply_network(builder: ClientBuilder) -> ClientBuilder {
+    let network = NETWORK.get().cloned().unwrap_or_default();
+    let configured = apply_proxy(builder, &network).and_then(|b| apply_custom_ca(b, &network));
+    match configured {
+        Ok(builder) => builder,
+        Err(e) => {
+            log::warn!("Ignoring network settings: {}", e);
+            Client::builder()
+        }
+    }
+}
+
+/// Store the timeout and retry settings. Values are clamped to sane ranges.
+pub fn apply_timeout_settings(settings: &AppSettings) {
+    REQUEST_TIMEOUT_SECS.store(settings.request_timeout_secs.clamp(5, 300) as u64, Ordering::Relaxed);
+    CHECKER_TIMEOUT_SECS.store(settings.checker_timeout_secs.clamp(10, 600) as u64, Ordering::Relaxed);
+    MAX_RETRIES.store(settings.network_retries.min(5), Ordering::Relaxed);
+}
+
+/// Time allowed for a single HTTP request, including reading the response.
+pub fn request_timeout() -> Duration {
+    Duration::from_secs(REQUEST_TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+/// Time allowed for one update checker to finish for one app, retries included.
+pub fn checker_timeout() -> Duration {
+    Duration::from_secs(CHECKER_TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+fn is_transient_error(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.is_request()
+}
+
+/// Send a request, retrying connection failures, timeouts, 429 and 5xx responses
+/// up to the configured retry count with exponential backoff. The last response
+/// or error is returned as-is.
+async fn send_retrying(request: RequestBuilder) -> reqwest::Result<Response> {
+    let retries = MAX_RETRIES.load(Ordering::Relaxed);
+    let mut attempt = 0;
+    loop {
+        // Requests with streaming bodies can't be cloned, so they get a single attempt
+        let Some(this_attempt) = request.try_clone() else {
+            return request.send().await;
+        };
+        let can_retry = attempt < retries;
+        match this_attempt.send().await {
+            Ok(resp) if can_retry && is_transient_status(resp.status()) => {
+                log::debug!("{} returned {} — retrying", resp.url(), resp.status());
+            }
+            Err(e) if can_retry && is_transient_error(&e) => {
+                log::debug!("Request failed ({}) — retrying", e);
+            }
+            result => return result,
+        }
+        tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+tokio::task_local! {
+    /// Status of the last response [`send`] returned inside [`capture_status`].
+    static LAST_STATUS: Cell<Option<u16>>;
+    /// Whether a request inside [`capture_status`] was held back by [`note_deferred`].
+    static DEFERRED: Cell<bool>;
+}
+
+/// Run `fut` and return, along with its output, the HTTP status of the last
+/// response it got from [`send`], e.g. to tell which status a checker saw, and
+/// whether it held back a request to stay within a rate limit.
+pub async fn capture_status<F: Future>(fut: F) -> (F::Output, Option<u16>, bool) {
+    let captured = DEFERRED.scope(Cell::new(false), async move {
+        let output = fut.await;
+        let deferred = DEFERRED.with(Cell::get);
+        (output, LAST_STATUS.with(Cell::get), deferred)
+    });
+    LAST_STATUS.scope(Cell::new(None), captured).await
+}
+
+/// Note that a request wasn't sent to stay within a rate limit, so what the
+/// caller returns instead isn't taken as the source's answer.
+pub fn note_deferred() {
+    let _ = DEFERRED.try_with(|deferred| deferred.set(true));
+}
+
+/// Send an API/feed request with the configured request timeout and retries.
+/// Use this instead of `RequestBuilder::send` in update checkers.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let result = send_retrying(request.timeout(request_timeout())).await;
+    if let Ok(ref resp) = result {
+        let _ = LAST_STATUS.try_with(|status| status.set(Some(resp.status().as_u16())));
+    }
+    result
+}
+
+/// A response kept for conditional requests: its validators are sent back with
+/// the next request, so an unchanged feed costs a 304 rather than the full body.
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// Responses larger than this aren't kept.
+const MAX_CACHED_BODY: usize = 4 * 1024 * 1024;
+
+/// Responses kept by [`get_cached`], keyed by URL. Held in memory only; the
+/// first check after launch fetches everything again. GitHub API responses and
+/// cask.json keep their own caches: GitHub's is saved across launches and
+/// stands in while the rate limit holds requests back, and cask.json is parsed
+/// into an index and is too large to keep here.
+fn response_cache() -> &'static StdMutex<HashMap<String, CachedResponse>> {
+    static CACHE: OnceLock<StdMutex<HashMap<String, CachedResponse>>> = OnceLock::new();
+    CACHE.get_or_init(|| StdMutex::new(HashMap::new()))
+}
+
+/// Send a GET like [`send`], made conditional on the response kept from last
+/// time (`If-None-Match` / `If-Modified-Since`), and return the body of a
+/// successful response. A 304 returns the kept body; any other status `None`.
+pub async fn get_cached(request: RequestBuilder) -> reqwest::Result<Option<String>> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    let url = request.url().to_string();
+    let mut request = RequestBuilder::from_parts(client, request);
+    {
+        let cache = response_cache().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&url) {
+            if let Some(ref etag) = cached.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(ref last_modified) = cached.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+    }
+
+    let resp = send(request).await?;
+    let status = resp.status();
+    if status == StatusCode::NOT_MODIFIED {
+        let cache = response_cache().lock().unwrap_or_else(|e| e.into_inner());
+        return Ok(cache.get(&url).map(|cached| cached.body.clone()));
+    }
+    if !status.is_success() {
+        return Ok(None);
+    }
+
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let body = resp.text().await?;
+
+    let mut cache = response_cache().lock().unwrap_or_else(|e| e.into_inner());
+    if (etag.is_some() || last_modified.is_some()) && body.len() <= MAX_CACHED_BODY {
+        let cached = CachedResponse {
+            etag,
+            last_modified,
+            body: body.clone(),
+        };
+        cache.insert(url, cached);
+    } else {
+        cache.remove(&url);
+    }
+    Ok(Some(body))
+}
+
+/// Forget the responses kept for conditional requests.
+pub fn clear_response_cache() {
+    let mut cache = response_cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.clear();
+}
+
+/// Start a download with the configured retries but no overall timeout, since large
+/// files can legitimately take minutes. Pair with a client from [`download_client_builder`]
+/// so a stalled transfer still fails.
+pub async fn send_download(request: RequestBuilder) -> reqwest::Result<Response> {
+    send_retrying(request).await
+}
+
+/// Size in bytes of the file at `url`, from the Content-Length of a HEAD request.
+/// Redirects are followed; web pages (a vendor's download landing page rather
+/// than the file) report None.
+pub async fn content_length(client: &Client, url: &str) -> Option<u64> {
+    let resp = send(client.head(url)).await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let header = |name| resp.headers().get(name).and_then(|v| v.to_str().ok());
+    if header(reqwest::header::CONTENT_TYPE).is_some_and(|t| t.starts_with("text/")) {
+        return None;
+    }
+    header(reqwest::header::CONTENT_LENGTH)
+        .and_then(|len| len.parse::<u64>().ok())
+        .filter(|&len| len > 0)
+}
+
+/// Reject a proxy URL or CA bundle that couldn't be used, so the mistake surfaces
+/// when saving rather than as failing checks after the next restart. A manual proxy
+/// without a URL yet is allowed while the user fills it in.
+pub fn validate_network_settings(settings: &AppSettings) -> AppResult<()> {
+    let network = NetworkSettings::from_settings(settings);
+    if network.proxy_mode == ProxyMode::Manual {
+        if let Some(url) = non_empty(&network.proxy_url) {
+            Proxy::all(url).map_err(|e| AppError::Custom(format!("Invalid proxy URL \"{}\": {}", url, e)))?;
+        }
+    }
+    apply_custom_ca(Client::builder(), &network).map(|_| ())
+}
+
+/// Client builder for large downloads: the user's proxy and CA settings, plus a
+/// read timeout so a transfer that stops making progress fails instead of hanging.
+pub fn download_client_builder() -> ClientBuilder {
+    apply_network(Client::builder())
+        .user_agent(APP_USER_AGENT)
+        .connect_timeout(Duration::from_secs(10))
+        .read_timeout(request_timeout())
+}
+
+pub fn create_http_client(settings: &AppSettings) -> Client {
+    let _ = NETWORK.set(NetworkSettings::from_settings(settings));
+    apply_timeout_settings(settings);
+
+    apply_network(Client::builder())
         .user_agent(APP_USER_AGENT)
-        .timeout(Duration::from_secs(30))
+        .timeout(request_timeout())
         .connect_timeout(Duration::from_secs(10))
         .gzip(true)
         .http2_adaptive_window(true)
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::sync::OnceLock;
+
+use crate::models::{AppSettings, Language};
+
+/// Translations keyed by the English message, gettext-style: a message without an
+/// entry is shown in English. Placeholders are written `{name}` and must appear
+/// unchanged in every translation.
+const CATALOGS: &[(Language, &str)] = &[
+    (Language::De, include_str!("../../locales/de.json")),
+    (Language::Fr, include_str!("../../locales/fr.json")),
+    (Language::Es, include_str!("../../locales/es.json")),
+];
+
+/// Language in effect, resolved from settings (never `Language::System`).
+static ACTIVE: StdMutex<Language> = StdMutex::new(Language::En);
+
+fn catalogs() -> &'static HashMap<Language, HashMap<String, String>> {
+    static PARSED: OnceLock<HashMap<Language, HashMap<String, String>>> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(lang, json)| {
+                let catalog = serde_json::from_str(json).unwrap_or_else(|e| {
+                    log::warn!("Invalid {:?} message catalog: {}", lang, e);
+                    HashMap::new()
+                });
+                (*lang, catalog)
+            })
+            .collect()
+    })
+}
+
+/// First supported language in the user's macOS language list, else English.
+fn system_language() -> Language {
+    let languages = dirs::home_dir()
+        .map(|h| h.join("Library/Preferences/.GlobalPreferences.plist"))
+        .and_then(|p| plist::Value::from_file(p).ok())
+        .and_then(|v| v.as_dictionary()?.get("AppleLanguages")?.as_array().cloned())
+        .unwrap_or_default();
+    languages
+        .iter()
+        .filter_map(|v| v.as_string())
+        .find_map(language_for_code)
+        .unwrap_or(Language::En)
+}
+
+/// Map a BCP 47 code such as "de-CH" or "es-419" to a supported language.
+fn language_for_code(code: &str) -> Option<Language> {
+    match code.split(['-', '_']).next()? {
+        "en" => Some(Language::En),
+        "de" => Some(Language::De),
+        "fr" => Some(Language::Fr),
+        "es" => Some(Language::Es),
+        _ => None,
+    }
+}
+
+/// Switch the backend language to the one in `settings`.
+pub fn apply_language(settings: &AppSettings) {
+    let language = match settings.language {
+        Language::System => system_language(),
+        other => other,
+    };
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = language;
+}
+
+fn active() -> Language {
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn lookup(language: Language, msg: &str) -> &str {
+    catalogs()
+        .get(&language)
+        .and_then(|c| c.get(msg))
+        .map_or(msg, String::as_str)
+}
+
+fn substitute(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// Translate a message. Strings that aren't in the catalog (e.g. already formatted
+/// text) pass through unchanged, so it's safe to apply to any phase or error.
+pub fn tr(msg: &str) -> String {
+    lookup(active(), msg).to_string()
+}
+
+/// Translate a message and fill in its `{name}` placeholders.
+pub fn tr_args(msg: &str, args: &[(&str, &str)]) -> String {
+    substitute(lookup(active(), msg), args)
+}
+
+/// Translate the singular or plural form of a message for `count`, filling in
+/// `{count}` and any other placeholders.
+pub fn tr_count(one: &str, other: &str, count: usize, args: &[(&str, &str)]) -> String {
+    let language = active();
+    // French uses the singular for 0 as well as 1
+    let singular = match language {
+        Language::Fr => count <= 1,
+        _ => count == 1,
+    };
+    let count = count.to_string();
+    let mut all_args = vec![("count", count.as_str())];
+    all_args.extend_from_slice(args);
+    substitute(lookup(language, if singular { one } else { other }), &all_args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::collections::BTreeSet;
+
+    fn placeholders(text: &str) -> BTreeSet<String> {
+        let re = Regex::new(r"\{(\w+)\}").unwrap();
+        re.captures_iter(text).map(|c| c[1].to_string()).collect()
+    }
+
+    #[test]
+    fn catalogs_parse_and_keep_placeholders() {
+        for (lang, json) in CATALOGS {
+            let catalog: HashMap<String, String> = serde_json::from_str(json).unwrap();
+            for (msg, translation) in &catalog {
+                assert!(!translation.trim().is_empty(), "{:?}: empty translation for {:?}", lang, msg);
+                assert_eq!(placeholders(msg), placeholders(translation), "{:?}: {:?}", lang, msg);
+            }
+        }
+    }
+
+    #[test]
+    fn catalogs_cover_the_same_messages() {
+        let keys: Vec<BTreeSet<String>> = CATALOGS
+            .iter()
+            .map(|(_, json)| serde_json::from_str::<HashMap<String, String>>(json).unwrap().into_keys().collect())
+            .collect();
+        for (i, other) in keys.iter().enumerate().skip(1) {
+            let missing: Vec<_> = keys[0].symmetric_difference(other).collect();
+            assert!(missing.is_empty(), "{:?} differs from {:?}: {:?}", CATALOGS[i].0, CATALOGS[0].0, missing);
+        }
+    }
+
+    #[test]
+    fn language_codes() {
+        assert_eq!(language_for_code("de-CH"), Some(Language::De));
+        assert_eq!(language_for_code("es-419"), Some(Language::Es));
+        assert_eq!(language_for_code("en_GB"), Some(Language::En));
+        assert_eq!(language_for_code("ja-JP"), None);
+    }
+
+    #[test]
+    fn substitute_fills_placeholders() {
+        assert_eq!(
+            substitute("{app} updated to {version}", &[("app", "Foo"), ("version", "2.0")]),
+            "Foo updated to 2.0"
+        );
+        assert_eq!(lookup(Language::En, "Check for Updates"), "Check for Updates");
+        assert_eq!(lookup(Language::De, "not in any catalog"), "not in any catalog");
+    }
+}
@@ -0,0 +1,103 @@
+use security_framework::passwords::{delete_generic_password, get_generic_password, set_generic_password};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::{AppError, AppResult};
+
+/// Keychain service all macPlus secrets are stored under; the key is the account name.
+const SERVICE: &str = "com.macplus.app";
+
+/// Personal access token sent with GitHub API requests (raises the rate limit).
+pub const GITHUB_TOKEN: &str = "github_token";
+
+/// Password for an authenticating proxy (the username lives in settings).
+pub const PROXY_PASSWORD: &str = "proxy_password";
+
+/// `errSecItemNotFound`
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+/// Values already read this session, so hot paths (every GitHub request) don't hit
+/// the Keychain each time. `None` records a confirmed miss.
+fn cache() -> &'static Mutex<HashMap<String, Option<String>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn validate_key(key: &str) -> AppResult<()> {
+    let valid = !key.is_empty()
+        && key.len() <= 64
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Custom(format!("Invalid secret key: {}", key)))
+    }
+}
+
+/// Store a secret in the login Keychain, replacing any existing value.
+pub fn set_secret(key: &str, value: &str) -> AppResult<()> {
+    validate_key(key)?;
+    set_generic_password(SERVICE, key, value.as_bytes())
+        .map_err(|e| AppError::Custom(format!("Keychain write failed for {}: {}", key, e)))?;
+    if let Ok(mut cache) = cache().lock() {
+        cache.insert(key.to_string(), Some(value.to_string()));
+    }
+    Ok(())
+}
+
+/// Read a secret from the login Keychain. Returns None when it isn't set.
+pub fn get_secret(key: &str) -> AppResult<Option<String>> {
+    validate_key(key)?;
+    if let Some(cached) = cache().lock().ok().and_then(|c| c.get(key).cloned()) {
+        return Ok(cached);
+    }
+
+    let value = match get_generic_password(SERVICE, key) {
+        Ok(bytes) => Some(
+            String::from_utf8(bytes)
+                .map_err(|_| AppError::Custom(format!("Keychain item {} is not valid UTF-8", key)))?,
+        ),
+        Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => None,
+        Err(e) => {
+            return Err(AppError::Custom(format!("Keychain read failed for {}: {}", key, e)));
+        }
+    };
+    if let Ok(mut cache) = cache().lock() {
+        cache.insert(key.to_string(), value.clone());
+    }
+    Ok(value)
+}
+
+/// Remove a secret from the login Keychain. Deleting a missing secret is not an error.
+pub fn delete_secret(key: &str) -> AppResult<()> {
+    validate_key(key)?;
+    match delete_generic_password(SERVICE, key) {
+        Ok(()) => {}
+        Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => {}
+        Err(e) => {
+            return Err(AppError::Custom(format!("Keychain delete failed for {}: {}", key, e)));
+        }
+    }
+    if let Ok(mut cache) = cache().lock() {
+        cache.insert(key.to_string(), None);
+    }
+    Ok(())
+}
+
+/// The GitHub token, if one is configured. Errors are logged and treated as unset
+/// until the token is next saved or removed, so a locked or denied Keychain isn't
+/// asked again on every request.
+pub fn github_token() -> Option<String> {
+    match get_secret(GITHUB_TOKEN) {
+        Ok(token) => token.filter(|t| !t.trim().is_empty()),
+        Err(e) => {
+            log::debug!("{}", e);
+            if let Ok(mut cache) = cache().lock() {
+                cache.insert(GITHUB_TOKEN.to_string(), None);
+            }
+            None
+        }
+    }
+}
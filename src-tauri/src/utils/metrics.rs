@@ -0,0 +1,136 @@
+//! Opt-in usage metrics, kept only in the local database: how long update
+//! checks take, how often each source finds an update, how much is downloaded
+//! and why updates fail. Nothing is sent anywhere; the user can export a
+//! summary that holds counts only.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use crate::db::Database;
+use crate::models::AppSettings;
+
+/// Days of metrics kept in the database.
+const RETENTION_DAYS: u32 = 90;
+
+pub const CYCLE: &str = "cycle";
+pub const CHECKER: &str = "checker";
+pub const CHECKER_ERROR: &str = "checker_error";
+pub const DOWNLOAD: &str = "download";
+pub const FAILURE: &str = "failure";
+
+/// A metric waiting to be written: `count` events of `kind` for `name` (a
+/// source or failure category, empty otherwise), summing to `total`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricSample {
+    pub kind: &'static str,
+    pub name: String,
+    pub count: u64,
+    pub total: u64,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Metrics recorded since the last [`flush`].
+static PENDING: StdMutex<Vec<MetricSample>> = StdMutex::new(Vec::new());
+
+/// Start or stop collecting per the user's setting. Metrics not yet written
+/// are dropped when collection stops.
+pub fn apply_metrics_settings(settings: &AppSettings) {
+    ENABLED.store(settings.collect_metrics, Ordering::Relaxed);
+    if !settings.collect_metrics {
+        PENDING.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Add to the sample of the same kind and name. Every check cycle keeps a
+/// sample of its own, so the slowest one can be told apart.
+fn add_to(pending: &mut Vec<MetricSample>, kind: &'static str, name: &str, count: u64, total: u64) {
+    if kind != CYCLE {
+        if let Some(sample) = pending
+            .iter_mut()
+            .find(|s| s.kind == kind && s.name == name)
+        {
+            sample.count += count;
+            sample.total += total;
+            return;
+        }
+    }
+    pending.push(MetricSample {
+        kind,
+        name: name.to_string(),
+        count,
+        total,
+    });
+}
+
+fn add(kind: &'static str, name: &str, count: u64, total: u64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    add_to(&mut pending, kind, name, count, total);
+}
+
+/// A finished update check cycle over `apps` apps.
+pub fn record_cycle(duration: Duration, apps: usize) {
+    add(CYCLE, "", apps as u64, duration.as_millis() as u64);
+}
+
+/// One run of the update checker for `source`.
+pub fn record_checker(source: &str, found: bool, failed: bool) {
+    add(CHECKER, source, 1, found as u64);
+    if failed {
+        add(CHECKER_ERROR, source, 1, 0);
+    }
+}
+
+/// A finished download of `bytes`.
+pub fn record_download(bytes: u64) {
+    add(DOWNLOAD, "", 1, bytes);
+}
+
+/// A failed update, by its error code.
+pub fn record_failure(category: &str) {
+    add(FAILURE, category, 1, 0);
+}
+
+/// Write the metrics recorded since the last flush and drop ones older than
+/// the retention period.
+pub fn flush(db: &Database) {
+    let pending = std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()));
+    if pending.is_empty() {
+        return;
+    }
+    if let Err(e) = db.insert_metrics(&pending) {
+        log::warn!("Failed to save metrics: {}", e);
+    }
+    let _ = db.prune_metrics_older_than(RETENTION_DAYS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_samples_except_cycles() {
+        let mut pending = Vec::new();
+        add_to(&mut pending, CHECKER, "sparkle", 1, 1);
+        add_to(&mut pending, CHECKER, "sparkle", 1, 0);
+        add_to(&mut pending, CHECKER, "github_releases", 1, 0);
+        add_to(&mut pending, CYCLE, "", 120, 8000);
+        add_to(&mut pending, CYCLE, "", 120, 9500);
+
+        assert_eq!(pending.len(), 4);
+        assert_eq!(
+            pending[0],
+            MetricSample {
+                kind: CHECKER,
+                name: "sparkle".into(),
+                count: 2,
+                total: 1,
+            }
+        );
+        assert_eq!(pending.iter().filter(|s| s.kind == CYCLE).count(), 2);
+    }
+}
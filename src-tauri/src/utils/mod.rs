@@ -1,12 +1,24 @@
 pub mod app_lifecycle;
 pub mod askpass;
 pub mod brew;
+pub mod brew_health;
+pub mod codesign;
 pub mod command;
+pub mod disk_image;
 pub mod error;
 pub mod http_client;
+pub mod i18n;
+pub mod keychain;
+pub mod metrics;
 pub mod plist_parser;
 pub mod sanitize;
+pub mod staging;
 pub mod sudo_session;
+pub mod trash;
+
+use std::sync::Mutex as StdMutex;
+
+use crate::models::AppSettings;
 
 pub use error::{AppError, AppResult};
 
@@ -26,6 +38,55 @@ pub fn is_browser_extension(bundle_id: &str) -> bool {
         .any(|p| bundle_id.starts_with(p))
 }
 
+/// Apps with no cask of their own whose bundle ID or name matches an unrelated
+/// cask. Apple's apps are updated by macOS or the App Store, never Homebrew.
+const DEFAULT_CASK_BLOCKLIST: &[&str] = &[
+    "com.apple.Safari",
+    "com.apple.iWork.Pages",
+    "com.apple.iWork.Numbers",
+    "com.apple.iWork.Keynote",
+    "com.apple.garageband10",
+    "com.apple.iMovieApp",
+    "com.apple.dt.Xcode",
+];
+
+/// Bundle IDs the user reported as matched to the wrong cask.
+static USER_CASK_BLOCKLIST: StdMutex<Vec<String>> = StdMutex::new(Vec::new());
+
+/// Store the user's additions to the cask blocklist from `settings`.
+pub fn apply_cask_blocklist(settings: &AppSettings) {
+    *USER_CASK_BLOCKLIST.lock().unwrap_or_else(|e| e.into_inner()) = settings.cask_blocklist.clone();
+}
+
+/// Returns true if the bundle ID must never be matched against Homebrew casks:
+/// browser extensions, the built-in blocklist and apps the user reported.
+pub fn is_cask_blocked(bundle_id: &str) -> bool {
+    is_browser_extension(bundle_id)
+        || DEFAULT_CASK_BLOCKLIST.iter().any(|b| b.eq_ignore_ascii_case(bundle_id))
+        || USER_CASK_BLOCKLIST
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|b| b.eq_ignore_ascii_case(bundle_id))
+}
+
+/// Expand a leading `~/` to the home folder; other paths are returned as they are.
+pub fn expand_tilde(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
+
+/// Whether this user can write to `path`, without trying to.
+pub fn is_writable(path: &std::path::Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(c_path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    unsafe { libc::access(c_path.as_ptr(), libc::W_OK) == 0 }
+}
+
 /// Check whether Xcode Command Line Tools are installed.
 /// Uses spawn + poll + kill pattern to avoid hanging if xcode-select blocks.
 pub fn is_xcode_clt_installed() -> bool {
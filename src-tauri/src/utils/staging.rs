@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+
+use tempfile::TempDir;
+
+use crate::models::AppSettings;
+use crate::utils::{AppError, AppResult};
+
+/// User-chosen folder for update downloads; None uses the system temp folder.
+static DOWNLOAD_DIR: StdMutex<Option<PathBuf>> = StdMutex::new(None);
+
+/// Store the download folder from `settings`.
+pub fn apply_staging_settings(settings: &AppSettings) {
+    let dir = settings
+        .download_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from);
+    *DOWNLOAD_DIR.lock().unwrap_or_else(|e| e.into_inner()) = dir;
+}
+
+/// Reject a download folder that doesn't exist or can't be written to.
+pub fn validate_download_dir(dir: &str) -> AppResult<()> {
+    let path = Path::new(dir);
+    if !path.is_dir() {
+        return Err(AppError::Custom(format!("Download folder \"{}\" does not exist", dir)));
+    }
+    tempfile::tempdir_in(path)
+        .map(|_| ())
+        .map_err(|e| AppError::Custom(format!("Download folder \"{}\" is not writable: {}", dir, e)))
+}
+
+/// Folder update downloads are staged in. A configured folder on a volume that's
+/// been unplugged or stopped answering falls back to the system temp folder.
+pub fn staging_dir() -> PathBuf {
+    let configured = DOWNLOAD_DIR.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    match configured {
+        Some(dir) if crate::detection::volumes::is_reachable(&dir) && dir.is_dir() => dir,
+        Some(dir) => {
+            log::warn!("Download folder {} is unavailable; using the temp folder", dir.display());
+            std::env::temp_dir()
+        }
+        None => std::env::temp_dir(),
+    }
+}
+
+/// A fresh directory in the staging folder, removed when dropped.
+pub fn tempdir() -> std::io::Result<TempDir> {
+    tempfile::Builder::new().prefix("macplus-").tempdir_in(staging_dir())
+}
@@ -0,0 +1,75 @@
+use std::process::Command;
+
+use super::sudo_session::run_elevated_shell;
+
+fn finder_path(path: &str) -> String {
+    path.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Move a path to Trash via Finder AppleScript (reversible).
+pub fn move_to_trash(path: &str) -> Result<(), String> {
+    trash_item(path).map(|_| ())
+}
+
+/// Move a path to Trash via Finder and return where it landed in the Trash
+/// (Finder renames items on collision), when Finder reports it.
+pub fn trash_item(path: &str) -> Result<Option<String>, String> {
+    let output = Command::new("osascript")
+        .current_dir("/tmp")
+        .args([
+            "-e",
+            &format!(
+                "tell application \"Finder\"\nset t to move POSIX file \"{}\" to trash\nreturn POSIX path of (t as alias)\nend tell",
+                finder_path(path)
+            ),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if output.status.success() {
+        let location = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .trim_end_matches('/')
+            .to_string();
+        Ok(if location.is_empty() { None } else { Some(location) })
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Finder trash failed: {}", stderr.trim()))
+    }
+}
+
+/// Move a path to Trash with elevated privileges.
+pub fn move_to_trash_elevated(path: &str) -> Result<(), String> {
+    let script = format!("tell application \"Finder\" to move POSIX file \"{}\" to trash", finder_path(path));
+    let shell_cmd = format!("osascript -e '{}'", script.replace('\'', "'\\''"));
+    match run_elevated_shell(&shell_cmd) {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "Elevated trash failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("Elevated trash failed: {}", e)),
+    }
+}
+
+/// Ask whether `path` may be deleted permanently. Returns false if the user
+/// declines or the dialog can't be shown.
+pub fn confirm_permanent_delete(app_name: &str, path: &str) -> bool {
+    let message = super::i18n::tr_args(
+        "{app} couldn't be moved to the Trash. Delete the old version permanently to finish the update?",
+        &[("app", app_name)],
+    );
+    let script = format!(
+        "display alert \"{}\" message \"{}\" as critical buttons {{\"{}\", \"{}\"}} default button 1 cancel button 1",
+        finder_path(&message),
+        finder_path(path),
+        finder_path(&super::i18n::tr("Cancel")),
+        finder_path(&super::i18n::tr("Delete Permanently")),
+    );
+    Command::new("osascript")
+        .current_dir("/tmp")
+        .args(["-e", &script])
+        .output()
+        .map(|out| out.status.success())
+        .unwrap_or(false)
+}
@@ -10,6 +10,7 @@ import { useEffect, useMemo, useRef } from "react";
 import { Toaster } from "sonner";
 import { AppDetailSheet } from "@/components/app-detail/AppDetailSheet";
 import { AppListView } from "@/components/app-list/AppListView";
+import { UnusedAppsView } from "@/components/app-list/UnusedAppsView";
 import { default as DesktopShell } from "@/components/layout/DesktopShell";
 import { default as MenuBarPanel } from "@/components/layout/MenuBarPanel";
 import { MenuBarAppList } from "@/components/menubar/MenuBarAppList";
@@ -18,7 +19,10 @@ import { MenuBarSummary } from "@/components/menubar/MenuBarSummary";
 import { SettingsView } from "@/components/settings/SettingsView";
 import { CommandPalette } from "@/components/shared/CommandPalette";
 import { ErrorBoundary } from "@/components/shared/ErrorBoundary";
+import { OfflineBanner } from "@/components/shared/OfflineBanner";
+import { ScheduledUpdatesBanner } from "@/components/shared/ScheduledUpdatesBanner";
 import { SelfUpdateBanner } from "@/components/shared/SelfUpdateBanner";
+import { StartupIssuesBanner } from "@/components/shared/StartupIssuesBanner";
 import { UninstallDialog } from "@/components/shared/UninstallDialog";
 import { UpdateHistoryView } from "@/components/updates/UpdateHistoryView";
 import { UpdatesOverview } from "@/components/updates/UpdatesOverview";
@@ -77,7 +81,8 @@ function DesktopApp() {
     hasSetAutostart.current = true;
     isAutostartEnabled()
       .then((enabled) => {
-        if (settings.launchAtLogin && !enabled) {
+        // Re-register when on so the login item picks up the current launch arguments
+        if (settings.launchAtLogin) {
           enableAutostart().catch(console.error);
         } else if (!settings.launchAtLogin && enabled) {
           disableAutostart().catch(console.error);
@@ -108,6 +113,8 @@ function DesktopApp() {
         return <SettingsView />;
       case "ignored":
         return <AppListView />;
+      case "unused":
+        return <UnusedAppsView />;
       default:
         return <AppListView />;
     }
@@ -119,7 +126,10 @@ function DesktopApp() {
       updateCount={updateCount}
       ignoredCount={ignoredCount}
     >
+      <OfflineBanner />
+      <ScheduledUpdatesBanner />
       <SelfUpdateBanner />
+      <StartupIssuesBanner />
       <div className="flex flex-1 overflow-hidden">
         <AnimatePresence mode="wait">
           <motion.div
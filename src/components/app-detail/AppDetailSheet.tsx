@@ -1,15 +1,35 @@
-import { Bug, Eye, EyeOff, FolderOpen, PackageMinus, Play, RefreshCw, X } from "lucide-react";
+import {
+  Bug,
+  Eye,
+  EyeOff,
+  FolderOpen,
+  PackageMinus,
+  Play,
+  RefreshCw,
+  ScanSearch,
+  X,
+} from "lucide-react";
 import { AnimatePresence, motion } from "motion/react";
 import { useState } from "react";
 import { AppIcon } from "@/components/app-list/AppIcon";
-import { useAppDetail, useToggleIgnored } from "@/hooks/useApps";
+import {
+  useAppDetail,
+  useReportBadMatch,
+  useRescanApp,
+  useToggleIgnored,
+} from "@/hooks/useApps";
 import { useCheckSingleUpdate } from "@/hooks/useAppUpdates";
 import { springs } from "@/lib/animations";
+import { formatBytes } from "@/lib/format-bytes";
 import type { UpdateCheckDiagnostic } from "@/lib/tauri-commands";
 import { debugUpdateCheck, openApp, revealInFinder } from "@/lib/tauri-commands";
 import { cn } from "@/lib/utils";
 import { useUIStore } from "@/stores/uiStore";
 import { AppInfoSection } from "./AppInfoSection";
+import { CaskTokenSection } from "./CaskTokenSection";
+import { IntegritySection } from "./IntegritySection";
+import { LocationIssueBanner } from "./LocationIssueBanner";
+import { RelaunchPolicySection } from "./RelaunchPolicySection";
 import { ReleaseNotesSection } from "./ReleaseNotesSection";
 import { VersionHistorySection } from "./VersionHistorySection";
 
@@ -37,6 +57,8 @@ export function AppDetailSheet() {
 
   const { data: detail, isLoading } = useAppDetail(selectedAppId);
   const toggleIgnored = useToggleIgnored();
+  const reportBadMatch = useReportBadMatch();
+  const rescan = useRescanApp();
   const checkUpdate = useCheckSingleUpdate();
   const [debugResult, setDebugResult] = useState<UpdateCheckDiagnostic | null>(null);
   const [debugLoading, setDebugLoading] = useState(false);
@@ -65,7 +87,7 @@ export function AppDetailSheet() {
     if (detail?.bundleId) {
       setDebugLoading(true);
       try {
-        const result = await debugUpdateCheck(detail.bundleId);
+        const result = await debugUpdateCheck(detail.bundleId, true);
         setDebugResult(result);
       } catch (e) {
         console.error("Debug check failed:", e);
@@ -179,7 +201,23 @@ export function AppDetailSheet() {
                             via {formatSourceType(detail.availableUpdate.sourceType)}
                           </span>
                         )}
+                        {detail.availableUpdate.downloadSize != null && (
+                          <span className="ml-1.5 text-primary/70">
+                            · {formatBytes(detail.availableUpdate.downloadSize)}
+                          </span>
+                        )}
                       </p>
+                      {detail.availableUpdate.sourceType.startsWith("homebrew") &&
+                        detail.installSource !== "homebrew" && (
+                          <button
+                            type="button"
+                            onClick={() => reportBadMatch.mutate(detail.bundleId)}
+                            disabled={reportBadMatch.isPending}
+                            className="mt-1 text-caption text-muted-foreground underline-offset-2 hover:underline disabled:opacity-50"
+                          >
+                            Wrong app? Stop matching to Homebrew
+                          </button>
+                        )}
                     </div>
                   )}
 
@@ -291,6 +329,21 @@ export function AppDetailSheet() {
                       <Bug className={cn("h-3.5 w-3.5", debugLoading && "animate-spin")} />
                       Debug Update Check
                     </button>
+                    <button
+                      type="button"
+                      onClick={() => rescan.mutate(detail.bundleId)}
+                      disabled={rescan.isPending}
+                      className={cn(
+                        "flex items-center justify-center gap-2 rounded-lg",
+                        "border border-border bg-background px-3 py-2",
+                        "text-xs font-medium text-foreground",
+                        "transition-colors hover:bg-muted",
+                        "disabled:opacity-50 disabled:cursor-not-allowed",
+                      )}
+                    >
+                      <ScanSearch className={cn("h-3.5 w-3.5", rescan.isPending && "animate-pulse")} />
+                      Re-detect App
+                    </button>
                   </div>
 
                   {/* Debug output */}
@@ -321,16 +374,64 @@ export function AppDetailSheet() {
                             )}
                           >
                             <span>{c.source}</span>
-                            <span className="font-mono">{c.result}</span>
+                            <span className="font-mono">
+                              {c.result}
+                              {c.elapsedMs !== null && ` · ${c.elapsedMs} ms`}
+                              {c.httpStatus !== null && ` · HTTP ${c.httpStatus}`}
+                            </span>
                           </div>
                         ))}
                       </div>
+                      {debugResult.sparkleFeed && (
+                        <div className="space-y-1 text-caption text-muted-foreground">
+                          <p className="truncate font-mono" title={debugResult.sparkleFeed.url}>
+                            Feed: {debugResult.sparkleFeed.url}
+                          </p>
+                          <p>
+                            HTTP {debugResult.sparkleFeed.httpStatus ?? "—"} |{" "}
+                            {debugResult.sparkleFeed.itemsFound} items |{" "}
+                            {debugResult.sparkleFeed.preReleasesSkipped} pre-releases |{" "}
+                            {debugResult.sparkleFeed.minOsSkipped} need a newer macOS than{" "}
+                            {debugResult.sparkleFeed.macosVersion ?? "this Mac's"}
+                          </p>
+                          {debugResult.sparkleFeed.newestVersion && (
+                            <p>Newest offered: {debugResult.sparkleFeed.newestVersion}</p>
+                          )}
+                          {debugResult.sparkleFeed.xmlError && (
+                            <p className="text-destructive">
+                              XML: {debugResult.sparkleFeed.xmlError}
+                            </p>
+                          )}
+                          {debugResult.sparkleFeed.noMatchReason && (
+                            <p className="text-foreground">
+                              {debugResult.sparkleFeed.noMatchReason}
+                            </p>
+                          )}
+                        </div>
+                      )}
                     </div>
                   )}
 
+                  {/* Translocated / disk image / Downloads warning */}
+                  {detail.locationIssue && <LocationIssueBanner detail={detail} />}
+
                   {/* Info section */}
                   <AppInfoSection detail={detail} />
 
+                  {/* Quit/relaunch behavior */}
+                  <RelaunchPolicySection detail={detail} />
+
+                  {/* Homebrew cask override */}
+                  {detail.installSource !== "mas" && (
+                    <CaskTokenSection
+                      key={`${detail.bundleId}:${detail.homebrewCaskToken}`}
+                      detail={detail}
+                    />
+                  )}
+
+                  {/* Code signature check and repair */}
+                  <IntegritySection detail={detail} />
+
                   {/* Release notes */}
                   <ReleaseNotesSection
                     releaseNotesUrl={detail.availableUpdate?.releaseNotesUrl ?? null}
@@ -338,7 +439,7 @@ export function AppDetailSheet() {
                   />
 
                   {/* Version history */}
-                  <VersionHistorySection />
+                  <VersionHistorySection bundleId={detail.bundleId} />
                 </div>
               )}
             </div>
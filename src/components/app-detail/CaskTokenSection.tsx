@@ -0,0 +1,74 @@
+import { Check, RotateCcw } from "lucide-react";
+import { useState } from "react";
+import { useClearCaskToken, useSetCaskToken } from "@/hooks/useApps";
+import { cn } from "@/lib/utils";
+import type { AppDetail } from "@/types/app";
+
+interface CaskTokenSectionProps {
+  detail: AppDetail;
+}
+
+export function CaskTokenSection({ detail }: CaskTokenSectionProps) {
+  const [draft, setDraft] = useState(detail.homebrewCaskToken ?? "");
+  const setCaskToken = useSetCaskToken();
+  const clearCaskToken = useClearCaskToken();
+
+  const token = draft.trim().toLowerCase();
+  const canSave = token !== "" && token !== detail.homebrewCaskToken && !setCaskToken.isPending;
+
+  const handleSave = () => {
+    if (canSave) {
+      setCaskToken.mutate({ bundleId: detail.bundleId, token });
+    }
+  };
+
+  return (
+    <div className="space-y-1">
+      <h4 className="text-caption-uppercase tracking-wider text-muted-foreground">
+        Homebrew Cask
+      </h4>
+      <div className="rounded-lg border border-border bg-background p-3">
+        <p className="mb-2 text-xs text-muted-foreground">
+          The cask {detail.displayName} is updated from. Change it if the wrong cask was matched.
+        </p>
+        <div className="flex items-center gap-2">
+          <input
+            type="text"
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            onKeyDown={(e) => e.key === "Enter" && handleSave()}
+            placeholder="Not matched to a cask"
+            spellCheck={false}
+            className={cn(
+              "min-w-0 flex-1 rounded-md border border-border bg-background px-2.5 py-1",
+              "font-mono text-xs text-foreground placeholder:text-muted-foreground focus:outline-none",
+            )}
+          />
+          <button
+            type="button"
+            onClick={handleSave}
+            disabled={!canSave}
+            title="Use this cask"
+            className="shrink-0 rounded-md border border-border p-1 text-muted-foreground transition-colors hover:bg-muted hover:text-foreground disabled:opacity-50"
+          >
+            <Check className="h-3.5 w-3.5" />
+          </button>
+          {detail.homebrewCaskToken && (
+            <button
+              type="button"
+              onClick={() => {
+                clearCaskToken.mutate(detail.bundleId);
+                setDraft("");
+              }}
+              disabled={clearCaskToken.isPending}
+              title="Match automatically"
+              className="shrink-0 rounded-md border border-border p-1 text-muted-foreground transition-colors hover:bg-muted hover:text-foreground disabled:opacity-50"
+            >
+              <RotateCcw className="h-3.5 w-3.5" />
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,75 @@
+import { ShieldAlert, ShieldCheck } from "lucide-react";
+import { useRepairApp, useVerifyIntegrity } from "@/hooks/useApps";
+import type { AppDetail } from "@/types/app";
+
+interface IntegritySectionProps {
+  detail: AppDetail;
+}
+
+export function IntegritySection({ detail }: IntegritySectionProps) {
+  const verify = useVerifyIntegrity();
+  const repair = useRepairApp();
+  const report = verify.data?.bundleId === detail.bundleId ? verify.data : undefined;
+
+  return (
+    <div className="space-y-1">
+      <h4 className="text-caption-uppercase tracking-wider text-muted-foreground">Integrity</h4>
+      <div className="rounded-lg border border-border bg-background p-3">
+        {report ? (
+          <div className="space-y-2">
+            <div className="flex items-center gap-2 text-xs">
+              {report.valid ? (
+                <ShieldCheck className="h-3.5 w-3.5 shrink-0 text-success" />
+              ) : (
+                <ShieldAlert className="h-3.5 w-3.5 shrink-0 text-destructive" />
+              )}
+              <span className="text-foreground">
+                {report.valid
+                  ? "Signature valid, no files modified"
+                  : "The app bundle is damaged or has been modified"}
+              </span>
+            </div>
+            {report.problems.length > 0 && (
+              <pre className="max-h-[120px] overflow-y-auto whitespace-pre-wrap font-mono text-[10px] text-muted-foreground">
+                {report.problems.join("\n")}
+              </pre>
+            )}
+            {!report.valid &&
+              (report.repair ? (
+                <button
+                  type="button"
+                  onClick={() => repair.mutate(detail.bundleId, { onSuccess: () => verify.reset() })}
+                  disabled={repair.isPending}
+                  className="rounded-md border border-border px-2.5 py-1 text-xs font-medium text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+                >
+                  {repair.isPending
+                    ? "Reinstalling..."
+                    : report.repair === "homebrew"
+                      ? "Reinstall with Homebrew"
+                      : "Download and Reinstall"}
+                </button>
+              ) : (
+                <p className="text-xs text-muted-foreground">
+                  Download {detail.displayName} again from its developer to repair it.
+                </p>
+              ))}
+          </div>
+        ) : (
+          <div className="flex items-center justify-between gap-3">
+            <p className="text-xs text-muted-foreground">
+              Check the code signature and look for modified or missing files
+            </p>
+            <button
+              type="button"
+              onClick={() => verify.mutate(detail.bundleId)}
+              disabled={verify.isPending}
+              className="shrink-0 rounded-md border border-border px-2.5 py-1 text-xs font-medium text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+            >
+              {verify.isPending ? "Verifying..." : "Verify"}
+            </button>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
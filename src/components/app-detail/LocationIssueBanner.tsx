@@ -0,0 +1,36 @@
+import { AlertTriangle } from "lucide-react";
+import { useMoveToApplications } from "@/hooks/useApps";
+import type { AppDetail, LocationIssue } from "@/types/app";
+
+const ISSUE_TEXT: Record<LocationIssue, string> = {
+  translocated:
+    "macOS is running this app from a temporary read-only copy, so updates can't replace it.",
+  disk_image: "This app is running from a disk image, which is removed when it's ejected.",
+  downloads: "This app is still in your Downloads folder, where updates may not work reliably.",
+};
+
+interface LocationIssueBannerProps {
+  detail: AppDetail;
+}
+
+export function LocationIssueBanner({ detail }: LocationIssueBannerProps) {
+  const moveToApplications = useMoveToApplications();
+  if (!detail.locationIssue) return null;
+
+  return (
+    <div className="flex items-start gap-2 rounded-lg border border-amber-500/30 bg-amber-500/10 p-3">
+      <AlertTriangle className="mt-0.5 h-3.5 w-3.5 shrink-0 text-amber-600 dark:text-amber-400" />
+      <div className="min-w-0 flex-1">
+        <p className="text-xs text-foreground">{ISSUE_TEXT[detail.locationIssue]}</p>
+        <button
+          type="button"
+          onClick={() => moveToApplications.mutate(detail.bundleId)}
+          disabled={moveToApplications.isPending}
+          className="mt-2 rounded-md border border-border bg-background px-2.5 py-1 text-xs font-medium text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+        >
+          {moveToApplications.isPending ? "Moving..." : "Move to Applications"}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,47 @@
+import { CustomSelect } from "@/components/shared/CustomSelect";
+import { useSetRelaunchPolicy } from "@/hooks/useApps";
+import type { AppDetail } from "@/types/app";
+
+const RELAUNCH_POLICIES = [
+  {
+    label: "Quit and reopen",
+    value: "automatic",
+    description: "Close the app to update it and open it again afterwards",
+  },
+  {
+    label: "Quit, don't reopen",
+    value: "keep_closed",
+    description: "Close the app to update it and leave it closed",
+  },
+  {
+    label: "Never quit",
+    value: "never_quit",
+    description: "Wait until you quit the app yourself",
+  },
+] as const;
+
+interface RelaunchPolicySectionProps {
+  detail: AppDetail;
+}
+
+export function RelaunchPolicySection({ detail }: RelaunchPolicySectionProps) {
+  const setRelaunchPolicy = useSetRelaunchPolicy();
+
+  return (
+    <div className="space-y-1">
+      <h4 className="text-caption-uppercase tracking-wider text-muted-foreground">
+        While Open
+      </h4>
+      <div className="rounded-lg border border-border bg-background p-3">
+        <p className="mb-2 text-xs text-muted-foreground">
+          What updates may do when {detail.displayName} is running
+        </p>
+        <CustomSelect
+          value={detail.relaunchPolicy}
+          onChange={(policy) => setRelaunchPolicy.mutate({ bundleId: detail.bundleId, policy })}
+          options={RELAUNCH_POLICIES}
+        />
+      </div>
+    </div>
+  );
+}
@@ -1,15 +1,87 @@
-import { Clock } from "lucide-react";
+import { useQuery } from "@tanstack/react-query";
+import { ArrowUpCircle, Clock, Eye, XCircle } from "lucide-react";
+import { getAppTimeline } from "@/lib/tauri-commands";
+import { cn } from "@/lib/utils";
+import type { AppTimelineEvent } from "@/types/update";
+
+function formatDate(at: string): string {
+  return new Date(`${at}Z`).toLocaleDateString(undefined, {
+    year: "numeric",
+    month: "short",
+    day: "numeric",
+  });
+}
+
+function EventIcon({ kind }: { kind: AppTimelineEvent["kind"] }) {
+  switch (kind) {
+    case "updated":
+      return <ArrowUpCircle className="h-3.5 w-3.5 shrink-0 text-success" />;
+    case "update_failed":
+      return <XCircle className="h-3.5 w-3.5 shrink-0 text-destructive" />;
+    default:
+      return <Eye className="h-3.5 w-3.5 shrink-0 text-muted-foreground" />;
+  }
+}
+
+function describe(event: AppTimelineEvent): string {
+  switch (event.kind) {
+    case "updated":
+      return event.fromVersion ? `Updated from ${event.fromVersion}` : "Updated";
+    case "update_failed":
+      return event.errorMessage ?? "Update failed";
+    default:
+      return "First seen";
+  }
+}
+
+interface VersionHistorySectionProps {
+  bundleId: string;
+}
+
+export function VersionHistorySection({ bundleId }: VersionHistorySectionProps) {
+  const { data: timeline } = useQuery({
+    queryKey: ["app-timeline", bundleId],
+    queryFn: () => getAppTimeline(bundleId),
+    staleTime: 60 * 1000,
+  });
 
-export function VersionHistorySection() {
   return (
     <div className="space-y-1">
       <h4 className="text-caption-uppercase tracking-wider text-muted-foreground">
         Version History
       </h4>
-      <div className="flex flex-col items-center gap-2 rounded-lg border border-border bg-background p-6">
-        <Clock className="h-5 w-5 text-muted-foreground/50" />
-        <p className="text-xs text-muted-foreground">No update history yet</p>
-      </div>
+      {timeline && timeline.length > 0 ? (
+        <div className="divide-y divide-border rounded-lg border border-border bg-background">
+          {timeline.map((event) => (
+            <div
+              key={`${event.kind}-${event.version}-${event.at}`}
+              className="flex items-center gap-2 px-3 py-2"
+            >
+              <EventIcon kind={event.kind} />
+              <div className="min-w-0 flex-1">
+                <p className="font-mono text-xs text-foreground">{event.version}</p>
+                <p
+                  className={cn(
+                    "truncate text-[10px]",
+                    event.kind === "update_failed" ? "text-destructive/70" : "text-muted-foreground",
+                  )}
+                  title={describe(event)}
+                >
+                  {describe(event)}
+                </p>
+              </div>
+              <span className="shrink-0 text-[10px] text-muted-foreground">
+                {formatDate(event.at)}
+              </span>
+            </div>
+          ))}
+        </div>
+      ) : (
+        <div className="flex flex-col items-center gap-2 rounded-lg border border-border bg-background p-6">
+          <Clock className="h-5 w-5 text-muted-foreground/50" />
+          <p className="text-xs text-muted-foreground">No update history yet</p>
+        </div>
+      )}
     </div>
   );
 }
@@ -1,8 +1,8 @@
 import { useVirtualizer } from "@tanstack/react-virtual";
 import { ArrowUpDown, ChevronDown, PackageOpen, Search } from "lucide-react";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
-import { useApps, useToggleIgnored } from "@/hooks/useApps";
-import { useExecuteBulkUpdate } from "@/hooks/useUpdateExecution";
+import { useApps, useSetAppsIgnored } from "@/hooks/useApps";
+import { TONIGHT, useExecuteBulkUpdate, useScheduleUpdates } from "@/hooks/useUpdateExecution";
 import { cn } from "@/lib/utils";
 import { type SortField, useAppFilterStore } from "@/stores/appFilterStore";
 import { useSelectionStore } from "@/stores/selectionStore";
@@ -77,7 +77,8 @@ export function AppListView() {
   } = useAppFilterStore();
   const { selectedIds, toggle, clearSelection } = useSelectionStore();
   const executeBulk = useExecuteBulkUpdate();
-  const toggleIgnored = useToggleIgnored();
+  const scheduleUpdates = useScheduleUpdates();
+  const setAppsIgnored = useSetAppsIgnored();
   const [sortOpen, setSortOpen] = useState(false);
   const sortRef = useRef<HTMLDivElement>(null);
 
@@ -115,7 +116,7 @@ export function AppListView() {
 
   const handleUpdateSelected = useCallback(() => {
     const updatable = processedApps
-      .filter((a) => selectedIds.has(a.bundleId) && a.hasUpdate)
+      .filter((a) => selectedIds.has(a.bundleId) && a.hasUpdate && !a.needsLicense)
       .map((a) => a.bundleId);
     if (updatable.length > 0) {
       executeBulk.mutate(updatable);
@@ -124,26 +125,32 @@ export function AppListView() {
 
   const handleUpdateAll = useCallback(() => {
     const updatable = processedApps
-      .filter((a) => a.hasUpdate && !a.isIgnored)
+      .filter((a) => a.hasUpdate && !a.isIgnored && !a.needsLicense)
       .map((a) => a.bundleId);
     if (updatable.length > 0) {
       executeBulk.mutate(updatable);
     }
   }, [processedApps, executeBulk]);
 
-  const handleIgnoreSelected = useCallback(() => {
-    for (const id of selectedIds) {
-      toggleIgnored.mutate({ bundleId: id, ignored: true });
+  const handleUpdateTonight = useCallback(() => {
+    const updatable = processedApps
+      .filter((a) => selectedIds.has(a.bundleId) && a.hasUpdate && !a.needsLicense)
+      .map((a) => a.bundleId);
+    if (updatable.length > 0) {
+      scheduleUpdates.mutate({ bundleIds: updatable, time: TONIGHT });
+      clearSelection();
     }
+  }, [processedApps, selectedIds, scheduleUpdates, clearSelection]);
+
+  const handleIgnoreSelected = useCallback(() => {
+    setAppsIgnored.mutate({ bundleIds: [...selectedIds], ignored: true });
     clearSelection();
-  }, [selectedIds, toggleIgnored, clearSelection]);
+  }, [selectedIds, setAppsIgnored, clearSelection]);
 
   const handleUnignoreSelected = useCallback(() => {
-    for (const id of selectedIds) {
-      toggleIgnored.mutate({ bundleId: id, ignored: false });
-    }
+    setAppsIgnored.mutate({ bundleIds: [...selectedIds], ignored: false });
     clearSelection();
-  }, [selectedIds, toggleIgnored, clearSelection]);
+  }, [selectedIds, setAppsIgnored, clearSelection]);
 
   const selectedCount = selectedIds.size;
 
@@ -272,6 +279,7 @@ export function AppListView() {
             filterView={filterView}
             onUpdateSelected={handleUpdateSelected}
             onUpdateAll={handleUpdateAll}
+            onUpdateTonight={handleUpdateTonight}
             onIgnoreSelected={handleIgnoreSelected}
             onUnignoreSelected={handleUnignoreSelected}
             onClearSelection={clearSelection}
@@ -155,6 +155,7 @@ export const AppRow = memo(
               variant="compact"
               downloadedBytes={progress.downloadedBytes}
               totalBytes={progress.totalBytes}
+              waiting={progress.waiting}
             />
           ) : app.hasUpdate ? (
             isDelegatedUpdate(app) ? (
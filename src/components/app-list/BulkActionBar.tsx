@@ -6,6 +6,7 @@ interface BulkActionBarProps {
   filterView: string;
   onUpdateSelected: () => void;
   onUpdateAll: () => void;
+  onUpdateTonight?: () => void;
   onIgnoreSelected?: () => void;
   onUnignoreSelected?: () => void;
   onClearSelection: () => void;
@@ -16,6 +17,7 @@ export function BulkActionBar({
   filterView,
   onUpdateSelected,
   onUpdateAll,
+  onUpdateTonight,
   onIgnoreSelected,
   onUnignoreSelected,
   onClearSelection,
@@ -61,6 +63,16 @@ export function BulkActionBar({
                   Update All
                 </button>
 
+                {onUpdateTonight && (
+                  <button
+                    type="button"
+                    onClick={onUpdateTonight}
+                    className="rounded-md border border-border bg-background px-3 py-1.5 text-xs font-semibold text-foreground hover:bg-muted transition-colors"
+                  >
+                    Update Tonight
+                  </button>
+                )}
+
                 {onIgnoreSelected && (
                   <button
                     type="button"
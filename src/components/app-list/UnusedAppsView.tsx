@@ -0,0 +1,188 @@
+import { Hourglass, Package, PackageMinus, RefreshCw } from "lucide-react";
+import { useMemo, useState } from "react";
+import { AppIcon } from "@/components/app-list/AppIcon";
+import {
+  useApps,
+  useOrphanFormulae,
+  useRemoveOrphanFormulae,
+  useUnusedApps,
+} from "@/hooks/useApps";
+import { formatBytes } from "@/lib/format-bytes";
+import { cn } from "@/lib/utils";
+import { useUIStore } from "@/stores/uiStore";
+
+const PERIODS = [
+  { months: 3, label: "3 months" },
+  { months: 6, label: "6 months" },
+  { months: 12, label: "1 year" },
+];
+
+/** Formulae left behind as dependencies of things no longer installed. */
+function OrphanFormulaeSection() {
+  const { data } = useOrphanFormulae();
+  const removeOrphans = useRemoveOrphanFormulae();
+  const formulae = data?.formulae ?? [];
+  if (formulae.length === 0) return null;
+
+  return (
+    <div className="mt-4 flex flex-col gap-2">
+      <div className="flex items-center justify-between px-1">
+        <div>
+          <h2 className="text-sm font-semibold text-foreground">Unused Homebrew Dependencies</h2>
+          <p className="text-footnote text-muted-foreground">
+            Nothing installed needs these anymore ({formatBytes(data?.totalSizeBytes ?? 0)})
+          </p>
+        </div>
+        <button
+          type="button"
+          disabled={removeOrphans.isPending}
+          onClick={() => removeOrphans.mutate(formulae.map((f) => f.name))}
+          className="rounded-md px-2.5 py-1 text-xs font-medium text-destructive transition-colors hover:bg-destructive/10 disabled:opacity-50"
+        >
+          {removeOrphans.isPending ? "Removing…" : "Remove All"}
+        </button>
+      </div>
+      {formulae.map((formula) => (
+        <div
+          key={formula.name}
+          className="grid min-h-[36px] grid-cols-[28px_1fr_auto_auto] items-center gap-2.5 rounded-lg border border-border bg-card px-3"
+        >
+          <Package className="mx-auto h-4 w-4 text-muted-foreground" />
+          <p className="truncate text-sm font-medium leading-tight">{formula.name}</p>
+          <span className="text-footnote text-muted-foreground">
+            {formatBytes(formula.sizeBytes)}
+          </span>
+          <button
+            type="button"
+            disabled={removeOrphans.isPending}
+            onClick={() => removeOrphans.mutate([formula.name])}
+            className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md text-muted-foreground transition-colors hover:bg-destructive/10 hover:text-destructive disabled:opacity-50"
+            title="Remove"
+          >
+            <PackageMinus className="h-3.5 w-3.5" />
+          </button>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+function formatLastUsed(dateStr: string | null): string {
+  if (!dateStr) return "Never opened";
+  const date = new Date(`${dateStr}Z`); // SQLite stores UTC
+  return `Last opened ${date.toLocaleDateString(undefined, {
+    year: "numeric",
+    month: "short",
+    day: "numeric",
+  })}`;
+}
+
+export function UnusedAppsView() {
+  const [months, setMonths] = useState(6);
+  const { data: unused = [], isLoading } = useUnusedApps(months * 30);
+  const { data: apps = [] } = useApps();
+  const setUninstallTarget = useUIStore((s) => s.setUninstallTarget);
+
+  const appsById = useMemo(() => new Map(apps.map((a) => [a.bundleId, a])), [apps]);
+  const totalBytes = unused.reduce((sum, a) => sum + (a.totalSizeBytes ?? 0), 0);
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex items-center justify-between p-4 pb-2">
+        <div>
+          <h1 className="text-title text-foreground">Unused Apps</h1>
+          {unused.length > 0 && (
+            <p className="text-footnote text-muted-foreground">
+              {unused.length} app{unused.length === 1 ? "" : "s"} using {formatBytes(totalBytes)}
+            </p>
+          )}
+        </div>
+        <div className="flex items-center gap-0.5 rounded-md border border-input bg-background p-0.5">
+          {PERIODS.map((p) => (
+            <button
+              key={p.months}
+              type="button"
+              onClick={() => setMonths(p.months)}
+              className={cn(
+                "rounded-sm px-2 py-1 text-xs",
+                months === p.months
+                  ? "bg-accent font-medium text-accent-foreground"
+                  : "text-muted-foreground hover:bg-accent/50",
+              )}
+            >
+              {p.label}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      <div className="flex-1 overflow-y-auto px-3 pb-3">
+        {isLoading ? (
+          <div className="flex items-center justify-center py-16">
+            <RefreshCw className="h-5 w-5 animate-spin text-muted-foreground" />
+          </div>
+        ) : unused.length === 0 ? (
+          <div className="flex h-40 flex-col items-center justify-center gap-2 text-muted-foreground">
+            <Hourglass className="size-10 opacity-40" />
+            <span className="text-sm">Every app has been opened recently</span>
+          </div>
+        ) : (
+          <div className="flex flex-col gap-2">
+            {unused.map((entry) => {
+              const app = appsById.get(entry.bundleId);
+              return (
+                <div
+                  key={entry.bundleId}
+                  className="grid min-h-[44px] grid-cols-[28px_1fr_auto] items-center gap-2.5 rounded-lg border border-border bg-card px-3"
+                >
+                  <AppIcon
+                    iconPath={entry.iconCachePath}
+                    appPath={entry.appPath}
+                    displayName={entry.displayName}
+                    bundleId={entry.bundleId}
+                    size={28}
+                  />
+                  <div className="min-w-0">
+                    <p className="truncate text-sm font-medium leading-tight">{entry.displayName}</p>
+                    <p className="text-footnote leading-tight text-muted-foreground">
+                      {formatLastUsed(entry.lastUsedAt)}
+                    </p>
+                  </div>
+                  <div className="flex items-center gap-2">
+                    {entry.totalSizeBytes != null && (
+                      <span className="text-footnote text-muted-foreground">
+                        {formatBytes(entry.totalSizeBytes)}
+                      </span>
+                    )}
+                    {app && (
+                      <button
+                        type="button"
+                        onClick={() =>
+                          setUninstallTarget({
+                            bundleId: app.bundleId,
+                            displayName: app.displayName,
+                            appPath: app.appPath,
+                            installSource: app.installSource,
+                            iconCachePath: app.iconCachePath,
+                            installedVersion: app.installedVersion,
+                            homebrewCaskToken: app.homebrewCaskToken,
+                            homebrewFormulaName: app.homebrewFormulaName,
+                          })
+                        }
+                        className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md text-muted-foreground transition-colors hover:bg-destructive/10 hover:text-destructive"
+                        title="Uninstall"
+                      >
+                        <PackageMinus className="h-3.5 w-3.5" />
+                      </button>
+                    )}
+                  </div>
+                </div>
+              );
+            })}
+          </div>
+        )}
+        <OrphanFormulaeSection />
+      </div>
+    </div>
+  );
+}
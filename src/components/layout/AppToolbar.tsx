@@ -1,5 +1,5 @@
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { ArrowDownCircle, Clock, EyeOff, LayoutGrid, Search, Settings, X } from "lucide-react";
+import { ArrowDownCircle, Clock, EyeOff, Hourglass, LayoutGrid, Search, Settings, X } from "lucide-react";
 import { motion } from "motion/react";
 import MacPlusLogo from "@/components/shared/MacPlusLogo";
 import { springs } from "@/lib/animations";
@@ -16,6 +16,7 @@ const NAV_ITEMS: NavItem[] = [
   { icon: ArrowDownCircle, view: "updates", label: "Updates" },
   { icon: LayoutGrid, view: "all", label: "All Apps" },
   { icon: EyeOff, view: "ignored", label: "Ignored" },
+  { icon: Hourglass, view: "unused", label: "Unused" },
 ];
 
 interface AppToolbarProps {
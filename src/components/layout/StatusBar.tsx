@@ -96,6 +96,12 @@ export default function StatusBar({
     return `${diffDays}d ago`;
   };
 
+  const formatEta = (secs: number): string => {
+    if (secs < 60) return "less than a minute left";
+    const minutes = Math.round(secs / 60);
+    return `about ${minutes} min left`;
+  };
+
   // Right-side fixed elements (version + connectivity + logo)
   const rightSection = (
     <div className="ml-auto flex shrink-0 items-center gap-2">
@@ -133,22 +139,28 @@ export default function StatusBar({
     }
 
     if (isChecking && checkProgress) {
+      // Homebrew and index phases have no per-app count, so show a pulsing bar instead
+      const perApp = checkProgress.phase === "app_checks";
       const percent =
-        checkProgress.total > 0 ? (checkProgress.checked / checkProgress.total) * 100 : 0;
+        perApp && checkProgress.total > 0 ? (checkProgress.checked / checkProgress.total) * 100 : 100;
 
       return (
         <div className="flex flex-1 items-center gap-2">
           <div className="flex flex-1 flex-col gap-0.5">
             <div className="h-1.5 w-full overflow-hidden rounded-full bg-muted">
               <div
-                className="h-full rounded-full bg-primary shadow-[0_0_6px_var(--primary)] transition-all duration-300"
+                className={cn(
+                  "h-full rounded-full bg-primary shadow-[0_0_6px_var(--primary)] transition-all duration-300",
+                  !perApp && "animate-pulse opacity-40",
+                )}
                 style={{ width: `${percent}%` }}
               />
             </div>
             <span className="text-[10px] tabular-nums text-muted-foreground">
               Checking updates
               {checkProgress.currentApp ? ` \u2014 ${checkProgress.currentApp}` : ""}
-              {checkProgress.total > 0 ? ` (${checkProgress.checked}/${checkProgress.total})` : ""}
+              {perApp && checkProgress.total > 0 ? ` (${checkProgress.checked}/${checkProgress.total})` : ""}
+              {checkProgress.etaSecs != null ? ` \u00b7 ${formatEta(checkProgress.etaSecs)}` : ""}
             </span>
           </div>
           {rightSection}
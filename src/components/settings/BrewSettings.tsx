@@ -0,0 +1,59 @@
+import { ToggleSwitch } from "@/components/shared/ToggleSwitch";
+import type { AppSettings, BrewEnvironment } from "@/types/settings";
+import { TextSetting } from "./NetworkSettings";
+
+interface BrewSettingsProps {
+  settings: AppSettings;
+  onUpdate: (partial: Partial<AppSettings>) => void;
+}
+
+export function BrewSettings({ settings, onUpdate }: BrewSettingsProps) {
+  const env = settings.brewEnvironment;
+  const update = (partial: Partial<BrewEnvironment>) =>
+    onUpdate({ brewEnvironment: { ...env, ...partial } });
+
+  return (
+    <div className="flex flex-col gap-3 rounded-lg border border-border bg-background px-4 py-3">
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Skip Homebrew auto-update</p>
+          <p className="text-xs text-muted-foreground">
+            Don't update Homebrew itself before every cask operation
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={env.noAutoUpdate}
+          onChange={(noAutoUpdate) => update({ noAutoUpdate })}
+        />
+      </div>
+
+      <div className="flex flex-col gap-1.5">
+        <div>
+          <p className="text-sm font-medium text-foreground">Cask options</p>
+          <p className="text-xs text-muted-foreground">
+            Passed as HOMEBREW_CASK_OPTS, e.g. when casks go to a custom app folder
+          </p>
+        </div>
+        <TextSetting
+          value={env.caskOpts}
+          placeholder="--appdir=/Volumes/Apps/Applications"
+          onCommit={(caskOpts) => update({ caskOpts })}
+        />
+      </div>
+
+      <div className="flex flex-col gap-1.5">
+        <div>
+          <p className="text-sm font-medium text-foreground">Extra PATH folders</p>
+          <p className="text-xs text-muted-foreground">
+            Searched first by brew and cask scripts, separated by colons
+          </p>
+        </div>
+        <TextSetting
+          value={env.extraPath}
+          placeholder="~/.local/bin:/opt/tools/bin"
+          onCommit={(extraPath) => update({ extraPath })}
+        />
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,66 @@
+import { CustomSelect } from "@/components/shared/CustomSelect";
+import { useDetectionConflicts, useSetPreferredDetector } from "@/hooks/useApps";
+import type { DetectionCandidate, DetectionConflict } from "@/lib/tauri-commands";
+
+const FIELD_LABELS: Record<string, string> = {
+  path: "location",
+  version: "version",
+  source: "source",
+};
+
+type DetectedValues = Pick<DetectionCandidate, "appPath" | "installedVersion" | "installSource">;
+
+function describeCandidate(candidate: DetectedValues): string {
+  const version = candidate.installedVersion ? ` v${candidate.installedVersion}` : "";
+  return `${candidate.appPath || candidate.installSource}${version}`;
+}
+
+function ConflictRow({ conflict }: { conflict: DetectionConflict }) {
+  const setPreferred = useSetPreferredDetector();
+  const options = [
+    { value: "", label: "Merge automatically", description: describeCandidate(conflict) },
+    ...conflict.candidates.map((c) => ({
+      value: c.detector,
+      label: c.detector,
+      description: describeCandidate(c),
+    })),
+  ];
+
+  return (
+    <div className="rounded-md bg-muted/50 px-3 py-2">
+      <p className="text-xs font-medium text-foreground">{conflict.displayName}</p>
+      <p className="mb-1.5 text-xs text-muted-foreground">
+        Detectors disagree on the {conflict.fields.map((f) => FIELD_LABELS[f] ?? f).join(", ")}
+      </p>
+      <CustomSelect
+        value={conflict.preferredDetector ?? ""}
+        onChange={(detector) =>
+          setPreferred.mutate({ bundleId: conflict.bundleId, detector: detector || null })
+        }
+        options={options}
+        disabled={setPreferred.isPending}
+      />
+    </div>
+  );
+}
+
+export function DetectionConflicts() {
+  const { data: conflicts = [] } = useDetectionConflicts();
+  if (conflicts.length === 0) return null;
+
+  return (
+    <div className="rounded-lg border border-border bg-background px-4 py-3">
+      <div className="mb-3">
+        <p className="text-sm font-medium text-foreground">Detection Conflicts</p>
+        <p className="text-xs text-muted-foreground">
+          Pick which detector to trust for these apps. Applied on the next scan.
+        </p>
+      </div>
+      <div className="space-y-1.5">
+        {conflicts.map((conflict) => (
+          <ConflictRow key={conflict.bundleId} conflict={conflict} />
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -1,10 +1,17 @@
 import { disable, enable, isEnabled } from "@tauri-apps/plugin-autostart";
-import { ChevronRight } from "lucide-react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { ChevronRight, FolderOpen } from "lucide-react";
+import { toast } from "sonner";
 import { CustomSelect } from "@/components/shared/CustomSelect";
 import { ToggleSwitch } from "@/components/shared/ToggleSwitch";
 import { useSettings, useUpdateSettings } from "@/hooks/useSettings";
 import { cn } from "@/lib/utils";
 import type { AppSettings } from "@/types/settings";
+import { BrewSettings } from "./BrewSettings";
+import { GitHubTokenSetting } from "./GitHubTokenSetting";
+import { NetworkSettings } from "./NetworkSettings";
+import { PrivacySettings } from "./PrivacySettings";
+import { ShortcutInput } from "./ShortcutInput";
 
 const CHECK_INTERVALS = [
   { label: "Every 5 minutes", value: 5 },
@@ -16,6 +23,41 @@ const CHECK_INTERVALS = [
   { label: "Daily", value: 1440 },
 ] as const;
 
+const WINDOW_MODES = [
+  { label: "Menu bar popover", value: "popover" },
+  { label: "Standard window", value: "standard" },
+] as const;
+
+const LANGUAGES = [
+  { label: "System default", value: "system" },
+  { label: "English", value: "en" },
+  { label: "Deutsch", value: "de" },
+  { label: "Français", value: "fr" },
+  { label: "Español", value: "es" },
+] as const;
+
+const PERMANENT_DELETE_POLICIES = [
+  { label: "Allow", value: "allow" },
+  { label: "Ask first", value: "ask" },
+  { label: "Never", value: "never" },
+] as const;
+
+const DMG_LICENSE_POLICIES = [
+  { label: "Accept", value: "accept" },
+  { label: "Ask first", value: "prompt" },
+  { label: "Skip update", value: "abort" },
+] as const;
+
+const AUTO_UPDATE_POLICIES = [
+  { label: "Never", value: "manual" },
+  { label: "Security updates only", value: "security_only" },
+] as const;
+
+const UPDATE_CHANNELS = [
+  { label: "Stable", value: "stable" },
+  { label: "Beta (includes prereleases)", value: "beta" },
+] as const;
+
 export function GeneralSettings() {
   const { data: settings, isLoading } = useSettings();
   const updateSettings = useUpdateSettings();
@@ -32,6 +74,35 @@ export function GeneralSettings() {
     updateSettings.mutate({ ...settings, ...partial });
   };
 
+  const handleValidatedUpdate = (partial: Partial<AppSettings>) => {
+    updateSettings.mutate(
+      { ...settings, ...partial },
+      { onError: (err) => toast.error(String(err)) },
+    );
+  };
+
+  const handleChooseArchiveDir = async () => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Select archive folder",
+    });
+    if (selected && typeof selected === "string") {
+      handleUpdate({ archiveDir: selected });
+    }
+  };
+
+  const handleChooseDownloadDir = async () => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Select download folder",
+    });
+    if (selected && typeof selected === "string") {
+      handleValidatedUpdate({ downloadDir: selected });
+    }
+  };
+
   return (
     <div className="space-y-1">
       {/* Launch at login */}
@@ -59,6 +130,21 @@ export function GeneralSettings() {
         />
       </div>
 
+      {/* Start hidden at login */}
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Start hidden at login</p>
+          <p className="text-xs text-muted-foreground">
+            Stay in the menu bar instead of opening the window when launched at login
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.autostartHidden}
+          onChange={(checked) => handleUpdate({ autostartHidden: checked })}
+          disabled={!settings.launchAtLogin}
+        />
+      </div>
+
       {/* Auto-check on launch */}
       <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
         <div>
@@ -87,6 +173,200 @@ export function GeneralSettings() {
           options={CHECK_INTERVALS}
         />
       </div>
+
+      {/* Window mode */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-2">
+          <p className="text-sm font-medium text-foreground">Window style</p>
+          <p className="text-xs text-muted-foreground">
+            A standard window can be resized, appears in the Dock and Cmd+Tab, and remembers
+            where you left it
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.windowMode}
+          onChange={(value) => handleUpdate({ windowMode: value })}
+          options={WINDOW_MODES}
+        />
+      </div>
+
+      {/* Backend language */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-2">
+          <p className="text-sm font-medium text-foreground">Language</p>
+          <p className="text-xs text-muted-foreground">
+            Used for notifications, the menu bar menu, progress messages and errors
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.language}
+          onChange={(value) => handleUpdate({ language: value })}
+          options={LANGUAGES}
+        />
+      </div>
+
+      {/* Self-update channel */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-2">
+          <p className="text-sm font-medium text-foreground">macPlus update channel</p>
+          <p className="text-xs text-muted-foreground">
+            Beta gets new macPlus builds before they're released to everyone
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.updateChannel}
+          onChange={(value) => handleUpdate({ updateChannel: value })}
+          options={UPDATE_CHANNELS}
+        />
+      </div>
+
+      {/* Automatic self-update */}
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Update macPlus automatically</p>
+          <p className="text-xs text-muted-foreground">
+            Download new versions in the background and install them when macPlus quits
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.autoSelfUpdate}
+          onChange={(checked) => handleUpdate({ autoSelfUpdate: checked })}
+        />
+      </div>
+
+      {/* Automatic app updates */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Install app updates automatically</p>
+          <p className="text-xs text-muted-foreground">
+            Security fixes install after each check; other updates wait for you
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.autoUpdatePolicy}
+          onChange={(value) => handleUpdate({ autoUpdatePolicy: value })}
+          options={AUTO_UPDATE_POLICIES}
+        />
+      </div>
+
+      {/* Global shortcuts */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Show/hide shortcut</p>
+          <p className="text-xs text-muted-foreground">Toggle the macPlus window from anywhere</p>
+        </div>
+        <ShortcutInput
+          value={settings.toggleWindowShortcut}
+          onChange={(value) => handleValidatedUpdate({ toggleWindowShortcut: value })}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Check for updates shortcut</p>
+          <p className="text-xs text-muted-foreground">Start an update check from anywhere</p>
+        </div>
+        <ShortcutInput
+          value={settings.checkUpdatesShortcut}
+          onChange={(value) => handleValidatedUpdate({ checkUpdatesShortcut: value })}
+        />
+      </div>
+
+      {/* GitHub API token */}
+      <GitHubTokenSetting />
+
+      {/* Proxy and custom CA */}
+      <NetworkSettings settings={settings} onUpdate={handleValidatedUpdate} />
+
+      {/* Uninstall archive folder */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div className="min-w-0">
+          <p className="text-sm font-medium text-foreground">Archive folder</p>
+          <p className="truncate text-xs text-muted-foreground">
+            {settings.archiveDir ?? "~/Documents/macPlus Archives"}
+          </p>
+        </div>
+        <button
+          type="button"
+          onClick={handleChooseArchiveDir}
+          className={cn(
+            "flex shrink-0 items-center gap-1.5 rounded-md border border-border px-2.5 py-1",
+            "text-xs text-foreground transition-colors hover:bg-muted",
+          )}
+        >
+          <FolderOpen className="h-3 w-3" />
+          Choose...
+        </button>
+      </div>
+
+      {/* Update download folder */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div className="min-w-0">
+          <p className="text-sm font-medium text-foreground">Download folder</p>
+          <p className="truncate text-xs text-muted-foreground">
+            {settings.downloadDir ?? "Where updates are downloaded before installing (default: temp folder)"}
+          </p>
+        </div>
+        <div className="flex shrink-0 items-center gap-2">
+          {settings.downloadDir && (
+            <button
+              type="button"
+              onClick={() => handleUpdate({ downloadDir: null })}
+              className="rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:text-foreground"
+            >
+              Clear
+            </button>
+          )}
+          <button
+            type="button"
+            onClick={handleChooseDownloadDir}
+            className={cn(
+              "flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1",
+              "text-xs text-foreground transition-colors hover:bg-muted",
+            )}
+          >
+            <FolderOpen className="h-3 w-3" />
+            Choose...
+          </button>
+        </div>
+      </div>
+
+      {/* Permanent delete fallback */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Delete old versions permanently</p>
+          <p className="text-xs text-muted-foreground">
+            When an app being updated can't be moved to the Trash
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.permanentDelete}
+          onChange={(value) => handleUpdate({ permanentDelete: value })}
+          options={PERMANENT_DELETE_POLICIES}
+        />
+      </div>
+
+      {/* Disk image license agreements */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">License agreements</p>
+          <p className="text-xs text-muted-foreground">
+            When a downloaded disk image asks to accept a license before it opens
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.dmgLicense}
+          onChange={(value) => handleUpdate({ dmgLicense: value })}
+          options={DMG_LICENSE_POLICIES}
+        />
+      </div>
+
+      {/* Environment for brew commands */}
+      <BrewSettings settings={settings} onUpdate={handleUpdate} />
+
+      {/* Data retention and privacy */}
+      <PrivacySettings settings={settings} onUpdate={handleUpdate} />
+
       {/* Setup link */}
       <button
         type="button"
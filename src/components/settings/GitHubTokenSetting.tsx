@@ -0,0 +1,82 @@
+import { KeyRound } from "lucide-react";
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { deleteSecret, getSecret, SECRET_KEYS, setSecret } from "@/lib/tauri-commands";
+import { cn } from "@/lib/utils";
+
+export function GitHubTokenSetting() {
+  const [hasToken, setHasToken] = useState(false);
+  const [draft, setDraft] = useState("");
+
+  useEffect(() => {
+    getSecret(SECRET_KEYS.githubToken)
+      .then((token) => setHasToken(!!token))
+      .catch(console.error);
+  }, []);
+
+  const handleSave = () => {
+    const token = draft.trim();
+    if (!token) return;
+    setSecret(SECRET_KEYS.githubToken, token)
+      .then(() => {
+        setHasToken(true);
+        setDraft("");
+        toast.success("GitHub token saved to Keychain");
+      })
+      .catch((err) => toast.error(String(err)));
+  };
+
+  const handleRemove = () => {
+    deleteSecret(SECRET_KEYS.githubToken)
+      .then(() => setHasToken(false))
+      .catch((err) => toast.error(String(err)));
+  };
+
+  return (
+    <div className="rounded-lg border border-border bg-background px-4 py-3">
+      <div className="mb-2">
+        <p className="text-sm font-medium text-foreground">GitHub token</p>
+        <p className="text-xs text-muted-foreground">
+          Optional. Raises the GitHub API rate limit for update checks. Stored in your Keychain.
+        </p>
+      </div>
+      {hasToken ? (
+        <div className="flex items-center justify-between">
+          <span className="flex items-center gap-1.5 text-xs text-muted-foreground">
+            <KeyRound className="h-3 w-3" />
+            Token saved
+          </span>
+          <button
+            type="button"
+            onClick={handleRemove}
+            className="rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted"
+          >
+            Remove
+          </button>
+        </div>
+      ) : (
+        <div className="flex items-center gap-2">
+          <input
+            type="password"
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            onKeyDown={(e) => e.key === "Enter" && handleSave()}
+            placeholder="ghp_..."
+            className={cn(
+              "min-w-0 flex-1 rounded-md border border-border bg-background px-2.5 py-1",
+              "text-xs text-foreground placeholder:text-muted-foreground focus:outline-none",
+            )}
+          />
+          <button
+            type="button"
+            onClick={handleSave}
+            disabled={!draft.trim()}
+            className="shrink-0 rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+          >
+            Save
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,120 @@
+import {
+  AlertTriangle,
+  CheckCircle2,
+  RefreshCw,
+  Stethoscope,
+  Terminal,
+  XCircle,
+} from "lucide-react";
+import { useState } from "react";
+import {
+  checkHomebrewHealth,
+  type HomebrewHealth as Health,
+  openTerminalWithCommand,
+} from "@/lib/tauri-commands";
+import { cn } from "@/lib/utils";
+
+function summary(health: Health): string {
+  const count = health.findings.length;
+  if (count > 0) return `${count} problem${count === 1 ? "" : "s"} found`;
+  return health.version ? `No problems found (Homebrew ${health.version})` : "No problems found";
+}
+
+export function HomebrewHealth() {
+  const [health, setHealth] = useState<Health | null>(null);
+  const [running, setRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const runCheck = async () => {
+    setRunning(true);
+    setError(null);
+    try {
+      setHealth(await checkHomebrewHealth());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <div className="space-y-1">
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div className="flex items-center gap-2">
+          <Stethoscope className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <p className="text-sm font-medium text-foreground">Homebrew Health</p>
+            <p className="text-xs text-muted-foreground">
+              {health ? summary(health) : "Runs brew doctor and checks folder permissions"}
+            </p>
+            {error && <p className="text-xs text-destructive">{error}</p>}
+          </div>
+        </div>
+        <button
+          type="button"
+          onClick={runCheck}
+          disabled={running}
+          className={cn(
+            "flex items-center gap-1 rounded-md px-2.5 py-1",
+            "text-xs font-medium transition-colors",
+            "bg-muted text-muted-foreground hover:bg-muted/80",
+            "disabled:cursor-not-allowed disabled:opacity-50",
+          )}
+        >
+          <RefreshCw className={cn("h-3 w-3", running && "animate-spin")} />
+          {running ? "Checking..." : "Run Check"}
+        </button>
+      </div>
+
+      {health?.findings.map((finding) => (
+        <div
+          key={finding.title}
+          className="flex items-start justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3"
+        >
+          <div className="flex min-w-0 items-start gap-2">
+            {finding.severity === "error" ? (
+              <XCircle className="mt-0.5 h-4 w-4 shrink-0 text-destructive" />
+            ) : (
+              <AlertTriangle className="mt-0.5 h-4 w-4 shrink-0 text-yellow-500" />
+            )}
+            <div className="min-w-0">
+              <p className="text-sm font-medium text-foreground">{finding.title}</p>
+              {finding.detail && (
+                <p className="whitespace-pre-wrap break-words text-xs text-muted-foreground">
+                  {finding.detail}
+                </p>
+              )}
+            </div>
+          </div>
+          {finding.fixCommand && (
+            <button
+              type="button"
+              onClick={() => openTerminalWithCommand(finding.fixCommand ?? "")}
+              className="flex shrink-0 items-center gap-1 rounded-md bg-primary/10 px-2.5 py-1 text-xs font-medium text-primary transition-colors hover:bg-primary/20"
+            >
+              <Terminal className="h-3 w-3" />
+              Fix
+            </button>
+          )}
+        </div>
+      ))}
+
+      {health && health.config.length > 0 && (
+        <div className="rounded-lg border border-border bg-background px-4 py-3">
+          <div className="mb-1 flex items-center gap-1.5">
+            <CheckCircle2 className="h-3.5 w-3.5 text-muted-foreground" />
+            <p className="text-xs font-medium text-foreground">brew config</p>
+          </div>
+          <dl className="grid grid-cols-[auto_1fr] gap-x-3 text-xs">
+            {health.config.map(([key, value]) => (
+              <div key={key} className="contents">
+                <dt className="text-muted-foreground">{key}</dt>
+                <dd className="truncate font-mono text-foreground">{value}</dd>
+              </div>
+            ))}
+          </dl>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,248 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { FolderOpen } from "lucide-react";
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { CustomSelect } from "@/components/shared/CustomSelect";
+import { deleteSecret, getSecret, SECRET_KEYS, setSecret } from "@/lib/tauri-commands";
+import { cn } from "@/lib/utils";
+import type { AppSettings } from "@/types/settings";
+
+const PROXY_MODES = [
+  { label: "System proxy", value: "system" },
+  { label: "No proxy", value: "none" },
+  { label: "Manual", value: "manual" },
+] as const;
+
+const REQUEST_TIMEOUTS = [
+  { label: "15 seconds", value: 15 },
+  { label: "30 seconds", value: 30 },
+  { label: "1 minute", value: 60 },
+  { label: "2 minutes", value: 120 },
+  { label: "5 minutes", value: 300 },
+] as const;
+
+const CHECKER_TIMEOUTS = [
+  { label: "30 seconds", value: 30 },
+  { label: "1 minute", value: 60 },
+  { label: "2 minutes", value: 120 },
+  { label: "5 minutes", value: 300 },
+  { label: "10 minutes", value: 600 },
+] as const;
+
+const RETRY_COUNTS = [
+  { label: "No retries", value: 0 },
+  { label: "1 retry", value: 1 },
+  { label: "2 retries", value: 2 },
+  { label: "3 retries", value: 3 },
+  { label: "5 retries", value: 5 },
+] as const;
+
+const inputClass = cn(
+  "min-w-0 flex-1 rounded-md border border-border bg-background px-2.5 py-1",
+  "text-xs text-foreground placeholder:text-muted-foreground focus:outline-none",
+);
+
+interface TextSettingProps {
+  value: string | null;
+  placeholder: string;
+  onCommit: (value: string | null) => void;
+}
+
+/** Text input that saves on blur or Enter, so settings aren't written per keystroke. */
+export function TextSetting({ value, placeholder, onCommit }: TextSettingProps) {
+  const [draft, setDraft] = useState(value ?? "");
+
+  useEffect(() => setDraft(value ?? ""), [value]);
+
+  const commit = () => {
+    const next = draft.trim() || null;
+    if (next !== value) onCommit(next);
+  };
+
+  return (
+    <input
+      type="text"
+      value={draft}
+      onChange={(e) => setDraft(e.target.value)}
+      onBlur={commit}
+      onKeyDown={(e) => e.key === "Enter" && commit()}
+      placeholder={placeholder}
+      spellCheck={false}
+      className={inputClass}
+    />
+  );
+}
+
+interface NetworkSettingsProps {
+  settings: AppSettings;
+  onUpdate: (partial: Partial<AppSettings>) => void;
+}
+
+export function NetworkSettings({ settings, onUpdate }: NetworkSettingsProps) {
+  const [hasPassword, setHasPassword] = useState(false);
+  const [password, setPassword] = useState("");
+
+  useEffect(() => {
+    getSecret(SECRET_KEYS.proxyPassword)
+      .then((value) => setHasPassword(!!value))
+      .catch(console.error);
+  }, []);
+
+  const handleSavePassword = () => {
+    if (!password) return;
+    setSecret(SECRET_KEYS.proxyPassword, password)
+      .then(() => {
+        setHasPassword(true);
+        setPassword("");
+        toast.success("Proxy password saved to Keychain");
+      })
+      .catch((err) => toast.error(String(err)));
+  };
+
+  const handleRemovePassword = () => {
+    deleteSecret(SECRET_KEYS.proxyPassword)
+      .then(() => setHasPassword(false))
+      .catch((err) => toast.error(String(err)));
+  };
+
+  const handleChooseCaBundle = async () => {
+    const selected = await open({
+      multiple: false,
+      title: "Select CA certificate bundle (PEM)",
+      filters: [{ name: "Certificates", extensions: ["pem", "crt", "cer"] }],
+    });
+    if (typeof selected === "string") {
+      onUpdate({ customCaPath: selected });
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-3 rounded-lg border border-border bg-background px-4 py-3">
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Proxy</p>
+          <p className="text-xs text-muted-foreground">Used for update checks and downloads. Applies after restart.</p>
+        </div>
+        <CustomSelect
+          value={settings.proxyMode}
+          onChange={(value) => onUpdate({ proxyMode: value })}
+          options={PROXY_MODES}
+        />
+      </div>
+
+      {settings.proxyMode === "manual" && (
+        <div className="flex flex-col gap-2">
+          <TextSetting
+            value={settings.proxyUrl}
+            placeholder="http://proxy.example.com:8080 or socks5://host:1080"
+            onCommit={(value) => onUpdate({ proxyUrl: value })}
+          />
+          <TextSetting
+            value={settings.proxyBypass}
+            placeholder="Bypass for: localhost, .corp.example.com"
+            onCommit={(value) => onUpdate({ proxyBypass: value })}
+          />
+          <div className="flex items-center gap-2">
+            <TextSetting
+              value={settings.proxyUsername}
+              placeholder="Username (optional)"
+              onCommit={(value) => onUpdate({ proxyUsername: value })}
+            />
+            {hasPassword ? (
+              <button
+                type="button"
+                onClick={handleRemovePassword}
+                className="shrink-0 rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted"
+              >
+                Remove password
+              </button>
+            ) : (
+              <>
+                <input
+                  type="password"
+                  value={password}
+                  onChange={(e) => setPassword(e.target.value)}
+                  onKeyDown={(e) => e.key === "Enter" && handleSavePassword()}
+                  placeholder="Password"
+                  className={inputClass}
+                />
+                <button
+                  type="button"
+                  onClick={handleSavePassword}
+                  disabled={!password}
+                  className="shrink-0 rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+                >
+                  Save
+                </button>
+              </>
+            )}
+          </div>
+        </div>
+      )}
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Request timeout</p>
+          <p className="text-xs text-muted-foreground">Raise for slow vendor servers</p>
+        </div>
+        <CustomSelect
+          value={settings.requestTimeoutSecs}
+          onChange={(value) => onUpdate({ requestTimeoutSecs: value })}
+          options={REQUEST_TIMEOUTS}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Per-source timeout</p>
+          <p className="text-xs text-muted-foreground">Time one update source may spend on one app</p>
+        </div>
+        <CustomSelect
+          value={settings.checkerTimeoutSecs}
+          onChange={(value) => onUpdate({ checkerTimeoutSecs: value })}
+          options={CHECKER_TIMEOUTS}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Retries</p>
+          <p className="text-xs text-muted-foreground">For timeouts, connection errors and server errors</p>
+        </div>
+        <CustomSelect
+          value={settings.networkRetries}
+          onChange={(value) => onUpdate({ networkRetries: value })}
+          options={RETRY_COUNTS}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3">
+        <div className="min-w-0">
+          <p className="text-sm font-medium text-foreground">Custom CA bundle</p>
+          <p className="truncate text-xs text-muted-foreground">
+            {settings.customCaPath ?? "For networks that inspect TLS traffic"}
+          </p>
+        </div>
+        <div className="flex shrink-0 items-center gap-2">
+          {settings.customCaPath && (
+            <button
+              type="button"
+              onClick={() => onUpdate({ customCaPath: null })}
+              className="rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:text-foreground"
+            >
+              Clear
+            </button>
+          )}
+          <button
+            type="button"
+            onClick={handleChooseCaBundle}
+            className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted"
+          >
+            <FolderOpen className="h-3 w-3" />
+            Choose...
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,47 @@
+import { CustomSelect } from "@/components/shared/CustomSelect";
 import { ToggleSwitch } from "@/components/shared/ToggleSwitch";
 import { useSettings, useUpdateSettings } from "@/hooks/useSettings";
-import type { AppSettings } from "@/types/settings";
+import type { AppSettings, NotificationPolicy } from "@/types/settings";
+
+const TRAY_ICON_STYLES = [
+  { label: "Monochrome", value: "monochrome" },
+  { label: "Colored", value: "colored" },
+] as const;
+
+const TRAY_BADGE_STYLES = [
+  { label: "Number", value: "number" },
+  { label: "Dot", value: "dot" },
+] as const;
+
+const DIGEST_OPTIONS = [
+  { label: "After every check", value: "off" },
+  { label: "Daily summary", value: "daily" },
+  { label: "Weekly summary", value: "weekly" },
+] as const;
+
+const SOUNDS = [
+  { label: "None", value: "none" },
+  { label: "Basso", value: "basso" },
+  { label: "Blow", value: "blow" },
+  { label: "Bottle", value: "bottle" },
+  { label: "Frog", value: "frog" },
+  { label: "Funk", value: "funk" },
+  { label: "Glass", value: "glass" },
+  { label: "Hero", value: "hero" },
+  { label: "Morse", value: "morse" },
+  { label: "Ping", value: "ping" },
+  { label: "Pop", value: "pop" },
+  { label: "Purr", value: "purr" },
+  { label: "Sosumi", value: "sosumi" },
+  { label: "Submarine", value: "submarine" },
+  { label: "Tink", value: "tink" },
+] as const;
+
+const UPDATE_RESULT_OPTIONS = [
+  { label: "Successes and failures", value: "all" },
+  { label: "Failures only", value: "failures_only" },
+  { label: "Never", value: "off" },
+] as const;
 
 export function NotificationSettings() {
   const { data: settings, isLoading } = useSettings();
@@ -18,6 +59,10 @@ export function NotificationSettings() {
     updateSettings.mutate({ ...settings, ...partial });
   };
 
+  const handlePolicyUpdate = (partial: Partial<NotificationPolicy>) => {
+    handleUpdate({ notificationPolicy: { ...settings.notificationPolicy, ...partial } });
+  };
+
   return (
     <div className="space-y-1">
       {/* Notify on updates */}
@@ -34,19 +79,95 @@ export function NotificationSettings() {
         />
       </div>
 
-      {/* Notification sound */}
+      {/* Digest */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-2">
+          <p className="text-sm font-medium text-foreground">Notification frequency</p>
+          <p className="text-xs text-muted-foreground">
+            Summaries group pending and applied updates into a single notification
+          </p>
+        </div>
+        <CustomSelect
+          value={settings.notificationDigest}
+          onChange={(value) => handleUpdate({ notificationDigest: value })}
+          options={DIGEST_OPTIONS}
+          disabled={!settings.notificationOnUpdates}
+        />
+      </div>
+
+      {/* Finished updates */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Finished updates</p>
+          <p className="text-xs text-muted-foreground">Which installed updates show a notification</p>
+        </div>
+        <div className="w-44 shrink-0">
+          <CustomSelect
+            value={settings.notificationPolicy.updateResults}
+            onChange={(value) => handlePolicyUpdate({ updateResults: value })}
+            options={UPDATE_RESULT_OPTIONS}
+            disabled={!settings.notificationOnUpdates}
+          />
+        </div>
+      </div>
+
+      {/* Uninstalls */}
       <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Uninstall notifications</p>
+          <p className="text-xs text-muted-foreground">Show a notification when an uninstall finishes</p>
+        </div>
+        <ToggleSwitch
+          checked={settings.notificationPolicy.onUninstall}
+          onChange={(checked) => handlePolicyUpdate({ onUninstall: checked })}
+          disabled={!settings.notificationOnUpdates}
+        />
+      </div>
+
+      {/* Notification sound */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
         <div>
           <p className="text-sm font-medium text-foreground">Notification sound</p>
-          <p className="text-xs text-muted-foreground">Play the default sound with notifications</p>
+          <p className="text-xs text-muted-foreground">Played with every macPlus notification</p>
+        </div>
+        <div className="w-32 shrink-0">
+          <CustomSelect
+            value={settings.notificationPolicy.sound}
+            onChange={(value) => handlePolicyUpdate({ sound: value })}
+            options={SOUNDS}
+            disabled={!settings.notificationOnUpdates}
+          />
+        </div>
+      </div>
+
+      {/* Focus awareness */}
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Respect Focus</p>
+          <p className="text-xs text-muted-foreground">
+            Hold notifications while a Focus mode is on and deliver them when it ends
+          </p>
         </div>
         <ToggleSwitch
-          checked={settings.notificationSound}
-          onChange={(checked) => handleUpdate({ notificationSound: checked })}
+          checked={settings.deferNotificationsDuringFocus}
+          onChange={(checked) => handleUpdate({ deferNotificationsDuringFocus: checked })}
           disabled={!settings.notificationOnUpdates}
         />
       </div>
 
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Pause automatic updates during Focus</p>
+          <p className="text-xs text-muted-foreground">
+            Hold scheduled updates and macPlus's own download until Focus ends. Security updates still install.
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.deferAutoUpdatesDuringFocus}
+          onChange={(checked) => handleUpdate({ deferAutoUpdatesDuringFocus: checked })}
+        />
+      </div>
+
       {/* Show menu bar icon */}
       <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
         <div>
@@ -64,12 +185,73 @@ export function NotificationSettings() {
         <div>
           <p className="text-sm font-medium text-foreground">Update count badge</p>
           <p className="text-xs text-muted-foreground">
-            Show the number of available updates on the tray icon
+            Show the number of available updates on the tray and Dock icons
           </p>
         </div>
         <ToggleSwitch
           checked={settings.showBadgeCount}
           onChange={(checked) => handleUpdate({ showBadgeCount: checked })}
+        />
+      </div>
+
+      {/* Badge style */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Badge style</p>
+          <p className="text-xs text-muted-foreground">Show the count, or just a dot</p>
+        </div>
+        <div className="w-32 shrink-0">
+          <CustomSelect
+            value={settings.trayBadgeStyle}
+            onChange={(value) => handleUpdate({ trayBadgeStyle: value })}
+            options={TRAY_BADGE_STYLES}
+            disabled={!settings.showBadgeCount || !settings.showMenuBarIcon}
+          />
+        </div>
+      </div>
+
+      {/* Icon style */}
+      <div className="flex items-center justify-between gap-3 rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Menu bar icon style</p>
+          <p className="text-xs text-muted-foreground">
+            Monochrome matches the menu bar; colored stands out
+          </p>
+        </div>
+        <div className="w-32 shrink-0">
+          <CustomSelect
+            value={settings.trayIconStyle}
+            onChange={(value) => handleUpdate({ trayIconStyle: value })}
+            options={TRAY_ICON_STYLES}
+            disabled={!settings.showMenuBarIcon}
+          />
+        </div>
+      </div>
+
+      {/* Compact icon */}
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Compact icon</p>
+          <p className="text-xs text-muted-foreground">Use a smaller icon that takes less menu bar space</p>
+        </div>
+        <ToggleSwitch
+          checked={settings.compactTrayIcon}
+          onChange={(checked) => handleUpdate({ compactTrayIcon: checked })}
+          disabled={!settings.showMenuBarIcon}
+        />
+      </div>
+
+      {/* Hide when idle */}
+      <div className="flex items-center justify-between rounded-lg border border-border bg-background px-4 py-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Hide icon when up to date</p>
+          <p className="text-xs text-muted-foreground">
+            Only show the menu bar icon while updates are available
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.hideTrayWhenIdle}
+          onChange={(checked) => handleUpdate({ hideTrayWhenIdle: checked })}
           disabled={!settings.showMenuBarIcon}
         />
       </div>
@@ -0,0 +1,199 @@
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Loader2 } from "lucide-react";
+import { useState } from "react";
+import { toast } from "sonner";
+import { CustomSelect } from "@/components/shared/CustomSelect";
+import { ToggleSwitch } from "@/components/shared/ToggleSwitch";
+import { formatBytes } from "@/lib/format-bytes";
+import {
+  clearMetrics,
+  deleteAllData,
+  exportMetrics,
+  getMetrics,
+  relaunchSelf,
+} from "@/lib/tauri-commands";
+import type { AppSettings } from "@/types/settings";
+
+const RETENTION_PERIODS = [
+  { label: "Forever", value: 0 },
+  { label: "30 days", value: 30 },
+  { label: "90 days", value: 90 },
+  { label: "6 months", value: 180 },
+  { label: "1 year", value: 365 },
+] as const;
+
+function LocalMetrics() {
+  const queryClient = useQueryClient();
+  const { data: metrics } = useQuery({
+    queryKey: ["metrics"],
+    queryFn: getMetrics,
+    staleTime: 30 * 1000,
+  });
+
+  const handleExport = async () => {
+    const path = await save({
+      defaultPath: "macplus-metrics.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (!path) return;
+    exportMetrics(path)
+      .then(() => toast.success("Metrics exported"))
+      .catch((error) => toast.error("Export failed", { description: String(error) }));
+  };
+
+  const handleClear = () => {
+    clearMetrics()
+      .then(() => queryClient.invalidateQueries({ queryKey: ["metrics"] }))
+      .catch((error) => toast.error(String(error)));
+  };
+
+  if (!metrics) return null;
+  const checks = metrics.checkers.reduce((sum, c) => sum + c.runs, 0);
+  const hits = metrics.checkers.reduce((sum, c) => sum + c.found, 0);
+
+  return (
+    <div className="space-y-1 rounded-md bg-muted/50 px-3 py-2 text-xs text-muted-foreground">
+      <p>
+        {metrics.cycles} update checks, {(metrics.avgCycleMs / 1000).toFixed(1)} s on average,{" "}
+        {(metrics.maxCycleMs / 1000).toFixed(1)} s at most
+      </p>
+      {checks > 0 && (
+        <p>
+          {checks} source checks, {Math.round((hits / checks) * 100)}% found an update
+        </p>
+      )}
+      <p>
+        {metrics.downloads} downloads, {formatBytes(metrics.downloadBytes)}
+      </p>
+      {metrics.failures.length > 0 && (
+        <p>
+          Failures:{" "}
+          {metrics.failures
+            .map((f) => `${f.category.replaceAll("_", " ")} (${f.count})`)
+            .join(", ")}
+        </p>
+      )}
+      <div className="flex gap-2 pt-1">
+        <button
+          type="button"
+          onClick={handleExport}
+          className="rounded-md border border-border px-2.5 py-1 text-xs text-foreground transition-colors hover:bg-muted"
+        >
+          Export...
+        </button>
+        <button
+          type="button"
+          onClick={handleClear}
+          className="rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:text-foreground"
+        >
+          Clear
+        </button>
+      </div>
+    </div>
+  );
+}
+
+interface PrivacySettingsProps {
+  settings: AppSettings;
+  onUpdate: (partial: Partial<AppSettings>) => void;
+}
+
+export function PrivacySettings({ settings, onUpdate }: PrivacySettingsProps) {
+  const [confirming, setConfirming] = useState(false);
+  const [deleting, setDeleting] = useState(false);
+
+  const handleDeleteAll = () => {
+    setDeleting(true);
+    deleteAllData()
+      .then(() => relaunchSelf())
+      .catch((err) => {
+        toast.error(String(err));
+        setDeleting(false);
+        setConfirming(false);
+      });
+  };
+
+  return (
+    <div className="flex flex-col gap-3 rounded-lg border border-border bg-background px-4 py-3">
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Keep history</p>
+          <p className="text-xs text-muted-foreground">Update and uninstall history older than this is deleted</p>
+        </div>
+        <CustomSelect
+          value={settings.historyRetentionDays}
+          onChange={(value) => onUpdate({ historyRetentionDays: value })}
+          options={RETENTION_PERIODS}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Store release notes</p>
+          <p className="text-xs text-muted-foreground">
+            When off, only links to release notes are kept
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.cacheReleaseNotes}
+          onChange={(checked) => onUpdate({ cacheReleaseNotes: checked })}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Collect usage metrics</p>
+          <p className="text-xs text-muted-foreground">
+            Check times, downloads and failures, kept on this Mac for 90 days and never sent.
+            Turning this off deletes them
+          </p>
+        </div>
+        <ToggleSwitch
+          checked={settings.collectMetrics}
+          onChange={(checked) => onUpdate({ collectMetrics: checked })}
+        />
+      </div>
+      {settings.collectMetrics && <LocalMetrics />}
+
+      <div className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-sm font-medium text-foreground">Delete all data</p>
+          <p className="text-xs text-muted-foreground">
+            Removes the app database, settings, caches and Keychain items, then restarts.
+            Uninstall archives are kept.
+          </p>
+        </div>
+        {confirming ? (
+          <div className="flex shrink-0 items-center gap-2">
+            <button
+              type="button"
+              onClick={() => setConfirming(false)}
+              disabled={deleting}
+              className="rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:text-foreground disabled:opacity-50"
+            >
+              Cancel
+            </button>
+            <button
+              type="button"
+              onClick={handleDeleteAll}
+              disabled={deleting}
+              className="flex items-center gap-1.5 rounded-md bg-destructive px-2.5 py-1 text-xs font-medium text-destructive-foreground transition-colors hover:bg-destructive/90 disabled:opacity-50"
+            >
+              {deleting && <Loader2 className="h-3 w-3 animate-spin" />}
+              Delete everything
+            </button>
+          </div>
+        ) : (
+          <button
+            type="button"
+            onClick={() => setConfirming(true)}
+            className="shrink-0 rounded-md border border-border px-2.5 py-1 text-xs text-destructive transition-colors hover:bg-muted"
+          >
+            Delete...
+          </button>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,12 +1,23 @@
-import { useQuery } from "@tanstack/react-query";
-import { open } from "@tauri-apps/plugin-dialog";
-import { AlertCircle, ChevronRight, FolderOpen, X } from "lucide-react";
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { AlertCircle, ChevronRight, Download, FolderOpen, Plus, Upload, X } from "lucide-react";
+import { useState } from "react";
+import { toast } from "sonner";
 import { CustomSelect } from "@/components/shared/CustomSelect";
+import { ToggleSwitch } from "@/components/shared/ToggleSwitch";
 import { useSettings, useUpdateSettings } from "@/hooks/useSettings";
-import { checkPathsExist } from "@/lib/tauri-commands";
+import {
+  type CheckerMetrics,
+  checkPathsExist,
+  exportMappings,
+  getCheckerMetrics,
+  importMappings,
+  type MappingImportReport,
+} from "@/lib/tauri-commands";
 import { cn } from "@/lib/utils";
 import { useAppFilterStore } from "@/stores/appFilterStore";
-import type { AppSettings } from "@/types/settings";
+import type { AppSettings, IgnoreRule, ScanLocation, UpdateSource } from "@/types/settings";
+import { DetectionConflicts } from "./DetectionConflicts";
 
 const DEFAULT_LOCATIONS = ["/Applications", "~/Applications"];
 
@@ -20,17 +31,219 @@ const SCAN_DEPTH_OPTIONS = [
   { value: 3, label: "Deep scan", description: "Scans two subfolders deep — slower but thorough" },
 ] as const;
 
+// 0 stands for "use the default scan depth" (null in settings)
+const LOCATION_DEPTH_OPTIONS = [
+  { value: 0, label: "Default depth" },
+  { value: 1, label: "Top level" },
+  { value: 2, label: "1 subfolder" },
+  { value: 3, label: "2 subfolders" },
+  { value: 4, label: "3 subfolders" },
+] as const;
+
+const IGNORE_SOURCES = [
+  { value: "mas", label: "Mac App Store" },
+  { value: "homebrew", label: "Homebrew casks" },
+  { value: "homebrew_formula", label: "Homebrew formulae" },
+  { value: "direct", label: "Direct downloads" },
+] as const;
+
+// Homebrew's cask and API checkers are one source as far as users are concerned
+const UPDATE_SOURCES: { label: string; sources: UpdateSource[] }[] = [
+  { label: "Sparkle feeds", sources: ["sparkle"] },
+  { label: "Homebrew", sources: ["homebrew_cask", "homebrew_api"] },
+  { label: "Mac App Store", sources: ["mac_app_store"] },
+  { label: "GitHub Releases", sources: ["github_releases"] },
+  { label: "Electron updaters", sources: ["electron"] },
+  { label: "Google Software Update", sources: ["keystone"] },
+  { label: "Microsoft AutoUpdate", sources: ["microsoft_autoupdate"] },
+  { label: "JetBrains Toolbox", sources: ["jetbrains_toolbox"] },
+  { label: "Adobe Creative Cloud", sources: ["adobe_cc"] },
+  { label: "Mozilla", sources: ["mozilla"] },
+];
+
+/** Average check time of a source and how many of its checks failed, since launch. */
+function sourceTiming(metrics: CheckerMetrics[], sources: UpdateSource[]): string | null {
+  const matching = metrics.filter((m) => sources.includes(m.source as UpdateSource));
+  const runs = matching.reduce((sum, m) => sum + m.runs, 0);
+  if (runs === 0) return null;
+  const avgMs = matching.reduce((sum, m) => sum + m.avgMs * m.runs, 0) / runs;
+  const errors = matching.reduce((sum, m) => sum + m.errors, 0);
+  const time = avgMs >= 1000 ? `${(avgMs / 1000).toFixed(1)} s` : `${Math.round(avgMs)} ms`;
+  return errors > 0 ? `avg ${time} · ${errors} failed` : `avg ${time}`;
+}
+
+interface ExclusionListProps {
+  title: string;
+  description: string;
+  placeholder: string;
+  values: string[];
+  onChange: (values: string[]) => void;
+}
+
+function ExclusionList({ title, description, placeholder, values, onChange }: ExclusionListProps) {
+  const [draft, setDraft] = useState("");
+
+  const handleAdd = () => {
+    const value = draft.trim();
+    if (!value) return;
+    if (!values.includes(value)) {
+      onChange([...values, value]);
+    }
+    setDraft("");
+  };
+
+  return (
+    <div>
+      <p className="text-xs font-medium text-foreground">{title}</p>
+      <p className="mb-1.5 text-xs text-muted-foreground">{description}</p>
+      <div className="space-y-1.5">
+        {values.map((value) => (
+          <div
+            key={value}
+            className="flex items-center justify-between rounded-md bg-muted/50 px-3 py-2"
+          >
+            <span className="truncate font-mono text-xs text-foreground">{value}</span>
+            <button
+              type="button"
+              onClick={() => onChange(values.filter((v) => v !== value))}
+              className="ml-2 shrink-0 rounded p-0.5 text-muted-foreground hover:bg-muted hover:text-foreground"
+            >
+              <X className="h-3.5 w-3.5" />
+            </button>
+          </div>
+        ))}
+        <div className="flex items-center gap-2">
+          <input
+            type="text"
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            onKeyDown={(e) => e.key === "Enter" && handleAdd()}
+            placeholder={placeholder}
+            spellCheck={false}
+            className={cn(
+              "min-w-0 flex-1 rounded-md border border-border bg-background px-2.5 py-1",
+              "font-mono text-xs text-foreground placeholder:text-muted-foreground focus:outline-none",
+            )}
+          />
+          <button
+            type="button"
+            onClick={handleAdd}
+            disabled={!draft.trim()}
+            className="shrink-0 rounded-md border border-border p-1 text-muted-foreground transition-colors hover:bg-muted hover:text-foreground disabled:opacity-50"
+          >
+            <Plus className="h-3.5 w-3.5" />
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+const MAPPING_FILTERS = [{ name: "macPlus Mappings", extensions: ["json"] }];
+
+function describeImport(report: MappingImportReport): string {
+  const parts = [`${report.added} added`];
+  if (report.replaced > 0) parts.push(`${report.replaced} replaced`);
+  if (report.unchanged > 0) parts.push(`${report.unchanged} already set`);
+  if (report.invalid.length > 0) parts.push(`${report.invalid.length} invalid skipped`);
+  return parts.join(", ");
+}
+
+function MappingTransfer() {
+  const queryClient = useQueryClient();
+  const [busy, setBusy] = useState(false);
+
+  const handleExport = async () => {
+    const path = await save({ defaultPath: "macplus-mappings.json", filters: MAPPING_FILTERS });
+    if (!path) return;
+    setBusy(true);
+    try {
+      const count = await exportMappings(path);
+      toast.success(`Exported ${count} mapping${count === 1 ? "" : "s"}`);
+    } catch (error) {
+      toast.error("Export failed", { description: String(error) });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const runImport = async (path: string, overwrite: boolean) => {
+    setBusy(true);
+    try {
+      const report = await importMappings(path, overwrite);
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      const conflicts = report.conflicts.length;
+      if (conflicts > 0) {
+        toast.warning(`${conflicts} mapping${conflicts === 1 ? "" : "s"} differ from yours`, {
+          description: `${describeImport(report)}. Your existing mappings were kept for: ${report.conflicts.map((c) => c.current.bundleId).join(", ")}`,
+          action: { label: "Use Imported", onClick: () => runImport(path, true) },
+        });
+      } else {
+        toast.success("Mappings imported", { description: describeImport(report) });
+      }
+    } catch (error) {
+      toast.error("Import failed", { description: String(error) });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleImport = async () => {
+    const path = await open({ multiple: false, filters: MAPPING_FILTERS });
+    if (path && typeof path === "string") {
+      await runImport(path, false);
+    }
+  };
+
+  return (
+    <div className="rounded-lg border border-border bg-background px-4 py-3">
+      <div className="mb-3">
+        <p className="text-sm font-medium text-foreground">Custom Mappings</p>
+        <p className="text-xs text-muted-foreground">
+          Share the GitHub repos, feeds and casks you've set for apps as a file
+        </p>
+      </div>
+      <div className="flex gap-2">
+        <button
+          type="button"
+          onClick={handleExport}
+          disabled={busy}
+          className="flex items-center gap-1.5 rounded-md border border-border px-3 py-1.5 text-xs font-medium text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+        >
+          <Upload className="h-3 w-3" />
+          Export
+        </button>
+        <button
+          type="button"
+          onClick={handleImport}
+          disabled={busy}
+          className="flex items-center gap-1.5 rounded-md border border-border px-3 py-1.5 text-xs font-medium text-foreground transition-colors hover:bg-muted disabled:opacity-50"
+        >
+          <Download className="h-3 w-3" />
+          Import
+        </button>
+      </div>
+    </div>
+  );
+}
+
 export function ScanningSettings() {
   const { data: settings, isLoading } = useSettings();
   const updateSettings = useUpdateSettings();
 
   const { data: pathStatus } = useQuery({
-    queryKey: ["path-status", settings?.scanLocations],
-    queryFn: () => checkPathsExist(settings?.scanLocations ?? []),
+    queryKey: ["path-status", settings?.scanLocations.map((l) => l.path)],
+    queryFn: () => checkPathsExist(settings?.scanLocations.map((l) => l.path) ?? []),
     enabled: !!settings?.scanLocations?.length,
     staleTime: 30 * 1000,
   });
 
+  const { data: checkerMetrics } = useQuery({
+    queryKey: ["checker-metrics"],
+    queryFn: getCheckerMetrics,
+    staleTime: 30 * 1000,
+  });
+
   if (isLoading || !settings) {
     return (
       <div className="rounded-lg border border-border bg-background p-6">
@@ -51,20 +264,43 @@ export function ScanningSettings() {
     });
 
     if (selected && typeof selected === "string") {
-      if (!settings.scanLocations.includes(selected)) {
+      if (!settings.scanLocations.some((l) => l.path === selected)) {
         handleUpdate({
-          scanLocations: [...settings.scanLocations, selected],
+          scanLocations: [
+            ...settings.scanLocations,
+            { path: selected, depth: null, followSymlinks: true },
+          ],
         });
       }
     }
   };
 
-  const handleRemoveLocation = (location: string) => {
+  const handleRemoveLocation = (path: string) => {
     handleUpdate({
-      scanLocations: settings.scanLocations.filter((l) => l !== location),
+      scanLocations: settings.scanLocations.filter((l) => l.path !== path),
     });
   };
 
+  const handleUpdateLocation = (path: string, partial: Partial<ScanLocation>) => {
+    handleUpdate({
+      scanLocations: settings.scanLocations.map((l) => (l.path === path ? { ...l, ...partial } : l)),
+    });
+  };
+
+  const prefixRules = settings.ignoreRules
+    .filter((r) => r.kind === "bundle_id_prefix")
+    .map((r) => r.value);
+
+  const handleSourceRule = (source: string, on: boolean) => {
+    const others = settings.ignoreRules.filter((r) => !(r.kind === "source" && r.value === source));
+    handleUpdate({ ignoreRules: on ? [...others, { kind: "source", value: source }] : others });
+  };
+
+  const handleSourceEnabled = (sources: UpdateSource[], on: boolean) => {
+    const others = settings.disabledSources.filter((s) => !sources.includes(s));
+    handleUpdate({ disabledSources: on ? others : [...others, ...sources] });
+  };
+
   return (
     <div className="space-y-1">
       {/* Scan Locations */}
@@ -77,51 +313,66 @@ export function ScanningSettings() {
         </div>
         <div className="space-y-1.5">
           {settings.scanLocations.map((location) => {
-            const isDefault = DEFAULT_LOCATIONS.includes(location);
-            const exists = pathStatus?.[location] ?? true;
-            const isVolume = location.startsWith("/Volumes/");
+            const isDefault = DEFAULT_LOCATIONS.includes(location.path);
+            const exists = pathStatus?.[location.path] ?? true;
+            const isVolume = location.path.startsWith("/Volumes/");
             return (
               <div
-                key={location}
-                className={cn(
-                  "flex items-center justify-between rounded-md px-3 py-2",
-                  exists ? "bg-muted/50" : "bg-destructive/5",
-                )}
+                key={location.path}
+                className={cn("rounded-md px-3 py-2", exists ? "bg-muted/50" : "bg-destructive/5")}
               >
-                <div className="flex items-center gap-2 min-w-0">
-                  {exists ? (
-                    <FolderOpen className="h-3.5 w-3.5 shrink-0 text-muted-foreground" />
-                  ) : (
-                    <AlertCircle className="h-3.5 w-3.5 shrink-0 text-destructive" />
-                  )}
-                  <span
-                    className={cn(
-                      "truncate text-xs",
-                      exists ? "text-foreground" : "text-destructive",
+                <div className="flex items-center justify-between">
+                  <div className="flex items-center gap-2 min-w-0">
+                    {exists ? (
+                      <FolderOpen className="h-3.5 w-3.5 shrink-0 text-muted-foreground" />
+                    ) : (
+                      <AlertCircle className="h-3.5 w-3.5 shrink-0 text-destructive" />
                     )}
-                  >
-                    {location}
-                  </span>
-                  {isDefault && (
-                    <span className="shrink-0 rounded bg-muted px-1.5 py-0.5 text-caption font-medium text-muted-foreground">
-                      default
-                    </span>
-                  )}
-                  {!exists && (
-                    <span className="shrink-0 rounded bg-destructive/10 px-1.5 py-0.5 text-caption font-medium text-destructive">
-                      {isVolume ? "Not mounted" : "Not found"}
+                    <span
+                      className={cn(
+                        "truncate text-xs",
+                        exists ? "text-foreground" : "text-destructive",
+                      )}
+                    >
+                      {location.path}
                     </span>
+                    {isDefault && (
+                      <span className="shrink-0 rounded bg-muted px-1.5 py-0.5 text-caption font-medium text-muted-foreground">
+                        default
+                      </span>
+                    )}
+                    {!exists && (
+                      <span className="shrink-0 rounded bg-destructive/10 px-1.5 py-0.5 text-caption font-medium text-destructive">
+                        {isVolume ? "Not mounted" : "Not found"}
+                      </span>
+                    )}
+                  </div>
+                  {!isDefault && (
+                    <button
+                      type="button"
+                      onClick={() => handleRemoveLocation(location.path)}
+                      className="ml-2 shrink-0 rounded p-0.5 text-muted-foreground hover:bg-muted hover:text-foreground"
+                    >
+                      <X className="h-3.5 w-3.5" />
+                    </button>
                   )}
                 </div>
-                {!isDefault && (
-                  <button
-                    type="button"
-                    onClick={() => handleRemoveLocation(location)}
-                    className="ml-2 shrink-0 rounded p-0.5 text-muted-foreground hover:bg-muted hover:text-foreground"
-                  >
-                    <X className="h-3.5 w-3.5" />
-                  </button>
-                )}
+                <div className="mt-2 flex items-center justify-between gap-3 pl-5">
+                  <CustomSelect
+                    value={location.depth ?? 0}
+                    onChange={(value) =>
+                      handleUpdateLocation(location.path, { depth: value === 0 ? null : value })
+                    }
+                    options={LOCATION_DEPTH_OPTIONS}
+                  />
+                  <label className="flex shrink-0 items-center gap-2 text-xs text-muted-foreground">
+                    Follow symlinks
+                    <ToggleSwitch
+                      checked={location.followSymlinks}
+                      onChange={(followSymlinks) => handleUpdateLocation(location.path, { followSymlinks })}
+                    />
+                  </label>
+                </div>
               </div>
             );
           })}
@@ -145,9 +396,9 @@ export function ScanningSettings() {
       {/* Scan Depth */}
       <div className="rounded-lg border border-border bg-background px-4 py-3">
         <div className="mb-2">
-          <p className="text-sm font-medium text-foreground">Scan Depth</p>
+          <p className="text-sm font-medium text-foreground">Default Scan Depth</p>
           <p className="text-xs text-muted-foreground">
-            How deep to look for apps inside scan locations
+            How deep to look inside scan locations that don't set their own depth
           </p>
         </div>
         <CustomSelect
@@ -157,6 +408,119 @@ export function ScanningSettings() {
         />
       </div>
 
+      {/* Exclusion rules */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-3">
+          <p className="text-sm font-medium text-foreground">Exclusions</p>
+          <p className="text-xs text-muted-foreground">
+            Matching apps are never added. Applied on the next scan.
+          </p>
+        </div>
+        <div className="space-y-3">
+          <ExclusionList
+            title="Paths"
+            description="Folders or glob patterns (* within a folder, ** across folders)"
+            placeholder="~/Applications/Archive or /Applications/Clients/*/*.app"
+            values={settings.excludedPaths}
+            onChange={(excludedPaths) => handleUpdate({ excludedPaths })}
+          />
+          <ExclusionList
+            title="Bundle IDs"
+            description="Apps whose bundle ID starts with any of these"
+            placeholder="com.example."
+            values={settings.excludedBundleIds}
+            onChange={(excludedBundleIds) => handleUpdate({ excludedBundleIds })}
+          />
+        </div>
+      </div>
+
+      {/* Cask blocklist */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <ExclusionList
+          title="Never Match to Homebrew"
+          description="Bundle IDs that are never matched to a Homebrew cask, e.g. after a wrong match was reported"
+          placeholder="com.example.App"
+          values={settings.caskBlocklist}
+          onChange={(caskBlocklist) => handleUpdate({ caskBlocklist })}
+        />
+      </div>
+
+      {/* Ignore rules */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-3">
+          <p className="text-sm font-medium text-foreground">Ignore Rules</p>
+          <p className="text-xs text-muted-foreground">
+            Matching apps stay in the list but are skipped by update checks
+          </p>
+        </div>
+        <div className="space-y-3">
+          <div className="space-y-1.5">
+            {IGNORE_SOURCES.map((source) => (
+              <label
+                key={source.value}
+                className="flex items-center justify-between rounded-md bg-muted/50 px-3 py-2 text-xs text-foreground"
+              >
+                Ignore all {source.label.toLowerCase()}
+                <ToggleSwitch
+                  checked={settings.ignoreRules.some(
+                    (r) => r.kind === "source" && r.value === source.value,
+                  )}
+                  onChange={(on) => handleSourceRule(source.value, on)}
+                />
+              </label>
+            ))}
+          </div>
+          <ExclusionList
+            title="Bundle IDs"
+            description="Apps whose bundle ID starts with any of these"
+            placeholder="com.adobe."
+            values={prefixRules}
+            onChange={(prefixes) =>
+              handleUpdate({
+                ignoreRules: [
+                  ...settings.ignoreRules.filter((r) => r.kind !== "bundle_id_prefix"),
+                  ...prefixes.map((value): IgnoreRule => ({ kind: "bundle_id_prefix", value })),
+                ],
+              })
+            }
+          />
+        </div>
+      </div>
+
+      {/* Update sources */}
+      <div className="rounded-lg border border-border bg-background px-4 py-3">
+        <div className="mb-3">
+          <p className="text-sm font-medium text-foreground">Update Sources</p>
+          <p className="text-xs text-muted-foreground">
+            Turned-off sources are never checked, for any app
+          </p>
+        </div>
+        <div className="space-y-1.5">
+          {UPDATE_SOURCES.map(({ label, sources }) => {
+            const timing = sourceTiming(checkerMetrics ?? [], sources);
+            return (
+              <label
+                key={label}
+                className="flex items-center justify-between rounded-md bg-muted/50 px-3 py-2 text-xs text-foreground"
+              >
+                <span>
+                  {label}
+                  {timing && <span className="ml-2 text-muted-foreground">{timing}</span>}
+                </span>
+                <ToggleSwitch
+                  checked={!sources.some((s) => settings.disabledSources.includes(s))}
+                  onChange={(on) => handleSourceEnabled(sources, on)}
+                />
+              </label>
+            );
+          })}
+        </div>
+      </div>
+
+      <MappingTransfer />
+
+      <DetectionConflicts />
+
       {/* Ignored apps link */}
       <button
         type="button"
@@ -6,19 +6,46 @@ import {
   Info,
   KeyRound,
   RefreshCw,
+  Settings,
+  ShieldCheck,
   Terminal,
   Wrench,
   XCircle,
 } from "lucide-react";
 import { useCallback, useEffect, useState } from "react";
+import { HomebrewHealth } from "@/components/settings/HomebrewHealth";
 import {
+  type AutomationTarget,
   checkSetupStatus,
   ensureAskpassHelper,
+  getAutomationTargets,
+  openSystemPreferences,
   openTerminalWithCommand,
+  type PermissionKind,
+  requestAutomationForUpdates,
   type SetupStatus,
 } from "@/lib/tauri-commands";
 import { cn } from "@/lib/utils";
 
+const PERMISSION_INFO: Record<PermissionKind, { label: string; description: string }> = {
+  automation: {
+    label: "Automation",
+    description: "Quits and relaunches apps around their updates",
+  },
+  full_disk_access: {
+    label: "Full Disk Access",
+    description: "Finds apps and leftovers in protected folders",
+  },
+  app_management: {
+    label: "App Management",
+    description: "Replaces app bundles in /Applications during updates",
+  },
+  notifications: {
+    label: "Notifications",
+    description: "Tells you about new and installed updates",
+  },
+};
+
 function StatusIcon({ ok, optional }: { ok: boolean; optional?: boolean }) {
   if (ok) return <CheckCircle2 className="h-4 w-4 text-green-500" />;
   if (optional) return <XCircle className="h-4 w-4 text-muted-foreground" />;
@@ -101,13 +128,19 @@ export function SetupView() {
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState(false);
   const [configuringAskpass, setConfiguringAskpass] = useState(false);
+  const [automationTargets, setAutomationTargets] = useState<AutomationTarget[]>([]);
+  const [requestingAutomation, setRequestingAutomation] = useState(false);
 
   const refresh = useCallback(async () => {
     setLoading(true);
     setError(false);
     try {
-      const result = await checkSetupStatus();
+      const [result, targets] = await Promise.all([
+        checkSetupStatus(),
+        getAutomationTargets().catch(() => []),
+      ]);
       setStatus(result);
+      setAutomationTargets(targets);
     } catch {
       setError(true);
     } finally {
@@ -137,6 +170,20 @@ export function SetupView() {
     }
   };
 
+  const handleRequestAutomation = async () => {
+    setRequestingAutomation(true);
+    try {
+      setAutomationTargets(await requestAutomationForUpdates());
+    } catch {
+      // ignore
+    } finally {
+      setRequestingAutomation(false);
+    }
+  };
+
+  const automationAllowed = automationTargets.filter((t) => t.state === "granted").length;
+  const automationDenied = automationTargets.filter((t) => t.state === "denied").length;
+
   if (loading && !status) {
     return (
       <div className="rounded-lg border border-border bg-background p-6">
@@ -225,7 +272,62 @@ export function SetupView() {
         </div>
       </div>
 
-      {/* Section 2 — Tools */}
+      {/* Section 2 — Permissions */}
+      <div>
+        <SectionHeader
+          icon={<ShieldCheck className="h-3.5 w-3.5 text-muted-foreground" />}
+          title="Permissions"
+        />
+        <div className="space-y-1">
+          {status.permissions.matrix.map((entry) => {
+            const info = PERMISSION_INFO[entry.kind];
+            return (
+              <SetupRow
+                key={entry.kind}
+                ok={entry.state === "granted"}
+                optional={!entry.required}
+                label={info.label}
+                description={
+                  entry.state === "unknown"
+                    ? `${info.description} · couldn't be checked`
+                    : info.description
+                }
+                action={
+                  entry.state !== "granted" ? (
+                    <ActionButton
+                      onClick={() => openSystemPreferences(entry.kind)}
+                      icon={<Settings className="h-3 w-3" />}
+                      label="Open Settings"
+                      variant={entry.required ? "primary" : "muted"}
+                    />
+                  ) : undefined
+                }
+              />
+            );
+          })}
+          {automationTargets.length > 0 && (
+            <SetupRow
+              ok={automationAllowed === automationTargets.length}
+              optional
+              label="App Automation"
+              description={`${automationAllowed} of ${automationTargets.length} apps with updates can be quit with AppleScript${automationDenied > 0 ? ` · ${automationDenied} denied, quit with a signal instead` : ""}`}
+              action={
+                automationAllowed + automationDenied < automationTargets.length ? (
+                  <ActionButton
+                    onClick={handleRequestAutomation}
+                    disabled={requestingAutomation}
+                    icon={<ShieldCheck className="h-3 w-3" />}
+                    label={requestingAutomation ? "Requesting..." : "Request"}
+                    variant="muted"
+                  />
+                ) : undefined
+              }
+            />
+          )}
+        </div>
+      </div>
+
+      {/* Section 3 — Tools */}
       <div>
         <SectionHeader
           icon={<Beer className="h-3.5 w-3.5 text-muted-foreground" />}
@@ -297,10 +399,11 @@ export function SetupView() {
               />
             )}
           </div>
+          {status.homebrewInstalled && <HomebrewHealth />}
         </div>
       </div>
 
-      {/* Section 3 — App Info */}
+      {/* Section 4 — App Info */}
       <div>
         <SectionHeader
           icon={<Info className="h-3.5 w-3.5 text-muted-foreground" />}
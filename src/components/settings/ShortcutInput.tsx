@@ -0,0 +1,96 @@
+import { X } from "lucide-react";
+import { useState } from "react";
+import { cn } from "@/lib/utils";
+
+const MODIFIER_KEYS = new Set(["Meta", "Control", "Alt", "Shift"]);
+
+/** Convert a keydown into a Tauri accelerator, e.g. "CommandOrControl+Shift+U". */
+function toAccelerator(e: React.KeyboardEvent): string | null {
+  if (MODIFIER_KEYS.has(e.key)) return null;
+  const parts: string[] = [];
+  if (e.metaKey) parts.push("CommandOrControl");
+  if (e.ctrlKey) parts.push("Control");
+  if (e.altKey) parts.push("Alt");
+  if (e.shiftKey) parts.push("Shift");
+  // Global shortcuts need at least one modifier
+  if (parts.length === 0) return null;
+
+  let key = e.code;
+  if (key.startsWith("Key")) key = key.slice(3);
+  else if (key.startsWith("Digit")) key = key.slice(5);
+  parts.push(key);
+  return parts.join("+");
+}
+
+/** Render an accelerator with macOS modifier symbols. */
+function formatAccelerator(accelerator: string): string {
+  return accelerator
+    .split("+")
+    .map((part) => {
+      switch (part) {
+        case "CommandOrControl":
+        case "Command":
+          return "⌘";
+        case "Control":
+          return "⌃";
+        case "Alt":
+          return "⌥";
+        case "Shift":
+          return "⇧";
+        default:
+          return part;
+      }
+    })
+    .join("");
+}
+
+export function ShortcutInput({
+  value,
+  onChange,
+}: {
+  value: string | null;
+  onChange: (value: string | null) => void;
+}) {
+  const [recording, setRecording] = useState(false);
+
+  return (
+    <div className="flex shrink-0 items-center gap-1">
+      <button
+        type="button"
+        onClick={() => setRecording(true)}
+        onBlur={() => setRecording(false)}
+        onKeyDown={(e) => {
+          if (!recording) return;
+          e.preventDefault();
+          if (e.key === "Escape") {
+            setRecording(false);
+            return;
+          }
+          const accelerator = toAccelerator(e);
+          if (accelerator) {
+            setRecording(false);
+            onChange(accelerator);
+          }
+        }}
+        className={cn(
+          "min-w-24 rounded-md border px-2.5 py-1 text-xs transition-colors",
+          recording
+            ? "border-primary text-primary"
+            : "border-border text-foreground hover:bg-muted",
+        )}
+      >
+        {recording ? "Press keys..." : value ? formatAccelerator(value) : "Record"}
+      </button>
+      {value && !recording && (
+        <button
+          type="button"
+          onClick={() => onChange(null)}
+          className="rounded-md p-1 text-muted-foreground transition-colors hover:bg-muted"
+          aria-label="Clear shortcut"
+        >
+          <X className="h-3 w-3" />
+        </button>
+      )}
+    </div>
+  );
+}
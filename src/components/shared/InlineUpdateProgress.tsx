@@ -12,9 +12,10 @@ interface InlineUpdateProgressProps {
   variant?: "compact" | "card";
   downloadedBytes?: number;
   totalBytes?: number | null;
+  waiting?: boolean;
 }
 
-export function useCrawlingPercent(percent: number, phase?: string, hasRealBytes?: boolean) {
+export function useCrawlingPercent(percent: number, waiting?: boolean, hasRealBytes?: boolean) {
   const [displayPercent, setDisplayPercent] = useState(percent);
   const lastPercent = useRef(percent);
 
@@ -40,9 +41,7 @@ export function useCrawlingPercent(percent: number, phase?: string, hasRealBytes
     if (percent <= 0) return;
 
     // Don't crawl while waiting for user input (e.g., admin password dialog)
-    const isWaiting =
-      phase?.toLowerCase().includes("administrator") || phase?.toLowerCase().includes("waiting");
-    if (isWaiting) return;
+    if (waiting) return;
 
     const interval = setInterval(() => {
       setDisplayPercent((prev) => {
@@ -53,7 +52,7 @@ export function useCrawlingPercent(percent: number, phase?: string, hasRealBytes
     }, 600);
 
     return () => clearInterval(interval);
-  }, [percent, phase, hasRealBytes]);
+  }, [percent, waiting, hasRealBytes]);
 
   return displayPercent;
 }
@@ -64,6 +63,7 @@ export function InlineUpdateProgress({
   variant = "compact",
   downloadedBytes,
   totalBytes,
+  waiting,
 }: InlineUpdateProgressProps) {
   const hasRealBytes = downloadedBytes != null && downloadedBytes > 0;
 
@@ -73,7 +73,7 @@ export function InlineUpdateProgress({
       ? (downloadedBytes / totalBytes) * 100
       : percent;
 
-  const displayPercent = useCrawlingPercent(effectivePercent, phase, hasRealBytes);
+  const displayPercent = useCrawlingPercent(effectivePercent, waiting, hasRealBytes);
 
   const byteLabel = hasRealBytes ? formatDownloadProgress(downloadedBytes, totalBytes) : null;
 
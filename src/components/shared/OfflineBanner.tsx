@@ -0,0 +1,39 @@
+import { WifiOff } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useTauriEvent } from "@/hooks/useTauriEvent";
+import { getOfflineState, type OfflineState } from "@/lib/tauri-commands";
+
+function describeQueue(state: OfflineState): string {
+  const updates = state.queued.filter((a) => a.kind === "update").length;
+  const check = state.queued.some((a) => a.kind === "update_check");
+  const parts = [
+    updates > 0 && `${updates} update${updates === 1 ? "" : "s"}`,
+    check && "an update check",
+  ].filter(Boolean);
+  return parts.length > 0
+    ? `${parts.join(" and ")} will run when you're back online`
+    : "Showing results from the last update check";
+}
+
+export function OfflineBanner() {
+  const [state, setState] = useState<OfflineState | null>(null);
+
+  useEffect(() => {
+    getOfflineState()
+      .then(setState)
+      .catch(() => {});
+  }, []);
+
+  useTauriEvent<OfflineState>("offline-state", setState);
+
+  if (!state?.offline) return null;
+
+  return (
+    <div className="flex items-center gap-3 border-b border-yellow-500/20 bg-yellow-500/5 px-4 py-2.5">
+      <WifiOff className="h-4 w-4 shrink-0 text-yellow-600 dark:text-yellow-400" />
+      <p className="flex-1 truncate text-xs text-yellow-700 dark:text-yellow-300">
+        Offline — {describeQueue(state)}
+      </p>
+    </div>
+  );
+}
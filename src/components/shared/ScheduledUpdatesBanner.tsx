@@ -0,0 +1,43 @@
+import { Clock } from "lucide-react";
+import { useEffect, useState } from "react";
+import { useTauriEvent } from "@/hooks/useTauriEvent";
+import { cancelScheduledUpdates, getScheduledUpdates } from "@/lib/tauri-commands";
+import type { ScheduledUpdate } from "@/types/update";
+
+export function ScheduledUpdatesBanner() {
+  const [scheduled, setScheduled] = useState<ScheduledUpdate[]>([]);
+
+  useEffect(() => {
+    getScheduledUpdates()
+      .then(setScheduled)
+      .catch(() => {});
+  }, []);
+
+  useTauriEvent<ScheduledUpdate[]>("scheduled-updates-changed", setScheduled);
+
+  if (scheduled.length === 0) return null;
+
+  const at = new Date(scheduled[0].runAt).toLocaleTimeString([], {
+    hour: "2-digit",
+    minute: "2-digit",
+  });
+  const count = scheduled.length;
+
+  return (
+    <div className="flex items-center gap-3 border-b border-primary/20 bg-primary/5 px-4 py-2.5">
+      <Clock className="h-4 w-4 shrink-0 text-primary" />
+      <p className="flex-1 truncate text-xs text-foreground">
+        {count} update{count === 1 ? "" : "s"} scheduled for {at}
+      </p>
+      <button
+        type="button"
+        onClick={() => {
+          cancelScheduledUpdates(scheduled.map((s) => s.bundleId)).catch(() => {});
+        }}
+        className="rounded-md px-2 py-1 text-xs font-medium text-muted-foreground hover:bg-muted hover:text-foreground transition-colors"
+      >
+        Cancel
+      </button>
+    </div>
+  );
+}
@@ -66,7 +66,7 @@ export function SelfUpdateBanner() {
     setIsUpdating(true);
     setError(null);
     setProgress(null);
-    executeSelfUpdate(info.downloadUrl).catch((err) => {
+    executeSelfUpdate(info.downloadUrl, info.availableVersion).catch((err) => {
       setIsUpdating(false);
       setError(String(err));
     });
@@ -149,7 +149,13 @@ export function SelfUpdateBanner() {
     <div className="flex items-center gap-3 border-b border-primary/20 bg-primary/5 px-4 py-2.5">
       <ArrowUpCircle className="h-4 w-4 shrink-0 text-primary" />
       <p className="flex-1 text-xs text-primary">
-        macPlus {info.availableVersion} is available{" "}
+        macPlus {info.availableVersion}
+        {info.isPrerelease && (
+          <span className="mx-1 rounded bg-primary/10 px-1 py-px text-[10px] font-semibold uppercase">
+            beta
+          </span>
+        )}{" "}
+        is available{" "}
         <span className="text-primary/60">(current: {info.currentVersion})</span>
       </p>
       <button
@@ -183,7 +189,7 @@ function SelfUpdateProgressBar({ progress }: { progress: SelfUpdateProgress | nu
   const percent = progress?.percent ?? 0;
   const phase = progress?.phase ?? "Preparing update...";
   const hasRealBytes = progress?.downloadedBytes != null && progress.downloadedBytes > 0;
-  const displayPercent = useCrawlingPercent(percent, phase, hasRealBytes);
+  const displayPercent = useCrawlingPercent(percent, progress?.waiting, hasRealBytes);
 
   const byteLabel =
     hasRealBytes && progress
@@ -0,0 +1,78 @@
+import { Wrench } from "lucide-react";
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { getStartupIssues, repairStartupIssue } from "@/lib/tauri-commands";
+import type { StartupIssue } from "@/types/app";
+
+function describeIssue(issue: StartupIssue): string {
+  const name = issue.displayName ?? issue.path.split("/").pop();
+  switch (issue.kind) {
+    case "missing_app": {
+      const missing = `${name} went missing during an interrupted update`;
+      return issue.repairable ? missing : `${missing} — download it again from its developer`;
+    }
+    case "leftover_backup":
+      return `A backup of ${name} was left behind by an interrupted update`;
+    case "stale_mount":
+      return "An update disk image is still mounted";
+  }
+}
+
+export function StartupIssuesBanner() {
+  const [issues, setIssues] = useState<StartupIssue[]>([]);
+  const [repairing, setRepairing] = useState(false);
+
+  useEffect(() => {
+    getStartupIssues()
+      .then(setIssues)
+      .catch(() => {});
+  }, []);
+
+  if (issues.length === 0) return null;
+
+  const repairable = issues.filter((i) => i.repairable);
+
+  const repairAll = async () => {
+    setRepairing(true);
+    for (const issue of repairable) {
+      try {
+        await repairStartupIssue(issue.path);
+      } catch (error) {
+        toast.error("Repair failed", { description: String(error) });
+      }
+    }
+    setIssues(await getStartupIssues().catch(() => []));
+    setRepairing(false);
+  };
+
+  return (
+    <div className="flex items-center gap-3 border-b border-yellow-500/20 bg-yellow-500/5 px-4 py-2.5">
+      <Wrench className="h-4 w-4 shrink-0 text-yellow-600 dark:text-yellow-400" />
+      <p
+        className="flex-1 truncate text-xs text-yellow-700 dark:text-yellow-300"
+        title={issues.map(describeIssue).join("\n")}
+      >
+        {issues.length === 1
+          ? describeIssue(issues[0])
+          : `${issues.length} problems were left behind by interrupted updates`}
+      </p>
+      {repairable.length > 0 && (
+        <button
+          type="button"
+          disabled={repairing}
+          onClick={repairAll}
+          className="rounded-md px-2 py-1 text-xs font-medium text-foreground hover:bg-muted transition-colors disabled:opacity-50"
+        >
+          {repairing ? "Repairing…" : "Repair"}
+        </button>
+      )}
+      <button
+        type="button"
+        onClick={() => setIssues([])}
+        className="rounded-md px-2 py-1 text-xs font-medium text-muted-foreground hover:bg-muted hover:text-foreground transition-colors"
+      >
+        Dismiss
+      </button>
+    </div>
+  );
+}
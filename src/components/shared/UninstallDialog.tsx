@@ -5,7 +5,7 @@ import { AppIcon } from "@/components/app-list/AppIcon";
 import { useUninstallApp } from "@/hooks/useApps";
 import { useTauriEvent } from "@/hooks/useTauriEvent";
 import { springs } from "@/lib/animations";
-import type { AssociatedFiles, UninstallProgress } from "@/lib/tauri-commands";
+import type { AssociatedFile, AssociatedFiles, UninstallProgress } from "@/lib/tauri-commands";
 import { scanAssociatedFiles } from "@/lib/tauri-commands";
 import { cn } from "@/lib/utils";
 import { useUIStore } from "@/stores/uiStore";
@@ -28,6 +28,12 @@ const KIND_LABELS: Record<string, string> = {
   group_containers: "Group Containers",
   logs: "Logs",
   webkit: "WebKit Data",
+  launch_agent: "Launch Agents",
+  launch_daemon: "Launch Daemons",
+  privileged_helper: "Privileged Helpers",
+  system_extension: "System Extensions",
+  login_item: "Login Items",
+  zap: "Homebrew Zap",
 };
 
 function getSourceLabel(source: string): string {
@@ -52,9 +58,17 @@ export function UninstallDialog() {
 
   const [associatedFiles, setAssociatedFiles] = useState<AssociatedFiles | null>(null);
   const [loadingFiles, setLoadingFiles] = useState(false);
-  const [cleanupChecked, setCleanupChecked] = useState(false);
+  const [selectedPaths, setSelectedPaths] = useState<Set<string>>(new Set());
   const [filesExpanded, setFilesExpanded] = useState(false);
+  const [archiveChecked, setArchiveChecked] = useState(false);
   const [progress, setProgress] = useState<UninstallProgress | null>(null);
+  const [sizedBytes, setSizedBytes] = useState(0);
+
+  useTauriEvent<{ bundleId: string; file: AssociatedFile }>("associated-file-sized", (payload) => {
+    if (loadingFiles && payload.bundleId === target?.bundleId) {
+      setSizedBytes((total) => total + payload.file.sizeBytes);
+    }
+  });
 
   useTauriEvent<UninstallProgress>("uninstall-progress", (payload) => {
     if (uninstall.isPending) {
@@ -73,13 +87,15 @@ export function UninstallDialog() {
   useEffect(() => {
     if (!target) {
       setAssociatedFiles(null);
-      setCleanupChecked(false);
+      setSelectedPaths(new Set());
       setFilesExpanded(false);
+      setArchiveChecked(false);
       setProgress(null);
       return;
     }
 
     setLoadingFiles(true);
+    setSizedBytes(0);
     scanAssociatedFiles(target.bundleId)
       .then(setAssociatedFiles)
       .catch(() => setAssociatedFiles(null))
@@ -93,11 +109,38 @@ export function UninstallDialog() {
   const handleUninstall = () => {
     if (!target || isProtected) return;
     uninstall.mutate(
-      { bundleId: target.bundleId, cleanupAssociated: cleanupChecked },
+      {
+        bundleId: target.bundleId,
+        paths: [...selectedPaths],
+        archive: archiveChecked,
+        archiveSupportFiles: archiveChecked && selectedPaths.size > 0,
+      },
       { onSuccess: () => setUninstallTarget(null) },
     );
   };
 
+  const cleanupChecked = selectedPaths.size > 0;
+  const allChecked =
+    !!associatedFiles && associatedFiles.paths.length > 0 &&
+    selectedPaths.size === associatedFiles.paths.length;
+  const selectedBytes =
+    associatedFiles?.paths
+      .filter((f) => selectedPaths.has(f.path))
+      .reduce((sum, f) => sum + f.sizeBytes, 0) ?? 0;
+
+  const toggleAll = (checked: boolean) => {
+    setSelectedPaths(checked ? new Set(associatedFiles?.paths.map((f) => f.path)) : new Set());
+  };
+
+  const togglePath = (path: string) => {
+    setSelectedPaths((prev) => {
+      const next = new Set(prev);
+      if (next.has(path)) next.delete(path);
+      else next.add(path);
+      return next;
+    });
+  };
+
   // Group files by kind for display
   const groupedFiles = associatedFiles?.paths.reduce(
     (acc, file) => {
@@ -185,29 +228,47 @@ export function UninstallDialog() {
                     </div>
                   )}
 
+                  {/* Archive option */}
+                  <label className="mt-4 flex items-center gap-2 cursor-pointer">
+                    <input
+                      type="checkbox"
+                      checked={archiveChecked}
+                      onChange={(e) => setArchiveChecked(e.target.checked)}
+                      className="h-3.5 w-3.5 rounded border-border accent-primary"
+                    />
+                    <span className="text-xs text-foreground">Save a zip archive first</span>
+                  </label>
+
                   {/* Associated files section */}
-                  <div className="mt-4">
+                  <div className="mt-3">
                     {loadingFiles ? (
                       <div className="flex items-center gap-2 text-xs text-muted-foreground">
                         <Loader2 className="h-3 w-3 animate-spin" />
                         Scanning for associated files...
+                        {sizedBytes > 0 && ` (${formatBytes(sizedBytes)} so far)`}
                       </div>
                     ) : associatedFiles && associatedFiles.paths.length > 0 ? (
                       <div className="space-y-2">
                         <label className="flex items-center gap-2 cursor-pointer">
                           <input
                             type="checkbox"
-                            checked={cleanupChecked}
-                            onChange={(e) => setCleanupChecked(e.target.checked)}
+                            checked={allChecked}
+                            ref={(el) => {
+                              if (el) el.indeterminate = cleanupChecked && !allChecked;
+                            }}
+                            onChange={(e) => toggleAll(e.target.checked)}
                             className="h-3.5 w-3.5 rounded border-border accent-primary"
                           />
                           <span className="text-xs text-foreground">
                             Also remove associated data (
-                            {formatBytes(associatedFiles.totalSizeBytes)})
+                            {cleanupChecked && !allChecked
+                              ? `${formatBytes(selectedBytes)} of ${formatBytes(associatedFiles.totalSizeBytes)}`
+                              : formatBytes(associatedFiles.totalSizeBytes)}
+                            )
                           </span>
                         </label>
 
-                        {cleanupChecked && (
+                        {associatedFiles.paths.length > 0 && (
                           <div>
                             <button
                               type="button"
@@ -219,8 +280,8 @@ export function UninstallDialog() {
                               ) : (
                                 <ChevronRight className="h-3 w-3" />
                               )}
-                              {associatedFiles.paths.length} file
-                              {associatedFiles.paths.length === 1 ? "" : "s"} found
+                              {selectedPaths.size} of {associatedFiles.paths.length} item
+                              {associatedFiles.paths.length === 1 ? "" : "s"} selected
                             </button>
                             <AnimatePresence>
                               {filesExpanded && groupedFiles && (
@@ -238,17 +299,25 @@ export function UninstallDialog() {
                                           {KIND_LABELS[kind] ?? kind}
                                         </p>
                                         {files.map((f) => (
-                                          <div
+                                          <label
                                             key={f.path}
-                                            className="flex items-center justify-between text-[10px] text-muted-foreground"
+                                            className="flex cursor-pointer items-center justify-between text-[10px] text-muted-foreground"
                                           >
-                                            <span className="truncate mr-2">
-                                              {f.path.replace(/^.*\/Library\//, "~/Library/")}
+                                            <span className="flex min-w-0 items-center gap-1.5 mr-2">
+                                              <input
+                                                type="checkbox"
+                                                checked={selectedPaths.has(f.path)}
+                                                onChange={() => togglePath(f.path)}
+                                                className="h-3 w-3 shrink-0 rounded border-border accent-primary"
+                                              />
+                                              <span className="truncate">
+                                                {f.path.replace(/^.*\/Library\//, "~/Library/")}
+                                              </span>
                                             </span>
                                             <span className="shrink-0 tabular-nums">
                                               {formatBytes(f.sizeBytes)}
                                             </span>
-                                          </div>
+                                          </label>
                                         ))}
                                       </div>
                                     ))}
@@ -162,6 +162,14 @@ export function UpdateHistoryView() {
                     </div>
                   </div>
                   <div className="flex items-center gap-2">
+                    {entry.attempts > 1 && (
+                      <span
+                        className="text-footnote text-muted-foreground"
+                        title="Retried after network problems"
+                      >
+                        {entry.attempts} tries
+                      </span>
+                    )}
                     <span className="text-footnote text-muted-foreground">
                       {formatRelativeTime(entry.completedAt ?? entry.startedAt)}
                     </span>
@@ -176,6 +184,19 @@ export function UpdateHistoryView() {
                     {entry.errorMessage}
                   </p>
                 )}
+                {entry.gatekeeperVerdict?.startsWith("rejected") && (
+                  <p
+                    className="truncate pb-2 text-[10px] text-amber-600 dark:text-amber-400"
+                    title={entry.gatekeeperVerdict}
+                  >
+                    Gatekeeper would block this version ({entry.gatekeeperVerdict})
+                  </p>
+                )}
+                {entry.licenseAccepted && (
+                  <p className="truncate pb-2 text-[10px] text-muted-foreground">
+                    License agreement accepted automatically
+                  </p>
+                )}
               </div>
             ))}
           </div>
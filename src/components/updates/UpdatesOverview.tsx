@@ -1,4 +1,5 @@
 import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { save } from "@tauri-apps/plugin-dialog";
 import { open } from "@tauri-apps/plugin-shell";
 import {
   ArrowRight,
@@ -8,28 +9,34 @@ import {
   EyeOff,
   FileText,
   Globe,
+  KeyRound,
   Loader2,
   PackageMinus,
+  PackagePlus,
   RefreshCw,
+  ShieldAlert,
+  Terminal,
+  XCircle,
 } from "lucide-react";
 import { AnimatePresence, motion } from "motion/react";
 import { useEffect, useMemo, useState } from "react";
+import { toast } from "sonner";
 import { ReleaseNotesContent } from "@/components/app-detail/ReleaseNotesSection";
 import { AppIcon } from "@/components/app-list/AppIcon";
 import { InfoPopover } from "@/components/shared/InfoPopover";
 import { RelaunchButton, useCrawlingPercent } from "@/components/shared/InlineUpdateProgress";
-import { useApps, useFullScan, useToggleIgnored } from "@/hooks/useApps";
-import { useCheckAllUpdates } from "@/hooks/useAppUpdates";
+import { useApps, useConfirmUpgradeLicense, useFullScan, useToggleIgnored } from "@/hooks/useApps";
+import { useCheckAllUpdates, useOsSecurityStatus } from "@/hooks/useAppUpdates";
 import { useTauriEvent } from "@/hooks/useTauriEvent";
 import { useExecuteBulkUpdate, useExecuteUpdate } from "@/hooks/useUpdateExecution";
-import { formatDownloadProgress } from "@/lib/format-bytes";
-import { getUpdateHistory } from "@/lib/tauri-commands";
+import { formatBytes, formatDownloadProgress } from "@/lib/format-bytes";
+import { exportUpdateScript, getBulkUpdateIds, getRecentActivity } from "@/lib/tauri-commands";
 import { isDelegatedUpdate } from "@/lib/update-utils";
 import { cn } from "@/lib/utils";
 import { useUIStore } from "@/stores/uiStore";
 import { useUpdateProgressStore } from "@/stores/updateProgressStore";
 import type { AppSummary } from "@/types/app";
-import type { UpdateExecuteComplete } from "@/types/update";
+import type { ActivityKind, UpdateExecuteComplete } from "@/types/update";
 
 // --- Categorization ---
 
@@ -51,6 +58,12 @@ function categorizeApp(app: AppSummary): UpdateCategory {
   return "desktop_apps";
 }
 
+/** Security updates first, then A-Z. */
+function compareUpdates(a: AppSummary, b: AppSummary): number {
+  if (a.isSecurityUpdate !== b.isSecurityUpdate) return a.isSecurityUpdate ? -1 : 1;
+  return a.displayName.localeCompare(b.displayName);
+}
+
 function getBrowserName(bundleId: string): string | null {
   if (bundleId.startsWith("com.google.Chrome")) return "Chrome";
   if (bundleId.startsWith("com.brave.Browser")) return "Brave";
@@ -71,6 +84,7 @@ function UpdateCard({ app, onUpdate }: { app: AppSummary; onUpdate: (bundleId: s
   const progress = useUpdateProgressStore((s) => s.progress[app.bundleId]);
   const relaunch = useUpdateProgressStore((s) => s.relaunchNeeded[app.bundleId]);
   const toggleIgnored = useToggleIgnored();
+  const confirmLicense = useConfirmUpgradeLicense();
   const setUninstallTarget = useUIStore((s) => s.setUninstallTarget);
   const [changelogOpen, setChangelogOpen] = useState(false);
 
@@ -84,7 +98,7 @@ function UpdateCard({ app, onUpdate }: { app: AppSummary; onUpdate: (bundleId: s
     hasRealBytes && totalBytes != null && totalBytes > 0
       ? (downloadedBytes / totalBytes) * 100
       : (progress?.percent ?? 0);
-  const displayPercent = useCrawlingPercent(effectivePercent, progress?.phase, hasRealBytes);
+  const displayPercent = useCrawlingPercent(effectivePercent, progress?.waiting, hasRealBytes);
   const byteLabel = hasRealBytes ? formatDownloadProgress(downloadedBytes, totalBytes) : null;
 
   // Auto-close changelog when progress starts
@@ -177,6 +191,25 @@ function UpdateCard({ app, onUpdate }: { app: AppSummary; onUpdate: (bundleId: s
                 <span className="text-muted-foreground">{app.installedVersion ?? "Unknown"}</span>
                 <ArrowRight className="size-2.5 shrink-0 text-muted-foreground/50" />
                 <span className="font-semibold text-success">{app.availableVersion}</span>
+                {app.isSecurityUpdate && (
+                  <span
+                    className="rounded bg-destructive/15 px-1 text-[10px] font-medium text-destructive"
+                    title="This update fixes security issues"
+                  >
+                    Security
+                  </span>
+                )}
+                {app.needsLicense && (
+                  <span
+                    className="rounded bg-amber-500/15 px-1 text-[10px] font-medium text-amber-600 dark:text-amber-400"
+                    title="This version requires a new license"
+                  >
+                    Paid
+                  </span>
+                )}
+                {app.downloadSize != null && (
+                  <span className="text-muted-foreground">· {formatBytes(app.downloadSize)}</span>
+                )}
               </div>
             </div>
 
@@ -243,6 +276,22 @@ function UpdateCard({ app, onUpdate }: { app: AppSummary; onUpdate: (bundleId: s
                   appPath={relaunch.appPath}
                   variant="compact"
                 />
+              ) : app.needsLicense ? (
+                <button
+                  type="button"
+                  onClick={() => confirmLicense.mutate(app.bundleId)}
+                  disabled={confirmLicense.isPending}
+                  className={cn(
+                    "flex shrink-0 items-center gap-1.5 rounded-md",
+                    "border border-input bg-background px-2.5 py-1.5",
+                    "text-xs font-medium text-foreground",
+                    "transition-colors hover:bg-accent",
+                  )}
+                  title="Unlock updating once you've bought the new version"
+                >
+                  <KeyRound className="h-3 w-3" />
+                  I Own a License
+                </button>
               ) : isDelegatedUpdate(app) ? (
                 <div className="flex items-center gap-1">
                   <button
@@ -315,7 +364,7 @@ function UpdateCard({ app, onUpdate }: { app: AppSummary; onUpdate: (bundleId: s
   );
 }
 
-// --- Recently Updated ---
+// --- Recent Activity ---
 
 function formatRelativeTime(dateStr: string | null): string {
   if (!dateStr) return "";
@@ -331,18 +380,39 @@ function formatRelativeTime(dateStr: string | null): string {
   return `${Math.floor(diffHr / 24)}d ago`;
 }
 
-function RecentlyUpdated() {
+const ACTIVITY_LABELS: Record<Exclude<ActivityKind, "updated">, string> = {
+  update_failed: "Update failed",
+  uninstalled: "Uninstalled",
+  self_updated: "Updated",
+  app_added: "New",
+};
+
+function ActivityIcon({ kind }: { kind: ActivityKind }) {
+  switch (kind) {
+    case "updated":
+    case "self_updated":
+      return <CheckCircle2 className="h-3.5 w-3.5 text-success/60" />;
+    case "update_failed":
+      return <XCircle className="h-3.5 w-3.5 text-destructive/60" />;
+    case "uninstalled":
+      return <PackageMinus className="h-3.5 w-3.5 text-muted-foreground/60" />;
+    case "app_added":
+      return <PackagePlus className="h-3.5 w-3.5 text-primary/60" />;
+  }
+}
+
+function RecentActivity() {
   const queryClient = useQueryClient();
 
   const { data: entries } = useQuery({
-    queryKey: ["update-history-recent"],
-    queryFn: () => getUpdateHistory(20),
+    queryKey: ["recent-activity"],
+    queryFn: () => getRecentActivity(20),
     refetchInterval: 60 * 1000,
   });
 
   useTauriEvent<UpdateExecuteComplete>("update-execute-complete", () => {
     setTimeout(() => {
-      queryClient.invalidateQueries({ queryKey: ["update-history-recent"] });
+      queryClient.invalidateQueries({ queryKey: ["recent-activity"] });
     }, 2000);
   });
 
@@ -362,15 +432,15 @@ function RecentlyUpdated() {
     const clearedAtMs = clearedAt ? new Date(clearedAt).getTime() : 0;
     const seen = new Set<string>();
     return entries
-      .filter((e) => e.status === "completed")
       .filter((e) => {
-        const ts = e.startedAt ? new Date(`${e.startedAt}Z`).getTime() : 0;
+        const ts = new Date(`${e.at}Z`).getTime();
         return ts > sevenDaysAgo && ts > clearedAtMs;
       })
-      .filter((e) => e.fromVersion !== e.toVersion)
+      .filter((e) => e.kind !== "updated" || e.fromVersion !== e.version)
       .filter((e) => {
-        if (seen.has(e.bundleId)) return false;
-        seen.add(e.bundleId);
+        const key = `${e.kind}:${e.bundleId ?? e.displayName}`;
+        if (seen.has(key)) return false;
+        seen.add(key);
         return true;
       });
   }, [entries, clearedAt]);
@@ -381,7 +451,7 @@ function RecentlyUpdated() {
     <div>
       <div className="flex items-center justify-between mb-2">
         <h2 className="text-xs font-semibold uppercase tracking-wide text-muted-foreground">
-          Recently Updated
+          Recent Activity
         </h2>
         <button
           type="button"
@@ -394,30 +464,40 @@ function RecentlyUpdated() {
       <div className="flex flex-col gap-2">
         {recent.map((entry) => (
           <div
-            key={entry.id}
+            key={`${entry.kind}-${entry.bundleId ?? entry.displayName}-${entry.at}`}
             className="grid min-h-[44px] items-center rounded-lg border border-border/50 bg-card px-3 grid-cols-[28px_1fr_auto] gap-2.5 opacity-60"
           >
             <AppIcon
               iconPath={entry.iconCachePath}
               displayName={entry.displayName}
-              bundleId={entry.bundleId}
+              bundleId={entry.bundleId ?? undefined}
               size={28}
             />
             <div className="flex min-w-0 items-center gap-1.5">
               <span className="truncate text-sm font-medium leading-tight">
                 {entry.displayName}
               </span>
-              <div className="flex shrink-0 items-center gap-1 text-footnote leading-tight">
-                <span className="text-muted-foreground">{entry.fromVersion}</span>
-                <ArrowRight className="size-2.5 shrink-0 text-muted-foreground/50" />
-                <span className="text-muted-foreground">{entry.toVersion}</span>
-              </div>
+              {entry.kind === "updated" ? (
+                <div className="flex shrink-0 items-center gap-1 text-footnote leading-tight">
+                  <span className="text-muted-foreground">{entry.fromVersion}</span>
+                  <ArrowRight className="size-2.5 shrink-0 text-muted-foreground/50" />
+                  <span className="text-muted-foreground">{entry.version}</span>
+                </div>
+              ) : (
+                <span
+                  className="shrink-0 truncate text-footnote leading-tight text-muted-foreground"
+                  title={entry.errorMessage ?? undefined}
+                >
+                  {ACTIVITY_LABELS[entry.kind]}
+                  {entry.version && entry.kind !== "update_failed" ? ` ${entry.version}` : ""}
+                </span>
+              )}
             </div>
             <div className="flex items-center gap-1.5">
               <span className="text-caption text-muted-foreground/50">
-                {formatRelativeTime(entry.completedAt ?? entry.startedAt)}
+                {formatRelativeTime(entry.at)}
               </span>
-              <CheckCircle2 className="h-3.5 w-3.5 text-success/60" />
+              <ActivityIcon kind={entry.kind} />
             </div>
           </div>
         ))}
@@ -426,6 +506,43 @@ function RecentlyUpdated() {
   );
 }
 
+// --- macOS Security ---
+
+function OsSecurityBanner() {
+  const { data: status } = useOsSecurityStatus();
+  if (!status?.latestVersion) return null;
+
+  const exploited = status.activelyExploitedCount;
+  return (
+    <div className="flex items-center gap-2.5 rounded-lg border border-destructive/30 bg-destructive/5 px-3 py-2">
+      <ShieldAlert className="size-4 shrink-0 text-destructive" />
+      <p className="flex-1 text-xs text-foreground">
+        macOS {status.latestVersion} fixes {status.missingCveCount} security issue
+        {status.missingCveCount === 1 ? "" : "s"} in macOS {status.installedVersion}
+        {exploited > 0 && <span className="font-medium"> ({exploited} actively exploited)</span>}
+      </p>
+      {status.securityInfoUrl && (
+        <button
+          type="button"
+          onClick={() => status.securityInfoUrl && open(status.securityInfoUrl)}
+          className="text-xs text-muted-foreground hover:text-foreground"
+        >
+          Details
+        </button>
+      )}
+      <button
+        type="button"
+        onClick={() =>
+          open("x-apple.systempreferences:com.apple.Software-Update-Settings.extension")
+        }
+        className="rounded-md border border-border bg-background px-2 py-1 text-xs font-medium hover:bg-muted"
+      >
+        Software Update
+      </button>
+    </div>
+  );
+}
+
 // --- Main Component ---
 
 export function UpdatesOverview() {
@@ -438,6 +555,10 @@ export function UpdatesOverview() {
 
   const updatableApps = apps?.filter((app) => app.hasUpdate && !app.isIgnored) ?? [];
   const updateCount = updatableApps.length;
+  // Sum of the sizes that are known; apps without one don't count toward it
+  const totalDownloadSize = updatableApps
+    .filter((app) => !app.needsLicense)
+    .reduce((sum, app) => sum + (app.downloadSize ?? 0), 0);
 
   const categorized = useMemo(() => {
     const groups: Record<UpdateCategory, AppSummary[]> = {
@@ -451,9 +572,9 @@ export function UpdatesOverview() {
       groups[cat].push(app);
     }
 
-    // Sort each category A-Z
+    // Sort each category, security updates first
     for (const cat of Object.keys(groups) as UpdateCategory[]) {
-      groups[cat].sort((a, b) => a.displayName.localeCompare(b.displayName));
+      groups[cat].sort(compareUpdates);
     }
 
     return groups;
@@ -471,10 +592,14 @@ export function UpdatesOverview() {
     });
   };
 
-  const handleUpdateAll = () => {
-    const ids = updatableApps.map((app) => app.bundleId);
-    if (ids.length > 0) {
-      executeBulk.mutate(ids);
+  const handleUpdateAll = async () => {
+    try {
+      const ids = await getBulkUpdateIds();
+      if (ids.length > 0) {
+        executeBulk.mutate(ids);
+      }
+    } catch (error) {
+      toast.error("Update All failed", { description: String(error) });
     }
   };
 
@@ -482,6 +607,23 @@ export function UpdatesOverview() {
     executeUpdate.mutate(bundleId);
   };
 
+  const handleExportScript = async () => {
+    const path = await save({
+      defaultPath: "macplus-updates.sh",
+      filters: [{ name: "Shell script", extensions: ["sh"] }],
+    });
+    if (!path) return;
+    try {
+      const { scripted, manual } = await exportUpdateScript(path);
+      toast.success(`Exported ${scripted} update${scripted === 1 ? "" : "s"} as commands`, {
+        description:
+          manual > 0 ? `${manual} more can't be scripted and are listed as comments` : undefined,
+      });
+    } catch (error) {
+      toast.error("Export failed", { description: String(error) });
+    }
+  };
+
   if (isLoading) {
     return (
       <div className="flex items-center justify-center py-16">
@@ -532,6 +674,20 @@ export function UpdatesOverview() {
             />
             Check Now
           </button>
+          {updateCount > 0 && (
+            <button
+              type="button"
+              onClick={handleExportScript}
+              title="Export pending updates as a shell script"
+              className={cn(
+                "flex items-center rounded-lg",
+                "border border-border bg-background px-2 py-1.5",
+                "text-foreground transition-colors hover:bg-muted",
+              )}
+            >
+              <Terminal className="h-3.5 w-3.5" />
+            </button>
+          )}
           {updateCount > 0 && (
             <button
               type="button"
@@ -547,11 +703,16 @@ export function UpdatesOverview() {
             >
               <Download className="h-3.5 w-3.5" />
               Update All
+              {totalDownloadSize > 0 && (
+                <span className="font-normal opacity-80">({formatBytes(totalDownloadSize)})</span>
+              )}
             </button>
           )}
         </div>
       </div>
 
+      <OsSecurityBanner />
+
       {/* Update list or empty state */}
       {updateCount === 0 ? (
         <div className="flex flex-col items-center gap-3 py-16">
@@ -580,16 +741,14 @@ export function UpdatesOverview() {
         </div>
       ) : (
         <div className="flex flex-col gap-2">
-          {[...updatableApps]
-            .sort((a, b) => a.displayName.localeCompare(b.displayName))
-            .map((app) => (
-              <UpdateCard key={app.bundleId} app={app} onUpdate={handleUpdateSingle} />
-            ))}
+          {[...updatableApps].sort(compareUpdates).map((app) => (
+            <UpdateCard key={app.bundleId} app={app} onUpdate={handleUpdateSingle} />
+          ))}
         </div>
       )}
 
       {/* Recently Updated */}
-      <RecentlyUpdated />
+      <RecentActivity />
     </div>
   );
 }
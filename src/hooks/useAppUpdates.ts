@@ -1,5 +1,5 @@
-import { useMutation, useQueryClient } from "@tanstack/react-query";
-import { checkAllUpdates, checkSingleUpdate } from "@/lib/tauri-commands";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { checkAllUpdates, checkSingleUpdate, getOsSecurityStatus } from "@/lib/tauri-commands";
 
 export function useCheckAllUpdates() {
   const queryClient = useQueryClient();
@@ -20,3 +20,12 @@ export function useCheckSingleUpdate() {
     },
   });
 }
+
+/** Missing macOS security releases; the SOFA feed changes a few times a month. */
+export function useOsSecurityStatus() {
+  return useQuery({
+    queryKey: ["os-security-status"],
+    queryFn: getOsSecurityStatus,
+    staleTime: 60 * 60 * 1000,
+  });
+}
@@ -1,12 +1,31 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { toast } from "sonner";
+import { formatBytes } from "@/lib/format-bytes";
 import {
+  clearCaskToken,
+  confirmUpgradeLicense,
   getAllApps,
   getAppDetail,
+  getDetectionConflicts,
+  getUnusedApps,
+  moveAppToApplications,
+  removeOrphanFormulae,
+  repairApp,
+  reportBadMatch,
+  rescanApp,
+  scanOrphanFormulae,
   setAppIgnored,
+  setAppsIgnored,
+  setAppRelaunchPolicy,
+  setCaskToken,
+  setPreferredDetector,
+  trashLeftoverFiles,
   triggerFullScan,
+  undoUninstall,
   uninstallApp,
+  verifyAppIntegrity,
 } from "@/lib/tauri-commands";
+import type { RelaunchPolicy } from "@/types/app";
 
 export function useApps() {
   return useQuery({
@@ -26,16 +45,78 @@ export function useAppDetail(bundleId: string | null) {
   });
 }
 
+export function useUnusedApps(days: number) {
+  return useQuery({
+    queryKey: ["unused-apps", days],
+    queryFn: () => getUnusedApps(days),
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useOrphanFormulae() {
+  return useQuery({
+    queryKey: ["orphan-formulae"],
+    queryFn: scanOrphanFormulae,
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useRemoveOrphanFormulae() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (names: string[]) => removeOrphanFormulae(names),
+    onSuccess: (removed) => {
+      toast.success(`Removed ${removed.length} formula${removed.length === 1 ? "" : "e"}`);
+      queryClient.invalidateQueries({ queryKey: ["orphan-formulae"] });
+    },
+    onError: (error) => toast.error("Couldn't remove formulae", { description: String(error) }),
+  });
+}
+
+export function useDetectionConflicts() {
+  return useQuery({
+    queryKey: ["detection-conflicts"],
+    queryFn: getDetectionConflicts,
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useSetPreferredDetector() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ bundleId, detector }: { bundleId: string; detector: string | null }) =>
+      setPreferredDetector(bundleId, detector),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["detection-conflicts"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
 export function useFullScan() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: triggerFullScan,
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["detection-conflicts"] });
     },
   });
 }
 
+export function useRescanApp() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => rescanApp(bundleId),
+    onSuccess: (detail) => {
+      queryClient.setQueryData(["app-detail", detail.bundleId], detail);
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      toast.success(`${detail.displayName} re-detected`);
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
 export function useToggleIgnored() {
   const queryClient = useQueryClient();
   return useMutation({
@@ -47,26 +128,197 @@ export function useToggleIgnored() {
   });
 }
 
+export function useConfirmUpgradeLicense() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => confirmUpgradeLicense(bundleId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
+export function useReportBadMatch() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => reportBadMatch(bundleId),
+    onSuccess: () => {
+      toast.success("This app won't be matched to Homebrew again");
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
+export function useSetCaskToken() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ bundleId, token }: { bundleId: string; token: string }) =>
+      setCaskToken(bundleId, token),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
+export function useClearCaskToken() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => clearCaskToken(bundleId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
+export function useSetAppsIgnored() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ bundleIds, ignored }: { bundleIds: string[]; ignored: boolean }) =>
+      setAppsIgnored(bundleIds, ignored),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
+export function useMoveToApplications() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => moveAppToApplications(bundleId),
+    onSuccess: (path, bundleId) => {
+      toast.success("Moved to Applications", { description: path });
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail", bundleId] });
+    },
+    onError: (error) => toast.error("Couldn't move app", { description: String(error) }),
+  });
+}
+
+export function useVerifyIntegrity() {
+  return useMutation({
+    mutationFn: (bundleId: string) => verifyAppIntegrity(bundleId),
+    onError: (error) => toast.error("Couldn't verify app", { description: String(error) }),
+  });
+}
+
+export function useRepairApp() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (bundleId: string) => repairApp(bundleId),
+    onSuccess: (result) => {
+      if (result.success) {
+        toast.success("App reinstalled");
+      } else {
+        toast.error("Reinstall failed", { description: result.message ?? undefined });
+      }
+      queryClient.invalidateQueries({ queryKey: ["apps"] });
+      queryClient.invalidateQueries({ queryKey: ["app-detail", result.bundleId] });
+    },
+    onError: (error) => toast.error("Reinstall failed", { description: String(error) }),
+  });
+}
+
+export function useSetRelaunchPolicy() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ bundleId, policy }: { bundleId: string; policy: RelaunchPolicy }) =>
+      setAppRelaunchPolicy(bundleId, policy),
+    onSuccess: (_, { bundleId }) => {
+      queryClient.invalidateQueries({ queryKey: ["app-detail", bundleId] });
+    },
+    onError: (err) => toast.error(String(err)),
+  });
+}
+
 export function useUninstallApp() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: ({
       bundleId,
-      cleanupAssociated,
+      paths,
+      archive,
+      archiveSupportFiles,
     }: {
       bundleId: string;
-      cleanupAssociated: boolean;
-    }) => uninstallApp(bundleId, cleanupAssociated),
+      paths: string[];
+      archive?: boolean;
+      archiveSupportFiles?: boolean;
+    }) => uninstallApp(bundleId, paths, archive, archiveSupportFiles),
     onSuccess: (result) => {
       if (result.success) {
-        toast.success(`Moved to Trash`, {
+        toast.success(result.removal === "homebrew" ? "Uninstalled with Homebrew" : "Moved to Trash", {
           description:
             result.cleanedPaths.length > 0
               ? `Also removed ${result.cleanedPaths.length} associated file${result.cleanedPaths.length === 1 ? "" : "s"}`
               : undefined,
+          action: {
+            label: "Undo",
+            onClick: () => {
+              undoUninstall(result.bundleId)
+                .then(() => {
+                  toast.success("Uninstall undone");
+                  queryClient.invalidateQueries({ queryKey: ["apps"] });
+                })
+                .catch((error) => toast.error("Undo failed", { description: String(error) }));
+            },
+          },
         });
+        if (result.leftoverFiles.length > 0) {
+          const count = result.leftoverFiles.length;
+          toast.info(`${count} leftover item${count === 1 ? "" : "s"} remain`, {
+            description: "The uninstaller left some data in ~/Library",
+            action: {
+              label: "Move to Trash",
+              onClick: () => {
+                trashLeftoverFiles(result.leftoverFiles.map((f) => f.path))
+                  .then((trashed) =>
+                    toast.success(`Moved ${trashed.length} item${trashed.length === 1 ? "" : "s"} to Trash`),
+                  )
+                  .catch((error) => toast.error("Cleanup failed", { description: String(error) }));
+              },
+            },
+          });
+        }
+        if (result.removal === "homebrew") {
+          // Dependencies only the removed package needed are now orphaned
+          scanOrphanFormulae()
+            .then(({ formulae, totalSizeBytes }) => {
+              queryClient.setQueryData(["orphan-formulae"], { formulae, totalSizeBytes });
+              if (formulae.length === 0) return;
+              const count = formulae.length;
+              toast.info(`${count} unused Homebrew dependenc${count === 1 ? "y" : "ies"}`, {
+                description: `Nothing installed needs them anymore (${formatBytes(totalSizeBytes)})`,
+                action: {
+                  label: "Remove",
+                  onClick: () => {
+                    removeOrphanFormulae(formulae.map((f) => f.name))
+                      .then((removed) => {
+                        toast.success(
+                          `Removed ${removed.length} formula${removed.length === 1 ? "" : "e"}`,
+                        );
+                        queryClient.invalidateQueries({ queryKey: ["orphan-formulae"] });
+                      })
+                      .catch((error) =>
+                        toast.error("Couldn't remove formulae", { description: String(error) }),
+                      );
+                  },
+                },
+              });
+            })
+            .catch(() => {});
+        }
         queryClient.invalidateQueries({ queryKey: ["apps"] });
         queryClient.invalidateQueries({ queryKey: ["app-detail"] });
+        queryClient.invalidateQueries({ queryKey: ["unused-apps"] });
       } else {
         toast.error("Uninstall failed", { description: result.message ?? undefined });
       }
@@ -1,9 +1,44 @@
 import { AnimatePresence, motion } from "motion/react";
 import { useState } from "react";
 import { toast } from "sonner";
-import type { ScanComplete, UpdateCheckComplete, UpdateExecuteComplete } from "@/types/update";
+import {
+  acceptLicenseAndUpdate,
+  confirmUpdateDespiteWarning,
+  openSystemPreferences,
+  openTerminalWithCommand,
+} from "@/lib/tauri-commands";
+import type {
+  BulkUpdateSummary,
+  ScanComplete,
+  UpdateCheckComplete,
+  UpdateElevationPreflight,
+  UpdateExecuteComplete,
+  UpdateErrorCode,
+  UpdateLicensePrompt,
+  UpdateTrustWarning,
+} from "@/types/update";
 import { useTauriEvent } from "./useTauriEvent";
 
+const FAILURE_LABELS: Partial<Record<UpdateErrorCode, string>> = {
+  needs_elevation: "need administrator rights",
+  app_management_denied: "need App Management",
+  user_cancelled: "cancelled",
+  network_error: "network error",
+  app_running: "app still open",
+  checksum_mismatch: "checksum mismatch",
+  team_id_mismatch: "different developer",
+  license_agreement: "license agreement",
+  version_unchanged: "version unchanged",
+  paid_upgrade: "paid upgrade",
+  source_mismatch: "not from the App Store",
+};
+
+function describeFailures(summary: BulkUpdateSummary): string {
+  return summary.failures
+    .map((g) => `${g.apps.length} ${FAILURE_LABELS[g.errorCode] ?? "failed"}`)
+    .join(" · ");
+}
+
 function ExpandableErrorDescription({ message }: { message: string }) {
   const [expanded, setExpanded] = useState(false);
   const isLong = message.length > 80 || message.includes("\n");
@@ -92,23 +127,115 @@ export function useToastNotifications() {
     }
   });
 
+  useTauriEvent<UpdateElevationPreflight>("update-elevation-preflight", (payload) => {
+    if (!payload.needsPassword) return;
+    toast.info(`${payload.displayName} will ask for your administrator password`, {
+      id: `elevation-${payload.bundleId}`,
+      description: payload.reason ?? undefined,
+    });
+  });
+
   useTauriEvent<UpdateExecuteComplete>("update-execute-complete", (payload) => {
     if (payload.success && payload.delegated) {
       toast.success(`Opened ${payload.displayName} — update within the app`, {
         id: `update-${payload.displayName}`,
       });
+    } else if (payload.errorCode === "gatekeeper_blocked") {
+      toast.warning(`Updated ${payload.displayName}, but macOS may block it`, {
+        id: `update-${payload.displayName}`,
+        description: `Gatekeeper rejected the new version${payload.gatekeeper?.source ? ` (${payload.gatekeeper.source})` : ""}. You may need to allow it in System Settings › Privacy & Security.`,
+        duration: 10000,
+      });
     } else if (payload.success) {
       toast.success(`Updated ${payload.displayName}`, {
         id: `update-${payload.displayName}`,
       });
+    } else if (payload.errorCode === "user_cancelled") {
+      toast.info(`Update of ${payload.displayName} cancelled`, {
+        id: `update-error-${payload.displayName}`,
+        description: payload.message,
+      });
+    } else if (payload.errorCode === "app_management_denied") {
+      toast.warning(`Allow macPlus to update ${payload.displayName}`, {
+        id: `update-error-${payload.displayName}`,
+        description: payload.message,
+        duration: 10000,
+        action: {
+          label: "Open Settings",
+          onClick: () => {
+            openSystemPreferences("app_management").catch(console.error);
+          },
+        },
+      });
+    } else if (payload.errorCode === "app_running") {
+      toast.warning(`Quit ${payload.displayName} to update it`, {
+        id: `update-error-${payload.displayName}`,
+        description: payload.message,
+      });
     } else {
+      const fixCommand = payload.fixCommand;
       toast.error(`Failed to update ${payload.displayName}`, {
         id: `update-error-${payload.displayName}`,
         description: payload.message ? (
           <ExpandableErrorDescription message={payload.message} />
         ) : undefined,
         duration: 10000,
+        action: fixCommand
+          ? {
+              label: "Fix in Terminal",
+              onClick: () => {
+                openTerminalWithCommand(fixCommand).catch((error) =>
+                  toast.error("Failed to open Terminal", { description: String(error) }),
+                );
+              },
+            }
+          : undefined,
       });
     }
   });
+
+  useTauriEvent<BulkUpdateSummary>("bulk-update-summary", (summary) => {
+    if (summary.failed === 0 || summary.total < 2) return;
+    toast.warning(`${summary.failed} of ${summary.total} updates failed`, {
+      id: "bulk-update-summary",
+      description: describeFailures(summary),
+      duration: 10000,
+    });
+  });
+
+  // Replaces the generic failure toast for the same update
+  useTauriEvent<UpdateTrustWarning>("update-trust-warning", (payload) => {
+    toast.warning(`${payload.displayName} update is signed by a different developer`, {
+      id: `update-error-${payload.displayName}`,
+      description: `Installed: ${payload.installedTeamId} · Update: ${payload.downloadedTeamId ?? "unsigned"}. The publisher may have changed, or the download may have been tampered with.`,
+      duration: Infinity,
+      action: {
+        label: "Install Anyway",
+        onClick: () => {
+          confirmUpdateDespiteWarning(
+            payload.bundleId,
+            payload.installedTeamId,
+            payload.downloadedTeamId,
+          ).catch((error) => toast.error("Update failed", { description: String(error) }));
+        },
+      },
+    });
+  });
+
+  // Replaces the generic failure toast for the same update
+  useTauriEvent<UpdateLicensePrompt>("update-license-prompt", (payload) => {
+    toast.info(`Accept the license agreement to update ${payload.displayName}`, {
+      id: `update-error-${payload.displayName}`,
+      description: payload.text ? <ExpandableErrorDescription message={payload.text} /> : undefined,
+      duration: Infinity,
+      action: {
+        label: "Accept & Update",
+        onClick: () => {
+          acceptLicenseAndUpdate(payload.bundleId).catch((error) =>
+            toast.error("Update failed", { description: String(error) }),
+          );
+        },
+      },
+    });
+  });
 }
@@ -1,11 +1,24 @@
 import { useMutation, useQueryClient } from "@tanstack/react-query";
-import { executeBulkUpdate, executeUpdate } from "@/lib/tauri-commands";
+import { toast } from "sonner";
+import { executeBulkUpdate, executeUpdate, scheduleUpdates } from "@/lib/tauri-commands";
+import type { UpdateResult } from "@/types/update";
+
+/** Tell the user when an old app version was deleted instead of trashed. */
+function reportPermanentDeletes(results: UpdateResult[]) {
+  const deleted = results.filter((r) => r.removal === "deleted").length;
+  if (deleted > 0) {
+    toast.info(`${deleted === 1 ? "Old version" : `${deleted} old versions`} deleted permanently`, {
+      description: "Moving to the Trash failed",
+    });
+  }
+}
 
 export function useExecuteUpdate() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: (bundleId: string) => executeUpdate(bundleId),
-    onSuccess: () => {
+    onSuccess: (result) => {
+      reportPermanentDeletes([result]);
       queryClient.invalidateQueries({ queryKey: ["apps"] });
     },
   });
@@ -15,8 +28,34 @@ export function useExecuteBulkUpdate() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: (bundleIds: string[]) => executeBulkUpdate(bundleIds),
-    onSuccess: () => {
+    onSuccess: (results) => {
+      reportPermanentDeletes(results);
       queryClient.invalidateQueries({ queryKey: ["apps"] });
     },
   });
 }
+
+/** Time "Update Tonight" schedules updates for. */
+export const TONIGHT = "02:00";
+
+export function useScheduleUpdates() {
+  return useMutation({
+    mutationFn: ({ bundleIds, time }: { bundleIds: string[]; time: string }) =>
+      scheduleUpdates(bundleIds, time),
+    onSuccess: (schedule) => {
+      const at = new Date(schedule.runAt).toLocaleTimeString([], {
+        hour: "2-digit",
+        minute: "2-digit",
+      });
+      const count = schedule.scheduled;
+      toast.success(`${count} update${count === 1 ? "" : "s"} scheduled for ${at}`, {
+        description: schedule.launchAtLogin
+          ? "You'll get a notification when they're done"
+          : "Keep macPlus running in the menu bar so they can run",
+      });
+    },
+    onError: (error) => {
+      toast.error("Couldn't schedule updates", { description: String(error) });
+    },
+  });
+}
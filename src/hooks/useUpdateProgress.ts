@@ -16,6 +16,7 @@ export function useUpdateProgressListener() {
         payload.percent,
         payload.downloadedBytes ?? undefined,
         payload.totalBytes ?? undefined,
+        payload.waiting,
       );
     },
     [setProgress],
@@ -1,7 +1,26 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { AppDetail, AppSummary } from "@/types/app";
+import type {
+  AppDetail,
+  AppSummary,
+  IntegrityReport,
+  RelaunchPolicy,
+  StartupIssue,
+} from "@/types/app";
 import type { AppSettings } from "@/types/settings";
-import type { UpdateHistoryEntry, UpdateInfo, UpdateResult } from "@/types/update";
+import type {
+  ActivityEntry,
+  AppTimelineEvent,
+  MetricsSummary,
+  OsSecurityStatus,
+  RemovalMethod,
+  ScheduledUpdate,
+  UpdateHistoryEntry,
+  UpdateInfo,
+  UpdatePreview,
+  UpdateResult,
+  UpdateSchedule,
+  UpdateScriptExport,
+} from "@/types/update";
 
 export async function getAllApps(): Promise<AppSummary[]> {
   return invoke<AppSummary[]>("get_all_apps");
@@ -15,10 +34,88 @@ export async function triggerFullScan(): Promise<number> {
   return invoke<number>("trigger_full_scan");
 }
 
+export async function rescanApp(bundleId: string): Promise<AppDetail> {
+  return invoke<AppDetail>("rescan_app", { bundleId });
+}
+
 export async function setAppIgnored(bundleId: string, ignored: boolean): Promise<void> {
   return invoke("set_app_ignored", { bundleId, ignored });
 }
 
+export async function reportBadMatch(bundleId: string): Promise<void> {
+  return invoke("report_bad_match", { bundleId });
+}
+
+export async function setCaskToken(bundleId: string, token: string): Promise<void> {
+  return invoke("set_cask_token", { bundleId, token });
+}
+
+export async function clearCaskToken(bundleId: string): Promise<void> {
+  return invoke("clear_cask_token", { bundleId });
+}
+
+export async function confirmUpgradeLicense(bundleId: string): Promise<void> {
+  return invoke("confirm_upgrade_license", { bundleId });
+}
+
+export interface AppMapping {
+  bundleId: string;
+  githubRepo: string | null;
+  customFeedUrl: string | null;
+  homebrewCaskToken: string | null;
+  masId: number | null;
+}
+
+export interface MappingImportReport {
+  added: number;
+  /** Existing mappings replaced because the import was told to overwrite */
+  replaced: number;
+  unchanged: number;
+  /** Existing mappings kept because the import disagreed with them */
+  conflicts: { current: AppMapping; incoming: AppMapping }[];
+  /** Bundle IDs whose entries were malformed and skipped */
+  invalid: string[];
+}
+
+export async function exportMappings(path: string): Promise<number> {
+  return invoke<number>("export_mappings", { path });
+}
+
+export async function importMappings(
+  path: string,
+  overwrite: boolean,
+): Promise<MappingImportReport> {
+  return invoke<MappingImportReport>("import_mappings", { path, overwrite });
+}
+
+export async function setAppsIgnored(bundleIds: string[], ignored: boolean): Promise<number> {
+  return invoke("set_apps_ignored", { bundleIds, ignored });
+}
+
+export async function moveAppToApplications(bundleId: string): Promise<string> {
+  return invoke<string>("move_app_to_applications", { bundleId });
+}
+
+export async function verifyAppIntegrity(bundleId: string): Promise<IntegrityReport> {
+  return invoke<IntegrityReport>("verify_app_integrity", { bundleId });
+}
+
+export async function repairApp(bundleId: string): Promise<UpdateResult> {
+  return invoke<UpdateResult>("repair_app", { bundleId });
+}
+
+export async function getStartupIssues(): Promise<StartupIssue[]> {
+  return invoke<StartupIssue[]>("get_startup_issues");
+}
+
+export async function repairStartupIssue(path: string): Promise<void> {
+  return invoke("repair_startup_issue", { path });
+}
+
+export async function setAppRelaunchPolicy(bundleId: string, policy: RelaunchPolicy): Promise<void> {
+  return invoke("set_app_relaunch_policy", { bundleId, policy });
+}
+
 export async function checkAllUpdates(): Promise<number> {
   return invoke<number>("check_all_updates");
 }
@@ -31,14 +128,51 @@ export async function getUpdateCount(): Promise<number> {
   return invoke<number>("get_update_count");
 }
 
+export async function previewUpdate(bundleId: string): Promise<UpdatePreview> {
+  return invoke<UpdatePreview>("preview_update", { bundleId });
+}
+
 export async function executeUpdate(bundleId: string): Promise<UpdateResult> {
   return invoke<UpdateResult>("execute_update", { bundleId });
 }
 
+export async function confirmUpdateDespiteWarning(
+  bundleId: string,
+  installedTeamId: string,
+  downloadedTeamId: string | null,
+): Promise<UpdateResult> {
+  return invoke<UpdateResult>("confirm_update_despite_warning", {
+    bundleId,
+    installedTeamId,
+    downloadedTeamId,
+  });
+}
+
+export async function acceptLicenseAndUpdate(bundleId: string): Promise<UpdateResult> {
+  return invoke<UpdateResult>("accept_license_and_update", { bundleId });
+}
+
+/** The apps "Update All" installs; paid upgrades wait for license confirmation. */
+export async function getBulkUpdateIds(): Promise<string[]> {
+  return invoke<string[]>("get_bulk_update_ids");
+}
+
 export async function executeBulkUpdate(bundleIds: string[]): Promise<UpdateResult[]> {
   return invoke<UpdateResult[]>("execute_bulk_update", { bundleIds });
 }
 
+export async function scheduleUpdates(bundleIds: string[], time: string): Promise<UpdateSchedule> {
+  return invoke<UpdateSchedule>("schedule_updates", { bundleIds, time });
+}
+
+export async function getScheduledUpdates(): Promise<ScheduledUpdate[]> {
+  return invoke<ScheduledUpdate[]>("get_scheduled_updates");
+}
+
+export async function cancelScheduledUpdates(bundleIds: string[]): Promise<number> {
+  return invoke<number>("cancel_scheduled_updates", { bundleIds });
+}
+
 export async function getSettings(): Promise<AppSettings> {
   return invoke<AppSettings>("get_settings");
 }
@@ -47,6 +181,22 @@ export async function updateSettings(settings: AppSettings): Promise<void> {
   return invoke("update_settings", { settings });
 }
 
+export async function getAppTimeline(bundleId: string): Promise<AppTimelineEvent[]> {
+  return invoke<AppTimelineEvent[]>("get_app_timeline", { bundleId });
+}
+
+export async function getRecentActivity(limit?: number): Promise<ActivityEntry[]> {
+  return invoke<ActivityEntry[]>("get_recent_activity", { limit: limit ?? 50 });
+}
+
+export async function getOsSecurityStatus(): Promise<OsSecurityStatus | null> {
+  return invoke<OsSecurityStatus | null>("get_os_security_status");
+}
+
+export async function exportUpdateScript(path: string): Promise<UpdateScriptExport> {
+  return invoke<UpdateScriptExport>("export_update_script", { path });
+}
+
 export async function getUpdateHistory(limit?: number): Promise<UpdateHistoryEntry[]> {
   return invoke<UpdateHistoryEntry[]>("get_update_history", { limit: limit ?? 50 });
 }
@@ -67,12 +217,53 @@ export async function getAppIcon(appPath: string, bundleId: string): Promise<str
   return invoke<string | null>("get_app_icon", { appPath, bundleId });
 }
 
+/** Extract an app's icon again, or generate its monogram; returns the new path. */
+export async function refreshAppIcon(bundleId: string): Promise<string> {
+  return invoke<string>("refresh_app_icon", { bundleId });
+}
+
+export type PermissionKind =
+  | "automation"
+  | "full_disk_access"
+  | "app_management"
+  | "notifications";
+
+export interface PermissionEntry {
+  kind: PermissionKind;
+  state: "granted" | "denied" | "unknown";
+  /** Updates fail without it */
+  required: boolean;
+  settingsUrl: string;
+}
+
 export interface PermissionsStatus {
   automation: boolean;
   automationState: "granted" | "denied" | "unknown";
   fullDiskAccess: boolean;
   appManagement: boolean;
   notifications: boolean;
+  matrix: PermissionEntry[];
+}
+
+/** An app with a pending update; denied apps are quit with SIGTERM instead of AppleScript. */
+export interface AutomationTarget {
+  bundleId: string;
+  displayName: string;
+  state: "granted" | "denied" | "unknown";
+}
+
+export async function getAutomationTargets(): Promise<AutomationTarget[]> {
+  return invoke<AutomationTarget[]>("get_automation_targets");
+}
+
+/** Ask for Automation of the running apps with pending updates, one prompt each. */
+export async function requestAutomationForUpdates(): Promise<AutomationTarget[]> {
+  return invoke<AutomationTarget[]>("request_automation_for_updates");
+}
+
+/** Open the System Settings pane where a permission is granted. */
+export async function openSystemPreferences(pane: PermissionKind): Promise<void> {
+  return invoke("open_system_preferences", { pane });
 }
 
 export interface SetupStatus {
@@ -90,6 +281,25 @@ export async function checkSetupStatus(): Promise<SetupStatus> {
   return invoke<SetupStatus>("check_setup_status");
 }
 
+export interface HealthFinding {
+  severity: "error" | "warning";
+  title: string;
+  detail: string | null;
+  fixCommand: string | null;
+}
+
+export interface HomebrewHealth {
+  installed: boolean;
+  version: string | null;
+  prefix: string | null;
+  config: [string, string][];
+  findings: HealthFinding[];
+}
+
+export async function checkHomebrewHealth(): Promise<HomebrewHealth> {
+  return invoke<HomebrewHealth>("check_homebrew_health");
+}
+
 export async function ensureAskpassHelper(): Promise<string | null> {
   return invoke<string | null>("ensure_askpass_helper");
 }
@@ -104,6 +314,8 @@ export interface SelfUpdateInfo {
   releaseNotesUrl: string | null;
   downloadUrl: string | null;
   canBrewUpgrade: boolean;
+  channel: "stable" | "beta";
+  isPrerelease: boolean;
 }
 
 export async function checkSelfUpdate(): Promise<SelfUpdateInfo | null> {
@@ -115,10 +327,14 @@ export interface SelfUpdateProgress {
   percent: number;
   downloadedBytes: number | null;
   totalBytes: number | null;
+  waiting: boolean;
 }
 
-export async function executeSelfUpdate(downloadUrl: string): Promise<void> {
-  return invoke("execute_self_update", { downloadUrl });
+export async function executeSelfUpdate(
+  downloadUrl: string,
+  expectedVersion?: string,
+): Promise<void> {
+  return invoke("execute_self_update", { downloadUrl, expectedVersion: expectedVersion ?? null });
 }
 
 export async function relaunchSelf(): Promise<void> {
@@ -136,10 +352,50 @@ export async function checkConnectivity(): Promise<ConnectivityStatus> {
   return invoke<ConnectivityStatus>("check_connectivity");
 }
 
+export type QueuedAction = { kind: "update_check" } | { kind: "update"; bundleId: string };
+
+export interface OfflineState {
+  offline: boolean;
+  queued: QueuedAction[];
+}
+
+export async function getOfflineState(): Promise<OfflineState> {
+  return invoke<OfflineState>("get_offline_state");
+}
+
 export interface CheckerDiagnostic {
   source: string;
   canCheck: boolean;
   result: string;
+  elapsedMs: number | null;
+  httpStatus: number | null;
+}
+
+export interface CheckerMetrics {
+  source: string;
+  runs: number;
+  found: number;
+  errors: number;
+  timeouts: number;
+  avgMs: number;
+  maxMs: number;
+  lastHttpStatus: number | null;
+}
+
+export async function getCheckerMetrics(): Promise<CheckerMetrics[]> {
+  return invoke<CheckerMetrics[]>("get_checker_metrics");
+}
+
+export interface FeedDiagnostic {
+  url: string;
+  httpStatus: number | null;
+  xmlError: string | null;
+  itemsFound: number;
+  preReleasesSkipped: number;
+  minOsSkipped: number;
+  macosVersion: string | null;
+  newestVersion: string | null;
+  noMatchReason: string | null;
 }
 
 export interface UpdateCheckDiagnostic {
@@ -149,10 +405,14 @@ export interface UpdateCheckDiagnostic {
   installSource: string;
   homebrewCaskToken: string | null;
   checkersTried: CheckerDiagnostic[];
+  sparkleFeed: FeedDiagnostic | null;
 }
 
-export async function debugUpdateCheck(bundleId: string): Promise<UpdateCheckDiagnostic> {
-  return invoke<UpdateCheckDiagnostic>("debug_update_check", { bundleId });
+export async function debugUpdateCheck(
+  bundleId: string,
+  feed = false,
+): Promise<UpdateCheckDiagnostic> {
+  return invoke<UpdateCheckDiagnostic>("debug_update_check", { bundleId, feed });
 }
 
 export async function checkPathsExist(paths: string[]): Promise<Record<string, boolean>> {
@@ -170,7 +430,9 @@ export interface UninstallResult {
   message: string | null;
   running: boolean;
   cleanedPaths: string[];
+  leftoverFiles: AssociatedFile[];
   protected: boolean;
+  removal: RemovalMethod | null;
 }
 
 export interface AssociatedFile {
@@ -186,11 +448,242 @@ export interface AssociatedFiles {
 
 export async function uninstallApp(
   bundleId: string,
-  cleanupAssociated: boolean,
+  paths: string[],
+  archive = false,
+  archiveSupportFiles = false,
 ): Promise<UninstallResult> {
-  return invoke<UninstallResult>("uninstall_app", { bundleId, cleanupAssociated });
+  return invoke<UninstallResult>("uninstall_app", {
+    bundleId,
+    paths,
+    archive,
+    archiveSupportFiles,
+  });
 }
 
 export async function scanAssociatedFiles(bundleId: string): Promise<AssociatedFiles> {
   return invoke<AssociatedFiles>("scan_associated_files", { bundleId });
 }
+
+export interface SystemExtensionInfo {
+  identifier: string;
+  name: string;
+  kind: "system_extension" | "kext";
+  category: string | null;
+  teamId: string | null;
+  version: string | null;
+  state: string | null;
+  enabled: boolean;
+  path: string | null;
+  parentBundleId: string | null;
+  parentAppPath: string | null;
+  orphaned: boolean;
+}
+
+export async function getSystemExtensions(): Promise<SystemExtensionInfo[]> {
+  return invoke<SystemExtensionInfo[]>("get_system_extensions");
+}
+
+export interface LoginItem {
+  label: string;
+  kind: "launch_agent" | "launch_daemon" | "login_item";
+  scope: "user" | "system" | "app";
+  path: string;
+  program: string | null;
+  enabled: boolean;
+  runAtLoad: boolean;
+  keepAlive: boolean;
+  parentBundleId: string | null;
+  parentAppPath: string | null;
+  orphaned: boolean;
+}
+
+export async function listLoginItems(): Promise<LoginItem[]> {
+  return invoke<LoginItem[]>("list_login_items");
+}
+
+export async function enableLoginItem(label: string): Promise<void> {
+  return invoke("enable_login_item", { label });
+}
+
+export async function disableLoginItem(label: string): Promise<void> {
+  return invoke("disable_login_item", { label });
+}
+
+export interface OrphanedReceipt {
+  packageId: string;
+  version: string | null;
+  installTime: number | null;
+  installRoot: string;
+  fileCount: number;
+  appPaths: string[];
+  reason: "app_removed" | "payload_missing";
+}
+
+export async function auditPkgReceipts(): Promise<OrphanedReceipt[]> {
+  return invoke<OrphanedReceipt[]>("audit_pkg_receipts");
+}
+
+export async function forgetPkgReceipts(packageIds: string[]): Promise<string[]> {
+  return invoke<string[]>("forget_pkg_receipts", { packageIds });
+}
+
+export interface LeftoverFile {
+  path: string;
+  bundleId: string;
+  sizeBytes: number;
+  kind: string;
+}
+
+export interface LeftoverFiles {
+  files: LeftoverFile[];
+  totalSizeBytes: number;
+}
+
+export async function scanLeftoverFiles(): Promise<LeftoverFiles> {
+  return invoke<LeftoverFiles>("scan_leftover_files");
+}
+
+export async function trashLeftoverFiles(paths: string[]): Promise<string[]> {
+  return invoke<string[]>("trash_leftover_files", { paths });
+}
+
+/** A Homebrew formula installed only as a dependency of formulae that are gone. */
+export interface OrphanFormula {
+  name: string;
+  sizeBytes: number;
+}
+
+export interface OrphanFormulae {
+  formulae: OrphanFormula[];
+  totalSizeBytes: number;
+}
+
+export async function scanOrphanFormulae(): Promise<OrphanFormulae> {
+  return invoke<OrphanFormulae>("scan_orphan_formulae");
+}
+
+export async function removeOrphanFormulae(names: string[]): Promise<string[]> {
+  return invoke<string[]>("remove_orphan_formulae", { names });
+}
+
+export interface DiskUsageEntry {
+  bundleId: string;
+  displayName: string;
+  appPath: string;
+  iconCachePath: string | null;
+  bundleSizeBytes: number;
+  dataSizeBytes: number;
+  totalSizeBytes: number;
+  sizeComputedAt: string | null;
+}
+
+export async function getDiskUsage(): Promise<DiskUsageEntry[]> {
+  return invoke<DiskUsageEntry[]>("get_disk_usage");
+}
+
+export interface UnusedApp {
+  bundleId: string;
+  displayName: string;
+  appPath: string;
+  iconCachePath: string | null;
+  /** null when Spotlight has no record of the app ever being opened */
+  lastUsedAt: string | null;
+  totalSizeBytes: number | null;
+}
+
+export interface DetectionCandidate {
+  detector: string;
+  appPath: string;
+  installedVersion: string | null;
+  installSource: string;
+}
+
+export interface DetectionConflict {
+  bundleId: string;
+  displayName: string;
+  fields: ("path" | "version" | "source")[];
+  candidates: DetectionCandidate[];
+  appPath: string;
+  installedVersion: string | null;
+  installSource: string;
+  preferredDetector: string | null;
+}
+
+export async function getDetectionConflicts(): Promise<DetectionConflict[]> {
+  return invoke<DetectionConflict[]>("get_detection_conflicts");
+}
+
+export async function setPreferredDetector(
+  bundleId: string,
+  detector: string | null,
+): Promise<void> {
+  return invoke("set_preferred_detector", { bundleId, detector });
+}
+
+export async function getUnusedApps(days: number): Promise<UnusedApp[]> {
+  return invoke<UnusedApp[]>("get_unused_apps", { days });
+}
+
+export interface ArchivedFile {
+  originalPath: string;
+  entry: string;
+}
+
+export interface AppArchive {
+  id: number;
+  bundleId: string;
+  displayName: string;
+  appPath: string;
+  archivePath: string;
+  sizeBytes: number;
+  supportFiles: ArchivedFile[];
+  createdAt: string;
+  restoredAt: string | null;
+}
+
+export async function listAppArchives(): Promise<AppArchive[]> {
+  return invoke<AppArchive[]>("list_app_archives");
+}
+
+export async function restoreArchivedApp(archiveId: number): Promise<string[]> {
+  return invoke<string[]>("restore_archived_app", { archiveId });
+}
+
+export async function undoUninstall(bundleId: string): Promise<string[]> {
+  return invoke<string[]>("undo_uninstall", { bundleId });
+}
+
+/** Keychain account names for secrets stored by macPlus. */
+export const SECRET_KEYS = {
+  githubToken: "github_token",
+  proxyPassword: "proxy_password",
+} as const;
+
+export async function setSecret(key: string, value: string): Promise<void> {
+  return invoke("set_secret", { key, value });
+}
+
+export async function getSecret(key: string): Promise<string | null> {
+  return invoke<string | null>("get_secret", { key });
+}
+
+export async function deleteSecret(key: string): Promise<void> {
+  return invoke("delete_secret", { key });
+}
+
+/** Wipe the database, caches and Keychain secrets. Relaunch afterwards. */
+export async function deleteAllData(): Promise<void> {
+  return invoke("delete_all_data");
+}
+
+export async function getMetrics(): Promise<MetricsSummary> {
+  return invoke<MetricsSummary>("get_metrics");
+}
+
+export async function exportMetrics(path: string): Promise<void> {
+  return invoke("export_metrics", { path });
+}
+
+export async function clearMetrics(): Promise<void> {
+  return invoke("clear_metrics");
+}
@@ -2,7 +2,7 @@ import { create } from "zustand";
 
 export type SortField = "name" | "source" | "status";
 export type SortDirection = "asc" | "desc";
-export type FilterView = "all" | "updates" | "ignored" | "history" | "settings";
+export type FilterView = "all" | "updates" | "ignored" | "unused" | "history" | "settings";
 
 interface AppFilterState {
   search: string;
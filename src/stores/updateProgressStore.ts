@@ -6,6 +6,7 @@ interface ProgressEntry {
   percent: number;
   downloadedBytes?: number;
   totalBytes?: number | null;
+  waiting?: boolean;
 }
 
 interface RelaunchEntry {
@@ -22,6 +23,7 @@ interface UpdateProgressState {
     percent: number,
     downloadedBytes?: number,
     totalBytes?: number | null,
+    waiting?: boolean,
   ) => void;
   clearProgress: (bundleId: string) => void;
   setRelaunchNeeded: (bundleId: string, appPath: string) => void;
@@ -31,11 +33,11 @@ interface UpdateProgressState {
 export const useUpdateProgressStore = create<UpdateProgressState>((set) => ({
   progress: {},
   relaunchNeeded: {},
-  setProgress: (bundleId, phase, percent, downloadedBytes, totalBytes) =>
+  setProgress: (bundleId, phase, percent, downloadedBytes, totalBytes, waiting) =>
     set((s) => ({
       progress: {
         ...s.progress,
-        [bundleId]: { bundleId, phase, percent, downloadedBytes, totalBytes },
+        [bundleId]: { bundleId, phase, percent, downloadedBytes, totalBytes, waiting },
       },
     })),
   clearProgress: (bundleId) =>
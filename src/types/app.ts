@@ -16,8 +16,42 @@ export interface AppSummary {
   releaseNotesUrl: string | null;
   updateNotes: string | null;
   description: string | null;
+  /** Expected download size of the pending update in bytes */
+  downloadSize: number | null;
+  /** When the app was last opened (UTC, per Spotlight) */
+  lastUsedAt: string | null;
+  /** The pending update is a paid upgrade the user hasn't confirmed a license for */
+  needsLicense: boolean;
+  /** The pending update fixes security issues */
+  isSecurityUpdate: boolean;
 }
 
+/** What updaters may do with the app while it's running. */
+export type RelaunchPolicy = "automatic" | "keep_closed" | "never_quit";
+
+export type RepairMethod = "homebrew" | "download";
+
+export interface IntegrityReport {
+  bundleId: string;
+  valid: boolean;
+  problems: string[];
+  repair: RepairMethod | null;
+}
+
+/** A sign, found at launch, that an update in an earlier run was cut short. */
+export type StartupIssueKind = "missing_app" | "leftover_backup" | "stale_mount";
+
+export interface StartupIssue {
+  kind: StartupIssueKind;
+  path: string;
+  bundleId: string | null;
+  displayName: string | null;
+  repairable: boolean;
+}
+
+/** Why an app's location gets in the way of updating it. */
+export type LocationIssue = "translocated" | "disk_image" | "downloads";
+
 export interface AppDetail {
   id: number;
   bundleId: string;
@@ -37,6 +71,15 @@ export interface AppDetail {
   description: string | null;
   updateSources: UpdateSourceInfo[];
   availableUpdate: AvailableUpdateInfo | null;
+  helperApps: HelperApp[];
+  relaunchPolicy: RelaunchPolicy;
+  locationIssue: LocationIssue | null;
+}
+
+export interface HelperApp {
+  bundleId: string;
+  displayName: string;
+  appPath: string;
 }
 
 export interface UpdateSourceInfo {
@@ -53,6 +96,9 @@ export interface AvailableUpdateInfo {
   downloadUrl: string | null;
   releaseNotes: string | null;
   isPaidUpgrade: boolean;
+  /** The user has confirmed owning a license that covers this paid upgrade */
+  licenseConfirmed: boolean;
   detectedAt: string | null;
   notes: string | null;
+  downloadSize: number | null;
 }
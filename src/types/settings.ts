@@ -1,13 +1,103 @@
+export interface ScanLocation {
+  path: string;
+  depth: number | null;
+  followSymlinks: boolean;
+}
+
+export type NotificationSound =
+  | "none"
+  | "basso"
+  | "blow"
+  | "bottle"
+  | "frog"
+  | "funk"
+  | "glass"
+  | "hero"
+  | "morse"
+  | "ping"
+  | "pop"
+  | "purr"
+  | "sosumi"
+  | "submarine"
+  | "tink";
+
+export interface BrewEnvironment {
+  noAutoUpdate: boolean;
+  caskOpts: string | null;
+  extraPath: string | null;
+}
+
+export interface NotificationPolicy {
+  sound: NotificationSound;
+  updateResults: "all" | "failures_only" | "off";
+  onUninstall: boolean;
+}
+
+export type IgnoreRule =
+  | { kind: "source"; value: string }
+  | { kind: "bundle_id_prefix"; value: string };
+
+/** Update checker sources, as named in settings. */
+export type UpdateSource =
+  | "sparkle"
+  | "homebrew_cask"
+  | "homebrew_api"
+  | "mac_app_store"
+  | "github_releases"
+  | "electron"
+  | "keystone"
+  | "microsoft_autoupdate"
+  | "jetbrains_toolbox"
+  | "adobe_cc"
+  | "mozilla";
+
 export interface AppSettings {
   checkIntervalMinutes: number;
   launchAtLogin: boolean;
+  autostartHidden: boolean;
   showMenuBarIcon: boolean;
   notificationOnUpdates: boolean;
   autoCheckOnLaunch: boolean;
   theme: "system" | "light" | "dark";
   ignoredBundleIds: string[];
-  scanLocations: string[];
+  ignoreRules: IgnoreRule[];
+  disabledSources: UpdateSource[];
+  scanLocations: ScanLocation[];
   scanDepth: number;
+  excludedPaths: string[];
+  excludedBundleIds: string[];
+  caskBlocklist: string[];
   showBadgeCount: boolean;
-  notificationSound: boolean;
+  trayIconStyle: "monochrome" | "colored";
+  trayBadgeStyle: "number" | "dot";
+  hideTrayWhenIdle: boolean;
+  compactTrayIcon: boolean;
+  notificationPolicy: NotificationPolicy;
+  archiveDir: string | null;
+  downloadDir: string | null;
+  updateChannel: "stable" | "beta";
+  autoSelfUpdate: boolean;
+  /** Which app updates install right after a check */
+  autoUpdatePolicy: "manual" | "security_only";
+  notificationDigest: "off" | "daily" | "weekly";
+  deferNotificationsDuringFocus: boolean;
+  deferAutoUpdatesDuringFocus: boolean;
+  toggleWindowShortcut: string | null;
+  checkUpdatesShortcut: string | null;
+  windowMode: "popover" | "standard";
+  proxyMode: "system" | "none" | "manual";
+  proxyUrl: string | null;
+  proxyBypass: string | null;
+  proxyUsername: string | null;
+  customCaPath: string | null;
+  requestTimeoutSecs: number;
+  checkerTimeoutSecs: number;
+  networkRetries: number;
+  historyRetentionDays: number;
+  cacheReleaseNotes: boolean;
+  collectMetrics: boolean;
+  language: "system" | "en" | "de" | "fr" | "es";
+  permanentDelete: "never" | "ask" | "allow";
+  dmgLicense: "accept" | "prompt" | "abort";
+  brewEnvironment: BrewEnvironment;
 }
@@ -8,6 +8,9 @@ export interface UpdateInfo {
   isPaidUpgrade: boolean;
 }
 
+/** How an app bundle was removed: moved to the Trash, deleted outright, or by Homebrew. */
+export type RemovalMethod = "trash" | "deleted" | "homebrew";
+
 export interface UpdateResult {
   bundleId: string;
   success: boolean;
@@ -15,6 +18,67 @@ export interface UpdateResult {
   sourceType: string;
   fromVersion: string | null;
   toVersion: string | null;
+  removal: RemovalMethod | null;
+  teamIdChange: TeamIdChange | null;
+  gatekeeper: GatekeeperVerdict | null;
+  license: LicenseAgreement | null;
+  errorCode: UpdateErrorCode | null;
+}
+
+/** A license agreement shown by a downloaded disk image before it mounts. */
+export interface LicenseAgreement {
+  text: string;
+  /** False when the update was held back for the user to read it */
+  accepted: boolean;
+}
+
+/** What went wrong with an update; `message` has the details. */
+export type UpdateErrorCode =
+  | "needs_elevation"
+  | "app_management_denied"
+  | "user_cancelled"
+  | "network_error"
+  | "gatekeeper_blocked"
+  | "app_running"
+  | "checksum_mismatch"
+  | "team_id_mismatch"
+  | "license_agreement"
+  | "version_unchanged"
+  | "paid_upgrade"
+  | "source_mismatch"
+  | "offline"
+  | "unknown";
+
+/** Result of `spctl --assess` on an installed bundle. */
+export interface GatekeeperVerdict {
+  accepted: boolean;
+  source: string | null;
+}
+
+/** Signing Team IDs of an installed app and a downloaded update that don't match. */
+export interface TeamIdChange {
+  installed: string;
+  downloaded: string | null;
+}
+
+export interface UpdateTrustWarning {
+  bundleId: string;
+  displayName: string;
+  installedTeamId: string;
+  downloadedTeamId: string | null;
+}
+
+export interface UpdateLicensePrompt {
+  bundleId: string;
+  displayName: string;
+  text: string;
+}
+
+export interface UpdateElevationPreflight {
+  bundleId: string;
+  displayName: string;
+  needsPassword: boolean;
+  reason: string | null;
 }
 
 export interface ScanProgress {
@@ -29,10 +93,15 @@ export interface ScanComplete {
   durationMs: number;
 }
 
+export type CheckPhase = "preparing" | "brew_update" | "brew_outdated" | "cask_index" | "app_checks";
+
 export interface UpdateCheckProgress {
   checked: number;
   total: number;
   currentApp: string | null;
+  phase: CheckPhase;
+  /** Estimated seconds left; null until a previous check has been timed */
+  etaSecs: number | null;
 }
 
 export interface UpdateFound {
@@ -53,6 +122,8 @@ export interface UpdateExecuteProgress {
   percent: number;
   downloadedBytes: number | null;
   totalBytes: number | null;
+  /** Waiting on the user, e.g. for an administrator password */
+  waiting: boolean;
 }
 
 export interface UpdateExecuteComplete {
@@ -63,6 +134,89 @@ export interface UpdateExecuteComplete {
   needsRelaunch: boolean;
   appPath?: string;
   delegated?: boolean;
+  gatekeeper?: GatekeeperVerdict | null;
+  errorCode?: UpdateErrorCode | null;
+  /** Command that retries the failed update in Terminal */
+  fixCommand?: string | null;
+}
+
+export interface BulkSummaryApp {
+  bundleId: string;
+  displayName: string;
+  message: string | null;
+  appPath: string | null;
+  fixCommand: string | null;
+}
+
+export interface BulkFailureGroup {
+  errorCode: UpdateErrorCode;
+  apps: BulkSummaryApp[];
+}
+
+/** Sent once a bulk update has finished, after all its completion events. */
+export interface BulkUpdateSummary {
+  total: number;
+  succeeded: number;
+  failed: number;
+  delegated: number;
+  failures: BulkFailureGroup[];
+  needsRelaunch: BulkSummaryApp[];
+  needsAction: BulkSummaryApp[];
+}
+
+export interface PackageChange {
+  name: string;
+  fromVersion: string;
+  toVersion: string;
+}
+
+/** What else a formula upgrade touches besides the formula itself. */
+export interface FormulaImpact {
+  dependencyUpgrades: PackageChange[];
+  dependentUpgrades: PackageChange[];
+  dependentsChecked: string[];
+}
+
+export interface UpdatePreview {
+  bundleId: string;
+  displayName: string;
+  fromVersion: string | null;
+  toVersion: string;
+  sourceType: string;
+  downloadSize: number | null;
+  elevationReason: string | null;
+  formulaImpact: FormulaImpact | null;
+}
+
+export type ActivityKind =
+  | "updated"
+  | "update_failed"
+  | "uninstalled"
+  | "self_updated"
+  | "app_added";
+
+/** One entry of the recent activity feed; `at` is an SQLite datetime (UTC). */
+export interface ActivityEntry {
+  kind: ActivityKind;
+  bundleId: string | null;
+  displayName: string;
+  iconCachePath: string | null;
+  version: string | null;
+  fromVersion: string | null;
+  errorMessage: string | null;
+  at: string;
+}
+
+export type TimelineEventKind = "first_seen" | "updated" | "update_failed";
+
+export interface AppTimelineEvent {
+  kind: TimelineEventKind;
+  version: string;
+  fromVersion: string | null;
+  sourceType: string | null;
+  errorMessage: string | null;
+  /** SQLite datetime (UTC) */
+  at: string;
 }
 
 export interface UpdateHistoryEntry {
@@ -75,6 +229,59 @@ export interface UpdateHistoryEntry {
   sourceType: string;
   status: string;
   errorMessage: string | null;
+  gatekeeperVerdict: string | null;
+  /** Whether macPlus accepted a disk image license agreement without asking */
+  licenseAccepted: boolean;
   startedAt: string | null;
   completedAt: string | null;
+  /** More than 1 when the update was retried after a network problem */
+  attempts: number;
+}
+
+export interface ScheduledUpdate {
+  bundleId: string;
+  displayName: string;
+  runAt: string;
+}
+
+export interface UpdateSchedule {
+  runAt: string;
+  scheduled: number;
+  /** Without launch at login, the updates only run if macPlus is open by then */
+  launchAtLogin: boolean;
+}
+
+/** Security releases of macOS this Mac hasn't installed, per the SOFA feed. */
+export interface OsSecurityStatus {
+  installedVersion: string;
+  /** Newest security release for the installed major version, if newer than it */
+  latestVersion: string | null;
+  /** Distinct CVEs fixed by the releases not yet installed */
+  missingCveCount: number;
+  /** How many of those Apple reports as actively exploited */
+  activelyExploitedCount: number;
+  securityInfoUrl: string | null;
+}
+
+/** Outcome of exporting the pending updates as a shell script. */
+export interface UpdateScriptExport {
+  /** Updates the script installs with a command */
+  scripted: number;
+  /** Updates only listed in a comment, e.g. ones installed from within the app */
+  manual: number;
+}
+
+/** Local usage metrics over the days kept. Counts only, nothing identifying. */
+export interface MetricsSummary {
+  enabled: boolean;
+  /** When the oldest metric kept was recorded */
+  since: string | null;
+  cycles: number;
+  avgCycleMs: number;
+  maxCycleMs: number;
+  checkers: { source: string; runs: number; found: number; errors: number }[];
+  downloads: number;
+  downloadBytes: number;
+  /** Failed updates by error code, most frequent first */
+  failures: { category: string; count: number }[];
 }